@@ -2,6 +2,10 @@ use crate::analyzer::resource_table::PathId;
 use crate::analyzer_error::AnalyzerError;
 use crate::assign::{AssignPath, AssignPosition, AssignPositionTree, AssignPositionType};
 use crate::attribute_table;
+use crate::comb_dependency::{find_loops, CombGraph};
+use crate::evaluator::Evaluator;
+use crate::fix::Fix;
+use crate::function_table;
 use crate::handlers::*;
 use crate::msb_table;
 use crate::namespace::Namespace;
@@ -40,19 +44,25 @@ impl<'a> VerylWalker for AnalyzerPass1<'a> {
 
 pub struct AnalyzerPass2<'a> {
     handlers: Pass2Handlers<'a>,
+    tables_only: bool,
 }
 
 impl<'a> AnalyzerPass2<'a> {
     pub fn new(text: &'a str, build_opt: &'a Build, lint_opt: &'a Lint) -> Self {
         AnalyzerPass2 {
             handlers: Pass2Handlers::new(text, build_opt, lint_opt),
+            tables_only: false,
         }
     }
 }
 
 impl<'a> VerylWalker for AnalyzerPass2<'a> {
     fn get_handlers(&mut self) -> Option<Vec<&mut dyn Handler>> {
-        Some(self.handlers.get_handlers())
+        if self.tables_only {
+            Some(self.handlers.get_table_handlers())
+        } else {
+            Some(self.handlers.get_handlers())
+        }
     }
 }
 
@@ -64,7 +74,9 @@ pub struct AnalyzerPass3<'a> {
 
 impl<'a> AnalyzerPass3<'a> {
     pub fn new(path: &'a Path, text: &'a str) -> Self {
-        let symbols = symbol_table::get_all();
+        // Sort by declaration order to make the order of errors deterministic
+        let mut symbols = symbol_table::get_all();
+        symbols.sort_by_key(|x| x.token.id);
         let path = resource_table::get_path_id(path.to_path_buf()).unwrap();
         AnalyzerPass3 {
             path,
@@ -147,12 +159,39 @@ impl<'a> AnalyzerPass3<'a> {
             }
 
             ret.append(&mut check_assign_position_tree(
-                &symbol, self.text, positions,
+                &symbol, path, self.text, positions,
             ));
         }
 
         ret
     }
+
+    pub fn check_combinational_loop(&self) -> Vec<AnalyzerError> {
+        let mut ret = Vec::new();
+
+        let mut modules: Vec<_> = self
+            .symbols
+            .iter()
+            .filter(|x| x.token.source == self.path && matches!(x.kind, SymbolKind::Module(_)))
+            .map(|x| x.id)
+            .collect();
+        modules.sort();
+
+        let mut graph = CombGraph::new(symbol_table::get_comb_dependency_list());
+        for module in modules {
+            for edges in find_loops(&graph.edges(module)) {
+                let mut path: Vec<_> = edges.iter().map(|x| x.source.path.to_string()).collect();
+                path.push(path[0].clone());
+                ret.push(AnalyzerError::combinational_loop(
+                    &path.join(" -> "),
+                    self.text,
+                    &edges[0].token.into(),
+                ));
+            }
+        }
+
+        ret
+    }
 }
 
 pub struct Analyzer {
@@ -232,6 +271,22 @@ impl Analyzer {
         ret
     }
 
+    /// Create tables of pass2 without checks.
+    ///
+    /// This is for files which are known to have no error, and pass3 can be skipped for them too.
+    pub fn analyze_pass2_tables<T: AsRef<Path>>(
+        &self,
+        project_name: &str,
+        text: &str,
+        _path: T,
+        input: &Veryl,
+    ) {
+        namespace_table::set_default(&[project_name.into()]);
+        let mut pass2 = AnalyzerPass2::new(text, &self.build_opt, &self.lint_opt);
+        pass2.tables_only = true;
+        pass2.veryl(input);
+    }
+
     pub fn analyze_pass3<T: AsRef<Path>>(
         &self,
         project_name: &str,
@@ -245,12 +300,14 @@ impl Analyzer {
         let pass3 = AnalyzerPass3::new(path.as_ref(), text);
         ret.append(&mut pass3.check_variables());
         ret.append(&mut pass3.check_assignment());
+        ret.append(&mut pass3.check_combinational_loop());
 
         ret
     }
 
     pub fn clear(&self) {
         attribute_table::clear();
+        function_table::clear();
         msb_table::clear();
         namespace_table::clear();
         symbol_table::clear();
@@ -451,8 +508,65 @@ fn check_multiple_assignment(
     ret
 }
 
+fn missing_reset_fix(symbol: &Symbol, text: &str, token: &Token) -> Option<Fix> {
+    let pos = token.pos as usize;
+    if text.get(pos..pos + token.length as usize)? != token.to_string() {
+        return None;
+    }
+
+    // insert reset statement into the block of if_reset
+    let brace = pos + text.get(pos..)?.find('{')?;
+    let mut brace_token = *token;
+    brace_token.pos = brace as u32;
+    brace_token.length = 1;
+    let fix = Fix::insert_after(
+        "Add reset statement",
+        &brace_token,
+        &format!("\n{} = 0;", symbol.token),
+    )?;
+
+    // `= 0` is not always valid for array, struct and enum
+    let r#type = match &symbol.kind {
+        SymbolKind::Variable(x) => Some(&x.r#type),
+        SymbolKind::Port(x) => x.r#type.as_ref(),
+        _ => None,
+    };
+    let packed = r#type.is_some_and(|x| {
+        x.array.is_empty()
+            && matches!(
+                x.kind,
+                TypeKind::Bit
+                    | TypeKind::Logic
+                    | TypeKind::U32
+                    | TypeKind::U64
+                    | TypeKind::I32
+                    | TypeKind::I64
+            )
+    });
+    if packed {
+        Some(fix)
+    } else {
+        Some(fix.suggestion())
+    }
+}
+
+fn assignable_width(path: &AssignPath) -> Option<usize> {
+    let symbol = symbol_table::get(*path.0.last()?)?;
+    let r#type = match symbol.kind {
+        SymbolKind::Variable(x) => x.r#type,
+        SymbolKind::Port(x) => x.r#type?,
+        _ => return None,
+    };
+    if r#type.array.is_empty() {
+        Evaluator::new().type_width(r#type)
+    } else {
+        None
+    }
+}
+
 fn check_assign_position_tree(
     symbol: &Symbol,
+    path: &AssignPath,
     text: &str,
     positions: &[(AssignPosition, bool)],
 ) -> Vec<AnalyzerError> {
@@ -472,15 +586,27 @@ fn check_assign_position_tree(
             &symbol.token.into(),
             &token.into(),
         ));
+    } else if let Some(width) = assignable_width(path) {
+        if let Some(token) = tree.check_always_comb_uncovered_bits(width) {
+            ret.push(AnalyzerError::uncovered_bit(
+                &path.to_string(),
+                text,
+                &symbol.token.into(),
+                &token.into(),
+            ));
+        }
     }
 
     if let Some(token) = tree.check_always_ff_missing_reset() {
-        ret.push(AnalyzerError::missing_reset_statement(
-            &symbol.token.to_string(),
-            text,
-            &symbol.token.into(),
-            &token.into(),
-        ));
+        ret.push(
+            AnalyzerError::missing_reset_statement(
+                &symbol.token.to_string(),
+                text,
+                &symbol.token.into(),
+                &token.into(),
+            )
+            .with_fix(missing_reset_fix(symbol, text, &token)),
+        );
     }
 
     ret
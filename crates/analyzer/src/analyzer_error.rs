@@ -1,3 +1,4 @@
+use crate::fix::Fix;
 use miette::{self, Diagnostic, NamedSource, SourceSpan};
 use thiserror::Error;
 use veryl_parser::veryl_token::TokenRange;
@@ -22,6 +23,23 @@ pub enum AnalyzerError {
         error_location: SourceSpan,
     },
 
+    #[diagnostic(
+        severity(Error),
+        code(combinational_loop),
+        help("insert a register or break the dependency"),
+        url(
+            "https://doc.veryl-lang.org/book/07_appendix/02_semantic_error.html#combinational_loop"
+        )
+    )]
+    #[error("Combinational loop is detected: {path}")]
+    CombinationalLoop {
+        path: String,
+        #[source_code]
+        input: NamedSource<String>,
+        #[label("Error location")]
+        error_location: SourceSpan,
+    },
+
     #[diagnostic(
         severity(Error),
         code(cyclice_type_dependency),
@@ -172,6 +190,7 @@ pub enum AnalyzerError {
         input: NamedSource<String>,
         #[label("Error location")]
         error_location: SourceSpan,
+        fixes: Vec<Fix>,
     },
 
     #[diagnostic(
@@ -216,6 +235,22 @@ pub enum AnalyzerError {
         error_location: SourceSpan,
     },
 
+    #[diagnostic(
+        severity(Warning),
+        code(implicit_truncation),
+        help("fit the width of expression to the target explicitly"),
+        url("https://doc.veryl-lang.org/book/07_appendix/02_semantic_error.html#implicit_truncation")
+    )]
+    #[error("{width}-bit expression is truncated to {target_width}-bit implicitly")]
+    ImplicitTruncation {
+        width: usize,
+        target_width: usize,
+        #[source_code]
+        input: NamedSource<String>,
+        #[label("Error location")]
+        error_location: SourceSpan,
+    },
+
     #[diagnostic(
         severity(Error),
         code(invalid_number_character),
@@ -393,6 +428,7 @@ pub enum AnalyzerError {
         input: NamedSource<String>,
         #[label("Error location")]
         error_location: SourceSpan,
+        fixes: Vec<Fix>,
     },
 
     #[diagnostic(
@@ -447,6 +483,23 @@ pub enum AnalyzerError {
         error_location: SourceSpan,
     },
 
+    #[diagnostic(
+        severity(Warning),
+        code(mismatch_port_width),
+        help("fit the width of connection to the port"),
+        url("https://doc.veryl-lang.org/book/07_appendix/02_semantic_error.html#mismatch_port_width")
+    )]
+    #[error("port {name} is {port_width}-bit, but {width}-bit expression is connected")]
+    MismatchPortWidth {
+        name: String,
+        port_width: usize,
+        width: usize,
+        #[source_code]
+        input: NamedSource<String>,
+        #[label("Error location")]
+        error_location: SourceSpan,
+    },
+
     #[diagnostic(
         severity(Error),
         code(mismatch_type),
@@ -482,6 +535,41 @@ pub enum AnalyzerError {
         other_location: SourceSpan,
     },
 
+    #[diagnostic(
+        severity(Warning),
+        code(reset_domain_crossing),
+        help("reset both registers by the same reset, or synchronize the signal to the destination reset"),
+        url("https://doc.veryl-lang.org/book/07_appendix/02_semantic_error.html#reset_domain_crossing")
+    )]
+    #[error("Reset domain crossing is detected")]
+    ResetDomainCrossing {
+        reset: String,
+        other_reset: String,
+        #[source_code]
+        input: NamedSource<String>,
+        #[label("reset {reset}")]
+        error_location: SourceSpan,
+        #[label("reset {other_reset}")]
+        other_location: SourceSpan,
+    },
+
+    #[diagnostic(
+        severity(Warning),
+        code(unsynchronized_reset),
+        help("drive the reset by a reset synchronizer in the same clock domain"),
+        url("https://doc.veryl-lang.org/book/07_appendix/02_semantic_error.html#unsynchronized_reset")
+    )]
+    #[error("deassertion of asynchronous reset {identifier} is not synchronized")]
+    UnsynchronizedReset {
+        identifier: String,
+        #[source_code]
+        input: NamedSource<String>,
+        #[label("Error location")]
+        error_location: SourceSpan,
+        #[label("Unsynchronized source")]
+        source_location: SourceSpan,
+    },
+
     #[diagnostic(
         severity(Error),
         code(missing_if_reset),
@@ -510,6 +598,7 @@ pub enum AnalyzerError {
         input: NamedSource<String>,
         #[label("Error location")]
         error_location: SourceSpan,
+        fixes: Vec<Fix>,
     },
 
     #[diagnostic(
@@ -555,6 +644,7 @@ pub enum AnalyzerError {
         error_location: SourceSpan,
         #[label("Not reset")]
         reset: SourceSpan,
+        fixes: Vec<Fix>,
     },
 
     #[diagnostic(
@@ -585,6 +675,24 @@ pub enum AnalyzerError {
         error_location: SourceSpan,
     },
 
+    #[diagnostic(
+        severity(Warning),
+        code(signed_zero_extension),
+        help("cast the operands to be the same signedness"),
+        url("https://doc.veryl-lang.org/book/07_appendix/02_semantic_error.html#signed_zero_extension")
+    )]
+    #[error(
+        "signed {width}-bit operand is zero-extended to {context_width}-bit in unsigned expression"
+    )]
+    SignedZeroExtension {
+        width: usize,
+        context_width: usize,
+        #[source_code]
+        input: NamedSource<String>,
+        #[label("Error location")]
+        error_location: SourceSpan,
+    },
+
     #[diagnostic(
         severity(Error),
         code(sv_keyword_usage),
@@ -598,6 +706,7 @@ pub enum AnalyzerError {
         input: NamedSource<String>,
         #[label("Error location")]
         error_location: SourceSpan,
+        fixes: Vec<Fix>,
     },
 
     #[diagnostic(
@@ -714,6 +823,7 @@ pub enum AnalyzerError {
         input: NamedSource<String>,
         #[label("Error location")]
         error_location: SourceSpan,
+        fixes: Vec<Fix>,
     },
 
     #[diagnostic(
@@ -815,6 +925,43 @@ pub enum AnalyzerError {
         error_location: SourceSpan,
     },
 
+    #[diagnostic(
+        severity(Error),
+        code(unsupported_by_target),
+        help("change build.target_language or rewrite without it"),
+        url(
+            "https://doc.veryl-lang.org/book/07_appendix/02_semantic_error.html#unsupported_by_target"
+        )
+    )]
+    #[error("{feature} is not supported by {target}")]
+    UnsupportedByTarget {
+        feature: String,
+        target: String,
+        #[source_code]
+        input: NamedSource<String>,
+        #[label("Error location")]
+        error_location: SourceSpan,
+    },
+
+    #[diagnostic(
+        severity(Warning),
+        code(case_insensitive_identifier),
+        help("rename either of them, or they are emitted as extended identifiers"),
+        url(
+            "https://doc.veryl-lang.org/book/07_appendix/02_semantic_error.html#case_insensitive_identifier"
+        )
+    )]
+    #[error("{identifier} differs only in case from {conflict}, but {target} is case-insensitive")]
+    CaseInsensitiveIdentifier {
+        identifier: String,
+        conflict: String,
+        target: String,
+        #[source_code]
+        input: NamedSource<String>,
+        #[label("Error location")]
+        error_location: SourceSpan,
+    },
+
     #[diagnostic(
         severity(Error),
         code(private_member),
@@ -889,6 +1036,7 @@ pub enum AnalyzerError {
         input: NamedSource<String>,
         #[label("Error location")]
         error_location: SourceSpan,
+        fixes: Vec<Fix>,
     },
 
     #[diagnostic(
@@ -940,6 +1088,23 @@ pub enum AnalyzerError {
         uncovered: SourceSpan,
     },
 
+    #[diagnostic(
+        severity(Warning),
+        code(uncovered_bit),
+        help("assign all bits or assign a default value at the beginning of always_comb"),
+        url("https://doc.veryl-lang.org/book/07_appendix/02_semantic_error.html#uncovered_bit")
+    )]
+    #[error("{identifier} is not assigned in all bits, it causes latch generation")]
+    UncoveredBit {
+        identifier: String,
+        #[source_code]
+        input: NamedSource<String>,
+        #[label("Error location")]
+        error_location: SourceSpan,
+        #[label("Partially assigned")]
+        uncovered: SourceSpan,
+    },
+
     #[diagnostic(
         severity(Error),
         code(reserved_identifier),
@@ -979,6 +1144,33 @@ impl AnalyzerError {
         NamedSource::new(token.beg.source.to_string(), source.to_string())
     }
 
+    pub fn fixes(&self) -> &[Fix] {
+        match self {
+            AnalyzerError::InvalidIdentifier { fixes, .. }
+            | AnalyzerError::MissingDefaultArgument { fixes, .. }
+            | AnalyzerError::MissingPort { fixes, .. }
+            | AnalyzerError::MissingResetStatement { fixes, .. }
+            | AnalyzerError::SvKeywordUsage { fixes, .. }
+            | AnalyzerError::UndefinedIdentifier { fixes, .. }
+            | AnalyzerError::UnusedVariable { fixes, .. } => fixes,
+            _ => &[],
+        }
+    }
+
+    pub fn with_fix(mut self, fix: Option<Fix>) -> Self {
+        match &mut self {
+            AnalyzerError::InvalidIdentifier { fixes, .. }
+            | AnalyzerError::MissingDefaultArgument { fixes, .. }
+            | AnalyzerError::MissingPort { fixes, .. }
+            | AnalyzerError::MissingResetStatement { fixes, .. }
+            | AnalyzerError::SvKeywordUsage { fixes, .. }
+            | AnalyzerError::UndefinedIdentifier { fixes, .. }
+            | AnalyzerError::UnusedVariable { fixes, .. } => fixes.extend(fix),
+            _ => (),
+        }
+        self
+    }
+
     pub fn call_non_function(
         identifier: &str,
         kind: &str,
@@ -993,6 +1185,14 @@ impl AnalyzerError {
         }
     }
 
+    pub fn combinational_loop(path: &str, source: &str, token: &TokenRange) -> Self {
+        AnalyzerError::CombinationalLoop {
+            path: path.to_string(),
+            input: AnalyzerError::named_source(source, token),
+            error_location: token.into(),
+        }
+    }
+
     pub fn cyclic_type_dependency(
         source: &str,
         start: &str,
@@ -1095,6 +1295,7 @@ impl AnalyzerError {
             rule: rule.to_string(),
             input: AnalyzerError::named_source(source, token),
             error_location: token.into(),
+            fixes: vec![],
         }
     }
 
@@ -1119,6 +1320,20 @@ impl AnalyzerError {
         }
     }
 
+    pub fn implicit_truncation(
+        width: usize,
+        target_width: usize,
+        source: &str,
+        token: &TokenRange,
+    ) -> Self {
+        AnalyzerError::ImplicitTruncation {
+            width,
+            target_width,
+            input: AnalyzerError::named_source(source, token),
+            error_location: token.into(),
+        }
+    }
+
     pub fn invalid_number_character(
         cause: char,
         kind: &str,
@@ -1233,6 +1448,7 @@ impl AnalyzerError {
             identifier: identifier.into(),
             input: AnalyzerError::named_source(source, token),
             error_location: token.into(),
+            fixes: vec![],
         }
     }
 
@@ -1268,6 +1484,22 @@ impl AnalyzerError {
         }
     }
 
+    pub fn mismatch_port_width(
+        name: &str,
+        port_width: usize,
+        width: usize,
+        source: &str,
+        token: &TokenRange,
+    ) -> Self {
+        AnalyzerError::MismatchPortWidth {
+            name: name.to_string(),
+            port_width,
+            width,
+            input: AnalyzerError::named_source(source, token),
+            error_location: token.into(),
+        }
+    }
+
     pub fn mismatch_type(
         name: &str,
         expected: &str,
@@ -1300,6 +1532,36 @@ impl AnalyzerError {
         }
     }
 
+    pub fn reset_domain_crossing(
+        reset: &str,
+        other_reset: &str,
+        source: &str,
+        token: &TokenRange,
+        other_token: &TokenRange,
+    ) -> Self {
+        AnalyzerError::ResetDomainCrossing {
+            reset: reset.to_string(),
+            other_reset: other_reset.to_string(),
+            input: AnalyzerError::named_source(source, token),
+            error_location: token.into(),
+            other_location: other_token.into(),
+        }
+    }
+
+    pub fn unsynchronized_reset(
+        identifier: &str,
+        source: &str,
+        token: &TokenRange,
+        source_token: &TokenRange,
+    ) -> Self {
+        AnalyzerError::UnsynchronizedReset {
+            identifier: identifier.to_string(),
+            input: AnalyzerError::named_source(source, token),
+            error_location: token.into(),
+            source_location: source_token.into(),
+        }
+    }
+
     pub fn missing_clock_signal(source: &str, token: &TokenRange) -> Self {
         AnalyzerError::MissingClockSignal {
             input: AnalyzerError::named_source(source, token),
@@ -1332,6 +1594,7 @@ impl AnalyzerError {
             input: AnalyzerError::named_source(source, token),
             error_location: token.into(),
             reset: reset.into(),
+            fixes: vec![],
         }
     }
 
@@ -1369,6 +1632,21 @@ impl AnalyzerError {
             port: port.to_string(),
             input: AnalyzerError::named_source(source, token),
             error_location: token.into(),
+            fixes: vec![],
+        }
+    }
+
+    pub fn signed_zero_extension(
+        width: usize,
+        context_width: usize,
+        source: &str,
+        token: &TokenRange,
+    ) -> Self {
+        AnalyzerError::SignedZeroExtension {
+            width,
+            context_width,
+            input: AnalyzerError::named_source(source, token),
+            error_location: token.into(),
         }
     }
 
@@ -1377,6 +1655,11 @@ impl AnalyzerError {
             identifier: identifier.to_string(),
             input: AnalyzerError::named_source(source, token),
             error_location: token.into(),
+            fixes: vec![Fix::rename(
+                "Add suffix `_`",
+                &token.beg,
+                &format!("{}_", identifier.strip_prefix("r#").unwrap_or(identifier)),
+            )],
         }
     }
 
@@ -1462,6 +1745,7 @@ impl AnalyzerError {
             identifier: identifier.to_string(),
             input: AnalyzerError::named_source(source, token),
             error_location: token.into(),
+            fixes: vec![],
         }
     }
 
@@ -1514,6 +1798,36 @@ impl AnalyzerError {
         }
     }
 
+    pub fn unsupported_by_target(
+        feature: &str,
+        target: &str,
+        source: &str,
+        token: &TokenRange,
+    ) -> Self {
+        AnalyzerError::UnsupportedByTarget {
+            feature: feature.to_string(),
+            target: target.to_string(),
+            input: AnalyzerError::named_source(source, token),
+            error_location: token.into(),
+        }
+    }
+
+    pub fn case_insensitive_identifier(
+        identifier: &str,
+        conflict: &str,
+        target: &str,
+        source: &str,
+        token: &TokenRange,
+    ) -> Self {
+        AnalyzerError::CaseInsensitiveIdentifier {
+            identifier: identifier.to_string(),
+            conflict: conflict.to_string(),
+            target: target.to_string(),
+            input: AnalyzerError::named_source(source, token),
+            error_location: token.into(),
+        }
+    }
+
     pub fn private_member(name: &str, source: &str, token: &TokenRange) -> Self {
         AnalyzerError::PrivateMember {
             name: name.to_string(),
@@ -1552,6 +1866,12 @@ impl AnalyzerError {
             identifier: identifier.to_string(),
             input: AnalyzerError::named_source(source, token),
             error_location: token.into(),
+            // raw identifier can't be prefixed as is
+            fixes: vec![Fix::rename(
+                "Add prefix `_`",
+                &token.beg,
+                &format!("_{}", identifier.strip_prefix("r#").unwrap_or(identifier)),
+            )],
         }
     }
 
@@ -1585,6 +1905,20 @@ impl AnalyzerError {
         }
     }
 
+    pub fn uncovered_bit(
+        identifier: &str,
+        source: &str,
+        token: &TokenRange,
+        uncovered: &TokenRange,
+    ) -> Self {
+        AnalyzerError::UncoveredBit {
+            identifier: identifier.to_string(),
+            input: AnalyzerError::named_source(source, token),
+            error_location: token.into(),
+            uncovered: uncovered.into(),
+        }
+    }
+
     pub fn reserved_identifier(identifier: &str, source: &str, token: &TokenRange) -> Self {
         AnalyzerError::ReservedIdentifier {
             identifier: identifier.to_string(),
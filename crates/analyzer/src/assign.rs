@@ -114,6 +114,8 @@ pub enum AssignPositionType {
     Statement {
         token: Token,
         resettable: bool,
+        /// Bit range (msb, lsb) if only a part of the variable is assigned
+        range: Option<(usize, usize)>,
     },
     Connect {
         token: Token,
@@ -157,6 +159,7 @@ pub enum AssignStatementBranchType {
     If,
     IfReset,
     Case,
+    Switch,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -165,6 +168,7 @@ pub enum AssignStatementBranchItemType {
     IfReset,
     Else,
     Case,
+    Switch,
 }
 
 #[derive(Clone, Default, Debug)]
@@ -257,6 +261,85 @@ impl AssignPositionTree {
         }
     }
 
+    pub fn check_always_comb_uncovered_bits(&self, width: usize) -> Option<Token> {
+        if let Some(AssignPositionType::Declaration { ref r#type, .. }) = self.r#type {
+            if *r#type == AssignDeclarationType::AlwaysComb {
+                if self.covered_bits(width).iter().all(|x| *x) {
+                    return None;
+                } else {
+                    return self.partial_statement();
+                }
+            }
+        }
+
+        for child in &self.children {
+            let ret = child.check_always_comb_uncovered_bits(width);
+            if ret.is_some() {
+                return ret;
+            }
+        }
+
+        None
+    }
+
+    fn covered_bits(&self, width: usize) -> Vec<bool> {
+        match self.r#type {
+            Some(AssignPositionType::StatementBranch {
+                branches,
+                has_default,
+                ..
+            }) => {
+                if !has_default || self.children.len() != branches {
+                    vec![false; width]
+                } else {
+                    let mut ret = vec![true; width];
+                    for child in &self.children {
+                        for (x, y) in ret.iter_mut().zip(child.covered_bits(width)) {
+                            *x &= y;
+                        }
+                    }
+                    ret
+                }
+            }
+            Some(AssignPositionType::Statement { range, .. }) => {
+                if let Some((msb, lsb)) = range {
+                    (0..width).map(|x| lsb <= x && x <= msb).collect()
+                } else {
+                    vec![true; width]
+                }
+            }
+            _ => {
+                let mut ret = vec![false; width];
+                for child in &self.children {
+                    for (x, y) in ret.iter_mut().zip(child.covered_bits(width)) {
+                        *x |= y;
+                    }
+                }
+                ret
+            }
+        }
+    }
+
+    fn partial_statement(&self) -> Option<Token> {
+        if let Some(AssignPositionType::Statement {
+            token,
+            range: Some(_),
+            ..
+        }) = self.r#type
+        {
+            return Some(token);
+        }
+
+        for child in &self.children {
+            let ret = child.partial_statement();
+            if ret.is_some() {
+                return ret;
+            }
+        }
+
+        None
+    }
+
     pub fn check_always_ff_missing_reset(&self) -> Option<Token> {
         if let Some(AssignPositionType::StatementBranch {
             ref r#type,
@@ -85,6 +85,11 @@ struct Pattern {
     pub missing_port: StrId,
     pub missing_reset_statement: StrId,
     pub unused_variable: StrId,
+    pub implicit_truncation: StrId,
+    pub signed_zero_extension: StrId,
+    pub mismatch_port_width: StrId,
+    pub reset_domain_crossing: StrId,
+    pub unsynchronized_reset: StrId,
     pub enum_encoding: StrId,
     pub sequential: StrId,
     pub onehot: StrId,
@@ -103,6 +108,11 @@ impl Pattern {
             missing_port: resource_table::insert_str("missing_port"),
             missing_reset_statement: resource_table::insert_str("missing_reset_statement"),
             unused_variable: resource_table::insert_str("unused_variable"),
+            implicit_truncation: resource_table::insert_str("implicit_truncation"),
+            signed_zero_extension: resource_table::insert_str("signed_zero_extension"),
+            mismatch_port_width: resource_table::insert_str("mismatch_port_width"),
+            reset_domain_crossing: resource_table::insert_str("reset_domain_crossing"),
+            unsynchronized_reset: resource_table::insert_str("unsynchronized_reset"),
             enum_encoding: resource_table::insert_str("enum_encoding"),
             sequential: resource_table::insert_str("sequential"),
             onehot: resource_table::insert_str("onehot"),
@@ -154,6 +164,21 @@ impl TryFrom<&veryl_parser::veryl_grammar_trait::Attribute> for Attribute {
                         x if x == pat.unused_variable => {
                             Ok(Attribute::Allow(AllowItem::UnusedVariable))
                         }
+                        x if x == pat.implicit_truncation => {
+                            Ok(Attribute::Allow(AllowItem::ImplicitTruncation))
+                        }
+                        x if x == pat.signed_zero_extension => {
+                            Ok(Attribute::Allow(AllowItem::SignedZeroExtension))
+                        }
+                        x if x == pat.mismatch_port_width => {
+                            Ok(Attribute::Allow(AllowItem::MismatchPortWidth))
+                        }
+                        x if x == pat.reset_domain_crossing => {
+                            Ok(Attribute::Allow(AllowItem::ResetDomainCrossing))
+                        }
+                        x if x == pat.unsynchronized_reset => {
+                            Ok(Attribute::Allow(AllowItem::UnsynchronizedReset))
+                        }
                         _ => Err(AttributeError::InvalidAllow(arg.text)),
                     }
                 } else {
@@ -207,6 +232,11 @@ pub enum AllowItem {
     MissingPort,
     MissingResetStatement,
     UnusedVariable,
+    ImplicitTruncation,
+    SignedZeroExtension,
+    MismatchPortWidth,
+    ResetDomainCrossing,
+    UnsynchronizedReset,
 }
 
 impl fmt::Display for AllowItem {
@@ -215,6 +245,11 @@ impl fmt::Display for AllowItem {
             AllowItem::MissingPort => "missing_port",
             AllowItem::MissingResetStatement => "missing_reset_statement",
             AllowItem::UnusedVariable => "unused_variable",
+            AllowItem::ImplicitTruncation => "implicit_truncation",
+            AllowItem::SignedZeroExtension => "signed_zero_extension",
+            AllowItem::MismatchPortWidth => "mismatch_port_width",
+            AllowItem::ResetDomainCrossing => "reset_domain_crossing",
+            AllowItem::UnsynchronizedReset => "unsynchronized_reset",
         };
         text.fmt(f)
     }
@@ -0,0 +1,777 @@
+use crate::elaborator::{self, Instance};
+use crate::evaluator::Evaluator;
+use crate::r#unsafe::Unsafe;
+use crate::symbol::{ClockDomain, Direction, GenericBoundKind, Port, Symbol, SymbolId, SymbolKind};
+use crate::symbol_table;
+use crate::unsafe_table;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use veryl_parser::resource_table::{self, StrId};
+use veryl_parser::veryl_grammar_trait::*;
+use veryl_parser::veryl_token::{Token, TokenRange};
+use veryl_parser::veryl_walker::{Handler, HandlerPoint, VerylWalker};
+use veryl_parser::ParolError;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Protection {
+    Synchronizer,
+    AsyncFifo,
+    AsyncHandshake,
+    /// Only waived by `unsafe (cdc)`
+    Unsafe,
+    None,
+}
+
+impl Protection {
+    pub fn is_synchronized(&self) -> bool {
+        matches!(
+            self,
+            Protection::Synchronizer | Protection::AsyncFifo | Protection::AsyncHandshake
+        )
+    }
+}
+
+impl fmt::Display for Protection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text = match self {
+            Protection::Synchronizer => "synchronizer",
+            Protection::AsyncFifo => "async_fifo",
+            Protection::AsyncHandshake => "async_handshake",
+            Protection::Unsafe => "unsafe (cdc)",
+            Protection::None => "unprotected",
+        };
+        text.fmt(f)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CdcIssue {
+    CombinationalInput,
+    MultiBitWithoutGray,
+}
+
+impl fmt::Display for CdcIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text = match self {
+            CdcIssue::CombinationalInput => "combinational logic feeds synchronizer",
+            CdcIssue::MultiBitWithoutGray => "multi-bit signal crosses without gray coding",
+        };
+        text.fmt(f)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Crossing {
+    pub module: StrId,
+    pub src: ClockDomain,
+    pub dst: ClockDomain,
+    /// Signal or instance name which crosses the domains
+    pub signal: StrId,
+    /// Instance and port name if the crossing is through an instance port
+    pub port: Option<(StrId, StrId)>,
+    pub range: TokenRange,
+    pub protection: Protection,
+    pub issues: Vec<CdcIssue>,
+}
+
+/// Clock domain crossings in module declarations of the syntax trees
+///
+/// Modules are analyzed through the instance tree elaborated from each top module,
+/// so signals are followed into instances to find synchronizers and combinational paths in them.
+/// Modules which are not instantiated are the top modules.
+pub fn analyze(inputs: &[&Veryl]) -> Vec<Crossing> {
+    let mut hierarchy = Hierarchy::default();
+    let mut modules = Vec::new();
+    for input in inputs {
+        for x in &input.veryl_list {
+            let items: Vec<DescriptionItem> = x.description_group.as_ref().into();
+            for item in items {
+                if let DescriptionItem::ModuleDeclaration(x) = item {
+                    let x = x.module_declaration;
+                    if let Ok(symbol) = symbol_table::resolve(x.identifier.as_ref()) {
+                        modules.push(symbol.found.clone());
+                        hierarchy.declarations.insert(symbol.found.id, *x);
+                    }
+                }
+            }
+        }
+    }
+
+    // modules which are not reached from the top modules are analyzed as top modules too
+    modules.sort_by_key(|x| !x.references.is_empty());
+    for module in &modules {
+        if hierarchy.visited.contains(&module.id) {
+            continue;
+        }
+        if let Some(top) = elaborator::elaborate(&module.token.to_string(), inputs) {
+            hierarchy.instance(&top);
+        }
+    }
+    hierarchy.crossings
+}
+
+#[derive(Default)]
+struct Hierarchy {
+    declarations: HashMap<SymbolId, ModuleDeclaration>,
+    visited: HashSet<SymbolId>,
+    crossings: Vec<Crossing>,
+}
+
+impl Hierarchy {
+    /// Analyze the elaborated instance after its children, and return the summary of its ports
+    fn instance(&mut self, instance: &Instance) -> Summary {
+        let children = instance
+            .children
+            .iter()
+            .map(|x| Child {
+                range: x.range,
+                symbol: x.symbol,
+                summary: self.instance(x),
+            })
+            .collect();
+
+        let Some(id) = instance.symbol else {
+            return Summary::default();
+        };
+        let Some(declaration) = self.declarations.get(&id) else {
+            return Summary::default();
+        };
+        self.visited.insert(id);
+
+        let mut walker = CdcWalker::default();
+        walker.handler.children = children;
+        walker.module_declaration(declaration);
+
+        // each elaborated instance of the same module reports the same crossings
+        for x in walker.handler.crossings {
+            if !self.crossings.contains(&x) {
+                self.crossings.push(x);
+            }
+        }
+        walker.handler.summary
+    }
+}
+
+/// Paths from the ports of a module instance which are visible from its parent
+#[derive(Clone, Default)]
+struct Summary {
+    /// Input ports which feed the data input of a synchronizer,
+    /// and whether they are connected to it directly
+    synchronized: Vec<(StrId, bool)>,
+    /// Input and output ports which are connected by combinational logic
+    through: Vec<(StrId, StrId)>,
+}
+
+/// Elaborated instance in the module
+struct Child {
+    range: TokenRange,
+    symbol: Option<SymbolId>,
+    summary: Summary,
+}
+
+#[derive(Default)]
+struct CdcWalker {
+    handler: CdcHandler,
+}
+
+impl VerylWalker for CdcWalker {
+    fn get_handlers(&mut self) -> Option<Vec<&mut dyn Handler>> {
+        Some(vec![&mut self.handler])
+    }
+}
+
+#[derive(Clone)]
+struct Source {
+    symbol: Symbol,
+    range: TokenRange,
+}
+
+impl Source {
+    fn clock_domain(&self) -> ClockDomain {
+        clock_domain(&self.symbol)
+    }
+}
+
+/// Crossing which requires information of the whole module to determine issues
+struct Pending {
+    crossing: Crossing,
+    multi_bit: Option<SymbolId>,
+    synchronized: Option<SymbolId>,
+}
+
+#[derive(Default)]
+struct CdcHandler {
+    point: HandlerPoint,
+    crossings: Vec<Crossing>,
+    module: Option<StrId>,
+    default_clock: Option<SymbolId>,
+    always_ff_clock_domain: Option<ClockDomain>,
+    sources: Vec<Source>,
+    connects: Vec<(StrId, Vec<Source>, bool)>,
+    pending: Vec<Pending>,
+    comb_driven: HashSet<SymbolId>,
+    ff_copies: Vec<(SymbolId, SymbolId)>,
+    gray: HashSet<SymbolId>,
+    ports: Vec<Port>,
+    comb_edges: Vec<(SymbolId, SymbolId)>,
+    children: Vec<Child>,
+    summary: Summary,
+}
+
+impl CdcHandler {
+    fn source_of(&self, expression: &Expression) -> Option<Source> {
+        let range: TokenRange = expression.into();
+        match self.sources.as_slice() {
+            [x] if x.range == range => Some(x.clone()),
+            _ => None,
+        }
+    }
+
+    /// Assignment from `self.sources` to `target`
+    fn assignment(&mut self, target: Option<Symbol>, expression: &Expression, token: &Token) {
+        let Some(target) = target else {
+            return;
+        };
+
+        if self.always_ff_clock_domain.is_some() {
+            if let Some(x) = self.source_of(expression) {
+                self.ff_copies.push((target.id, x.symbol.id));
+            }
+        } else {
+            self.comb_driven.insert(target.id);
+            for x in &self.sources {
+                self.comb_edges.push((target.id, x.symbol.id));
+            }
+        }
+
+        let dst = self
+            .always_ff_clock_domain
+            .unwrap_or_else(|| clock_domain(&target));
+        if dst == ClockDomain::None {
+            return;
+        }
+
+        let protection = if unsafe_table::contains(token, Unsafe::Cdc) {
+            Protection::Unsafe
+        } else {
+            Protection::None
+        };
+
+        for source in self.sources.clone() {
+            let src = source.clock_domain();
+            if src.compatible(&dst) {
+                continue;
+            }
+            self.push(source, src, dst, None, protection);
+        }
+    }
+
+    fn push(
+        &mut self,
+        source: Source,
+        src: ClockDomain,
+        dst: ClockDomain,
+        port: Option<(StrId, StrId)>,
+        protection: Protection,
+    ) {
+        let Some(module) = self.module else {
+            return;
+        };
+
+        let multi_bit = if matches!(
+            protection,
+            Protection::AsyncFifo | Protection::AsyncHandshake
+        ) || !is_multi_bit(&source.symbol)
+        {
+            None
+        } else {
+            Some(source.symbol.id)
+        };
+
+        self.pending.push(Pending {
+            crossing: Crossing {
+                module,
+                src,
+                dst,
+                signal: source.symbol.token.text,
+                port,
+                range: source.range,
+                protection,
+                issues: Vec::new(),
+            },
+            multi_bit,
+            synchronized: None,
+        });
+    }
+
+    /// Name of the input port of this module if the source is it
+    fn input_port(&self, source: &Source) -> Option<StrId> {
+        self.ports
+            .iter()
+            .find(|x| {
+                x.symbol == source.symbol.id && matches!(x.property().direction, Direction::Input)
+            })
+            .map(|x| x.name)
+    }
+
+    fn instance(&mut self, arg: &InstDeclaration) {
+        // generic instance is resolved to the actual module by elaboration
+        let range: TokenRange = arg.identifier.as_ref().into();
+        let child = self.children.iter().find(|x| x.range == range);
+        let summary = child.map(|x| x.summary.clone()).unwrap_or_default();
+        let symbol = match child.and_then(|x| x.symbol).and_then(symbol_table::get) {
+            Some(x) => x,
+            None => match symbol_table::resolve(arg.scoped_identifier.as_ref()) {
+                Ok(x) => x.found,
+                Err(_) => return,
+            },
+        };
+        let Some((ports, crossing_point)) = instance_ports(&symbol) else {
+            return;
+        };
+        let connects = std::mem::take(&mut self.connects);
+        let connect = |port: &Port| connects.iter().find(|x| x.0 == port.name);
+        let name = arg.identifier.identifier_token.token.text;
+
+        if is_std(&symbol, &["gray_encoder", "gray_counter"]) {
+            for port in &ports {
+                if let Some((_, sources, true)) = connect(port) {
+                    if matches!(port.property().direction, Direction::Output) {
+                        self.gray.insert(sources[0].symbol.id);
+                    }
+                }
+            }
+        }
+
+        // clock domains of the instance are mapped through connections of clock ports
+        let mut domain_map = HashMap::new();
+        let mut clock_domains = Vec::new();
+        for port in &ports {
+            let property = port.property();
+            if property.r#type.as_ref().is_some_and(|x| x.kind.is_clock()) {
+                if let Some(x) = connect(port).and_then(|x| x.1.first()) {
+                    domain_map.insert(property.clock_domain, x.clock_domain());
+                    clock_domains.push((x.clone(), x.clock_domain()));
+                }
+            }
+        }
+
+        let unsafe_cdc = unsafe_table::contains(&arg.semicolon.semicolon_token.token, Unsafe::Cdc);
+        let unprotected = if unsafe_cdc {
+            Protection::Unsafe
+        } else {
+            Protection::None
+        };
+
+        let len = self.pending.len();
+        for port in &ports {
+            let property = port.property();
+            let Some((_, sources, single)) = connect(port) else {
+                continue;
+            };
+            let single = *single;
+
+            // data input of synchronizer, which may be in the instance
+            let is_data = !property
+                .r#type
+                .as_ref()
+                .is_some_and(|x| x.kind.is_clock() || x.kind.is_reset());
+            let synchronized = match crossing_point {
+                Some(Protection::Synchronizer) if is_data => Some(true),
+                Some(_) => None,
+                None => summary
+                    .synchronized
+                    .iter()
+                    .find(|x| x.0 == port.name)
+                    .map(|x| x.1),
+            };
+            let synchronized = synchronized.filter(|_| property.direction == Direction::Input);
+            if let Some(direct) = synchronized {
+                for source in sources {
+                    if let Some(x) = self.input_port(source) {
+                        self.summary.synchronized.push((x, single && direct));
+                    }
+                }
+            }
+            let protection = match (crossing_point, synchronized) {
+                (Some(x), _) => x,
+                (None, Some(_)) => Protection::Synchronizer,
+                (None, None) => unprotected,
+            };
+
+            let Some(expected) = domain_map.get(&property.clock_domain).copied() else {
+                continue;
+            };
+
+            for source in sources {
+                let domain = source.clock_domain();
+                if domain.compatible(&expected) {
+                    continue;
+                }
+                let (src, dst) = match property.direction {
+                    Direction::Input => (domain, expected),
+                    Direction::Output => (expected, domain),
+                    _ => continue,
+                };
+                self.push(
+                    source.clone(),
+                    src,
+                    dst,
+                    Some((name, port.name)),
+                    protection,
+                );
+                if let Some(direct) = synchronized {
+                    let pending = self.pending.last_mut().unwrap();
+                    if single && direct {
+                        pending.synchronized = Some(source.symbol.id);
+                    } else {
+                        pending.crossing.issues.push(CdcIssue::CombinationalInput);
+                    }
+                }
+            }
+        }
+
+        // combinational paths in the instance connect the signals of this module
+        for (input, output) in &summary.through {
+            let port = |name: &StrId| ports.iter().find(|x| x.name == *name);
+            let (Some(input_port), Some(output_port)) = (port(input), port(output)) else {
+                continue;
+            };
+            let (Some((_, sources, _)), Some((_, targets, true))) =
+                (connect(input_port), connect(output_port))
+            else {
+                continue;
+            };
+            // paths in a clock domain of the instance are checked at the ports
+            let checked = domain_map.contains_key(&input_port.property().clock_domain);
+
+            for target in targets {
+                self.comb_driven.insert(target.symbol.id);
+                for source in sources {
+                    self.comb_edges.push((target.symbol.id, source.symbol.id));
+
+                    let src = source.clock_domain();
+                    let dst = target.clock_domain();
+                    if checked || dst == ClockDomain::None || src.compatible(&dst) {
+                        continue;
+                    }
+                    self.push(source.clone(), src, dst, Some((name, *input)), unprotected);
+                }
+            }
+        }
+
+        // FIFO and handshake have no crossing at the ports because each port belongs to a domain
+        let crossed = self.pending.len() > len;
+        if let (false, Some(x @ (Protection::AsyncFifo | Protection::AsyncHandshake))) =
+            (crossed, crossing_point)
+        {
+            if let [(source, src), .., (_, dst)] = clock_domains.as_slice() {
+                if !src.compatible(dst) {
+                    let source = Source {
+                        symbol: source.symbol.clone(),
+                        range: arg.identifier.as_ref().into(),
+                    };
+                    self.push(source, *src, *dst, None, x);
+                    self.pending.last_mut().unwrap().crossing.signal = name;
+                }
+            }
+        }
+    }
+
+    fn finish_module(&mut self) {
+        // output ports which are reached from input ports through combinational assignments
+        for output in &self.ports {
+            if !matches!(output.property().direction, Direction::Output) {
+                continue;
+            }
+            let mut visited = HashSet::new();
+            let mut stack = vec![output.symbol];
+            while let Some(x) = stack.pop() {
+                for (target, source) in &self.comb_edges {
+                    if *target == x && visited.insert(*source) {
+                        stack.push(*source);
+                    }
+                }
+            }
+            for input in &self.ports {
+                let is_input = matches!(input.property().direction, Direction::Input);
+                if is_input && visited.contains(&input.symbol) {
+                    self.summary.through.push((input.name, output.name));
+                }
+            }
+        }
+
+        // registered copies of gray-coded signals are also gray-coded
+        loop {
+            let mut added = false;
+            for (dst, src) in &self.ff_copies {
+                if self.gray.contains(src) {
+                    added |= self.gray.insert(*dst);
+                }
+            }
+            if !added {
+                break;
+            }
+        }
+
+        for mut pending in self.pending.drain(..) {
+            if let Some(x) = pending.synchronized {
+                if self.comb_driven.contains(&x) {
+                    pending.crossing.issues.push(CdcIssue::CombinationalInput);
+                }
+            }
+            if let Some(x) = pending.multi_bit {
+                if !self.gray.contains(&x) {
+                    pending.crossing.issues.push(CdcIssue::MultiBitWithoutGray);
+                }
+            }
+            self.crossings.push(pending.crossing);
+        }
+
+        self.comb_driven.clear();
+        self.ff_copies.clear();
+        self.gray.clear();
+        self.comb_edges.clear();
+        self.ports.clear();
+    }
+}
+
+impl Handler for CdcHandler {
+    fn set_point(&mut self, p: HandlerPoint) {
+        self.point = p;
+    }
+}
+
+impl VerylGrammarTrait for CdcHandler {
+    fn expression_identifier(&mut self, arg: &ExpressionIdentifier) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            if let Ok(symbol) = symbol_table::resolve(arg.scoped_identifier.as_ref()) {
+                if matches!(
+                    symbol.found.kind,
+                    SymbolKind::Variable(_) | SymbolKind::Port(_)
+                ) {
+                    self.sources.push(Source {
+                        symbol: symbol.found,
+                        range: arg.into(),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn let_statement(&mut self, arg: &LetStatement) -> Result<(), ParolError> {
+        match self.point {
+            HandlerPoint::Before => self.sources.clear(),
+            HandlerPoint::After => {
+                let target = symbol_table::resolve(arg.identifier.as_ref()).ok();
+                self.assignment(
+                    target.map(|x| x.found),
+                    &arg.expression,
+                    &arg.semicolon.semicolon_token.token,
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn identifier_statement(&mut self, arg: &IdentifierStatement) -> Result<(), ParolError> {
+        match self.point {
+            HandlerPoint::Before => self.sources.clear(),
+            HandlerPoint::After => {
+                if let IdentifierStatementGroup::Assignment(x) =
+                    arg.identifier_statement_group.as_ref()
+                {
+                    // the first source is the left-hand side
+                    let target = if self.sources.is_empty() {
+                        None
+                    } else {
+                        Some(self.sources.remove(0).symbol)
+                    };
+                    self.assignment(
+                        target,
+                        &x.assignment.expression,
+                        &arg.semicolon.semicolon_token.token,
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn let_declaration(&mut self, arg: &LetDeclaration) -> Result<(), ParolError> {
+        match self.point {
+            HandlerPoint::Before => self.sources.clear(),
+            HandlerPoint::After => {
+                let target = symbol_table::resolve(arg.identifier.as_ref()).ok();
+                self.assignment(
+                    target.map(|x| x.found),
+                    &arg.expression,
+                    &arg.semicolon.semicolon_token.token,
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn always_ff_declaration(&mut self, arg: &AlwaysFfDeclaration) -> Result<(), ParolError> {
+        match self.point {
+            HandlerPoint::Before => {
+                let clock = if let Some(ref x) = arg.always_ff_declaration_opt {
+                    let ident = x
+                        .alwayf_ff_event_list
+                        .always_ff_clock
+                        .hierarchical_identifier
+                        .identifier
+                        .as_ref();
+                    symbol_table::resolve(ident).ok().map(|x| x.found)
+                } else {
+                    self.default_clock.and_then(symbol_table::get)
+                };
+                self.always_ff_clock_domain =
+                    Some(clock.map(|x| clock_domain(&x)).unwrap_or(ClockDomain::None));
+            }
+            HandlerPoint::After => self.always_ff_clock_domain = None,
+        }
+        Ok(())
+    }
+
+    fn assign_declaration(&mut self, arg: &AssignDeclaration) -> Result<(), ParolError> {
+        match self.point {
+            HandlerPoint::Before => self.sources.clear(),
+            HandlerPoint::After => {
+                let ident = arg.hierarchical_identifier.identifier.as_ref();
+                let target = symbol_table::resolve(ident).ok();
+                self.assignment(
+                    target.map(|x| x.found),
+                    &arg.expression,
+                    &arg.semicolon.semicolon_token.token,
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn inst_port_item(&mut self, arg: &InstPortItem) -> Result<(), ParolError> {
+        match self.point {
+            HandlerPoint::Before => self.sources.clear(),
+            HandlerPoint::After => {
+                let name = arg.identifier.identifier_token.token.text;
+                if let Some(ref x) = arg.inst_port_item_opt {
+                    let single = self.source_of(&x.expression).is_some();
+                    self.connects
+                        .push((name, std::mem::take(&mut self.sources), single));
+                } else if let Ok(symbol) = symbol_table::resolve(arg.identifier.as_ref()) {
+                    let source = Source {
+                        symbol: symbol.found,
+                        range: arg.identifier.as_ref().into(),
+                    };
+                    self.connects.push((name, vec![source], true));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn inst_declaration(&mut self, arg: &InstDeclaration) -> Result<(), ParolError> {
+        match self.point {
+            HandlerPoint::Before => self.connects.clear(),
+            HandlerPoint::After => self.instance(arg),
+        }
+        Ok(())
+    }
+
+    fn module_declaration(&mut self, arg: &ModuleDeclaration) -> Result<(), ParolError> {
+        match self.point {
+            HandlerPoint::Before => {
+                self.module = Some(arg.identifier.identifier_token.token.text);
+                if let Ok(symbol) = symbol_table::resolve(arg.identifier.as_ref()) {
+                    if let SymbolKind::Module(ref x) = symbol.found.kind {
+                        self.default_clock = x.default_clock;
+                        self.ports = x.ports.clone();
+                    }
+                }
+            }
+            HandlerPoint::After => {
+                self.finish_module();
+                self.module = None;
+                self.default_clock = None;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn clock_domain(symbol: &Symbol) -> ClockDomain {
+    match &symbol.kind {
+        SymbolKind::Variable(x) => x.clock_domain,
+        SymbolKind::Port(x) => x.clock_domain,
+        _ => ClockDomain::None,
+    }
+}
+
+fn is_multi_bit(symbol: &Symbol) -> bool {
+    let r#type = match &symbol.kind {
+        SymbolKind::Variable(x) => x.r#type.clone(),
+        SymbolKind::Port(x) => match &x.r#type {
+            Some(x) => x.clone(),
+            None => return false,
+        },
+        _ => return false,
+    };
+    if !r#type.array.is_empty() {
+        return true;
+    }
+    match Evaluator::new().type_width(r#type.clone()) {
+        Some(x) => x > 1,
+        None => !r#type.width.is_empty(),
+    }
+}
+
+/// Whether the symbol is defined as one of `names` in the standard library
+fn is_std(symbol: &Symbol, names: &[&str]) -> bool {
+    let std = resource_table::get_str_id("$std".to_string());
+    std.is_some() && symbol.namespace.paths.first() == std.as_ref() && {
+        let name = symbol.token.to_string();
+        names.contains(&name.as_str())
+    }
+}
+
+fn crossing_point(symbol: &Symbol) -> Option<Protection> {
+    if is_std(symbol, &["synchronizer"]) {
+        Some(Protection::Synchronizer)
+    } else if is_std(symbol, &["async_fifo"]) {
+        Some(Protection::AsyncFifo)
+    } else if is_std(symbol, &["async_handshake"]) {
+        Some(Protection::AsyncHandshake)
+    } else {
+        None
+    }
+}
+
+/// Ports of the instantiated module and its kind as crossing point
+fn instance_ports(symbol: &Symbol) -> Option<(Vec<Port>, Option<Protection>)> {
+    match &symbol.kind {
+        SymbolKind::Module(x) => {
+            let proto = x
+                .proto
+                .as_ref()
+                .and_then(|x| symbol_table::resolve((x, &symbol.namespace)).ok());
+            let kind = crossing_point(symbol).or(proto.and_then(|x| crossing_point(&x.found)));
+            Some((x.ports.clone(), kind))
+        }
+        SymbolKind::GenericInstance(x) => instance_ports(&symbol_table::get(x.base)?),
+        SymbolKind::GenericParameter(x) => {
+            let GenericBoundKind::Proto(ref x) = x.bound else {
+                return None;
+            };
+            let proto = symbol_table::resolve((x, &symbol.namespace)).ok()?.found;
+            if let SymbolKind::ProtoModule(ref x) = proto.kind {
+                Some((x.ports.clone(), crossing_point(&proto)))
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
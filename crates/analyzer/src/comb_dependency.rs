@@ -0,0 +1,331 @@
+use crate::assign::AssignPath;
+use crate::symbol::{Direction, SymbolId, SymbolKind};
+use crate::symbol_table;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet, VecDeque};
+use veryl_parser::resource_table::StrId;
+use veryl_parser::veryl_token::Token;
+
+#[derive(Clone, Debug)]
+pub enum CombDependency {
+    /// `target` is driven from `source` through combinational logic in `module`
+    Assign {
+        module: SymbolId,
+        source: CombSignal,
+        target: CombSignal,
+        token: Token,
+    },
+    /// Ports of `r#type` are connected to signals of `module`
+    Instance {
+        module: SymbolId,
+        r#type: SymbolId,
+        connects: Vec<(StrId, Vec<CombSignal>)>,
+        token: Token,
+    },
+}
+
+impl CombDependency {
+    pub fn module(&self) -> SymbolId {
+        match self {
+            CombDependency::Assign { module, .. } => *module,
+            CombDependency::Instance { module, .. } => *module,
+        }
+    }
+
+    pub fn token(&self) -> &Token {
+        match self {
+            CombDependency::Assign { token, .. } => token,
+            CombDependency::Instance { token, .. } => token,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CombSignal {
+    pub path: AssignPath,
+    /// selected by index, so only a part of the signal may be referred
+    pub partial: bool,
+}
+
+impl CombSignal {
+    fn overlap(&self, x: &CombSignal) -> bool {
+        self.path.included(&x.path) || x.path.included(&self.path)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct CombEdge {
+    pub source: CombSignal,
+    pub target: CombSignal,
+    pub token: Token,
+}
+
+impl CombEdge {
+    /// Either side is selected by index, so the dependency can't be determined without elaboration
+    fn is_uncertain(&self) -> bool {
+        self.source.partial || self.target.partial
+    }
+}
+
+#[derive(Default)]
+pub struct CombGraph {
+    dependencies: HashMap<SymbolId, Vec<CombDependency>>,
+    port_paths: HashMap<SymbolId, Vec<(StrId, StrId)>>,
+    visiting: HashSet<SymbolId>,
+}
+
+impl CombGraph {
+    pub fn new(dependencies: Vec<CombDependency>) -> Self {
+        let mut ret = CombGraph::default();
+        for x in dependencies {
+            ret.dependencies.entry(x.module()).or_default().push(x);
+        }
+        ret
+    }
+
+    /// Combinational dependencies in the module including paths through instances
+    pub fn edges(&mut self, module: SymbolId) -> Vec<CombEdge> {
+        let mut ret = Vec::new();
+        let dependencies = self.dependencies.get(&module).cloned().unwrap_or_default();
+        for x in dependencies {
+            match x {
+                CombDependency::Assign {
+                    source,
+                    target,
+                    token,
+                    ..
+                } => ret.push(CombEdge {
+                    source,
+                    target,
+                    token,
+                }),
+                CombDependency::Instance {
+                    r#type,
+                    connects,
+                    token,
+                    ..
+                } => {
+                    for (input, output) in self.port_paths(r#type) {
+                        let sources = connects.iter().filter(|x| x.0 == input);
+                        let targets: Vec<_> = connects
+                            .iter()
+                            .filter(|x| x.0 == output)
+                            .flat_map(|x| x.1.iter())
+                            .collect();
+                        for source in sources.flat_map(|x| x.1.iter()) {
+                            for target in &targets {
+                                ret.push(CombEdge {
+                                    source: source.clone(),
+                                    target: (*target).clone(),
+                                    token,
+                                });
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        ret
+    }
+
+    /// Pairs of input and output ports which are connected through combinational logic
+    fn port_paths(&mut self, module: SymbolId) -> Vec<(StrId, StrId)> {
+        if let Some(x) = self.port_paths.get(&module) {
+            return x.clone();
+        }
+        // recursive instantiation is reported by other check
+        if !self.visiting.insert(module) {
+            return Vec::new();
+        }
+
+        let ports = match symbol_table::get(module).map(|x| x.kind) {
+            Some(SymbolKind::Module(x)) => x.ports,
+            _ => Vec::new(),
+        };
+        let edges = self.edges(module);
+
+        let mut ret = Vec::new();
+        for input in &ports {
+            if input.property().direction != Direction::Input {
+                continue;
+            }
+            let reached = reachable(&edges, input.symbol);
+            for output in &ports {
+                if output.property().direction != Direction::Output {
+                    continue;
+                }
+                let output_path = AssignPath::new(output.symbol);
+                if reached
+                    .iter()
+                    .any(|x| x.included(&output_path) || output_path.included(x))
+                {
+                    ret.push((input.name, output.name));
+                }
+            }
+        }
+
+        self.visiting.remove(&module);
+        self.port_paths.insert(module, ret.clone());
+        ret
+    }
+}
+
+fn reachable(edges: &[CombEdge], start: SymbolId) -> Vec<AssignPath> {
+    let start = CombSignal {
+        path: AssignPath::new(start),
+        partial: false,
+    };
+    let mut ret = vec![start.path.clone()];
+    let mut queue = VecDeque::from([start]);
+    let mut visited = vec![false; edges.len()];
+    while let Some(signal) = queue.pop_front() {
+        for (i, edge) in edges.iter().enumerate() {
+            if !visited[i] && edge.source.overlap(&signal) {
+                visited[i] = true;
+                ret.push(edge.target.path.clone());
+                queue.push_back(edge.target.clone());
+            }
+        }
+    }
+    ret
+}
+
+/// Find combinational loops, each loop is returned as a sequence of edges
+pub fn find_loops(edges: &[CombEdge]) -> Vec<Vec<CombEdge>> {
+    // edges are connected if target of the former overlaps source of the latter
+    let mut by_base: HashMap<SymbolId, Vec<usize>> = HashMap::new();
+    for (i, edge) in edges.iter().enumerate() {
+        if let Some(base) = edge.source.path.0.first() {
+            by_base.entry(*base).or_default().push(i);
+        }
+    }
+    let successors: Vec<Vec<usize>> = edges
+        .iter()
+        .map(|edge| {
+            let Some(base) = edge.target.path.0.first() else {
+                return Vec::new();
+            };
+            by_base
+                .get(base)
+                .map(|x| {
+                    x.iter()
+                        .copied()
+                        .filter(|x| edge.target.overlap(&edges[*x].source))
+                        .collect()
+                })
+                .unwrap_or_default()
+        })
+        .collect();
+
+    let mut ret = Vec::new();
+    for component in strongly_connected_components(&successors) {
+        let members: HashSet<_> = component.iter().copied().collect();
+        let is_loop = component.len() > 1 || successors[component[0]].contains(&component[0]);
+        if !is_loop {
+            continue;
+        }
+        // a loop consisting of only uncertain edges may be false positive
+        let Some(start) = component
+            .iter()
+            .copied()
+            .filter(|x| !edges[*x].is_uncertain())
+            .min()
+        else {
+            continue;
+        };
+        if let Some(path) = shortest_cycle(&successors, &members, start) {
+            ret.push(path.iter().map(|x| edges[*x].clone()).collect());
+        }
+    }
+    ret.sort_by_key(|x: &Vec<CombEdge>| x.first().map(|x| (x.token.line, x.token.column)));
+    ret
+}
+
+fn shortest_cycle(
+    successors: &[Vec<usize>],
+    members: &HashSet<usize>,
+    start: usize,
+) -> Option<Vec<usize>> {
+    let mut prev: HashMap<usize, usize> = HashMap::new();
+    let mut queue = VecDeque::from([start]);
+    while let Some(x) = queue.pop_front() {
+        for y in &successors[x] {
+            if !members.contains(y) {
+                continue;
+            }
+            if *y == start {
+                let mut path = vec![x];
+                let mut cur = x;
+                while cur != start {
+                    cur = prev[&cur];
+                    path.push(cur);
+                }
+                path.reverse();
+                return Some(path);
+            }
+            if let Entry::Vacant(e) = prev.entry(*y) {
+                e.insert(x);
+                queue.push_back(*y);
+            }
+        }
+    }
+    None
+}
+
+/// Tarjan's algorithm without recursion because the graph can be deep
+fn strongly_connected_components(successors: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let n = successors.len();
+    let mut index = vec![usize::MAX; n];
+    let mut lowlink = vec![0; n];
+    let mut on_stack = vec![false; n];
+    let mut stack = Vec::new();
+    let mut next_index = 0;
+    let mut ret = Vec::new();
+
+    for root in 0..n {
+        if index[root] != usize::MAX {
+            continue;
+        }
+        let mut call_stack = vec![(root, 0)];
+        index[root] = next_index;
+        lowlink[root] = next_index;
+        next_index += 1;
+        stack.push(root);
+        on_stack[root] = true;
+
+        while let Some((v, i)) = call_stack.pop() {
+            if let Some(&w) = successors[v].get(i) {
+                call_stack.push((v, i + 1));
+                if index[w] == usize::MAX {
+                    index[w] = next_index;
+                    lowlink[w] = next_index;
+                    next_index += 1;
+                    stack.push(w);
+                    on_stack[w] = true;
+                    call_stack.push((w, 0));
+                } else if on_stack[w] {
+                    lowlink[v] = lowlink[v].min(index[w]);
+                }
+                continue;
+            }
+
+            if lowlink[v] == index[v] {
+                let mut component = Vec::new();
+                while let Some(w) = stack.pop() {
+                    on_stack[w] = false;
+                    component.push(w);
+                    if w == v {
+                        break;
+                    }
+                }
+                component.sort();
+                ret.push(component);
+            }
+            if let Some(&(parent, _)) = call_stack.last() {
+                lowlink[parent] = lowlink[parent].min(lowlink[v]);
+            }
+        }
+    }
+
+    ret
+}
@@ -0,0 +1,605 @@
+use crate::evaluator::{Evaluated, Evaluator};
+use crate::namespace_table;
+use crate::symbol::{Parameter, ParameterKind, ParameterValue, Symbol, SymbolId, SymbolKind};
+use crate::symbol_path::{GenericSymbolPath, GenericSymbolPathKind};
+use crate::symbol_table;
+use std::collections::HashMap;
+use std::fmt;
+use veryl_parser::veryl_grammar_trait::*;
+use veryl_parser::veryl_token::TokenRange;
+
+/// Upper limit of instance depth to stop recursive instantiation
+const MAX_DEPTH: usize = 256;
+
+/// Upper limit of iterations of a generate for
+const MAX_ITERATION: usize = 65536;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ValueKind {
+    Generic,
+    Param,
+    Const,
+}
+
+impl fmt::Display for ValueKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text = match self {
+            ValueKind::Generic => "generic",
+            ValueKind::Param => "param",
+            ValueKind::Const => "const",
+        };
+        text.fmt(f)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Value {
+    /// Name including the path of generate blocks like `g_for[1].VALUE`
+    pub name: String,
+    pub kind: ValueKind,
+    pub value: Evaluated,
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let value = match self.value {
+            Evaluated::Fixed { value, .. } => value.to_string(),
+            Evaluated::Real { value } => value.to_string(),
+            _ => "unknown".to_string(),
+        };
+        format!("{} {} = {}", self.kind, self.name, value).fmt(f)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Instance {
+    /// Name including the path of generate blocks like `g_for[1].u_inst`
+    pub name: String,
+    pub module: String,
+    /// `None` if the instantiated module is not a Veryl module or interface
+    pub symbol: Option<SymbolId>,
+    pub values: Vec<Value>,
+    pub children: Vec<Instance>,
+    pub range: TokenRange,
+}
+
+impl Instance {
+    /// Value by name in this instance
+    pub fn value(&self, name: &str) -> Option<Evaluated> {
+        self.values.iter().find(|x| x.name == name).map(|x| x.value)
+    }
+
+    /// Child instance by dot-separated hierarchical path from this instance
+    pub fn find(&self, path: &str) -> Option<&Instance> {
+        if path.is_empty() {
+            return Some(self);
+        }
+        for child in &self.children {
+            if let Some(rest) = path.strip_prefix(child.name.as_str()) {
+                if rest.is_empty() {
+                    return Some(child);
+                } else if let Some(rest) = rest.strip_prefix('.') {
+                    if let Some(x) = child.find(rest) {
+                        return Some(x);
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    fn fmt_indent(&self, f: &mut fmt::Formatter, indent: usize) -> fmt::Result {
+        let pad = " ".repeat(indent * 4);
+        writeln!(f, "{}{}: {}", pad, self.name, self.module)?;
+        for x in &self.values {
+            writeln!(f, "{}    {}", pad, x)?;
+        }
+        for x in &self.children {
+            x.fmt_indent(f, indent + 1)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Instance {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_indent(f, 0)
+    }
+}
+
+/// Elaborate instance tree from the top module
+pub fn elaborate(top: &str, inputs: &[&Veryl]) -> Option<Instance> {
+    let mut elaborator = Elaborator::default();
+    let mut top_declaration = None;
+    for input in inputs {
+        for x in &input.veryl_list {
+            let items: Vec<DescriptionItem> = x.description_group.as_ref().into();
+            for item in &items {
+                if let Some((symbol, range)) = elaborator.add(item) {
+                    if symbol.token.to_string() == top {
+                        top_declaration = Some((symbol, range));
+                    }
+                }
+            }
+        }
+    }
+
+    let (symbol, range) = top_declaration?;
+    Some(elaborator.instance(
+        top.to_string(),
+        &symbol,
+        range,
+        HashMap::new(),
+        Vec::new(),
+        0,
+    ))
+}
+
+enum GenericArg {
+    Value(Evaluated),
+    Symbol(SymbolId),
+}
+
+#[derive(Default)]
+struct Context {
+    evaluator: Evaluator,
+    generics: HashMap<SymbolId, SymbolId>,
+    values: Vec<Value>,
+    children: Vec<Instance>,
+}
+
+#[derive(Default)]
+struct Elaborator {
+    bodies: HashMap<SymbolId, Vec<GenerateItem>>,
+}
+
+impl Elaborator {
+    fn add(&mut self, item: &DescriptionItem) -> Option<(Symbol, TokenRange)> {
+        let (identifier, items) = match item {
+            DescriptionItem::ModuleDeclaration(x) => {
+                let x = &x.module_declaration;
+                let mut items = Vec::new();
+                for x in &x.module_declaration_list {
+                    let x: Vec<ModuleItem> = x.module_group.as_ref().into();
+                    items.extend(x.into_iter().map(|x| *x.generate_item));
+                }
+                (x.identifier.as_ref(), items)
+            }
+            DescriptionItem::InterfaceDeclaration(x) => {
+                let x = &x.interface_declaration;
+                let mut items = Vec::new();
+                for x in &x.interface_declaration_list {
+                    let x: Vec<InterfaceItem> = x.interface_group.as_ref().into();
+                    items.extend(x.into_iter().filter_map(|x| match x {
+                        InterfaceItem::GenerateItem(x) => Some(*x.generate_item),
+                        InterfaceItem::ModportDeclaration(_) => None,
+                    }));
+                }
+                (x.identifier.as_ref(), items)
+            }
+            _ => return None,
+        };
+
+        let symbol = symbol_table::resolve(identifier).ok()?.found;
+        self.bodies.insert(symbol.id, items);
+        Some((symbol, identifier.into()))
+    }
+
+    fn instance(
+        &self,
+        name: String,
+        symbol: &Symbol,
+        range: TokenRange,
+        overrides: HashMap<String, Evaluated>,
+        generic_args: Vec<GenericArg>,
+        depth: usize,
+    ) -> Instance {
+        let (generic_parameters, parameters): (&[SymbolId], &[Parameter]) = match &symbol.kind {
+            SymbolKind::Module(x) => (&x.generic_parameters, &x.parameters),
+            SymbolKind::Interface(x) => (&x.generic_parameters, &x.parameters),
+            _ => (&[], &[]),
+        };
+
+        let mut context = Context::default();
+
+        let mut generic_args = generic_args.into_iter();
+        for id in generic_parameters {
+            let arg = match generic_args.next() {
+                Some(x) => x,
+                None => self.default_generic_arg(&context, *id),
+            };
+            match arg {
+                GenericArg::Value(value) => {
+                    context.evaluator.values.insert(*id, value);
+                    if let Some(x) = symbol_table::get(*id) {
+                        context.values.push(Value {
+                            name: x.token.to_string(),
+                            kind: ValueKind::Generic,
+                            value,
+                        });
+                    }
+                }
+                GenericArg::Symbol(x) => {
+                    context.generics.insert(*id, x);
+                }
+            }
+        }
+
+        for x in parameters {
+            let property = x.property();
+            let ParameterValue::Expression(ref expression) = property.value else {
+                continue;
+            };
+            let name = x.name.to_string();
+            let value = if let Some(x) = overrides.get(&name) {
+                *x
+            } else {
+                let width = context.evaluator.type_width(property.r#type.clone());
+                if let Some(width) = width {
+                    context.evaluator.context_width.push(width);
+                }
+                let value = context.evaluator.expression(expression);
+                if width.is_some() {
+                    context.evaluator.context_width.pop();
+                }
+                value
+            };
+            context.evaluator.values.insert(x.symbol, value);
+            context.values.push(Value {
+                name,
+                kind: match property.kind {
+                    ParameterKind::Param => ValueKind::Param,
+                    ParameterKind::Const => ValueKind::Const,
+                },
+                value,
+            });
+        }
+
+        if depth < MAX_DEPTH {
+            if let Some(items) = self.bodies.get(&symbol.id) {
+                self.generate_items(&mut context, items, "", depth);
+            }
+        }
+
+        Instance {
+            name,
+            module: symbol.token.to_string(),
+            symbol: Some(symbol.id),
+            values: context.values,
+            children: context.children,
+            range,
+        }
+    }
+
+    fn generate_items(
+        &self,
+        context: &mut Context,
+        items: &[GenerateItem],
+        prefix: &str,
+        depth: usize,
+    ) {
+        for item in items {
+            match item {
+                GenerateItem::ConstDeclaration(x) => {
+                    self.const_declaration(context, &x.const_declaration, prefix)
+                }
+                GenerateItem::InstDeclaration(x) => {
+                    self.inst_declaration(context, &x.inst_declaration, prefix, depth)
+                }
+                GenerateItem::GenerateIfDeclaration(x) => {
+                    self.generate_if_declaration(context, &x.generate_if_declaration, prefix, depth)
+                }
+                GenerateItem::GenerateForDeclaration(x) => self.generate_for_declaration(
+                    context,
+                    &x.generate_for_declaration,
+                    prefix,
+                    depth,
+                ),
+                GenerateItem::GenerateBlockDeclaration(x) => {
+                    let x = &x.generate_block_declaration.generate_named_block;
+                    let prefix = format!("{}{}.", prefix, x.identifier.identifier_token);
+                    self.generate_named_block(context, x, &prefix, depth);
+                }
+                GenerateItem::UnsafeBlock(x) => {
+                    let mut items = Vec::new();
+                    for x in &x.unsafe_block.unsafe_block_list {
+                        let mut x: Vec<GenerateItem> = x.generate_group.as_ref().into();
+                        items.append(&mut x);
+                    }
+                    self.generate_items(context, &items, prefix, depth);
+                }
+                _ => (),
+            }
+        }
+    }
+
+    fn generate_named_block(
+        &self,
+        context: &mut Context,
+        arg: &GenerateNamedBlock,
+        prefix: &str,
+        depth: usize,
+    ) {
+        let mut items = Vec::new();
+        for x in &arg.generate_named_block_list {
+            let mut x: Vec<GenerateItem> = x.generate_group.as_ref().into();
+            items.append(&mut x);
+        }
+        self.generate_items(context, &items, prefix, depth);
+    }
+
+    fn const_declaration(&self, context: &mut Context, arg: &ConstDeclaration, prefix: &str) {
+        let ConstDeclarationGroup::ArrayTypeEquExpression(ref x) = *arg.const_declaration_group
+        else {
+            return;
+        };
+        let value = context.evaluator.expression(&x.expression);
+        if let Ok(symbol) = symbol_table::resolve(arg.identifier.as_ref()) {
+            context.evaluator.values.insert(symbol.found.id, value);
+        }
+        context.values.push(Value {
+            name: format!("{}{}", prefix, arg.identifier.identifier_token),
+            kind: ValueKind::Const,
+            value,
+        });
+    }
+
+    fn generate_if_declaration(
+        &self,
+        context: &mut Context,
+        arg: &GenerateIfDeclaration,
+        prefix: &str,
+        depth: usize,
+    ) {
+        let label = arg
+            .generate_named_block
+            .identifier
+            .identifier_token
+            .to_string();
+        if is_true(context.evaluator.expression(&arg.expression)) {
+            let prefix = format!("{}{}.", prefix, label);
+            self.generate_named_block(context, &arg.generate_named_block, &prefix, depth);
+            return;
+        }
+
+        let mut blocks: Vec<_> = arg
+            .generate_if_declaration_list
+            .iter()
+            .map(|x| {
+                (
+                    Some(x.expression.as_ref()),
+                    x.generate_optional_named_block.as_ref(),
+                )
+            })
+            .collect();
+        if let Some(ref x) = arg.generate_if_declaration_opt {
+            blocks.push((None, x.generate_optional_named_block.as_ref()));
+        }
+
+        for (cond, block) in blocks {
+            if cond.is_some_and(|x| !is_true(context.evaluator.expression(x))) {
+                continue;
+            }
+
+            // unnamed block inherits the label of the first block
+            let label = if let Some(ref x) = block.generate_optional_named_block_opt {
+                x.identifier.identifier_token.to_string()
+            } else {
+                label
+            };
+            let prefix = format!("{}{}.", prefix, label);
+            let mut items = Vec::new();
+            for x in &block.generate_optional_named_block_list {
+                let mut x: Vec<GenerateItem> = x.generate_group.as_ref().into();
+                items.append(&mut x);
+            }
+            self.generate_items(context, &items, &prefix, depth);
+            break;
+        }
+    }
+
+    fn generate_for_declaration(
+        &self,
+        context: &mut Context,
+        arg: &GenerateForDeclaration,
+        prefix: &str,
+        depth: usize,
+    ) {
+        let Evaluated::Fixed { value: beg, .. } =
+            context.evaluator.expression(&arg.range.expression)
+        else {
+            return;
+        };
+        let end = if let Some(ref x) = arg.range.range_opt {
+            let Evaluated::Fixed { value, .. } = context.evaluator.expression(&x.expression) else {
+                return;
+            };
+            match *x.range_operator {
+                RangeOperator::DotDot(_) => value,
+                RangeOperator::DotDotEqu(_) => value + 1,
+            }
+        } else {
+            beg + 1
+        };
+        let step = if let Some(ref x) = arg.generate_for_declaration_opt {
+            let Evaluated::Fixed { value, .. } = context.evaluator.expression(&x.expression) else {
+                return;
+            };
+            let operator = x.assignment_operator.assignment_operator_token.to_string();
+            (operator, value)
+        } else {
+            ("+=".to_string(), 1)
+        };
+
+        // genvar is declared in the namespace of the generate block
+        let label = &arg.generate_named_block.identifier.identifier_token.token;
+        let genvar = namespace_table::get(label.id).and_then(|mut x| {
+            x.push(label.text);
+            let token = &arg.identifier.identifier_token.token;
+            symbol_table::resolve((token, &x)).ok().map(|x| x.found.id)
+        });
+        let label = label.to_string();
+
+        let mut i = beg;
+        let mut iteration = 0;
+        while i < end && iteration < MAX_ITERATION {
+            if let Some(genvar) = genvar {
+                context.evaluator.values.insert(
+                    genvar,
+                    Evaluated::Fixed {
+                        width: 32,
+                        value: i,
+                    },
+                );
+            }
+            let prefix = format!("{}{}[{}].", prefix, label, i);
+            self.generate_named_block(context, &arg.generate_named_block, &prefix, depth);
+
+            let next = match step.0.as_str() {
+                "+=" => i.checked_add(step.1),
+                "-=" => i.checked_sub(step.1),
+                "*=" => i.checked_mul(step.1),
+                "/=" => i.checked_div(step.1),
+                "<<=" => u32::try_from(step.1).ok().and_then(|x| i.checked_shl(x)),
+                ">>=" => u32::try_from(step.1).ok().and_then(|x| i.checked_shr(x)),
+                _ => None,
+            };
+            match next {
+                Some(x) if x != i => i = x,
+                _ => break,
+            }
+            iteration += 1;
+        }
+    }
+
+    fn inst_declaration(
+        &self,
+        context: &mut Context,
+        arg: &InstDeclaration,
+        prefix: &str,
+        depth: usize,
+    ) {
+        let mut name = format!("{}{}", prefix, arg.identifier.identifier_token);
+        if let Some(ref x) = arg.inst_declaration_opt {
+            let mut dims = vec![x.array.expression.as_ref()];
+            dims.extend(x.array.array_list.iter().map(|x| x.expression.as_ref()));
+            for x in dims {
+                match context.evaluator.expression(x) {
+                    Evaluated::Fixed { value, .. } => name.push_str(&format!("[{}]", value)),
+                    _ => name.push_str("[?]"),
+                }
+            }
+        }
+        let range: TokenRange = arg.identifier.as_ref().into();
+
+        let mut overrides = HashMap::new();
+        if let Some(ref x) = arg.inst_declaration_opt0 {
+            if let Some(ref x) = x.inst_parameter.inst_parameter_opt {
+                let items: Vec<InstParameterItem> = x.inst_parameter_list.as_ref().into();
+                for x in &items {
+                    let value = context.evaluator.inst_parameter_item(x);
+                    overrides.insert(x.identifier.identifier_token.to_string(), value);
+                }
+            }
+        }
+
+        let generic_args: Vec<Option<WithGenericArgument>> = arg.scoped_identifier.as_ref().into();
+        let mut args = Vec::new();
+        if let Some(Some(x)) = generic_args.last() {
+            if let Some(ref x) = x.with_generic_argument_opt {
+                let items: Vec<WithGenericArgumentItem> =
+                    x.with_generic_argument_list.as_ref().into();
+                for x in &items {
+                    args.push(self.generic_arg(context, x));
+                }
+            }
+        }
+
+        let symbol = symbol_table::resolve(arg.scoped_identifier.as_ref())
+            .ok()
+            .and_then(|x| self.instantiated(context, x.found));
+
+        let child = if let Some(symbol) = symbol {
+            self.instance(name, &symbol, range, overrides, args, depth + 1)
+        } else {
+            let path: GenericSymbolPath = arg.scoped_identifier.as_ref().into();
+            Instance {
+                name,
+                module: path.to_string(),
+                symbol: None,
+                values: Vec::new(),
+                children: Vec::new(),
+                range,
+            }
+        };
+        context.children.push(child);
+    }
+
+    /// Module or interface which is actually instantiated
+    fn instantiated(&self, context: &Context, symbol: Symbol) -> Option<Symbol> {
+        match &symbol.kind {
+            SymbolKind::Module(_) | SymbolKind::Interface(_) => Some(symbol),
+            SymbolKind::GenericInstance(x) => symbol_table::get(x.base),
+            SymbolKind::GenericParameter(_) => context
+                .generics
+                .get(&symbol.id)
+                .and_then(|x| symbol_table::get(*x)),
+            _ => None,
+        }
+    }
+
+    fn default_generic_arg(&self, context: &Context, id: SymbolId) -> GenericArg {
+        let default = symbol_table::get(id).and_then(|symbol| match &symbol.kind {
+            SymbolKind::GenericParameter(x) => x
+                .default_value
+                .clone()
+                .map(|x| (x, symbol.namespace.clone())),
+            _ => None,
+        });
+        let Some((path, namespace)) = default else {
+            return GenericArg::Value(Evaluated::Unknown);
+        };
+
+        match path.kind {
+            GenericSymbolPathKind::Identifier => {
+                let symbol = symbol_table::resolve((&path.generic_path(), &namespace))
+                    .ok()
+                    .and_then(|x| self.instantiated(context, x.found));
+                match symbol {
+                    Some(x) => GenericArg::Symbol(x.id),
+                    None => GenericArg::Value(Evaluated::Unknown),
+                }
+            }
+            GenericSymbolPathKind::IntegerBaseLess => {
+                let text = path.paths[0].base.to_string().replace('_', "");
+                match text.parse() {
+                    Ok(value) => GenericArg::Value(Evaluated::Fixed { width: 32, value }),
+                    Err(_) => GenericArg::Value(Evaluated::Unknown),
+                }
+            }
+            _ => GenericArg::Value(Evaluated::Unknown),
+        }
+    }
+
+    fn generic_arg(&self, context: &mut Context, arg: &WithGenericArgumentItem) -> GenericArg {
+        match arg {
+            WithGenericArgumentItem::Number(x) => {
+                GenericArg::Value(context.evaluator.number(&x.number))
+            }
+            WithGenericArgumentItem::ScopedIdentifier(x) => {
+                let symbol = symbol_table::resolve(x.scoped_identifier.as_ref())
+                    .ok()
+                    .and_then(|x| self.instantiated(context, x.found));
+                if let Some(symbol) = symbol {
+                    GenericArg::Symbol(symbol.id)
+                } else {
+                    GenericArg::Value(context.evaluator.scoped_identifier(&x.scoped_identifier))
+                }
+            }
+        }
+    }
+}
+
+fn is_true(x: Evaluated) -> bool {
+    matches!(x, Evaluated::Fixed { value, .. } if value != 0)
+}
@@ -1,10 +1,19 @@
-use crate::symbol::{Type, TypeKind};
+use crate::function_table;
+use crate::symbol::{Direction, SymbolId, SymbolKind, Type, TypeKind, TypeModifier};
 use crate::symbol_table::{self, ResolveError, ResolveResult};
+use std::collections::HashMap;
 use veryl_parser::veryl_grammar_trait::*;
 
+/// Upper limit of nested function calls to stop recursive evaluation
+const MAX_CALL_DEPTH: usize = 64;
+
+/// Upper limit of iterations of a for statement in a function
+const MAX_ITERATION: usize = 65536;
+
 #[derive(Clone, Copy, Debug)]
 pub enum Evaluated {
     Fixed { width: usize, value: isize },
+    Real { value: f64 },
     Variable { width: usize },
     Clock,
     ClockPosedge,
@@ -21,7 +30,75 @@ pub enum Evaluated {
 
 impl Evaluated {
     fn is_known_static(&self) -> bool {
-        matches!(self, Evaluated::Fixed { .. } | Evaluated::UnknownStatic)
+        matches!(
+            self,
+            Evaluated::Fixed { .. } | Evaluated::Real { .. } | Evaluated::UnknownStatic
+        )
+    }
+
+    fn is_constant(&self) -> bool {
+        matches!(self, Evaluated::Fixed { .. } | Evaluated::Real { .. })
+    }
+
+    fn to_real(self) -> Option<f64> {
+        match self {
+            Evaluated::Fixed { value, .. } => Some(value as f64),
+            Evaluated::Real { value } => Some(value),
+            _ => None,
+        }
+    }
+
+    fn to_fixed(self) -> Option<isize> {
+        match self {
+            Evaluated::Fixed { value, .. } => Some(value),
+            _ => None,
+        }
+    }
+
+    fn real_binary_op(left: Evaluated, right: Evaluated, operator: &str) -> Evaluated {
+        let (Some(x), Some(y)) = (left.to_real(), right.to_real()) else {
+            return Evaluated::Unknown;
+        };
+        let value = match operator {
+            "**" => x.powf(y),
+            "/" => x / y,
+            "*" => x * y,
+            "+" => x + y,
+            "-" => x - y,
+            _ => {
+                let value = match operator {
+                    "<=" => x <= y,
+                    ">=" => x >= y,
+                    "<:" | "<" => x < y,
+                    ">:" | ">" => x > y,
+                    "==" | "===" => x == y,
+                    "!=" | "!==" => x != y,
+                    "&&" => x != 0.0 && y != 0.0,
+                    "||" => x != 0.0 || y != 0.0,
+                    _ => return Evaluated::Unknown,
+                };
+                return Evaluated::Fixed {
+                    width: 1,
+                    value: value as isize,
+                };
+            }
+        };
+        Evaluated::Real { value }
+    }
+
+    fn real_unary_op(left: Evaluated, operator: &str) -> Evaluated {
+        let Some(x) = left.to_real() else {
+            return Evaluated::Unknown;
+        };
+        match operator {
+            "+" => Evaluated::Real { value: x },
+            "-" => Evaluated::Real { value: -x },
+            "!" => Evaluated::Fixed {
+                width: 1,
+                value: (x == 0.0) as isize,
+            },
+            _ => Evaluated::Unknown,
+        }
     }
 
     pub fn is_clock(&self) -> bool {
@@ -303,9 +380,19 @@ impl Evaluated {
     }
 }
 
+/// Control flow after executing a statement in a function
+enum Flow {
+    Next,
+    Break,
+    Return(Evaluated),
+}
+
 #[derive(Default)]
 pub struct Evaluator {
     pub context_width: Vec<usize>,
+    /// Values bound to symbols by elaboration, which take priority over declarations
+    pub values: HashMap<SymbolId, Evaluated>,
+    call_depth: usize,
 }
 
 impl Evaluator {
@@ -314,6 +401,9 @@ impl Evaluator {
     }
 
     fn binary_operator(&mut self, operator: &str, left: Evaluated, right: Evaluated) -> Evaluated {
+        if matches!(left, Evaluated::Real { .. }) || matches!(right, Evaluated::Real { .. }) {
+            return Evaluated::real_binary_op(left, right, operator);
+        }
         match operator {
             "**" => left.pow(right),
             "/" => left.div(right),
@@ -327,6 +417,8 @@ impl Evaluator {
             ">>" => left.unsigned_shr(right),
             "<=" => left.le(right),
             ">=" => left.ge(right),
+            "<:" => left.lt(right),
+            ">:" => left.gt(right),
             "<" => left.lt(right),
             ">" => left.gt(right),
             "===" => left.eq(right),
@@ -347,6 +439,9 @@ impl Evaluator {
     }
 
     fn unary_operator(&mut self, operator: &str, left: Evaluated) -> Evaluated {
+        if matches!(left, Evaluated::Real { .. }) {
+            return Evaluated::real_unary_op(left, operator);
+        }
         match operator {
             "+" => left.plus(),
             "-" => left.minus(),
@@ -401,12 +496,22 @@ impl Evaluator {
         }
     }
 
-    fn exponent(&mut self, _arg: &Exponent) -> Evaluated {
-        Evaluated::Unknown
+    fn exponent(&mut self, arg: &Exponent) -> Evaluated {
+        let text = arg.exponent_token.to_string().replace('_', "");
+        if let Ok(value) = text.parse() {
+            Evaluated::Real { value }
+        } else {
+            Evaluated::Unknown
+        }
     }
 
-    fn fixed_point(&mut self, _arg: &FixedPoint) -> Evaluated {
-        Evaluated::Unknown
+    fn fixed_point(&mut self, arg: &FixedPoint) -> Evaluated {
+        let text = arg.fixed_point_token.to_string().replace('_', "");
+        if let Ok(value) = text.parse() {
+            Evaluated::Real { value }
+        } else {
+            Evaluated::Unknown
+        }
     }
 
     fn based(&mut self, arg: &Based) -> Evaluated {
@@ -475,7 +580,7 @@ impl Evaluator {
         }
     }
 
-    fn number(&mut self, arg: &Number) -> Evaluated {
+    pub fn number(&mut self, arg: &Number) -> Evaluated {
         match arg {
             Number::IntegralNumber(x) => self.integral_number(&x.integral_number),
             Number::RealNumber(x) => self.real_number(&x.real_number),
@@ -656,6 +761,9 @@ impl Evaluator {
 
     fn identifier_helper(&mut self, symbol: Result<ResolveResult, ResolveError>) -> Evaluated {
         if let Ok(symbol) = symbol {
+            if let Some(x) = self.values.get(&symbol.found.id) {
+                return *x;
+            }
             symbol.found.evaluate()
         } else {
             Evaluated::Unknown
@@ -672,13 +780,377 @@ impl Evaluator {
         self.identifier_helper(symbol)
     }
 
+    pub fn scoped_identifier(&mut self, arg: &ScopedIdentifier) -> Evaluated {
+        let symbol = symbol_table::resolve(arg);
+        self.identifier_helper(symbol)
+    }
+
+    fn function_call(&mut self, name: &ExpressionIdentifier, arg: &FunctionCall) -> Evaluated {
+        let mut args = Vec::new();
+        if let Some(ref x) = arg.function_call_opt {
+            let list = &x.argument_list;
+            args.push(self.expression(&list.argument_item.expression));
+            for x in &list.argument_list_list {
+                args.push(self.expression(&x.argument_item.expression));
+            }
+        }
+
+        if let ScopedIdentifierGroup::DollarIdentifier(ref x) =
+            name.scoped_identifier.scoped_identifier_group.as_ref()
+        {
+            let name = x.dollar_identifier.dollar_identifier_token.to_string();
+            self.system_function(&name, &args)
+        } else {
+            self.user_function(name, &args)
+        }
+    }
+
+    fn system_function(&mut self, name: &str, args: &[Evaluated]) -> Evaluated {
+        let Some(arg0) = args.first().copied() else {
+            return Evaluated::Unknown;
+        };
+        let real = |f: fn(f64) -> f64| match arg0.to_real() {
+            Some(x) => Evaluated::Real { value: f(x) },
+            None => Evaluated::Unknown,
+        };
+
+        match name {
+            "$clog2" => Evaluated::unary_op(
+                arg0,
+                |_| 32,
+                |x| {
+                    let x = u64::try_from(x).ok()?;
+                    let ret = if x <= 1 {
+                        0
+                    } else {
+                        64 - (x - 1).leading_zeros()
+                    };
+                    Some(ret as isize)
+                },
+            ),
+            "$signed" | "$unsigned" => arg0,
+            "$bits" => match arg0 {
+                Evaluated::Fixed { width, .. } | Evaluated::Variable { width } => {
+                    Evaluated::Fixed {
+                        width: 32,
+                        value: width as isize,
+                    }
+                }
+                _ => Evaluated::Unknown,
+            },
+            "$itor" => real(|x| x),
+            "$rtoi" => match arg0.to_real() {
+                Some(x) => Evaluated::Fixed {
+                    width: 32,
+                    value: x.trunc() as isize,
+                },
+                None => Evaluated::Unknown,
+            },
+            "$ln" => real(f64::ln),
+            "$log10" => real(f64::log10),
+            "$exp" => real(f64::exp),
+            "$sqrt" => real(f64::sqrt),
+            "$floor" => real(f64::floor),
+            "$ceil" => real(f64::ceil),
+            "$pow" => match args.get(1) {
+                Some(arg1) => Evaluated::real_binary_op(arg0, *arg1, "**"),
+                None => Evaluated::Unknown,
+            },
+            _ => Evaluated::Unknown,
+        }
+    }
+
+    /// Execute the function body if all arguments are known
+    fn user_function(&mut self, name: &ExpressionIdentifier, args: &[Evaluated]) -> Evaluated {
+        let Ok(symbol) = symbol_table::resolve(name) else {
+            return Evaluated::Unknown;
+        };
+        let SymbolKind::Function(ref property) = symbol.found.kind else {
+            return Evaluated::Unknown;
+        };
+        if self.call_depth >= MAX_CALL_DEPTH
+            || !property.generic_parameters.is_empty()
+            || property.ports.len() != args.len()
+            || !args.iter().all(|x| x.is_constant())
+        {
+            return Evaluated::Unknown;
+        }
+        let Some(declaration) = function_table::get(symbol.found.token.id) else {
+            return Evaluated::Unknown;
+        };
+
+        let input = property.ports.iter().all(|x| {
+            symbol_table::get(x.symbol).is_some_and(|x| match x.kind {
+                SymbolKind::Port(x) => x.direction == Direction::Input,
+                _ => false,
+            })
+        });
+        if !input {
+            return Evaluated::Unknown;
+        }
+
+        let values = self.values.clone();
+        for (port, arg) in property.ports.iter().zip(args) {
+            self.values.insert(port.symbol, *arg);
+        }
+
+        let width = property.ret.clone().and_then(|x| self.type_width(x));
+        let signed = property.ret.as_ref().is_some_and(|x| {
+            matches!(x.kind, TypeKind::I32 | TypeKind::I64)
+                || x.modifier.contains(&TypeModifier::Signed)
+        });
+        self.call_depth += 1;
+        let flow = self.statement_block(&declaration.statement_block);
+        self.call_depth -= 1;
+        self.values = values;
+
+        match (flow, width) {
+            (Some(Flow::Return(Evaluated::Fixed { value, .. })), Some(width)) => Evaluated::Fixed {
+                width,
+                value: truncate(value, width, signed),
+            },
+            (Some(Flow::Return(x)), _) if x.is_constant() => x,
+            _ => Evaluated::Unknown,
+        }
+    }
+
+    /// `None` means that the statements can't be executed statically
+    fn statement_block(&mut self, arg: &StatementBlock) -> Option<Flow> {
+        for x in &arg.statement_block_list {
+            let flow = match x.statement_block_item.as_ref() {
+                StatementBlockItem::VarDeclaration(_) => Flow::Next,
+                StatementBlockItem::LetStatement(x) => self.let_statement(&x.let_statement)?,
+                StatementBlockItem::Statement(x) => self.statement(&x.statement)?,
+            };
+            if !matches!(flow, Flow::Next) {
+                return Some(flow);
+            }
+        }
+        Some(Flow::Next)
+    }
+
+    fn statement(&mut self, arg: &Statement) -> Option<Flow> {
+        match arg {
+            Statement::IdentifierStatement(x) => {
+                let x = &x.identifier_statement;
+                match x.identifier_statement_group.as_ref() {
+                    IdentifierStatementGroup::Assignment(y) => {
+                        self.assignment(&x.expression_identifier, &y.assignment)
+                    }
+                    IdentifierStatementGroup::FunctionCall(_) => None,
+                }
+            }
+            Statement::IfStatement(x) => self.if_statement(&x.if_statement),
+            Statement::IfResetStatement(_) => None,
+            Statement::ReturnStatement(x) => {
+                let value = self.expression(&x.return_statement.expression);
+                Some(Flow::Return(value))
+            }
+            Statement::BreakStatement(_) => Some(Flow::Break),
+            Statement::ForStatement(x) => self.for_statement(&x.for_statement),
+            Statement::CaseStatement(x) => self.case_statement(&x.case_statement),
+            Statement::SwitchStatement(x) => self.switch_statement(&x.switch_statement),
+        }
+    }
+
+    fn let_statement(&mut self, arg: &LetStatement) -> Option<Flow> {
+        let symbol = symbol_table::resolve(arg.identifier.as_ref()).ok()?;
+        let value = self.variable_value(&symbol.found.kind, &arg.expression);
+        self.values.insert(symbol.found.id, value);
+        Some(Flow::Next)
+    }
+
+    fn assignment(&mut self, dst: &ExpressionIdentifier, arg: &Assignment) -> Option<Flow> {
+        // Assignment to a part of variable is not supported
+        if !dst.expression_identifier_list.is_empty() || !dst.expression_identifier_list0.is_empty()
+        {
+            return None;
+        }
+        let symbol = symbol_table::resolve(dst).ok()?;
+        let value = self.variable_value(&symbol.found.kind, &arg.expression);
+        let value = match arg.assignment_group.as_ref() {
+            AssignmentGroup::Equ(_) => value,
+            AssignmentGroup::AssignmentOperator(x) => {
+                let operator = x.assignment_operator.assignment_operator_token.to_string();
+                let current = self.identifier_helper(Ok(symbol.clone()));
+                self.binary_operator(&operator[0..operator.len() - 1], current, value)
+            }
+        };
+        self.values.insert(symbol.found.id, value);
+        Some(Flow::Next)
+    }
+
+    /// Evaluate the expression with the width of the assigned variable as context
+    fn variable_value(&mut self, kind: &SymbolKind, arg: &Expression) -> Evaluated {
+        let width = match kind {
+            SymbolKind::Variable(x) => self.type_width(x.r#type.clone()),
+            _ => None,
+        };
+        if let Some(width) = width {
+            self.context_width.push(width);
+        }
+        let value = self.expression(arg);
+        if width.is_some() {
+            self.context_width.pop();
+        }
+        match (value, width) {
+            (Evaluated::Fixed { value, .. }, Some(width)) => Evaluated::Fixed { width, value },
+            _ => value,
+        }
+    }
+
+    fn if_statement(&mut self, arg: &IfStatement) -> Option<Flow> {
+        if is_true(self.expression(&arg.expression))? {
+            return self.statement_block(&arg.statement_block);
+        }
+        for x in &arg.if_statement_list {
+            if is_true(self.expression(&x.expression))? {
+                return self.statement_block(&x.statement_block);
+            }
+        }
+        if let Some(ref x) = arg.if_statement_opt {
+            self.statement_block(&x.statement_block)
+        } else {
+            Some(Flow::Next)
+        }
+    }
+
+    fn for_statement(&mut self, arg: &ForStatement) -> Option<Flow> {
+        let symbol = symbol_table::resolve(arg.identifier.as_ref()).ok()?;
+        let width = match symbol.found.kind {
+            SymbolKind::Variable(ref x) => self.type_width(x.r#type.clone()).unwrap_or(32),
+            _ => 32,
+        };
+        let beg = self.expression(&arg.range.expression).to_fixed()?;
+        let (end, inclusive) = if let Some(ref x) = arg.range.range_opt {
+            let inclusive = matches!(*x.range_operator, RangeOperator::DotDotEqu(_));
+            (self.expression(&x.expression).to_fixed()?, inclusive)
+        } else {
+            (beg, true)
+        };
+
+        let mut i = beg;
+        for _ in 0..MAX_ITERATION {
+            if i > end || (i == end && !inclusive) {
+                return Some(Flow::Next);
+            }
+            let index = Evaluated::Fixed { width, value: i };
+            self.values.insert(symbol.found.id, index);
+            match self.statement_block(&arg.statement_block)? {
+                Flow::Next => (),
+                Flow::Break => return Some(Flow::Next),
+                x => return Some(x),
+            }
+            let next = if let Some(ref x) = arg.for_statement_opt {
+                let operator = x.assignment_operator.assignment_operator_token.to_string();
+                let step = self.expression(&x.expression);
+                self.binary_operator(&operator[0..operator.len() - 1], index, step)
+            } else {
+                index.add(Evaluated::Fixed { width, value: 1 })
+            };
+            let next = next.to_fixed()?;
+            // stop a step which doesn't progress like `step *= 1`
+            if next <= i {
+                return None;
+            }
+            i = next;
+        }
+        None
+    }
+
+    fn case_statement(&mut self, arg: &CaseStatement) -> Option<Flow> {
+        let value = self.expression(&arg.expression);
+        for x in &arg.case_statement_list {
+            let x = &x.case_item;
+            let matched = match x.case_item_group.as_ref() {
+                CaseItemGroup::CaseCondition(y) => self.case_condition(value, &y.case_condition)?,
+                CaseItemGroup::Defaul(_) => true,
+            };
+            if matched {
+                return match x.case_item_group0.as_ref() {
+                    CaseItemGroup0::Statement(y) => self.statement(&y.statement),
+                    CaseItemGroup0::StatementBlock(y) => self.statement_block(&y.statement_block),
+                };
+            }
+        }
+        Some(Flow::Next)
+    }
+
+    fn switch_statement(&mut self, arg: &SwitchStatement) -> Option<Flow> {
+        for x in &arg.switch_statement_list {
+            let x = &x.switch_item;
+            let matched = match x.switch_item_group.as_ref() {
+                SwitchItemGroup::SwitchCondition(y) => {
+                    self.switch_condition(&y.switch_condition)?
+                }
+                SwitchItemGroup::Defaul(_) => true,
+            };
+            if matched {
+                return match x.switch_item_group0.as_ref() {
+                    SwitchItemGroup0::Statement(y) => self.statement(&y.statement),
+                    SwitchItemGroup0::StatementBlock(y) => self.statement_block(&y.statement_block),
+                };
+            }
+        }
+        Some(Flow::Next)
+    }
+
+    fn case_condition(&mut self, value: Evaluated, arg: &CaseCondition) -> Option<bool> {
+        if self.range_contains(value, &arg.range_item.range)? {
+            return Some(true);
+        }
+        for x in &arg.case_condition_list {
+            if self.range_contains(value, &x.range_item.range)? {
+                return Some(true);
+            }
+        }
+        Some(false)
+    }
+
+    fn switch_condition(&mut self, arg: &SwitchCondition) -> Option<bool> {
+        if is_true(self.expression(&arg.expression))? {
+            return Some(true);
+        }
+        for x in &arg.switch_condition_list {
+            if is_true(self.expression(&x.expression))? {
+                return Some(true);
+            }
+        }
+        Some(false)
+    }
+
+    fn range_list_contains(&mut self, value: Evaluated, arg: &RangeList) -> Option<bool> {
+        if self.range_contains(value, &arg.range_item.range)? {
+            return Some(true);
+        }
+        for x in &arg.range_list_list {
+            if self.range_contains(value, &x.range_item.range)? {
+                return Some(true);
+            }
+        }
+        Some(false)
+    }
+
+    fn range_contains(&mut self, value: Evaluated, arg: &Range) -> Option<bool> {
+        let value = value.to_fixed()?;
+        let beg = self.expression(&arg.expression).to_fixed()?;
+        if let Some(ref x) = arg.range_opt {
+            let end = self.expression(&x.expression).to_fixed()?;
+            match *x.range_operator {
+                RangeOperator::DotDot(_) => Some(beg <= value && value < end),
+                RangeOperator::DotDotEqu(_) => Some(beg <= value && value <= end),
+            }
+        } else {
+            Some(value == beg)
+        }
+    }
+
     fn factor(&mut self, arg: &Factor) -> Evaluated {
         match arg {
             Factor::Number(x) => self.number(&x.number),
             Factor::ExpressionIdentifierFactorOpt(x) => {
-                if x.factor_opt.is_some() {
-                    // Function call
-                    Evaluated::Unknown
+                if let Some(ref y) = x.factor_opt {
+                    self.function_call(&x.expression_identifier, &y.function_call)
                 } else {
                     // Identifier
                     self.expression_identifier(x.expression_identifier.as_ref())
@@ -696,44 +1168,91 @@ impl Evaluator {
             Factor::SwitchExpression(x) => self.switch_expression(&x.switch_expression),
             Factor::StringLiteral(_) => Evaluated::Unknown,
             Factor::FactorGroup(_) => Evaluated::Unknown,
-            Factor::InsideExpression(_) => Evaluated::Unknown,
-            Factor::OutsideExpression(_) => Evaluated::Unknown,
+            Factor::InsideExpression(x) => {
+                let x = &x.inside_expression;
+                let value = self.expression(&x.expression);
+                match self.range_list_contains(value, &x.range_list) {
+                    Some(x) => Evaluated::Fixed {
+                        width: 1,
+                        value: x as isize,
+                    },
+                    None => Evaluated::Unknown,
+                }
+            }
+            Factor::OutsideExpression(x) => {
+                let x = &x.outside_expression;
+                let value = self.expression(&x.expression);
+                match self.range_list_contains(value, &x.range_list) {
+                    Some(x) => Evaluated::Fixed {
+                        width: 1,
+                        value: !x as isize,
+                    },
+                    None => Evaluated::Unknown,
+                }
+            }
         }
     }
 
-    fn do_concatenation(&mut self, exp: Evaluated, rep: Evaluated) -> Evaluated {
-        match exp {
-            Evaluated::Fixed {
-                width: ewidth,
-                value: eval,
-            } => match rep {
+    fn do_concatenation(&mut self, upper: Evaluated, lower: Evaluated) -> Evaluated {
+        match (upper, lower) {
+            (
                 Evaluated::Fixed {
-                    width: rwidth,
-                    value: rval,
-                } => {
-                    let width = ewidth * rwidth;
-                    let mut value = eval;
-                    for _ in 0..rval {
-                        value <<= ewidth;
-                        value |= eval;
-                    }
-                    Evaluated::Fixed { width, value }
-                }
-                Evaluated::Variable { width: rwidth } => Evaluated::Variable {
-                    width: ewidth * rwidth,
+                    width: uwidth,
+                    value: uval,
                 },
-                Evaluated::UnknownStatic => Evaluated::UnknownStatic,
-                _ => Evaluated::Unknown,
-            },
-            Evaluated::Variable { width: ewidth } => match rep {
-                Evaluated::Fixed { width: rwidth, .. } | Evaluated::Variable { width: rwidth } => {
-                    Evaluated::Variable {
-                        width: ewidth * rwidth,
-                    }
+                Evaluated::Fixed {
+                    width: lwidth,
+                    value: lval,
+                },
+            ) => {
+                let width = uwidth + lwidth;
+                if width >= isize::BITS as usize {
+                    return Evaluated::Variable { width };
                 }
-                Evaluated::UnknownStatic => Evaluated::UnknownStatic,
-                _ => Evaluated::Unknown,
+                let value =
+                    (truncate(uval, uwidth, false) << lwidth) | truncate(lval, lwidth, false);
+                Evaluated::Fixed { width, value }
+            }
+            (
+                Evaluated::Fixed { width: uwidth, .. } | Evaluated::Variable { width: uwidth },
+                Evaluated::Fixed { width: lwidth, .. } | Evaluated::Variable { width: lwidth },
+            ) => Evaluated::Variable {
+                width: uwidth + lwidth,
             },
+            (Evaluated::UnknownStatic, x) | (x, Evaluated::UnknownStatic)
+                if x.is_known_static() =>
+            {
+                Evaluated::UnknownStatic
+            }
+            _ => Evaluated::Unknown,
+        }
+    }
+
+    fn do_repetition(&mut self, exp: Evaluated, rep: Evaluated) -> Evaluated {
+        let rep = match rep {
+            Evaluated::Fixed { value, .. } if value >= 0 => value as usize,
+            Evaluated::UnknownStatic if exp.is_known_static() => return Evaluated::UnknownStatic,
+            _ => return Evaluated::Unknown,
+        };
+        match exp {
+            Evaluated::Fixed { width, .. }
+                if width
+                    .checked_mul(rep)
+                    .is_some_and(|x| x < isize::BITS as usize)
+                    && rep < isize::BITS as usize =>
+            {
+                let mut ret = Evaluated::Fixed { width: 0, value: 0 };
+                for _ in 0..rep {
+                    ret = self.do_concatenation(ret, exp);
+                }
+                ret
+            }
+            Evaluated::Fixed { width, .. } | Evaluated::Variable { width } => {
+                match width.checked_mul(rep) {
+                    Some(width) => Evaluated::Variable { width },
+                    None => Evaluated::Unknown,
+                }
+            }
             Evaluated::UnknownStatic => Evaluated::UnknownStatic,
             _ => Evaluated::Unknown,
         }
@@ -743,7 +1262,7 @@ impl Evaluator {
         let e = self.expression(arg.expression.as_ref());
         if let Some(cio) = &arg.concatenation_item_opt {
             let c = self.expression(cio.expression.as_ref());
-            self.do_concatenation(e, c)
+            self.do_repetition(e, c)
         } else {
             e
         }
@@ -754,16 +1273,12 @@ impl Evaluator {
     }
 
     fn concatenation_list(&mut self, arg: &ConcatenationList) -> Evaluated {
-        let mut eval_vec = vec![];
-        eval_vec.push(self.concatenation_item(arg.concatenation_item.as_ref()));
+        let mut ret = self.concatenation_item(arg.concatenation_item.as_ref());
         for cll in arg.concatenation_list_list.iter() {
-            eval_vec.push(self.concatenation_list_list(cll));
+            let x = self.concatenation_list_list(cll);
+            ret = self.do_concatenation(ret, x);
         }
-        eval_vec.reverse();
-        let default_value = Evaluated::Fixed { width: 1, value: 0 };
-        eval_vec
-            .iter()
-            .fold(default_value, |acc, x| self.do_concatenation(acc, *x))
+        ret
     }
 
     fn array_literal_item_group_default_colon_expression(
@@ -783,8 +1298,13 @@ impl Evaluator {
             ArrayLiteralItemGroup::ExpressionArrayLiteralItemOpt(x) => {
                 let exp_eval = self.expression(x.expression.as_ref());
                 if let Some(alio) = &x.array_literal_item_opt {
+                    // Repeated items are elements of an unpacked array, not a packed value
                     let repeat_exp = self.expression(alio.expression.as_ref());
-                    self.do_concatenation(exp_eval, repeat_exp)
+                    if exp_eval.is_known_static() && repeat_exp.is_known_static() {
+                        Evaluated::UnknownStatic
+                    } else {
+                        Evaluated::Unknown
+                    }
                 } else {
                     exp_eval
                 }
@@ -824,15 +1344,107 @@ impl Evaluator {
         }
     }
 
-    fn if_expression(&mut self, _arg: &IfExpression) -> Evaluated {
-        Evaluated::Unknown
+    fn if_expression(&mut self, arg: &IfExpression) -> Evaluated {
+        match is_true(self.expression(&arg.expression)) {
+            Some(true) => return self.expression(&arg.expression0),
+            Some(false) => (),
+            None => return Evaluated::Unknown,
+        }
+        for x in &arg.if_expression_list {
+            match is_true(self.expression(&x.expression)) {
+                Some(true) => return self.expression(&x.expression0),
+                Some(false) => (),
+                None => return Evaluated::Unknown,
+            }
+        }
+        self.expression(&arg.expression1)
     }
 
-    fn case_expression(&mut self, _arg: &CaseExpression) -> Evaluated {
-        Evaluated::Unknown
+    fn case_expression(&mut self, arg: &CaseExpression) -> Evaluated {
+        let value = self.expression(&arg.expression);
+        match self.case_condition(value, &arg.case_condition) {
+            Some(true) => return self.expression(&arg.expression0),
+            Some(false) => (),
+            None => return Evaluated::Unknown,
+        }
+        for x in &arg.case_expression_list {
+            match self.case_condition(value, &x.case_condition) {
+                Some(true) => return self.expression(&x.expression),
+                Some(false) => (),
+                None => return Evaluated::Unknown,
+            }
+        }
+        self.expression(&arg.expression1)
     }
 
-    fn switch_expression(&mut self, _arg: &SwitchExpression) -> Evaluated {
-        Evaluated::Unknown
+    fn switch_expression(&mut self, arg: &SwitchExpression) -> Evaluated {
+        match self.switch_condition(&arg.switch_condition) {
+            Some(true) => return self.expression(&arg.expression),
+            Some(false) => (),
+            None => return Evaluated::Unknown,
+        }
+        for x in &arg.switch_expression_list {
+            match self.switch_condition(&x.switch_condition) {
+                Some(true) => return self.expression(&x.expression),
+                Some(false) => (),
+                None => return Evaluated::Unknown,
+            }
+        }
+        self.expression(&arg.expression0)
     }
 }
+
+/// `None` if the condition is not known statically
+fn is_true(x: Evaluated) -> Option<bool> {
+    match x {
+        Evaluated::Fixed { value, .. } => Some(value != 0),
+        Evaluated::Real { value } => Some(value != 0.0),
+        _ => None,
+    }
+}
+
+/// Fit the value into `width` bits, sign-extended if `signed`
+fn truncate(value: isize, width: usize, signed: bool) -> isize {
+    if width >= isize::BITS as usize {
+        return value;
+    }
+    if width == 0 {
+        return 0;
+    }
+    let value = value & ((1 << width) - 1);
+    if signed && (value >> (width - 1)) & 1 == 1 {
+        value - (1 << width)
+    } else {
+        value
+    }
+}
+
+/// Operand of equality operators if the expression has no operator with lower precedence
+pub(crate) fn expression05(arg: &Expression) -> Option<&Expression05> {
+    let x = &arg.expression01;
+    let x = (arg.expression_list.is_empty() && x.expression01_list.is_empty())
+        .then_some(&x.expression02)?;
+    let x = x.expression02_list.is_empty().then_some(&x.expression03)?;
+    let x = x.expression03_list.is_empty().then_some(&x.expression04)?;
+    x.expression04_list.is_empty().then_some(&x.expression05)
+}
+
+pub(crate) fn expression06_factor(arg: &Expression06) -> Option<&Factor> {
+    let x = arg
+        .expression06_list
+        .is_empty()
+        .then_some(&arg.expression07)?;
+    let x = x.expression07_list.is_empty().then_some(&x.expression08)?;
+    let x = x.expression08_list.is_empty().then_some(&x.expression09)?;
+    let x = x.expression09_list.is_empty().then_some(&x.expression10)?;
+    let x = x.expression10_list.is_empty().then_some(&x.expression11)?;
+    let x = x.expression11_opt.is_none().then_some(&x.expression12)?;
+    x.expression12_list.is_empty().then_some(&x.factor)
+}
+
+/// Factor if the expression has no operator
+pub(crate) fn factor(arg: &Expression) -> Option<&Factor> {
+    let x = expression05(arg)?;
+    let x = x.expression05_list.is_empty().then_some(&x.expression06)?;
+    expression06_factor(x)
+}
@@ -0,0 +1,280 @@
+use crate::analyzer_error::AnalyzerError;
+use crate::attribute::AllowItem;
+use crate::rename;
+use crate::symbol_table;
+use veryl_parser::resource_table::PathId;
+use veryl_parser::veryl_grammar_trait::{GenerateItem, Veryl, VerylGrammarTrait};
+use veryl_parser::veryl_token::{Token, TokenSource, VerylToken};
+use veryl_parser::veryl_walker::{Handler, HandlerPoint, VerylWalker};
+use veryl_parser::ParolError;
+
+/// Machine-applicable suggestion attached to AnalyzerError
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Fix {
+    pub title: String,
+    pub edits: Vec<FixEdit>,
+    /// Applied by `veryl fix`, otherwise it is only suggested through the language server
+    pub preferred: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FixEdit {
+    /// Insert text at the byte position
+    Insert {
+        path: PathId,
+        pos: u32,
+        text: String,
+    },
+    /// Rename the declared identifier and all references of it
+    Rename { token: Token, name: String },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TextEdit {
+    pub path: PathId,
+    pub beg: u32,
+    pub end: u32,
+    pub text: String,
+}
+
+impl Fix {
+    pub fn insert_before(title: &str, token: &Token, text: &str) -> Option<Self> {
+        Self::insert(title, token, token.pos, text)
+    }
+
+    pub fn insert_after(title: &str, token: &Token, text: &str) -> Option<Self> {
+        Self::insert(title, token, token.pos + token.length, text)
+    }
+
+    pub fn rename(title: &str, token: &Token, name: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            edits: vec![FixEdit::Rename {
+                token: *token,
+                name: name.to_string(),
+            }],
+            preferred: true,
+        }
+    }
+
+    /// Mark as a suggestion which is not applied by `veryl fix`
+    pub fn suggestion(mut self) -> Self {
+        self.preferred = false;
+        self
+    }
+
+    /// Append edits of the other fix
+    pub fn merge(mut self, other: Self) -> Self {
+        self.edits.extend(other.edits);
+        self
+    }
+
+    fn insert(title: &str, token: &Token, pos: u32, text: &str) -> Option<Self> {
+        if let TokenSource::File(path) = token.source {
+            Some(Self {
+                title: title.to_string(),
+                edits: vec![FixEdit::Insert {
+                    path,
+                    pos,
+                    text: text.to_string(),
+                }],
+                preferred: true,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Convert to text edits.
+    /// Rename is expanded in the same way as rename of the language server,
+    /// and the fix is rejected if the new name collides with other symbols.
+    pub fn text_edits(&self) -> Result<Vec<TextEdit>, String> {
+        let mut ret = Vec::new();
+        for edit in &self.edits {
+            match edit {
+                FixEdit::Insert { path, pos, text } => ret.push(TextEdit {
+                    path: *path,
+                    beg: *pos,
+                    end: *pos,
+                    text: text.clone(),
+                }),
+                FixEdit::Rename { token, name } => {
+                    let symbol = symbol_table::get_all()
+                        .into_iter()
+                        .find(|x| x.token.id == token.id)
+                        .ok_or_else(|| format!("\"{}\" is not declared", token.text))?;
+
+                    for x in rename::rename(&symbol, name)? {
+                        if let TokenSource::File(path) = x.token.source {
+                            ret.push(TextEdit {
+                                path,
+                                beg: x.token.pos,
+                                end: x.token.pos + x.token.length,
+                                text: x.text,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+        Ok(ret)
+    }
+}
+
+/// Suggest `#[allow(...)]` attribute to the module item which causes the lint-class error
+pub fn allow_attribute(error: &AnalyzerError, text: &str, veryl: &Veryl) -> Option<Fix> {
+    use miette::Diagnostic;
+
+    let code = error.code()?.to_string();
+    let item = [
+        AllowItem::MissingPort,
+        AllowItem::MissingResetStatement,
+        AllowItem::UnusedVariable,
+        AllowItem::ImplicitTruncation,
+        AllowItem::SignedZeroExtension,
+        AllowItem::MismatchPortWidth,
+        AllowItem::ResetDomainCrossing,
+        AllowItem::UnsynchronizedReset,
+    ]
+    .into_iter()
+    .find(|x| x.to_string() == code)?;
+    let offset = error.labels()?.next()?.offset() as u32;
+
+    let mut walker = AttributeTargetWalker {
+        target: AttributeTarget {
+            offset,
+            ..Default::default()
+        },
+    };
+    walker.veryl(veryl);
+    let token = walker.target.found?;
+
+    // keep indentation of the item
+    let pos = token.pos as usize;
+    let line_head = text.get(..pos)?.rsplit('\n').next()?;
+    let text = if line_head.chars().all(|x| x.is_ascii_whitespace()) {
+        format!("#[allow({item})]\n{line_head}")
+    } else {
+        format!("#[allow({item})] ")
+    };
+
+    Fix::insert_before(&format!("Add `#[allow({item})]`"), &token, &text).map(Fix::suggestion)
+}
+
+/// Find the innermost item which can be annotated by attribute
+#[derive(Default)]
+struct AttributeTarget {
+    point: HandlerPoint,
+    offset: u32,
+    stack: Vec<Option<Token>>,
+    last: Option<Token>,
+    found: Option<Token>,
+}
+
+impl Handler for AttributeTarget {
+    fn set_point(&mut self, p: HandlerPoint) {
+        self.point = p;
+    }
+}
+
+impl VerylGrammarTrait for AttributeTarget {
+    fn generate_item(&mut self, _arg: &GenerateItem) -> Result<(), ParolError> {
+        match self.point {
+            HandlerPoint::Before => self.stack.push(None),
+            HandlerPoint::After => {
+                // inner item is finished earlier than outer one
+                if let (Some(Some(beg)), Some(end)) = (self.stack.pop(), self.last) {
+                    let included = beg.pos <= self.offset && self.offset < end.pos + end.length;
+                    if included && self.found.is_none() {
+                        self.found = Some(beg);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+struct AttributeTargetWalker {
+    target: AttributeTarget,
+}
+
+impl VerylWalker for AttributeTargetWalker {
+    fn veryl_token(&mut self, arg: &VerylToken) {
+        if let Some(x @ None) = self.target.stack.last_mut() {
+            *x = Some(arg.token);
+        }
+        self.target.last = Some(arg.token);
+    }
+
+    fn get_handlers(&mut self) -> Option<Vec<&mut dyn Handler>> {
+        Some(vec![&mut self.target])
+    }
+}
+
+/// Apply text edits to the text.
+/// All edits should be for the text, and should not be overlapped.
+/// Edits violating it are returned as error without applying any edit.
+pub fn apply(text: &str, edits: &[TextEdit]) -> Result<String, Vec<TextEdit>> {
+    let mut edits: Vec<_> = edits.iter().collect();
+    edits.sort_by_key(|x| (x.beg, x.end));
+
+    let mut ret = String::new();
+    let mut invalid = Vec::new();
+    let mut pos = 0;
+    for edit in edits {
+        let beg = edit.beg as usize;
+        let end = edit.end as usize;
+        let in_bounds = beg <= end && text.is_char_boundary(beg) && text.is_char_boundary(end);
+        if beg < pos || !in_bounds {
+            invalid.push(edit.clone());
+            continue;
+        }
+        ret.push_str(&text[pos..beg]);
+        ret.push_str(&edit.text);
+        pos = end;
+    }
+    ret.push_str(&text[pos..]);
+
+    if invalid.is_empty() {
+        Ok(ret)
+    } else {
+        Err(invalid)
+    }
+}
+
+/// Select fixes which can be applied together.
+/// A fix is skipped if it is rejected,
+/// or if any of its edits overlaps with the edits of the already selected fixes.
+/// Insertions at the same position are merged into one edit.
+pub fn select(fixes: &[&Fix]) -> Vec<TextEdit> {
+    let mut ret: Vec<TextEdit> = Vec::new();
+    for fix in fixes {
+        let Ok(edits) = fix.text_edits() else {
+            continue;
+        };
+        let conflict = edits.iter().any(|x| {
+            ret.iter().any(|y| {
+                let insert = x.beg == x.end && y.beg == y.end;
+                x.path == y.path && (x.beg < y.end && y.beg < x.end || x.beg == y.beg && !insert)
+            })
+        });
+        if conflict {
+            continue;
+        }
+        for edit in edits {
+            let same = ret.iter_mut().find(|x| {
+                x.path == edit.path
+                    && x.beg == edit.beg
+                    && x.end == edit.beg
+                    && edit.beg == edit.end
+            });
+            if let Some(x) = same {
+                x.text.push_str(&edit.text);
+            } else {
+                ret.push(edit);
+            }
+        }
+    }
+    ret
+}
@@ -0,0 +1,757 @@
+use crate::evaluator::{expression05, expression06_factor, factor};
+use crate::symbol::{Symbol, SymbolId, SymbolKind, TypeKind};
+use crate::symbol_path::SymbolPath;
+use crate::symbol_table;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use veryl_parser::resource_table::StrId;
+use veryl_parser::veryl_grammar_trait::*;
+use veryl_parser::veryl_token::TokenRange;
+use veryl_parser::veryl_walker::{Handler, HandlerPoint, VerylWalker};
+use veryl_parser::ParolError;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FsmIssue {
+    /// State which can't be reached from the reset state
+    Unreachable(StrId),
+    /// State which has no transition to other states
+    NoExit(StrId),
+    /// `case` of the state register which has neither `default` nor all encodings
+    MissingDefault(TokenRange),
+}
+
+impl fmt::Display for FsmIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text = match self {
+            FsmIssue::Unreachable(x) => format!("state {x} is unreachable from reset state"),
+            FsmIssue::NoExit(x) => format!("state {x} has no exit"),
+            FsmIssue::MissingDefault(x) => format!(
+                "case without default ({}:{}:{})",
+                x.beg.source, x.beg.line, x.beg.column
+            ),
+        };
+        text.fmt(f)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Transition {
+    pub from: StrId,
+    pub to: StrId,
+}
+
+#[derive(Clone, Debug)]
+pub struct StateMachine {
+    pub module: StrId,
+    /// Variable which holds the current state
+    pub register: StrId,
+    pub r#enum: StrId,
+    pub states: Vec<StrId>,
+    pub reset: Option<StrId>,
+    /// Transitions except staying in the same state
+    pub transitions: Vec<Transition>,
+    pub issues: Vec<FsmIssue>,
+    pub range: TokenRange,
+}
+
+impl StateMachine {
+    /// State diagram in Mermaid syntax
+    pub fn mermaid(&self) -> String {
+        let mut ret = "stateDiagram-v2\n".to_string();
+        for x in &self.states {
+            ret.push_str(&format!("    {x}\n"));
+        }
+        if let Some(x) = self.reset {
+            ret.push_str(&format!("    [*] --> {x}\n"));
+        }
+        for x in &self.transitions {
+            ret.push_str(&format!("    {} --> {}\n", x.from, x.to));
+        }
+        ret
+    }
+}
+
+/// State machines in module declarations of the syntax tree
+pub fn analyze(input: &Veryl) -> Vec<StateMachine> {
+    let mut walker = FsmWalker::default();
+    walker.veryl(input);
+    walker.handler.state_machines
+}
+
+#[derive(Default)]
+struct FsmWalker {
+    handler: FsmHandler,
+}
+
+impl VerylWalker for FsmWalker {
+    fn get_handlers(&mut self) -> Option<Vec<&mut dyn Handler>> {
+        Some(vec![&mut self.handler])
+    }
+}
+
+enum Block {
+    AlwaysFf(StatementBlock),
+    AlwaysComb(StatementBlock),
+    Assign(HierarchicalIdentifier, Expression),
+}
+
+#[derive(Default)]
+struct FsmHandler {
+    point: HandlerPoint,
+    state_machines: Vec<StateMachine>,
+    module: Option<StrId>,
+    blocks: Vec<Block>,
+}
+
+impl FsmHandler {
+    fn finish_module(&mut self) {
+        let Some(module) = self.module else {
+            return;
+        };
+        let blocks = std::mem::take(&mut self.blocks);
+
+        let mut ff_assignments = Vec::new();
+        for x in &blocks {
+            if let Block::AlwaysFf(x) = x {
+                collect_assignments(x, false, &mut ff_assignments);
+            }
+        }
+        let ff_targets: HashSet<_> = ff_assignments.iter().map(|x| x.0.id).collect();
+
+        let mut candidates: Vec<Candidate> = Vec::new();
+        for (target, expression, in_reset) in &ff_assignments {
+            let index = if let Some(i) = candidates.iter().position(|x| x.register.id == target.id)
+            {
+                i
+            } else if let Some(x) = Candidate::new(target) {
+                candidates.push(x);
+                candidates.len() - 1
+            } else {
+                continue;
+            };
+            let candidate = &mut candidates[index];
+
+            let Some(source) = single_identifier(expression) else {
+                continue;
+            };
+            if let Some(member) = candidate.members.get(&source.id) {
+                if *in_reset {
+                    candidate.reset = Some(*member);
+                }
+            } else if !*in_reset
+                && !ff_targets.contains(&source.id)
+                && matches!(source.kind, SymbolKind::Variable(_))
+            {
+                candidate.next.insert(source.id);
+            }
+        }
+
+        for candidate in candidates {
+            let mut extractor = Extractor::new(&candidate);
+            for x in &blocks {
+                let from = candidate.states.clone();
+                match x {
+                    Block::AlwaysFf(x) => extractor.statement_block(x, &from, true),
+                    Block::AlwaysComb(x) => extractor.statement_block(x, &from, false),
+                    Block::Assign(x, y) => {
+                        let target = symbol_table::resolve(x.identifier.as_ref()).ok();
+                        if target.is_some_and(|x| candidate.next.contains(&x.found.id)) {
+                            extractor.assigned(y, &from);
+                        }
+                    }
+                }
+            }
+
+            if extractor.transitions.is_empty() {
+                continue;
+            }
+            let issues = extractor.issues();
+            self.state_machines.push(StateMachine {
+                module,
+                register: candidate.register.token.text,
+                r#enum: candidate.r#enum.token.text,
+                states: candidate.states.clone(),
+                reset: candidate.reset,
+                transitions: extractor.transitions,
+                issues,
+                range: candidate.register.token.into(),
+            });
+        }
+    }
+}
+
+impl Handler for FsmHandler {
+    fn set_point(&mut self, p: HandlerPoint) {
+        self.point = p;
+    }
+}
+
+impl VerylGrammarTrait for FsmHandler {
+    fn always_ff_declaration(&mut self, arg: &AlwaysFfDeclaration) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            let block = arg.statement_block.as_ref().clone();
+            self.blocks.push(Block::AlwaysFf(block));
+        }
+        Ok(())
+    }
+
+    fn always_comb_declaration(&mut self, arg: &AlwaysCombDeclaration) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            let block = arg.statement_block.as_ref().clone();
+            self.blocks.push(Block::AlwaysComb(block));
+        }
+        Ok(())
+    }
+
+    fn assign_declaration(&mut self, arg: &AssignDeclaration) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            let target = arg.hierarchical_identifier.as_ref().clone();
+            let expression = arg.expression.as_ref().clone();
+            self.blocks.push(Block::Assign(target, expression));
+        }
+        Ok(())
+    }
+
+    fn module_declaration(&mut self, arg: &ModuleDeclaration) -> Result<(), ParolError> {
+        match self.point {
+            HandlerPoint::Before => {
+                self.module = Some(arg.identifier.identifier_token.token.text);
+            }
+            HandlerPoint::After => {
+                self.finish_module();
+                self.module = None;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Enum-typed variable assigned in `always_ff`
+struct Candidate {
+    register: Symbol,
+    r#enum: Symbol,
+    width: usize,
+    states: Vec<StrId>,
+    members: HashMap<SymbolId, StrId>,
+    reset: Option<StrId>,
+    /// Variables which hold the next state
+    next: HashSet<SymbolId>,
+}
+
+impl Candidate {
+    fn new(register: &Symbol) -> Option<Self> {
+        let SymbolKind::Variable(ref x) = register.kind else {
+            return None;
+        };
+        let mut r#type = x.r#type.clone();
+        let mut namespace = register.namespace.clone();
+        let r#enum = loop {
+            if !r#type.array.is_empty() {
+                return None;
+            }
+            let TypeKind::UserDefined(ref path) = r#type.kind else {
+                return None;
+            };
+            let symbol = symbol_table::resolve((&SymbolPath::new(path), &namespace))
+                .ok()?
+                .found;
+            match symbol.kind {
+                SymbolKind::TypeDef(ref x) => {
+                    r#type = x.r#type.clone();
+                    namespace = symbol.namespace.clone();
+                }
+                SymbolKind::Enum(_) => break symbol,
+                _ => return None,
+            }
+        };
+        let SymbolKind::Enum(ref property) = r#enum.kind else {
+            unreachable!();
+        };
+
+        let mut states = Vec::new();
+        let mut members = HashMap::new();
+        for id in &property.members {
+            let symbol = symbol_table::get(*id)?;
+            states.push(symbol.token.text);
+            members.insert(*id, symbol.token.text);
+        }
+        let width = property.width;
+
+        Some(Self {
+            register: register.clone(),
+            r#enum,
+            width,
+            states,
+            members,
+            reset: None,
+            next: HashSet::new(),
+        })
+    }
+}
+
+/// Transitions of a candidate from statements and expressions
+struct Extractor<'a> {
+    candidate: &'a Candidate,
+    transitions: Vec<Transition>,
+    /// States which have transitions to unknown states
+    unknown: HashSet<StrId>,
+    missing_default: Vec<TokenRange>,
+}
+
+impl<'a> Extractor<'a> {
+    fn new(candidate: &'a Candidate) -> Self {
+        Self {
+            candidate,
+            transitions: Vec::new(),
+            unknown: HashSet::new(),
+            missing_default: Vec::new(),
+        }
+    }
+
+    fn issues(&self) -> Vec<FsmIssue> {
+        let mut ret: Vec<_> = self
+            .missing_default
+            .iter()
+            .map(|x| FsmIssue::MissingDefault(*x))
+            .collect();
+
+        let mut reachable = HashSet::new();
+        // destinations of unknown transitions may be any state
+        if let (Some(reset), true) = (self.candidate.reset, self.unknown.is_empty()) {
+            let mut queue = vec![reset];
+            while let Some(x) = queue.pop() {
+                if reachable.insert(x) {
+                    for t in self.transitions.iter().filter(|t| t.from == x) {
+                        queue.push(t.to);
+                    }
+                }
+            }
+            for x in &self.candidate.states {
+                if !reachable.contains(x) {
+                    ret.push(FsmIssue::Unreachable(*x));
+                }
+            }
+        } else {
+            reachable.extend(self.candidate.states.iter().copied());
+        }
+
+        for x in &self.candidate.states {
+            let exit = self.unknown.contains(x) || self.transitions.iter().any(|t| t.from == *x);
+            if reachable.contains(x) && !exit {
+                ret.push(FsmIssue::NoExit(*x));
+            }
+        }
+        ret
+    }
+
+    fn add_transition(&mut self, from: &[StrId], to: StrId) {
+        for x in from {
+            let transition = Transition { from: *x, to };
+            if *x != to && !self.transitions.contains(&transition) {
+                self.transitions.push(transition);
+            }
+        }
+    }
+
+    fn operand(&self, arg: &Factor) -> Operand {
+        let Factor::ExpressionIdentifierFactorOpt(x) = arg else {
+            return Operand::Other;
+        };
+        let identifier = x.expression_identifier.as_ref();
+        if x.factor_opt.is_some()
+            || !identifier.expression_identifier_list.is_empty()
+            || !identifier.expression_identifier_list0.is_empty()
+        {
+            return Operand::Other;
+        }
+        let Ok(symbol) = symbol_table::resolve(identifier) else {
+            return Operand::Other;
+        };
+        if let Some(x) = self.candidate.members.get(&symbol.found.id) {
+            Operand::State(*x)
+        } else if symbol.found.id == self.candidate.register.id {
+            Operand::Register
+        } else if self.candidate.next.contains(&symbol.found.id) {
+            Operand::Next
+        } else {
+            Operand::Other
+        }
+    }
+
+    fn is_register(&self, arg: &Expression) -> bool {
+        factor(arg).is_some_and(|x| matches!(self.operand(x), Operand::Register))
+    }
+
+    /// State compared with the register like `state == State::A`
+    fn state_condition(&self, arg: &Expression) -> Option<StrId> {
+        let x = expression05(arg)?;
+        let [ref y] = x.expression05_list[..] else {
+            return None;
+        };
+        if y.operator06.operator06_token.to_string() != "==" {
+            return None;
+        }
+        let lhs = self.operand(expression06_factor(&x.expression06)?);
+        let rhs = self.operand(expression06_factor(&y.expression06)?);
+        match (lhs, rhs) {
+            (Operand::Register, Operand::State(x)) | (Operand::State(x), Operand::Register) => {
+                Some(x)
+            }
+            _ => None,
+        }
+    }
+
+    fn case_states(&self, arg: &CaseCondition) -> Option<Vec<StrId>> {
+        let mut ret = Vec::new();
+        let items = std::iter::once(&arg.range_item)
+            .chain(arg.case_condition_list.iter().map(|x| &x.range_item));
+        for x in items {
+            if x.range.range_opt.is_some() {
+                return None;
+            }
+            match self.operand(factor(&x.range.expression)?) {
+                Operand::State(x) => ret.push(x),
+                _ => return None,
+            }
+        }
+        Some(ret)
+    }
+
+    fn switch_states(&self, arg: &SwitchCondition) -> Option<Vec<StrId>> {
+        let mut ret = vec![self.state_condition(&arg.expression)?];
+        for x in &arg.switch_condition_list {
+            ret.push(self.state_condition(&x.expression)?);
+        }
+        Some(ret)
+    }
+
+    /// Expression assigned to the register or the next state variable
+    fn assigned(&mut self, arg: &Expression, from: &[StrId]) {
+        let Some(x) = factor(arg) else {
+            self.unknown.extend(from);
+            return;
+        };
+        match x {
+            Factor::LParenExpressionRParen(x) => self.assigned(&x.expression, from),
+            Factor::IfExpression(x) => {
+                let x = &x.if_expression;
+                self.assigned(&x.expression0, from);
+                for y in &x.if_expression_list {
+                    self.assigned(&y.expression0, from);
+                }
+                self.assigned(&x.expression1, from);
+            }
+            Factor::CaseExpression(x) => {
+                let x = &x.case_expression;
+                let items: Vec<_> = std::iter::once((&x.case_condition, &x.expression0))
+                    .chain(
+                        x.case_expression_list
+                            .iter()
+                            .map(|y| (&y.case_condition, &y.expression)),
+                    )
+                    .collect();
+                let branches: Vec<_> = items
+                    .iter()
+                    .map(|(x, y)| (self.case_states(x), y.as_ref()))
+                    .collect();
+                self.branches(
+                    self.is_register(&x.expression),
+                    branches,
+                    &x.expression1,
+                    from,
+                );
+            }
+            Factor::SwitchExpression(x) => {
+                let x = &x.switch_expression;
+                let items: Vec<_> = std::iter::once((&x.switch_condition, &x.expression))
+                    .chain(
+                        x.switch_expression_list
+                            .iter()
+                            .map(|y| (&y.switch_condition, &y.expression)),
+                    )
+                    .collect();
+                let branches: Vec<_> = items
+                    .iter()
+                    .map(|(x, y)| (self.switch_states(x), y.as_ref()))
+                    .collect();
+                let by_state = branches.iter().all(|x| x.0.is_some());
+                self.branches(by_state, branches, &x.expression0, from);
+            }
+            x => match self.operand(x) {
+                Operand::State(to) => self.add_transition(from, to),
+                Operand::Register | Operand::Next => (),
+                Operand::Other => self.unknown.extend(from),
+            },
+        }
+    }
+
+    /// Branches of case/switch expression, which are selected by state if `by_state`
+    fn branches(
+        &mut self,
+        by_state: bool,
+        branches: Vec<(Option<Vec<StrId>>, &Expression)>,
+        default: &Expression,
+        from: &[StrId],
+    ) {
+        let mut rest = from.to_vec();
+        for (states, x) in branches {
+            match states {
+                Some(states) if by_state => {
+                    let states: Vec<_> = from
+                        .iter()
+                        .filter(|x| states.contains(x))
+                        .copied()
+                        .collect();
+                    rest.retain(|x| !states.contains(x));
+                    self.assigned(x, &states);
+                }
+                _ => self.assigned(x, from),
+            }
+        }
+        self.assigned(default, &rest);
+    }
+
+    fn statement_block(&mut self, arg: &StatementBlock, from: &[StrId], in_ff: bool) {
+        for x in &arg.statement_block_list {
+            if let StatementBlockItem::Statement(x) = x.statement_block_item.as_ref() {
+                self.statement(&x.statement, from, in_ff);
+            }
+        }
+    }
+
+    fn statement(&mut self, arg: &Statement, from: &[StrId], in_ff: bool) {
+        match arg {
+            Statement::IdentifierStatement(x) => {
+                let x = &x.identifier_statement;
+                let IdentifierStatementGroup::Assignment(ref y) = *x.identifier_statement_group
+                else {
+                    return;
+                };
+                let Ok(target) = symbol_table::resolve(x.expression_identifier.as_ref()) else {
+                    return;
+                };
+                let target = target.found.id;
+                let assigned = self.candidate.next.contains(&target)
+                    || (in_ff && target == self.candidate.register.id);
+                if !assigned {
+                    return;
+                }
+                if let AssignmentGroup::Equ(_) = *y.assignment.assignment_group {
+                    self.assigned(&y.assignment.expression, from);
+                } else {
+                    self.unknown.extend(from);
+                }
+            }
+            Statement::IfStatement(x) => {
+                let x = &x.if_statement;
+                self.statement_block(&x.statement_block, from, in_ff);
+                for y in &x.if_statement_list {
+                    self.statement_block(&y.statement_block, from, in_ff);
+                }
+                if let Some(ref y) = x.if_statement_opt {
+                    self.statement_block(&y.statement_block, from, in_ff);
+                }
+            }
+            Statement::IfResetStatement(x) => {
+                // transitions in reset are collected as the reset state
+                let x = &x.if_reset_statement;
+                for y in &x.if_reset_statement_list {
+                    self.statement_block(&y.statement_block, from, in_ff);
+                }
+                if let Some(ref y) = x.if_reset_statement_opt {
+                    self.statement_block(&y.statement_block, from, in_ff);
+                }
+            }
+            Statement::ForStatement(x) => {
+                self.statement_block(&x.for_statement.statement_block, from, in_ff);
+            }
+            Statement::CaseStatement(x) => {
+                let x = &x.case_statement;
+                let by_state = self.is_register(&x.expression);
+                let mut rest = from.to_vec();
+                let mut covered = 0;
+                let mut default = false;
+                for y in &x.case_statement_list {
+                    let y = &y.case_item;
+                    let states = match *y.case_item_group {
+                        CaseItemGroup::CaseCondition(ref z) => self.case_states(&z.case_condition),
+                        CaseItemGroup::Defaul(_) => {
+                            default = true;
+                            Some(rest.clone())
+                        }
+                    };
+                    let states = match states {
+                        Some(states) if by_state => {
+                            covered += states.len();
+                            let states: Vec<_> = rest
+                                .iter()
+                                .filter(|x| states.contains(x))
+                                .copied()
+                                .collect();
+                            rest.retain(|x| !states.contains(x));
+                            states
+                        }
+                        _ => from.to_vec(),
+                    };
+                    match *y.case_item_group0 {
+                        CaseItemGroup0::Statement(ref z) => {
+                            self.statement(&z.statement, &states, in_ff)
+                        }
+                        CaseItemGroup0::StatementBlock(ref z) => {
+                            self.statement_block(&z.statement_block, &states, in_ff)
+                        }
+                    }
+                }
+
+                let encodings = 1usize.checked_shl(self.candidate.width as u32);
+                let full = encodings.is_some_and(|x| covered >= x);
+                if by_state && !default && !full {
+                    let range: TokenRange = x.case.case_token.token.into();
+                    if !self.missing_default.contains(&range) {
+                        self.missing_default.push(range);
+                    }
+                }
+            }
+            Statement::SwitchStatement(x) => {
+                let x = &x.switch_statement;
+                let items: Vec<_> = x
+                    .switch_statement_list
+                    .iter()
+                    .map(|y| {
+                        let states = match *y.switch_item.switch_item_group {
+                            SwitchItemGroup::SwitchCondition(ref z) => {
+                                self.switch_states(&z.switch_condition)
+                            }
+                            SwitchItemGroup::Defaul(_) => Some(Vec::new()),
+                        };
+                        (states, &y.switch_item)
+                    })
+                    .collect();
+                let by_state = items.iter().all(|x| x.0.is_some());
+                let mut rest = from.to_vec();
+                for (states, y) in items {
+                    let states = match (states, y.switch_item_group.as_ref()) {
+                        (_, SwitchItemGroup::Defaul(_)) if by_state => rest.clone(),
+                        (Some(states), _) if by_state => {
+                            let states: Vec<_> = rest
+                                .iter()
+                                .filter(|x| states.contains(x))
+                                .copied()
+                                .collect();
+                            rest.retain(|x| !states.contains(x));
+                            states
+                        }
+                        _ => from.to_vec(),
+                    };
+                    match *y.switch_item_group0 {
+                        SwitchItemGroup0::Statement(ref z) => {
+                            self.statement(&z.statement, &states, in_ff)
+                        }
+                        SwitchItemGroup0::StatementBlock(ref z) => {
+                            self.statement_block(&z.statement_block, &states, in_ff)
+                        }
+                    }
+                }
+            }
+            Statement::ReturnStatement(_) | Statement::BreakStatement(_) => (),
+        }
+    }
+}
+
+enum Operand {
+    State(StrId),
+    Register,
+    Next,
+    Other,
+}
+
+/// Assignments in `always_ff` with whether it is in `if_reset`
+fn collect_assignments(
+    arg: &StatementBlock,
+    in_reset: bool,
+    ret: &mut Vec<(Symbol, Expression, bool)>,
+) {
+    for x in &arg.statement_block_list {
+        if let StatementBlockItem::Statement(ref x) = *x.statement_block_item {
+            collect_statement(&x.statement, in_reset, ret);
+        }
+    }
+}
+
+fn collect_statement(arg: &Statement, in_reset: bool, ret: &mut Vec<(Symbol, Expression, bool)>) {
+    match arg {
+        Statement::IdentifierStatement(x) => {
+            let x = &x.identifier_statement;
+            let IdentifierStatementGroup::Assignment(ref y) = *x.identifier_statement_group else {
+                return;
+            };
+            if let Ok(target) = symbol_table::resolve(x.expression_identifier.as_ref()) {
+                let expression = y.assignment.expression.as_ref().clone();
+                ret.push((target.found, expression, in_reset));
+            }
+        }
+        Statement::IfStatement(x) => {
+            let x = &x.if_statement;
+            collect_assignments(&x.statement_block, in_reset, ret);
+            for y in &x.if_statement_list {
+                collect_assignments(&y.statement_block, in_reset, ret);
+            }
+            if let Some(ref y) = x.if_statement_opt {
+                collect_assignments(&y.statement_block, in_reset, ret);
+            }
+        }
+        Statement::IfResetStatement(x) => {
+            let x = &x.if_reset_statement;
+            collect_assignments(&x.statement_block, true, ret);
+            for y in &x.if_reset_statement_list {
+                collect_assignments(&y.statement_block, in_reset, ret);
+            }
+            if let Some(ref y) = x.if_reset_statement_opt {
+                collect_assignments(&y.statement_block, in_reset, ret);
+            }
+        }
+        Statement::ForStatement(x) => {
+            collect_assignments(&x.for_statement.statement_block, in_reset, ret);
+        }
+        Statement::CaseStatement(x) => {
+            for y in &x.case_statement.case_statement_list {
+                match *y.case_item.case_item_group0 {
+                    CaseItemGroup0::Statement(ref z) => {
+                        collect_statement(&z.statement, in_reset, ret)
+                    }
+                    CaseItemGroup0::StatementBlock(ref z) => {
+                        collect_assignments(&z.statement_block, in_reset, ret)
+                    }
+                }
+            }
+        }
+        Statement::SwitchStatement(x) => {
+            for y in &x.switch_statement.switch_statement_list {
+                match *y.switch_item.switch_item_group0 {
+                    SwitchItemGroup0::Statement(ref z) => {
+                        collect_statement(&z.statement, in_reset, ret)
+                    }
+                    SwitchItemGroup0::StatementBlock(ref z) => {
+                        collect_assignments(&z.statement_block, in_reset, ret)
+                    }
+                }
+            }
+        }
+        Statement::ReturnStatement(_) | Statement::BreakStatement(_) => (),
+    }
+}
+
+/// Symbol of the expression which consists of a single identifier
+fn single_identifier(arg: &Expression) -> Option<Symbol> {
+    let Factor::ExpressionIdentifierFactorOpt(x) = factor(arg)? else {
+        return None;
+    };
+    let identifier = x.expression_identifier.as_ref();
+    if x.factor_opt.is_some()
+        || !identifier.expression_identifier_list.is_empty()
+        || !identifier.expression_identifier_list0.is_empty()
+    {
+        return None;
+    }
+    symbol_table::resolve(identifier).ok().map(|x| x.found)
+}
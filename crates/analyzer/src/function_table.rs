@@ -0,0 +1,48 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use veryl_parser::resource_table::{PathId, TokenId};
+use veryl_parser::veryl_grammar_trait::FunctionDeclaration;
+use veryl_parser::veryl_token::TokenSource;
+
+#[derive(Clone, Default, Debug)]
+pub struct FunctionTable {
+    table: HashMap<TokenId, FunctionDeclaration>,
+}
+
+impl FunctionTable {
+    pub fn insert(&mut self, id: TokenId, declaration: &FunctionDeclaration) {
+        self.table.insert(id, declaration.clone());
+    }
+
+    pub fn get(&self, id: TokenId) -> Option<&FunctionDeclaration> {
+        self.table.get(&id)
+    }
+
+    pub fn drop(&mut self, file_path: PathId) {
+        self.table.retain(|_, x| {
+            x.identifier.identifier_token.token.source != TokenSource::File(file_path)
+        });
+    }
+
+    pub fn clear(&mut self) {
+        self.table.clear()
+    }
+}
+
+thread_local!(static FUNCTION_TABLE: RefCell<FunctionTable> = RefCell::new(FunctionTable::default()));
+
+pub fn insert(id: TokenId, declaration: &FunctionDeclaration) {
+    FUNCTION_TABLE.with(|f| f.borrow_mut().insert(id, declaration))
+}
+
+pub fn get(id: TokenId) -> Option<FunctionDeclaration> {
+    FUNCTION_TABLE.with(|f| f.borrow().get(id).cloned())
+}
+
+pub fn drop(file_path: PathId) {
+    FUNCTION_TABLE.with(|f| f.borrow_mut().drop(file_path))
+}
+
+pub fn clear() {
+    FUNCTION_TABLE.with(|f| f.borrow_mut().clear())
+}
@@ -12,9 +12,13 @@ pub mod check_modport;
 pub mod check_msb_lsb;
 pub mod check_number;
 pub mod check_proto;
+pub mod check_reset_domain;
 pub mod check_statement;
+pub mod check_target_language;
 pub mod check_type;
 pub mod check_unsafe;
+pub mod check_width;
+pub mod create_comb_dependency;
 pub mod create_reference;
 pub mod create_symbol_table;
 pub mod create_type_dag;
@@ -31,9 +35,13 @@ use check_modport::*;
 use check_msb_lsb::*;
 use check_number::*;
 use check_proto::*;
+use check_reset_domain::*;
 use check_statement::*;
+use check_target_language::*;
 use check_type::*;
 use check_unsafe::*;
+use check_width::*;
+use create_comb_dependency::*;
 use create_reference::*;
 use create_symbol_table::*;
 
@@ -105,13 +113,17 @@ pub struct Pass2Handlers<'a> {
     check_clock_reset: CheckClockReset<'a>,
     create_reference: CreateReference<'a>,
     create_type_dag: CreateTypeDag<'a>,
+    create_comb_dependency: CreateCombDependency,
     check_expression: CheckExpression<'a>,
     check_clock_domain: CheckClockDomain<'a>,
+    check_reset_domain: CheckResetDomain<'a>,
     check_proto: CheckProto<'a>,
+    check_width: CheckWidth<'a>,
+    check_target_language: CheckTargetLanguage<'a>,
 }
 
 impl<'a> Pass2Handlers<'a> {
-    pub fn new(text: &'a str, _build_opt: &'a Build, _lint_opt: &'a Lint) -> Self {
+    pub fn new(text: &'a str, build_opt: &'a Build, lint_opt: &'a Lint) -> Self {
         Self {
             check_enum: CheckEnum::new(text),
             check_modport: CheckModport::new(text),
@@ -122,9 +134,13 @@ impl<'a> Pass2Handlers<'a> {
             check_clock_reset: CheckClockReset::new(text),
             create_reference: CreateReference::new(text),
             create_type_dag: CreateTypeDag::new(text),
+            create_comb_dependency: CreateCombDependency::new(),
             check_expression: CheckExpression::new(text),
             check_clock_domain: CheckClockDomain::new(text),
+            check_reset_domain: CheckResetDomain::new(text, build_opt, lint_opt),
             check_proto: CheckProto::new(text),
+            check_width: CheckWidth::new(text, lint_opt),
+            check_target_language: CheckTargetLanguage::new(text, build_opt),
         }
     }
 
@@ -139,9 +155,24 @@ impl<'a> Pass2Handlers<'a> {
             &mut self.check_clock_reset as &mut dyn Handler,
             &mut self.create_reference as &mut dyn Handler,
             &mut self.create_type_dag as &mut dyn Handler,
+            &mut self.create_comb_dependency as &mut dyn Handler,
             &mut self.check_expression as &mut dyn Handler,
             &mut self.check_clock_domain as &mut dyn Handler,
+            &mut self.check_reset_domain as &mut dyn Handler,
             &mut self.check_proto as &mut dyn Handler,
+            &mut self.check_width as &mut dyn Handler,
+            &mut self.check_target_language as &mut dyn Handler,
+        ]
+    }
+
+    /// Handlers which create tables referred by other files and the emitter
+    pub fn get_table_handlers(&mut self) -> Vec<&mut dyn Handler> {
+        vec![
+            &mut self.check_msb_lsb as &mut dyn Handler,
+            &mut self.check_assignment as &mut dyn Handler,
+            &mut self.create_reference as &mut dyn Handler,
+            &mut self.create_type_dag as &mut dyn Handler,
+            &mut self.create_comb_dependency as &mut dyn Handler,
         ]
     }
 
@@ -158,7 +189,10 @@ impl<'a> Pass2Handlers<'a> {
         ret.append(&mut self.create_type_dag.errors);
         ret.append(&mut self.check_expression.errors);
         ret.append(&mut self.check_clock_domain.errors);
+        ret.append(&mut self.check_reset_domain.errors);
         ret.append(&mut self.check_proto.errors);
+        ret.append(&mut self.check_width.errors);
+        ret.append(&mut self.check_target_language.errors);
         ret
     }
 }
@@ -6,6 +6,7 @@ use crate::assign::{
 use crate::attribute::AllowItem;
 use crate::attribute::Attribute as Attr;
 use crate::attribute_table;
+use crate::evaluator::{Evaluated, Evaluator};
 use crate::symbol::{Direction, SymbolId, SymbolKind, TypeKind};
 use crate::symbol_table;
 use std::collections::HashMap;
@@ -67,6 +68,43 @@ fn can_assign(full_path: &[SymbolId]) -> bool {
     false
 }
 
+/// Bit range selected by constant index of single dimension variable
+fn select_range(arg: &ExpressionIdentifier) -> Option<(usize, usize)> {
+    if arg.expression_identifier_list.len() != 1 || !arg.expression_identifier_list0.is_empty() {
+        return None;
+    }
+
+    let select = &arg.expression_identifier_list[0].select;
+    let mut evaluator = Evaluator::new();
+    let Evaluated::Fixed { value: x, .. } = evaluator.expression(&select.expression) else {
+        return None;
+    };
+    let (msb, lsb) = if let Some(ref opt) = select.select_opt {
+        let Evaluated::Fixed { value: y, .. } = evaluator.expression(&opt.expression) else {
+            return None;
+        };
+        match opt.select_operator.as_ref() {
+            SelectOperator::Colon(_) => (x, y),
+            SelectOperator::PlusColon(_) => (x.checked_add(y)?.checked_sub(1)?, x),
+            SelectOperator::MinusColon(_) => (x, x.checked_sub(y)?.checked_add(1)?),
+            SelectOperator::Step(_) => (
+                y.checked_mul(x.checked_add(1)?)?.checked_sub(1)?,
+                y.checked_mul(x)?,
+            ),
+        }
+    } else {
+        (x, x)
+    };
+
+    let msb = msb.try_into().ok()?;
+    let lsb = lsb.try_into().ok()?;
+    if msb >= lsb {
+        Some((msb, lsb))
+    } else {
+        None
+    }
+}
+
 impl<'a> VerylGrammarTrait for CheckAssignment<'a> {
     fn r#else(&mut self, arg: &Else) -> Result<(), ParolError> {
         if let HandlerPoint::Before = self.point {
@@ -110,6 +148,7 @@ impl<'a> VerylGrammarTrait for CheckAssignment<'a> {
                 self.assign_position.push(AssignPositionType::Statement {
                     token: arg.equ.equ_token.token,
                     resettable: false,
+                    range: None,
                 });
                 symbol_table::add_assign(x.full_path, &self.assign_position, false);
                 self.assign_position.pop();
@@ -140,9 +179,16 @@ impl<'a> VerylGrammarTrait for CheckAssignment<'a> {
                             .iter()
                             .any(|x| !x.expression_identifier_list0_list.is_empty());
 
+                        let range = if partial {
+                            select_range(&arg.expression_identifier)
+                        } else {
+                            None
+                        };
+
                         self.assign_position.push(AssignPositionType::Statement {
                             token,
                             resettable: true,
+                            range,
                         });
                         symbol_table::add_assign(full_path, &self.assign_position, partial);
                         self.assign_position.pop();
@@ -245,6 +291,7 @@ impl<'a> VerylGrammarTrait for CheckAssignment<'a> {
                 self.assign_position.push(AssignPositionType::Statement {
                     token: arg.r#for.for_token.token,
                     resettable: false,
+                    range: None,
                 });
                 symbol_table::add_assign(x.full_path, &self.assign_position, false);
                 self.assign_position.pop();
@@ -298,6 +345,51 @@ impl<'a> VerylGrammarTrait for CheckAssignment<'a> {
         Ok(())
     }
 
+    fn switch_statement(&mut self, arg: &SwitchStatement) -> Result<(), ParolError> {
+        match self.point {
+            HandlerPoint::Before => {
+                self.branch_index = 0;
+                let branches = arg.switch_statement_list.len();
+                let has_default = arg.switch_statement_list.iter().any(|x| {
+                    matches!(
+                        x.switch_item.switch_item_group.as_ref(),
+                        SwitchItemGroup::Defaul(_)
+                    )
+                });
+                self.assign_position
+                    .push(AssignPositionType::StatementBranch {
+                        token: arg.switch.switch_token.token,
+                        branches,
+                        has_default,
+                        allow_missing_reset_statement: false,
+                        r#type: AssignStatementBranchType::Switch,
+                    });
+            }
+            HandlerPoint::After => {
+                self.assign_position.pop();
+            }
+        }
+        Ok(())
+    }
+
+    fn switch_item(&mut self, arg: &SwitchItem) -> Result<(), ParolError> {
+        match self.point {
+            HandlerPoint::Before => {
+                self.assign_position
+                    .push(AssignPositionType::StatementBranchItem {
+                        token: arg.colon.colon_token.token,
+                        index: self.branch_index,
+                        r#type: AssignStatementBranchItemType::Switch,
+                    });
+                self.branch_index += 1;
+            }
+            HandlerPoint::After => {
+                self.assign_position.pop();
+            }
+        }
+        Ok(())
+    }
+
     fn let_declaration(&mut self, arg: &LetDeclaration) -> Result<(), ParolError> {
         if let HandlerPoint::Before = self.point {
             if let Ok(x) = symbol_table::resolve(arg.identifier.as_ref()) {
@@ -553,6 +645,7 @@ impl<'a> VerylGrammarTrait for CheckAssignment<'a> {
                 self.assign_position.push(AssignPositionType::Statement {
                     token: arg.r#for.for_token.token,
                     resettable: false,
+                    range: None,
                 });
                 symbol_table::add_assign(x.full_path, &self.assign_position, false);
                 self.assign_position.pop();
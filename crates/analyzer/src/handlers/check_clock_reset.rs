@@ -261,7 +261,7 @@ impl<'a> VerylGrammarTrait for CheckClockReset<'a> {
             if self.in_if_reset {
                 // Check to see right hand side of reset is const evaluable
                 match self.evaluator.expression(&arg.expression) {
-                    UnknownStatic | Fixed { .. } => (),
+                    UnknownStatic | Fixed { .. } | Real { .. } => (),
                     _ => {
                         self.errors
                             .push(AnalyzerError::invalid_reset_non_elaborative(
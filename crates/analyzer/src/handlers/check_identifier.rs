@@ -1,4 +1,5 @@
 use crate::analyzer_error::AnalyzerError;
+use crate::fix::Fix;
 use crate::symbol::Direction as SymDirection;
 use crate::symbol_table::is_sv_keyword;
 use veryl_metadata::{Case, Lint};
@@ -211,24 +212,33 @@ impl<'a> CheckIdentifier<'a> {
             }
         }
 
+        let fixed = fixed_identifier(&identifier, prefix, suffix, case);
+        let fix = Fix::rename(&format!("Rename to `{fixed}`"), token, &fixed);
+
         if let Some(prefix) = prefix {
             if !identifier.starts_with(prefix) {
-                self.errors.push(AnalyzerError::invalid_identifier(
-                    &identifier,
-                    &format!("prefix: {prefix}"),
-                    self.text,
-                    &token.into(),
-                ));
+                self.errors.push(
+                    AnalyzerError::invalid_identifier(
+                        &identifier,
+                        &format!("prefix: {prefix}"),
+                        self.text,
+                        &token.into(),
+                    )
+                    .with_fix(Some(fix.clone())),
+                );
             }
         }
         if let Some(suffix) = suffix {
             if !identifier.ends_with(suffix) {
-                self.errors.push(AnalyzerError::invalid_identifier(
-                    &identifier,
-                    &format!("suffix: {suffix}"),
-                    self.text,
-                    &token.into(),
-                ));
+                self.errors.push(
+                    AnalyzerError::invalid_identifier(
+                        &identifier,
+                        &format!("suffix: {suffix}"),
+                        self.text,
+                        &token.into(),
+                    )
+                    .with_fix(Some(fix.clone())),
+                );
             }
         }
         if let Some(case) = case {
@@ -239,12 +249,15 @@ impl<'a> CheckIdentifier<'a> {
                 Case::LowerCamel => is_lower_camel_case(&identifier),
             };
             if !pass {
-                self.errors.push(AnalyzerError::invalid_identifier(
-                    &identifier,
-                    &format!("case: {case}"),
-                    self.text,
-                    &token.into(),
-                ));
+                self.errors.push(
+                    AnalyzerError::invalid_identifier(
+                        &identifier,
+                        &format!("case: {case}"),
+                        self.text,
+                        &token.into(),
+                    )
+                    .with_fix(Some(fix.clone())),
+                );
             }
         }
         if let Some(re_required) = re_required {
@@ -458,6 +471,89 @@ fn is_upper_camel_case(text: &str) -> bool {
     !text.contains('_') && head.is_ascii_uppercase()
 }
 
+fn split_words(text: &str) -> Vec<String> {
+    let mut ret = Vec::new();
+    for x in text.split('_').filter(|x| !x.is_empty()) {
+        let chars: Vec<_> = x.chars().collect();
+        let mut word = String::new();
+        for (i, c) in chars.iter().enumerate() {
+            let prev_lower = i > 0 && !chars[i - 1].is_ascii_uppercase();
+            let next_lower = chars.get(i + 1).map(|x| x.is_ascii_lowercase()) == Some(true);
+            let acronym_end = i > 0 && chars[i - 1].is_ascii_uppercase() && next_lower;
+            if c.is_ascii_uppercase() && (prev_lower || acronym_end) && !word.is_empty() {
+                ret.push(word.clone());
+                word.clear();
+            }
+            word.push(*c);
+        }
+        ret.push(word);
+    }
+    ret
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(x) => x.to_ascii_uppercase().to_string() + &chars.as_str().to_ascii_lowercase(),
+        None => String::new(),
+    }
+}
+
+fn to_case(text: &str, case: &Case) -> String {
+    let words = split_words(text);
+    match case {
+        Case::Snake => words
+            .iter()
+            .map(|x| x.to_ascii_lowercase())
+            .collect::<Vec<_>>()
+            .join("_"),
+        Case::ScreamingSnake => words
+            .iter()
+            .map(|x| x.to_ascii_uppercase())
+            .collect::<Vec<_>>()
+            .join("_"),
+        Case::UpperCamel => words.iter().map(|x| capitalize(x)).collect(),
+        Case::LowerCamel => words
+            .iter()
+            .enumerate()
+            .map(|(i, x)| {
+                if i == 0 {
+                    x.to_ascii_lowercase()
+                } else {
+                    capitalize(x)
+                }
+            })
+            .collect(),
+    }
+}
+
+fn fixed_identifier(
+    identifier: &str,
+    prefix: &Option<String>,
+    suffix: &Option<String>,
+    case: &Option<Case>,
+) -> String {
+    let mut stem = identifier.strip_prefix("r#").unwrap_or(identifier);
+    if let Some(prefix) = prefix {
+        stem = stem.strip_prefix(prefix.as_str()).unwrap_or(stem);
+    }
+    if let Some(suffix) = suffix {
+        stem = stem.strip_suffix(suffix.as_str()).unwrap_or(stem);
+    }
+
+    let stem = if let Some(case) = case {
+        to_case(stem, case)
+    } else {
+        stem.to_string()
+    };
+
+    format!(
+        "{}{stem}{}",
+        prefix.as_deref().unwrap_or(""),
+        suffix.as_deref().unwrap_or("")
+    )
+}
+
 fn is_snake_case(text: &str) -> bool {
     text.to_ascii_lowercase() == text
 }
@@ -494,6 +590,36 @@ mod tests {
         assert_eq!(is_upper_camel_case("A_B_C"), false);
     }
 
+    #[test]
+    fn fixed_identifier() {
+        let prefix = Some("i_".to_string());
+        let suffix = Some("_n".to_string());
+        assert_eq!(
+            super::fixed_identifier("DataIn", &prefix, &None, &Some(Case::Snake)),
+            "i_data_in"
+        );
+        assert_eq!(
+            super::fixed_identifier("i_rst", &None, &suffix, &None),
+            "i_rst_n"
+        );
+        assert_eq!(
+            super::fixed_identifier("HTTPServer", &None, &None, &Some(Case::Snake)),
+            "http_server"
+        );
+        assert_eq!(
+            super::fixed_identifier("data_valid", &None, &None, &Some(Case::UpperCamel)),
+            "DataValid"
+        );
+        assert_eq!(
+            super::fixed_identifier("data_valid", &None, &None, &Some(Case::LowerCamel)),
+            "dataValid"
+        );
+        assert_eq!(
+            super::fixed_identifier("dataWidth", &None, &None, &Some(Case::ScreamingSnake)),
+            "DATA_WIDTH"
+        );
+    }
+
     #[test]
     fn snake_case() {
         assert_eq!(is_snake_case("abc"), true);
@@ -0,0 +1,473 @@
+use crate::analyzer_error::AnalyzerError;
+use crate::attribute::AllowItem;
+use crate::attribute::Attribute as Attr;
+use crate::attribute_table;
+use crate::symbol::{ClockDomain, Symbol, SymbolId, SymbolKind, Type, TypeKind};
+use crate::symbol_table;
+use std::collections::{HashMap, HashSet};
+use veryl_metadata::{Build, Lint, ResetType};
+use veryl_parser::veryl_grammar_trait::*;
+use veryl_parser::veryl_token::TokenRange;
+use veryl_parser::veryl_walker::{Handler, HandlerPoint};
+use veryl_parser::ParolError;
+
+#[derive(Clone, Copy)]
+struct AlwaysFf {
+    reset: Option<SymbolId>,
+    clock_domain: ClockDomain,
+}
+
+struct Flop {
+    reset: Option<SymbolId>,
+    clock_domain: ClockDomain,
+}
+
+/// Reference from a register to the source of the assignment
+struct FlopInput {
+    reset: Option<SymbolId>,
+    source: SymbolId,
+    range: TokenRange,
+    target: TokenRange,
+}
+
+pub struct CheckResetDomain<'a> {
+    pub errors: Vec<AnalyzerError>,
+    text: &'a str,
+    build_opt: &'a Build,
+    lint_opt: &'a Lint,
+    point: HandlerPoint,
+    default_clock: Option<SymbolId>,
+    default_reset: Option<SymbolId>,
+    always_ff: Option<AlwaysFf>,
+    in_always_comb: bool,
+    in_if_reset: bool,
+    if_reset_brace: usize,
+    sources: Vec<(SymbolId, TokenRange)>,
+    flops: HashMap<SymbolId, Flop>,
+    flop_inputs: Vec<FlopInput>,
+    combs: HashMap<SymbolId, Vec<(SymbolId, TokenRange)>>,
+    async_resets: Vec<(SymbolId, ClockDomain, TokenRange)>,
+}
+
+impl<'a> CheckResetDomain<'a> {
+    pub fn new(text: &'a str, build_opt: &'a Build, lint_opt: &'a Lint) -> Self {
+        Self {
+            errors: Vec::new(),
+            text,
+            build_opt,
+            lint_opt,
+            point: HandlerPoint::Before,
+            default_clock: None,
+            default_reset: None,
+            always_ff: None,
+            in_always_comb: false,
+            in_if_reset: false,
+            if_reset_brace: 0,
+            sources: Vec::new(),
+            flops: HashMap::new(),
+            flop_inputs: Vec::new(),
+            combs: HashMap::new(),
+            async_resets: Vec::new(),
+        }
+    }
+
+    fn is_async(&self, symbol: &Symbol) -> bool {
+        let kind = match &symbol.kind {
+            SymbolKind::Port(x) => x.r#type.as_ref().map(|x| &x.kind),
+            SymbolKind::Variable(x) => Some(&x.r#type.kind),
+            _ => None,
+        };
+        match kind {
+            Some(TypeKind::ResetAsyncHigh) | Some(TypeKind::ResetAsyncLow) => true,
+            Some(TypeKind::Reset) => matches!(
+                self.build_opt.reset_type,
+                ResetType::AsyncLow | ResetType::AsyncHigh
+            ),
+            _ => false,
+        }
+    }
+
+    fn assignment(&mut self, target: Option<(SymbolId, TokenRange)>) {
+        let Some((target, range)) = target else {
+            return;
+        };
+        let sources = std::mem::take(&mut self.sources);
+
+        if let Some(x) = self.always_ff {
+            let reset = x.reset;
+            self.flops.insert(
+                target,
+                Flop {
+                    reset,
+                    clock_domain: x.clock_domain,
+                },
+            );
+            // reset values are checked as elaborative expression by CheckClockReset
+            if !self.in_if_reset {
+                for (source, source_range) in sources {
+                    self.flop_inputs.push(FlopInput {
+                        reset,
+                        source,
+                        range: source_range,
+                        target: range,
+                    });
+                }
+            }
+        } else {
+            self.combs.entry(target).or_default().extend(sources);
+        }
+    }
+
+    /// Reset which is connected through combinational aliases is the same reset
+    fn canonical_reset(&self, mut reset: SymbolId) -> SymbolId {
+        let mut visited = HashSet::new();
+        while visited.insert(reset) {
+            match self.combs.get(&reset).map(|x| x.as_slice()) {
+                Some([(x, _)]) => reset = *x,
+                _ => break,
+            }
+        }
+        reset
+    }
+
+    /// Registers which drive `symbol` through combinational logic
+    fn driving_flops(
+        &self,
+        symbol: SymbolId,
+        range: TokenRange,
+        visited: &mut HashSet<SymbolId>,
+        ret: &mut Vec<(SymbolId, TokenRange)>,
+    ) {
+        if !visited.insert(symbol) {
+            return;
+        }
+        if self.flops.contains_key(&symbol) {
+            ret.push((symbol, range));
+        } else if let Some(sources) = self.combs.get(&symbol) {
+            for (x, _) in sources {
+                self.driving_flops(*x, range, visited, ret);
+            }
+        }
+    }
+
+    /// Source which is not synchronized to `clock_domain` in the driver of the reset
+    fn unsynchronized_source(
+        &self,
+        symbol: SymbolId,
+        range: TokenRange,
+        clock_domain: &ClockDomain,
+        visited: &mut HashSet<SymbolId>,
+    ) -> Option<TokenRange> {
+        if !visited.insert(symbol) {
+            return None;
+        }
+        if let Some(x) = self.flops.get(&symbol) {
+            if x.clock_domain.compatible(clock_domain) {
+                None
+            } else {
+                Some(range)
+            }
+        } else if let Some(sources) = self.combs.get(&symbol) {
+            sources.iter().find_map(|(x, range)| {
+                self.unsynchronized_source(*x, *range, clock_domain, visited)
+            })
+        } else {
+            // reset ports are assumed to be synchronized by the parent module
+            match symbol_table::get(symbol).map(|x| x.kind) {
+                Some(SymbolKind::Port(x)) if !x.r#type.as_ref().is_some_and(is_reset) => {
+                    Some(range)
+                }
+                _ => None,
+            }
+        }
+    }
+
+    fn check_reset_domain_crossing(&mut self) {
+        let mut reported = HashSet::new();
+        for input in &self.flop_inputs {
+            if attribute_table::contains(
+                &input.target.beg,
+                Attr::Allow(AllowItem::ResetDomainCrossing),
+            ) {
+                continue;
+            }
+
+            let dst_reset = input.reset.map(|x| self.canonical_reset(x));
+            let mut flops = Vec::new();
+            self.driving_flops(input.source, input.range, &mut HashSet::new(), &mut flops);
+
+            for (flop, range) in flops {
+                let Some(src_reset) = self.flops[&flop].reset else {
+                    continue;
+                };
+                if Some(self.canonical_reset(src_reset)) != dst_reset
+                    && reported.insert((input.target.beg, flop))
+                {
+                    self.errors.push(AnalyzerError::reset_domain_crossing(
+                        &reset_name(Some(src_reset)),
+                        &reset_name(input.reset),
+                        self.text,
+                        &range,
+                        &input.target,
+                    ));
+                }
+            }
+        }
+    }
+
+    fn check_unsynchronized_reset(&mut self) {
+        let mut reported = HashSet::new();
+        for (reset, clock_domain, range) in &self.async_resets {
+            if attribute_table::contains(&range.beg, Attr::Allow(AllowItem::UnsynchronizedReset))
+                || !reported.insert((*reset, *clock_domain))
+            {
+                continue;
+            }
+
+            if let Some(source) =
+                self.unsynchronized_source(*reset, *range, clock_domain, &mut HashSet::new())
+            {
+                self.errors.push(AnalyzerError::unsynchronized_reset(
+                    &reset_name(Some(*reset)),
+                    self.text,
+                    range,
+                    &source,
+                ));
+            }
+        }
+    }
+}
+
+fn is_reset(x: &Type) -> bool {
+    matches!(
+        x.kind,
+        TypeKind::Reset
+            | TypeKind::ResetAsyncHigh
+            | TypeKind::ResetAsyncLow
+            | TypeKind::ResetSyncHigh
+            | TypeKind::ResetSyncLow
+    )
+}
+
+fn reset_name(reset: Option<SymbolId>) -> String {
+    reset
+        .and_then(symbol_table::get)
+        .map(|x| x.token.to_string())
+        .unwrap_or("none".to_string())
+}
+
+fn clock_domain(symbol: &Symbol) -> ClockDomain {
+    match &symbol.kind {
+        SymbolKind::Variable(x) => x.clock_domain,
+        SymbolKind::Port(x) => x.clock_domain,
+        _ => ClockDomain::None,
+    }
+}
+
+impl<'a> Handler for CheckResetDomain<'a> {
+    fn set_point(&mut self, p: HandlerPoint) {
+        self.point = p;
+    }
+}
+
+impl<'a> VerylGrammarTrait for CheckResetDomain<'a> {
+    fn l_brace(&mut self, _arg: &LBrace) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            if self.in_if_reset {
+                self.if_reset_brace += 1;
+            }
+        }
+        Ok(())
+    }
+
+    fn r_brace(&mut self, _arg: &RBrace) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            if self.in_if_reset {
+                self.if_reset_brace -= 1;
+                if self.if_reset_brace == 0 {
+                    self.in_if_reset = false;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn if_reset(&mut self, _arg: &IfReset) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            self.in_if_reset = true;
+        }
+        Ok(())
+    }
+
+    fn expression_identifier(&mut self, arg: &ExpressionIdentifier) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            if let Ok(symbol) = symbol_table::resolve(arg.scoped_identifier.as_ref()) {
+                if matches!(
+                    symbol.found.kind,
+                    SymbolKind::Variable(_) | SymbolKind::Port(_)
+                ) {
+                    self.sources.push((symbol.found.id, arg.into()));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn let_statement(&mut self, arg: &LetStatement) -> Result<(), ParolError> {
+        match self.point {
+            HandlerPoint::Before => self.sources.clear(),
+            HandlerPoint::After => {
+                let target = symbol_table::resolve(arg.identifier.as_ref()).ok();
+                self.assignment(target.map(|x| (x.found.id, arg.identifier.as_ref().into())));
+            }
+        }
+        Ok(())
+    }
+
+    fn identifier_statement(&mut self, arg: &IdentifierStatement) -> Result<(), ParolError> {
+        match self.point {
+            HandlerPoint::Before => self.sources.clear(),
+            HandlerPoint::After => {
+                if let IdentifierStatementGroup::Assignment(_) =
+                    arg.identifier_statement_group.as_ref()
+                {
+                    if self.always_ff.is_some() || self.in_always_comb {
+                        // the first source is the left-hand side
+                        let target = if self.sources.is_empty() {
+                            None
+                        } else {
+                            Some(self.sources.remove(0))
+                        };
+                        self.assignment(target);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn let_declaration(&mut self, arg: &LetDeclaration) -> Result<(), ParolError> {
+        match self.point {
+            HandlerPoint::Before => self.sources.clear(),
+            HandlerPoint::After => {
+                let target = symbol_table::resolve(arg.identifier.as_ref()).ok();
+                self.assignment(target.map(|x| (x.found.id, arg.identifier.as_ref().into())));
+            }
+        }
+        Ok(())
+    }
+
+    fn assign_declaration(&mut self, arg: &AssignDeclaration) -> Result<(), ParolError> {
+        match self.point {
+            HandlerPoint::Before => self.sources.clear(),
+            HandlerPoint::After => {
+                let ident = arg.hierarchical_identifier.identifier.as_ref();
+                let target = symbol_table::resolve(ident).ok();
+                self.assignment(
+                    target.map(|x| (x.found.id, arg.hierarchical_identifier.as_ref().into())),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn always_comb_declaration(&mut self, _arg: &AlwaysCombDeclaration) -> Result<(), ParolError> {
+        match self.point {
+            HandlerPoint::Before => self.in_always_comb = true,
+            HandlerPoint::After => self.in_always_comb = false,
+        }
+        Ok(())
+    }
+
+    fn always_ff_declaration(&mut self, arg: &AlwaysFfDeclaration) -> Result<(), ParolError> {
+        match self.point {
+            HandlerPoint::Before => {
+                let (clock, reset) = if let Some(ref x) = arg.always_ff_declaration_opt {
+                    let ident = x
+                        .alwayf_ff_event_list
+                        .always_ff_clock
+                        .hierarchical_identifier
+                        .identifier
+                        .as_ref();
+                    let clock = symbol_table::resolve(ident).ok().map(|x| x.found);
+                    let reset = x
+                        .alwayf_ff_event_list
+                        .alwayf_ff_event_list_opt
+                        .as_ref()
+                        .map(|x| {
+                            let ident = x.always_ff_reset.hierarchical_identifier.as_ref();
+                            let symbol = symbol_table::resolve(ident).ok().map(|x| x.found);
+                            (symbol, ident.into())
+                        });
+                    (clock, reset)
+                } else {
+                    let clock = self.default_clock.and_then(symbol_table::get);
+                    let reset = self.default_reset.map(|x| {
+                        (
+                            symbol_table::get(x),
+                            arg.always_ff.always_ff_token.token.into(),
+                        )
+                    });
+                    (clock, reset)
+                };
+
+                // registers are reset only if if_reset exists
+                let has_if_reset = arg
+                    .statement_block
+                    .statement_block_list
+                    .first()
+                    .is_some_and(|x| match x.statement_block_item.as_ref() {
+                        StatementBlockItem::Statement(x) => {
+                            matches!(*x.statement, Statement::IfResetStatement(_))
+                        }
+                        _ => false,
+                    });
+                let clock_domain = clock.map(|x| clock_domain(&x)).unwrap_or(ClockDomain::None);
+                let reset = match reset {
+                    Some((Some(symbol), range)) if has_if_reset => {
+                        if self.is_async(&symbol) {
+                            self.async_resets.push((symbol.id, clock_domain, range));
+                        }
+                        Some(symbol.id)
+                    }
+                    _ => None,
+                };
+
+                self.always_ff = Some(AlwaysFf {
+                    reset,
+                    clock_domain,
+                });
+            }
+            HandlerPoint::After => self.always_ff = None,
+        }
+        Ok(())
+    }
+
+    fn module_declaration(&mut self, arg: &ModuleDeclaration) -> Result<(), ParolError> {
+        match self.point {
+            HandlerPoint::Before => {
+                if let Ok(symbol) = symbol_table::resolve(arg.identifier.as_ref()) {
+                    if let SymbolKind::Module(ref x) = symbol.found.kind {
+                        self.default_clock = x.default_clock;
+                        self.default_reset = x.default_reset;
+                    }
+                }
+            }
+            HandlerPoint::After => {
+                if self.lint_opt.reset.reset_domain_crossing {
+                    self.check_reset_domain_crossing();
+                }
+                if self.lint_opt.reset.unsynchronized_reset {
+                    self.check_unsynchronized_reset();
+                }
+                self.default_clock = None;
+                self.default_reset = None;
+                self.flops.clear();
+                self.flop_inputs.clear();
+                self.combs.clear();
+                self.async_resets.clear();
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,615 @@
+use crate::analyzer_error::AnalyzerError;
+use crate::evaluator::factor;
+use crate::namespace_table;
+use crate::symbol::{SymbolKind, TypeKind};
+use crate::symbol_path::GenericSymbolPath;
+use crate::symbol_table::{self, ResolveError, ResolveResult};
+use veryl_metadata::{Build, TargetLanguage};
+use veryl_parser::resource_table::TokenId;
+use veryl_parser::veryl_grammar_trait::*;
+use veryl_parser::veryl_token::{TokenRange, TokenSource};
+use veryl_parser::veryl_walker::{Handler, HandlerPoint, VerylWalker};
+use veryl_parser::ParolError;
+
+pub struct CheckTargetLanguage<'a> {
+    pub errors: Vec<AnalyzerError>,
+    text: &'a str,
+    build_opt: &'a Build,
+    point: HandlerPoint,
+    array_literals: Vec<TokenId>,
+    in_test: Vec<bool>,
+    in_generic_module: bool,
+    source: Option<TokenSource>,
+    generate_conditions: Vec<TokenRange>,
+    unreferenced_only: Vec<(Identifier, &'static str)>,
+    generic_modules: Vec<Identifier>,
+}
+
+impl<'a> CheckTargetLanguage<'a> {
+    pub fn new(text: &'a str, build_opt: &'a Build) -> Self {
+        Self {
+            errors: Vec::new(),
+            text,
+            build_opt,
+            point: HandlerPoint::Before,
+            array_literals: Vec::new(),
+            in_test: Vec::new(),
+            in_generic_module: false,
+            source: None,
+            generate_conditions: Vec::new(),
+            unreferenced_only: Vec::new(),
+            generic_modules: Vec::new(),
+        }
+    }
+
+    fn is_verilog(&self) -> bool {
+        self.build_opt.target_language == TargetLanguage::Verilog
+    }
+
+    fn is_vhdl(&self) -> bool {
+        self.build_opt.target_language == TargetLanguage::Vhdl
+    }
+
+    fn unsupported_by_vhdl(&mut self, feature: &str, token: &TokenRange) {
+        // testbench and generic module are not emitted to VHDL,
+        // and generic module itself is reported if it is instantiated
+        let in_test = self.in_test.iter().any(|x| *x);
+        if self.is_vhdl() && !in_test && !self.in_generic_module {
+            self.errors.push(AnalyzerError::unsupported_by_target(
+                feature, "VHDL", self.text, token,
+            ));
+        }
+    }
+
+    /// Declarations which are omitted from VHDL are unsupported only if they are referenced
+    /// in their own scope. References are checked after all declarations are visited.
+    fn unsupported_by_vhdl_if_referenced(&mut self, feature: &'static str, arg: &Identifier) {
+        let in_test = self.in_test.iter().any(|x| *x);
+        if self.is_vhdl() && !in_test && !self.in_generic_module {
+            self.unreferenced_only.push((arg.clone(), feature));
+        }
+    }
+
+    /// VHDL has no interface, so interface ports are always flattened like Verilog
+    fn flatten_interface_ports(&self) -> bool {
+        self.build_opt.flatten_interface_ports || self.is_verilog() || self.is_vhdl()
+    }
+
+    fn flatten_target(&self) -> &'static str {
+        if self.is_verilog() {
+            "Verilog"
+        } else {
+            "flattened interface ports"
+        }
+    }
+
+    fn unsupported_by_flatten(&mut self, feature: &str, token: &TokenRange) {
+        if self.is_vhdl() {
+            self.unsupported_by_vhdl(feature, token);
+        } else {
+            self.errors.push(AnalyzerError::unsupported_by_target(
+                feature,
+                self.flatten_target(),
+                self.text,
+                token,
+            ));
+        }
+    }
+
+    /// System function which can be lowered to VHDL with its single argument
+    fn system_function(&mut self, arg: &ExpressionIdentifier, call: &FunctionCall) {
+        let name = arg.identifier().to_string();
+        if !self.is_vhdl() || !name.starts_with('$') || name == "$sv" {
+            return;
+        }
+        let args = call
+            .function_call_opt
+            .as_ref()
+            .map(|x| x.argument_list.argument_list_list.len() + 1)
+            .unwrap_or(0);
+        let supported = matches!(
+            name.as_str(),
+            "$clog2" | "$signed" | "$unsigned" | "$bits" | "$size"
+        );
+        // $onehot of genvars is lowered to integer arithmetic in generate conditions only
+        let onehot = name == "$onehot" && self.in_generate_condition(arg) && {
+            let mut finder = GenvarFinder::default();
+            if let Some(ref x) = call.function_call_opt {
+                finder.expression(&x.argument_list.argument_item.expression);
+            }
+            !finder.other
+        };
+        if !(supported || onehot) || args != 1 {
+            self.unsupported_by_vhdl(&format!("system function {name}"), &arg.into());
+        }
+    }
+
+    fn in_generate_condition(&self, arg: &ExpressionIdentifier) -> bool {
+        let token = arg.identifier().token;
+        self.generate_conditions.iter().any(|x| {
+            x.beg.source == token.source && x.beg.pos <= token.pos && token.pos <= x.end.pos
+        })
+    }
+
+    /// Member access is emitted as record element, so the base should be struct or interface
+    fn member_access(&mut self, base: Result<ResolveResult, ResolveError>, token: &TokenRange) {
+        if !self.is_vhdl() {
+            return;
+        }
+        let r#type = match base.map(|x| x.found.kind) {
+            Ok(SymbolKind::Variable(x)) => Some(x.r#type),
+            Ok(SymbolKind::Port(x)) => x.r#type,
+            _ => None,
+        };
+        if let Some(r#type) = r#type {
+            if !matches!(r#type.kind, TypeKind::UserDefined(_)) {
+                self.unsupported_by_vhdl("member access of non-struct variable", token);
+            }
+        }
+    }
+
+    /// Array literal which can be lowered to element-wise assignments
+    fn add_array_literal(&mut self, arg: &Expression) {
+        if let Some(Factor::QuoteLBraceArrayLiteralListRBrace(x)) = factor(arg) {
+            self.array_literals
+                .push(x.quote_l_brace.quote_l_brace_token.token.id);
+        }
+    }
+}
+
+/// Symbols which are emitted as VHDL identifiers in the scope of the namespace
+fn is_vhdl_name(kind: &SymbolKind) -> bool {
+    matches!(
+        kind,
+        SymbolKind::Port(_)
+            | SymbolKind::Variable(_)
+            | SymbolKind::Module(_)
+            | SymbolKind::Function(_)
+            | SymbolKind::Parameter(_)
+            | SymbolKind::Instance(_)
+            | SymbolKind::Block
+            | SymbolKind::Package(_)
+            | SymbolKind::Struct(_)
+            | SymbolKind::StructMember(_)
+            | SymbolKind::TypeDef(_)
+            | SymbolKind::Enum(_)
+            | SymbolKind::EnumMember(_)
+            | SymbolKind::Genvar
+    )
+}
+
+/// Whether the expression refers identifiers other than genvar
+#[derive(Default)]
+struct GenvarFinder {
+    other: bool,
+}
+
+impl VerylWalker for GenvarFinder {
+    fn expression_identifier(&mut self, arg: &ExpressionIdentifier) {
+        if !matches!(
+            symbol_table::resolve(arg).map(|x| x.found.kind),
+            Ok(SymbolKind::Genvar)
+        ) {
+            self.other = true;
+        }
+    }
+}
+
+fn unpacked_array(symbol: Result<ResolveResult, ResolveError>) -> Vec<Expression> {
+    match symbol.map(|x| x.found.kind) {
+        Ok(SymbolKind::Variable(x)) => x.r#type.array,
+        Ok(SymbolKind::Port(x)) => x.r#type.map(|x| x.array).unwrap_or_default(),
+        _ => Vec::new(),
+    }
+}
+
+impl<'a> Handler for CheckTargetLanguage<'a> {
+    fn set_point(&mut self, p: HandlerPoint) {
+        self.point = p;
+    }
+}
+
+impl<'a> VerylGrammarTrait for CheckTargetLanguage<'a> {
+    fn identifier(&mut self, arg: &Identifier) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            if self.source.is_none() {
+                self.source = Some(arg.identifier_token.token.source);
+            }
+        }
+        Ok(())
+    }
+
+    fn veryl(&mut self, _arg: &Veryl) -> Result<(), ParolError> {
+        if let HandlerPoint::After = self.point {
+            if !self.is_vhdl() {
+                return Ok(());
+            }
+
+            for (identifier, feature) in &self.unreferenced_only {
+                if let Ok(symbol) = symbol_table::resolve(identifier) {
+                    // connections from outside are omitted with the declaration
+                    let symbol = symbol.found;
+                    let referenced = symbol.references.iter().any(|x| {
+                        namespace_table::get(x.id)
+                            .map(|x| x.included(&symbol.namespace))
+                            .unwrap_or(false)
+                    });
+                    if referenced {
+                        self.errors.push(AnalyzerError::unsupported_by_target(
+                            feature,
+                            "VHDL",
+                            self.text,
+                            &identifier.into(),
+                        ));
+                    }
+                }
+            }
+
+            // generic module is omitted from VHDL, so its instances can't be elaborated
+            for identifier in &self.generic_modules {
+                if let Ok(symbol) = symbol_table::resolve(identifier) {
+                    if !symbol.found.references.is_empty() {
+                        self.errors.push(AnalyzerError::unsupported_by_target(
+                            "generic module",
+                            "VHDL",
+                            self.text,
+                            &identifier.into(),
+                        ));
+                    }
+                }
+            }
+
+            // VHDL identifiers are case-insensitive,
+            // so the emitter escapes the colliding ones as extended identifiers
+            let symbols: Vec<_> = symbol_table::get_all()
+                .into_iter()
+                .filter(|x| is_vhdl_name(&x.kind))
+                .collect();
+            for symbol in &symbols {
+                if Some(symbol.token.source) != self.source {
+                    continue;
+                }
+                let text = symbol.token.to_string();
+                let conflict = symbols.iter().find(|x| {
+                    let other = x.token.to_string();
+                    x.token.id < symbol.token.id
+                        && x.namespace == symbol.namespace
+                        && other != text
+                        && other.to_lowercase() == text.to_lowercase()
+                });
+                if let Some(conflict) = conflict {
+                    self.errors.push(AnalyzerError::case_insensitive_identifier(
+                        &text,
+                        &conflict.token.to_string(),
+                        "VHDL",
+                        self.text,
+                        &symbol.token.into(),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn expression_identifier(&mut self, arg: &ExpressionIdentifier) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            if !arg.expression_identifier_list0.is_empty() {
+                self.member_access(
+                    symbol_table::resolve(arg.scoped_identifier.as_ref()),
+                    &arg.into(),
+                );
+            }
+            if !self.flatten_interface_ports() || arg.expression_identifier_list0.is_empty() {
+                return Ok(());
+            }
+
+            // functions of interface are not emitted with flattened signals
+            let Ok(symbol) = symbol_table::resolve(arg) else {
+                return Ok(());
+            };
+            let through_interface = match symbol.found.kind {
+                SymbolKind::ModportFunctionMember(_) => true,
+                SymbolKind::Function(_) => {
+                    matches!(
+                        symbol_table::resolve(arg.scoped_identifier.as_ref()),
+                        Ok(x) if matches!(x.found.kind, SymbolKind::Instance(_))
+                    )
+                }
+                _ => false,
+            };
+            if through_interface {
+                self.unsupported_by_flatten("function call through interface", &arg.into());
+            }
+        }
+        Ok(())
+    }
+
+    fn hierarchical_identifier(&mut self, arg: &HierarchicalIdentifier) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            if !arg.hierarchical_identifier_list0.is_empty() {
+                self.member_access(
+                    symbol_table::resolve(arg.identifier.as_ref()),
+                    &arg.identifier.as_ref().into(),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn interface_declaration(&mut self, arg: &InterfaceDeclaration) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            // parameters can't be passed to flattened signals
+            if let Some(ref x) = arg.interface_declaration_opt1 {
+                if let Some(ref x) = x.with_parameter.with_parameter_opt {
+                    let items: Vec<WithParameterItem> = x.with_parameter_list.as_ref().into();
+                    for item in items {
+                        self.unsupported_by_vhdl_if_referenced(
+                            "parameter of interface",
+                            &item.identifier,
+                        );
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+    fn description_group(&mut self, arg: &DescriptionGroup) -> Result<(), ParolError> {
+        match self.point {
+            HandlerPoint::Before => {
+                let is_test = arg
+                    .description_group_list
+                    .iter()
+                    .any(|x| x.attribute.identifier.identifier_token.to_string() == "test");
+                self.in_test.push(is_test);
+            }
+            HandlerPoint::After => {
+                self.in_test.pop();
+            }
+        }
+        Ok(())
+    }
+
+    fn port_declaration_item(&mut self, arg: &PortDeclarationItem) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            let token: TokenRange = arg.identifier.as_ref().into();
+            match arg.port_declaration_item_group.as_ref() {
+                PortDeclarationItemGroup::PortTypeAbstract(_) => {
+                    // unreferenced generic interface port is omitted from VHDL
+                    if self.is_vhdl() {
+                        self.unsupported_by_vhdl_if_referenced(
+                            "generic interface port",
+                            &arg.identifier,
+                        );
+                    } else if self.flatten_interface_ports() {
+                        self.unsupported_by_flatten("generic interface port", &token);
+                    }
+                }
+                PortDeclarationItemGroup::PortTypeConcrete(x) => {
+                    if x.port_type_concrete.array_type.array_type_opt.is_some() {
+                        self.unsupported_by_vhdl("array port", &token);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn module_declaration(&mut self, arg: &ModuleDeclaration) -> Result<(), ParolError> {
+        match self.point {
+            HandlerPoint::Before => {
+                self.in_generic_module = arg.module_declaration_opt0.is_some();
+                let in_test = self.in_test.iter().any(|x| *x);
+                if self.is_vhdl() && self.in_generic_module && !in_test {
+                    self.generic_modules.push(arg.identifier.as_ref().clone());
+                }
+            }
+            HandlerPoint::After => {
+                self.in_generic_module = false;
+            }
+        }
+        Ok(())
+    }
+
+    fn package_declaration(&mut self, arg: &PackageDeclaration) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            if arg.package_declaration_opt0.is_some() {
+                self.unsupported_by_vhdl("generic package", &arg.identifier.as_ref().into());
+            }
+        }
+        Ok(())
+    }
+
+    fn function_declaration(&mut self, arg: &FunctionDeclaration) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            if arg.function_declaration_opt.is_some() {
+                self.unsupported_by_vhdl("generic function", &arg.identifier.as_ref().into());
+            }
+        }
+        Ok(())
+    }
+
+    fn embed_declaration(&mut self, arg: &EmbedDeclaration) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            self.unsupported_by_vhdl("embed declaration", &arg.embed.embed_token.token.into());
+        }
+        Ok(())
+    }
+
+    fn include_declaration(&mut self, arg: &IncludeDeclaration) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            self.unsupported_by_vhdl(
+                "include declaration",
+                &arg.include.include_token.token.into(),
+            );
+        }
+        Ok(())
+    }
+
+    fn final_declaration(&mut self, arg: &FinalDeclaration) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            self.unsupported_by_vhdl("final declaration", &arg.r#final.final_token.token.into());
+        }
+        Ok(())
+    }
+
+    fn struct_union_declaration(&mut self, arg: &StructUnionDeclaration) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            if let StructUnion::Union(_) = arg.struct_union.as_ref() {
+                self.unsupported_by_vhdl("union", &arg.identifier.as_ref().into());
+            }
+        }
+        Ok(())
+    }
+
+    fn inst_declaration(&mut self, arg: &InstDeclaration) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            let token: TokenRange = arg.identifier.as_ref().into();
+
+            // generic module is not emitted because VHDL entity can't be monomorphized
+            let path: GenericSymbolPath = arg.scoped_identifier.as_ref().into();
+            let namespace = namespace_table::get(arg.identifier.identifier_token.token.id).unwrap();
+            let (is_module, is_generic) =
+                match symbol_table::resolve((&path.generic_path(), &namespace)) {
+                    Ok(x) => match x.found.kind {
+                        SymbolKind::Module(x) => (true, !x.generic_parameters.is_empty()),
+                        _ => (false, false),
+                    },
+                    Err(_) => (false, false),
+                };
+
+            // array of interface instance is flattened to array signals,
+            // but array of module instance splits connected signals implicitly
+            if is_module && arg.inst_declaration_opt.is_some() {
+                self.unsupported_by_vhdl("array instance of module", &token);
+            }
+            if is_generic {
+                self.unsupported_by_vhdl("instance of generic module", &token);
+            }
+        }
+        Ok(())
+    }
+
+    fn for_statement(&mut self, arg: &ForStatement) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            // step is lowered to integer arithmetic of while loop
+            if let Some(ref x) = arg.for_statement_opt {
+                let operator = x.assignment_operator.assignment_operator_token.to_string();
+                if matches!(operator.as_str(), "&=" | "|=" | "^=") {
+                    self.unsupported_by_vhdl(
+                        "bitwise step of for statement",
+                        &arg.r#for.for_token.token.into(),
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn generate_if_declaration(&mut self, arg: &GenerateIfDeclaration) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            self.generate_conditions
+                .push(arg.expression.as_ref().into());
+            for x in &arg.generate_if_declaration_list {
+                self.generate_conditions.push(x.expression.as_ref().into());
+            }
+        }
+        Ok(())
+    }
+
+    fn generate_for_declaration(&mut self, arg: &GenerateForDeclaration) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            // `+=` step is lowered to scaled index of for generate
+            if let Some(ref x) = arg.generate_for_declaration_opt {
+                let operator = x.assignment_operator.assignment_operator_token.to_string();
+                if operator != "+=" {
+                    self.unsupported_by_vhdl(
+                        "step of for declaration except for +=",
+                        &arg.r#for.for_token.token.into(),
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn let_declaration(&mut self, arg: &LetDeclaration) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            if self.is_verilog() && arg.array_type.array_type_opt.is_some() {
+                self.add_array_literal(&arg.expression);
+            }
+        }
+        Ok(())
+    }
+
+    fn assign_declaration(&mut self, arg: &AssignDeclaration) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            let ident = &arg.hierarchical_identifier;
+            if !self.is_verilog() || !ident.hierarchical_identifier_list0.is_empty() {
+                return Ok(());
+            }
+            let array = unpacked_array(symbol_table::resolve(ident.as_ref()));
+            if array.len() > ident.hierarchical_identifier_list.len() {
+                self.add_array_literal(&arg.expression);
+            }
+        }
+        Ok(())
+    }
+
+    fn identifier_statement(&mut self, arg: &IdentifierStatement) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            let ident = &arg.expression_identifier;
+            if let IdentifierStatementGroup::FunctionCall(x) =
+                arg.identifier_statement_group.as_ref()
+            {
+                self.system_function(ident, &x.function_call);
+            }
+            if !self.is_verilog() || !ident.expression_identifier_list0.is_empty() {
+                return Ok(());
+            }
+            if let IdentifierStatementGroup::Assignment(x) = arg.identifier_statement_group.as_ref()
+            {
+                if let AssignmentGroup::Equ(_) = x.assignment.assignment_group.as_ref() {
+                    let array = unpacked_array(symbol_table::resolve(ident.as_ref()));
+                    if array.len() > ident.expression_identifier_list.len() {
+                        self.add_array_literal(&x.assignment.expression);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn array_literal_item(&mut self, arg: &ArrayLiteralItem) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            // nested array literal is lowered with the outer one
+            if let ArrayLiteralItemGroup::ExpressionArrayLiteralItemOpt(x) =
+                arg.array_literal_item_group.as_ref()
+            {
+                self.add_array_literal(&x.expression);
+            }
+        }
+        Ok(())
+    }
+
+    fn factor(&mut self, arg: &Factor) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            if let Factor::ExpressionIdentifierFactorOpt(x) = arg {
+                if let Some(ref call) = x.factor_opt {
+                    self.system_function(&x.expression_identifier, &call.function_call);
+                }
+            }
+            if let Factor::QuoteLBraceArrayLiteralListRBrace(x) = arg {
+                let id = x.quote_l_brace.quote_l_brace_token.token.id;
+                if self.is_verilog() && !self.array_literals.contains(&id) {
+                    self.errors.push(AnalyzerError::unsupported_by_target(
+                        "array literal except for the whole of an unpacked array at let or assign declaration",
+                        "Verilog",
+                        self.text,
+                        &x.quote_l_brace.quote_l_brace_token.token.into(),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
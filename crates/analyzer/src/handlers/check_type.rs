@@ -2,10 +2,12 @@ use crate::analyzer_error::AnalyzerError;
 use crate::attribute::AllowItem;
 use crate::attribute::Attribute as Attr;
 use crate::attribute_table;
+use crate::fix::Fix;
 use crate::namespace_table;
 use crate::symbol::{GenericBoundKind, Symbol, SymbolKind, TypeKind};
 use crate::symbol_path::GenericSymbolPath;
 use crate::symbol_table;
+use veryl_parser::last_token::LastToken;
 use veryl_parser::resource_table;
 use veryl_parser::veryl_grammar_trait::*;
 use veryl_parser::veryl_walker::{Handler, HandlerPoint, VerylWalker};
@@ -273,20 +275,28 @@ impl<'a> VerylGrammarTrait for CheckType<'a> {
                 }
 
                 if check_port_connection {
-                    for port in &ports {
-                        if !connected_ports.contains(&port.name)
-                            && !attribute_table::contains(
-                                &arg.inst.inst_token.token,
-                                Attr::Allow(AllowItem::MissingPort),
-                            )
-                        {
-                            let port = resource_table::get_str_value(port.name).unwrap();
-                            self.errors.push(AnalyzerError::missing_port(
-                                name,
-                                &port,
-                                self.text,
-                                &arg.identifier.as_ref().into(),
-                            ));
+                    let missing_ports: Vec<_> = ports
+                        .iter()
+                        .filter(|x| !connected_ports.contains(&x.name))
+                        .map(|x| resource_table::get_str_value(x.name).unwrap())
+                        .collect();
+                    let allow_missing_port = attribute_table::contains(
+                        &arg.inst.inst_token.token,
+                        Attr::Allow(AllowItem::MissingPort),
+                    );
+                    if !allow_missing_port && !missing_ports.is_empty() {
+                        // The fix connects all missing ports, so it is attached to the first error only
+                        let mut fix = missing_port_fix(self.text, arg, &missing_ports);
+                        for port in &missing_ports {
+                            self.errors.push(
+                                AnalyzerError::missing_port(
+                                    name,
+                                    port,
+                                    self.text,
+                                    &arg.identifier.as_ref().into(),
+                                )
+                                .with_fix(fix.take()),
+                            );
                         }
                     }
                     for param in &connected_params {
@@ -317,3 +327,49 @@ impl<'a> VerylGrammarTrait for CheckType<'a> {
         Ok(())
     }
 }
+
+/// Connect missing ports as unconnected
+fn missing_port_fix(text: &str, arg: &InstDeclaration, ports: &[String]) -> Option<Fix> {
+    let title = "Add missing ports";
+    let Some(ref x) = arg.inst_declaration_opt1 else {
+        let ports: Vec<_> = ports.iter().map(|x| format!("{x}: _")).collect();
+        let text = format!(" ({})", ports.join(", "));
+        return Fix::insert_before(title, &arg.semicolon.semicolon_token.token, &text)
+            .map(Fix::suggestion);
+    };
+
+    let r_paren = x.r_paren.r_paren_token.token;
+    let list = x.inst_declaration_opt2.as_ref().map(|x| &x.inst_port_list);
+    let has_trailing_comma = list.map(|x| x.inst_port_list_opt.is_some());
+
+    // multiline port list: insert one port per line before `)`
+    let pos = r_paren.pos as usize;
+    let line_head = text.get(..pos)?.rsplit('\n').next()?;
+    let multiline = r_paren.line != x.l_paren.l_paren_token.token.line;
+    if multiline && line_head.chars().all(|x| x.is_ascii_whitespace()) {
+        let mut line_start = r_paren;
+        line_start.pos -= line_head.len() as u32;
+        let text: String = ports
+            .iter()
+            .map(|x| format!("{line_head}    {x}: _,\n"))
+            .collect();
+        let fix = Fix::insert_before(title, &line_start, &text)?;
+        let fix = if has_trailing_comma == Some(false) {
+            let mut last_token = LastToken::default();
+            last_token.inst_port_list(list.unwrap());
+            let comma = Fix::insert_after(title, last_token.token().as_ref()?, ",")?;
+            comma.merge(fix)
+        } else {
+            fix
+        };
+        return Some(fix.suggestion());
+    }
+
+    let ports: Vec<_> = ports.iter().map(|x| format!("{x}: _")).collect();
+    let text = if has_trailing_comma == Some(false) {
+        format!(", {}", ports.join(", "))
+    } else {
+        ports.join(", ")
+    };
+    Fix::insert_before(title, &r_paren, &text).map(Fix::suggestion)
+}
@@ -0,0 +1,784 @@
+use crate::analyzer_error::AnalyzerError;
+use crate::attribute::AllowItem;
+use crate::attribute::Attribute as Attr;
+use crate::attribute_table;
+use crate::evaluator::{Evaluated, Evaluator};
+use crate::symbol::{
+    ParameterKind, ParameterProperty, ParameterValue, SymbolId, SymbolKind, Type, TypeKind,
+    TypeModifier,
+};
+use crate::symbol_path::SymbolPath;
+use crate::symbol_table;
+use std::collections::{HashMap, HashSet};
+use veryl_metadata::Lint;
+use veryl_parser::resource_table::StrId;
+use veryl_parser::veryl_grammar_trait::*;
+use veryl_parser::veryl_token::TokenRange;
+use veryl_parser::veryl_walker::{Handler, HandlerPoint, VerylWalker};
+use veryl_parser::ParolError;
+
+/// Width of expression inferred by SystemVerilog sizing rules
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Width {
+    Fixed {
+        width: usize,
+        signed: bool,
+    },
+    /// Unsized constant which fits any context
+    Flexible,
+    Unknown,
+}
+
+impl Width {
+    fn bit() -> Self {
+        Width::Fixed {
+            width: 1,
+            signed: false,
+        }
+    }
+}
+
+/// Inferred width and signed operands which are extended by the context
+#[derive(Clone, Debug)]
+struct Inferred {
+    width: Width,
+    signed_operands: Vec<(usize, TokenRange)>,
+}
+
+impl Inferred {
+    fn new(width: Width) -> Self {
+        Self {
+            width,
+            signed_operands: Vec::new(),
+        }
+    }
+
+    fn operand(width: Width, range: TokenRange) -> Self {
+        let signed_operands = match width {
+            Width::Fixed {
+                width,
+                signed: true,
+            } => vec![(width, range)],
+            _ => Vec::new(),
+        };
+        Self {
+            width,
+            signed_operands,
+        }
+    }
+
+    /// Operator whose operands are context-determined
+    fn merge(mut self, mut x: Inferred) -> Self {
+        self.width = match (self.width, x.width) {
+            (
+                Width::Fixed {
+                    width: width0,
+                    signed: signed0,
+                },
+                Width::Fixed {
+                    width: width1,
+                    signed: signed1,
+                },
+            ) => Width::Fixed {
+                width: width0.max(width1),
+                signed: signed0 && signed1,
+            },
+            (Width::Fixed { .. }, Width::Flexible) => self.width,
+            (Width::Flexible, Width::Fixed { .. }) => x.width,
+            (Width::Flexible, Width::Flexible) => Width::Flexible,
+            _ => Width::Unknown,
+        };
+        self.signed_operands.append(&mut x.signed_operands);
+        self
+    }
+}
+
+pub struct CheckWidth<'a> {
+    pub errors: Vec<AnalyzerError>,
+    text: &'a str,
+    lint_opt: &'a Lint,
+    point: HandlerPoint,
+    ports: HashMap<StrId, Type>,
+}
+
+impl<'a> CheckWidth<'a> {
+    pub fn new(text: &'a str, lint_opt: &'a Lint) -> Self {
+        Self {
+            errors: Vec::new(),
+            text,
+            lint_opt,
+            point: HandlerPoint::Before,
+            ports: HashMap::new(),
+        }
+    }
+
+    fn is_allowed(&self, range: &TokenRange, item: AllowItem) -> bool {
+        attribute_table::contains(&range.beg, Attr::Allow(item))
+    }
+
+    fn check_assignment(&mut self, target: Width, arg: &Expression) {
+        let inferred = self.expression(arg);
+        let range: TokenRange = arg.into();
+
+        if let (
+            Width::Fixed {
+                width: target_width,
+                ..
+            },
+            Width::Fixed { width, .. },
+        ) = (target, inferred.width)
+        {
+            if width > target_width
+                && self.lint_opt.width.implicit_truncation
+                && !self.is_allowed(&range, AllowItem::ImplicitTruncation)
+            {
+                self.errors.push(AnalyzerError::implicit_truncation(
+                    width,
+                    target_width,
+                    self.text,
+                    &range,
+                ));
+            }
+        }
+
+        let context = match target {
+            Width::Fixed { width, .. } => Some(width),
+            _ => None,
+        };
+        self.check_extension(inferred, context);
+    }
+
+    /// Check signed operands in unsigned expression because they are zero-extended
+    fn check_extension(&mut self, inferred: Inferred, context: Option<usize>) {
+        let Width::Fixed {
+            width,
+            signed: false,
+        } = inferred.width
+        else {
+            return;
+        };
+        if !self.lint_opt.width.signed_zero_extension {
+            return;
+        }
+
+        let context = context.unwrap_or(0).max(width);
+        for (width, range) in inferred.signed_operands {
+            if width < context && !self.is_allowed(&range, AllowItem::SignedZeroExtension) {
+                self.errors.push(AnalyzerError::signed_zero_extension(
+                    width, context, self.text, &range,
+                ));
+            }
+        }
+    }
+
+    /// Expression which is not extended by the surrounding context
+    fn self_determined(&mut self, arg: &Expression) -> Width {
+        let inferred = self.expression(arg);
+        let width = inferred.width;
+        self.check_extension(inferred, None);
+        width
+    }
+
+    fn expression(&mut self, arg: &Expression) -> Inferred {
+        let ret = self.expression01(&arg.expression01);
+        if arg.expression_list.is_empty() {
+            return ret;
+        }
+        self.check_extension(ret, None);
+        for x in &arg.expression_list {
+            let operand = self.expression01(&x.expression01);
+            self.check_extension(operand, None);
+        }
+        Inferred::new(Width::bit())
+    }
+
+    fn expression01(&mut self, arg: &Expression01) -> Inferred {
+        let ret = self.expression02(&arg.expression02);
+        if arg.expression01_list.is_empty() {
+            return ret;
+        }
+        self.check_extension(ret, None);
+        for x in &arg.expression01_list {
+            let operand = self.expression02(&x.expression02);
+            self.check_extension(operand, None);
+        }
+        Inferred::new(Width::bit())
+    }
+
+    fn expression02(&mut self, arg: &Expression02) -> Inferred {
+        let mut ret = self.expression03(&arg.expression03);
+        for x in &arg.expression02_list {
+            let operand = self.expression03(&x.expression03);
+            ret = ret.merge(operand);
+        }
+        ret
+    }
+
+    fn expression03(&mut self, arg: &Expression03) -> Inferred {
+        let mut ret = self.expression04(&arg.expression04);
+        for x in &arg.expression03_list {
+            let operand = self.expression04(&x.expression04);
+            ret = ret.merge(operand);
+        }
+        ret
+    }
+
+    fn expression04(&mut self, arg: &Expression04) -> Inferred {
+        let mut ret = self.expression05(&arg.expression05);
+        for x in &arg.expression04_list {
+            let operand = self.expression05(&x.expression05);
+            ret = ret.merge(operand);
+        }
+        ret
+    }
+
+    fn expression05(&mut self, arg: &Expression05) -> Inferred {
+        let mut ret = self.expression06(&arg.expression06);
+        for x in &arg.expression05_list {
+            // operands of equality are sized each other
+            let operand = self.expression06(&x.expression06);
+            self.check_extension(ret.merge(operand), None);
+            ret = Inferred::new(Width::bit());
+        }
+        ret
+    }
+
+    fn expression06(&mut self, arg: &Expression06) -> Inferred {
+        let mut ret = self.expression07(&arg.expression07);
+        for x in &arg.expression06_list {
+            // operands of relational operator are sized each other
+            let operand = self.expression07(&x.expression07);
+            self.check_extension(ret.merge(operand), None);
+            ret = Inferred::new(Width::bit());
+        }
+        ret
+    }
+
+    fn expression07(&mut self, arg: &Expression07) -> Inferred {
+        let ret = self.expression08(&arg.expression08);
+        for x in &arg.expression07_list {
+            // shift amount is self-determined
+            let operand = self.expression08(&x.expression08);
+            self.check_extension(operand, None);
+        }
+        ret
+    }
+
+    fn expression08(&mut self, arg: &Expression08) -> Inferred {
+        let mut ret = self.expression09(&arg.expression09);
+        for x in &arg.expression08_list {
+            let operand = self.expression09(&x.expression09);
+            ret = ret.merge(operand);
+        }
+        ret
+    }
+
+    fn expression09(&mut self, arg: &Expression09) -> Inferred {
+        let mut ret = self.expression10(&arg.expression10);
+        for x in &arg.expression09_list {
+            let operand = self.expression10(&x.expression10);
+            ret = ret.merge(operand);
+        }
+        ret
+    }
+
+    fn expression10(&mut self, arg: &Expression10) -> Inferred {
+        let ret = self.expression11(&arg.expression11);
+        for x in &arg.expression10_list {
+            // exponent is self-determined
+            let operand = self.expression11(&x.expression11);
+            self.check_extension(operand, None);
+        }
+        ret
+    }
+
+    fn expression11(&mut self, arg: &Expression11) -> Inferred {
+        let ret = self.expression12(&arg.expression12);
+        if let Some(ref x) = arg.expression11_opt {
+            let width = match x.casting_type.as_ref() {
+                CastingType::U32(_) => Width::Fixed {
+                    width: 32,
+                    signed: false,
+                },
+                CastingType::U64(_) => Width::Fixed {
+                    width: 64,
+                    signed: false,
+                },
+                CastingType::I32(_) => Width::Fixed {
+                    width: 32,
+                    signed: true,
+                },
+                CastingType::I64(_) => Width::Fixed {
+                    width: 64,
+                    signed: true,
+                },
+                CastingType::ScopedIdentifier(_) | CastingType::F32(_) | CastingType::F64(_) => {
+                    Width::Unknown
+                }
+                // clock and reset casting keeps width
+                _ => return ret,
+            };
+            self.check_extension(ret, None);
+            Inferred::operand(width, arg.into())
+        } else {
+            ret
+        }
+    }
+
+    fn expression12(&mut self, arg: &Expression12) -> Inferred {
+        let mut ret = self.factor(&arg.factor);
+        for x in arg.expression12_list.iter().rev() {
+            let keep_width = match &*x.expression12_list_group {
+                Expression12ListGroup::UnaryOperator(x) => {
+                    x.unary_operator.unary_operator_token.to_string() == "~"
+                }
+                Expression12ListGroup::Operator09(_) => true,
+                _ => false,
+            };
+            if !keep_width {
+                // operand of reduction is self-determined
+                self.check_extension(ret, None);
+                ret = Inferred::new(Width::bit());
+            }
+        }
+        ret
+    }
+
+    fn factor(&mut self, arg: &Factor) -> Inferred {
+        match arg {
+            Factor::Number(x) => Inferred::operand(number_width(&x.number), arg.into()),
+            Factor::ExpressionIdentifierFactorOpt(x) => {
+                if let Some(ref call) = x.factor_opt {
+                    self.function_call(&x.expression_identifier, &call.function_call)
+                } else {
+                    let width = self.expression_identifier(&x.expression_identifier);
+                    Inferred::operand(width, arg.into())
+                }
+            }
+            Factor::LParenExpressionRParen(x) => self.expression(&x.expression),
+            Factor::LBraceConcatenationListRBrace(x) => {
+                Inferred::new(self.concatenation_list(&x.concatenation_list))
+            }
+            Factor::IfExpression(x) => {
+                let x = &x.if_expression;
+                self.self_determined(&x.expression);
+                let mut ret = self.expression(&x.expression0);
+                for x in &x.if_expression_list {
+                    self.self_determined(&x.expression);
+                    let operand = self.expression(&x.expression0);
+                    ret = ret.merge(operand);
+                }
+                let operand = self.expression(&x.expression1);
+                ret.merge(operand)
+            }
+            Factor::CaseExpression(x) => {
+                let x = &x.case_expression;
+                let mut ret = self.expression(&x.expression0);
+                for x in &x.case_expression_list {
+                    let operand = self.expression(&x.expression);
+                    ret = ret.merge(operand);
+                }
+                let operand = self.expression(&x.expression1);
+                ret.merge(operand)
+            }
+            Factor::SwitchExpression(x) => {
+                let x = &x.switch_expression;
+                let mut ret = self.expression(&x.expression);
+                for x in &x.switch_expression_list {
+                    let operand = self.expression(&x.expression);
+                    ret = ret.merge(operand);
+                }
+                let operand = self.expression(&x.expression0);
+                ret.merge(operand)
+            }
+            Factor::InsideExpression(_) | Factor::OutsideExpression(_) => {
+                Inferred::new(Width::bit())
+            }
+            Factor::FactorGroup(_) => Inferred::new(Width::Flexible),
+            Factor::QuoteLBraceArrayLiteralListRBrace(_) | Factor::StringLiteral(_) => {
+                Inferred::new(Width::Unknown)
+            }
+        }
+    }
+
+    fn function_call(&mut self, ident: &ExpressionIdentifier, arg: &FunctionCall) -> Inferred {
+        if let Some(ref x) = arg.function_call_opt {
+            self.self_determined(&x.argument_list.argument_item.expression);
+            for x in &x.argument_list.argument_list_list {
+                self.self_determined(&x.argument_item.expression);
+            }
+        }
+
+        let width = match symbol_table::resolve(ident).map(|x| x.found.kind) {
+            Ok(SymbolKind::Function(x)) => x.ret.map(type_width).unwrap_or(Width::Unknown),
+            // system functions like $clog2 return constant value mostly
+            Ok(SymbolKind::SystemFunction) => Width::Flexible,
+            _ => Width::Unknown,
+        };
+        Inferred::new(width)
+    }
+
+    fn concatenation_list(&mut self, arg: &ConcatenationList) -> Width {
+        let mut items = vec![arg.concatenation_item.as_ref()];
+        for x in &arg.concatenation_list_list {
+            items.push(x.concatenation_item.as_ref());
+        }
+
+        let mut ret = 0;
+        let mut unknown = false;
+        for item in items {
+            let width = self.self_determined(&item.expression);
+            let repeat = if let Some(ref x) = item.concatenation_item_opt {
+                match Evaluator::new().expression(&x.expression) {
+                    Evaluated::Fixed { value, .. } => value.try_into().ok(),
+                    _ => None,
+                }
+            } else {
+                Some(1)
+            };
+            match (width, repeat) {
+                (Width::Fixed { width, .. }, Some(repeat)) => ret += width * repeat,
+                _ => unknown = true,
+            }
+        }
+
+        if unknown {
+            Width::Unknown
+        } else {
+            Width::Fixed {
+                width: ret,
+                signed: false,
+            }
+        }
+    }
+
+    fn expression_identifier(&mut self, arg: &ExpressionIdentifier) -> Width {
+        let selects: Vec<&Select> = if let Some(x) = arg.expression_identifier_list0.last() {
+            x.expression_identifier_list0_list
+                .iter()
+                .map(|x| x.select.as_ref())
+                .collect()
+        } else {
+            arg.expression_identifier_list
+                .iter()
+                .map(|x| x.select.as_ref())
+                .collect()
+        };
+        for x in &selects {
+            self.select(x);
+        }
+
+        let Ok(symbol) = symbol_table::resolve(arg) else {
+            return Width::Unknown;
+        };
+        match symbol.found.kind {
+            SymbolKind::Parameter(_) => Width::Flexible,
+            kind => symbol_type(kind)
+                .map(|x| selected_width(x, &selects))
+                .unwrap_or(Width::Unknown),
+        }
+    }
+
+    fn select(&mut self, arg: &Select) {
+        self.self_determined(&arg.expression);
+        if let Some(ref x) = arg.select_opt {
+            self.self_determined(&x.expression);
+        }
+    }
+
+    /// Width of assignment target
+    fn target_width(&mut self, arg: &ExpressionIdentifier) -> Width {
+        let inferred = self.expression_identifier(arg);
+        if let Width::Fixed { .. } = inferred {
+            inferred
+        } else {
+            Width::Unknown
+        }
+    }
+}
+
+impl<'a> Handler for CheckWidth<'a> {
+    fn set_point(&mut self, p: HandlerPoint) {
+        self.point = p;
+    }
+}
+
+fn number_width(arg: &Number) -> Width {
+    match arg {
+        Number::IntegralNumber(x) => match x.integral_number.as_ref() {
+            IntegralNumber::Based(x) => {
+                let text = x.based.based_token.to_string().replace('_', "");
+                if let Some((width, rest)) = text.split_once('\'') {
+                    if let Ok(width) = width.parse() {
+                        return Width::Fixed {
+                            width,
+                            signed: rest.starts_with('s'),
+                        };
+                    }
+                }
+                Width::Flexible
+            }
+            IntegralNumber::BaseLess(_) | IntegralNumber::AllBit(_) => Width::Flexible,
+        },
+        Number::RealNumber(_) => Width::Unknown,
+    }
+}
+
+fn symbol_type(kind: SymbolKind) -> Option<Type> {
+    match kind {
+        SymbolKind::Variable(x) => Some(x.r#type),
+        SymbolKind::Port(x) => x.r#type,
+        SymbolKind::StructMember(x) => Some(x.r#type),
+        SymbolKind::UnionMember(x) => Some(x.r#type),
+        _ => None,
+    }
+}
+
+fn type_width(x: Type) -> Width {
+    // width depending on parameters can't be determined without elaboration
+    if is_parameterized(&x) {
+        return Width::Unknown;
+    }
+
+    let signed = x.modifier.contains(&TypeModifier::Signed)
+        || matches!(x.kind, TypeKind::I32 | TypeKind::I64);
+    match x.kind {
+        TypeKind::Bit
+        | TypeKind::Logic
+        | TypeKind::U32
+        | TypeKind::U64
+        | TypeKind::I32
+        | TypeKind::I64 => {
+            if let Some(width) = Evaluator::new().type_width(x) {
+                Width::Fixed { width, signed }
+            } else {
+                Width::Unknown
+            }
+        }
+        _ => Width::Unknown,
+    }
+}
+
+fn selected_width(mut x: Type, selects: &[&Select]) -> Width {
+    if selects.len() < x.array.len() {
+        return Width::Unknown;
+    }
+    let selects = &selects[x.array.len()..];
+    x.array.clear();
+
+    match selects {
+        [] => type_width(x),
+        [select] => {
+            let Width::Fixed { .. } = type_width(x) else {
+                return Width::Unknown;
+            };
+            let Some(ref opt) = select.select_opt else {
+                return Width::bit();
+            };
+
+            let mut evaluator = Evaluator::new();
+            let Evaluated::Fixed { value: y, .. } = evaluator.expression(&opt.expression) else {
+                return Width::Unknown;
+            };
+            let width = match opt.select_operator.as_ref() {
+                SelectOperator::Colon(_) => {
+                    let Evaluated::Fixed { value: x, .. } =
+                        evaluator.expression(&select.expression)
+                    else {
+                        return Width::Unknown;
+                    };
+                    x.checked_sub(y).and_then(|x| x.checked_add(1))
+                }
+                SelectOperator::PlusColon(_)
+                | SelectOperator::MinusColon(_)
+                | SelectOperator::Step(_) => Some(y),
+            };
+            match width.and_then(|x| x.try_into().ok()) {
+                Some(width) if width > 0 => Width::Fixed {
+                    width,
+                    signed: false,
+                },
+                _ => Width::Unknown,
+            }
+        }
+        _ => Width::Unknown,
+    }
+}
+
+/// Whether the type width depends on any identifier which can be overridden like parameter
+#[derive(Default)]
+struct IdentifierFinder {
+    found: bool,
+    visited: HashSet<SymbolId>,
+}
+
+impl VerylWalker for IdentifierFinder {
+    fn expression_identifier(&mut self, arg: &ExpressionIdentifier) {
+        let Ok(symbol) = symbol_table::resolve(arg) else {
+            self.found = true;
+            return;
+        };
+        if !self.visited.insert(symbol.found.id) {
+            return;
+        }
+        match symbol.found.kind {
+            // const can be evaluated statically if it doesn't depend on parameter
+            SymbolKind::Parameter(ParameterProperty {
+                kind: ParameterKind::Const,
+                value: ParameterValue::Expression(ref x),
+                ..
+            }) => self.expression(x),
+            SymbolKind::EnumMember(_) | SymbolKind::SystemFunction => (),
+            // function in package can't refer parameter
+            SymbolKind::Function(_) => {
+                let mut namespace = symbol.found.namespace.clone();
+                let in_package = namespace.pop().is_some_and(|name| {
+                    let path = SymbolPath::new(&[name]);
+                    symbol_table::resolve((&path, &namespace))
+                        .is_ok_and(|x| matches!(x.found.kind, SymbolKind::Package(_)))
+                });
+                if !in_package {
+                    self.found = true;
+                }
+            }
+            _ => self.found = true,
+        }
+    }
+}
+
+fn is_parameterized(x: &Type) -> bool {
+    let mut finder = IdentifierFinder::default();
+    for x in x.width.iter().chain(x.array.iter()) {
+        finder.expression(x);
+    }
+    finder.found
+}
+
+impl<'a> VerylGrammarTrait for CheckWidth<'a> {
+    fn let_declaration(&mut self, arg: &LetDeclaration) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            let target = match symbol_table::resolve(arg.identifier.as_ref()) {
+                Ok(x) => symbol_type(x.found.kind)
+                    .map(|x| selected_width(x, &[]))
+                    .unwrap_or(Width::Unknown),
+                Err(_) => Width::Unknown,
+            };
+            self.check_assignment(target, &arg.expression);
+        }
+        Ok(())
+    }
+
+    fn let_statement(&mut self, arg: &LetStatement) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            let target = match symbol_table::resolve(arg.identifier.as_ref()) {
+                Ok(x) => symbol_type(x.found.kind)
+                    .map(|x| selected_width(x, &[]))
+                    .unwrap_or(Width::Unknown),
+                Err(_) => Width::Unknown,
+            };
+            self.check_assignment(target, &arg.expression);
+        }
+        Ok(())
+    }
+
+    fn assign_declaration(&mut self, arg: &AssignDeclaration) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            let ident = arg.hierarchical_identifier.as_ref();
+            let selects: Vec<&Select> = if let Some(x) = ident.hierarchical_identifier_list0.last()
+            {
+                x.hierarchical_identifier_list0_list
+                    .iter()
+                    .map(|x| x.select.as_ref())
+                    .collect()
+            } else {
+                ident
+                    .hierarchical_identifier_list
+                    .iter()
+                    .map(|x| x.select.as_ref())
+                    .collect()
+            };
+            let target = match symbol_table::resolve(ident) {
+                Ok(x) => symbol_type(x.found.kind)
+                    .map(|x| selected_width(x, &selects))
+                    .unwrap_or(Width::Unknown),
+                Err(_) => Width::Unknown,
+            };
+            self.check_assignment(target, &arg.expression);
+        }
+        Ok(())
+    }
+
+    fn identifier_statement(&mut self, arg: &IdentifierStatement) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            if let IdentifierStatementGroup::Assignment(x) = &*arg.identifier_statement_group {
+                let target = self.target_width(&arg.expression_identifier);
+                self.check_assignment(target, &x.assignment.expression);
+            }
+        }
+        Ok(())
+    }
+
+    fn inst_declaration(&mut self, arg: &InstDeclaration) -> Result<(), ParolError> {
+        match self.point {
+            HandlerPoint::Before => {
+                self.ports.clear();
+                if let Ok(x) = symbol_table::resolve(arg.scoped_identifier.as_ref()) {
+                    if let SymbolKind::Module(x) = x.found.kind {
+                        for port in &x.ports {
+                            if let Some(x) = port.property().r#type {
+                                self.ports.insert(port.name, x);
+                            }
+                        }
+                    }
+                }
+            }
+            HandlerPoint::After => self.ports.clear(),
+        }
+        Ok(())
+    }
+
+    fn inst_port_item(&mut self, arg: &InstPortItem) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            let name = arg.identifier.identifier_token.token.text;
+            let Some(port) = self.ports.get(&name).cloned() else {
+                return Ok(());
+            };
+            let (width, range): (Width, TokenRange) = if let Some(ref x) = arg.inst_port_item_opt {
+                (
+                    self.self_determined(&x.expression),
+                    x.expression.as_ref().into(),
+                )
+            } else {
+                let width = match symbol_table::resolve(arg.identifier.as_ref()) {
+                    Ok(x) => symbol_type(x.found.kind)
+                        .map(|x| selected_width(x, &[]))
+                        .unwrap_or(Width::Unknown),
+                    Err(_) => Width::Unknown,
+                };
+                (width, arg.identifier.as_ref().into())
+            };
+
+            if let (
+                Width::Fixed {
+                    width: port_width, ..
+                },
+                Width::Fixed { width, .. },
+            ) = (selected_width(port, &[]), width)
+            {
+                if port_width != width
+                    && self.lint_opt.width.mismatch_port_width
+                    && !self.is_allowed(&range, AllowItem::MismatchPortWidth)
+                {
+                    self.errors.push(AnalyzerError::mismatch_port_width(
+                        &name.to_string(),
+                        port_width,
+                        width,
+                        self.text,
+                        &range,
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
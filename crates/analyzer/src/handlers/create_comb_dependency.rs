@@ -0,0 +1,528 @@
+use crate::assign::AssignPath;
+use crate::comb_dependency::{CombDependency, CombSignal};
+use crate::symbol::{SymbolId, SymbolKind};
+use crate::symbol_table;
+use veryl_parser::resource_table::StrId;
+use veryl_parser::veryl_grammar_trait::*;
+use veryl_parser::veryl_token::Token;
+use veryl_parser::veryl_walker::{Handler, HandlerPoint, VerylWalker};
+use veryl_parser::ParolError;
+
+/// Variables assigned on the current path, and whether the assigned value
+/// refers a variable which is assigned in the same block
+type Assigned = Vec<(AssignPath, bool)>;
+
+#[derive(Default)]
+struct Branches {
+    entry: Assigned,
+    results: Vec<Assigned>,
+    /// one of the branches is always taken
+    exhaustive: bool,
+    /// branches are statement blocks of if/for statement
+    by_block: bool,
+    /// nesting of statement blocks in the current branch
+    depth: usize,
+}
+
+fn included(x: &AssignPath, y: &AssignPath) -> bool {
+    x.included(y) || y.included(x)
+}
+
+/// Variables assigned on every path
+fn merge(paths: &[Assigned]) -> Assigned {
+    let mut ret: Assigned = Vec::new();
+    for (path, _) in paths.iter().flatten() {
+        if ret.iter().any(|(x, _)| x == path) {
+            continue;
+        }
+        let mut all = true;
+        let mut live = false;
+        for x in paths {
+            match x.iter().find(|(x, _)| x == path) {
+                Some((_, x)) => live |= x,
+                None => all = false,
+            }
+        }
+        if all {
+            ret.push((path.clone(), live));
+        }
+    }
+    ret
+}
+
+#[derive(Default)]
+pub struct CreateCombDependency {
+    point: HandlerPoint,
+    module: Option<SymbolId>,
+    in_always_comb: bool,
+    conditions: Vec<Vec<CombSignal>>,
+    assigned: Assigned,
+    targets: Vec<AssignPath>,
+    branches: Vec<Branches>,
+    instance: Option<(SymbolId, Token)>,
+    connects: Vec<(StrId, Vec<CombSignal>)>,
+}
+
+impl CreateCombDependency {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    fn add_dependencies(&mut self, target: CombSignal, sources: Vec<CombSignal>, token: &Token) {
+        let Some(module) = self.module else {
+            return;
+        };
+        for source in sources {
+            symbol_table::add_comb_dependency(CombDependency::Assign {
+                module,
+                source,
+                target: target.clone(),
+                token: *token,
+            });
+        }
+    }
+
+    /// Add dependencies of assignment in always_comb
+    fn add_statement_dependencies(
+        &mut self,
+        target: CombSignal,
+        mut sources: Vec<CombSignal>,
+        token: &Token,
+    ) {
+        sources.extend(self.conditions.iter().flatten().cloned());
+
+        // variables assigned previously on every path are not feedback
+        // unless the assigned value refers a value before assignment
+        let mut live = false;
+        sources.retain(|x| {
+            let assigned: Vec<_> = self
+                .assigned
+                .iter()
+                .filter(|(y, _)| included(&x.path, y))
+                .collect();
+            if assigned.is_empty() {
+                live |= self.targets.iter().any(|y| included(&x.path, y));
+                true
+            } else {
+                let keep = assigned.iter().any(|(_, x)| *x);
+                live |= keep;
+                keep
+            }
+        });
+
+        self.assign(target.path.clone(), live);
+        self.add_dependencies(target, sources, token);
+    }
+
+    fn assign(&mut self, path: AssignPath, live: bool) {
+        self.assigned.retain(|(x, _)| *x != path);
+        self.assigned.push((path, live));
+    }
+
+    fn push_branches(&mut self, exhaustive: bool, by_block: bool) {
+        if self.in_always_comb {
+            self.branches.push(Branches {
+                entry: self.assigned.clone(),
+                exhaustive,
+                by_block,
+                depth: 0,
+                results: Vec::new(),
+            });
+        }
+    }
+
+    fn pop_branches(&mut self) {
+        if !self.in_always_comb {
+            return;
+        }
+        if let Some(mut x) = self.branches.pop() {
+            if !x.exhaustive || x.results.is_empty() {
+                x.results.push(x.entry);
+            }
+            self.assigned = merge(&x.results);
+        }
+    }
+
+    fn begin_branch(&mut self) {
+        if let Some(x) = self.branches.last() {
+            self.assigned = x.entry.clone();
+        }
+    }
+
+    fn end_branch(&mut self) {
+        if let Some(x) = self.branches.last_mut() {
+            x.results.push(self.assigned.clone());
+        }
+    }
+}
+
+impl Handler for CreateCombDependency {
+    fn set_point(&mut self, p: HandlerPoint) {
+        self.point = p;
+    }
+}
+
+fn is_signal(full_path: &[SymbolId]) -> bool {
+    full_path.iter().any(|x| {
+        symbol_table::get(*x)
+            .map(|x| matches!(x.kind, SymbolKind::Variable(_) | SymbolKind::Port(_)))
+            .unwrap_or(false)
+    })
+}
+
+/// Signals referred in the syntax tree
+#[derive(Default)]
+struct References {
+    signals: Vec<CombSignal>,
+}
+
+impl VerylWalker for References {
+    fn expression_identifier(&mut self, arg: &ExpressionIdentifier) {
+        if let Ok(x) = symbol_table::resolve(arg) {
+            if is_signal(&x.full_path) {
+                let partial = !arg.expression_identifier_list.is_empty()
+                    || arg
+                        .expression_identifier_list0
+                        .iter()
+                        .any(|x| !x.expression_identifier_list0_list.is_empty());
+                self.signals.push(CombSignal {
+                    path: AssignPath(x.full_path),
+                    partial,
+                });
+            }
+        }
+
+        // index expressions are also referred
+        for x in &arg.expression_identifier_list {
+            self.select(&x.select);
+        }
+        for x in &arg.expression_identifier_list0 {
+            for x in &x.expression_identifier_list0_list {
+                self.select(&x.select);
+            }
+        }
+    }
+}
+
+/// Variables assigned in always_comb
+#[derive(Default)]
+struct Targets {
+    paths: Vec<AssignPath>,
+}
+
+impl VerylWalker for Targets {
+    fn let_statement(&mut self, arg: &LetStatement) {
+        if let Ok(x) = symbol_table::resolve(arg.identifier.as_ref()) {
+            self.paths.push(AssignPath(x.full_path));
+        }
+    }
+
+    fn identifier_statement(&mut self, arg: &IdentifierStatement) {
+        if let IdentifierStatementGroup::Assignment(_) = &*arg.identifier_statement_group {
+            if let Some((target, _)) = target_references(&arg.expression_identifier) {
+                self.paths.push(target.path);
+            }
+        }
+    }
+}
+
+fn expression_references(arg: &Expression) -> Vec<CombSignal> {
+    let mut references = References::default();
+    references.expression(arg);
+    references.signals
+}
+
+/// Assignment target and references in its selects
+fn target_references(arg: &ExpressionIdentifier) -> Option<(CombSignal, Vec<CombSignal>)> {
+    let mut references = References::default();
+    references.expression_identifier(arg);
+    let mut signals = references.signals.into_iter();
+    let target = signals.next()?;
+    Some((target, signals.collect()))
+}
+
+impl VerylGrammarTrait for CreateCombDependency {
+    fn module_declaration(&mut self, arg: &ModuleDeclaration) -> Result<(), ParolError> {
+        match self.point {
+            HandlerPoint::Before => {
+                self.module = symbol_table::resolve(arg.identifier.as_ref())
+                    .ok()
+                    .map(|x| x.found.id);
+            }
+            HandlerPoint::After => self.module = None,
+        }
+        Ok(())
+    }
+
+    fn always_comb_declaration(&mut self, arg: &AlwaysCombDeclaration) -> Result<(), ParolError> {
+        match self.point {
+            HandlerPoint::Before => {
+                self.in_always_comb = true;
+                self.assigned.clear();
+                let mut targets = Targets::default();
+                targets.always_comb_declaration(arg);
+                self.targets = targets.paths;
+            }
+            HandlerPoint::After => {
+                self.in_always_comb = false;
+                self.conditions.clear();
+                self.branches.clear();
+            }
+        }
+        Ok(())
+    }
+
+    fn let_declaration(&mut self, arg: &LetDeclaration) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            if let Ok(x) = symbol_table::resolve(arg.identifier.as_ref()) {
+                let target = CombSignal {
+                    path: AssignPath(x.full_path),
+                    partial: false,
+                };
+                let sources = expression_references(&arg.expression);
+                self.add_dependencies(target, sources, &arg.r#let.let_token.token);
+            }
+        }
+        Ok(())
+    }
+
+    fn assign_declaration(&mut self, arg: &AssignDeclaration) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            if let Ok(x) = symbol_table::resolve(arg.hierarchical_identifier.as_ref()) {
+                let ident = arg.hierarchical_identifier.as_ref();
+                let partial = !ident.hierarchical_identifier_list.is_empty()
+                    || ident
+                        .hierarchical_identifier_list0
+                        .iter()
+                        .any(|x| !x.hierarchical_identifier_list0_list.is_empty());
+                let target = CombSignal {
+                    path: AssignPath(x.full_path),
+                    partial,
+                };
+
+                let mut references = References::default();
+                for x in &ident.hierarchical_identifier_list {
+                    references.select(&x.select);
+                }
+                for x in &ident.hierarchical_identifier_list0 {
+                    for x in &x.hierarchical_identifier_list0_list {
+                        references.select(&x.select);
+                    }
+                }
+                references.expression(&arg.expression);
+                self.add_dependencies(target, references.signals, &arg.assign.assign_token.token);
+            }
+        }
+        Ok(())
+    }
+
+    fn let_statement(&mut self, arg: &LetStatement) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            if !self.in_always_comb {
+                return Ok(());
+            }
+            if let Ok(x) = symbol_table::resolve(arg.identifier.as_ref()) {
+                let target = CombSignal {
+                    path: AssignPath(x.full_path),
+                    partial: false,
+                };
+                let sources = expression_references(&arg.expression);
+                self.add_statement_dependencies(target, sources, &arg.equ.equ_token.token);
+            }
+        }
+        Ok(())
+    }
+
+    fn identifier_statement(&mut self, arg: &IdentifierStatement) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            if !self.in_always_comb {
+                return Ok(());
+            }
+            if let IdentifierStatementGroup::Assignment(x) = &*arg.identifier_statement_group {
+                let Some((target, mut sources)) = target_references(&arg.expression_identifier)
+                else {
+                    return Ok(());
+                };
+                let token = match x.assignment.assignment_group.as_ref() {
+                    AssignmentGroup::Equ(x) => x.equ.equ_token.token,
+                    AssignmentGroup::AssignmentOperator(x) => {
+                        // compound assignment refers the target itself
+                        sources.push(target.clone());
+                        x.assignment_operator.assignment_operator_token.token
+                    }
+                };
+                sources.append(&mut expression_references(&x.assignment.expression));
+                self.add_statement_dependencies(target, sources, &token);
+            }
+        }
+        Ok(())
+    }
+
+    fn if_statement(&mut self, arg: &IfStatement) -> Result<(), ParolError> {
+        match self.point {
+            HandlerPoint::Before => {
+                let mut conditions = expression_references(&arg.expression);
+                for x in &arg.if_statement_list {
+                    conditions.append(&mut expression_references(&x.expression));
+                }
+                self.conditions.push(conditions);
+                self.push_branches(arg.if_statement_opt.is_some(), true);
+            }
+            HandlerPoint::After => {
+                self.conditions.pop();
+                self.pop_branches();
+            }
+        }
+        Ok(())
+    }
+
+    fn case_statement(&mut self, arg: &CaseStatement) -> Result<(), ParolError> {
+        match self.point {
+            HandlerPoint::Before => {
+                self.conditions.push(expression_references(&arg.expression));
+                let exhaustive = arg
+                    .case_statement_list
+                    .iter()
+                    .any(|x| matches!(*x.case_item.case_item_group, CaseItemGroup::Defaul(_)));
+                self.push_branches(exhaustive, false);
+            }
+            HandlerPoint::After => {
+                self.conditions.pop();
+                self.pop_branches();
+            }
+        }
+        Ok(())
+    }
+
+    fn case_item(&mut self, arg: &CaseItem) -> Result<(), ParolError> {
+        match self.point {
+            HandlerPoint::Before => {
+                let mut references = References::default();
+                if let CaseItemGroup::CaseCondition(x) = &*arg.case_item_group {
+                    references.case_condition(&x.case_condition);
+                }
+                self.conditions.push(references.signals);
+                self.begin_branch();
+            }
+            HandlerPoint::After => {
+                self.conditions.pop();
+                self.end_branch();
+            }
+        }
+        Ok(())
+    }
+
+    fn switch_item(&mut self, arg: &SwitchItem) -> Result<(), ParolError> {
+        match self.point {
+            HandlerPoint::Before => {
+                let mut references = References::default();
+                if let SwitchItemGroup::SwitchCondition(x) = &*arg.switch_item_group {
+                    references.switch_condition(&x.switch_condition);
+                }
+                self.conditions.push(references.signals);
+                self.begin_branch();
+            }
+            HandlerPoint::After => {
+                self.conditions.pop();
+                self.end_branch();
+            }
+        }
+        Ok(())
+    }
+
+    fn switch_statement(&mut self, arg: &SwitchStatement) -> Result<(), ParolError> {
+        match self.point {
+            HandlerPoint::Before => {
+                let exhaustive = arg.switch_statement_list.iter().any(|x| {
+                    matches!(*x.switch_item.switch_item_group, SwitchItemGroup::Defaul(_))
+                });
+                self.push_branches(exhaustive, false);
+            }
+            HandlerPoint::After => self.pop_branches(),
+        }
+        Ok(())
+    }
+
+    fn for_statement(&mut self, arg: &ForStatement) -> Result<(), ParolError> {
+        match self.point {
+            HandlerPoint::Before => {
+                if let Ok(x) = symbol_table::resolve(arg.identifier.as_ref()) {
+                    self.assign(AssignPath(x.full_path), false);
+                }
+                // the loop body may not be executed
+                self.push_branches(false, true);
+            }
+            HandlerPoint::After => self.pop_branches(),
+        }
+        Ok(())
+    }
+
+    fn statement_block(&mut self, _arg: &StatementBlock) -> Result<(), ParolError> {
+        let Some(branches) = self.branches.last_mut().filter(|x| x.by_block) else {
+            return Ok(());
+        };
+        match self.point {
+            HandlerPoint::Before => {
+                branches.depth += 1;
+                if branches.depth == 1 {
+                    self.begin_branch();
+                }
+            }
+            HandlerPoint::After => {
+                branches.depth -= 1;
+                if branches.depth == 0 {
+                    self.end_branch();
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn inst_declaration(&mut self, arg: &InstDeclaration) -> Result<(), ParolError> {
+        match self.point {
+            HandlerPoint::Before => {
+                self.connects.clear();
+                self.instance = symbol_table::resolve(arg.scoped_identifier.as_ref())
+                    .ok()
+                    .filter(|x| matches!(x.found.kind, SymbolKind::Module(_)))
+                    .map(|x| (x.found.id, arg.identifier.identifier_token.token));
+            }
+            HandlerPoint::After => {
+                if let (Some(module), Some((r#type, token))) = (self.module, self.instance.take()) {
+                    symbol_table::add_comb_dependency(CombDependency::Instance {
+                        module,
+                        r#type,
+                        connects: self.connects.drain(..).collect(),
+                        token,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn inst_port_item(&mut self, arg: &InstPortItem) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            if self.instance.is_none() {
+                return Ok(());
+            }
+            let signals = if let Some(ref x) = arg.inst_port_item_opt {
+                expression_references(&x.expression)
+            } else if let Ok(x) = symbol_table::resolve(arg.identifier.as_ref()) {
+                if is_signal(&x.full_path) {
+                    vec![CombSignal {
+                        path: AssignPath(x.full_path),
+                        partial: false,
+                    }]
+                } else {
+                    vec![]
+                }
+            } else {
+                vec![]
+            };
+            self.connects
+                .push((arg.identifier.identifier_token.token.text, signals));
+        }
+        Ok(())
+    }
+}
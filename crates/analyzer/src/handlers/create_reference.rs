@@ -1,13 +1,15 @@
 use crate::analyzer_error::AnalyzerError;
+use crate::fix::Fix;
 use crate::namespace::Namespace;
 use crate::namespace_table;
 use crate::symbol::{GenericMap, SymbolKind};
 use crate::symbol_path::{GenericSymbolPath, SymbolPath};
 use crate::symbol_table::{self, ResolveError, ResolveErrorCause};
-use veryl_parser::resource_table::TokenId;
+use veryl_parser::first_token::FirstToken;
+use veryl_parser::resource_table::{StrId, TokenId};
 use veryl_parser::veryl_grammar_trait::*;
-use veryl_parser::veryl_token::TokenRange;
-use veryl_parser::veryl_walker::{Handler, HandlerPoint};
+use veryl_parser::veryl_token::{Token, TokenRange};
+use veryl_parser::veryl_walker::{Handler, HandlerPoint, VerylWalker};
 use veryl_parser::ParolError;
 
 #[derive(Default)]
@@ -18,6 +20,8 @@ pub struct CreateReference<'a> {
     top_level: bool,
     file_scope_imported_items: Vec<TokenId>,
     file_scope_imported_packages: Vec<Namespace>,
+    description_start: Option<Token>,
+    package_names: Option<Vec<StrId>>,
 }
 
 impl<'a> CreateReference<'a> {
@@ -46,15 +50,81 @@ impl<'a> CreateReference<'a> {
             }
         } else if let ResolveErrorCause::NotFound(not_found) = err.cause {
             let name = format!("{}", not_found);
-            self.errors
-                .push(AnalyzerError::undefined_identifier(&name, self.text, token));
+            let mut error = AnalyzerError::undefined_identifier(&name, self.text, token);
+            for fix in self.import_fixes(not_found, token) {
+                error = error.with_fix(Some(fix));
+            }
+            self.errors.push(error);
         } else {
             unreachable!();
         }
     }
 
+    /// Import the identifier from packages which define it
+    fn import_fixes(&mut self, name: StrId, token: &TokenRange) -> Vec<Fix> {
+        let (Some(description), Some(namespace)) =
+            (self.description_start, namespace_table::get(token.beg.id))
+        else {
+            return vec![];
+        };
+
+        // Package names are collected once because scanning all symbols is expensive
+        let package_names = self.package_names.get_or_insert_with(|| {
+            let mut ret: Vec<_> = symbol_table::get_all()
+                .into_iter()
+                .filter(|x| {
+                    matches!(x.kind, SymbolKind::Package(_)) && x.generic_parameters().is_empty()
+                })
+                .map(|x| x.token.text)
+                .collect();
+            ret.sort();
+            ret.dedup();
+            ret
+        });
+
+        let mut packages: Vec<_> = package_names
+            .iter()
+            .filter(|x| {
+                let path = SymbolPath::new(&[**x, name]);
+                symbol_table::resolve((&path, &namespace)).is_ok()
+            })
+            .map(|x| x.to_string())
+            .collect();
+        packages.sort();
+
+        packages
+            .iter()
+            .filter_map(|x| {
+                let import = format!("import {x}::{name};");
+                Fix::insert_before(
+                    &format!("Add `{import}`"),
+                    &description,
+                    &format!("{import}\n"),
+                )
+                .map(Fix::suggestion)
+            })
+            .collect()
+    }
+
+    /// Refer generic parameters in the path which is resolved at instantiation
+    fn generic_parameter_reference(&self, path: &GenericSymbolPath) {
+        let args = path.paths.iter().flat_map(|x| x.arguments.iter());
+        let heads = std::iter::once(path)
+            .chain(args)
+            .filter(|x| x.is_resolvable())
+            .map(|x| &x.paths[0].base);
+        for token in heads {
+            if let Ok(symbol) = symbol_table::resolve(token) {
+                if matches!(symbol.found.kind, SymbolKind::GenericParameter(_)) {
+                    symbol_table::add_reference(symbol.found.id, token);
+                }
+            }
+        }
+    }
+
     fn generic_symbol_path(&mut self, path: &GenericSymbolPath, namespace: &Namespace) {
         if path.is_generic_reference() {
+            self.generic_parameter_reference(path);
             return;
         }
 
@@ -63,7 +133,7 @@ impl<'a> CreateReference<'a> {
 
             match symbol_table::resolve((&base_path, namespace)) {
                 Ok(symbol) => {
-                    symbol_table::add_reference(symbol.found.id, &path.paths[0].base);
+                    symbol_table::add_reference(symbol.found.id, &path.paths[i].base);
 
                     // Check number of arguments
                     let params = symbol.found.generic_parameters();
@@ -135,8 +205,12 @@ impl<'a> VerylGrammarTrait for CreateReference<'a> {
         if let HandlerPoint::Before = self.point {
             match symbol_table::resolve(arg) {
                 Ok(symbol) => {
-                    for id in symbol.full_path {
-                        symbol_table::add_reference(id, &arg.identifier.identifier_token.token);
+                    let mut tokens = vec![arg.identifier.identifier_token.token];
+                    for x in &arg.hierarchical_identifier_list0 {
+                        tokens.push(x.identifier.identifier_token.token);
+                    }
+                    for (id, token) in symbol.full_path.iter().zip(tokens.iter()) {
+                        symbol_table::add_reference(*id, token);
                     }
                 }
                 Err(err) => {
@@ -177,7 +251,10 @@ impl<'a> VerylGrammarTrait for CreateReference<'a> {
 
                 match symbol_table::resolve((&path, &namespace)) {
                     Ok(symbol) => {
-                        symbol_table::add_reference(symbol.found.id, &ident);
+                        symbol_table::add_reference(
+                            symbol.found.id,
+                            &x.identifier.identifier_token.token,
+                        );
                     }
                     Err(err) => {
                         self.push_resolve_error(err, &arg.into());
@@ -204,6 +281,39 @@ impl<'a> VerylGrammarTrait for CreateReference<'a> {
         Ok(())
     }
 
+    fn inst_declaration(&mut self, arg: &InstDeclaration) -> Result<(), ParolError> {
+        // Should be executed after scoped_identifier to resolve generic instance
+        if let HandlerPoint::After = self.point {
+            let Some(ref x) = arg.inst_declaration_opt1 else {
+                return Ok(());
+            };
+            let Some(ref x) = x.inst_declaration_opt2 else {
+                return Ok(());
+            };
+            let Ok(symbol) = symbol_table::resolve(arg.scoped_identifier.as_ref()) else {
+                return Ok(());
+            };
+            let symbol = match symbol.found.kind {
+                SymbolKind::GenericInstance(ref x) => symbol_table::get(x.base),
+                _ => Some(symbol.found),
+            };
+            let Some(SymbolKind::Module(module)) = symbol.map(|x| x.kind) else {
+                return Ok(());
+            };
+
+            // port name of connection refers the port of the instantiated module
+            // (identifier of implicit connection refers both the port and the connected variable)
+            let items: Vec<InstPortItem> = x.inst_port_list.as_ref().into();
+            for item in &items {
+                let token = &item.identifier.identifier_token.token;
+                if let Some(port) = module.ports.iter().find(|x| x.name == token.text) {
+                    symbol_table::add_reference(port.symbol, token);
+                }
+            }
+        }
+        Ok(())
+    }
+
     fn inst_port_item(&mut self, arg: &InstPortItem) -> Result<(), ParolError> {
         if let HandlerPoint::Before = self.point {
             // implicit port connection by name
@@ -223,6 +333,15 @@ impl<'a> VerylGrammarTrait for CreateReference<'a> {
         Ok(())
     }
 
+    fn description_group(&mut self, arg: &DescriptionGroup) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            let mut first_token = FirstToken::default();
+            first_token.description_group(arg);
+            self.description_start = *first_token.token();
+        }
+        Ok(())
+    }
+
     fn module_declaration(&mut self, arg: &ModuleDeclaration) -> Result<(), ParolError> {
         match self.point {
             HandlerPoint::Before => {
@@ -4,6 +4,8 @@ use crate::attribute::{AllowItem, EnumEncodingItem};
 use crate::attribute_table;
 use crate::evaluator::Evaluated;
 use crate::evaluator::Evaluator;
+use crate::fix::Fix;
+use crate::function_table;
 use crate::namespace::Namespace;
 use crate::namespace_table;
 use crate::symbol;
@@ -959,11 +961,29 @@ impl<'a> VerylGrammarTrait for CreateSymbolTable<'a> {
                     self.generic_parameters.last_mut().unwrap().push(id);
                 }
             } else {
-                self.errors.push(AnalyzerError::missing_default_argument(
-                    &arg.identifier.identifier_token.token.to_string(),
-                    self.text,
-                    &arg.identifier.as_ref().into(),
-                ));
+                let fix = match arg.generic_bound.as_ref() {
+                    GenericBound::Const(x) => Fix::insert_after(
+                        "Add default argument",
+                        &x.r#const.const_token.token,
+                        " = 0",
+                    ),
+                    GenericBound::Type(x) => Fix::insert_after(
+                        "Add default argument",
+                        &x.r#type.type_token.token,
+                        " = logic",
+                    ),
+                    GenericBound::ScopedIdentifier(_) => None,
+                };
+                // the default value is a placeholder which should be chosen by user
+                let fix = fix.map(Fix::suggestion);
+                self.errors.push(
+                    AnalyzerError::missing_default_argument(
+                        &arg.identifier.identifier_token.token.to_string(),
+                        self.text,
+                        &arg.identifier.as_ref().into(),
+                    )
+                    .with_fix(fix),
+                );
             }
         }
         Ok(())
@@ -1082,6 +1102,7 @@ impl<'a> VerylGrammarTrait for CreateSymbolTable<'a> {
                 ) {
                     self.function_ids
                         .insert(arg.identifier.identifier_token.token.text, id);
+                    function_table::insert(arg.identifier.identifier_token.token.id, arg);
                 }
             }
         }
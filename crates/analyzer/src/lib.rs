@@ -3,12 +3,20 @@ pub mod analyzer_error;
 pub mod assign;
 pub mod attribute;
 pub mod attribute_table;
+pub mod cdc;
+pub mod comb_dependency;
+pub mod elaborator;
 pub mod evaluator;
+pub mod fix;
+pub mod fsm;
+pub mod function_table;
 pub mod handlers;
+pub mod keyword;
 pub mod msb_table;
 pub mod namespace;
 pub mod namespace_table;
 pub mod range_table;
+pub mod rename;
 pub mod symbol;
 pub mod symbol_path;
 pub mod symbol_table;
@@ -0,0 +1,150 @@
+use crate::keyword::KEYWORDS;
+use crate::symbol::{Symbol, SymbolId, SymbolKind};
+use crate::symbol_path::SymbolPath;
+use crate::symbol_table::{self, is_sv_keyword};
+use veryl_parser::resource_table;
+use veryl_parser::veryl_token::{Token, TokenSource};
+
+/// Replacement of a token by rename
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RenameEdit {
+    pub token: Token,
+    pub text: String,
+}
+
+/// Rename the symbol and the symbols which should be renamed together.
+/// The new name is rejected if it is not a valid identifier or is already defined.
+pub fn rename(symbol: &Symbol, new_name: &str) -> Result<Vec<RenameEdit>, String> {
+    let is_identifier = {
+        let name = new_name.strip_prefix("r#").unwrap_or(new_name);
+        let mut chars = name.chars();
+        chars
+            .next()
+            .map(|x| x.is_ascii_alphabetic() || x == '_')
+            .unwrap_or(false)
+            && chars.all(|x| x.is_ascii_alphanumeric() || x == '_' || x == '$')
+    };
+    if !is_identifier {
+        return Err(format!("\"{new_name}\" is not a valid identifier"));
+    }
+    if KEYWORDS.contains(&new_name) {
+        return Err(format!("\"{new_name}\" is a keyword"));
+    }
+    let sv_name = new_name.strip_prefix("r#").unwrap_or(new_name);
+    if is_sv_keyword(sv_name) {
+        return Err(format!("\"{new_name}\" is a SystemVerilog keyword"));
+    }
+
+    let symbols = rename_group(symbol);
+    let new_text = resource_table::insert_str(new_name);
+    let all = symbol_table::get_all();
+    for x in &symbols {
+        let mut names = vec![(new_text, x.namespace.clone())];
+
+        // enum member is also emitted as "{prefix}_{name}" outside of enum
+        if let SymbolKind::EnumMember(ref property) = x.kind {
+            let mangled = format!("{}_{new_name}", property.prefix);
+            let mut namespace = x.namespace.clone();
+            namespace.pop();
+            names.push((resource_table::insert_str(&mangled), namespace));
+        }
+
+        for (name, namespace) in &names {
+            let collided = all.iter().any(|y| {
+                y.token.text == *name
+                    && y.namespace.matched(namespace)
+                    && !symbols.iter().any(|z| z.id == y.id)
+            });
+            if collided {
+                return Err(format!("\"{}\" is already defined", name));
+            }
+        }
+    }
+
+    let mut tokens: Vec<Token> = Vec::new();
+    for x in &symbols {
+        let candidates = std::iter::once(&x.token).chain(x.references.iter());
+        for token in candidates {
+            let duplicated = tokens
+                .iter()
+                .any(|y| y.source == token.source && y.pos == token.pos);
+            if token.text == x.token.text && !duplicated {
+                tokens.push(*token);
+            }
+        }
+    }
+
+    let mut ret = Vec::new();
+    for token in tokens {
+        if !matches!(token.source, TokenSource::File(_)) {
+            continue;
+        }
+        // implicit connection is expanded to keep the other side of the connection
+        let text = match implicit_connection(&token, &all) {
+            Some((port, variable)) => {
+                let old_name = token.to_string();
+                let name = |id: SymbolId| {
+                    if symbols.iter().any(|x| x.id == id) {
+                        new_name.to_string()
+                    } else {
+                        old_name.clone()
+                    }
+                };
+                format!("{}: {}", name(port), name(variable))
+            }
+            None => new_name.to_string(),
+        };
+        ret.push(RenameEdit { token, text });
+    }
+    Ok(ret)
+}
+
+/// Symbols which should be renamed together
+pub fn rename_group(symbol: &Symbol) -> Vec<Symbol> {
+    // modport members have the same name as variables or functions of the interface
+    let base = match symbol.kind {
+        SymbolKind::ModportVariableMember(_) | SymbolKind::ModportFunctionMember(_) => {
+            let mut namespace = symbol.namespace.clone();
+            namespace.pop();
+            let path = SymbolPath::new(&[symbol.token.text]);
+            match symbol_table::resolve((&path, &namespace)) {
+                Ok(x) => x.found,
+                Err(_) => return vec![symbol.clone()],
+            }
+        }
+        _ => symbol.clone(),
+    };
+
+    let mut ret = vec![base.clone()];
+    if matches!(base.kind, SymbolKind::Variable(_) | SymbolKind::Function(_)) {
+        for x in symbol_table::get_all() {
+            let is_member = matches!(
+                x.kind,
+                SymbolKind::ModportVariableMember(_) | SymbolKind::ModportFunctionMember(_)
+            );
+            let mut namespace = x.namespace.clone();
+            namespace.pop();
+            if is_member && x.token.text == base.token.text && namespace.matched(&base.namespace) {
+                ret.push(x);
+            }
+        }
+    }
+    ret
+}
+
+/// Port and connected variable if the token is an implicit connection like `inst u: M (a);`
+fn implicit_connection(token: &Token, symbols: &[Symbol]) -> Option<(SymbolId, SymbolId)> {
+    let refers = |x: &Symbol| {
+        x.references
+            .iter()
+            .any(|y| y.source == token.source && y.pos == token.pos)
+    };
+    let variable = symbol_table::resolve(token).ok()?.found;
+    if !refers(&variable) {
+        return None;
+    }
+    let port = symbols
+        .iter()
+        .find(|x| x.id != variable.id && matches!(x.kind, SymbolKind::Port(_)) && refers(x))?;
+    Some((port.id, variable.id))
+}
@@ -151,9 +151,24 @@ impl Symbol {
                         ParameterValue::TypeExpression(_) => Evaluated::Unknown,
                     }
                 }
-                SymbolKind::EnumMember(_) => {
-                    // TODO: Actually Evaluate its Width
-                    Evaluated::UnknownStatic
+                SymbolKind::EnumMember(x) => {
+                    // width of member is defined by the enum which has it
+                    let mut namespace = self.namespace.clone();
+                    let width = namespace.pop().and_then(|name| {
+                        let path = SymbolPath::new(&[name]);
+                        match symbol_table::resolve((&path, &namespace)).ok()?.found.kind {
+                            SymbolKind::Enum(x) if x.width != 0 => Some(x.width),
+                            _ => None,
+                        }
+                    });
+                    if let (Some(value), Some(width)) = (x.value.value(), width) {
+                        Evaluated::Fixed {
+                            width,
+                            value: value as isize,
+                        }
+                    } else {
+                        Evaluated::UnknownStatic
+                    }
                 }
                 _ => Evaluated::Unknown,
             };
@@ -1,4 +1,5 @@
 use crate::assign::{Assign, AssignPath, AssignPosition};
+use crate::comb_dependency::CombDependency;
 use crate::evaluator::Evaluated;
 use crate::namespace::Namespace;
 use crate::symbol::{DocComment, Symbol, SymbolId, SymbolKind, TypeKind};
@@ -42,6 +43,7 @@ pub struct SymbolTable {
     symbol_table: HashMap<SymbolId, Symbol>,
     project_local_table: HashMap<StrId, HashMap<StrId, StrId>>,
     assign_list: Vec<Assign>,
+    comb_dependency_list: Vec<CombDependency>,
 }
 
 impl SymbolTable {
@@ -360,6 +362,12 @@ impl SymbolTable {
         for (_, symbol) in self.symbol_table.iter_mut() {
             symbol.references.retain(|x| x.source != file_path);
         }
+
+        self.assign_list
+            .retain(|x| x.position.0.iter().all(|x| x.token().source != file_path));
+
+        self.comb_dependency_list
+            .retain(|x| x.token().source != file_path);
     }
 
     pub fn add_reference(&mut self, target: SymbolId, token: &Token) {
@@ -427,6 +435,14 @@ impl SymbolTable {
         self.assign_list.clone()
     }
 
+    pub fn add_comb_dependency(&mut self, dependency: CombDependency) {
+        self.comb_dependency_list.push(dependency);
+    }
+
+    pub fn get_comb_dependency_list(&self) -> Vec<CombDependency> {
+        self.comb_dependency_list.clone()
+    }
+
     pub fn clear(&mut self) {
         self.clone_from(&Self::new());
     }
@@ -1035,6 +1051,14 @@ pub fn get_assign_list() -> Vec<Assign> {
     SYMBOL_TABLE.with(|f| f.borrow_mut().get_assign_list())
 }
 
+pub fn add_comb_dependency(dependency: CombDependency) {
+    SYMBOL_TABLE.with(|f| f.borrow_mut().add_comb_dependency(dependency))
+}
+
+pub fn get_comb_dependency_list() -> Vec<CombDependency> {
+    SYMBOL_TABLE.with(|f| f.borrow().get_comb_dependency_list())
+}
+
 pub fn clear() {
     SYMBOL_TABLE.with(|f| f.borrow_mut().clear())
 }
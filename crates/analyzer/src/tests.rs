@@ -1,15 +1,25 @@
-use crate::{symbol_table, Analyzer, AnalyzerError};
-use veryl_metadata::Metadata;
+use crate::evaluator::Evaluated;
+use crate::{
+    cdc, elaborator, fix, fsm, namespace_table, symbol_table, type_dag, Analyzer, AnalyzerError,
+};
+use std::path::PathBuf;
+use veryl_metadata::{Metadata, TargetLanguage};
+use veryl_parser::resource_table::{self, PathId};
 use veryl_parser::Parser;
 
 #[track_caller]
 fn analyze(code: &str) -> Vec<AnalyzerError> {
-    symbol_table::clear();
-
     let metadata: Metadata =
         toml::from_str(&Metadata::create_default_toml("prj").unwrap()).unwrap();
+    analyze_with_metadata(code, &metadata)
+}
+
+#[track_caller]
+fn analyze_with_metadata(code: &str, metadata: &Metadata) -> Vec<AnalyzerError> {
+    symbol_table::clear();
+
     let parser = Parser::parse(&code, &"").unwrap();
-    let analyzer = Analyzer::new(&metadata);
+    let analyzer = Analyzer::new(metadata);
 
     let mut errors = vec![];
     errors.append(&mut analyzer.analyze_pass1(&"prj", &code, &"", &parser.veryl));
@@ -447,6 +457,235 @@ fn cyclic_type_dependency() {
     ));
 }
 
+#[test]
+fn combinational_loop() {
+    let code = r#"
+    module ModuleA {
+        var a: logic;
+        var b: logic;
+        assign a = b;
+        assign b = a;
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(matches!(errors[0], AnalyzerError::CombinationalLoop { .. }));
+
+    let code = r#"
+    module ModuleB (
+        i_a: input  logic,
+        o_b: output logic,
+    ) {
+        var a: logic;
+        always_comb {
+            if i_a {
+                a = 1;
+            } else {
+                a = o_b;
+            }
+        }
+        assign o_b = a;
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(matches!(errors[0], AnalyzerError::CombinationalLoop { .. }));
+
+    let code = r#"
+    module ModuleC (
+        i_a: input  logic,
+        o_b: output logic,
+    ) {
+        assign o_b = ~i_a;
+    }
+    module ModuleD {
+        var a: logic;
+        inst u: ModuleC (
+            i_a: a,
+            o_b: a,
+        );
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(matches!(errors[0], AnalyzerError::CombinationalLoop { .. }));
+
+    let code = r#"
+    module ModuleE (
+        i_clk: input clock,
+        i_rst: input reset,
+    ) {
+        var a: logic;
+        var b: logic;
+        always_ff {
+            if_reset {
+                a = 0;
+            } else {
+                a = b;
+            }
+        }
+        assign b = ~a;
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(errors.is_empty());
+
+    let code = r#"
+    module ModuleF (
+        i_a: input  logic<4>,
+        o_b: output logic<3>,
+    ) {
+        var a: logic<3>;
+        always_comb {
+            a = 0;
+            for i: u32 in 0..4 {
+                a += i_a[i];
+            }
+        }
+        assign o_b = a;
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(errors.is_empty());
+
+    let code = r#"
+    module ModuleG (
+        i_c: input logic,
+    ) {
+        var a: logic;
+        var b: logic;
+        always_comb {
+            if i_c {
+                a = 0;
+            }
+            b = a;
+            a = b;
+        }
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(matches!(
+        &errors[0],
+        AnalyzerError::CombinationalLoop { path, .. } if path == "a -> b -> a"
+    ));
+
+    let code = r#"
+    module ModuleH (
+        i_c: input logic,
+    ) {
+        var a: logic;
+        var b: logic;
+        always_comb {
+            if i_c {
+                a = 0;
+            } else {
+                a = 1;
+            }
+            b = a;
+            a = b;
+        }
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(errors.is_empty());
+}
+
+#[test]
+fn width_check() {
+    let mut metadata: Metadata =
+        toml::from_str(&Metadata::create_default_toml("prj").unwrap()).unwrap();
+    metadata.lint.width.implicit_truncation = true;
+    metadata.lint.width.signed_zero_extension = true;
+    metadata.lint.width.mismatch_port_width = true;
+
+    let code = r#"
+    module ModuleA {
+        var a: logic<4>;
+        var b: logic<8>;
+        var c: logic<8>;
+        var d: logic<8>;
+        assign a = b + c;
+        assign d = {a, a};
+        assign b = 0;
+        assign c = 1;
+    }
+    "#;
+
+    let errors = analyze_with_metadata(code, &metadata);
+    assert!(matches!(
+        errors[0],
+        AnalyzerError::ImplicitTruncation { .. }
+    ));
+    assert_eq!(errors.len(), 1);
+
+    let code = r#"
+    module ModuleB {
+        var a: signed logic<4>;
+        var b: logic<8>;
+        var c: logic<8>;
+        assign a = 0;
+        assign b = 0;
+        assign c = a + b;
+    }
+    "#;
+
+    let errors = analyze_with_metadata(code, &metadata);
+    assert!(matches!(
+        errors[0],
+        AnalyzerError::SignedZeroExtension { .. }
+    ));
+
+    let code = r#"
+    module ModuleC (
+        i_a: input  logic<4>,
+        o_b: output logic<4>,
+    ) {
+        assign o_b = i_a;
+    }
+    module ModuleD {
+        var a: logic<8>;
+        var b: logic<4>;
+        assign a = 0;
+        inst u: ModuleC (
+            i_a: a,
+            o_b: b,
+        );
+    }
+    "#;
+
+    let errors = analyze_with_metadata(code, &metadata);
+    assert!(matches!(errors[0], AnalyzerError::MismatchPortWidth { .. }));
+
+    let code = r#"
+    module ModuleE {
+        var a: logic<4>;
+        var b: logic<8>;
+        assign b = 0;
+        #[allow(implicit_truncation)]
+        assign a = b;
+    }
+    "#;
+
+    let errors = analyze_with_metadata(code, &metadata);
+    assert!(errors.is_empty());
+
+    let code = r#"
+    module ModuleF {
+        var a: logic<4>;
+        var b: logic<8>;
+        assign b = 0;
+        assign a = b;
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(errors.is_empty());
+}
+
 #[test]
 fn duplicated_identifier() {
     let code = r#"
@@ -842,6 +1081,8 @@ fn missing_default_generic_argument() {
         errors[0],
         AnalyzerError::MissingDefaultArgument { .. }
     ));
+    // default value is only suggested
+    assert!(errors[0].fixes().iter().all(|x| !x.preferred));
 }
 
 #[test]
@@ -1842,615 +2083,2001 @@ fn uncovered_branch() {
 
     let errors = analyze(code);
     assert!(matches!(errors[0], AnalyzerError::UncoveredBranch { .. }));
-}
 
-#[test]
-fn reserved_identifier() {
     let code = r#"
-    module __ModuleA {
+    module ModuleC {
+        var a: logic;
+        let x: logic<2> = 1;
+
+        always_comb {
+            case x {
+                0: a = 1;
+                1: a = 0;
+            }
+        }
     }
     "#;
 
     let errors = analyze(code);
-    assert!(matches!(
-        errors[0],
-        AnalyzerError::ReservedIdentifier { .. }
-    ));
-}
+    assert!(matches!(errors[0], AnalyzerError::UncoveredBranch { .. }));
 
-#[test]
-fn reset_value_non_elaborative() {
     let code = r#"
-    module ModuleA (
-        i_clk: input clock,
-        i_rst: input reset,
-    ) {
+    module ModuleD {
         var a: logic;
-        var b: logic;
+        let x: logic = 1;
 
-        always_ff {
-            if_reset {
-                a = b;
-            } else {
-                a = 1'b0;
+        always_comb {
+            switch {
+                x == 0: a = 1;
+                x == 1: a = 0;
             }
         }
-    }"#;
+    }
+    "#;
 
     let errors = analyze(code);
-    assert!(matches!(
-        errors[0],
-        AnalyzerError::InvalidResetNonElaborative { .. }
-    ));
+    assert!(matches!(errors[0], AnalyzerError::UncoveredBranch { .. }));
 }
 
 #[test]
-fn invalid_factor_kind() {
+fn uncovered_bit() {
     let code = r#"
     module ModuleA {
-        function f (
-            a: input logic,
-        ) -> logic {
-            return a;
-        }
+        var a: logic<4>;
+        var b: logic<4>;
+        let x: logic = 1;
 
-        var a: logic;
+        always_comb {
+            a[1:0] = 0;
+            a[3:2] = 1;
+        }
 
-        assign a = f + 1;
-    }"#;
+        always_comb {
+            b = 0;
+            if x {
+                b[0] = 1;
+            }
+        }
+    }
+    "#;
 
     let errors = analyze(code);
-    assert!(matches!(errors[0], AnalyzerError::InvalidFactor { .. }));
-}
+    assert!(errors.is_empty());
 
-#[test]
-fn call_non_function() {
     let code = r#"
-    module ModuleA {
-        function f (
-            a: input logic,
-        ) -> logic {
-            return a;
-        }
-
-        var a: logic;
-        var b: logic;
+    module ModuleB {
+        var a: logic<4>;
 
-        assign a = b() + 1;
-    }"#;
+        always_comb {
+            a[1:0] = 0;
+            a[2]   = 1;
+        }
+    }
+    "#;
 
     let errors = analyze(code);
-    assert!(matches!(errors[0], AnalyzerError::CallNonFunction { .. }));
-}
+    assert!(matches!(errors[0], AnalyzerError::UncoveredBit { .. }));
 
-#[test]
-fn invalid_assignment_to_const() {
     let code = r#"
-    module ModuleA (
-        i_clk: input clock,
-        i_rst: input reset,
-    ) {
+    module ModuleC {
+        var a: logic<2>;
+        let x: logic = 1;
+
         always_comb {
-            let y: logic = 1;
-            y = 0;
+            a[0] = 0;
+            if x {
+                a[1] = 1;
+            } else {
+                a[0] = 1;
+            }
         }
-    }"#;
+    }
+    "#;
 
     let errors = analyze(code);
-    assert!(matches!(
-        errors[0],
-        AnalyzerError::InvalidAssignmentToConst { .. }
-    ));
-}
-
-#[test]
-fn test_factors() {
+    assert!(matches!(errors[0], AnalyzerError::UncoveredBit { .. }));
     let code = r#"
-    interface InterfaceA {
-        var a: logic;
-        modport master {
-            a: input,
-        }
-    }
-
-    module ModuleA #(
-        param K: i32 = 32,
-    ) (
-        i_clk: input   clock             ,
-        i_rst: input   reset             ,
-        mst  : modport InterfaceA::master,
-    ) {
+    module ModuleD {
+        var a: logic<8>;
+        var b: logic<4>;
 
-        enum State: logic<3> {
-            Idle = 3'bxx1,
-            Run0 = 3'b000,
-            Run1 = 3'b010,
-            Run2 = 3'b100,
-            Done = 3'b110,
+        always_comb {
+            a[0 step 4] = 0;
+            a[1 step 4] = 1;
         }
 
-        struct S {
-            v: logic,
+        always_comb {
+            b[3 -: 2] = 0;
+            b[1 -: 2] = 1;
         }
+    }
+    "#;
 
-        union U {
-            v: logic,
-            w: logic,
-        }
+    let errors = analyze(code);
+    assert!(errors.is_empty());
 
-        let state: State = State::Run1;
-        var u    : U    ;
-        var s    : S    ;
-        const J    : i32   = 32;
+    let code = r#"
+    module ModuleE {
+        var a: logic<8>;
 
-        for i in 0..1 :g_display {
-            always_ff {
-                $display("%d", i);
-            }
+        always_comb {
+            a[0 step 4] = 1;
+            a[7 -: 2]   = 0;
         }
+    }
+    "#;
 
-        function foo () -> logic {
-            return 1'b1;
-        }
+    let errors = analyze(code);
+    assert!(matches!(errors[0], AnalyzerError::UncoveredBit { .. }));
 
-        function bar (
-            l: input logic,
-        ) -> logic {
-            return foo();
+    let code = r#"
+    module ModuleF {
+        var a: logic<4>;
+        var b: logic<4>;
+
+        always_comb {
+            a[64'h7FFF_FFFF_FFFF_FFFF +: 2] = 0;
         }
 
-        assign u.v = 1'b1;
-        assign s.v = 1'b1;
-        initial {
-            $display("%d", u);
-            $display("%d", s);
-            $display("%d", state);
-            $display("%d", mst.a);
-            $display("%d", i_clk);
-            $display("%d", K);
-            $display("%d", J);
-            $display("%d", foo());
-            // Using $bits as a placeholder SystemFunciton.
-            $display("%d", $bits(S));
-            $display("%d", $bits(U));
-            $display("%d", $bits(foo(), State));
-            $display("%d", bar($bits(State)));
-            $display("%d", $bits(State));
-            $display("%d", bar(S));
-            $display("%d", bar(U));
-            $display("%d", bar(State));
-            $display("%d", $bits(bar(State)));
+        always_comb {
+            b[64'h4000_0000_0000_0000 step 4] = 0;
         }
-    }"#;
+    }
+    "#;
 
     let errors = analyze(code);
-    assert!(errors.len() == 4);
-    for error in errors {
-        assert!(matches!(error, AnalyzerError::InvalidFactor { .. }));
+    assert!(!errors
+        .iter()
+        .any(|x| matches!(x, AnalyzerError::UncoveredBit { .. })));
+}
+
+#[test]
+fn reserved_identifier() {
+    let code = r#"
+    module __ModuleA {
     }
+    "#;
+
+    let errors = analyze(code);
+    assert!(matches!(
+        errors[0],
+        AnalyzerError::ReservedIdentifier { .. }
+    ));
 }
 
 #[test]
-fn enum_non_const_exception() {
+fn reset_value_non_elaborative() {
     let code = r#"
     module ModuleA (
         i_clk: input clock,
         i_rst: input reset,
     ) {
-
-        enum State: logic<3> {
-            Idle = 3'bxx1,
-            Run0 = 3'b000,
-            Run1 = 3'b010,
-            Run2 = 3'b100,
-            Done = 3'b110,
-        }
-        var state: State;
+        var a: logic;
+        var b: logic;
 
         always_ff {
             if_reset {
-                state = State::Idle;
+                a = b;
+            } else {
+                a = 1'b0;
             }
         }
-
     }"#;
+
     let errors = analyze(code);
-    assert!(errors.is_empty());
+    assert!(matches!(
+        errors[0],
+        AnalyzerError::InvalidResetNonElaborative { .. }
+    ));
 }
 
 #[test]
-fn invalid_case_condition_expression() {
+fn reset_domain_check() {
     let code = r#"
     module ModuleA (
-        i_sel: input  logic<3>,
-        i_a  : input  logic<4>,
-        o_b  : output logic,
-        o_c  : output logic,
+        i_clk  : input  clock,
+        i_rst_a: input  reset,
+        i_rst_b: input  reset,
+        o_b    : output logic,
+        o_c    : output logic,
     ) {
-        const ONE: bit <3> = 3'd1;
+        var a: logic;
+        var b: logic;
+        var c: logic;
+        let d: logic = ~a;
 
-        always_comb {
-          case i_sel {
-            3'd0   : o_b = i_a[0];
-            ONE    : o_b = i_a[1];
-            2..=3  : o_b = i_a[2];
-            3'b1xx : o_b = i_a[3];
-            default: o_b = i_a[3];
-          }
+        always_ff (i_clk, i_rst_a) {
+            if_reset {
+                a = 0;
+            } else {
+                a = ~a;
+            }
         }
 
-        assign o_c = case i_sel {
-            3'd0   : i_a[0],
-            ONE    : i_a[1],
-            2..=3  : i_a[2],
-            3'b1xx : i_a[3],
-            default: i_a[3],
-        };
+        always_ff (i_clk, i_rst_b) {
+            if_reset {
+                b = 0;
+            } else {
+                b = d;
+            }
+        }
+
+        always_ff (i_clk) {
+            c = a;
+        }
+
+        assign o_b = b;
+        assign o_c = c;
     }
     "#;
 
     let errors = analyze(code);
-    assert!(errors.is_empty());
+    assert!(matches!(
+        errors[0],
+        AnalyzerError::ResetDomainCrossing { .. }
+    ));
+    assert!(matches!(
+        errors[1],
+        AnalyzerError::ResetDomainCrossing { .. }
+    ));
+    assert_eq!(errors.len(), 2);
 
     let code = r#"
     module ModuleB (
-        i_sel: input  logic<2>,
-        i_a  : input  logic<3>,
+        i_clk: input  clock,
+        i_rst: input  reset,
         o_b  : output logic,
+        o_c  : output logic,
     ) {
-        let c: logic<2> = 2'd0;
+        var a: logic;
+        var b: logic;
+        var c: logic;
+        var r: reset;
 
-        always_comb {
-          case i_sel {
-            c      : o_b = i_a[0];
-            default: o_b = i_a[1];
-          }
+        assign r = i_rst;
+
+        always_ff (i_clk, i_rst) {
+            if_reset {
+                a = 0;
+            } else {
+                a = ~a;
+            }
+        }
+
+        always_ff (i_clk, r) {
+            if_reset {
+                b = 0;
+            } else {
+                b = a;
+            }
+        }
+
+        #[allow(reset_domain_crossing)]
+        always_ff (i_clk) {
+            c = a;
         }
+
+        assign o_b = b;
+        assign o_c = c;
     }
     "#;
 
     let errors = analyze(code);
-    assert!(matches!(
-        errors[0],
-        AnalyzerError::InvalidCaseConditionNonElaborative { .. }
-    ));
+    assert!(errors.is_empty());
 
     let code = r#"
     module ModuleC (
-        i_sel: input  logic<2>,
-        i_a  : input  logic<3>,
-        o_b  : output logic,
+        i_clk: input  clock,
+        i_rst: input  reset,
+        i_clr: input  logic,
+        o_a  : output logic,
     ) {
-        let c: logic<2> = 2'd1;
+        var r: reset;
+        var a: logic;
 
-        always_comb {
-          case i_sel {
-            0..=c  : o_b = i_a[0];
-            default: o_b = i_a[1];
-          }
+        assign r = i_rst | i_clr;
+
+        always_ff (i_clk, r) {
+            if_reset {
+                a = 0;
+            } else {
+                a = ~a;
+            }
         }
+
+        assign o_a = a;
     }
     "#;
 
     let errors = analyze(code);
     assert!(matches!(
         errors[0],
-        AnalyzerError::InvalidCaseConditionNonElaborative { .. }
+        AnalyzerError::UnsynchronizedReset { .. }
     ));
 
     let code = r#"
     module ModuleD (
-        i_sel: input  logic<2>,
-        i_a  : input  logic<3>,
-        o_b  : output logic,
+        i_clk: input  clock,
+        i_rst: input  reset,
+        i_clr: input  logic,
+        o_a  : output logic,
     ) {
-        let c: logic<2> = 2'd0;
+        var r: reset;
+        var q: logic;
+        var a: logic;
 
-        assign o_b = case i_sel {
-            c      : i_a[0],
-            default: i_a[1],
-        };
+        always_ff (i_clk, i_rst) {
+            if_reset {
+                q = 1;
+            } else {
+                q = i_clr;
+            }
+        }
+
+        assign r = i_rst | q;
+
+        always_ff (i_clk, r) {
+            if_reset {
+                a = 0;
+            } else {
+                a = ~a;
+            }
+        }
+
+        assign o_a = a;
     }
     "#;
 
     let errors = analyze(code);
-    assert!(matches!(
-        errors[0],
-        AnalyzerError::InvalidCaseConditionNonElaborative { .. }
-    ));
+    assert!(errors.is_empty());
 
     let code = r#"
     module ModuleE (
-        i_sel: input  logic<2>,
-        i_a  : input  logic<3>,
-        o_b  : output logic,
+        i_clk: input  clock,
+        i_rst: input  reset,
+        i_clr: input  logic,
+        o_a  : output logic,
     ) {
-        let c: logic<2> = 2'd1;
+        var r: reset;
+        var a: logic;
 
-        assign o_b = case i_sel {
-            0..=c  : i_a[0],
-            default: i_a[1],
-        };
-    }
-    "#;
+        assign r = i_rst | i_clr;
+
+        always_ff (i_clk, r) {
+            if_reset {
+                a = 0;
+            } else {
+                a = ~a;
+            }
+        }
+
+        assign o_a = a;
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(matches!(
+        errors[0],
+        AnalyzerError::UnsynchronizedReset { .. }
+    ));
+
+    // disabled by Veryl.toml
+    let mut metadata: Metadata =
+        toml::from_str(&Metadata::create_default_toml("prj").unwrap()).unwrap();
+    metadata.lint.reset.unsynchronized_reset = false;
+
+    let errors = analyze_with_metadata(code, &metadata);
+    assert!(errors.is_empty());
+}
+
+#[test]
+fn invalid_factor_kind() {
+    let code = r#"
+    module ModuleA {
+        function f (
+            a: input logic,
+        ) -> logic {
+            return a;
+        }
+
+        var a: logic;
+
+        assign a = f + 1;
+    }"#;
+
+    let errors = analyze(code);
+    assert!(matches!(errors[0], AnalyzerError::InvalidFactor { .. }));
+}
+
+#[test]
+fn call_non_function() {
+    let code = r#"
+    module ModuleA {
+        function f (
+            a: input logic,
+        ) -> logic {
+            return a;
+        }
+
+        var a: logic;
+        var b: logic;
+
+        assign a = b() + 1;
+    }"#;
+
+    let errors = analyze(code);
+    assert!(matches!(errors[0], AnalyzerError::CallNonFunction { .. }));
+}
+
+#[test]
+fn invalid_assignment_to_const() {
+    let code = r#"
+    module ModuleA (
+        i_clk: input clock,
+        i_rst: input reset,
+    ) {
+        always_comb {
+            let y: logic = 1;
+            y = 0;
+        }
+    }"#;
+
+    let errors = analyze(code);
+    assert!(matches!(
+        errors[0],
+        AnalyzerError::InvalidAssignmentToConst { .. }
+    ));
+}
+
+#[test]
+fn test_factors() {
+    let code = r#"
+    interface InterfaceA {
+        var a: logic;
+        modport master {
+            a: input,
+        }
+    }
+
+    module ModuleA #(
+        param K: i32 = 32,
+    ) (
+        i_clk: input   clock             ,
+        i_rst: input   reset             ,
+        mst  : modport InterfaceA::master,
+    ) {
+
+        enum State: logic<3> {
+            Idle = 3'bxx1,
+            Run0 = 3'b000,
+            Run1 = 3'b010,
+            Run2 = 3'b100,
+            Done = 3'b110,
+        }
+
+        struct S {
+            v: logic,
+        }
+
+        union U {
+            v: logic,
+            w: logic,
+        }
+
+        let state: State = State::Run1;
+        var u    : U    ;
+        var s    : S    ;
+        const J    : i32   = 32;
+
+        for i in 0..1 :g_display {
+            always_ff {
+                $display("%d", i);
+            }
+        }
+
+        function foo () -> logic {
+            return 1'b1;
+        }
+
+        function bar (
+            l: input logic,
+        ) -> logic {
+            return foo();
+        }
+
+        assign u.v = 1'b1;
+        assign s.v = 1'b1;
+        initial {
+            $display("%d", u);
+            $display("%d", s);
+            $display("%d", state);
+            $display("%d", mst.a);
+            $display("%d", i_clk);
+            $display("%d", K);
+            $display("%d", J);
+            $display("%d", foo());
+            // Using $bits as a placeholder SystemFunciton.
+            $display("%d", $bits(S));
+            $display("%d", $bits(U));
+            $display("%d", $bits(foo(), State));
+            $display("%d", bar($bits(State)));
+            $display("%d", $bits(State));
+            $display("%d", bar(S));
+            $display("%d", bar(U));
+            $display("%d", bar(State));
+            $display("%d", $bits(bar(State)));
+        }
+    }"#;
+
+    let errors = analyze(code);
+    assert!(errors.len() == 4);
+    for error in errors {
+        assert!(matches!(error, AnalyzerError::InvalidFactor { .. }));
+    }
+}
+
+#[test]
+fn enum_non_const_exception() {
+    let code = r#"
+    module ModuleA (
+        i_clk: input clock,
+        i_rst: input reset,
+    ) {
+
+        enum State: logic<3> {
+            Idle = 3'bxx1,
+            Run0 = 3'b000,
+            Run1 = 3'b010,
+            Run2 = 3'b100,
+            Done = 3'b110,
+        }
+        var state: State;
+
+        always_ff {
+            if_reset {
+                state = State::Idle;
+            }
+        }
+
+    }"#;
+    let errors = analyze(code);
+    assert!(errors.is_empty());
+}
+
+#[test]
+fn invalid_case_condition_expression() {
+    let code = r#"
+    module ModuleA (
+        i_sel: input  logic<3>,
+        i_a  : input  logic<4>,
+        o_b  : output logic,
+        o_c  : output logic,
+    ) {
+        const ONE: bit <3> = 3'd1;
+
+        always_comb {
+          case i_sel {
+            3'd0   : o_b = i_a[0];
+            ONE    : o_b = i_a[1];
+            2..=3  : o_b = i_a[2];
+            3'b1xx : o_b = i_a[3];
+            default: o_b = i_a[3];
+          }
+        }
+
+        assign o_c = case i_sel {
+            3'd0   : i_a[0],
+            ONE    : i_a[1],
+            2..=3  : i_a[2],
+            3'b1xx : i_a[3],
+            default: i_a[3],
+        };
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(errors.is_empty());
+
+    let code = r#"
+    module ModuleB (
+        i_sel: input  logic<2>,
+        i_a  : input  logic<3>,
+        o_b  : output logic,
+    ) {
+        let c: logic<2> = 2'd0;
+
+        always_comb {
+          case i_sel {
+            c      : o_b = i_a[0];
+            default: o_b = i_a[1];
+          }
+        }
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(matches!(
+        errors[0],
+        AnalyzerError::InvalidCaseConditionNonElaborative { .. }
+    ));
+
+    let code = r#"
+    module ModuleC (
+        i_sel: input  logic<2>,
+        i_a  : input  logic<3>,
+        o_b  : output logic,
+    ) {
+        let c: logic<2> = 2'd1;
+
+        always_comb {
+          case i_sel {
+            0..=c  : o_b = i_a[0];
+            default: o_b = i_a[1];
+          }
+        }
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(matches!(
+        errors[0],
+        AnalyzerError::InvalidCaseConditionNonElaborative { .. }
+    ));
+
+    let code = r#"
+    module ModuleD (
+        i_sel: input  logic<2>,
+        i_a  : input  logic<3>,
+        o_b  : output logic,
+    ) {
+        let c: logic<2> = 2'd0;
+
+        assign o_b = case i_sel {
+            c      : i_a[0],
+            default: i_a[1],
+        };
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(matches!(
+        errors[0],
+        AnalyzerError::InvalidCaseConditionNonElaborative { .. }
+    ));
+
+    let code = r#"
+    module ModuleE (
+        i_sel: input  logic<2>,
+        i_a  : input  logic<3>,
+        o_b  : output logic,
+    ) {
+        let c: logic<2> = 2'd1;
+
+        assign o_b = case i_sel {
+            0..=c  : i_a[0],
+            default: i_a[1],
+        };
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(matches!(
+        errors[0],
+        AnalyzerError::InvalidCaseConditionNonElaborative { .. }
+    ));
+}
+
+#[test]
+fn invalid_cast() {
+    let code = r#"
+    module ModuleA {
+        let a: clock = 1;
+        let _b: reset = a as reset;
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(matches!(errors[0], AnalyzerError::InvalidCast { .. }));
+}
+
+#[test]
+fn invalid_test() {
+    let code = r#"
+    module ModuleA {}
+
+    #[test(TestA)]
+    embed (cocotb) py {{{
+    }}}
+    "#;
+
+    let errors = analyze(code);
+    assert!(matches!(errors[0], AnalyzerError::InvalidTest { .. }));
+}
+
+#[test]
+fn clock_domain() {
+    let code = r#"
+    module ModuleA (
+        i_clk: input  `a clock,
+        i_dat: input  `a logic,
+        o_dat: output `b logic,
+    ) {
+        assign o_dat = i_dat;
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(matches!(
+        errors[0],
+        AnalyzerError::MismatchClockDomain { .. }
+    ));
+
+    let code = r#"
+    module ModuleB (
+        i_clk : input  `a clock,
+        i_dat0: input  `a logic,
+        i_dat1: input  `b logic,
+        o_dat : output `a logic,
+    ) {
+        assign o_dat = {i_dat0, i_dat1};
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(matches!(
+        errors[0],
+        AnalyzerError::MismatchClockDomain { .. }
+    ));
+
+    let code = r#"
+    module ModuleC (
+        i_clk : input  `a clock,
+        i_dat0: input  `a logic,
+        i_dat1: input  `b logic,
+        o_dat : output `a logic,
+    ) {
+        inst u: ModuleD (
+            i_dat: i_dat1,
+            o_dat,
+        );
+    }
+
+    module ModuleD (
+        i_dat: input  logic,
+        o_dat: output logic,
+    ) {
+        assign o_dat = i_dat;
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(matches!(
+        errors[0],
+        AnalyzerError::MismatchClockDomain { .. }
+    ));
+
+    let code = r#"
+    module ModuleE (
+        i_clk : input  `a clock,
+        i_dat0: input  `a logic,
+        i_dat1: input  `b logic,
+        o_dat : output `a logic,
+    ) {
+        inst u: $sv::Module (
+            i_dat: i_dat1,
+            o_dat,
+        );
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(matches!(
+        errors[0],
+        AnalyzerError::MismatchClockDomain { .. }
+    ));
+
+    let code = r#"
+    module ModuleF (
+        i_clk : input  `a clock,
+        i_dat0: input  `a logic,
+        i_dat1: input  `b logic,
+        o_dat : output `b logic,
+    ) {
+        var r_dat: `b logic;
+
+        always_ff {
+            r_dat = i_dat1;
+        }
+
+        assign o_dat = r_dat;
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(matches!(
+        errors[0],
+        AnalyzerError::MismatchClockDomain { .. }
+    ));
+
+    let code = r#"
+    module ModuleG (
+        i_clk: input   `a clock,
+        i_dat: input   `a logic,
+        o_dat: modport `b InterfaceA::port,
+    ) {
+        assign o_dat.a = i_dat;
+    }
+
+    interface InterfaceA {
+        var a: logic;
+
+        modport port {
+            a: output,
+        }
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(matches!(
+        errors[0],
+        AnalyzerError::MismatchClockDomain { .. }
+    ));
+
+    let code = r#"
+    module ModuleH (
+        i_clk: input  `a clock,
+        i_dat: input  `a logic,
+        o_dat: output `b logic,
+    ) {
+        always_comb {
+            o_dat = i_dat;
+        }
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(matches!(
+        errors[0],
+        AnalyzerError::MismatchClockDomain { .. }
+    ));
+}
+
+#[test]
+fn r#unsafe() {
+    let code = r#"
+    module ModuleA {
+        unsafe(x) {
+        }
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(matches!(errors[0], AnalyzerError::UnknownUnsafe { .. }));
+}
+
+#[test]
+fn detect_recursive() {
+    let code = r#"
+    module ModuleA (
+        x: input x,
+    ) {
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(matches!(errors[0], AnalyzerError::MismatchType { .. }));
+}
+
+#[test]
+fn sv_keyword_usage() {
+    let code = r#"
+    module ModuleA {
+        var always: logic;
+        assign always = 1;
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(matches!(errors[0], AnalyzerError::SvKeywordUsage { .. }));
+
+    let code = r#"
+    module ModuleA {
+        var r#always: logic;
+        assign r#always = 1;
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(matches!(errors[0], AnalyzerError::SvKeywordUsage { .. }));
+}
+
+#[test]
+fn sv_with_implicit_reset() {
+    let code = r#"
+    module ModuleA {
+        var rst: reset;
+
+        inst u: $sv::Module (
+            rst,
+        );
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(matches!(
+        errors[0],
+        AnalyzerError::SvWithImplicitReset { .. }
+    ));
+
+    let code = r#"
+    module ModuleB {
+        var rst: reset_async_low;
+
+        inst u: $sv::Module (
+            rst,
+        );
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(errors.is_empty());
+}
+
+#[test]
+fn unsupported_by_target() {
+    let code = r#"
+    interface InterfaceA {
+        var a: logic;
+        function get_a () -> logic {
+            return a;
+        }
+        modport slave {
+            a    : input ,
+            get_a: import,
+        }
+    }
+    module ModuleA (
+        slv: modport InterfaceA::slave,
+    ) {
+        let _a: logic = slv.get_a();
+        let _b: logic = slv.a;
+    }
+    module ModuleB {
+        inst bus: InterfaceA;
+        assign bus.a = 0;
+        let _a: logic = bus.get_a();
+    }
+    "#;
 
     let errors = analyze(code);
-    assert!(matches!(
-        errors[0],
-        AnalyzerError::InvalidCaseConditionNonElaborative { .. }
-    ));
-}
+    assert!(errors.is_empty());
+
+    let mut metadata: Metadata =
+        toml::from_str(&Metadata::create_default_toml("prj").unwrap()).unwrap();
+    metadata.build.flatten_interface_ports = true;
+
+    let errors = analyze_with_metadata(code, &metadata);
+    assert_eq!(errors.len(), 2);
+    for error in &errors {
+        assert!(matches!(error, AnalyzerError::UnsupportedByTarget { .. }));
+    }
 
-#[test]
-fn invalid_cast() {
     let code = r#"
-    module ModuleA {
-        let a: clock = 1;
-        let _b: reset = a as reset;
+    module ModuleA (
+        i_clk: input clock,
+        i_rst: input reset,
+        bus  : interface,
+    ) {
+        let _a: logic [2] = '{1, default: 0};
+        var b: logic [2, 2];
+        assign b[0] = '{0 repeat 2};
+        assign b[1] = '{0, 1};
+        var c: logic [2];
+        always_ff {
+            if_reset {
+                c = '{default: 0};
+            } else {
+                c = '{0, 1} | '{1, 0};
+            }
+        }
     }
     "#;
 
     let errors = analyze(code);
-    assert!(matches!(errors[0], AnalyzerError::InvalidCast { .. }));
-}
+    assert!(errors.is_empty());
 
-#[test]
-fn invalid_test() {
-    let code = r#"
-    module ModuleA {}
+    metadata.build.flatten_interface_ports = false;
+    metadata.build.target_language = TargetLanguage::Verilog;
 
-    #[test(TestA)]
-    embed (cocotb) py {{{
+    let errors = analyze_with_metadata(code, &metadata);
+    assert_eq!(errors.len(), 3);
+    for error in &errors {
+        assert!(matches!(error, AnalyzerError::UnsupportedByTarget { .. }));
+    }
+
+    let code = r#"
+    module ModuleA #(
+        param W: u32 = 1,
+    ) {
+        union UnionA {
+            a: logic,
+        }
+        initial {
+            $display("a");
+        }
+        var a: logic<2>;
+        always_comb {
+            for i: u32 in 1..2 step |= 2 {
+                a[i] = 0;
+            }
+        }
+    }
+    module ModuleB {
+        inst u: ModuleA;
+    }
+    proto module ProtoA;
+    module ModuleC::<T: ProtoA> {
+        inst u: T;
+    }
+    module ModuleD for ProtoA {}
+    module ModuleE {
+        inst u: ModuleC::<ModuleD>;
+    }
+    embed (inline) sv{{{
+        module ModuleF; endmodule
     }}}
     "#;
 
     let errors = analyze(code);
-    assert!(matches!(errors[0], AnalyzerError::InvalidTest { .. }));
+    assert!(errors.is_empty());
+
+    metadata.build.target_language = TargetLanguage::Vhdl;
+
+    let errors = analyze_with_metadata(code, &metadata);
+    assert_eq!(errors.len(), 6);
+    for error in &errors {
+        assert!(matches!(error, AnalyzerError::UnsupportedByTarget { .. }));
+    }
+
+    let code = r#"
+    proto module ProtoA;
+    module ModuleA::<T: ProtoA> {
+        inst u: T;
+    }
+    module ModuleB::<T: ProtoA> {
+        inst u: ModuleA::<T>;
+    }
+    module ModuleC::<T: ProtoA> {
+        inst u: T;
+    }
+    module ModuleD for ProtoA {}
+    module ModuleE {
+        inst u: ModuleB::<ModuleD>;
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(errors.is_empty());
+
+    // ModuleA and ModuleB are used, but ModuleC is not
+    let errors = analyze_with_metadata(code, &metadata);
+    assert_eq!(errors.len(), 3, "{errors:?}");
+    for error in &errors {
+        assert!(matches!(error, AnalyzerError::UnsupportedByTarget { .. }));
+    }
+
+    let code = r#"
+    interface InterfaceA {
+        var a: logic;
+        modport mp {
+            a: input,
+        }
+    }
+    module ModuleA (
+        p_a: interface,
+        p_b: interface,
+    ) {
+        inst w: ModuleC (
+            p: p_b,
+        );
+    }
+    module ModuleC (
+        p: modport InterfaceA::mp,
+    ) {}
+    module ModuleB {
+        inst u: InterfaceA [2];
+        inst v: ModuleA [2] (
+            p_a: u[0],
+            p_b: u[1],
+        );
+        var a: logic<4>;
+        for i in 0..4 step += 2 :g {
+            assign a[i] = u[0].a;
+        }
+        for i in 1..4 step *= 2 :h {
+            assign a[i] = 0;
+        }
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(errors.is_empty(), "{errors:?}");
+
+    let errors = analyze_with_metadata(code, &metadata);
+    assert_eq!(errors.len(), 3, "{errors:?}");
+    for error in &errors {
+        assert!(matches!(error, AnalyzerError::UnsupportedByTarget { .. }));
+    }
+
+    let code = r#"
+    interface InterfaceA #(
+        param W: u32 = 1,
+    ) {
+        var a: logic<W>;
+        assign a = 0;
+    }
+    module ModuleA (
+        i_a: input  logic<4>,
+        o_a: output logic<4>,
+        o_b: output logic<4>,
+    ) {
+        assign o_a = $countones(i_a);
+        function FuncA::<W: const> (
+            a: input logic<W>,
+        ) -> logic<W> {
+            return a;
+        }
+        for i in 0..4 :g {
+            if $onehot(i + 1) :g {
+                assign o_b[i] = $onehot(i_a);
+            }
+        }
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(errors.is_empty());
+
+    let errors = analyze_with_metadata(code, &metadata);
+    assert_eq!(errors.len(), 4);
+    for error in &errors {
+        assert!(matches!(error, AnalyzerError::UnsupportedByTarget { .. }));
+    }
 }
 
 #[test]
-fn clock_domain() {
+fn case_insensitive_identifier() {
     let code = r#"
-    module ModuleA (
-        i_clk: input  `a clock,
-        i_dat: input  `a logic,
-        o_dat: output `b logic,
+    module ModuleA #(
+        param COUNT: u32 = 1,
     ) {
-        assign o_dat = i_dat;
+        var count: logic<COUNT>;
+        assign count = 0;
     }
     "#;
 
     let errors = analyze(code);
+    assert!(errors.is_empty());
+
+    let metadata_path = Metadata::search_from_current().unwrap();
+    let mut metadata = Metadata::load(&metadata_path).unwrap();
+    metadata.build.target_language = TargetLanguage::Vhdl;
+
+    let errors = analyze_with_metadata(code, &metadata);
     assert!(matches!(
         errors[0],
-        AnalyzerError::MismatchClockDomain { .. }
+        AnalyzerError::CaseInsensitiveIdentifier { .. }
     ));
+}
 
+#[test]
+fn conflict_with_mangled_enum_member() {
     let code = r#"
-    module ModuleB (
-        i_clk : input  `a clock,
-        i_dat0: input  `a logic,
-        i_dat1: input  `b logic,
-        o_dat : output `a logic,
-    ) {
-        assign o_dat = {i_dat0, i_dat1};
+    module ModuleA {
+        enum EnumA: logic {
+            MemberA,
+        }
+        var EnumA_MemberA: logic;
     }
     "#;
 
     let errors = analyze(code);
     assert!(matches!(
         errors[0],
-        AnalyzerError::MismatchClockDomain { .. }
+        AnalyzerError::DuplicatedIdentifier { .. }
     ));
+}
+
+#[test]
+fn reanalyze_after_drop() {
+    let code = r#"
+    module ModuleA {
+        var a: logic;
+        assign a = 1;
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(errors.is_empty());
+
+    let metadata: Metadata =
+        toml::from_str(&Metadata::create_default_toml("prj").unwrap()).unwrap();
+    let parser = Parser::parse(code, &"").unwrap();
+    let analyzer = Analyzer::new(&metadata);
+
+    let path = resource_table::get_path_id(PathBuf::from("")).unwrap();
+    symbol_table::drop(path);
+    namespace_table::drop(path);
+    type_dag::drop(path);
+
+    let mut errors = vec![];
+    errors.append(&mut analyzer.analyze_pass1("prj", code, "", &parser.veryl));
+    errors.append(&mut analyzer.analyze_pass2("prj", code, "", &parser.veryl));
+    errors.append(&mut analyzer.analyze_pass3("prj", code, "", &parser.veryl));
+    assert!(errors.is_empty());
+    assert_eq!(type_dag::toposort().len(), 1);
+}
+
+#[test]
+fn fix_edits() {
+    let code = r#"
+    module ModuleA {
+        let a: logic = 1;
+    }
+    "#;
+
+    let errors = analyze(code);
+    let fixes: Vec<_> = errors.iter().flat_map(|x| x.fixes()).collect();
+    assert_eq!(fixes.len(), 1);
+    let fixed = fix::apply(code, &fix::select(&fixes)).unwrap();
+    assert!(fixed.contains("let _a: logic = 1;"));
+
+    let code = r#"
+    module ModuleA {
+        let r#always: logic = 1;
+    }
+    "#;
+
+    let errors = analyze(code);
+    let fixes: Vec<_> = errors.iter().flat_map(|x| x.fixes()).collect();
+    let fixed = fix::apply(code, &fix::select(&fixes)).unwrap();
+    assert!(fixed.contains("let _always: logic = 1;"));
+
+    let code = r#"
+    module ModuleB {
+        var always: logic;
+        assign always = 1;
+        let b: logic = always;
+    }
+    "#;
+
+    let errors = analyze(code);
+    let fixes: Vec<_> = errors.iter().flat_map(|x| x.fixes()).collect();
+    let fixed = fix::apply(code, &fix::select(&fixes)).unwrap();
+    assert!(fixed.contains("var always_: logic;"));
+    assert!(fixed.contains("assign always_ = 1;"));
+    assert!(fixed.contains("let _b: logic = always_;"));
 
     let code = r#"
     module ModuleC (
-        i_clk : input  `a clock,
-        i_dat0: input  `a logic,
-        i_dat1: input  `b logic,
-        o_dat : output `a logic,
+        i_clk: input clock,
+        i_rst: input reset,
     ) {
-        inst u: ModuleD (
-            i_dat: i_dat1,
-            o_dat,
-        );
+        var a: logic;
+        var b: logic<2>;
+        always_ff {
+            if_reset {
+            } else {
+                a = 1;
+                b = 1;
+            }
+        }
     }
+    "#;
 
+    // insertions at the same position are merged
+    let errors = analyze(code);
+    let fixes: Vec<_> = errors.iter().flat_map(|x| x.fixes()).collect();
+    assert!(fixes.iter().all(|x| x.preferred));
+    let fixed = fix::apply(code, &fix::select(&fixes)).unwrap();
+    assert!(fixed.contains("if_reset {\na = 0;\nb = 0;"));
+    assert!(analyze(&fixed).is_empty());
+
+    let code = r#"
     module ModuleD (
-        i_dat: input  logic,
-        o_dat: output logic,
+        i_clk: input clock,
+        i_rst: input reset,
     ) {
-        assign o_dat = i_dat;
+        struct StructA {
+            x: logic,
+        }
+        var a: logic [2];
+        var b: StructA;
+        always_ff {
+            if_reset {
+            } else {
+                a[0] = 1;
+                b.x  = 1;
+            }
+        }
     }
     "#;
 
+    // `= 0` for array and struct is only suggested
     let errors = analyze(code);
-    assert!(matches!(
-        errors[0],
-        AnalyzerError::MismatchClockDomain { .. }
-    ));
+    let fixes: Vec<_> = errors.iter().flat_map(|x| x.fixes()).collect();
+    assert_eq!(fixes.len(), 2);
+    assert!(fixes.iter().all(|x| !x.preferred));
 
     let code = r#"
     module ModuleE (
-        i_clk : input  `a clock,
-        i_dat0: input  `a logic,
-        i_dat1: input  `b logic,
-        o_dat : output `a logic,
-    ) {
-        inst u: $sv::Module (
-            i_dat: i_dat1,
-            o_dat,
-        );
+        a: input logic,
+    ) {}
+    module ModuleF {
+        var a: logic;
+        assign a = 1;
+        inst u: ModuleE (a);
     }
     "#;
 
-    let errors = analyze(code);
-    assert!(matches!(
-        errors[0],
-        AnalyzerError::MismatchClockDomain { .. }
-    ));
+    // implicit connection is expanded by rename
+    let mut metadata: Metadata =
+        toml::from_str(&Metadata::create_default_toml("prj").unwrap()).unwrap();
+    metadata.lint.naming.prefix_var = Some("r_".to_string());
+    let errors = analyze_with_metadata(code, &metadata);
+    let fixes: Vec<_> = errors.iter().flat_map(|x| x.fixes()).collect();
+    assert_eq!(fixes.len(), 1);
+    let fixed = fix::apply(code, &fix::select(&fixes)).unwrap();
+    assert!(fixed.contains("var r_a: logic;"));
+    assert!(fixed.contains("assign r_a = 1;"));
+    assert!(fixed.contains("inst u: ModuleE (a: r_a);"));
 
     let code = r#"
-    module ModuleF (
-        i_clk : input  `a clock,
-        i_dat0: input  `a logic,
-        i_dat1: input  `b logic,
-        o_dat : output `b logic,
-    ) {
-        var r_dat: `b logic;
+    module ModuleG {
+        var _a: logic;
+        assign _a = 1;
+        let a: logic = 1;
+    }
+    "#;
 
-        always_ff {
-            r_dat = i_dat1;
-        }
+    // rename to the existing name is rejected
+    let errors = analyze(code);
+    let fixes: Vec<_> = errors.iter().flat_map(|x| x.fixes()).collect();
+    assert_eq!(fixes.len(), 1);
+    assert_eq!(
+        fixes[0].text_edits(),
+        Err("\"_a\" is already defined".to_string())
+    );
+    assert!(fix::select(&fixes).is_empty());
+
+    // overlapped and out-of-bounds edits are reported
+    let edits = vec![
+        fix::TextEdit {
+            path: PathId::default(),
+            beg: 0,
+            end: 4,
+            text: "a".to_string(),
+        },
+        fix::TextEdit {
+            path: PathId::default(),
+            beg: 2,
+            end: 3,
+            text: "b".to_string(),
+        },
+        fix::TextEdit {
+            path: PathId::default(),
+            beg: 8,
+            end: 9,
+            text: "c".to_string(),
+        },
+    ];
+    let invalid = fix::apply("abcdef", &edits).unwrap_err();
+    assert_eq!(invalid.len(), 2);
+    assert_eq!(fix::apply("abcdef", &edits[..1]).unwrap(), "aef");
+}
 
-        assign o_dat = r_dat;
+#[test]
+fn suggestion_fixes() {
+    let code = r#"
+    package PackageA {
+        const A: u32 = 1;
+    }
+    module ModuleA {
+        let _a: u32 = A;
     }
     "#;
 
     let errors = analyze(code);
-    assert!(matches!(
-        errors[0],
-        AnalyzerError::MismatchClockDomain { .. }
-    ));
+    let fixes: Vec<_> = errors.iter().flat_map(|x| x.fixes()).collect();
+    assert_eq!(fixes.len(), 1);
+    assert!(!fixes[0].preferred);
+    let fixed = fix::apply(code, &fix::select(&fixes)).unwrap();
+    assert!(fixed.contains("import PackageA::A;\nmodule ModuleA"));
+    assert!(analyze(&fixed).is_empty());
 
     let code = r#"
-    module ModuleG (
-        i_clk: input   `a clock,
-        i_dat: input   `a logic,
-        o_dat: modport `b InterfaceA::port,
+    module ModuleB (
+        a: input  logic,
+        b: output logic,
+        c: output logic,
     ) {
-        assign o_dat.a = i_dat;
+        assign b = a;
+        assign c = a;
     }
-
-    interface InterfaceA {
-        var a: logic;
-
-        modport port {
-            a: output,
-        }
+    module ModuleC {
+        inst u0: ModuleB (a: 1);
+        inst u1: ModuleB (
+            a: 1
+        );
+        inst u2: ModuleB;
     }
     "#;
 
     let errors = analyze(code);
-    assert!(matches!(
-        errors[0],
-        AnalyzerError::MismatchClockDomain { .. }
-    ));
+    let fixes: Vec<_> = errors.iter().flat_map(|x| x.fixes()).collect();
+    // one fix for each instance even if several ports are missing
+    assert_eq!(errors.len(), 7);
+    assert_eq!(fixes.len(), 3);
+    let fixed = fix::apply(code, &fix::select(&fixes)).unwrap();
+    assert!(fixed.contains("inst u0: ModuleB (a: 1, b: _, c: _);"));
+    assert!(fixed.contains("a: 1,\n            b: _,\n            c: _,\n        );"));
+    assert!(fixed.contains("inst u2: ModuleB (a: _, b: _, c: _);"));
 
     let code = r#"
-    module ModuleH (
-        i_clk: input  `a clock,
-        i_dat: input  `a logic,
-        o_dat: output `b logic,
-    ) {
-        always_comb {
-            o_dat = i_dat;
-        }
+    module ModuleD {
+        let a: logic = 1;
     }
     "#;
 
     let errors = analyze(code);
-    assert!(matches!(
-        errors[0],
-        AnalyzerError::MismatchClockDomain { .. }
-    ));
+    let parser = Parser::parse(&code, &"").unwrap();
+    let fixes: Vec<_> = errors
+        .iter()
+        .filter_map(|x| fix::allow_attribute(x, code, &parser.veryl))
+        .collect();
+    assert_eq!(fixes.len(), 1);
+    let fixed = fix::apply(code, &fixes[0].text_edits().unwrap()).unwrap();
+    assert!(fixed.contains("#[allow(unused_variable)]\n        let a: logic = 1;"));
+    assert!(analyze(&fixed).is_empty());
 }
 
 #[test]
-fn r#unsafe() {
-    let code = r#"
-    module ModuleA {
-        unsafe(x) {
+fn cdc_report() {
+    let std_code = r#"
+    pub proto module synchronizer (
+        i_clk: input  clock,
+        i_rst: input  reset,
+        i_d  : input  logic,
+        o_d  : output logic,
+    );
+    "#;
+
+    let code = r#"
+    module ModuleA for $std::synchronizer (
+        i_clk: input  clock,
+        i_rst: input  reset,
+        i_d  : input  logic,
+        o_d  : output logic,
+    ) {
+        always_ff {
+            if_reset {
+                o_d = 0;
+            } else {
+                o_d = i_d;
+            }
+        }
+    }
+    module ModuleB (
+        i_clk_a: input  `a clock,
+        i_rst_a: input  `a reset,
+        i_clk_b: input  `b clock,
+        i_rst_b: input  `b reset,
+        i_data : input  `a logic<8>,
+        o_flag : output `b logic,
+        o_bus  : output `b logic<8>,
+    ) {
+        var flag_a: `a logic;
+        var comb_a: `a logic;
+        var flag_b: `b logic;
+
+        always_ff (i_clk_a, i_rst_a) {
+            if_reset {
+                flag_a = 0;
+            } else {
+                flag_a = i_data[0];
+            }
+        }
+
+        assign comb_a = flag_a & i_data[1];
+
+        unsafe (cdc) {
+            inst u0: ModuleA (
+                i_clk: i_clk_b,
+                i_rst: i_rst_b,
+                i_d  : flag_a ,
+                o_d  : flag_b ,
+            );
+            inst u1: ModuleA (
+                i_clk: i_clk_b,
+                i_rst: i_rst_b,
+                i_d  : comb_a ,
+                o_d  : o_flag ,
+            );
+            always_ff (i_clk_b, i_rst_b) {
+                if_reset {
+                    o_bus = 0;
+                } else {
+                    o_bus = i_data;
+                }
+            }
         }
     }
     "#;
 
-    let errors = analyze(code);
-    assert!(matches!(errors[0], AnalyzerError::UnknownUnsafe { .. }));
+    symbol_table::clear();
+
+    let metadata: Metadata =
+        toml::from_str(&Metadata::create_default_toml("prj").unwrap()).unwrap();
+    let std_parser = Parser::parse(std_code, &"std.veryl").unwrap();
+    let parser = Parser::parse(code, &"").unwrap();
+    let analyzer = Analyzer::new(&metadata);
+
+    analyzer.analyze_pass1("$std", std_code, "std.veryl", &std_parser.veryl);
+    analyzer.analyze_pass1("prj", code, "", &parser.veryl);
+    analyzer.analyze_pass2("$std", std_code, "std.veryl", &std_parser.veryl);
+    analyzer.analyze_pass2("prj", code, "", &parser.veryl);
+
+    let crossings = cdc::analyze(&[&parser.veryl]);
+    assert_eq!(crossings.len(), 3);
+
+    assert_eq!(crossings[0].signal.to_string(), "flag_a");
+    assert_eq!(crossings[0].src.to_string(), "'a");
+    assert_eq!(crossings[0].dst.to_string(), "'b");
+    assert_eq!(crossings[0].protection, cdc::Protection::Synchronizer);
+    assert!(crossings[0].issues.is_empty());
+
+    assert_eq!(crossings[1].signal.to_string(), "comb_a");
+    assert_eq!(crossings[1].protection, cdc::Protection::Synchronizer);
+    assert_eq!(crossings[1].issues, vec![cdc::CdcIssue::CombinationalInput]);
+
+    assert_eq!(crossings[2].signal.to_string(), "i_data");
+    assert_eq!(crossings[2].protection, cdc::Protection::Unsafe);
+    assert_eq!(
+        crossings[2].issues,
+        vec![cdc::CdcIssue::MultiBitWithoutGray]
+    );
 }
 
 #[test]
-fn detect_recursive() {
-    let code = r#"
-    module ModuleA (
-        x: input x,
+fn cdc_hierarchy() {
+    let std_code = r#"
+    pub proto module synchronizer (
+        i_clk: input  clock,
+        i_rst: input  reset,
+        i_d  : input  logic,
+        o_d  : output logic,
+    );
+    "#;
+
+    let code = r#"
+    module ModuleA for $std::synchronizer (
+        i_clk: input  clock,
+        i_rst: input  reset,
+        i_d  : input  logic,
+        o_d  : output logic,
+    ) {
+        always_ff {
+            if_reset {
+                o_d = 0;
+            } else {
+                o_d = i_d;
+            }
+        }
+    }
+    module ModuleB (
+        i_clk: input  clock,
+        i_rst: input  reset,
+        i_d  : input  logic,
+        o_d  : output logic,
+    ) {
+        inst u: ModuleA (
+            i_clk,
+            i_rst,
+            i_d  ,
+            o_d  ,
+        );
+    }
+    module ModuleC (
+        i_d: input  logic,
+        o_d: output logic,
+    ) {
+        assign o_d = i_d;
+    }
+    module ModuleD (
+        i_clk_a: input  `a clock,
+        i_rst_a: input  `a reset,
+        i_clk_b: input  `b clock,
+        i_rst_b: input  `b reset,
+        i_data : input  `a logic,
+        o_sync : output `b logic,
+        o_comb : output `b logic,
     ) {
+        var flag_a: `a logic;
+
+        always_ff (i_clk_a, i_rst_a) {
+            if_reset {
+                flag_a = 0;
+            } else {
+                flag_a = i_data;
+            }
+        }
+
+        inst u0: ModuleB (
+            i_clk: i_clk_b,
+            i_rst: i_rst_b,
+            i_d  : flag_a ,
+            o_d  : o_sync ,
+        );
+        inst u1: ModuleC (
+            i_d: flag_a,
+            o_d: o_comb,
+        );
     }
     "#;
 
-    let errors = analyze(code);
-    assert!(matches!(errors[0], AnalyzerError::MismatchType { .. }));
+    symbol_table::clear();
+
+    let metadata: Metadata =
+        toml::from_str(&Metadata::create_default_toml("prj").unwrap()).unwrap();
+    let std_parser = Parser::parse(std_code, &"std.veryl").unwrap();
+    let parser = Parser::parse(code, &"").unwrap();
+    let analyzer = Analyzer::new(&metadata);
+
+    analyzer.analyze_pass1("$std", std_code, "std.veryl", &std_parser.veryl);
+    analyzer.analyze_pass1("prj", code, "", &parser.veryl);
+    analyzer.analyze_pass2("$std", std_code, "std.veryl", &std_parser.veryl);
+    analyzer.analyze_pass2("prj", code, "", &parser.veryl);
+
+    let crossings = cdc::analyze(&[&parser.veryl]);
+    assert_eq!(crossings.len(), 2, "{crossings:?}");
+
+    // synchronizer in the wrapper module
+    assert_eq!(crossings[0].signal.to_string(), "flag_a");
+    assert_eq!(crossings[0].port.unwrap().0.to_string(), "u0");
+    assert_eq!(crossings[0].protection, cdc::Protection::Synchronizer);
+    assert!(crossings[0].issues.is_empty());
+
+    // combinational path in the instance
+    assert_eq!(crossings[1].signal.to_string(), "flag_a");
+    assert_eq!(crossings[1].port.unwrap().0.to_string(), "u1");
+    assert_eq!(crossings[1].src.to_string(), "'a");
+    assert_eq!(crossings[1].dst.to_string(), "'b");
+    assert_eq!(crossings[1].protection, cdc::Protection::None);
 }
 
 #[test]
-fn sv_keyword_usage() {
+fn elaborate() {
     let code = r#"
-    module ModuleA {
-        var always: logic;
-        assign always = 1;
+    module ModuleA #(
+        param WIDTH: u32 = 8,
+        param DEPTH: u32 = 4,
+    ) {
+        const ADDR_WIDTH: u32 = $clog2(DEPTH);
+
+        if WIDTH >: 8 :g_wide {
+            inst u: ModuleB #(
+                VALUE: WIDTH * 2,
+            );
+        } else {
+            inst u: ModuleB;
+        }
+    }
+    module ModuleB #(
+        param VALUE: u32 = 1,
+    ) {
+        const HALF: u32 = VALUE / 2;
+    }
+    module ModuleC::<N: const> {
+        for i in 0..N :g_for {
+            const ITEM: u32 = i * 10;
+            inst u: ModuleA #(
+                WIDTH: i + 8,
+                DEPTH: 1 << i,
+            );
+        }
+    }
+    module ModuleD {
+        inst u: ModuleC::<3>;
     }
     "#;
 
-    let errors = analyze(code);
-    assert!(matches!(errors[0], AnalyzerError::SvKeywordUsage { .. }));
+    symbol_table::clear();
 
-    let code = r#"
-    module ModuleA {
-        var r#always: logic;
-        assign r#always = 1;
-    }
-    "#;
+    let metadata: Metadata =
+        toml::from_str(&Metadata::create_default_toml("prj").unwrap()).unwrap();
+    let parser = Parser::parse(code, &"").unwrap();
+    let analyzer = Analyzer::new(&metadata);
 
-    let errors = analyze(code);
-    assert!(matches!(errors[0], AnalyzerError::SvKeywordUsage { .. }));
+    let mut errors = vec![];
+    errors.append(&mut analyzer.analyze_pass1(&"prj", &code, &"", &parser.veryl));
+    errors.append(&mut analyzer.analyze_pass2(&"prj", &code, &"", &parser.veryl));
+    assert!(errors.is_empty());
+
+    let top = elaborator::elaborate("ModuleD", &[&parser.veryl]).unwrap();
+    assert_eq!(top.name, "ModuleD");
+    assert_eq!(top.module, "ModuleD");
+    assert!(top.values.is_empty());
+    assert_eq!(top.children.len(), 1);
+
+    let fixed = |x: Option<Evaluated>| match x {
+        Some(Evaluated::Fixed { value, .. }) => Some(value),
+        _ => None,
+    };
+
+    let u = top.find("u").unwrap();
+    assert_eq!(u.module, "ModuleC");
+    assert_eq!(fixed(u.value("N")), Some(3));
+    assert_eq!(u.children.len(), 3);
+    assert_eq!(u.values[0].kind, elaborator::ValueKind::Generic);
+    assert_eq!(fixed(u.value("g_for[2].ITEM")), Some(20));
+    let names: Vec<_> = u.children.iter().map(|x| x.name.as_str()).collect();
+    assert_eq!(names, ["g_for[0].u", "g_for[1].u", "g_for[2].u"]);
+
+    let a = top.find("u.g_for[0].u").unwrap();
+    assert_eq!(a.module, "ModuleA");
+    assert_eq!(a.values[0].kind, elaborator::ValueKind::Param);
+    assert_eq!(a.values[2].kind, elaborator::ValueKind::Const);
+    assert_eq!(a.children.len(), 1);
+    assert_eq!(fixed(a.value("WIDTH")), Some(8));
+    assert_eq!(fixed(a.value("DEPTH")), Some(1));
+    assert_eq!(fixed(a.value("ADDR_WIDTH")), Some(0));
+    let b = top.find("u.g_for[0].u.g_wide.u").unwrap();
+    assert_eq!(b.module, "ModuleB");
+    assert!(b.children.is_empty());
+    assert_eq!(fixed(b.value("VALUE")), Some(1));
+    assert_eq!(fixed(b.value("HALF")), Some(0));
+
+    let a = top.find("u.g_for[2].u").unwrap();
+    assert_eq!(fixed(a.value("WIDTH")), Some(10));
+    assert_eq!(fixed(a.value("DEPTH")), Some(4));
+    assert_eq!(fixed(a.value("ADDR_WIDTH")), Some(2));
+    let b = top.find("u.g_for[2].u.g_wide.u").unwrap();
+    assert_eq!(fixed(b.value("VALUE")), Some(20));
+    assert_eq!(fixed(b.value("HALF")), Some(10));
+
+    assert!(elaborator::elaborate("ModuleX", &[&parser.veryl]).is_none());
 }
 
 #[test]
-fn sv_with_implicit_reset() {
+fn evaluate_function() {
     let code = r#"
-    module ModuleA {
-        var rst: reset;
+    package PkgA {
+        enum EnumA: logic<3> {
+            A,
+            B,
+            C,
+        }
 
-        inst u: $sv::Module (
-            rst,
-        );
+        function log2 (
+            x: input u32,
+        ) -> u32 {
+            var ret: u32;
+            ret = 0;
+            for i: u32 in 0..32 {
+                if (1 << i) >= x {
+                    return i;
+                }
+                ret += 1;
+            }
+            return ret;
+        }
+
+        function select (
+            x: input u32,
+        ) -> u32 {
+            case x {
+                0, 1   : return 10;
+                2..=4  : return 20;
+                default: return 30;
+            }
+        }
+
+        function fact (
+            x: input u32,
+        ) -> u32 {
+            if x <= 1 {
+                return 1;
+            } else {
+                return x * fact(x - 1);
+            }
+        }
+
+        function nibble (
+            x: input u32,
+        ) -> logic<4> {
+            return x + 4;
+        }
+
+        function signed_nibble (
+            x: input u32,
+        ) -> signed logic<4> {
+            return x[3:0];
+        }
+    }
+
+    module ModuleA #(
+        param DEPTH: u32 = 16,
+    ) {
+        import PkgA::*;
+
+        const A: u32 = log2(DEPTH);
+        const B: u32 = select(3) + select(7);
+        const C: u32 = fact(5);
+        const D: u32 = PkgA::EnumA::C;
+        const E: u32 = if DEPTH >: 8 { 1 } else { 2 };
+        const F: u32 = case DEPTH { 16: 3, default: 4 };
+        const G: u32 = inside DEPTH {1..=4, 16};
+        const H: f64 = 1.5 * 2.0e1;
+        const I: u32 = $rtoi($sqrt(H) * 10.0);
+        const J: u32 = nibble(20);
+        const K: i32 = signed_nibble(12);
+        const L: i32 = signed_nibble(3);
+        const M: u32 = {4'h3, 4'h5};
+        const N: u32 = {2'b10 repeat 3};
+        const O: u32 = {1'b1, {2'b01 repeat 2}, 3'd0};
+
+        const SIZE: u32 = 16;
+
+        var a: logic<$clog2(SIZE)>;
+        var b: logic<8>;
+        var c: logic<$clog2(DEPTH)>;
+
+        assign b = 0;
+        assign a = b;
+        assign c = b;
     }
     "#;
 
-    let errors = analyze(code);
+    let mut metadata: Metadata =
+        toml::from_str(&Metadata::create_default_toml("prj").unwrap()).unwrap();
+    metadata.lint.width.implicit_truncation = true;
+
+    symbol_table::clear();
+
+    let parser = Parser::parse(code, &"").unwrap();
+    let analyzer = Analyzer::new(&metadata);
+
+    let mut errors = vec![];
+    errors.append(&mut analyzer.analyze_pass1(&"prj", &code, &"", &parser.veryl));
+    errors.append(&mut analyzer.analyze_pass2(&"prj", &code, &"", &parser.veryl));
+    errors.append(&mut analyzer.analyze_pass3(&"prj", &code, &"", &parser.veryl));
+    assert_eq!(errors.len(), 1);
     assert!(matches!(
         errors[0],
-        AnalyzerError::SvWithImplicitReset { .. }
+        AnalyzerError::ImplicitTruncation { .. }
     ));
 
+    let top = elaborator::elaborate("ModuleA", &[&parser.veryl]).unwrap();
+
+    let fixed = |x: Option<Evaluated>| match x {
+        Some(Evaluated::Fixed { value, .. }) => Some(value),
+        _ => None,
+    };
+
+    assert_eq!(fixed(top.value("A")), Some(4));
+    assert_eq!(fixed(top.value("B")), Some(50));
+    assert_eq!(fixed(top.value("C")), Some(120));
+    assert_eq!(fixed(top.value("D")), Some(2));
+    assert_eq!(fixed(top.value("E")), Some(1));
+    assert_eq!(fixed(top.value("F")), Some(3));
+    assert_eq!(fixed(top.value("G")), Some(1));
+    assert!(matches!(top.value("H"), Some(Evaluated::Real { value }) if value == 30.0));
+    assert_eq!(fixed(top.value("I")), Some(54));
+    assert_eq!(fixed(top.value("J")), Some(8));
+    assert_eq!(fixed(top.value("K")), Some(-4));
+    assert_eq!(fixed(top.value("L")), Some(3));
+    assert_eq!(fixed(top.value("M")), Some(0x35));
+    assert_eq!(fixed(top.value("N")), Some(0b101010));
+    assert_eq!(fixed(top.value("O")), Some(0b10101000));
+}
+
+#[test]
+fn fsm_extraction() {
     let code = r#"
-    module ModuleB {
-        var rst: reset_async_low;
+    module ModuleA (
+        i_clk  : input clock,
+        i_rst  : input reset,
+        i_start: input logic,
+        i_done : input logic,
+    ) {
+        enum State: logic<2> {
+            Idle,
+            Run,
+            Done,
+            Error,
+        }
 
-        inst u: $sv::Module (
-            rst,
-        );
+        var state     : State;
+        var state_next: State;
+
+        always_ff {
+            if_reset {
+                state = State::Idle;
+            } else {
+                state = state_next;
+            }
+        }
+
+        always_comb {
+            state_next = state;
+            case state {
+                State::Idle: if i_start {
+                                 state_next = State::Run;
+                             }
+                State::Run: if i_done {
+                                state_next = State::Done;
+                            }
+            }
+        }
     }
-    "#;
+    module ModuleB (
+        i_clk: input clock,
+        i_rst: input reset,
+    ) {
+        enum State {
+            A,
+            B,
+            C,
+        }
 
-    let errors = analyze(code);
-    assert!(errors.is_empty());
-}
+        var state: State;
 
-#[test]
-fn conflict_with_mangled_enum_member() {
-    let code = r#"
-    module ModuleA {
-        enum EnumA: logic {
-            MemberA,
+        always_ff {
+            if_reset {
+                state = State::A;
+            } else {
+                state = case state {
+                    State::A: State::B,
+                    State::B: State::C,
+                    default : State::A,
+                };
+            }
         }
-        var EnumA_MemberA: logic;
     }
     "#;
 
-    let errors = analyze(code);
-    assert!(matches!(
-        errors[0],
-        AnalyzerError::DuplicatedIdentifier { .. }
-    ));
+    symbol_table::clear();
+
+    let metadata: Metadata =
+        toml::from_str(&Metadata::create_default_toml("prj").unwrap()).unwrap();
+    let parser = Parser::parse(code, &"").unwrap();
+    let analyzer = Analyzer::new(&metadata);
+
+    let mut errors = vec![];
+    errors.append(&mut analyzer.analyze_pass1(&"prj", &code, &"", &parser.veryl));
+    errors.append(&mut analyzer.analyze_pass2(&"prj", &code, &"", &parser.veryl));
+    assert!(errors.is_empty());
+
+    let fsms = fsm::analyze(&parser.veryl);
+    let transitions = |x: &fsm::StateMachine| -> Vec<String> {
+        x.transitions
+            .iter()
+            .map(|x| format!("{} -> {}", x.from, x.to))
+            .collect()
+    };
+
+    assert_eq!(fsms.len(), 2);
+    assert_eq!(fsms[0].register.to_string(), "state");
+    assert_eq!(fsms[0].reset.unwrap().to_string(), "Idle");
+    assert_eq!(transitions(&fsms[0]), vec!["Idle -> Run", "Run -> Done"]);
+    let issues: Vec<_> = fsms[0].issues.iter().map(|x| x.to_string()).collect();
+    assert_eq!(issues.len(), 3);
+    assert!(issues[0].starts_with("case without default"));
+    assert_eq!(issues[1], "state Error is unreachable from reset state");
+    assert_eq!(issues[2], "state Done has no exit");
+    assert!(fsms[0].mermaid().contains("[*] --> Idle\n"));
+
+    assert_eq!(fsms[1].module.to_string(), "ModuleB");
+    assert_eq!(transitions(&fsms[1]), vec!["A -> B", "B -> C", "C -> A"]);
+    assert!(fsms[1].issues.is_empty());
 }
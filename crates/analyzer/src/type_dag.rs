@@ -4,8 +4,10 @@ use crate::symbol_table;
 use bimap::BiMap;
 use daggy::petgraph::unionfind::UnionFind;
 use daggy::petgraph::visit::{EdgeRef, NodeIndexable};
+use daggy::petgraph::Direction;
 use daggy::{petgraph::algo, Dag, Walker};
 use std::{cell::RefCell, collections::HashMap, collections::HashSet};
+use veryl_parser::resource_table::PathId;
 use veryl_parser::veryl_token::Token;
 
 #[derive(Clone, Default)]
@@ -128,6 +130,41 @@ impl TypeDag {
         }
     }
 
+    fn drop(&mut self, file_path: PathId) {
+        let drop_list: Vec<_> = self
+            .symbols
+            .iter()
+            .filter(|(_, x)| x.token.source == file_path)
+            .map(|(x, _)| *x)
+            .collect();
+
+        // Removing nodes from DAG changes the other node indices,
+        // so the dropped nodes are kept as isolated nodes.
+        for node in drop_list {
+            let mut edges: Vec<_> = self
+                .dag
+                .graph()
+                .edges_directed(node.into(), Direction::Incoming)
+                .chain(
+                    self.dag
+                        .graph()
+                        .edges_directed(node.into(), Direction::Outgoing),
+                )
+                .map(|x| x.id())
+                .collect();
+            // Edge removal swaps the last edge into the removed index,
+            // so edges should be removed from the largest index.
+            edges.sort_by_key(|x| std::cmp::Reverse(x.index()));
+            edges.dedup();
+            for edge in edges {
+                self.dag.remove_edge(edge);
+            }
+            self.nodes.remove_by_right(&node);
+            self.paths.remove(&node);
+            self.symbols.remove(&node);
+        }
+    }
+
     fn toposort(&self) -> Vec<Symbol> {
         let nodes = algo::toposort(self.dag.graph(), None).unwrap();
         let mut ret = vec![];
@@ -217,6 +254,10 @@ pub fn get_symbol(node: u32) -> Symbol {
     TYPE_DAG.with(|f| f.borrow().get_symbol(node))
 }
 
+pub fn drop(file_path: PathId) {
+    TYPE_DAG.with(|f| f.borrow_mut().drop(file_path))
+}
+
 pub fn toposort() -> Vec<Symbol> {
     TYPE_DAG.with(|f| f.borrow().toposort())
 }
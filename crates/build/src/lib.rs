@@ -0,0 +1,202 @@
+use log::{debug, info};
+use miette::{IntoDiagnostic, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use veryl_analyzer::namespace::Namespace;
+use veryl_analyzer::symbol::SymbolKind;
+use veryl_analyzer::type_dag;
+use veryl_emitter::{Emitter, VhdlEmitter};
+use veryl_metadata::{FilelistType, Metadata, SourceMapTarget, Target, TargetLanguage};
+use veryl_parser::{resource_table, veryl_token::TokenSource, Parser};
+use veryl_path::PathPair;
+use veryl_sourcemap::SourceMap;
+
+/// Emit an analyzed source file and its source map.
+///
+/// If `temp_dir` is specified, the output is placed in it to be bundled by `gen_filelist`.
+pub fn emit(
+    metadata: &Metadata,
+    path: &PathPair,
+    input: &str,
+    parser: &Parser,
+    temp_dir: Option<&Path>,
+) -> Result<()> {
+    let dst = if let Some(temp_dir) = temp_dir {
+        temp_dir.join(
+            path.dst
+                .strip_prefix(metadata.project_path())
+                .into_diagnostic()?,
+        )
+    } else {
+        path.dst.clone()
+    };
+
+    let map_ext = format!("{}.map", metadata.build.target_language.extension());
+    let map = match &metadata.build.sourcemap_target {
+        SourceMapTarget::Directory { path: map_dir } => {
+            let dst = path
+                .dst
+                .strip_prefix(metadata.project_path())
+                .into_diagnostic()?;
+            let mut map = dst.to_path_buf();
+            map.set_extension(&map_ext);
+            metadata.project_path().join(map_dir).join(map)
+        }
+        _ => {
+            let mut map = dst.clone();
+            map.set_extension(&map_ext);
+            map
+        }
+    };
+
+    let source_map = |map: &mut SourceMap| -> Result<Option<Vec<u8>>> {
+        if metadata.build.sourcemap_target != SourceMapTarget::None {
+            map.set_source_content(input);
+            Ok(Some(map.to_bytes().into_diagnostic()?))
+        } else {
+            Ok(None)
+        }
+    };
+
+    let (text, source_map) = match metadata.build.target_language {
+        TargetLanguage::SystemVerilog | TargetLanguage::Verilog => {
+            let mut emitter = Emitter::new(metadata, &path.src, &dst, &map);
+            emitter.emit(&path.prj, &parser.veryl);
+            let source_map = source_map(emitter.source_map())?;
+            (emitter.as_str().to_string(), source_map)
+        }
+        TargetLanguage::Vhdl => {
+            let mut emitter = VhdlEmitter::new(metadata, &path.src, &dst, &map);
+            emitter.emit(&path.prj, &parser.veryl);
+            let source_map = source_map(emitter.source_map())?;
+            (emitter.as_str().to_string(), source_map)
+        }
+    };
+
+    write(&dst, text.as_bytes())?;
+    debug!("Output file ({})", dst.to_string_lossy());
+
+    if let Some(source_map) = source_map {
+        write(&map, &source_map)?;
+        debug!("Output map ({})", map.to_string_lossy());
+    }
+
+    Ok(())
+}
+
+/// Generate the filelist, and the bundled file if the target is bundle
+pub fn gen_filelist(
+    metadata: &Metadata,
+    paths: &[PathPair],
+    temp_dir: Option<&Path>,
+) -> Result<()> {
+    let filelist_path = metadata.filelist_path();
+    let base_path = metadata.project_path();
+
+    let paths = sort_filelist(metadata, paths);
+
+    let text = if let Target::Bundle { path } = &metadata.build.target {
+        let temp_dir = temp_dir.unwrap();
+        let mut text = String::new();
+        let target_path = base_path.join(path);
+
+        for path in paths {
+            let dst = temp_dir.join(path.dst.strip_prefix(&base_path).into_diagnostic()?);
+
+            text.push_str(&fs::read_to_string(&dst).into_diagnostic()?);
+        }
+
+        debug!("Output file ({})", target_path.to_string_lossy());
+        write(&target_path, text.as_bytes())?;
+
+        gen_filelist_line(metadata, &target_path)?
+    } else {
+        let mut text = String::new();
+        for path in paths {
+            let line = gen_filelist_line(metadata, &path.dst)?;
+            text.push_str(&line);
+        }
+        text
+    };
+
+    info!("Output filelist ({})", filelist_path.to_string_lossy());
+    write(&filelist_path, text.as_bytes())
+}
+
+fn gen_filelist_line(metadata: &Metadata, path: &Path) -> Result<String> {
+    let base_path = metadata.project_path();
+    let path = path.canonicalize().into_diagnostic()?;
+    let relative = path.strip_prefix(&base_path).into_diagnostic()?;
+    Ok(match metadata.build.filelist_type {
+        FilelistType::Absolute => format!("{}\n", path.to_string_lossy()),
+        FilelistType::Relative => format!("{}\n", relative.to_string_lossy()),
+        FilelistType::Flgen => {
+            format!("source_file '{}'\n", relative.to_string_lossy())
+        }
+    })
+}
+
+fn sort_filelist(metadata: &Metadata, paths: &[PathPair]) -> Vec<PathPair> {
+    let mut table = HashMap::new();
+    for path in paths {
+        table.insert(path.src.clone(), path);
+    }
+
+    // Remove files which are not connected from project
+    let connected_components = type_dag::connected_components();
+    let mut prj_namespace = Namespace::new();
+    prj_namespace.push(resource_table::insert_str(&metadata.project.name));
+    for symbols in &connected_components {
+        let used = symbols.iter().any(|x| x.namespace.included(&prj_namespace));
+        if !used {
+            for symbol in symbols {
+                if let TokenSource::File(x) = symbol.token.source {
+                    let path = PathBuf::from(format!("{}", x));
+                    table.remove(&path);
+                }
+            }
+        }
+    }
+
+    let mut ret = vec![];
+    let sorted_symbols = type_dag::toposort();
+    for symbol in sorted_symbols {
+        if matches!(
+            symbol.kind,
+            SymbolKind::Module(_) | SymbolKind::Interface(_) | SymbolKind::Package(_)
+        ) {
+            if let TokenSource::File(x) = symbol.token.source {
+                let path = PathBuf::from(format!("{}", x));
+                if let Some(x) = table.remove(&path) {
+                    ret.push(x.clone());
+                }
+            }
+        }
+    }
+
+    for path in table.into_values() {
+        ret.push(path.clone());
+    }
+
+    ret
+}
+
+fn write(path: &Path, contents: &[u8]) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        if !dir.exists() {
+            fs::create_dir_all(dir).into_diagnostic()?;
+        }
+    }
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .truncate(true)
+        .open(path)
+        .into_diagnostic()?;
+    file.write_all(contents).into_diagnostic()?;
+    file.flush().into_diagnostic()
+}
@@ -1,20 +1,27 @@
 use crate::aligner::{Aligner, Location};
+use std::collections::HashSet;
 use std::fs;
 use std::path::Path;
+use veryl_analyzer::assign::{AssignDeclarationType, AssignPositionType};
 use veryl_analyzer::attribute::EnumEncodingItem;
 use veryl_analyzer::evaluator::{Evaluated, Evaluator};
 use veryl_analyzer::namespace::Namespace;
+use veryl_analyzer::symbol::Direction as SymDirection;
+use veryl_analyzer::symbol::Type as SymType;
 use veryl_analyzer::symbol::TypeModifier as SymTypeModifier;
 use veryl_analyzer::symbol::{
-    GenericMap, Symbol, SymbolId, SymbolKind, TypeKind, VariableAffiniation,
+    EnumMemberValue, GenericMap, ParameterValue, Port, Symbol, SymbolId, SymbolKind, TypeKind,
+    VariableAffiniation,
 };
 use veryl_analyzer::symbol_path::{GenericSymbolPath, SymbolPath};
 use veryl_analyzer::symbol_table;
-use veryl_analyzer::{msb_table, namespace_table};
-use veryl_metadata::{Build, BuiltinType, ClockType, Format, Metadata, ResetType, SourceMapTarget};
+use veryl_analyzer::{function_table, msb_table, namespace_table};
+use veryl_metadata::{
+    Build, BuiltinType, ClockType, Format, Metadata, ResetType, SourceMapTarget, TargetLanguage,
+};
 use veryl_parser::resource_table::{self, StrId};
 use veryl_parser::veryl_grammar_trait::*;
-use veryl_parser::veryl_token::{Token, TokenSource, VerylToken};
+use veryl_parser::veryl_token::{Token, TokenRange, TokenSource, VerylToken};
 use veryl_parser::veryl_walker::VerylWalker;
 use veryl_parser::Stringifier;
 use veryl_sourcemap::SourceMap;
@@ -24,6 +31,13 @@ const NEWLINE: &str = "\r\n";
 #[cfg(not(target_os = "windows"))]
 const NEWLINE: &str = "\n";
 
+#[derive(Clone, Copy)]
+enum ArrayLiteralAssign {
+    Continuous,
+    Blocking,
+    NonBlocking,
+}
+
 pub enum AttributeType {
     Ifdef,
     Sv,
@@ -60,6 +74,19 @@ pub struct Emitter {
     assignment_lefthand_side: Option<ExpressionIdentifier>,
     generic_map: Vec<GenericMap>,
     source_map: Option<SourceMap>,
+    procedural_assigns: HashSet<(SymbolId, Option<StrId>)>,
+    verilog_net: Option<&'static str>,
+    verilog_function: Option<String>,
+    verilog_function_name: Option<String>,
+    verilog_functions: Vec<SymbolId>,
+    verilog_enum_members: Vec<SymbolId>,
+    verilog_instance_ports: Vec<Port>,
+    verilog_genvars: Vec<Vec<StrId>>,
+    verilog_generate_depth: usize,
+    verilog_block_id: usize,
+    verilog_callee_returns: Option<bool>,
+    in_inlined_function: bool,
+    in_flattened_type: bool,
 }
 
 impl Default for Emitter {
@@ -94,6 +121,19 @@ impl Default for Emitter {
             assignment_lefthand_side: None,
             generic_map: Vec::new(),
             source_map: None,
+            procedural_assigns: HashSet::new(),
+            verilog_net: None,
+            verilog_function: None,
+            verilog_function_name: None,
+            verilog_functions: Vec::new(),
+            verilog_enum_members: Vec::new(),
+            verilog_instance_ports: Vec::new(),
+            verilog_genvars: Vec::new(),
+            verilog_generate_depth: 0,
+            verilog_block_id: 0,
+            verilog_callee_returns: None,
+            in_inlined_function: false,
+            in_flattened_type: false,
         }
     }
 }
@@ -117,7 +157,11 @@ impl Emitter {
 
     pub fn emit(&mut self, project_name: &str, input: &Veryl) {
         namespace_table::set_default(&[project_name.into()]);
-        self.aligner.align(input);
+        if self.is_verilog() {
+            self.collect_procedural_assigns();
+        } else {
+            self.aligner.align(input);
+        }
         self.veryl(input);
     }
 
@@ -244,7 +288,7 @@ impl Emitter {
             &text
         };
 
-        if x.line != 0 && x.column != 0 {
+        if x.line != 0 && x.column != 0 && !self.in_inlined_function {
             if let Some(ref mut map) = self.source_map {
                 map.add(self.dst_line, self.dst_column, x.line, x.column, text);
             }
@@ -385,7 +429,10 @@ impl Emitter {
     }
 
     fn case_expression_condition(&mut self, lhs: &Expression, rhs: &RangeItem) {
-        if rhs.range.range_opt.is_some() && !self.build_opt.expand_inside_operation {
+        if rhs.range.range_opt.is_some()
+            && !self.build_opt.expand_inside_operation
+            && !self.is_verilog()
+        {
             self.str("(");
             self.expression(lhs);
             self.str(") inside {");
@@ -460,7 +507,11 @@ impl Emitter {
         } else {
             self.str("(");
             self.expression(lhs);
-            self.str(") ==? (");
+            if self.is_verilog() {
+                self.str(") == (");
+            } else {
+                self.str(") ==? (");
+            }
             self.expression(&rhs.range.expression);
             self.str(")");
         }
@@ -643,10 +694,12 @@ impl Emitter {
         self.space(1);
         self.colon(&arg.colon);
         self.identifier(&arg.identifier);
+        self.verilog_genvars.push(Vec::new());
         for (i, x) in arg.generate_named_block_list.iter().enumerate() {
             self.newline_list(i);
             self.generate_group(&x.generate_group);
         }
+        self.verilog_genvars.pop();
         self.newline_list_post(arg.generate_named_block_list.is_empty());
         self.token(&arg.r_brace.r_brace_token.replace("end"));
     }
@@ -679,40 +732,1381 @@ impl Emitter {
                 _ => unreachable!(),
             }
         }
-        self.newline_list_post(arg.statement_block_list.is_empty());
-        self.token(&arg.r_brace.r_brace_token.replace(end_kw));
+        self.newline_list_post(arg.statement_block_list.is_empty());
+        self.token(&arg.r_brace.r_brace_token.replace(end_kw));
+    }
+
+    fn statement_variable_declatation_only(&mut self, arg: &StatementBlockItem) -> usize {
+        self.clear_adjust_line();
+        match arg {
+            StatementBlockItem::VarDeclaration(x) => {
+                self.var_declaration(&x.var_declaration);
+                1
+            }
+            StatementBlockItem::LetStatement(x) => {
+                let x = &x.let_statement;
+                if self.is_verilog() {
+                    self.verilog_net = Some("reg");
+                }
+                self.scalar_type(&x.array_type.scalar_type);
+                self.space(1);
+                self.identifier(&x.identifier);
+                if let Some(ref x) = x.array_type.array_type_opt {
+                    self.space(1);
+                    self.array(&x.array);
+                }
+                self.str(";");
+                1
+            }
+            _ => 0,
+        }
+    }
+
+    fn is_verilog(&self) -> bool {
+        self.build_opt.target_language == TargetLanguage::Verilog
+    }
+
+    /// Whether modport ports and interface instances are flattened to individual signals
+    fn flatten_interface_ports(&self) -> bool {
+        self.build_opt.flatten_interface_ports || self.is_verilog()
+    }
+
+    /// Net type of the flattened interface member
+    fn flattened_net_type(
+        &self,
+        id: SymbolId,
+        member: StrId,
+        direction: Option<SymDirection>,
+    ) -> &'static str {
+        match direction {
+            Some(SymDirection::Input) if self.is_verilog() => "wire",
+            Some(SymDirection::Inout | SymDirection::Ref) => "wire",
+            _ if self.is_verilog() => self.net_type(id, Some(member)),
+            _ => "logic",
+        }
+    }
+
+    /// Collect variables assigned in always blocks which are declared as `reg` at Verilog
+    fn collect_procedural_assigns(&mut self) {
+        for assign in symbol_table::get_assign_list() {
+            let procedural = assign.position.0.iter().any(|x| {
+                matches!(
+                    x,
+                    AssignPositionType::Declaration {
+                        r#type: AssignDeclarationType::AlwaysFf | AssignDeclarationType::AlwaysComb,
+                        ..
+                    }
+                )
+            });
+            if !procedural {
+                continue;
+            }
+            if let Some(id) = assign.path.0.first() {
+                self.procedural_assigns.insert((*id, None));
+                if let Some(member) = assign.path.0.get(1).and_then(|x| symbol_table::get(*x)) {
+                    self.procedural_assigns
+                        .insert((*id, Some(member.token.text)));
+                }
+            }
+        }
+    }
+
+    fn is_nba(&self) -> bool {
+        if !self.in_always_ff {
+            false
+        } else if let Some(lhs) = &self.assignment_lefthand_side {
+            if let Ok(lhs_symbol) = symbol_table::resolve(lhs.scoped_identifier.as_ref()) {
+                match lhs_symbol.found.kind {
+                    SymbolKind::Variable(x) => !matches!(
+                        x.affiniation,
+                        VariableAffiniation::StatementBlock | VariableAffiniation::Function
+                    ),
+                    _ => true,
+                }
+            } else {
+                true
+            }
+        } else {
+            true
+        }
+    }
+
+    fn net_type(&self, id: SymbolId, member: Option<StrId>) -> &'static str {
+        if self.procedural_assigns.contains(&(id, member)) {
+            "reg"
+        } else {
+            "wire"
+        }
+    }
+
+    fn expression_string(&mut self, x: &Expression) -> String {
+        self.sub_string(|emitter| emitter.expression(x))
+    }
+
+    fn sub_string(&mut self, f: impl FnOnce(&mut Emitter)) -> String {
+        let mut build_opt = self.build_opt.clone();
+        build_opt.strip_comments = true;
+        let mut emitter = Emitter {
+            project_name: self.project_name,
+            build_opt,
+            format_opt: self.format_opt.clone(),
+            generic_map: self.generic_map.clone(),
+            verilog_enum_members: self.verilog_enum_members.clone(),
+            in_flattened_type: self.in_flattened_type,
+            ..Default::default()
+        };
+        f(&mut emitter);
+        for id in emitter.verilog_functions {
+            if !self.verilog_functions.contains(&id) {
+                self.verilog_functions.push(id);
+            }
+        }
+        emitter.string
+    }
+
+    /// Declaration type of Verilog which consists of net type, signedness and packed range
+    fn verilog_type(
+        &mut self,
+        r#type: &SymType,
+        namespace: &Namespace,
+        net: Option<&'static str>,
+    ) -> String {
+        if matches!(r#type.kind, TypeKind::F32 | TypeKind::F64) {
+            return "real".to_string();
+        }
+
+        let mut ret = Vec::new();
+        if let Some(net) = net {
+            if net == "wire" && r#type.modifier.contains(&SymTypeModifier::Tri) {
+                ret.push("tri".to_string());
+            } else {
+                ret.push(net.to_string());
+            }
+        }
+        let signed = r#type.modifier.contains(&SymTypeModifier::Signed)
+            || matches!(r#type.kind, TypeKind::I32 | TypeKind::I64);
+        if signed {
+            ret.push("signed".to_string());
+        }
+        // parameters of interface are referred from outside of the interface when flattened
+        let in_flattened_type = self.in_flattened_type;
+        self.in_flattened_type = true;
+        let width = self.verilog_type_width(r#type, namespace);
+        self.in_flattened_type = in_flattened_type;
+        if let Some(width) = width {
+            if width != "1" {
+                ret.push(format!("[{}-1:0]", width));
+            } else if net.is_none() {
+                ret.push("[0:0]".to_string());
+            }
+        }
+        ret.join(" ")
+    }
+
+    fn verilog_scalar_type(&mut self, arg: &ScalarType, net: Option<&'static str>) -> String {
+        let r#type: SymType = arg.into();
+        let mut namespace = Namespace::new();
+        if let ScalarTypeGroup::VariableTypeScalarTypeOpt(x) = &*arg.scalar_type_group {
+            if let VariableType::ScopedIdentifier(x) = &*x.variable_type {
+                let id = x.scoped_identifier.identifier().token.id;
+                namespace = namespace_table::get(id).unwrap_or_default();
+            }
+        }
+        self.verilog_type(&r#type, &namespace, net)
+    }
+
+    /// Total bit width of the type
+    fn verilog_type_width(&mut self, r#type: &SymType, namespace: &Namespace) -> Option<String> {
+        let base = match &r#type.kind {
+            TypeKind::U32 | TypeKind::I32 | TypeKind::F32 => "32".to_string(),
+            TypeKind::U64 | TypeKind::I64 | TypeKind::F64 => "64".to_string(),
+            TypeKind::Type | TypeKind::String => return None,
+            TypeKind::UserDefined(x) => {
+                let symbol = symbol_table::resolve((x, namespace)).ok()?;
+                self.verilog_symbol_width(&symbol.found)?
+            }
+            _ => "1".to_string(),
+        };
+        let mut factors = vec![base];
+        for x in &r#type.width {
+            factors.push(self.expression_string(x));
+        }
+        Some(width_product(&factors))
+    }
+
+    fn verilog_symbol_width(&mut self, symbol: &Symbol) -> Option<String> {
+        match &symbol.kind {
+            SymbolKind::Enum(x) => {
+                if let Some(ref r#type) = x.r#type {
+                    self.verilog_type_width(r#type, &symbol.namespace)
+                } else {
+                    Some(x.width.to_string())
+                }
+            }
+            SymbolKind::TypeDef(x) => self.verilog_type_width(&x.r#type, &symbol.namespace),
+            SymbolKind::Struct(_) | SymbolKind::Union(_)
+                if !is_inlined_namespace(&symbol.namespace) =>
+            {
+                Some(format!("{}_WIDTH", symbol.token))
+            }
+            SymbolKind::Struct(x) => {
+                let mut terms = Vec::new();
+                for id in &x.members {
+                    terms.push(self.verilog_member_width(*id)?);
+                }
+                Some(width_sum(&terms))
+            }
+            SymbolKind::Union(x) => self.verilog_member_width(*x.members.first()?),
+            SymbolKind::Parameter(x) => {
+                if let ParameterValue::TypeExpression(TypeExpression::ScalarType(ref x)) = x.value {
+                    let r#type: SymType = x.scalar_type.as_ref().into();
+                    self.verilog_type_width(&r#type, &symbol.namespace)
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn verilog_member_width(&mut self, id: SymbolId) -> Option<String> {
+        let symbol = symbol_table::get(id)?;
+        match &symbol.kind {
+            SymbolKind::StructMember(x) => self.verilog_type_width(&x.r#type, &symbol.namespace),
+            SymbolKind::UnionMember(x) => self.verilog_type_width(&x.r#type, &symbol.namespace),
+            _ => None,
+        }
+    }
+
+    fn verilog_struct_of(&mut self, r#type: &SymType, namespace: &Namespace) -> Option<Symbol> {
+        if let TypeKind::UserDefined(ref x) = r#type.kind {
+            let symbol = symbol_table::resolve((x, namespace)).ok()?.found;
+            match &symbol.kind {
+                SymbolKind::Struct(_) | SymbolKind::Union(_) => Some(symbol),
+                SymbolKind::TypeDef(x) => self.verilog_struct_of(&x.r#type, &symbol.namespace),
+                _ => None,
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Offset and width of the struct member in the packed vector
+    fn verilog_member_range(
+        &mut self,
+        r#type: &SymType,
+        namespace: &Namespace,
+        members: &[StrId],
+    ) -> Option<(String, String)> {
+        let mut r#type = r#type.clone();
+        let mut namespace = namespace.clone();
+        let mut offsets = Vec::new();
+        let mut width = String::new();
+        for member in members {
+            let symbol = self.verilog_struct_of(&r#type, &namespace)?;
+            let (ids, is_union) = match &symbol.kind {
+                SymbolKind::Struct(x) => (x.members.clone(), false),
+                SymbolKind::Union(x) => (x.members.clone(), true),
+                _ => return None,
+            };
+            let position = ids
+                .iter()
+                .position(|x| symbol_table::get(*x).map(|x| x.token.text) == Some(*member))?;
+            let member_symbol = symbol_table::get(ids[position])?;
+            let member_type = match &member_symbol.kind {
+                SymbolKind::StructMember(x) => x.r#type.clone(),
+                SymbolKind::UnionMember(x) => x.r#type.clone(),
+                _ => return None,
+            };
+            if is_inlined_namespace(&symbol.namespace) {
+                // the first member is placed at MSB
+                if !is_union {
+                    for id in &ids[position + 1..] {
+                        offsets.push(self.verilog_member_width(*id)?);
+                    }
+                }
+                width = self.verilog_type_width(&member_type, &member_symbol.namespace)?;
+            } else {
+                offsets.push(format!("{}_{}_OFFSET", symbol.token, member));
+                width = format!("{}_{}_WIDTH", symbol.token, member);
+            }
+            r#type = member_type;
+            namespace = member_symbol.namespace;
+        }
+        Some((width_sum(&offsets), width))
+    }
+
+    fn verilog_member_select(&mut self, offset: &str, width: &str, select: Option<&Select>) {
+        let Some(select) = select else {
+            self.str(&format!("[{} +: {}]", offset, width));
+            return;
+        };
+        self.l_bracket(&select.l_bracket);
+        self.str(&format!("{} + (", offset));
+        self.expression(&select.expression);
+        if let Some(ref x) = select.select_opt {
+            match &*x.select_operator {
+                SelectOperator::Colon(_) => {
+                    self.str(&format!("):{} + (", offset));
+                    self.expression(&x.expression);
+                    self.str(")");
+                }
+                SelectOperator::Step(_) => {
+                    self.str(")*(");
+                    self.expression(&x.expression);
+                    self.str(")+:(");
+                    self.expression(&x.expression);
+                    self.str(")");
+                }
+                _ => {
+                    self.str(")");
+                    self.select_operator(&x.select_operator);
+                    self.expression(&x.expression);
+                }
+            }
+        } else {
+            self.str(")");
+        }
+        self.r_bracket(&select.r_bracket);
+    }
+
+    /// Namespace of the interface if the symbol is flattened modport port or interface instance
+    fn flattened_interface(&mut self, symbol: &Symbol) -> Option<Namespace> {
+        match &symbol.kind {
+            SymbolKind::Port(x) if x.direction == SymDirection::Modport => {
+                let TypeKind::UserDefined(ref path) = x.r#type.as_ref()?.kind else {
+                    return None;
+                };
+                let modport = symbol_table::resolve((path, &symbol.namespace)).ok()?;
+                Some(modport.found.namespace)
+            }
+            SymbolKind::Instance(x) => {
+                let found = symbol_table::resolve((&x.type_name, &symbol.namespace)).ok()?;
+                if matches!(found.found.kind, SymbolKind::Interface(_)) {
+                    Some(found.found.inner_namespace())
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Member access of flattened interface or packed struct
+    fn verilog_member_access(
+        &mut self,
+        base: &VerylToken,
+        symbol: &Symbol,
+        selects: &[&Select],
+        members: &[(&Identifier, Vec<&Select>)],
+    ) -> bool {
+        let Some((member, member_selects)) = members.first() else {
+            return false;
+        };
+
+        let (name, r#type, namespace, members, selects) =
+            if let Some(namespace) = self.flattened_interface(symbol) {
+                let name = format!("{}_{}", base, member.identifier_token);
+                let path = SymbolPath::new(&[member.identifier_token.token.text]);
+                let variable = symbol_table::resolve((&path, &namespace)).ok();
+                let r#type = variable.as_ref().and_then(|x| symbol_type(&x.found));
+                if members.len() == 1 || r#type.is_none() {
+                    self.veryl_token(&base.replace(&name));
+                    for x in member_selects {
+                        self.select(x);
+                    }
+                    return true;
+                }
+                (
+                    name,
+                    r#type.unwrap(),
+                    variable.unwrap().found.namespace,
+                    &members[1..],
+                    member_selects.as_slice(),
+                )
+            } else if self.is_verilog() {
+                let Some(r#type) = symbol_type(symbol) else {
+                    return false;
+                };
+                let (prefix, suffix) = match &symbol.kind {
+                    SymbolKind::Port(x) => (x.prefix.clone(), x.suffix.clone()),
+                    SymbolKind::Variable(x) => (x.prefix.clone(), x.suffix.clone()),
+                    _ => (None, None),
+                };
+                let name = base.strip_prefix("r#").append(&prefix, &suffix).to_string();
+                (name, r#type, symbol.namespace.clone(), members, selects)
+            } else {
+                return false;
+            };
+
+        let names: Vec<_> = members
+            .iter()
+            .map(|x| x.0.identifier_token.token.text)
+            .collect();
+        let Some((offset, width)) = self.verilog_member_range(&r#type, &namespace, &names) else {
+            return false;
+        };
+        self.veryl_token(&base.replace(&name));
+        for x in selects {
+            self.select(x);
+        }
+        let last = members.last().and_then(|x| x.1.first().copied());
+        self.verilog_member_select(&offset, &width, last);
+        true
+    }
+
+    /// Modport members which are flattened to ports, returns tuples of (name, direction, variable)
+    fn modport_variables(
+        &mut self,
+        r#type: &SymType,
+        namespace: &Namespace,
+    ) -> Vec<(StrId, SymDirection, Symbol)> {
+        let mut ret = Vec::new();
+        let TypeKind::UserDefined(ref path) = r#type.kind else {
+            return ret;
+        };
+        let Ok(modport) = symbol_table::resolve((path, namespace)) else {
+            return ret;
+        };
+        let SymbolKind::Modport(ref property) = modport.found.kind else {
+            return ret;
+        };
+        for id in &property.members {
+            let member = symbol_table::get(*id).unwrap();
+            if let SymbolKind::ModportVariableMember(ref x) = member.kind {
+                let path = SymbolPath::new(&[member.token.text]);
+                if let Ok(variable) = symbol_table::resolve((&path, &modport.found.namespace)) {
+                    ret.push((member.token.text, x.direction, variable.found));
+                }
+            }
+        }
+        ret
+    }
+
+    /// Symbols in package and interface are inlined because they are not emitted at Verilog
+    fn verilog_inlined_symbol(&mut self, symbol: &Symbol) -> Option<String> {
+        // only the type of flattened interface member requires inlining at SystemVerilog
+        if !self.is_verilog() && !matches!(symbol.kind, SymbolKind::Parameter(_)) {
+            return None;
+        }
+        match &symbol.kind {
+            SymbolKind::Parameter(x) if is_inlined_namespace(&symbol.namespace) => {
+                let ParameterValue::Expression(ref x) = x.value else {
+                    return None;
+                };
+                let text = self.expression_string(x);
+                if is_simple_term(&text) {
+                    Some(text)
+                } else {
+                    Some(format!("({})", text))
+                }
+            }
+            SymbolKind::EnumMember(x) => {
+                let mut namespace = symbol.namespace.clone();
+                let name = namespace.pop()?;
+                if !is_inlined_namespace(&namespace) {
+                    return None;
+                }
+                if self.verilog_enum_members.contains(&symbol.id) {
+                    let context: SymbolContext = self.into();
+                    return Some(verilog_inlined_name(symbol, &context));
+                }
+                if let EnumMemberValue::ExplicitValue(ref x, _) = x.value {
+                    let text = self.expression_string(x);
+                    return Some(format!("({})", text));
+                }
+                let value = x.value.value().unwrap_or(0);
+                let path = SymbolPath::new(&[name]);
+                let r#enum = symbol_table::resolve((&path, &namespace)).ok()?;
+                match self.verilog_symbol_width(&r#enum.found) {
+                    Some(width) if width.parse::<usize>().is_ok() => {
+                        Some(format!("{}'d{}", width, value))
+                    }
+                    _ => Some(value.to_string()),
+                }
+            }
+            SymbolKind::Function(_) if is_inlined_namespace(&symbol.namespace) => {
+                if !self.verilog_functions.contains(&symbol.id) {
+                    self.verilog_functions.push(symbol.id);
+                }
+                let context: SymbolContext = self.into();
+                Some(verilog_inlined_name(symbol, &context))
+            }
+            _ => None,
+        }
+    }
+
+    /// Enum members of packages referenced from the module are declared as localparams,
+    /// because they should be declared before use
+    fn verilog_package_enum_members(&mut self, arg: &ModuleDeclaration) -> usize {
+        let mut collector = PackageEnumMemberCollector::default();
+        for x in &arg.module_declaration_list {
+            collector.module_group(&x.module_group);
+        }
+        self.verilog_enum_members = collector.members.clone();
+
+        for (i, id) in collector.members.iter().enumerate() {
+            let symbol = symbol_table::get(*id).unwrap();
+            let SymbolKind::EnumMember(ref member) = symbol.kind else {
+                unreachable!();
+            };
+            let mut namespace = symbol.namespace.clone();
+            let name = namespace.pop().unwrap();
+            let path = SymbolPath::new(&[name]);
+            let width = symbol_table::resolve((&path, &namespace))
+                .ok()
+                .and_then(|x| self.verilog_symbol_width(&x.found))
+                .unwrap_or("1".to_string());
+
+            let value = if let EnumMemberValue::ExplicitValue(ref x, _) = member.value {
+                self.expression_string(x)
+            } else if width.parse::<usize>().is_ok() {
+                format!("{}'d{}", width, member.value.value().unwrap_or(0))
+            } else {
+                format!("{}", member.value.value().unwrap_or(0))
+            };
+
+            let context: SymbolContext = self.into();
+            let name = verilog_inlined_name(&symbol, &context);
+            self.newline_list(i);
+            self.str(&format!("localparam [{width}-1:0] {name} = {value};"));
+        }
+        if !collector.members.is_empty() && !arg.module_declaration_list.is_empty() {
+            self.newline();
+        }
+        collector.members.len()
+    }
+
+    /// Functions of packages referenced from the module are copied into the module
+    fn verilog_package_functions(&mut self, base: usize) -> usize {
+        // line adjustment should be based on the module source
+        let src_line = self.src_line;
+        let mut i = 0;
+        while i < self.verilog_functions.len() {
+            let symbol = symbol_table::get(self.verilog_functions[i]).unwrap();
+            if let Some(x) = function_table::get(symbol.token.id) {
+                let context: SymbolContext = self.into();
+                self.newline_list(base + i);
+                if base + i != 0 {
+                    self.newline();
+                }
+                self.verilog_function_name = Some(verilog_inlined_name(&symbol, &context));
+                self.in_inlined_function = true;
+                self.function_declaration(&x);
+                self.in_inlined_function = false;
+            }
+            i += 1;
+        }
+        self.src_line = src_line;
+        i
+    }
+
+    fn verilog_statement_block(&mut self, arg: &StatementBlock, begin_kw: &str, end_kw: &str) {
+        let mut loop_variables = Vec::new();
+        let mut array_literal_loops = Vec::new();
+        for x in &arg.statement_block_list {
+            if let StatementBlockItem::Statement(x) = &*x.statement_block_item {
+                match &*x.statement {
+                    Statement::ForStatement(x) => {
+                        let x = &x.for_statement;
+                        let name = x.identifier.identifier_token.token.text;
+                        if !loop_variables.iter().any(|(y, _)| *y == name) {
+                            let text = self.verilog_scalar_type(&x.scalar_type, Some("reg"));
+                            loop_variables.push((name, text));
+                        }
+                    }
+                    Statement::IdentifierStatement(x) => {
+                        if let Some((dims, list)) =
+                            self.verilog_statement_array_literal(&x.identifier_statement)
+                        {
+                            self.verilog_array_literal_loops(&dims, list, &mut array_literal_loops);
+                        }
+                    }
+                    _ => (),
+                }
+            }
+        }
+        for name in array_literal_loops {
+            if !loop_variables.iter().any(|(y, _)| *y == name) {
+                loop_variables.push((name, "integer".to_string()));
+            }
+        }
+        let declarations = arg
+            .statement_block_list
+            .iter()
+            .filter(|x| {
+                is_var_declaration(&x.statement_block_item)
+                    || is_let_statement(&x.statement_block_item)
+            })
+            .count()
+            + loop_variables.len();
+
+        // declarations in block require named block
+        let in_function = begin_kw.is_empty();
+        if in_function || declarations == 0 {
+            self.token_will_push(&arg.l_brace.l_brace_token.replace(begin_kw));
+        } else {
+            self.verilog_block_id += 1;
+            let text = format!("{} : veryl_block_{}", begin_kw, self.verilog_block_id);
+            self.token_will_push(&arg.l_brace.l_brace_token.replace(&text));
+        }
+
+        let mut base = 0;
+        for x in &arg.statement_block_list {
+            if is_var_declaration(&x.statement_block_item)
+                || is_let_statement(&x.statement_block_item)
+            {
+                self.newline_list(base);
+                base += self.statement_variable_declatation_only(&x.statement_block_item);
+            }
+        }
+        for (name, text) in &loop_variables {
+            self.newline_list(base);
+            self.str(&format!("{} {};", text, name));
+            base += 1;
+        }
+        if in_function {
+            self.newline_list(base);
+            self.str("begin");
+            base = 0;
+        }
+
+        let mut is_empty = true;
+        for x in arg
+            .statement_block_list
+            .iter()
+            .filter(|x| !is_var_declaration(&x.statement_block_item))
+        {
+            self.newline_list(base);
+            base += 1;
+            is_empty = false;
+            match &*x.statement_block_item {
+                StatementBlockItem::LetStatement(x) => self.let_statement(&x.let_statement),
+                StatementBlockItem::Statement(x) => self.statement(&x.statement),
+                _ => unreachable!(),
+            }
+        }
+        if in_function {
+            self.newline_list_post(is_empty);
+            self.str("end");
+            self.newline_pop();
+        } else {
+            self.newline_list_post(is_empty && declarations == 0);
+        }
+        self.token(&arg.r_brace.r_brace_token.replace(end_kw));
+    }
+
+    fn verilog_port_declaration_item(&mut self, arg: &PortDeclarationItem) {
+        let symbol = symbol_table::resolve(arg.identifier.as_ref())
+            .ok()
+            .map(|x| x.found);
+        let PortDeclarationItemGroup::PortTypeConcrete(x) = &*arg.port_declaration_item_group
+        else {
+            // generic interface can't be flattened
+            self.interface_port_declaration_item(arg);
+            return;
+        };
+        let x = x.port_type_concrete.as_ref();
+
+        if let Direction::Modport(_) = *x.direction {
+            if let Some(symbol) = symbol {
+                self.flattened_modport_port(arg, &symbol, &x.array_type.scalar_type);
+            }
+            return;
+        }
+
+        let net = if self.verilog_function.is_some() {
+            "reg"
+        } else if let Direction::Output(_) = *x.direction {
+            symbol.map(|x| self.net_type(x.id, None)).unwrap_or("wire")
+        } else {
+            "wire"
+        };
+        self.direction(&x.direction);
+        self.space(1);
+        self.verilog_net = Some(net);
+        self.scalar_type(&x.array_type.scalar_type);
+        self.space(1);
+        self.identifier(&arg.identifier);
+        if let Some(ref x) = x.array_type.array_type_opt {
+            self.space(1);
+            self.array(&x.array);
+        }
+    }
+
+    /// Modport port is flattened to ports named `{port}_{member}`
+    fn flattened_modport_port(
+        &mut self,
+        arg: &PortDeclarationItem,
+        symbol: &Symbol,
+        scalar_type: &ScalarType,
+    ) {
+        let r#type: SymType = scalar_type.into();
+        let members = self.modport_variables(&r#type, &symbol.namespace);
+        for (i, (member, direction, variable)) in members.iter().enumerate() {
+            let net = self.flattened_net_type(symbol.id, *member, Some(*direction));
+            let direction = match direction {
+                SymDirection::Input => "input",
+                SymDirection::Output => "output",
+                _ => "inout",
+            };
+            let r#type = symbol_type(variable)
+                .map(|x| self.verilog_type(&x, &variable.namespace, Some(net)))
+                .unwrap_or(net.to_string());
+            let text = format!(
+                "{} {} {}_{}",
+                direction, r#type, arg.identifier.identifier_token, member
+            );
+            if i == 0 {
+                self.veryl_token(&arg.identifier.identifier_token.replace(&text));
+            } else {
+                self.str(",");
+                self.newline();
+                self.str(&text);
+            }
+        }
+    }
+
+    fn interface_port_declaration_item(&mut self, arg: &PortDeclarationItem) {
+        if let PortDeclarationItemGroup::PortTypeAbstract(x) = &*arg.port_declaration_item_group {
+            let x = x.port_type_abstract.as_ref();
+            self.interface(&x.interface);
+            self.space(1);
+            self.identifier(&arg.identifier);
+            if let Some(ref x) = x.port_type_abstract_opt0 {
+                self.space(1);
+                self.array(&x.array);
+            }
+        }
+    }
+
+    fn verilog_interface_instance(&mut self, arg: &InstDeclaration) -> bool {
+        let Ok(found) = symbol_table::resolve(arg.scoped_identifier.as_ref()) else {
+            return false;
+        };
+        if !matches!(found.found.kind, SymbolKind::Interface(_)) {
+            return false;
+        }
+        let Ok(instance) = symbol_table::resolve(arg.identifier.as_ref()) else {
+            return false;
+        };
+
+        let namespace = found.found.inner_namespace();
+        let mut variables: Vec<_> = symbol_table::get_all()
+            .into_iter()
+            .filter(|x| x.namespace == namespace && matches!(x.kind, SymbolKind::Variable(_)))
+            .collect();
+        variables.sort_by_key(|x| x.id);
+
+        for (i, variable) in variables.iter().enumerate() {
+            let net = self.flattened_net_type(instance.found.id, variable.token.text, None);
+            let r#type = symbol_type(variable)
+                .map(|x| self.verilog_type(&x, &variable.namespace, Some(net)))
+                .unwrap_or(net.to_string());
+            let text = format!(
+                "{} {}_{};",
+                r#type, arg.identifier.identifier_token, variable.token
+            );
+            if i == 0 {
+                self.token(&arg.inst.inst_token.replace(&text));
+            } else {
+                self.newline();
+                self.str(&text);
+            }
+        }
+        true
+    }
+
+    fn verilog_inst_port_item(&mut self, arg: &InstPortItem) -> bool {
+        let name = arg.identifier.identifier_token.token.text;
+        let Some(port) = self
+            .verilog_instance_ports
+            .iter()
+            .find(|x| x.name == name)
+            .and_then(|x| symbol_table::get(x.symbol))
+        else {
+            return false;
+        };
+        let SymbolKind::Port(ref property) = port.kind else {
+            return false;
+        };
+        if property.direction != SymDirection::Modport {
+            return false;
+        }
+        let Some(ref r#type) = property.r#type else {
+            return false;
+        };
+
+        let base = if let Some(ref x) = arg.inst_port_item_opt {
+            let mut stringifier = Stringifier::new();
+            stringifier.expression(&x.expression);
+            let text = stringifier.as_str().to_string();
+            // modport selection of interface instance is not required
+            text.split('.').next().unwrap().to_string()
+        } else {
+            arg.identifier.identifier_token.to_string()
+        };
+
+        let members = self.modport_variables(r#type, &port.namespace);
+        for (i, (member, _, _)) in members.iter().enumerate() {
+            let text = format!(
+                ".{}_{} ({}_{})",
+                arg.identifier.identifier_token, member, base, member
+            );
+            if i == 0 {
+                self.veryl_token(&arg.identifier.identifier_token.replace(&text));
+            } else {
+                self.str(",");
+                self.newline();
+                self.str(&text);
+            }
+        }
+        true
+    }
+
+    fn verilog_enum_declaration(&mut self, arg: &EnumDeclaration) {
+        let symbol = symbol_table::resolve(arg.identifier.as_ref())
+            .unwrap()
+            .found;
+        let width = self
+            .verilog_symbol_width(&symbol)
+            .unwrap_or("1".to_string());
+
+        self.token(&arg.r#enum.enum_token.replace(""));
+        let items: Vec<EnumItem> = arg.enum_list.as_ref().into();
+        for (i, item) in items.iter().enumerate() {
+            if i != 0 {
+                self.newline();
+            }
+            let member = symbol_table::resolve(item.identifier.as_ref()).unwrap();
+            let SymbolKind::EnumMember(member) = member.found.kind else {
+                unreachable!();
+            };
+            let text = format!(
+                "localparam [{}-1:0] {}_{}",
+                width, member.prefix, item.identifier.identifier_token
+            );
+            self.token(&item.identifier.identifier_token.replace(&text));
+            self.space(1);
+            self.str("=");
+            self.space(1);
+            if let Some(ref x) = item.enum_item_opt {
+                self.expression(&x.expression);
+            } else if width.parse::<usize>().is_ok() {
+                self.str(&format!("{}'d{}", width, member.value.value().unwrap_or(0)));
+            } else {
+                self.str(&format!("{}", member.value.value().unwrap_or(0)));
+            }
+            self.str(";");
+        }
+        self.token(&arg.r_brace.r_brace_token.replace(""));
+    }
+
+    fn verilog_struct_union_declaration(&mut self, arg: &StructUnionDeclaration) {
+        let symbol = symbol_table::resolve(arg.identifier.as_ref())
+            .unwrap()
+            .found;
+        let (members, is_union) = match &symbol.kind {
+            SymbolKind::Struct(x) => (x.members.clone(), false),
+            SymbolKind::Union(x) => (x.members.clone(), true),
+            _ => unreachable!(),
+        };
+        let name = arg.identifier.identifier_token.to_string();
+
+        self.token(&arg.l_brace.l_brace_token.replace(""));
+        // the first member is placed at MSB, so offsets are calculated from the last member
+        let mut prev: Option<String> = None;
+        for (i, id) in members.iter().rev().enumerate() {
+            let member = symbol_table::get(*id).unwrap();
+            let width = self.verilog_member_width(*id).unwrap_or("1".to_string());
+            let offset = match prev {
+                Some(ref x) if !is_union => format!("{}_{}_OFFSET + {}_{}_WIDTH", name, x, name, x),
+                _ => "0".to_string(),
+            };
+            if i != 0 {
+                self.newline();
+            }
+            let text = format!("localparam {}_{}_WIDTH = {};", name, member.token, width);
+            self.token(&VerylToken::new(member.token).replace(&text));
+            self.newline();
+            self.str(&format!(
+                "localparam {}_{}_OFFSET = {};",
+                name, member.token, offset
+            ));
+            prev = Some(member.token.to_string());
+        }
+        if !members.is_empty() {
+            self.newline();
+        }
+        let width = if is_union {
+            members
+                .first()
+                .map(|x| format!("{}_{}_WIDTH", name, symbol_table::get(*x).unwrap().token))
+                .unwrap_or("0".to_string())
+        } else {
+            members
+                .first()
+                .map(|x| {
+                    let x = symbol_table::get(*x).unwrap().token;
+                    format!("{}_{}_OFFSET + {}_{}_WIDTH", name, x, name, x)
+                })
+                .unwrap_or("0".to_string())
+        };
+        self.str(&format!("localparam {}_WIDTH = {};", name, width));
+        // r_brace is placed after the last member in source, so line adjustment is not required
+        self.adjust_line = false;
+        self.token(&arg.r_brace.r_brace_token.replace(""));
+    }
+
+    fn verilog_generate_begin(&mut self, token: &Token) {
+        if self.verilog_generate_depth == 0 {
+            self.consume_adjust_line(token);
+            self.str("generate");
+            self.newline();
+            self.adjust_line = false;
+        }
+        self.verilog_generate_depth += 1;
+    }
+
+    fn verilog_generate_end(&mut self) {
+        self.verilog_generate_depth -= 1;
+        if self.verilog_generate_depth == 0 {
+            self.newline();
+            self.str("endgenerate");
+        }
+    }
+
+    fn verilog_genvar(&mut self, token: &Token, name: StrId) {
+        if !self.verilog_genvars.iter().flatten().any(|x| *x == name) {
+            self.consume_adjust_line(token);
+            self.str(&format!("genvar {};", name));
+            self.newline();
+            self.adjust_line = false;
+            if let Some(x) = self.verilog_genvars.last_mut() {
+                x.push(name);
+            }
+        }
+    }
+
+    fn verilog_array_dims(&mut self, arg: &Array) -> Vec<String> {
+        let mut ret = vec![self.expression_string(&arg.expression)];
+        for x in &arg.array_list {
+            ret.push(self.expression_string(&x.expression));
+        }
+        ret
+    }
+
+    /// Unpacked dimensions of assignment target except for selected ones
+    fn verilog_target_dims(&mut self, symbol: &Symbol, selects: usize) -> Vec<String> {
+        let array = match &symbol.kind {
+            SymbolKind::Variable(x) => x.r#type.array.clone(),
+            SymbolKind::Port(x) => match &x.r#type {
+                Some(x) => x.array.clone(),
+                None => Vec::new(),
+            },
+            _ => Vec::new(),
+        };
+        array
+            .iter()
+            .skip(selects)
+            .map(|x| self.expression_string(x))
+            .collect()
+    }
+
+    /// Array literal at the right hand side of assignment statement and the dimensions of the left hand side
+    fn verilog_statement_array_literal<'a>(
+        &mut self,
+        arg: &'a IdentifierStatement,
+    ) -> Option<(Vec<String>, &'a ArrayLiteralList)> {
+        let IdentifierStatementGroup::Assignment(x) = arg.identifier_statement_group.as_ref()
+        else {
+            return None;
+        };
+        let AssignmentGroup::Equ(_) = x.assignment.assignment_group.as_ref() else {
+            return None;
+        };
+        let Some(Factor::QuoteLBraceArrayLiteralListRBrace(y)) =
+            single_factor(&x.assignment.expression)
+        else {
+            return None;
+        };
+        let ident = &arg.expression_identifier;
+        if !ident.expression_identifier_list0.is_empty() {
+            return None;
+        }
+        let symbol = symbol_table::resolve(ident.as_ref()).ok()?;
+        let dims = self.verilog_target_dims(&symbol.found, ident.expression_identifier_list.len());
+        (!dims.is_empty()).then_some((dims, y.array_literal_list.as_ref()))
+    }
+
+    fn verilog_assign_array_literal(&mut self, arg: &AssignDeclaration) -> bool {
+        let Some(Factor::QuoteLBraceArrayLiteralListRBrace(x)) = single_factor(&arg.expression)
+        else {
+            return false;
+        };
+        let ident = &arg.hierarchical_identifier;
+        if !ident.hierarchical_identifier_list0.is_empty() {
+            return false;
+        }
+        let Ok(symbol) = symbol_table::resolve(ident.as_ref()) else {
+            return false;
+        };
+        let dims =
+            self.verilog_target_dims(&symbol.found, ident.hierarchical_identifier_list.len());
+        if dims.is_empty() {
+            return false;
+        }
+
+        let target = self.sub_string(|emitter| emitter.hierarchical_identifier(ident));
+        self.verilog_array_literal(
+            &arg.assign.assign_token.token,
+            ArrayLiteralAssign::Continuous,
+            &target,
+            &dims,
+            &x.array_literal_list,
+        );
+        self.token(&arg.semicolon.semicolon_token.replace(""));
+        true
+    }
+
+    /// Elements of array literal as (start index, end index of repeat, expression)
+    fn verilog_array_literal_elements<'a>(
+        &mut self,
+        dims: &[String],
+        arg: &'a ArrayLiteralList,
+    ) -> Vec<(String, Option<String>, &'a Expression)> {
+        let mut items = vec![arg.array_literal_item.as_ref()];
+        items.extend(
+            arg.array_literal_list_list
+                .iter()
+                .map(|x| x.array_literal_item.as_ref()),
+        );
+
+        let mut offset = Vec::new();
+        let mut ret = Vec::new();
+        for item in items {
+            let start = width_sum(&offset);
+            match item.array_literal_item_group.as_ref() {
+                ArrayLiteralItemGroup::ExpressionArrayLiteralItemOpt(x) => {
+                    if let Some(ref y) = x.array_literal_item_opt {
+                        let count = self.expression_string(&y.expression);
+                        let count = if count.contains(' ') {
+                            format!("({})", count)
+                        } else {
+                            count
+                        };
+                        offset.push(count);
+                        ret.push((start, Some(width_sum(&offset)), x.expression.as_ref()));
+                    } else {
+                        offset.push("1".to_string());
+                        ret.push((start, None, x.expression.as_ref()));
+                    }
+                }
+                ArrayLiteralItemGroup::DefaulColonExpression(x) => {
+                    ret.push((start, Some(dims[0].clone()), x.expression.as_ref()));
+                }
+            }
+        }
+        ret
+    }
+
+    /// Loop variables which are required to assign array literal in statement
+    fn verilog_array_literal_loops(
+        &mut self,
+        dims: &[String],
+        arg: &ArrayLiteralList,
+        loops: &mut Vec<StrId>,
+    ) {
+        for (start, end, expression) in self.verilog_array_literal_elements(dims, arg) {
+            if let Some(end) = end {
+                if start.parse::<usize>().is_err() || end.parse::<usize>().is_err() {
+                    let name = resource_table::insert_str(&format!("veryl_i{}", dims.len()));
+                    if !loops.contains(&name) {
+                        loops.push(name);
+                    }
+                }
+            }
+            if dims.len() > 1 {
+                if let Some(Factor::QuoteLBraceArrayLiteralListRBrace(x)) =
+                    single_factor(expression)
+                {
+                    self.verilog_array_literal_loops(&dims[1..], &x.array_literal_list, loops);
+                }
+            }
+        }
+    }
+
+    /// Assign array literal element by element because Verilog doesn't have array literal
+    fn verilog_array_literal(
+        &mut self,
+        token: &Token,
+        kind: ArrayLiteralAssign,
+        target: &str,
+        dims: &[String],
+        arg: &ArrayLiteralList,
+    ) {
+        let mut first = true;
+        for (start, end, expression) in self.verilog_array_literal_elements(dims, arg) {
+            let Some(end) = end else {
+                self.verilog_array_element_newline(&mut first);
+                let target = format!("{}[{}]", target, start);
+                self.verilog_array_element(token, kind, &target, &dims[1..], expression);
+                continue;
+            };
+
+            // constant range is unrolled, and others are emitted as loop
+            if let (Ok(start), Ok(end)) = (start.parse::<usize>(), end.parse::<usize>()) {
+                for i in start..end {
+                    self.verilog_array_element_newline(&mut first);
+                    let target = format!("{}[{}]", target, i);
+                    self.verilog_array_element(token, kind, &target, &dims[1..], expression);
+                }
+                continue;
+            }
+
+            self.verilog_array_element_newline(&mut first);
+            let var = format!("veryl_i{}", dims.len());
+            let header = format!("for ({var} = {start}; {var} < {end}; {var} = {var} + 1) begin");
+            if let ArrayLiteralAssign::Continuous = kind {
+                self.verilog_generate_begin(token);
+                self.verilog_genvar(token, resource_table::insert_str(&var));
+                self.verilog_block_id += 1;
+                self.str(&format!(
+                    "{} : veryl_block_{}",
+                    header, self.verilog_block_id
+                ));
+            } else {
+                self.str(&header);
+            }
+            self.newline_push();
+            let target = format!("{}[{}]", target, var);
+            self.verilog_array_element(token, kind, &target, &dims[1..], expression);
+            self.newline_pop();
+            self.str("end");
+            if let ArrayLiteralAssign::Continuous = kind {
+                self.verilog_generate_end();
+            }
+        }
+    }
+
+    fn verilog_array_element_newline(&mut self, first: &mut bool) {
+        if !*first {
+            self.newline();
+        }
+        *first = false;
+    }
+
+    fn verilog_array_element(
+        &mut self,
+        token: &Token,
+        kind: ArrayLiteralAssign,
+        target: &str,
+        dims: &[String],
+        arg: &Expression,
+    ) {
+        if !dims.is_empty() {
+            if let Some(Factor::QuoteLBraceArrayLiteralListRBrace(x)) = single_factor(arg) {
+                self.verilog_array_literal(token, kind, target, dims, &x.array_literal_list);
+                return;
+            }
+        }
+        let expression = self.expression_string(arg);
+        let text = match kind {
+            ArrayLiteralAssign::Continuous => format!("assign {} = {};", target, expression),
+            ArrayLiteralAssign::Blocking => format!("{} = {};", target, expression),
+            ArrayLiteralAssign::NonBlocking => format!("{} <= {};", target, expression),
+        };
+        self.str(&text);
+    }
+}
+
+fn is_var_declaration(arg: &StatementBlockItem) -> bool {
+    matches!(arg, StatementBlockItem::VarDeclaration(_))
+}
+
+fn is_let_statement(arg: &StatementBlockItem) -> bool {
+    matches!(arg, StatementBlockItem::LetStatement(_))
+}
+
+fn symbol_type(symbol: &Symbol) -> Option<SymType> {
+    match &symbol.kind {
+        SymbolKind::Port(x) => x.r#type.clone(),
+        SymbolKind::Variable(x) => Some(x.r#type.clone()),
+        SymbolKind::Parameter(x) => Some(x.r#type.clone()),
+        _ => None,
+    }
+}
+
+/// Whether the namespace is placed in package or interface which are not emitted at Verilog
+/// Collect enum members of packages referred from the syntax tree,
+/// including the bodies of referred package functions
+#[derive(Default)]
+struct PackageEnumMemberCollector {
+    members: Vec<SymbolId>,
+    functions: Vec<SymbolId>,
+}
+
+impl VerylWalker for PackageEnumMemberCollector {
+    fn scoped_identifier(&mut self, arg: &ScopedIdentifier) {
+        let Ok(symbol) = symbol_table::resolve(arg) else {
+            return;
+        };
+        match symbol.found.kind {
+            SymbolKind::EnumMember(_) => {
+                let mut namespace = symbol.found.namespace.clone();
+                namespace.pop();
+                if is_inlined_namespace(&namespace) && !self.members.contains(&symbol.found.id) {
+                    self.members.push(symbol.found.id);
+                }
+            }
+            SymbolKind::Function(_)
+                if is_inlined_namespace(&symbol.found.namespace)
+                    && !self.functions.contains(&symbol.found.id) =>
+            {
+                self.functions.push(symbol.found.id);
+                if let Some(x) = function_table::get(symbol.found.token.id) {
+                    self.function_declaration(&x);
+                }
+            }
+            _ => (),
+        }
+    }
+}
+
+fn is_inlined_namespace(namespace: &Namespace) -> bool {
+    let Some((prj, paths)) = namespace.paths.split_first() else {
+        return false;
+    };
+    let mut base = Namespace::new();
+    base.push(*prj);
+    for path in paths {
+        let symbol_path = SymbolPath::new(&[*path]);
+        if let Ok(symbol) = symbol_table::resolve((&symbol_path, &base)) {
+            if matches!(
+                symbol.found.kind,
+                SymbolKind::Package(_) | SymbolKind::Interface(_)
+            ) {
+                return true;
+            }
+        }
+        base.push(*path);
     }
+    false
+}
 
-    fn statement_variable_declatation_only(&mut self, arg: &StatementBlockItem) -> usize {
-        self.clear_adjust_line();
-        match arg {
-            StatementBlockItem::VarDeclaration(x) => {
-                self.var_declaration(&x.var_declaration);
-                1
-            }
-            StatementBlockItem::LetStatement(x) => {
-                let x = &x.let_statement;
-                self.scalar_type(&x.array_type.scalar_type);
-                self.space(1);
-                self.identifier(&x.identifier);
-                if let Some(ref x) = x.array_type.array_type_opt {
-                    self.space(1);
-                    self.array(&x.array);
-                }
-                self.str(";");
-                1
-            }
-            _ => 0,
+/// Whether the description group has only package and interface which are not emitted at Verilog
+fn is_inlined_description(arg: &DescriptionGroup) -> bool {
+    let items: Vec<DescriptionItem> = arg.into();
+    items.iter().all(|x| {
+        matches!(
+            x,
+            DescriptionItem::InterfaceDeclaration(_) | DescriptionItem::PackageDeclaration(_)
+        )
+    })
+}
+
+fn is_simple_term(x: &str) -> bool {
+    x.chars().all(|x| x.is_ascii_alphanumeric() || x == '_')
+}
+
+fn width_product(factors: &[String]) -> String {
+    let mut value = 1;
+    let mut terms = Vec::new();
+    for x in factors {
+        if let Ok(x) = x.parse::<usize>() {
+            value *= x;
+        } else if is_simple_term(x) {
+            terms.push(x.clone());
+        } else {
+            terms.push(format!("({})", x));
         }
     }
+    if value != 1 || terms.is_empty() {
+        terms.insert(0, value.to_string());
+    }
+    terms.join("*")
 }
 
-fn is_var_declaration(arg: &StatementBlockItem) -> bool {
-    matches!(arg, StatementBlockItem::VarDeclaration(_))
+fn width_sum(terms: &[String]) -> String {
+    let mut value = 0;
+    let mut ret = Vec::new();
+    for x in terms {
+        if let Ok(x) = x.parse::<usize>() {
+            value += x;
+        } else {
+            ret.push(x.clone());
+        }
+    }
+    if value != 0 || ret.is_empty() {
+        ret.push(value.to_string());
+    }
+    ret.join(" + ")
 }
 
-fn is_let_statement(arg: &StatementBlockItem) -> bool {
-    matches!(arg, StatementBlockItem::LetStatement(_))
+/// Factor if the expression has no operator
+pub(crate) fn single_factor(x: &Expression) -> Option<&Factor> {
+    if !x.expression_list.is_empty() {
+        return None;
+    }
+    let x = &x.expression01;
+    if !x.expression01_list.is_empty() {
+        return None;
+    }
+    let x = &x.expression02;
+    if !x.expression02_list.is_empty() {
+        return None;
+    }
+    let x = &x.expression03;
+    if !x.expression03_list.is_empty() {
+        return None;
+    }
+    let x = &x.expression04;
+    if !x.expression04_list.is_empty() {
+        return None;
+    }
+    let x = &x.expression05;
+    if !x.expression05_list.is_empty() {
+        return None;
+    }
+    let x = &x.expression06;
+    if !x.expression06_list.is_empty() {
+        return None;
+    }
+    let x = &x.expression07;
+    if !x.expression07_list.is_empty() {
+        return None;
+    }
+    let x = &x.expression08;
+    if !x.expression08_list.is_empty() {
+        return None;
+    }
+    let x = &x.expression09;
+    if !x.expression09_list.is_empty() {
+        return None;
+    }
+    let x = &x.expression10;
+    if !x.expression10_list.is_empty() {
+        return None;
+    }
+    let x = &x.expression11;
+    if x.expression11_opt.is_some() {
+        return None;
+    }
+    let x = &x.expression12;
+    if !x.expression12_list.is_empty() {
+        return None;
+    }
+    Some(&x.factor)
+}
+
+/// Name of package function and enum member which are copied into the module
+fn verilog_inlined_name(symbol: &Symbol, context: &SymbolContext) -> String {
+    let namespace = namespace_string(&symbol.namespace, context);
+    format!(
+        "{}{}",
+        namespace.replace("::", "_").replace('.', "_"),
+        symbol.token
+    )
 }
 
 impl VerylWalker for Emitter {
@@ -721,6 +2115,15 @@ impl VerylWalker for Emitter {
         self.token(arg);
     }
 
+    /// Semantic action for non-terminal 'QuoteLBrace'
+    fn quote_l_brace(&mut self, arg: &QuoteLBrace) {
+        if self.is_verilog() {
+            self.token(&arg.quote_l_brace_token.replace("{"));
+        } else {
+            self.token(&arg.quote_l_brace_token);
+        }
+    }
+
     /// Semantic action for non-terminal 'Based'
     fn based(&mut self, arg: &Based) {
         let token = &arg.based_token;
@@ -755,7 +2158,17 @@ impl VerylWalker for Emitter {
         let (width, tail) = text.split_once('\'').unwrap();
 
         if width.is_empty() {
-            self.veryl_token(&arg.all_bit_token);
+            if self.is_verilog() {
+                let text = match tail {
+                    "0" => "0",
+                    "1" => "~0",
+                    "x" | "X" => "'bx",
+                    _ => "'bz",
+                };
+                self.veryl_token(&arg.all_bit_token.replace(text));
+            } else {
+                self.veryl_token(&arg.all_bit_token);
+            }
         } else {
             let width: usize = width.parse().unwrap();
             let text = format!("{width}'b{}", tail.repeat(width));
@@ -904,6 +2317,32 @@ impl VerylWalker for Emitter {
 
     /// Semantic action for non-terminal 'HierarchicalIdentifier'
     fn hierarchical_identifier(&mut self, arg: &HierarchicalIdentifier) {
+        if self.flatten_interface_ports() && !arg.hierarchical_identifier_list0.is_empty() {
+            if let Ok(found) = symbol_table::resolve(arg.identifier.as_ref()) {
+                let selects: Vec<_> = arg
+                    .hierarchical_identifier_list
+                    .iter()
+                    .map(|x| x.select.as_ref())
+                    .collect();
+                let members: Vec<_> = arg
+                    .hierarchical_identifier_list0
+                    .iter()
+                    .map(|x| {
+                        let selects: Vec<_> = x
+                            .hierarchical_identifier_list0_list
+                            .iter()
+                            .map(|x| x.select.as_ref())
+                            .collect();
+                        (x.identifier.as_ref(), selects)
+                    })
+                    .collect();
+                let base = &arg.identifier.identifier_token;
+                if self.verilog_member_access(base, &found.found, &selects, &members) {
+                    return;
+                }
+            }
+        }
+
         let list_len = &arg.hierarchical_identifier_list0.len();
         let (prefix, suffix) = if let Ok(found) = symbol_table::resolve(arg) {
             match &found.found.kind {
@@ -976,6 +2415,12 @@ impl VerylWalker for Emitter {
 
         path.apply_map(&self.generic_map);
         if let Ok(symbol) = symbol_table::resolve((&path.mangled_path(), &namespace)) {
+            if self.is_verilog() || self.in_flattened_type {
+                if let Some(text) = self.verilog_inlined_symbol(&symbol.found) {
+                    self.veryl_token(&arg.identifier().replace(&text));
+                    return;
+                }
+            }
             let context: SymbolContext = self.into();
             let text = symbol_string(arg.identifier(), &symbol.found, &context);
             self.veryl_token(&arg.identifier().replace(&text));
@@ -988,6 +2433,43 @@ impl VerylWalker for Emitter {
 
     /// Semantic action for non-terminal 'ExpressionIdentifier'
     fn expression_identifier(&mut self, arg: &ExpressionIdentifier) {
+        let callee = if self.is_verilog() {
+            match symbol_table::resolve(arg) {
+                Ok(x) => match x.found.kind {
+                    SymbolKind::Function(ref x) => Some(x.ret.is_some()),
+                    _ => None,
+                },
+                Err(_) => None,
+            }
+        } else {
+            None
+        };
+        if self.flatten_interface_ports() && !arg.expression_identifier_list0.is_empty() {
+            if let Ok(found) = symbol_table::resolve(arg.scoped_identifier.as_ref()) {
+                let selects: Vec<_> = arg
+                    .expression_identifier_list
+                    .iter()
+                    .map(|x| x.select.as_ref())
+                    .collect();
+                let members: Vec<_> = arg
+                    .expression_identifier_list0
+                    .iter()
+                    .map(|x| {
+                        let selects: Vec<_> = x
+                            .expression_identifier_list0_list
+                            .iter()
+                            .map(|x| x.select.as_ref())
+                            .collect();
+                        (x.identifier.as_ref(), selects)
+                    })
+                    .collect();
+                let base = arg.scoped_identifier.identifier();
+                if self.verilog_member_access(base, &found.found, &selects, &members) {
+                    self.verilog_callee_returns = callee;
+                    return;
+                }
+            }
+        }
         self.scoped_identifier(&arg.scoped_identifier);
         for x in &arg.expression_identifier_list {
             self.select(&x.select);
@@ -999,6 +2481,35 @@ impl VerylWalker for Emitter {
                 self.select(&x.select);
             }
         }
+        self.verilog_callee_returns = callee;
+    }
+
+    /// Semantic action for non-terminal 'FunctionCall'
+    fn function_call(&mut self, arg: &FunctionCall) {
+        let callee = self.verilog_callee_returns.take();
+        if arg.function_call_opt.is_none() {
+            match callee {
+                // task without argument is enabled without parentheses
+                Some(false) => {
+                    self.token(&arg.l_paren.l_paren_token.replace(""));
+                    self.token(&arg.r_paren.r_paren_token.replace(""));
+                    return;
+                }
+                // dummy argument for the input added at function declaration
+                Some(true) => {
+                    self.l_paren(&arg.l_paren);
+                    self.str("1'b0");
+                    self.r_paren(&arg.r_paren);
+                    return;
+                }
+                None => (),
+            }
+        }
+        self.l_paren(&arg.l_paren);
+        if let Some(ref x) = arg.function_call_opt {
+            self.argument_list(&x.argument_list);
+        }
+        self.r_paren(&arg.r_paren);
     }
 
     /// Semantic action for non-terminal 'Expression'
@@ -1127,6 +2638,23 @@ impl VerylWalker for Emitter {
 
     /// Semantic action for non-terminal 'Expression11'
     fn expression11(&mut self, arg: &Expression11) {
+        if self.is_verilog() {
+            if let Some(x) = &arg.expression11_opt {
+                let text = match x.casting_type.as_ref() {
+                    CastingType::U32(_) | CastingType::U64(_) => Some("$unsigned("),
+                    CastingType::I32(_) | CastingType::I64(_) => Some("$signed("),
+                    CastingType::F32(_) | CastingType::F64(_) => Some("$itor("),
+                    CastingType::ScopedIdentifier(_) => Some("("),
+                    _ => None,
+                };
+                if let Some(text) = text {
+                    self.str(text);
+                    self.expression12(&arg.expression12);
+                    self.str(")");
+                    return;
+                }
+            }
+        }
         if let Some(x) = &arg.expression11_opt {
             match x.casting_type.as_ref() {
                 CastingType::U32(_) => self.str("unsigned'(int'("),
@@ -1420,7 +2948,7 @@ impl VerylWalker for Emitter {
 
     /// Semantic action for non-terminal 'InsideExpression'
     fn inside_expression(&mut self, arg: &InsideExpression) {
-        if self.build_opt.expand_inside_operation {
+        if self.build_opt.expand_inside_operation || self.is_verilog() {
             self.inside_expanded_expression(arg);
         } else {
             self.inside_normal_expression(arg);
@@ -1429,7 +2957,7 @@ impl VerylWalker for Emitter {
 
     /// Semantic action for non-terminal 'OutsideExpression'
     fn outside_expression(&mut self, arg: &OutsideExpression) {
-        if self.build_opt.expand_inside_operation {
+        if self.build_opt.expand_inside_operation || self.is_verilog() {
             self.outside_expanded_expression(arg);
         } else {
             self.outside_normal_expression(arg);
@@ -1531,6 +3059,13 @@ impl VerylWalker for Emitter {
 
     /// Semantic action for non-terminal 'ScalarType'
     fn scalar_type(&mut self, arg: &ScalarType) {
+        if self.is_verilog() {
+            let net = self.verilog_net.take();
+            let text = self.verilog_scalar_type(arg, net);
+            let range: TokenRange = arg.into();
+            self.veryl_token(&VerylToken::new(range.beg).replace(&text));
+            return;
+        }
         for x in &arg.scalar_type_list {
             self.type_modifier(&x.type_modifier);
         }
@@ -1553,7 +3088,11 @@ impl VerylWalker for Emitter {
 
     /// Semantic action for non-terminal 'StatementBlock'
     fn statement_block(&mut self, arg: &StatementBlock) {
-        self.emit_statement_block(arg, "begin", "end");
+        if self.is_verilog() {
+            self.verilog_statement_block(arg, "begin", "end");
+        } else {
+            self.emit_statement_block(arg, "begin", "end");
+        }
     }
 
     /// Semantic action for non-terminal 'LetStatement'
@@ -1578,6 +3117,23 @@ impl VerylWalker for Emitter {
 
     /// Semantic action for non-terminal 'IdentifierStatement'
     fn identifier_statement(&mut self, arg: &IdentifierStatement) {
+        if self.is_verilog() {
+            if let Some((dims, list)) = self.verilog_statement_array_literal(arg) {
+                self.assignment_lefthand_side = Some(*arg.expression_identifier.clone());
+                let kind = if self.is_nba() {
+                    ArrayLiteralAssign::NonBlocking
+                } else {
+                    ArrayLiteralAssign::Blocking
+                };
+                let target = self.sub_string(|emitter| {
+                    emitter.expression_identifier(&arg.expression_identifier)
+                });
+                let token = &arg.semicolon.semicolon_token;
+                self.verilog_array_literal(&token.token, kind, &target, &dims, list);
+                self.token(&token.replace(""));
+                return;
+            }
+        }
         self.expression_identifier(&arg.expression_identifier);
         self.assignment_lefthand_side = Some(*arg.expression_identifier.clone());
         match &*arg.identifier_statement_group {
@@ -1593,27 +3149,14 @@ impl VerylWalker for Emitter {
 
     /// Semantic action for non-terminal 'Assignment'
     fn assignment(&mut self, arg: &Assignment) {
-        let is_nba = if !self.in_always_ff {
-            false
-        } else if let Some(lhs) = &self.assignment_lefthand_side {
-            if let Ok(lhs_symbol) = symbol_table::resolve(lhs.scoped_identifier.as_ref()) {
-                match lhs_symbol.found.kind {
-                    SymbolKind::Variable(x) => !matches!(
-                        x.affiniation,
-                        VariableAffiniation::StatementBlock | VariableAffiniation::Function
-                    ),
-                    _ => true,
-                }
-            } else {
-                true
-            }
-        } else {
-            true
-        };
+        let is_nba = self.is_nba();
 
         self.space(1);
-        if is_nba {
-            self.str("<");
+        // Verilog doesn't have assignment operator
+        if is_nba || self.is_verilog() {
+            if is_nba {
+                self.str("<");
+            }
             match &*arg.assignment_group {
                 AssignmentGroup::Equ(x) => self.equ(&x.equ),
                 AssignmentGroup::AssignmentOperator(x) => {
@@ -1712,7 +3255,13 @@ impl VerylWalker for Emitter {
 
     /// Semantic action for non-terminal 'ReturnStatement'
     fn return_statement(&mut self, arg: &ReturnStatement) {
-        self.r#return(&arg.r#return);
+        if let Some(name) = self.verilog_function.clone() {
+            self.token(&arg.r#return.return_token.replace(&name));
+            self.space(1);
+            self.str("=");
+        } else {
+            self.r#return(&arg.r#return);
+        }
         self.space(1);
         self.expression(&arg.expression);
         self.semicolon(&arg.semicolon);
@@ -1729,8 +3278,11 @@ impl VerylWalker for Emitter {
         self.r#for(&arg.r#for);
         self.space(1);
         self.str("(");
-        self.scalar_type(&arg.scalar_type);
-        self.space(1);
+        // loop variable is declared at the enclosing block in Verilog
+        if !self.is_verilog() {
+            self.scalar_type(&arg.scalar_type);
+            self.space(1);
+        }
         self.identifier(&arg.identifier);
         self.space(1);
         self.str("=");
@@ -1756,7 +3308,28 @@ impl VerylWalker for Emitter {
         }
         self.str(";");
         self.space(1);
-        if let Some(ref x) = arg.for_statement_opt {
+        if self.is_verilog() {
+            self.identifier(&arg.identifier);
+            self.space(1);
+            self.str("=");
+            self.space(1);
+            self.identifier(&arg.identifier);
+            self.space(1);
+            if let Some(ref x) = arg.for_statement_opt {
+                let text = x.assignment_operator.assignment_operator_token.to_string();
+                self.token(
+                    &x.assignment_operator
+                        .assignment_operator_token
+                        .replace(&text[0..text.len() - 1]),
+                );
+                self.space(1);
+                self.str("(");
+                self.expression(&x.expression);
+                self.str(")");
+            } else {
+                self.str("+ 1");
+            }
+        } else if let Some(ref x) = arg.for_statement_opt {
             self.identifier(&arg.identifier);
             self.space(1);
             self.assignment_operator(&x.assignment_operator);
@@ -1773,7 +3346,7 @@ impl VerylWalker for Emitter {
 
     /// Semantic action for non-terminal 'CaseStatement'
     fn case_statement(&mut self, arg: &CaseStatement) {
-        if self.build_opt.expand_inside_operation {
+        if self.build_opt.expand_inside_operation || self.is_verilog() {
             self.case_expaneded_statement(arg);
         } else {
             self.case_inside_statement(arg);
@@ -1913,6 +3486,9 @@ impl VerylWalker for Emitter {
             .iter()
             .any(|x| matches!(x.type_modifier.as_ref(), TypeModifier::Tri(_)));
 
+        if self.is_verilog() {
+            self.verilog_net = Some("wire");
+        }
         self.scalar_type(&arg.array_type.scalar_type);
         self.space(1);
         self.identifier(&arg.identifier);
@@ -1922,7 +3498,25 @@ impl VerylWalker for Emitter {
         }
         self.str(";");
         self.newline();
-        if is_tri {
+        if self.is_verilog() {
+            if let (Some(x), Some(Factor::QuoteLBraceArrayLiteralListRBrace(y))) = (
+                &arg.array_type.array_type_opt,
+                single_factor(&arg.expression),
+            ) {
+                let dims = self.verilog_array_dims(&x.array);
+                let token = &arg.identifier.identifier_token;
+                self.verilog_array_literal(
+                    &token.token,
+                    ArrayLiteralAssign::Continuous,
+                    &token.to_string(),
+                    &dims,
+                    &y.array_literal_list,
+                );
+                self.token(&arg.semicolon.semicolon_token.replace(""));
+                return;
+            }
+        }
+        if is_tri || self.is_verilog() {
             self.str("assign");
         } else {
             self.str("always_comb");
@@ -1938,6 +3532,23 @@ impl VerylWalker for Emitter {
 
     /// Semantic action for non-terminal 'VarDeclaration'
     fn var_declaration(&mut self, arg: &VarDeclaration) {
+        if self.is_verilog() {
+            let net = match symbol_table::resolve(arg.identifier.as_ref()) {
+                Ok(x) => match x.found.kind {
+                    SymbolKind::Variable(ref y)
+                        if matches!(
+                            y.affiniation,
+                            VariableAffiniation::StatementBlock | VariableAffiniation::Function
+                        ) =>
+                    {
+                        "reg"
+                    }
+                    _ => self.net_type(x.found.id, None),
+                },
+                Err(_) => "wire",
+            };
+            self.verilog_net = Some(net);
+        }
         self.scalar_type(&arg.array_type.scalar_type);
         self.space(1);
         self.identifier(&arg.identifier);
@@ -1950,6 +3561,32 @@ impl VerylWalker for Emitter {
 
     /// Semantic action for non-terminal 'ConstDeclaration'
     fn const_declaration(&mut self, arg: &ConstDeclaration) {
+        if self.is_verilog() {
+            match &*arg.const_declaration_group {
+                ConstDeclarationGroup::ArrayTypeEquExpression(x) => {
+                    self.r#const(&arg.r#const);
+                    self.space(1);
+                    if !self.is_implicit_scalar_type(&x.array_type.scalar_type) {
+                        let text = self.verilog_scalar_type(&x.array_type.scalar_type, None);
+                        if !text.is_empty() {
+                            self.str(&text);
+                            self.space(1);
+                        }
+                    }
+                    self.identifier(&arg.identifier);
+                    self.space(1);
+                    self.equ(&x.equ);
+                    self.space(1);
+                    self.expression(&x.expression);
+                    self.semicolon(&arg.semicolon);
+                }
+                // type parameter is replaced by its width at the reference
+                ConstDeclarationGroup::TypeEquTypeExpression(_) => {
+                    self.token(&arg.r#const.const_token.replace(""));
+                }
+            }
+            return;
+        }
         self.r#const(&arg.r#const);
         self.space(1);
         match &*arg.const_declaration_group {
@@ -1985,6 +3622,11 @@ impl VerylWalker for Emitter {
 
     /// Semantic action for non-terminal 'TypeDefDeclaration'
     fn type_def_declaration(&mut self, arg: &TypeDefDeclaration) {
+        // typedef is replaced by its width at the reference
+        if self.is_verilog() {
+            self.token(&arg.r#type.type_token.replace(""));
+            return;
+        }
         self.token(&arg.r#type.type_token.replace("typedef"));
         self.space(1);
         self.scalar_type(&arg.array_type.scalar_type);
@@ -2001,7 +3643,11 @@ impl VerylWalker for Emitter {
     /// Semantic action for non-terminal 'AlwaysFfDeclaration'
     fn always_ff_declaration(&mut self, arg: &AlwaysFfDeclaration) {
         self.in_always_ff = true;
-        self.always_ff(&arg.always_ff);
+        if self.is_verilog() {
+            self.token(&arg.always_ff.always_ff_token.replace("always"));
+        } else {
+            self.always_ff(&arg.always_ff);
+        }
         self.space(1);
         self.str("@");
         self.space(1);
@@ -2106,7 +3752,11 @@ impl VerylWalker for Emitter {
 
     /// Semantic action for non-terminal 'AlwaysCombDeclaration'
     fn always_comb_declaration(&mut self, arg: &AlwaysCombDeclaration) {
-        self.always_comb(&arg.always_comb);
+        if self.is_verilog() {
+            self.token(&arg.always_comb.always_comb_token.replace("always @*"));
+        } else {
+            self.always_comb(&arg.always_comb);
+        }
         self.space(1);
         self.statement_block(&arg.statement_block);
     }
@@ -2130,7 +3780,10 @@ impl VerylWalker for Emitter {
                 // External symbols may be tri-state
                 true
             };
-        if emit_assign {
+        if self.is_verilog() && self.verilog_assign_array_literal(arg) {
+            return;
+        }
+        if emit_assign || self.is_verilog() {
             self.assign(&arg.assign);
         } else {
             self.token(&arg.assign.assign_token.replace("always_comb"));
@@ -2196,6 +3849,10 @@ impl VerylWalker for Emitter {
 
     /// Semantic action for non-terminal 'EnumDeclaration'
     fn enum_declaration(&mut self, arg: &EnumDeclaration) {
+        if self.is_verilog() {
+            self.verilog_enum_declaration(arg);
+            return;
+        }
         let enum_symbol = symbol_table::resolve(arg.identifier.as_ref()).unwrap();
         if let SymbolKind::Enum(r#enum) = enum_symbol.found.kind {
             self.enum_width = r#enum.width;
@@ -2287,6 +3944,10 @@ impl VerylWalker for Emitter {
 
     /// Semantic action for non-terminal 'StructUnionDeclaration'
     fn struct_union_declaration(&mut self, arg: &StructUnionDeclaration) {
+        if self.is_verilog() {
+            self.verilog_struct_union_declaration(arg);
+            return;
+        }
         let symbol = symbol_table::resolve(arg.identifier.as_ref()).unwrap();
         let maps = symbol.found.generic_maps();
 
@@ -2383,6 +4044,16 @@ impl VerylWalker for Emitter {
 
     /// Semantic action for non-terminal 'InstDeclaration'
     fn inst_declaration(&mut self, arg: &InstDeclaration) {
+        if self.flatten_interface_ports() {
+            if self.verilog_interface_instance(arg) {
+                return;
+            }
+            if let Ok(found) = symbol_table::resolve(arg.scoped_identifier.as_ref()) {
+                if let SymbolKind::Module(ref x) = found.found.kind {
+                    self.verilog_instance_ports.clone_from(&x.ports);
+                }
+            }
+        }
         if arg.inst_declaration_opt1.is_none() {
             self.single_line = true;
         }
@@ -2412,6 +4083,7 @@ impl VerylWalker for Emitter {
         }
         self.semicolon(&arg.semicolon);
         self.single_line = false;
+        self.verilog_instance_ports.clear();
     }
 
     /// Semantic action for non-terminal 'InstParameter'
@@ -2513,6 +4185,9 @@ impl VerylWalker for Emitter {
 
     /// Semantic action for non-terminal 'InstPortItem'
     fn inst_port_item(&mut self, arg: &InstPortItem) {
+        if self.flatten_interface_ports() && self.verilog_inst_port_item(arg) {
+            return;
+        }
         self.str(".");
         self.identifier(&arg.identifier);
         self.space(1);
@@ -2543,6 +4218,39 @@ impl VerylWalker for Emitter {
 
     /// Semantic action for non-terminal 'WithParameter'
     fn with_parameter(&mut self, arg: &WithParameter) {
+        if self.is_verilog() {
+            // type parameter is replaced by its width at the reference
+            let items: Vec<WithParameterItem> = if let Some(ref x) = arg.with_parameter_opt {
+                x.with_parameter_list.as_ref().into()
+            } else {
+                Vec::new()
+            };
+            let items: Vec<_> = items
+                .iter()
+                .filter(|x| {
+                    matches!(
+                        *x.with_parameter_item_group0,
+                        WithParameterItemGroup0::ArrayTypeEquExpression(_)
+                    )
+                })
+                .collect();
+            if !items.is_empty() {
+                self.hash(&arg.hash);
+                self.token_will_push(&arg.l_paren.l_paren_token);
+                for (i, x) in items.iter().enumerate() {
+                    if i == 0 {
+                        self.newline_push();
+                    } else {
+                        self.str(",");
+                        self.newline();
+                    }
+                    self.with_parameter_item(x);
+                }
+                self.newline_pop();
+                self.r_paren(&arg.r_paren);
+            }
+            return;
+        }
         if let Some(ref x) = arg.with_parameter_opt {
             self.hash(&arg.hash);
             self.token_will_push(&arg.l_paren.l_paren_token);
@@ -2590,6 +4298,32 @@ impl VerylWalker for Emitter {
 
     /// Semantic action for non-terminal 'WithParameterItem'
     fn with_parameter_item(&mut self, arg: &WithParameterItem) {
+        if self.is_verilog() {
+            if let WithParameterItemGroup0::ArrayTypeEquExpression(ref x) =
+                *arg.with_parameter_item_group0
+            {
+                // parameter port list of Verilog can't have localparam
+                let token = match &*arg.with_parameter_item_group {
+                    WithParameterItemGroup::Param(x) => &x.param.param_token,
+                    WithParameterItemGroup::Const(x) => &x.r#const.const_token,
+                };
+                self.token(&token.replace("parameter"));
+                self.space(1);
+                if !self.is_implicit_scalar_type(&x.array_type.scalar_type) {
+                    let text = self.verilog_scalar_type(&x.array_type.scalar_type, None);
+                    if !text.is_empty() {
+                        self.str(&text);
+                        self.space(1);
+                    }
+                }
+                self.identifier(&arg.identifier);
+                self.space(1);
+                self.equ(&x.equ);
+                self.space(1);
+                self.expression(&x.expression);
+            }
+            return;
+        }
         match &*arg.with_parameter_item_group {
             WithParameterItemGroup::Param(x) => self.param(&x.param),
             WithParameterItemGroup::Const(x) => self.r#const(&x.r#const),
@@ -2627,6 +4361,27 @@ impl VerylWalker for Emitter {
 
     /// Semantic action for non-terminal 'PortDeclaration'
     fn port_declaration(&mut self, arg: &PortDeclaration) {
+        if self.is_verilog() {
+            if let Some(ref x) = arg.port_declaration_opt {
+                let items: Vec<PortDeclarationItem> = x.port_declaration_list.as_ref().into();
+                self.token_will_push(&arg.l_paren.l_paren_token);
+                for (i, x) in items.iter().enumerate() {
+                    if i == 0 {
+                        self.newline_push();
+                    } else {
+                        self.str(",");
+                        self.newline();
+                    }
+                    self.port_declaration_item(x);
+                }
+                self.newline_pop();
+                self.r_paren(&arg.r_paren);
+            } else {
+                self.l_paren(&arg.l_paren);
+                self.r_paren(&arg.r_paren);
+            }
+            return;
+        }
         if let Some(ref x) = arg.port_declaration_opt {
             self.token_will_push(&arg.l_paren.l_paren_token);
             self.newline_push();
@@ -2672,9 +4427,25 @@ impl VerylWalker for Emitter {
 
     /// Semantic action for non-terminal 'PortDeclarationItem'
     fn port_declaration_item(&mut self, arg: &PortDeclarationItem) {
+        if self.is_verilog() {
+            self.verilog_port_declaration_item(arg);
+            return;
+        }
         match &*arg.port_declaration_item_group {
             PortDeclarationItemGroup::PortTypeConcrete(x) => {
                 let x = x.port_type_concrete.as_ref();
+                if let Direction::Modport(_) = *x.direction {
+                    if self.flatten_interface_ports() {
+                        if let Ok(symbol) = symbol_table::resolve(arg.identifier.as_ref()) {
+                            self.flattened_modport_port(
+                                arg,
+                                &symbol.found,
+                                &x.array_type.scalar_type,
+                            );
+                            return;
+                        }
+                    }
+                }
                 self.direction(&x.direction);
                 if let Direction::Modport(_) = *x.direction {
                     self.in_direction_modport = true;
@@ -2690,15 +4461,8 @@ impl VerylWalker for Emitter {
                 }
                 self.in_direction_modport = false;
             }
-            PortDeclarationItemGroup::PortTypeAbstract(x) => {
-                let x = x.port_type_abstract.as_ref();
-                self.interface(&x.interface);
-                self.space(1);
-                self.identifier(&arg.identifier);
-                if let Some(ref x) = x.port_type_abstract_opt0 {
-                    self.space(1);
-                    self.array(&x.array);
-                }
+            PortDeclarationItemGroup::PortTypeAbstract(_) => {
+                self.interface_port_declaration_item(arg);
             }
         }
     }
@@ -2709,6 +4473,9 @@ impl VerylWalker for Emitter {
             Direction::Input(x) => self.input(&x.input),
             Direction::Output(x) => self.output(&x.output),
             Direction::Inout(x) => self.inout(&x.inout),
+            Direction::Ref(x) if self.is_verilog() => {
+                self.token(&x.r#ref.ref_token.replace("inout"))
+            }
             Direction::Ref(x) => self.r#ref(&x.r#ref),
             Direction::Modport(_) => (),
             Direction::Import(x) => self.import(&x.import),
@@ -2720,6 +4487,60 @@ impl VerylWalker for Emitter {
         let symbol = symbol_table::resolve(arg.identifier.as_ref()).unwrap();
         let maps = symbol.found.generic_maps();
 
+        if self.is_verilog() {
+            let inlined_name = self.verilog_function_name.take();
+            for (i, map) in maps.iter().enumerate() {
+                if i != 0 {
+                    self.newline();
+                }
+                self.generic_map.push(map.clone());
+
+                // function without return value is emitted as task
+                let (begin_kw, end_kw) = if arg.function_declaration_opt1.is_some() {
+                    ("function", "endfunction")
+                } else {
+                    ("task", "endtask")
+                };
+                self.token(&arg.function.function_token.replace(begin_kw));
+                self.space(1);
+                self.str("automatic");
+                self.space(1);
+                if let Some(ref x) = &arg.function_declaration_opt1 {
+                    let text = self.verilog_scalar_type(&x.scalar_type, None);
+                    if !text.is_empty() {
+                        self.str(&text);
+                        self.space(1);
+                    }
+                }
+                let name = if map.generic() {
+                    map.name.clone()
+                } else if let Some(ref x) = inlined_name {
+                    x.clone()
+                } else {
+                    arg.identifier.identifier_token.to_string()
+                };
+                self.token(&arg.identifier.identifier_token.replace(&name));
+                self.verilog_function = Some(name);
+                let port = arg
+                    .function_declaration_opt0
+                    .as_ref()
+                    .map(|x| x.port_declaration.as_ref())
+                    .filter(|x| x.port_declaration_opt.is_some());
+                if let Some(x) = port {
+                    self.port_declaration(x);
+                } else if arg.function_declaration_opt1.is_some() {
+                    // function of Verilog requires at least one input
+                    self.str("(input reg veryl_unused)");
+                }
+                self.str(";");
+                self.verilog_statement_block(&arg.statement_block, "", end_kw);
+                self.verilog_function = None;
+
+                self.generic_map.pop();
+            }
+            return;
+        }
+
         for (i, map) in maps.iter().enumerate() {
             if i != 0 {
                 self.newline();
@@ -2757,6 +4578,11 @@ impl VerylWalker for Emitter {
 
     /// Semantic action for non-terminal 'ImportDeclaration'
     fn import_declaration(&mut self, arg: &ImportDeclaration) {
+        // package items are inlined at the reference
+        if self.is_verilog() {
+            self.token(&arg.import.import_token.replace(""));
+            return;
+        }
         self.in_import = true;
         self.import(&arg.import);
         self.space(1);
@@ -2810,6 +4636,9 @@ impl VerylWalker for Emitter {
                 self.newline();
             }
             self.generic_map.push(map.clone());
+            self.verilog_functions.clear();
+            self.verilog_genvars = vec![vec![]];
+            self.verilog_block_id = 0;
 
             self.module(&arg.module);
             self.space(1);
@@ -2844,11 +4673,25 @@ impl VerylWalker for Emitter {
                 self.port_declaration(&x.port_declaration);
             }
             self.token_will_push(&arg.l_brace.l_brace_token.replace(";"));
+            let members = if self.is_verilog() {
+                self.verilog_package_enum_members(arg)
+            } else {
+                0
+            };
             for (i, x) in arg.module_declaration_list.iter().enumerate() {
-                self.newline_list(i);
+                self.newline_list(members + i);
                 self.module_group(&x.module_group);
             }
-            self.newline_list_post(arg.module_declaration_list.is_empty());
+            // package functions referred in the module are emitted as module functions
+            let functions = if self.is_verilog() {
+                self.verilog_package_functions(members + arg.module_declaration_list.len())
+            } else {
+                0
+            };
+            self.verilog_enum_members.clear();
+            self.newline_list_post(
+                arg.module_declaration_list.is_empty() && members == 0 && functions == 0,
+            );
             self.token(&arg.r_brace.r_brace_token.replace("endmodule"));
 
             self.generic_map.pop();
@@ -2953,6 +4796,9 @@ impl VerylWalker for Emitter {
 
     /// Semantic action for non-terminal 'GenerateIfDeclaration'
     fn generate_if_declaration(&mut self, arg: &GenerateIfDeclaration) {
+        if self.is_verilog() {
+            self.verilog_generate_begin(&arg.r#if.if_token.token);
+        }
         self.r#if(&arg.r#if);
         self.space(1);
         self.str("(");
@@ -2978,15 +4824,27 @@ impl VerylWalker for Emitter {
             self.space(1);
             self.generate_optional_named_block(&x.generate_optional_named_block);
         }
+        if self.is_verilog() {
+            self.verilog_generate_end();
+        }
     }
 
     /// Semantic action for non-terminal 'GenerateForDeclaration'
     fn generate_for_declaration(&mut self, arg: &GenerateForDeclaration) {
+        if self.is_verilog() {
+            self.verilog_generate_begin(&arg.r#for.for_token.token);
+            self.verilog_genvar(
+                &arg.identifier.identifier_token.token,
+                arg.identifier.identifier_token.token.text,
+            );
+        }
         self.r#for(&arg.r#for);
         self.space(1);
         self.str("(");
-        self.str("genvar");
-        self.space(1);
+        if !self.is_verilog() {
+            self.str("genvar");
+            self.space(1);
+        }
         self.identifier(&arg.identifier);
         self.space(1);
         self.str("=");
@@ -3018,6 +4876,11 @@ impl VerylWalker for Emitter {
             self.assignment_operator(&x.assignment_operator);
             self.space(1);
             self.expression(&x.expression);
+        } else if self.is_verilog() {
+            self.identifier(&arg.identifier);
+            self.str(" = ");
+            self.identifier(&arg.identifier);
+            self.str(" + 1");
         } else {
             self.identifier(&arg.identifier);
             self.str("++");
@@ -3025,11 +4888,20 @@ impl VerylWalker for Emitter {
         self.str(")");
         self.space(1);
         self.generate_named_block(&arg.generate_named_block);
+        if self.is_verilog() {
+            self.verilog_generate_end();
+        }
     }
 
     /// Semantic action for non-terminal 'GenerateBlockDeclaration'
     fn generate_block_declaration(&mut self, arg: &GenerateBlockDeclaration) {
+        if self.is_verilog() {
+            self.verilog_generate_begin(&arg.generate_named_block.l_brace.l_brace_token.token);
+        }
         self.emit_generate_named_block(&arg.generate_named_block, "if (1) ");
+        if self.is_verilog() {
+            self.verilog_generate_end();
+        }
     }
 
     /// Semantic action for non-terminal 'GenerateNamedBlock'
@@ -3051,10 +4923,12 @@ impl VerylWalker for Emitter {
             self.str(&name);
         }
         self.token_will_push(&arg.l_brace.l_brace_token.replace(""));
+        self.verilog_genvars.push(Vec::new());
         for (i, x) in arg.generate_optional_named_block_list.iter().enumerate() {
             self.newline_list(i);
             self.generate_group(&x.generate_group);
         }
+        self.verilog_genvars.pop();
         self.newline_list_post(arg.generate_optional_named_block_list.is_empty());
         self.token(&arg.r_brace.r_brace_token.replace("end"));
     }
@@ -3194,6 +5068,9 @@ impl VerylWalker for Emitter {
     fn description_item(&mut self, arg: &DescriptionItem) {
         match arg {
             DescriptionItem::ModuleDeclaration(x) => self.module_declaration(&x.module_declaration),
+            // interface and package are flattened/inlined at Verilog
+            DescriptionItem::InterfaceDeclaration(_) | DescriptionItem::PackageDeclaration(_)
+                if self.is_verilog() => {}
             DescriptionItem::InterfaceDeclaration(x) => {
                 self.interface_declaration(&x.interface_declaration)
             }
@@ -3219,7 +5096,9 @@ impl VerylWalker for Emitter {
         if !arg.start.start_token.comments.is_empty() {
             self.newline();
         }
-        for x in &arg.veryl_list {
+        // file scope import is not required at Verilog because package items are inlined
+        let is_verilog = self.is_verilog();
+        for x in arg.veryl_list.iter().filter(|_| !is_verilog) {
             let items: Vec<DescriptionItem> = x.description_group.as_ref().into();
             for item in items {
                 if let DescriptionItem::ImportDeclaration(x) = item {
@@ -3234,7 +5113,12 @@ impl VerylWalker for Emitter {
                 }
             }
         }
-        for (i, x) in arg.veryl_list.iter().enumerate() {
+        for (i, x) in arg
+            .veryl_list
+            .iter()
+            .filter(|x| !is_verilog || !is_inlined_description(&x.description_group))
+            .enumerate()
+        {
             if i != 0 {
                 self.newline();
             }
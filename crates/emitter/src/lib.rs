@@ -1,5 +1,7 @@
 pub mod aligner;
 pub mod emitter;
 pub use emitter::Emitter;
+pub mod vhdl;
+pub use vhdl::VhdlEmitter;
 #[cfg(test)]
 mod tests;
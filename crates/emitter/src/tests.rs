@@ -1,7 +1,7 @@
-use crate::Emitter;
+use crate::{Emitter, VhdlEmitter};
 use std::path::PathBuf;
-use veryl_analyzer::Analyzer;
-use veryl_metadata::{ClockType, Metadata, ResetType};
+use veryl_analyzer::{Analyzer, AnalyzerError};
+use veryl_metadata::{ClockType, Metadata, ResetType, TargetLanguage};
 use veryl_parser::Parser;
 
 #[track_caller]
@@ -22,6 +22,42 @@ fn emit(metadata: &Metadata, code: &str) -> String {
     emitter.as_str().to_string()
 }
 
+#[track_caller]
+fn emit_verilog(metadata: &Metadata, code: &str) -> String {
+    let parser = Parser::parse(&code, &"").unwrap();
+    let analyzer = Analyzer::new(metadata);
+
+    analyzer.analyze_pass1(&"prj", &code, &"", &parser.veryl);
+    analyzer.analyze_pass2(&"prj", &code, &"", &parser.veryl);
+
+    let mut emitter = Emitter::new(
+        metadata,
+        &PathBuf::from("test.veryl"),
+        &PathBuf::from("test.v"),
+        &PathBuf::from("test.v.map"),
+    );
+    emitter.emit(&"prj", &parser.veryl);
+    emitter.as_str().to_string()
+}
+
+#[track_caller]
+fn emit_vhdl(metadata: &Metadata, code: &str) -> String {
+    let parser = Parser::parse(&code, &"").unwrap();
+    let analyzer = Analyzer::new(metadata);
+
+    analyzer.analyze_pass1(&"prj", &code, &"", &parser.veryl);
+    analyzer.analyze_pass2(&"prj", &code, &"", &parser.veryl);
+
+    let mut emitter = VhdlEmitter::new(
+        metadata,
+        &PathBuf::from("test.veryl"),
+        &PathBuf::from("test.vhd"),
+        &PathBuf::from("test.vhd.map"),
+    );
+    emitter.emit(&"prj", &parser.veryl);
+    emitter.as_str().to_string()
+}
+
 #[test]
 fn prefix_suffix_clock_posedge_reset_high() {
     let code = r#"module ModuleA (
@@ -466,3 +502,899 @@ endmodule
 
     assert_eq!(ret, expect);
 }
+
+#[test]
+fn vhdl_module_package_interface() {
+    let code = r#"package PackageA {
+    const WIDTH: u32 = 8;
+
+    function add (
+        a: input logic<WIDTH>,
+        b: input logic<WIDTH>,
+    ) -> logic<WIDTH> {
+        return a + b;
+    }
+}
+
+interface InterfaceA {
+    var data : logic<8>;
+    var valid: logic   ;
+
+    modport master {
+        data : output,
+        valid: output,
+    }
+}
+
+module ModuleA (
+    i_clk  : input   clock                    ,
+    i_rst  : input   reset                    ,
+    i_a    : input   logic<PackageA::WIDTH>   ,
+    i_b    : input   logic<PackageA::WIDTH>   ,
+    port_a : modport InterfaceA::master       ,
+) {
+    var r: logic<8>;
+
+    always_ff {
+        if_reset {
+            r = 0;
+        } else {
+            r = PackageA::add(i_a, i_b);
+        }
+    }
+
+    assign port_a.data  = r;
+    assign port_a.valid = 1;
+}
+"#;
+
+    let expect = r#"library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+package prj_PackageA is
+    constant WIDTH : natural := 8;
+    function add (a : std_logic_vector(WIDTH - 1 downto 0); b : std_logic_vector(WIDTH - 1 downto 0)) return std_logic_vector;
+end package prj_PackageA;
+
+package body prj_PackageA is
+    function add (a : std_logic_vector(WIDTH - 1 downto 0); b : std_logic_vector(WIDTH - 1 downto 0)) return std_logic_vector is
+    begin
+        return a + b;
+    end function add;
+end package body prj_PackageA;
+
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity prj_ModuleA is
+    port (
+        i_clk : in std_logic;
+        i_rst : in std_logic;
+        i_a : in std_logic_vector(work.prj_PackageA.WIDTH - 1 downto 0);
+        i_b : in std_logic_vector(work.prj_PackageA.WIDTH - 1 downto 0);
+        port_a_data : out std_logic_vector(7 downto 0);
+        port_a_valid : out std_logic
+    );
+end entity prj_ModuleA;
+
+architecture rtl of prj_ModuleA is
+    signal r : std_logic_vector(7 downto 0);
+begin
+
+    process (i_clk, i_rst)
+    begin
+        if i_rst = '0' then
+            r <= (others => '0');
+        elsif rising_edge(i_clk) then
+            r <= work.prj_PackageA.add(i_a, i_b);
+        end if;
+    end process;
+    port_a_data <= r;
+    port_a_valid <= '1';
+end architecture rtl;
+
+--# sourceMappingURL=test.vhd.map
+"#;
+
+    let metadata: Metadata =
+        toml::from_str(&Metadata::create_default_toml("prj").unwrap()).unwrap();
+
+    let ret = if cfg!(windows) {
+        emit_vhdl(&metadata, code).replace("\r\n", "\n")
+    } else {
+        emit_vhdl(&metadata, code)
+    };
+
+    assert_eq!(ret, expect);
+}
+
+#[test]
+fn vhdl_literal_and_select() {
+    let code = r#"module ModuleA (
+    i_d: input  logic<8>,
+    o_a: output logic   ,
+    o_b: output logic   ,
+    o_c: output logic<8>,
+    o_d: output logic   ,
+    o_e: output logic   ,
+    o_f: output logic<8>,
+) {
+    let g: logic = 1;
+
+    assign o_a = 10;
+    assign o_b = 10'b01z;
+    assign o_c = 3 + 4;
+    assign o_d = g[0];
+    assign o_e = i_d[4:1];
+    assign o_f = i_d & 1;
+
+    inst u: ModuleB (
+        i_a: g,
+        o_b: _,
+    );
+}
+
+module ModuleB (
+    i_a: input  logic,
+    o_b: output logic,
+) {
+    assign o_b = i_a;
+}
+"#;
+
+    let expect = r#"library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity prj_ModuleA is
+    port (
+        i_d : in std_logic_vector(7 downto 0);
+        o_a : out std_logic;
+        o_b : out std_logic;
+        o_c : out std_logic_vector(7 downto 0);
+        o_d : out std_logic;
+        o_e : out std_logic;
+        o_f : out std_logic_vector(7 downto 0)
+    );
+end entity prj_ModuleA;
+
+architecture rtl of prj_ModuleA is
+    signal g : std_logic;
+begin
+    g <= '1';
+    o_a <= to_unsigned(10, 32)(0);
+    o_b <= 'Z';
+    o_c <= std_logic_vector(to_unsigned(7, o_c'length));
+    o_d <= g;
+    o_e <= i_d(1);
+    o_f <= i_d and std_logic_vector(to_unsigned(1, o_f'length));
+
+    u: entity work.prj_ModuleB
+        port map (
+            i_a => g,
+            o_b => open
+        );
+end architecture rtl;
+
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity prj_ModuleB is
+    port (
+        i_a : in std_logic;
+        o_b : out std_logic
+    );
+end entity prj_ModuleB;
+
+architecture rtl of prj_ModuleB is
+begin
+    o_b <= i_a;
+end architecture rtl;
+
+--# sourceMappingURL=test.vhd.map
+"#;
+
+    let metadata: Metadata =
+        toml::from_str(&Metadata::create_default_toml("prj").unwrap()).unwrap();
+
+    let ret = if cfg!(windows) {
+        emit_vhdl(&metadata, code).replace("\r\n", "\n")
+    } else {
+        emit_vhdl(&metadata, code)
+    };
+
+    assert_eq!(ret, expect);
+}
+
+#[test]
+fn vhdl_interface_array_and_step() {
+    let code = r#"interface InterfaceA {
+    var data: logic<8>;
+
+    modport master {
+        data: output,
+    }
+}
+
+module ModuleA (
+    o_d: output logic<8>,
+) {
+    inst u: InterfaceA [2];
+    inst v: ModuleB (
+        p: u[1],
+    );
+
+    assign u[0].data = 1;
+    assign o_d       = u[1].data;
+
+    var a: logic<8>;
+
+    always_comb {
+        a = 0;
+        for i: u32 in 0..8 step += 2 {
+            a[i] = 1;
+        }
+    }
+
+    for i in 0..4 step += 2 :g {
+        let b: logic = a[i];
+    }
+
+    initial {
+        a = 0;
+    }
+}
+
+module ModuleB (
+    p: modport InterfaceA::master,
+) {
+    assign p.data = 0;
+}
+"#;
+
+    let expect = r#"library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity prj_ModuleA is
+    port (
+        o_d : out std_logic_vector(7 downto 0)
+    );
+end entity prj_ModuleA;
+
+architecture rtl of prj_ModuleA is
+    type u_data_type is array (0 to 1) of std_logic_vector(7 downto 0);
+    signal u_data : u_data_type;
+    signal a : std_logic_vector(7 downto 0);
+begin
+
+    v: entity work.prj_ModuleB
+        port map (
+            p_data => u_data(1)
+        );
+    u_data(0) <= std_logic_vector(to_unsigned(1, u_data(0)'length));
+    o_d <= u_data(1);
+
+    process (all)
+        variable i : natural;
+    begin
+        a <= (others => '0');
+        i := 0;
+        while i < 8 loop
+            a(i) <= '1';
+            i := i + 2;
+        end loop;
+    end process;
+
+    g: for i_index in 0 to (4 - 1) / 2 generate
+        constant i : integer := 0 + i_index * 2;
+        signal b : std_logic;
+    begin
+        b <= a(i);
+    end generate g;
+
+    process
+    begin
+        a <= (others => '0');
+        wait;
+    end process;
+end architecture rtl;
+
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity prj_ModuleB is
+    port (
+        p_data : out std_logic_vector(7 downto 0)
+    );
+end entity prj_ModuleB;
+
+architecture rtl of prj_ModuleB is
+begin
+    p_data <= (others => '0');
+end architecture rtl;
+
+--# sourceMappingURL=test.vhd.map
+"#;
+
+    let metadata: Metadata =
+        toml::from_str(&Metadata::create_default_toml("prj").unwrap()).unwrap();
+
+    let ret = if cfg!(windows) {
+        emit_vhdl(&metadata, code).replace("\r\n", "\n")
+    } else {
+        emit_vhdl(&metadata, code)
+    };
+
+    assert_eq!(ret, expect);
+}
+
+#[test]
+fn verilog_module_package_interface() {
+    let code = r#"package PackageA {
+    const WIDTH: u32 = 8;
+
+    enum Command: logic<2> {
+        Idle,
+        Add,
+        Sub,
+    }
+
+    function add (
+        a: input logic<WIDTH>,
+        b: input logic<WIDTH>,
+    ) -> logic<WIDTH> {
+        return a + b;
+    }
+}
+
+interface InterfaceA {
+    var data : logic<8>;
+    var valid: logic   ;
+
+    modport master {
+        data : output,
+        valid: output,
+    }
+
+    modport slave {
+        data : input,
+        valid: input,
+    }
+}
+
+module ModuleA (
+    i_clk  : input   clock                    ,
+    i_rst  : input   reset                    ,
+    i_cmd  : input   PackageA::Command        ,
+    i_a    : input   logic<PackageA::WIDTH>   ,
+    i_b    : input   logic<PackageA::WIDTH>   ,
+    port_a : modport InterfaceA::master       ,
+) {
+    struct Pair {
+        hi: logic<4>,
+        lo: logic<4>,
+    }
+
+    var r   : logic<8>;
+    var pair: Pair    ;
+
+    always_ff {
+        if_reset {
+            r = 0;
+        } else if inside i_cmd {PackageA::Command::Add} {
+            r = PackageA::add(i_a, i_b);
+        } else {
+            r += 1;
+        }
+    }
+
+    always_comb {
+        pair    = r;
+        pair.lo = 0;
+    }
+
+    assign port_a.data  = pair.hi;
+    assign port_a.valid = 1;
+}
+
+module ModuleB (
+    i_clk: input clock,
+    i_rst: input reset,
+) {
+    inst bus: InterfaceA;
+
+    inst u: ModuleA (
+        i_clk            ,
+        i_rst            ,
+        i_cmd : 0        ,
+        i_a   : 1        ,
+        i_b   : 2        ,
+        port_a: bus      ,
+    );
+
+    var a: logic<4>;
+
+    for i in 0..4 :g {
+        assign a[i] = bus.data[i];
+    }
+}
+"#;
+
+    let expect = r#"module prj_ModuleA (
+    input wire i_clk,
+    input wire i_rst,
+    input wire [2-1:0] i_cmd,
+    input wire [8-1:0] i_a,
+    input wire [8-1:0] i_b,
+    output wire [8-1:0] port_a_data,
+    output wire port_a_valid
+);
+    localparam [2-1:0] prj_PackageA_Command_Add = 2'd1;
+
+    localparam Pair_lo_WIDTH = 4;
+    localparam Pair_lo_OFFSET = 0;
+    localparam Pair_hi_WIDTH = 4;
+    localparam Pair_hi_OFFSET = Pair_lo_OFFSET + Pair_lo_WIDTH;
+    localparam Pair_WIDTH = Pair_hi_OFFSET + Pair_hi_WIDTH;
+
+    reg [8-1:0] r;
+    reg [Pair_WIDTH-1:0] pair;
+
+    always @ (posedge i_clk, negedge i_rst) begin
+        if (!i_rst) begin
+            r <= 0;
+        end else if (((i_cmd) == (prj_PackageA_Command_Add))) begin
+            r <= prj_PackageA_add(i_a, i_b);
+        end else begin
+            r <= r + (1);
+        end
+    end
+
+    always @* begin
+        pair = r;
+        pair[Pair_lo_OFFSET +: Pair_lo_WIDTH] = 0;
+    end
+
+    assign port_a_data = pair[Pair_hi_OFFSET +: Pair_hi_WIDTH];
+    assign port_a_valid = 1;
+
+    function automatic [8-1:0] prj_PackageA_add(
+        input reg [8-1:0] a,
+        input reg [8-1:0] b
+    );
+        begin
+            prj_PackageA_add = a + b;
+        end
+    endfunction
+endmodule
+
+module prj_ModuleB (
+    input wire i_clk,
+    input wire i_rst
+);
+    wire [8-1:0] bus_data;
+    wire bus_valid;
+
+    prj_ModuleA u (
+        .i_clk (i_clk),
+        .i_rst (i_rst),
+        .i_cmd (0),
+        .i_a (1),
+        .i_b (2),
+        .port_a_data (bus_data),
+        .port_a_valid (bus_valid)
+    );
+
+    wire [4-1:0] a;
+
+    generate
+    genvar i;
+    for (i = 0; i < 4; i = i + 1) begin :g
+        assign a[i] = bus_data[i];
+    end
+    endgenerate
+endmodule
+//# sourceMappingURL=test.v.map
+"#;
+
+    let mut metadata: Metadata =
+        toml::from_str(&Metadata::create_default_toml("prj").unwrap()).unwrap();
+    metadata.build.target_language = TargetLanguage::Verilog;
+
+    let ret = if cfg!(windows) {
+        emit_verilog(&metadata, code).replace("\r\n", "\n")
+    } else {
+        emit_verilog(&metadata, code)
+    };
+
+    assert_eq!(ret, expect);
+}
+
+#[test]
+fn verilog_array_literal_and_function() {
+    let code = r#"module ModuleA #(
+    param N: u32 = 4,
+) (
+    i_clk: input clock,
+    i_rst: input reset,
+) {
+    let _a: logic [2] = '{1, 1};
+    let _b: logic [N] = '{1, default: 0};
+    var _c: logic [N, 2];
+    assign _c = '{'{1, 0}, '{1 repeat 2} repeat N - 1};
+    var _d: logic [N];
+    always_ff {
+        if_reset {
+            _d = '{default: 0};
+        } else {
+            _d = '{0 repeat 2, 1 repeat N - 2};
+        }
+    }
+    let _e: logic<32> = get_one();
+
+    function get_one () -> logic<32> {
+        return 1;
+    }
+}
+"#;
+
+    let expect = r#"module prj_ModuleA #(
+    parameter [32-1:0] N = 4
+) (
+    input wire i_clk,
+    input wire i_rst
+);
+    wire _a [0:2-1];
+    assign _a[0] = 1;
+    assign _a[1] = 1;
+    wire _b [0:N-1];
+    assign _b[0] = 1;
+    generate
+    genvar veryl_i1;
+    for (veryl_i1 = 1; veryl_i1 < N; veryl_i1 = veryl_i1 + 1) begin : veryl_block_1
+        assign _b[veryl_i1] = 0;
+    end
+    endgenerate
+    wire _c [0:N-1][0:2-1];
+    assign _c[0][0] = 1;
+    assign _c[0][1] = 0;
+    generate
+    genvar veryl_i2;
+    for (veryl_i2 = 1; veryl_i2 < (N - 1) + 1; veryl_i2 = veryl_i2 + 1) begin : veryl_block_2
+        assign _c[veryl_i2][0] = 1;
+        assign _c[veryl_i2][1] = 1;
+    end
+    endgenerate
+    reg _d [0:N-1];
+    always @ (posedge i_clk, negedge i_rst) begin
+        if (!i_rst) begin : veryl_block_3
+            integer veryl_i1;
+            for (veryl_i1 = 0; veryl_i1 < N; veryl_i1 = veryl_i1 + 1) begin
+                _d[veryl_i1] <= 0;
+            end
+        end else begin : veryl_block_4
+            integer veryl_i1;
+            _d[0] <= 0;
+            _d[1] <= 0;
+            for (veryl_i1 = 2; veryl_i1 < (N - 2) + 2; veryl_i1 = veryl_i1 + 1) begin
+                _d[veryl_i1] <= 1;
+            end
+        end
+    end
+    wire [32-1:0] _e;
+    assign _e = get_one(1'b0);
+
+    function automatic [32-1:0] get_one(input reg veryl_unused);
+        begin
+            get_one = 1;
+        end
+    endfunction
+endmodule
+//# sourceMappingURL=test.v.map
+"#;
+
+    let mut metadata: Metadata =
+        toml::from_str(&Metadata::create_default_toml("prj").unwrap()).unwrap();
+    metadata.build.target_language = TargetLanguage::Verilog;
+
+    let ret = if cfg!(windows) {
+        emit_verilog(&metadata, code).replace("\r\n", "\n")
+    } else {
+        emit_verilog(&metadata, code)
+    };
+
+    assert_eq!(ret, expect);
+}
+
+#[test]
+fn flatten_interface_ports() {
+    let code = r#"interface InterfaceA #(
+    param WIDTH: u32 = 8,
+) {
+    var data : logic<WIDTH>;
+    var valid: logic       ;
+    var ready: logic       ;
+
+    modport master {
+        data : output,
+        valid: output,
+        ready: input ,
+    }
+
+    modport slave {
+        data : input ,
+        valid: input ,
+        ready: output,
+    }
+}
+
+module ModuleA (
+    port_a: modport InterfaceA::master,
+) {
+    assign port_a.data  = 0;
+    assign port_a.valid = port_a.ready;
+}
+
+module ModuleB (
+    port_b: modport InterfaceA::slave,
+) {
+    assign port_b.ready = port_b.valid;
+}
+
+module ModuleC {
+    inst bus: InterfaceA;
+
+    inst u0: ModuleA (
+        port_a: bus,
+    );
+
+    inst u1: ModuleB (
+        port_b: bus,
+    );
+}
+"#;
+
+    let expect = r#"interface prj_InterfaceA #(
+    parameter int unsigned WIDTH = 8
+);
+    logic [WIDTH-1:0] data ;
+    logic             valid;
+    logic             ready;
+
+    modport master (
+        output data ,
+        output valid,
+        input  ready
+    );
+
+    modport slave (
+        input  data ,
+        input  valid,
+        output ready
+    );
+endinterface
+
+module prj_ModuleA (
+    output logic [8-1:0] port_a_data,
+    output logic port_a_valid,
+    input logic port_a_ready
+);
+    always_comb port_a_data = 0;
+    always_comb port_a_valid = port_a_ready;
+endmodule
+
+module prj_ModuleB (
+    input logic [8-1:0] port_b_data,
+    input logic port_b_valid,
+    output logic port_b_ready
+);
+    always_comb port_b_ready = port_b_valid;
+endmodule
+
+module prj_ModuleC;
+    logic [8-1:0] bus_data;
+    logic bus_valid;
+    logic bus_ready;
+
+    prj_ModuleA u0 (
+        .port_a_data (bus_data),
+        .port_a_valid (bus_valid),
+        .port_a_ready (bus_ready)
+    );
+
+    prj_ModuleB u1 (
+        .port_b_data (bus_data),
+        .port_b_valid (bus_valid),
+        .port_b_ready (bus_ready)
+    );
+endmodule
+//# sourceMappingURL=test.sv.map
+"#;
+
+    let mut metadata: Metadata =
+        toml::from_str(&Metadata::create_default_toml("prj").unwrap()).unwrap();
+    metadata.build.flatten_interface_ports = true;
+
+    let ret = if cfg!(windows) {
+        emit(&metadata, code).replace("\r\n", "\n")
+    } else {
+        emit(&metadata, code)
+    };
+
+    assert_eq!(ret, expect);
+}
+
+#[test]
+fn flatten_interface_ports_function_import() {
+    let code = r#"interface InterfaceA {
+    var a: logic;
+
+    function get_a () -> logic {
+        return a;
+    }
+
+    modport slave {
+        a    : input ,
+        get_a: import,
+    }
+}
+
+module ModuleA (
+    slv: modport InterfaceA::slave,
+) {
+    let _a: logic = slv.get_a();
+}
+"#;
+
+    let mut metadata: Metadata =
+        toml::from_str(&Metadata::create_default_toml("prj").unwrap()).unwrap();
+    metadata.build.flatten_interface_ports = true;
+
+    // functions of interface are not emitted with flattened ports, so the call is rejected
+    let parser = Parser::parse(code, &"").unwrap();
+    let analyzer = Analyzer::new(&metadata);
+    let mut errors = analyzer.analyze_pass1("prj", code, "", &parser.veryl);
+    errors.append(&mut analyzer.analyze_pass2("prj", code, "", &parser.veryl));
+    assert!(matches!(
+        errors.as_slice(),
+        [AnalyzerError::UnsupportedByTarget { .. }]
+    ));
+}
+
+#[test]
+fn vhdl_compound_assignment_and_unsigned_port() {
+    let code = r#"module ModuleA (
+    i_s: input  logic<3> ,
+    o_a: output logic<8> ,
+    o_w: output logic<32>,
+    o_n: output u32      ,
+) {
+    var a: logic<8>;
+    var b: logic<4>;
+
+    always_comb {
+        a =  0;
+        a += 1;
+        a *= 3;
+        a += b;
+        a &= 1;
+        a <<= 2;
+        a >>= i_s;
+        a >>>= 1;
+        for i: u32 in 0..4 {
+            a |= i;
+        }
+    }
+
+    assign b   = 0;
+    assign o_a = a;
+    assign o_n = 1;
+
+    inst u: ModuleB (
+        i_n: a  ,
+        o_n: o_w,
+    );
+}
+
+module ModuleB (
+    i_n: input  u32,
+    o_n: output u32,
+) {
+    assign o_n = i_n;
+}
+"#;
+
+    let expect = r#"library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity prj_ModuleA is
+    port (
+        i_s : in std_logic_vector(2 downto 0);
+        o_a : out std_logic_vector(7 downto 0);
+        o_w : out std_logic_vector(31 downto 0);
+        o_n : out unsigned(31 downto 0)
+    );
+end entity prj_ModuleA;
+
+architecture rtl of prj_ModuleA is
+    signal a : std_logic_vector(7 downto 0);
+    signal b : std_logic_vector(3 downto 0);
+begin
+
+    process (all)
+    begin
+        a <= (others => '0');
+        a <= a + 1;
+        a <= resize(a * 3, a'length);
+        a <= resize(a + b, a'length);
+        a <= a and std_logic_vector(to_unsigned(1, a'length));
+        a <= a sll 2;
+        a <= a srl to_integer(i_s);
+        a <= std_logic_vector(shift_right(signed(a), 1));
+        for i in 0 to 3 loop
+            a <= a or std_logic_vector(to_unsigned(i, a'length));
+        end loop;
+    end process;
+    b <= (others => '0');
+    o_a <= a;
+    o_n <= to_unsigned(1, 32);
+
+    u: entity work.prj_ModuleB
+        port map (
+            i_n => resize(unsigned(a), 32),
+            std_logic_vector(o_n) => o_w
+        );
+end architecture rtl;
+
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity prj_ModuleB is
+    port (
+        i_n : in unsigned(31 downto 0);
+        o_n : out unsigned(31 downto 0)
+    );
+end entity prj_ModuleB;
+
+architecture rtl of prj_ModuleB is
+begin
+    o_n <= i_n;
+end architecture rtl;
+
+--# sourceMappingURL=test.vhd.map
+"#;
+
+    let metadata: Metadata =
+        toml::from_str(&Metadata::create_default_toml("prj").unwrap()).unwrap();
+
+    let ret = if cfg!(windows) {
+        emit_vhdl(&metadata, code).replace("\r\n", "\n")
+    } else {
+        emit_vhdl(&metadata, code)
+    };
+
+    assert_eq!(ret, expect);
+}
@@ -0,0 +1,3362 @@
+use crate::emitter::{single_factor, symbol_string, SymbolContext};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use veryl_analyzer::evaluator::{Evaluated, Evaluator};
+use veryl_analyzer::namespace::Namespace;
+use veryl_analyzer::symbol::TypeModifier as SymTypeModifier;
+use veryl_analyzer::symbol::{
+    Direction as SymDirection, Symbol, SymbolId, SymbolKind, Type as SymType, TypeKind,
+};
+use veryl_analyzer::symbol_path::SymbolPath;
+use veryl_analyzer::symbol_table::{ResolveError, ResolveResult};
+use veryl_analyzer::{msb_table, namespace_table, symbol_table};
+use veryl_metadata::{Build, ClockType, Format, Metadata, ResetType, SourceMapTarget};
+use veryl_parser::resource_table::StrId;
+use veryl_parser::veryl_grammar_trait::*;
+use veryl_parser::veryl_token::VerylToken;
+use veryl_parser::veryl_walker::VerylWalker;
+use veryl_sourcemap::SourceMap;
+
+#[cfg(target_os = "windows")]
+const NEWLINE: &str = "\r\n";
+#[cfg(not(target_os = "windows"))]
+const NEWLINE: &str = "\n";
+
+const RESERVED: [&str; 115] = [
+    "abs",
+    "access",
+    "after",
+    "alias",
+    "all",
+    "and",
+    "architecture",
+    "array",
+    "assert",
+    "assume",
+    "assume_guarantee",
+    "attribute",
+    "begin",
+    "block",
+    "body",
+    "buffer",
+    "bus",
+    "case",
+    "component",
+    "configuration",
+    "constant",
+    "context",
+    "cover",
+    "default",
+    "disconnect",
+    "downto",
+    "else",
+    "elsif",
+    "end",
+    "entity",
+    "exit",
+    "fairness",
+    "file",
+    "for",
+    "force",
+    "function",
+    "generate",
+    "generic",
+    "group",
+    "guarded",
+    "if",
+    "impure",
+    "in",
+    "inertial",
+    "inout",
+    "is",
+    "label",
+    "library",
+    "linkage",
+    "literal",
+    "loop",
+    "map",
+    "mod",
+    "nand",
+    "new",
+    "next",
+    "nor",
+    "not",
+    "null",
+    "of",
+    "on",
+    "open",
+    "or",
+    "others",
+    "out",
+    "package",
+    "parameter",
+    "port",
+    "postponed",
+    "procedure",
+    "process",
+    "property",
+    "protected",
+    "pure",
+    "range",
+    "record",
+    "register",
+    "reject",
+    "release",
+    "rem",
+    "report",
+    "restrict",
+    "restrict_guarantee",
+    "return",
+    "rol",
+    "ror",
+    "select",
+    "sequence",
+    "severity",
+    "shared",
+    "signal",
+    "sla",
+    "sll",
+    "sra",
+    "srl",
+    "strong",
+    "subtype",
+    "then",
+    "to",
+    "transport",
+    "type",
+    "unaffected",
+    "units",
+    "until",
+    "use",
+    "variable",
+    "vmode",
+    "vprop",
+    "vunit",
+    "wait",
+    "when",
+    "while",
+    "with",
+    "xnor",
+    "xor",
+];
+
+thread_local!(static CASE_ESCAPED: RefCell<HashSet<String>> = RefCell::new(HashSet::new()));
+
+/// Collect identifiers differing only in case from another one in the same namespace.
+/// They are escaped because basic identifiers of VHDL are case-insensitive.
+fn case_escaped() -> HashSet<String> {
+    let mut groups: HashMap<String, Vec<(String, Namespace)>> = HashMap::new();
+    for symbol in symbol_table::get_all() {
+        let text = symbol.token.to_string();
+        groups
+            .entry(text.to_lowercase())
+            .or_default()
+            .push((text, symbol.namespace));
+    }
+
+    let mut ret = HashSet::new();
+    for (lower, symbols) in &groups {
+        for (text, namespace) in symbols {
+            if text != lower && symbols.iter().any(|(x, y)| x != text && y == namespace) {
+                ret.insert(text.clone());
+            }
+        }
+    }
+    ret
+}
+
+/// Escape identifiers which are not valid basic identifiers of VHDL
+pub fn vhdl_identifier(x: &str) -> String {
+    let x = x.strip_prefix("r#").unwrap_or(x);
+    let lower = x.to_lowercase();
+    let invalid = x.starts_with('_')
+        || x.ends_with('_')
+        || x.contains("__")
+        || x.starts_with(|c: char| c.is_ascii_digit());
+    let escaped = CASE_ESCAPED.with(|f| f.borrow().contains(x));
+    if invalid || escaped || RESERVED.contains(&lower.as_str()) {
+        format!("\\{}\\", x)
+    } else {
+        x.to_string()
+    }
+}
+
+/// Convert `::` separated path emitted by `symbol_string` to VHDL selected name
+fn vhdl_name(x: &str) -> String {
+    if x.contains("::") {
+        let paths: Vec<_> = x.split("::").map(vhdl_identifier).collect();
+        format!("work.{}", paths.join("."))
+    } else {
+        vhdl_identifier(x)
+    }
+}
+
+fn minus_one(x: &str) -> String {
+    if let Ok(x) = x.parse::<usize>() {
+        format!("{}", x.saturating_sub(1))
+    } else {
+        format!("{x} - 1")
+    }
+}
+
+fn paren(x: (String, bool)) -> String {
+    if x.1 {
+        format!("({})", x.0)
+    } else {
+        x.0
+    }
+}
+
+/// Literal of constant folded value which fits to the assignment target
+fn fixed_literal(value: isize, target: &Target) -> String {
+    match target {
+        Target::Bit if value == 0 || value == 1 => format!("'{value}'"),
+        // truncate explicitly to the LSB like SystemVerilog
+        Target::Bit if value < 0 => format!("to_signed({value}, 32)(0)"),
+        Target::Bit => format!("to_unsigned({value}, 32)(0)"),
+        Target::Vector(_) if value == 0 => "(others => '0')".to_string(),
+        Target::Vector(width) if value < 0 => {
+            format!("std_logic_vector(to_signed({value}, {width}))")
+        }
+        Target::Vector(width) => format!("std_logic_vector(to_unsigned({value}, {width}))"),
+        Target::Unsigned(_) if value == 0 => "(others => '0')".to_string(),
+        Target::Unsigned(width) if value < 0 => format!("unsigned(to_signed({value}, {width}))"),
+        Target::Unsigned(width) => format!("to_unsigned({value}, {width})"),
+        Target::Integer | Target::Other => format!("{value}"),
+    }
+}
+
+/// Conversion of the value of `from` kind to `to` kind, used at assignments and port maps
+fn convert(value: &str, from: &Target, to: &Target) -> String {
+    match (from, to) {
+        (Target::Vector(_) | Target::Unsigned(_), Target::Bit) => format!("{value}(0)"),
+        (Target::Bit, Target::Vector(_) | Target::Unsigned(_)) => {
+            format!("(0 => {value}, others => '0')")
+        }
+        (Target::Integer, Target::Bit) => format!("to_unsigned({value}, 32)(0)"),
+        (Target::Integer, Target::Vector(width)) => {
+            format!("std_logic_vector(to_unsigned({value}, {width}))")
+        }
+        (Target::Integer, Target::Unsigned(width)) => format!("to_unsigned({value}, {width})"),
+        (Target::Unsigned(_), Target::Vector(width)) => {
+            format!("std_logic_vector(resize({value}, {width}))")
+        }
+        (Target::Vector(_), Target::Unsigned(width)) => {
+            format!("resize(unsigned({value}), {width})")
+        }
+        (Target::Bit, Target::Integer) => format!("to_integer(unsigned'(0 => {value}))"),
+        (Target::Vector(_) | Target::Unsigned(_), Target::Integer) => {
+            format!("to_integer({value})")
+        }
+        _ => value.to_string(),
+    }
+}
+
+/// Whether the expression is `_` which means unconnected port
+fn is_anonymous(arg: &Expression) -> bool {
+    if let Some(Factor::ExpressionIdentifierFactorOpt(x)) = single_factor(arg) {
+        let x = &x.expression_identifier;
+        x.expression_identifier_list.is_empty()
+            && x.expression_identifier_list0.is_empty()
+            && x.identifier().to_string() == "_"
+    } else {
+        false
+    }
+}
+
+fn is_test(arg: &DescriptionGroup) -> bool {
+    arg.description_group_list
+        .iter()
+        .any(|x| x.attribute.identifier.identifier_token.to_string() == "test")
+}
+
+/// Whether the expression refers to any identifier, which prevents constant folding
+#[derive(Default)]
+struct IdentifierFinder {
+    found: bool,
+}
+
+impl VerylWalker for IdentifierFinder {
+    fn identifier(&mut self, _arg: &Identifier) {
+        self.found = true;
+    }
+
+    fn msb(&mut self, _arg: &Msb) {
+        self.found = true;
+    }
+}
+
+/// Identifiers referred in the expression, used to decide whether it is integer
+#[derive(Default)]
+struct IdentifierCollector {
+    identifiers: Vec<ExpressionIdentifier>,
+}
+
+impl VerylWalker for IdentifierCollector {
+    fn expression_identifier(&mut self, arg: &ExpressionIdentifier) {
+        self.identifiers.push(arg.clone());
+    }
+}
+
+impl IdentifierCollector {
+    /// Whether all identifiers are integers and any of them is referred
+    fn is_integer(&self) -> bool {
+        !self.identifiers.is_empty()
+            && self.identifiers.iter().all(|x| {
+                symbol_table::resolve(x)
+                    .map(|x| is_integer(&x.found))
+                    .unwrap_or(false)
+            })
+    }
+}
+
+fn symbol_type(symbol: &Symbol) -> Option<SymType> {
+    match &symbol.kind {
+        SymbolKind::Port(x) => x.r#type.clone(),
+        SymbolKind::Variable(x) => Some(x.r#type.clone()),
+        SymbolKind::Parameter(x) => Some(x.r#type.clone()),
+        SymbolKind::StructMember(x) => Some(x.r#type.clone()),
+        _ => None,
+    }
+}
+
+/// Whether the symbol is a single bit, which can't be selected in VHDL
+fn is_scalar(symbol: Result<ResolveResult, ResolveError>) -> bool {
+    let Some(r#type) = symbol.ok().and_then(|x| symbol_type(&x.found)) else {
+        return false;
+    };
+    let bit = !matches!(
+        r#type.kind,
+        TypeKind::U32
+            | TypeKind::U64
+            | TypeKind::I32
+            | TypeKind::I64
+            | TypeKind::F32
+            | TypeKind::F64
+            | TypeKind::Type
+            | TypeKind::String
+            | TypeKind::UserDefined(_)
+    );
+    bit && r#type.array.is_empty() && r#type.width.is_empty()
+}
+
+/// Whether the symbol is emitted as `natural` or `integer`
+fn is_integer(symbol: &Symbol) -> bool {
+    if matches!(symbol.kind, SymbolKind::Genvar) {
+        return true;
+    }
+    let Some(r#type) = symbol_type(symbol) else {
+        return false;
+    };
+    match r#type.kind {
+        // u32/u64 port is emitted as `unsigned` to connect with vectors
+        TypeKind::U32 | TypeKind::U64 => !matches!(symbol.kind, SymbolKind::Port(_)),
+        TypeKind::I32 | TypeKind::I64 => true,
+        _ => false,
+    }
+}
+
+fn signal_name(symbol: &Symbol) -> String {
+    let (prefix, suffix) = match &symbol.kind {
+        SymbolKind::Port(x) => (x.prefix.clone(), x.suffix.clone()),
+        SymbolKind::Variable(x) => (x.prefix.clone(), x.suffix.clone()),
+        _ => (None, None),
+    };
+    let token = VerylToken::new(symbol.token)
+        .strip_prefix("r#")
+        .append(&prefix, &suffix);
+    vhdl_identifier(&token.to_string())
+}
+
+/// Kind of the destination of an assignment, used to convert literals
+#[derive(Clone, Debug)]
+enum Target {
+    Bit,
+    Vector(String),
+    /// `unsigned` port of u32/u64 type
+    Unsigned(String),
+    Integer,
+    Other,
+}
+
+struct ResetInfo {
+    name: String,
+    active: &'static str,
+    asynchronous: bool,
+}
+
+pub struct VhdlEmitter {
+    project_name: Option<StrId>,
+    build_opt: Build,
+    format_opt: Format,
+    string: String,
+    indent: usize,
+    dst_line: u32,
+    dst_column: u32,
+    default_clock: Option<SymbolId>,
+    default_reset: Option<SymbolId>,
+    file_scope_import: Vec<String>,
+    variables: Vec<String>,
+    in_function: bool,
+    boolean: bool,
+    literal_target: Target,
+    source_map: Option<SourceMap>,
+}
+
+impl Default for VhdlEmitter {
+    fn default() -> Self {
+        Self {
+            project_name: None,
+            build_opt: Build::default(),
+            format_opt: Format::default(),
+            string: String::new(),
+            indent: 0,
+            dst_line: 1,
+            dst_column: 1,
+            default_clock: None,
+            default_reset: None,
+            file_scope_import: Vec::new(),
+            variables: Vec::new(),
+            in_function: false,
+            boolean: false,
+            literal_target: Target::Other,
+            source_map: None,
+        }
+    }
+}
+
+impl VhdlEmitter {
+    pub fn new(metadata: &Metadata, src_path: &Path, dst_path: &Path, map_path: &Path) -> Self {
+        let source_map = SourceMap::new(src_path, dst_path, map_path);
+
+        Self {
+            project_name: Some(metadata.project.name.as_str().into()),
+            build_opt: metadata.build.clone(),
+            format_opt: metadata.format.clone(),
+            source_map: Some(source_map),
+            ..Default::default()
+        }
+    }
+
+    pub fn emit(&mut self, project_name: &str, input: &Veryl) {
+        namespace_table::set_default(&[project_name.into()]);
+        CASE_ESCAPED.with(|f| *f.borrow_mut() = case_escaped());
+        self.veryl(input);
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.string
+    }
+
+    pub fn source_map(&mut self) -> &mut SourceMap {
+        self.source_map.as_mut().unwrap()
+    }
+
+    fn str(&mut self, x: &str) {
+        self.string.push_str(x);
+
+        let new_lines = x.matches('\n').count() as u32;
+        self.dst_line += new_lines;
+        if new_lines == 0 {
+            self.dst_column += x.len() as u32;
+        } else {
+            self.dst_column = (x.len() - x.rfind('\n').unwrap_or(0)) as u32;
+        }
+    }
+
+    fn blank(&mut self) {
+        if !self.string.is_empty() && !self.string.ends_with(&format!("{NEWLINE}{NEWLINE}")) {
+            self.str(NEWLINE);
+        }
+    }
+
+    /// Emit a line at the current indent
+    fn line(&mut self, x: &str) {
+        self.str(&" ".repeat(self.indent * self.format_opt.indent_width));
+        self.str(x);
+        self.str(NEWLINE);
+    }
+
+    /// Emit a line and map its beginning to the given source token
+    fn line_with(&mut self, token: &VerylToken, x: &str) {
+        self.str(&" ".repeat(self.indent * self.format_opt.indent_width));
+        let token = &token.token;
+        if token.line != 0 && token.column != 0 {
+            if let Some(ref mut map) = self.source_map {
+                map.add(
+                    self.dst_line,
+                    self.dst_column,
+                    token.line,
+                    token.column,
+                    &token.to_string(),
+                );
+            }
+        }
+        self.str(x);
+        self.str(NEWLINE);
+    }
+
+    /// Emit items separated by `separator` without trailing separator
+    fn list(&mut self, items: Vec<(Option<VerylToken>, String)>, separator: &str) {
+        let len = items.len();
+        for (i, (token, text)) in items.into_iter().enumerate() {
+            let text = if i + 1 == len {
+                text
+            } else if let Some((body, comment)) = text.split_once(" --") {
+                format!("{body}{separator} --{comment}")
+            } else {
+                format!("{text}{separator}")
+            };
+            if let Some(token) = token {
+                self.line_with(&token, &text);
+            } else {
+                self.line(&text);
+            }
+        }
+    }
+
+    fn symbol_context(&self) -> SymbolContext {
+        SymbolContext {
+            project_name: self.project_name,
+            build_opt: self.build_opt.clone(),
+            in_import: false,
+            generic_map: Vec::new(),
+        }
+    }
+
+    fn veryl(&mut self, arg: &Veryl) {
+        for x in &arg.veryl_list {
+            let items: Vec<DescriptionItem> = x.description_group.as_ref().into();
+            for item in items {
+                if let DescriptionItem::ImportDeclaration(x) = item {
+                    let text = self.import_declaration(&x.import_declaration);
+                    self.file_scope_import.push(text);
+                }
+            }
+        }
+        // testbench is not emitted to VHDL
+        for x in arg
+            .veryl_list
+            .iter()
+            .filter(|x| !is_test(&x.description_group))
+        {
+            let items: Vec<DescriptionItem> = x.description_group.as_ref().into();
+            for item in items {
+                self.description_item(&item);
+            }
+        }
+
+        // build map and insert link to map
+        if self.build_opt.sourcemap_target != SourceMapTarget::None {
+            self.blank();
+            self.source_map.as_mut().unwrap().build();
+            let link = self.source_map.as_ref().unwrap().get_vhdl_link();
+            self.line(&link);
+        }
+    }
+
+    fn description_item(&mut self, arg: &DescriptionItem) {
+        match arg {
+            // generic module can't be emitted as entity, and its instance is reported by analyzer
+            DescriptionItem::ModuleDeclaration(x)
+                if x.module_declaration.module_declaration_opt0.is_some() => {}
+            DescriptionItem::ModuleDeclaration(x) => {
+                self.blank();
+                self.module_declaration(&x.module_declaration);
+            }
+            DescriptionItem::PackageDeclaration(x) => {
+                self.blank();
+                self.package_declaration(&x.package_declaration);
+            }
+            // Interfaces are flattened into port lists and signals of their users.
+            // Embed and include are reported as unsupported by analyzer.
+            DescriptionItem::EmbedDeclaration(_)
+            | DescriptionItem::IncludeDeclaration(_)
+            | DescriptionItem::InterfaceDeclaration(_)
+            | DescriptionItem::ProtoModuleDeclaration(_)
+            | DescriptionItem::ImportDeclaration(_) => (),
+        }
+    }
+
+    fn context_clause(&mut self, imports: &[String]) {
+        self.line("library ieee;");
+        self.line("use ieee.std_logic_1164.all;");
+        self.line("use ieee.numeric_std.all;");
+        self.line("use ieee.numeric_std_unsigned.all;");
+        self.line("use ieee.math_real.all;");
+        let mut used = Vec::new();
+        for x in self.file_scope_import.clone().iter().chain(imports) {
+            if !used.contains(x) {
+                self.line(x);
+                used.push(x.clone());
+            }
+        }
+        self.str(NEWLINE);
+    }
+
+    fn import_declaration(&mut self, arg: &ImportDeclaration) -> String {
+        let name = self.scoped_identifier_in_import(&arg.scoped_identifier);
+        if arg.import_declaration_opt.is_some() {
+            format!("use {name}.all;")
+        } else {
+            format!("use {name};")
+        }
+    }
+
+    fn scoped_identifier_in_import(&mut self, arg: &ScopedIdentifier) -> String {
+        if let Ok(symbol) = symbol_table::resolve(arg) {
+            let context = SymbolContext {
+                in_import: true,
+                ..self.symbol_context()
+            };
+            let text = symbol_string(arg.identifier(), &symbol.found, &context);
+            let text = vhdl_name(&text);
+            if text.starts_with("work.") {
+                text
+            } else {
+                format!("work.{text}")
+            }
+        } else {
+            format!("work.{}", arg.identifier())
+        }
+    }
+
+    fn declaration_name(&mut self, arg: &Identifier) -> String {
+        if let Ok(found) = symbol_table::resolve(arg) {
+            if matches!(
+                found.found.kind,
+                SymbolKind::Port(_) | SymbolKind::Variable(_)
+            ) {
+                return signal_name(&found.found);
+            }
+        }
+        vhdl_identifier(&arg.identifier_token.to_string())
+    }
+
+    fn design_unit_name(&mut self, arg: &Identifier) -> String {
+        let symbol = symbol_table::resolve(arg).unwrap();
+        let text = symbol_string(&arg.identifier_token, &symbol.found, &self.symbol_context());
+        vhdl_identifier(&text)
+    }
+
+    // ------------------------------------------------------------------
+    // Types
+    // ------------------------------------------------------------------
+
+    fn width(&mut self, kind: &str, widths: &[String]) -> String {
+        if widths.is_empty() {
+            return kind.to_string();
+        }
+        let width = if widths.len() == 1 {
+            widths[0].clone()
+        } else {
+            let x: Vec<_> = widths.iter().map(|x| format!("({x})")).collect();
+            x.join(" * ")
+        };
+        let kind = if kind == "std_logic" {
+            "std_logic_vector"
+        } else {
+            kind
+        };
+        format!("{kind}({} downto 0)", minus_one(&width))
+    }
+
+    fn scalar_type(&mut self, arg: &ScalarType) -> String {
+        let signed = arg
+            .scalar_type_list
+            .iter()
+            .any(|x| matches!(*x.type_modifier, TypeModifier::Signed(_)));
+        match &*arg.scalar_type_group {
+            ScalarTypeGroup::VariableTypeScalarTypeOpt(x) => {
+                let widths: Vec<String> = if let Some(ref x) = x.scalar_type_opt {
+                    let mut ret = vec![self.expression(&x.width.expression)];
+                    for x in &x.width.width_list {
+                        ret.push(self.expression(&x.expression));
+                    }
+                    ret
+                } else {
+                    Vec::new()
+                };
+                match &*x.variable_type {
+                    VariableType::ScopedIdentifier(x) => {
+                        self.scoped_identifier(&x.scoped_identifier)
+                    }
+                    _ => {
+                        if signed && !widths.is_empty() {
+                            self.width("signed", &widths)
+                        } else {
+                            self.width("std_logic", &widths)
+                        }
+                    }
+                }
+            }
+            ScalarTypeGroup::FixedType(x) => match &*x.fixed_type {
+                FixedType::U32(_) | FixedType::U64(_) => "natural".to_string(),
+                FixedType::I32(_) | FixedType::I64(_) => "integer".to_string(),
+                FixedType::F32(_) | FixedType::F64(_) => "real".to_string(),
+                FixedType::Strin(_) => "string".to_string(),
+            },
+        }
+    }
+
+    fn scalar_type_target(&mut self, arg: &ScalarType) -> Target {
+        match &*arg.scalar_type_group {
+            ScalarTypeGroup::VariableTypeScalarTypeOpt(x) => match &*x.variable_type {
+                VariableType::ScopedIdentifier(_) => Target::Other,
+                _ => {
+                    if let Some(ref x) = x.scalar_type_opt {
+                        let mut widths = vec![self.expression(&x.width.expression)];
+                        for x in &x.width.width_list {
+                            widths.push(format!("({})", self.expression(&x.expression)));
+                        }
+                        Target::Vector(widths.join(" * "))
+                    } else {
+                        Target::Bit
+                    }
+                }
+            },
+            ScalarTypeGroup::FixedType(x) => match &*x.fixed_type {
+                FixedType::U32(_) | FixedType::U64(_) | FixedType::I32(_) | FixedType::I64(_) => {
+                    Target::Integer
+                }
+                _ => Target::Other,
+            },
+        }
+    }
+
+    /// Type of a signal or variable declaration, declaring array types if required
+    fn array_type(&mut self, arg: &ArrayType, name: &str, decls: &mut Vec<String>) -> String {
+        let base = self.scalar_type(&arg.scalar_type);
+        if let Some(ref x) = arg.array_type_opt {
+            self.array_of(&x.array, base, name, decls)
+        } else {
+            base
+        }
+    }
+
+    /// Declare array types of the element for each dimension, and return the outermost one
+    fn array_of(
+        &mut self,
+        arg: &Array,
+        element: String,
+        name: &str,
+        decls: &mut Vec<String>,
+    ) -> String {
+        let mut dims = vec![self.expression(&arg.expression)];
+        for x in &arg.array_list {
+            dims.push(self.expression(&x.expression));
+        }
+        let mut element = element;
+        for (i, dim) in dims.iter().enumerate().rev() {
+            let type_name = if i == 0 {
+                format!("{name}_type")
+            } else {
+                format!("{name}_type{i}")
+            };
+            decls.push(format!(
+                "type {type_name} is array (0 to {}) of {element};",
+                minus_one(dim)
+            ));
+            element = type_name;
+        }
+        element
+    }
+
+    /// Type of symbol table, used for flattened interface members
+    fn symbol_type_string(&mut self, r#type: &SymType, namespace: &Symbol) -> String {
+        match &r#type.kind {
+            TypeKind::U32 | TypeKind::U64 => "natural".to_string(),
+            TypeKind::I32 | TypeKind::I64 => "integer".to_string(),
+            TypeKind::F32 | TypeKind::F64 => "real".to_string(),
+            TypeKind::String => "string".to_string(),
+            TypeKind::UserDefined(x) => {
+                if let Ok(found) = symbol_table::resolve((x, &namespace.namespace)) {
+                    let token = VerylToken::new(found.found.token);
+                    let text = symbol_string(&token, &found.found, &self.symbol_context());
+                    vhdl_name(&text)
+                } else {
+                    "std_logic".to_string()
+                }
+            }
+            _ => {
+                let mut widths = Vec::new();
+                for x in &r#type.width {
+                    let evaluated = Evaluator::new().expression(x);
+                    if let Evaluated::Fixed { value, .. } = evaluated {
+                        widths.push(format!("{value}"));
+                    } else {
+                        widths.push(self.expression(x));
+                    }
+                }
+                if r#type.modifier.contains(&SymTypeModifier::Signed) && !widths.is_empty() {
+                    self.width("signed", &widths)
+                } else {
+                    self.width("std_logic", &widths)
+                }
+            }
+        }
+    }
+
+    // ------------------------------------------------------------------
+    // Expressions
+    // ------------------------------------------------------------------
+
+    fn expression(&mut self, arg: &Expression) -> String {
+        let target = self.take_literal_target(true);
+        let ret = self.expression_inner(arg).0;
+        self.literal_target = target;
+        ret
+    }
+
+    /// Literals in operands of bitwise operators take the type of the assignment target,
+    /// while arithmetic, comparison and shift operators take them as integer.
+    fn take_literal_target(&mut self, integer: bool) -> Target {
+        if integer {
+            std::mem::replace(&mut self.literal_target, Target::Other)
+        } else {
+            self.literal_target.clone()
+        }
+    }
+
+    fn expression_inner(&mut self, arg: &Expression) -> (String, bool) {
+        let mut ret = vec![self.expression01(&arg.expression01)];
+        for x in &arg.expression_list {
+            ret.push(("or".to_string(), false));
+            ret.push(self.expression01(&x.expression01));
+        }
+        Self::binary(ret)
+    }
+
+    fn binary(x: Vec<(String, bool)>) -> (String, bool) {
+        if x.len() == 1 {
+            return x.into_iter().next().unwrap();
+        }
+        let mut ret = String::new();
+        for (i, x) in x.into_iter().enumerate() {
+            if i % 2 == 1 {
+                ret.push(' ');
+                ret.push_str(&x.0);
+                ret.push(' ');
+            } else {
+                ret.push_str(&paren(x));
+            }
+        }
+        (ret, true)
+    }
+
+    fn expression01(&mut self, arg: &Expression01) -> (String, bool) {
+        let mut ret = vec![self.expression02(&arg.expression02)];
+        for x in &arg.expression01_list {
+            ret.push(("and".to_string(), false));
+            ret.push(self.expression02(&x.expression02));
+        }
+        Self::binary(ret)
+    }
+
+    fn expression02(&mut self, arg: &Expression02) -> (String, bool) {
+        let mut ret = vec![self.expression03(&arg.expression03)];
+        for x in &arg.expression02_list {
+            ret.push(("or".to_string(), false));
+            ret.push(self.expression03(&x.expression03));
+        }
+        Self::binary(ret)
+    }
+
+    fn expression03(&mut self, arg: &Expression03) -> (String, bool) {
+        let mut ret = vec![self.expression04(&arg.expression04)];
+        for x in &arg.expression03_list {
+            let op = match x.operator04.operator04_token.to_string().as_str() {
+                "^" => "xor",
+                _ => "xnor",
+            };
+            ret.push((op.to_string(), false));
+            ret.push(self.expression04(&x.expression04));
+        }
+        Self::binary(ret)
+    }
+
+    fn expression04(&mut self, arg: &Expression04) -> (String, bool) {
+        let mut ret = vec![self.expression05(&arg.expression05)];
+        for x in &arg.expression04_list {
+            ret.push(("and".to_string(), false));
+            ret.push(self.expression05(&x.expression05));
+        }
+        Self::binary(ret)
+    }
+
+    fn expression05(&mut self, arg: &Expression05) -> (String, bool) {
+        let target = self.take_literal_target(!arg.expression05_list.is_empty());
+        // matching operators are not defined for integers
+        let boolean = self.boolean;
+        if !arg.expression05_list.is_empty() {
+            let mut collector = IdentifierCollector::default();
+            collector.expression05(arg);
+            self.boolean |= collector.is_integer();
+        }
+        let mut ret = vec![self.expression06(&arg.expression06)];
+        for x in &arg.expression05_list {
+            let equal = matches!(
+                x.operator06.operator06_token.to_string().as_str(),
+                "==" | "==?" | "==="
+            );
+            let op = match (equal, self.boolean) {
+                (true, true) => "=",
+                (false, true) => "/=",
+                (true, false) => "?=",
+                (false, false) => "?/=",
+            };
+            ret.push((op.to_string(), false));
+            ret.push(self.expression06(&x.expression06));
+        }
+        let ret = Self::binary(ret);
+        self.literal_target = target;
+        self.boolean = boolean;
+        ret
+    }
+
+    fn expression06(&mut self, arg: &Expression06) -> (String, bool) {
+        let target = self.take_literal_target(!arg.expression06_list.is_empty());
+        let boolean = self.boolean;
+        if !arg.expression06_list.is_empty() {
+            let mut collector = IdentifierCollector::default();
+            collector.expression06(arg);
+            self.boolean |= collector.is_integer();
+        }
+        let mut ret = vec![self.expression07(&arg.expression07)];
+        for x in &arg.expression06_list {
+            let op = match x.operator07.operator07_token.to_string().as_str() {
+                "<:" => "<",
+                ">:" => ">",
+                "<=" => "<=",
+                _ => ">=",
+            };
+            let op = if self.boolean {
+                op.to_string()
+            } else {
+                format!("?{op}")
+            };
+            ret.push((op, false));
+            ret.push(self.expression07(&x.expression07));
+        }
+        let ret = Self::binary(ret);
+        self.literal_target = target;
+        self.boolean = boolean;
+        ret
+    }
+
+    fn expression07(&mut self, arg: &Expression07) -> (String, bool) {
+        let mut ret = self.expression08(&arg.expression08);
+        for x in &arg.expression07_list {
+            let target = self.take_literal_target(true);
+            let rhs = self.expression08(&x.expression08);
+            self.literal_target = target;
+            // shift count is converted to integer only if it is a vector
+            let mut collector = IdentifierCollector::default();
+            collector.expression08(&x.expression08);
+            let rhs = if collector.identifiers.is_empty() || collector.is_integer() {
+                rhs.0
+            } else {
+                format!("to_integer({})", rhs.0)
+            };
+            ret = match x.operator08.operator08_token.to_string().as_str() {
+                "<<" | "<<<" => (format!("{} sll {rhs}", paren(ret)), true),
+                ">>" => (format!("{} srl {rhs}", paren(ret)), true),
+                _ => (
+                    format!("std_logic_vector(shift_right(signed({}), {rhs}))", ret.0),
+                    false,
+                ),
+            };
+        }
+        ret
+    }
+
+    fn expression08(&mut self, arg: &Expression08) -> (String, bool) {
+        let target = self.take_literal_target(!arg.expression08_list.is_empty());
+        let mut ret = vec![self.expression09(&arg.expression09)];
+        for x in &arg.expression08_list {
+            ret.push((x.operator09.operator09_token.to_string(), false));
+            ret.push(self.expression09(&x.expression09));
+        }
+        let ret = Self::binary(ret);
+        self.literal_target = target;
+        ret
+    }
+
+    fn expression09(&mut self, arg: &Expression09) -> (String, bool) {
+        let target = self.take_literal_target(!arg.expression09_list.is_empty());
+        let mut ret = vec![self.expression10(&arg.expression10)];
+        for x in &arg.expression09_list {
+            let op = match &*x.expression09_list_group {
+                Expression09ListGroup::Operator10(x) => {
+                    match x.operator10.operator10_token.to_string().as_str() {
+                        "%" => "mod",
+                        _ => "/",
+                    }
+                }
+                Expression09ListGroup::Star(_) => "*",
+            };
+            ret.push((op.to_string(), false));
+            ret.push(self.expression10(&x.expression10));
+        }
+        let ret = Self::binary(ret);
+        self.literal_target = target;
+        ret
+    }
+
+    fn expression10(&mut self, arg: &Expression10) -> (String, bool) {
+        let target = self.take_literal_target(!arg.expression10_list.is_empty());
+        let mut ret = vec![self.expression11(&arg.expression11)];
+        for x in &arg.expression10_list {
+            ret.push(("**".to_string(), false));
+            ret.push(self.expression11(&x.expression11));
+        }
+        let ret = Self::binary(ret);
+        self.literal_target = target;
+        ret
+    }
+
+    fn expression11(&mut self, arg: &Expression11) -> (String, bool) {
+        let ret = self.expression12(&arg.expression12);
+        if let Some(ref x) = arg.expression11_opt {
+            match &*x.casting_type {
+                CastingType::U32(_)
+                | CastingType::U64(_)
+                | CastingType::I32(_)
+                | CastingType::I64(_) => (format!("to_integer({})", ret.0), false),
+                CastingType::ScopedIdentifier(x) => {
+                    let name = self.scoped_identifier(&x.scoped_identifier);
+                    (format!("{name}({})", ret.0), false)
+                }
+                _ => ret,
+            }
+        } else {
+            ret
+        }
+    }
+
+    fn expression12(&mut self, arg: &Expression12) -> (String, bool) {
+        // The operand of reduction operators is unsized literal of 32bit like SystemVerilog
+        let target = match arg
+            .expression12_list
+            .last()
+            .map(|x| &*x.expression12_list_group)
+        {
+            None => self.literal_target.clone(),
+            Some(Expression12ListGroup::UnaryOperator(x))
+                if matches!(
+                    x.unary_operator.unary_operator_token.to_string().as_str(),
+                    "!" | "~"
+                ) =>
+            {
+                self.literal_target.clone()
+            }
+            Some(Expression12ListGroup::Operator09(_)) => Target::Other,
+            Some(_) => Target::Vector("32".to_string()),
+        };
+        let target = std::mem::replace(&mut self.literal_target, target);
+        let mut ret = self.factor(&arg.factor);
+        self.literal_target = target;
+        for x in arg.expression12_list.iter().rev() {
+            let op = match &*x.expression12_list_group {
+                Expression12ListGroup::UnaryOperator(x) => {
+                    match x.unary_operator.unary_operator_token.to_string().as_str() {
+                        "~&" => "nand ",
+                        "~|" => "nor ",
+                        _ => "not ",
+                    }
+                }
+                Expression12ListGroup::Operator09(x) => {
+                    if x.operator09.operator09_token.to_string() == "-" {
+                        "-"
+                    } else {
+                        "+"
+                    }
+                }
+                Expression12ListGroup::Operator05(_) => "and ",
+                Expression12ListGroup::Operator03(_) => "or ",
+                Expression12ListGroup::Operator04(x) => {
+                    match x.operator04.operator04_token.to_string().as_str() {
+                        "^" => "xor ",
+                        _ => "xnor ",
+                    }
+                }
+            };
+            ret = (format!("{op}{}", paren(ret)), true);
+        }
+        ret
+    }
+
+    fn number(&mut self, arg: &Number, target: &Target) -> String {
+        match arg {
+            Number::IntegralNumber(x) => match &*x.integral_number {
+                IntegralNumber::Based(x) => self.based(&x.based.based_token, target),
+                IntegralNumber::BaseLess(x) => {
+                    let text = x.base_less.base_less_token.to_string().replace('_', "");
+                    match target {
+                        Target::Bit => match text.parse::<isize>() {
+                            Ok(x) => fixed_literal(x, target),
+                            Err(_) => text,
+                        },
+                        Target::Vector(_) if text == "0" => "(others => '0')".to_string(),
+                        Target::Vector(width) => {
+                            format!("std_logic_vector(to_unsigned({text}, {width}))")
+                        }
+                        Target::Unsigned(_) if text == "0" => "(others => '0')".to_string(),
+                        Target::Unsigned(width) => format!("to_unsigned({text}, {width})"),
+                        Target::Integer | Target::Other => text,
+                    }
+                }
+                IntegralNumber::AllBit(x) => {
+                    let text = x.all_bit.all_bit_token.to_string();
+                    let (width, tail) = text.split_once('\'').unwrap();
+                    let bit = tail.to_uppercase();
+                    if width.is_empty() {
+                        match target {
+                            Target::Bit => format!("'{bit}'"),
+                            _ => format!("(others => '{bit}')"),
+                        }
+                    } else {
+                        let width: usize = width.parse().unwrap();
+                        if width == 1 {
+                            format!("'{bit}'")
+                        } else {
+                            format!("\"{}\"", bit.repeat(width))
+                        }
+                    }
+                }
+            },
+            Number::RealNumber(x) => match &*x.real_number {
+                RealNumber::FixedPoint(x) => x.fixed_point.fixed_point_token.to_string(),
+                RealNumber::Exponent(x) => x.exponent.exponent_token.to_string(),
+            },
+        }
+    }
+
+    fn based(&mut self, token: &VerylToken, target: &Target) -> String {
+        let text = token.to_string().replace('_', "");
+        let (width, tail) = text.split_once('\'').unwrap();
+        let (signed, tail) = if let Some(x) = tail.strip_prefix('s') {
+            (true, x)
+        } else {
+            (false, tail)
+        };
+        let base = &tail[0..1];
+        let number = &tail[1..];
+        let radix = match base {
+            "b" => 2,
+            "o" => 8,
+            "d" => 10,
+            _ => 16,
+        };
+        let width = if width.is_empty() {
+            strnum_bitwidth::bitwidth(number, radix).unwrap_or(32)
+        } else {
+            width.parse().unwrap_or(32)
+        };
+        if width == 1 || matches!(target, Target::Bit) {
+            // LSB is decided by the last digit except decimal
+            let lsb = if radix == 10 {
+                number.parse::<u128>().ok().map(|x| x & 1)
+            } else {
+                number
+                    .chars()
+                    .last()
+                    .and_then(|x| x.to_digit(radix))
+                    .map(|x| x as u128 & 1)
+            };
+            match lsb {
+                Some(x) => return format!("'{x}'"),
+                None => {
+                    let bit = number.chars().last().unwrap_or('x').to_ascii_uppercase();
+                    return format!("'{bit}'");
+                }
+            }
+        }
+        let sign = if signed { "s" } else { "" };
+        format!("{width}{sign}{base}\"{number}\"")
+    }
+
+    fn factor(&mut self, arg: &Factor) -> (String, bool) {
+        let target = self.literal_target.clone();
+        self.factor_with_target(arg, &target)
+    }
+
+    fn factor_with_target(&mut self, arg: &Factor, target: &Target) -> (String, bool) {
+        match arg {
+            Factor::Number(x) => (self.number(&x.number, target), false),
+            Factor::ExpressionIdentifierFactorOpt(x) => {
+                let identifier = &x.expression_identifier;
+                if let Some(ref call) = x.factor_opt {
+                    let args = self.function_call(&call.function_call);
+                    (self.call(identifier, args), false)
+                } else {
+                    (self.expression_identifier(identifier), false)
+                }
+            }
+            Factor::LParenExpressionRParen(x) => (
+                format!("({})", self.expression_inner(&x.expression).0),
+                false,
+            ),
+            Factor::LBraceConcatenationListRBrace(x) => {
+                let mut items =
+                    vec![self.concatenation_item(&x.concatenation_list.concatenation_item)];
+                for x in &x.concatenation_list.concatenation_list_list {
+                    items.push(self.concatenation_item(&x.concatenation_item));
+                }
+                if items.len() == 1 {
+                    (items.pop().unwrap(), false)
+                } else {
+                    (format!("({})", items.join(" & ")), false)
+                }
+            }
+            Factor::QuoteLBraceArrayLiteralListRBrace(x) => {
+                let list = &x.array_literal_list;
+                let mut items = vec![self.array_literal_item(&list.array_literal_item)];
+                for x in &list.array_literal_list_list {
+                    items.push(self.array_literal_item(&x.array_literal_item));
+                }
+                (format!("({})", items.join(", ")), false)
+            }
+            Factor::IfExpression(x) => {
+                (format!("({})", self.if_expression(&x.if_expression)), false)
+            }
+            Factor::CaseExpression(x) => (
+                format!("({})", self.case_expression(&x.case_expression)),
+                false,
+            ),
+            Factor::SwitchExpression(x) => (
+                format!("({})", self.switch_expression(&x.switch_expression)),
+                false,
+            ),
+            Factor::StringLiteral(x) => (x.string_literal.string_literal_token.to_string(), false),
+            Factor::FactorGroup(x) => match &*x.factor_group {
+                FactorGroup::Msb(x) => {
+                    if let Some(width) = msb_table::get(x.msb.msb_token.token.id) {
+                        let width = self.expression(&width);
+                        (minus_one(&width), true)
+                    } else {
+                        ("0".to_string(), false)
+                    }
+                }
+                FactorGroup::Lsb(_) => ("0".to_string(), false),
+            },
+            Factor::InsideExpression(x) => {
+                let x = &x.inside_expression;
+                (self.inside(&x.expression, &x.range_list), true)
+            }
+            Factor::OutsideExpression(x) => {
+                let x = &x.outside_expression;
+                let inside = self.inside(&x.expression, &x.range_list);
+                (format!("not ({inside})"), true)
+            }
+        }
+    }
+
+    fn concatenation_item(&mut self, arg: &ConcatenationItem) -> String {
+        let item = paren(self.expression_inner(&arg.expression));
+        if let Some(ref x) = arg.concatenation_item_opt {
+            let repeat = self.expression(&x.expression);
+            if let Ok(repeat) = repeat.parse::<usize>() {
+                vec![item; repeat].join(" & ")
+            } else {
+                format!("(1 to {repeat} => {item})")
+            }
+        } else {
+            item
+        }
+    }
+
+    fn array_literal_item(&mut self, arg: &ArrayLiteralItem) -> String {
+        match &*arg.array_literal_item_group {
+            ArrayLiteralItemGroup::ExpressionArrayLiteralItemOpt(x) => {
+                let item = self.expression(&x.expression);
+                if let Some(ref y) = x.array_literal_item_opt {
+                    let repeat = self.expression(&y.expression);
+                    if let Ok(repeat) = repeat.parse::<usize>() {
+                        return vec![item; repeat].join(", ");
+                    }
+                }
+                item
+            }
+            ArrayLiteralItemGroup::DefaulColonExpression(x) => {
+                format!("others => {}", self.expression(&x.expression))
+            }
+        }
+    }
+
+    fn function_call(&mut self, arg: &FunctionCall) -> Vec<String> {
+        let mut ret = Vec::new();
+        if let Some(ref x) = arg.function_call_opt {
+            let list = &x.argument_list;
+            ret.push(self.expression(&list.argument_item.expression));
+            for x in &list.argument_list_list {
+                ret.push(self.expression(&x.argument_item.expression));
+            }
+        }
+        ret
+    }
+
+    fn call(&mut self, identifier: &ExpressionIdentifier, args: Vec<String>) -> String {
+        let name = identifier.identifier().to_string();
+        let arg0 = args.first().cloned().unwrap_or_default();
+        match name.as_str() {
+            "$clog2" => format!("integer(ceil(log2(real({arg0}))))"),
+            "$signed" => format!("signed({arg0})"),
+            "$unsigned" => format!("unsigned({arg0})"),
+            "$bits" | "$size" => format!("{arg0}'length"),
+            // genvar only in generate condition, checked by analyzer
+            "$onehot" => {
+                format!("({arg0} > 0 and 2 ** integer(floor(log2(real({arg0})))) = {arg0})")
+            }
+            _ => {
+                let name = if let Some(x) = name.strip_prefix('$') {
+                    x.to_string()
+                } else {
+                    self.expression_identifier(identifier)
+                };
+                if args.is_empty() {
+                    name
+                } else {
+                    format!("{name}({})", args.join(", "))
+                }
+            }
+        }
+    }
+
+    fn range_condition(&mut self, target: &str, arg: &Range) -> String {
+        let (eq, ge, lt, le) = if self.boolean {
+            ("=", ">=", "<", "<=")
+        } else {
+            ("?=", "?>=", "?<", "?<=")
+        };
+        let beg = paren(self.expression_inner(&arg.expression));
+        if let Some(ref x) = arg.range_opt {
+            let end = paren(self.expression_inner(&x.expression));
+            let op = match &*x.range_operator {
+                RangeOperator::DotDot(_) => lt,
+                RangeOperator::DotDotEqu(_) => le,
+            };
+            format!("({target} {ge} {beg} and {target} {op} {end})")
+        } else {
+            format!("({target} {eq} {beg})")
+        }
+    }
+
+    fn inside(&mut self, expression: &Expression, arg: &RangeList) -> String {
+        let target = paren(self.expression_inner(expression));
+        let mut conds = vec![self.range_condition(&target, &arg.range_item.range)];
+        for x in &arg.range_list_list {
+            conds.push(self.range_condition(&target, &x.range_item.range));
+        }
+        conds.join(" or ")
+    }
+
+    fn case_condition(&mut self, target: &str, arg: &CaseCondition) -> String {
+        let mut conds = vec![self.range_condition(target, &arg.range_item.range)];
+        for x in &arg.case_condition_list {
+            conds.push(self.range_condition(target, &x.range_item.range));
+        }
+        conds.join(" or ")
+    }
+
+    fn switch_condition(&mut self, arg: &SwitchCondition) -> String {
+        let mut conds = vec![paren(self.expression_inner(&arg.expression))];
+        for x in &arg.switch_condition_list {
+            conds.push(paren(self.expression_inner(&x.expression)));
+        }
+        conds.join(" or ")
+    }
+
+    fn if_expression(&mut self, arg: &IfExpression) -> String {
+        let mut ret = format!(
+            "{} when {}",
+            self.expression(&arg.expression0),
+            self.expression(&arg.expression)
+        );
+        for x in &arg.if_expression_list {
+            ret.push_str(&format!(
+                " else {} when {}",
+                self.expression(&x.expression0),
+                self.expression(&x.expression)
+            ));
+        }
+        ret.push_str(&format!(" else {}", self.expression(&arg.expression1)));
+        ret
+    }
+
+    fn case_expression(&mut self, arg: &CaseExpression) -> String {
+        let target = paren(self.expression_inner(&arg.expression));
+        let cond = self.case_condition(&target, &arg.case_condition);
+        let mut ret = format!("{} when {cond}", self.expression(&arg.expression0));
+        for x in &arg.case_expression_list {
+            let cond = self.case_condition(&target, &x.case_condition);
+            ret.push_str(&format!(
+                " else {} when {cond}",
+                self.expression(&x.expression)
+            ));
+        }
+        ret.push_str(&format!(" else {}", self.expression(&arg.expression1)));
+        ret
+    }
+
+    fn switch_expression(&mut self, arg: &SwitchExpression) -> String {
+        let cond = self.switch_condition(&arg.switch_condition);
+        let mut ret = format!("{} when {cond}", self.expression(&arg.expression));
+        for x in &arg.switch_expression_list {
+            let cond = self.switch_condition(&x.switch_condition);
+            ret.push_str(&format!(
+                " else {} when {cond}",
+                self.expression(&x.expression)
+            ));
+        }
+        ret.push_str(&format!(" else {}", self.expression(&arg.expression0)));
+        ret
+    }
+
+    /// Expression at the right-hand side of an assignment to `target`
+    fn assigned_expression(&mut self, arg: &Expression, target: &Target) -> String {
+        if let Some(factor) = single_factor(arg) {
+            match factor {
+                Factor::Number(_) => return self.factor_with_target(factor, target).0,
+                Factor::IfExpression(x) => return self.if_expression(&x.if_expression),
+                Factor::CaseExpression(x) => return self.case_expression(&x.case_expression),
+                Factor::SwitchExpression(x) => return self.switch_expression(&x.switch_expression),
+                // Vector is truncated to bit, and bit is extended to vector implicitly in SystemVerilog
+                Factor::ExpressionIdentifierFactorOpt(x) if x.factor_opt.is_none() => {
+                    let identifier = &x.expression_identifier;
+                    let name = self.expression_identifier(identifier);
+                    let from = self.expression_identifier_target(identifier, &name);
+                    if matches!(
+                        (&from, target),
+                        (Target::Vector(_) | Target::Unsigned(_), Target::Bit)
+                    ) {
+                        return self.lsb_of(identifier, &name);
+                    }
+                    return convert(&name, &from, target);
+                }
+                _ => (),
+            }
+        }
+        if matches!(target, Target::Integer | Target::Other) {
+            return self.expression(arg);
+        }
+
+        let mut finder = IdentifierFinder::default();
+        finder.expression(arg);
+        if !finder.found {
+            if let Evaluated::Fixed { value, .. } = Evaluator::new().expression(arg) {
+                return fixed_literal(value, target);
+            }
+        }
+
+        // arithmetic of integers like loop variables is converted as a whole
+        let mut collector = IdentifierCollector::default();
+        collector.expression(arg);
+        if collector.is_integer() {
+            let value = self.expression(arg);
+            return convert(&value, &Target::Integer, target);
+        }
+
+        self.literal_target = target.clone();
+        let ret = self.expression_inner(arg).0;
+        self.literal_target = Target::Other;
+        ret
+    }
+
+    fn scoped_identifier(&mut self, arg: &ScopedIdentifier) -> String {
+        if let Ok(symbol) = symbol_table::resolve(arg) {
+            let text = symbol_string(arg.identifier(), &symbol.found, &self.symbol_context());
+            if !text.is_empty() {
+                return vhdl_name(&text);
+            }
+        }
+        vhdl_identifier(&arg.identifier().to_string())
+    }
+
+    fn select(&mut self, arg: &Select) -> String {
+        let beg = self.index(&arg.expression);
+        if let Some(ref x) = arg.select_opt {
+            let end = self.index(&x.expression);
+            match &*x.select_operator {
+                SelectOperator::Colon(_) => format!("({beg} downto {end})"),
+                SelectOperator::PlusColon(_) => {
+                    format!("({beg} + {} downto {beg})", minus_one(&end))
+                }
+                SelectOperator::MinusColon(_) => {
+                    format!("({beg} downto {beg} - {})", minus_one(&end))
+                }
+                SelectOperator::Step(_) => {
+                    format!("(({beg} + 1) * {end} - 1 downto {beg} * {end})")
+                }
+            }
+        } else {
+            format!("({beg})")
+        }
+    }
+
+    /// The least significant bit of the vector refered by the identifier
+    fn lsb_of(&mut self, arg: &ExpressionIdentifier, name: &str) -> String {
+        let select = if let Some(x) = arg.expression_identifier_list0.last() {
+            x.expression_identifier_list0_list.last().map(|x| &x.select)
+        } else {
+            arg.expression_identifier_list.last().map(|x| &x.select)
+        };
+        let Some(select) = select else {
+            return format!("{name}(0)");
+        };
+        let Some(ref x) = select.select_opt else {
+            return format!("{name}(0)");
+        };
+        let text = self.select(select);
+        let prefix = name.strip_suffix(&text).unwrap_or(name);
+        let beg = self.index(&select.expression);
+        let end = self.index(&x.expression);
+        let lsb = match &*x.select_operator {
+            SelectOperator::Colon(_) => end,
+            SelectOperator::PlusColon(_) => beg,
+            SelectOperator::MinusColon(_) => format!("{beg} - {}", minus_one(&end)),
+            SelectOperator::Step(_) => format!("{beg} * {end}"),
+        };
+        format!("{prefix}({lsb})")
+    }
+
+    /// Index expression which is converted to integer if it is a vector signal
+    fn index(&mut self, arg: &Expression) -> String {
+        let ret = self.expression(arg);
+        if let Some(Factor::ExpressionIdentifierFactorOpt(x)) = single_factor(arg) {
+            if x.factor_opt.is_none() {
+                if let Ok(found) = symbol_table::resolve(x.expression_identifier.as_ref()) {
+                    if let Some(r#type) = symbol_type(&found.found) {
+                        if matches!(r#type.kind, TypeKind::Logic | TypeKind::Bit) {
+                            return format!("to_integer({ret})");
+                        }
+                    }
+                }
+            }
+        }
+        ret
+    }
+
+    /// Whether the symbol is flattened interface (modport port or interface instance)
+    fn is_interface(&mut self, symbol: &Symbol) -> bool {
+        match &symbol.kind {
+            SymbolKind::Port(x) => matches!(x.direction, SymDirection::Modport),
+            SymbolKind::Instance(x) => {
+                if let Ok(found) = symbol_table::resolve((&x.type_name, &symbol.namespace)) {
+                    matches!(found.found.kind, SymbolKind::Interface(_))
+                } else {
+                    false
+                }
+            }
+            _ => false,
+        }
+    }
+
+    fn member_separator(&mut self, arg: &ScopedIdentifier) -> &'static str {
+        if let Ok(found) = symbol_table::resolve(arg) {
+            if self.is_interface(&found.found) {
+                return "_";
+            }
+        }
+        "."
+    }
+
+    fn expression_identifier(&mut self, arg: &ExpressionIdentifier) -> String {
+        let mut ret = self.scoped_identifier(&arg.scoped_identifier);
+        let mut selects = String::new();
+        if !is_scalar(symbol_table::resolve(arg.scoped_identifier.as_ref())) {
+            for x in &arg.expression_identifier_list {
+                selects.push_str(&self.select(&x.select));
+            }
+        }
+        if arg.expression_identifier_list0.is_empty() {
+            ret.push_str(&selects);
+        } else {
+            let separator = self.member_separator(&arg.scoped_identifier);
+            let scalar = is_scalar(symbol_table::resolve(arg));
+            for (i, x) in arg.expression_identifier_list0.iter().enumerate() {
+                // element of interface instance array selects the flattened member
+                if i == 0 && separator == "_" {
+                    ret.push_str(separator);
+                    ret.push_str(&x.identifier.identifier_token.to_string());
+                    ret.push_str(&selects);
+                } else {
+                    if i == 0 {
+                        ret.push_str(&selects);
+                    }
+                    ret.push_str(separator);
+                    ret.push_str(&x.identifier.identifier_token.to_string());
+                }
+                if !scalar {
+                    for x in &x.expression_identifier_list0_list {
+                        ret.push_str(&self.select(&x.select));
+                    }
+                }
+            }
+        }
+        ret
+    }
+
+    fn hierarchical_identifier(&mut self, arg: &HierarchicalIdentifier) -> String {
+        let mut ret = if let Ok(found) = symbol_table::resolve(arg.identifier.as_ref()) {
+            let name = signal_name(&found.found);
+            if arg.hierarchical_identifier_list0.is_empty() {
+                name
+            } else {
+                vhdl_identifier(&arg.identifier.identifier_token.to_string())
+            }
+        } else {
+            vhdl_identifier(&arg.identifier.identifier_token.to_string())
+        };
+        let mut selects = String::new();
+        if !is_scalar(symbol_table::resolve(arg.identifier.as_ref())) {
+            for x in &arg.hierarchical_identifier_list {
+                selects.push_str(&self.select(&x.select));
+            }
+        }
+        if arg.hierarchical_identifier_list0.is_empty() {
+            ret.push_str(&selects);
+        } else {
+            let flatten = if let Ok(found) = symbol_table::resolve(arg.identifier.as_ref()) {
+                self.is_interface(&found.found)
+            } else {
+                false
+            };
+            let separator = if flatten { "_" } else { "." };
+            let scalar = is_scalar(symbol_table::resolve(arg));
+            for (i, x) in arg.hierarchical_identifier_list0.iter().enumerate() {
+                // element of interface instance array selects the flattened member
+                if i == 0 && flatten {
+                    ret.push_str(separator);
+                    ret.push_str(&x.identifier.identifier_token.to_string());
+                    ret.push_str(&selects);
+                } else {
+                    if i == 0 {
+                        ret.push_str(&selects);
+                    }
+                    ret.push_str(separator);
+                    ret.push_str(&x.identifier.identifier_token.to_string());
+                }
+                if !scalar {
+                    for x in &x.hierarchical_identifier_list0_list {
+                        ret.push_str(&self.select(&x.select));
+                    }
+                }
+            }
+        }
+        ret
+    }
+
+    fn target_of(&mut self, name: &str, symbol: Option<Symbol>, selects: &[&Select]) -> Target {
+        let is_port = matches!(symbol, Some(ref x) if matches!(x.kind, SymbolKind::Port(_)));
+        let r#type = match symbol {
+            // Member of flattened modport refers to the variable of the interface
+            Some(symbol) if matches!(symbol.kind, SymbolKind::ModportVariableMember(_)) => {
+                let path = SymbolPath::new(&[symbol.token.text]);
+                let mut namespace = symbol.namespace.clone();
+                namespace.pop();
+                symbol_table::resolve((&path, &namespace))
+                    .ok()
+                    .and_then(|x| symbol_type(&x.found))
+            }
+            Some(symbol) => symbol_type(&symbol),
+            None => None,
+        };
+        let Some(r#type) = r#type else {
+            return Target::Other;
+        };
+        match r#type.kind {
+            TypeKind::U32 | TypeKind::U64 if is_port => {
+                let width = if r#type.kind == TypeKind::U32 { 32 } else { 64 };
+                Target::Unsigned(width.to_string())
+            }
+            TypeKind::U32 | TypeKind::U64 | TypeKind::I32 | TypeKind::I64 => Target::Integer,
+            TypeKind::UserDefined(_)
+            | TypeKind::F32
+            | TypeKind::F64
+            | TypeKind::String
+            | TypeKind::Type => Target::Other,
+            _ => {
+                let dims = r#type.array.len() + r#type.width.len();
+                let range = selects.last().map(|x| x.select_opt.is_some());
+                if dims == 0 {
+                    Target::Bit
+                } else if range == Some(true) {
+                    Target::Vector(format!("{name}'length"))
+                } else if selects.len() >= dims {
+                    Target::Bit
+                } else if selects.len() < r#type.array.len() {
+                    Target::Other
+                } else {
+                    Target::Vector(format!("{name}'length"))
+                }
+            }
+        }
+    }
+
+    fn expression_identifier_target(&mut self, arg: &ExpressionIdentifier, name: &str) -> Target {
+        let symbol = symbol_table::resolve(arg).ok().map(|x| x.found);
+        let selects: Vec<_> = if let Some(x) = arg.expression_identifier_list0.last() {
+            x.expression_identifier_list0_list
+                .iter()
+                .map(|x| x.select.as_ref())
+                .collect()
+        } else {
+            arg.expression_identifier_list
+                .iter()
+                .map(|x| x.select.as_ref())
+                .collect()
+        };
+        self.target_of(name, symbol, &selects)
+    }
+
+    fn hierarchical_identifier_target(
+        &mut self,
+        arg: &HierarchicalIdentifier,
+        name: &str,
+    ) -> Target {
+        let symbol = symbol_table::resolve(arg).ok().map(|x| x.found);
+        let selects: Vec<_> = if let Some(x) = arg.hierarchical_identifier_list0.last() {
+            x.hierarchical_identifier_list0_list
+                .iter()
+                .map(|x| x.select.as_ref())
+                .collect()
+        } else {
+            arg.hierarchical_identifier_list
+                .iter()
+                .map(|x| x.select.as_ref())
+                .collect()
+        };
+        self.target_of(name, symbol, &selects)
+    }
+
+    // ------------------------------------------------------------------
+    // Statements
+    // ------------------------------------------------------------------
+
+    /// Collect variables declared in statement blocks as process or function variables
+    fn collect_variables(&mut self, arg: &StatementBlock, decls: &mut Vec<(VerylToken, String)>) {
+        for x in &arg.statement_block_list {
+            match &*x.statement_block_item {
+                StatementBlockItem::VarDeclaration(x) => {
+                    let x = &x.var_declaration;
+                    self.variable_declaration(&x.identifier, &x.array_type, decls);
+                }
+                StatementBlockItem::LetStatement(x) => {
+                    let x = &x.let_statement;
+                    self.variable_declaration(&x.identifier, &x.array_type, decls);
+                }
+                StatementBlockItem::Statement(x) => {
+                    self.collect_variables_statement(&x.statement, decls)
+                }
+            }
+        }
+    }
+
+    fn variable_declaration(
+        &mut self,
+        identifier: &Identifier,
+        array_type: &ArrayType,
+        decls: &mut Vec<(VerylToken, String)>,
+    ) {
+        let name = self.declaration_name(identifier);
+        let mut types = Vec::new();
+        let r#type = self.array_type(array_type, &name, &mut types);
+        for x in types {
+            decls.push((identifier.identifier_token.clone(), x));
+        }
+        decls.push((
+            identifier.identifier_token.clone(),
+            format!("variable {name} : {type};"),
+        ));
+        self.variables.push(name);
+    }
+
+    fn collect_variables_statement(
+        &mut self,
+        arg: &Statement,
+        decls: &mut Vec<(VerylToken, String)>,
+    ) {
+        match arg {
+            Statement::IfStatement(x) => {
+                let x = &x.if_statement;
+                self.collect_variables(&x.statement_block, decls);
+                for x in &x.if_statement_list {
+                    self.collect_variables(&x.statement_block, decls);
+                }
+                if let Some(ref x) = x.if_statement_opt {
+                    self.collect_variables(&x.statement_block, decls);
+                }
+            }
+            Statement::IfResetStatement(x) => {
+                let x = &x.if_reset_statement;
+                self.collect_variables(&x.statement_block, decls);
+                for x in &x.if_reset_statement_list {
+                    self.collect_variables(&x.statement_block, decls);
+                }
+                if let Some(ref x) = x.if_reset_statement_opt {
+                    self.collect_variables(&x.statement_block, decls);
+                }
+            }
+            Statement::ForStatement(x) => {
+                let x = &x.for_statement;
+                // loop variable of for statement with step is updated explicitly
+                if x.for_statement_opt.is_some() {
+                    let name = vhdl_identifier(&x.identifier.identifier_token.to_string());
+                    let r#type = self.scalar_type(&x.scalar_type);
+                    let decl = format!("variable {name} : {type};");
+                    if !decls.iter().any(|(_, x)| *x == decl) {
+                        decls.push((x.identifier.identifier_token.clone(), decl));
+                    }
+                }
+                self.collect_variables(&x.statement_block, decls);
+            }
+            Statement::CaseStatement(x) => {
+                for x in &x.case_statement.case_statement_list {
+                    if let CaseItemGroup0::StatementBlock(x) = &*x.case_item.case_item_group0 {
+                        self.collect_variables(&x.statement_block, decls);
+                    }
+                }
+            }
+            Statement::SwitchStatement(x) => {
+                for x in &x.switch_statement.switch_statement_list {
+                    if let SwitchItemGroup0::StatementBlock(x) = &*x.switch_item.switch_item_group0
+                    {
+                        self.collect_variables(&x.statement_block, decls);
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn statement_block(&mut self, arg: &StatementBlock) {
+        let mut empty = true;
+        for x in &arg.statement_block_list {
+            match &*x.statement_block_item {
+                StatementBlockItem::VarDeclaration(_) => (),
+                StatementBlockItem::LetStatement(x) => {
+                    let x = &x.let_statement;
+                    let name = self.declaration_name(&x.identifier);
+                    let target = self.scalar_type_target(&x.array_type.scalar_type);
+                    let value = self.assigned_expression(&x.expression, &target);
+                    self.line_with(
+                        &x.identifier.identifier_token,
+                        &format!("{name} := {value};"),
+                    );
+                    empty = false;
+                }
+                StatementBlockItem::Statement(x) => {
+                    self.statement(&x.statement);
+                    empty = false;
+                }
+            }
+        }
+        if empty {
+            self.line("null;");
+        }
+    }
+
+    fn statement(&mut self, arg: &Statement) {
+        match arg {
+            Statement::IdentifierStatement(x) => self.identifier_statement(&x.identifier_statement),
+            Statement::IfStatement(x) => self.if_statement(&x.if_statement),
+            Statement::IfResetStatement(x) => {
+                let x = &x.if_reset_statement;
+                let cond = self.reset_condition(None);
+                self.line_with(&x.if_reset.if_reset_token, &format!("if {cond} then"));
+                self.indent += 1;
+                self.statement_block(&x.statement_block);
+                self.indent -= 1;
+                self.else_if_reset(x);
+                self.line("end if;");
+            }
+            Statement::ReturnStatement(x) => {
+                let x = &x.return_statement;
+                let value = self.expression(&x.expression);
+                self.line_with(&x.r#return.return_token, &format!("return {value};"));
+            }
+            Statement::BreakStatement(x) => {
+                self.line_with(&x.break_statement.r#break.break_token, "exit;");
+            }
+            Statement::ForStatement(x) => self.for_statement(&x.for_statement),
+            Statement::CaseStatement(x) => self.case_statement(&x.case_statement),
+            Statement::SwitchStatement(x) => self.switch_statement(&x.switch_statement),
+        }
+    }
+
+    fn identifier_statement(&mut self, arg: &IdentifierStatement) {
+        let token = arg.expression_identifier.identifier().clone();
+        match &*arg.identifier_statement_group {
+            IdentifierStatementGroup::FunctionCall(x) => {
+                let name = token.to_string();
+                if name.starts_with('$') {
+                    self.line_with(&token, &format!("-- unsupported: {name}"));
+                } else {
+                    let args = self.function_call(&x.function_call);
+                    let call = self.call(&arg.expression_identifier, args);
+                    self.line_with(&token, &format!("{call};"));
+                }
+            }
+            IdentifierStatementGroup::Assignment(x) => {
+                let lhs = self.expression_identifier(&arg.expression_identifier);
+                let base = self.scoped_identifier(&arg.expression_identifier.scoped_identifier);
+                let op = if self.in_function || self.variables.contains(&base) {
+                    ":="
+                } else {
+                    "<="
+                };
+                let target = self.expression_identifier_target(&arg.expression_identifier, &lhs);
+                let rhs = match &*x.assignment.assignment_group {
+                    AssignmentGroup::Equ(_) => {
+                        self.assigned_expression(&x.assignment.expression, &target)
+                    }
+                    AssignmentGroup::AssignmentOperator(y) => {
+                        let operator = y.assignment_operator.assignment_operator_token.to_string();
+                        let operator = operator.trim_end_matches('=');
+                        self.compound_assignment(&lhs, &target, operator, &x.assignment.expression)
+                    }
+                };
+                self.line_with(&token, &format!("{lhs} {op} {rhs};"));
+            }
+        }
+    }
+
+    /// Right-hand side of compound assignment like `lhs += rhs`.
+    /// Operands are sized to the target because VHDL has no implicit extension.
+    fn compound_assignment(
+        &mut self,
+        lhs: &str,
+        target: &Target,
+        operator: &str,
+        arg: &Expression,
+    ) -> String {
+        let op = match operator {
+            "%" => "mod",
+            "&" => "and",
+            "|" => "or",
+            "^" => "xor",
+            x => x,
+        };
+        match target {
+            // 1bit arithmetic is equivalent to bitwise operation on LSB
+            Target::Bit => match operator {
+                "+" | "-" | "*" | "&" | "|" | "^" => {
+                    let op = match operator {
+                        "+" | "-" => "xor",
+                        "*" => "and",
+                        _ => op,
+                    };
+                    let rhs = self.sized_operand(arg, target);
+                    format!("{lhs} {op} {rhs}")
+                }
+                "/" | "%" => {
+                    let rhs = self.integer_operand(arg);
+                    format!("to_unsigned(to_integer(unsigned'(0 => {lhs})) {op} {rhs}, 1)(0)")
+                }
+                // arithmetic shift of 1bit unsigned is the same as logical one
+                _ => match Evaluator::new().expression(arg) {
+                    Evaluated::Fixed { value: 0, .. } => lhs.to_string(),
+                    Evaluated::Fixed { .. } => "'0'".to_string(),
+                    _ => {
+                        let count = self.integer_operand(arg);
+                        format!("{lhs} when {count} = 0 else '0'")
+                    }
+                },
+            },
+            Target::Vector(width) | Target::Unsigned(width) => match operator {
+                // vector and natural operands are extended to the longer one
+                "+" | "-" | "*" | "/" | "%" => {
+                    let mut collector = IdentifierCollector::default();
+                    collector.expression(arg);
+                    let integer = collector.identifiers.is_empty() || collector.is_integer();
+                    let rhs = paren(self.expression_inner(arg));
+                    if integer && operator != "*" {
+                        format!("{lhs} {op} {rhs}")
+                    } else {
+                        format!("resize({lhs} {op} {rhs}, {width})")
+                    }
+                }
+                "&" | "|" | "^" => {
+                    let rhs = self.sized_operand(arg, target);
+                    format!("{lhs} {op} {rhs}")
+                }
+                _ => {
+                    let count = self.integer_operand(arg);
+                    match operator {
+                        "<<" | "<<<" => format!("{lhs} sll {count}"),
+                        ">>" => format!("{lhs} srl {count}"),
+                        _ if matches!(target, Target::Unsigned(_)) => {
+                            format!("unsigned(shift_right(signed({lhs}), {count}))")
+                        }
+                        _ => format!("std_logic_vector(shift_right(signed({lhs}), {count}))"),
+                    }
+                }
+            },
+            Target::Integer | Target::Other => match operator {
+                "<<" | "<<<" => format!("{lhs} * 2 ** {}", self.integer_operand(arg)),
+                ">>" | ">>>" => format!("{lhs} / 2 ** {}", self.integer_operand(arg)),
+                _ => format!("{lhs} {op} {}", paren(self.expression_inner(arg))),
+            },
+        }
+    }
+
+    /// Operand of bitwise operator, which has the same width as the target
+    fn sized_operand(&mut self, arg: &Expression, target: &Target) -> String {
+        let ret = self.assigned_expression(arg, target);
+        paren((ret, single_factor(arg).is_none()))
+    }
+
+    /// Expression converted to integer, used for shift count and divisor of 1bit
+    fn integer_operand(&mut self, arg: &Expression) -> String {
+        let mut collector = IdentifierCollector::default();
+        collector.expression(arg);
+        let value = paren(self.expression_inner(arg));
+        if collector.identifiers.is_empty() || collector.is_integer() {
+            return value;
+        }
+        if let Some(Factor::ExpressionIdentifierFactorOpt(x)) = single_factor(arg) {
+            if x.factor_opt.is_none() {
+                let from = self.expression_identifier_target(&x.expression_identifier, &value);
+                return convert(&value, &from, &Target::Integer);
+            }
+        }
+        format!("to_integer({value})")
+    }
+
+    fn if_statement(&mut self, arg: &IfStatement) {
+        let cond = self.expression(&arg.expression);
+        self.line_with(&arg.r#if.if_token, &format!("if {cond} then"));
+        self.indent += 1;
+        self.statement_block(&arg.statement_block);
+        self.indent -= 1;
+        for x in &arg.if_statement_list {
+            let cond = self.expression(&x.expression);
+            self.line_with(&x.r#if.if_token, &format!("elsif {cond} then"));
+            self.indent += 1;
+            self.statement_block(&x.statement_block);
+            self.indent -= 1;
+        }
+        if let Some(ref x) = arg.if_statement_opt {
+            self.line_with(&x.r#else.else_token, "else");
+            self.indent += 1;
+            self.statement_block(&x.statement_block);
+            self.indent -= 1;
+        }
+        self.line("end if;");
+    }
+
+    fn else_if_reset(&mut self, arg: &IfResetStatement) {
+        for x in &arg.if_reset_statement_list {
+            let cond = self.expression(&x.expression);
+            self.line_with(&x.r#if.if_token, &format!("elsif {cond} then"));
+            self.indent += 1;
+            self.statement_block(&x.statement_block);
+            self.indent -= 1;
+        }
+        if let Some(ref x) = arg.if_reset_statement_opt {
+            self.line_with(&x.r#else.else_token, "else");
+            self.indent += 1;
+            self.statement_block(&x.statement_block);
+            self.indent -= 1;
+        }
+    }
+
+    fn for_statement(&mut self, arg: &ForStatement) {
+        let name = vhdl_identifier(&arg.identifier.identifier_token.to_string());
+        if let Some(ref x) = arg.for_statement_opt {
+            self.for_statement_with_step(arg, x, &name);
+            return;
+        }
+        let range = self.for_range(&arg.range);
+        self.line_with(&arg.r#for.for_token, &format!("for {name} in {range} loop"));
+        self.indent += 1;
+        self.statement_block(&arg.statement_block);
+        self.indent -= 1;
+        self.line("end loop;");
+    }
+
+    /// VHDL for loop can't have step, so it is lowered to while loop
+    fn for_statement_with_step(&mut self, arg: &ForStatement, step: &ForStatementOpt, name: &str) {
+        let beg = self.expression(&arg.range.expression);
+        let cond = if let Some(ref x) = arg.range.range_opt {
+            let end = self.expression(&x.expression);
+            match &*x.range_operator {
+                RangeOperator::DotDot(_) => format!("{name} < {end}"),
+                RangeOperator::DotDotEqu(_) => format!("{name} <= {end}"),
+            }
+        } else {
+            format!("{name} < {beg}")
+        };
+        let beg = if arg.range.range_opt.is_some() {
+            beg
+        } else {
+            "0".to_string()
+        };
+        let value = paren(self.expression_inner(&step.expression));
+        let operator = step
+            .assignment_operator
+            .assignment_operator_token
+            .to_string();
+        let next = match operator.trim_end_matches('=') {
+            "%" => format!("{name} mod {value}"),
+            "<<" | "<<<" => format!("{name} * 2 ** {value}"),
+            ">>" | ">>>" => format!("{name} / 2 ** {value}"),
+            // bitwise step is reported as unsupported by analyzer
+            operator => format!("{name} {operator} {value}"),
+        };
+
+        self.line_with(&arg.r#for.for_token, &format!("{name} := {beg};"));
+        self.line(&format!("while {cond} loop"));
+        self.indent += 1;
+        self.statement_block(&arg.statement_block);
+        self.line(&format!("{name} := {next};"));
+        self.indent -= 1;
+        self.line("end loop;");
+    }
+
+    fn for_range(&mut self, arg: &Range) -> String {
+        let beg = self.expression(&arg.expression);
+        if let Some(ref x) = arg.range_opt {
+            let end = self.expression(&x.expression);
+            match &*x.range_operator {
+                RangeOperator::DotDot(_) => format!("{beg} to {}", minus_one(&end)),
+                RangeOperator::DotDotEqu(_) => format!("{beg} to {end}"),
+            }
+        } else {
+            format!("0 to {}", minus_one(&beg))
+        }
+    }
+
+    fn case_statement(&mut self, arg: &CaseStatement) {
+        let target = paren(self.expression_inner(&arg.expression));
+        let mut first = true;
+        let mut has_default = false;
+        for x in &arg.case_statement_list {
+            let item = &x.case_item;
+            match &*item.case_item_group {
+                CaseItemGroup::CaseCondition(x) => {
+                    let cond = self.case_condition(&target, &x.case_condition);
+                    let keyword = if first { "if" } else { "elsif" };
+                    self.line_with(&arg.case.case_token, &format!("{keyword} {cond} then"));
+                    first = false;
+                }
+                CaseItemGroup::Defaul(x) => {
+                    if first {
+                        self.line_with(&x.defaul.default_token, "if true then");
+                        first = false;
+                    } else {
+                        self.line_with(&x.defaul.default_token, "else");
+                    }
+                    has_default = true;
+                }
+            }
+            self.indent += 1;
+            match &*item.case_item_group0 {
+                CaseItemGroup0::Statement(x) => self.statement(&x.statement),
+                CaseItemGroup0::StatementBlock(x) => self.statement_block(&x.statement_block),
+            }
+            self.indent -= 1;
+            if has_default {
+                break;
+            }
+        }
+        if !first {
+            self.line("end if;");
+        }
+    }
+
+    fn switch_statement(&mut self, arg: &SwitchStatement) {
+        let mut first = true;
+        let mut has_default = false;
+        for x in &arg.switch_statement_list {
+            let item = &x.switch_item;
+            match &*item.switch_item_group {
+                SwitchItemGroup::SwitchCondition(x) => {
+                    let cond = self.switch_condition(&x.switch_condition);
+                    let keyword = if first { "if" } else { "elsif" };
+                    self.line_with(&arg.switch.switch_token, &format!("{keyword} {cond} then"));
+                    first = false;
+                }
+                SwitchItemGroup::Defaul(x) => {
+                    if first {
+                        self.line_with(&x.defaul.default_token, "if true then");
+                        first = false;
+                    } else {
+                        self.line_with(&x.defaul.default_token, "else");
+                    }
+                    has_default = true;
+                }
+            }
+            self.indent += 1;
+            match &*item.switch_item_group0 {
+                SwitchItemGroup0::Statement(x) => self.statement(&x.statement),
+                SwitchItemGroup0::StatementBlock(x) => self.statement_block(&x.statement_block),
+            }
+            self.indent -= 1;
+            if has_default {
+                break;
+            }
+        }
+        if !first {
+            self.line("end if;");
+        }
+    }
+
+    // ------------------------------------------------------------------
+    // Processes
+    // ------------------------------------------------------------------
+
+    fn clock_edge(&mut self, symbol: Option<Symbol>) -> String {
+        let Some(symbol) = symbol else {
+            return "rising_edge(clk)".to_string();
+        };
+        let kind = symbol_type(&symbol).map(|x| x.kind);
+        let clock_type = match kind {
+            Some(TypeKind::ClockPosedge) => ClockType::PosEdge,
+            Some(TypeKind::ClockNegedge) => ClockType::NegEdge,
+            _ => self.build_opt.clock_type,
+        };
+        let name = signal_name(&symbol);
+        match clock_type {
+            ClockType::PosEdge => format!("rising_edge({name})"),
+            ClockType::NegEdge => format!("falling_edge({name})"),
+        }
+    }
+
+    fn reset_info(&mut self, symbol: Option<Symbol>) -> ResetInfo {
+        let (kind, name) = if let Some(symbol) = symbol {
+            (symbol_type(&symbol).map(|x| x.kind), signal_name(&symbol))
+        } else {
+            (None, "rst".to_string())
+        };
+        let reset_type = match kind {
+            Some(TypeKind::ResetAsyncHigh) => ResetType::AsyncHigh,
+            Some(TypeKind::ResetAsyncLow) => ResetType::AsyncLow,
+            Some(TypeKind::ResetSyncHigh) => ResetType::SyncHigh,
+            Some(TypeKind::ResetSyncLow) => ResetType::SyncLow,
+            _ => self.build_opt.reset_type,
+        };
+        let (active, asynchronous) = match reset_type {
+            ResetType::AsyncHigh => ("'1'", true),
+            ResetType::AsyncLow => ("'0'", true),
+            ResetType::SyncHigh => ("'1'", false),
+            ResetType::SyncLow => ("'0'", false),
+        };
+        ResetInfo {
+            name,
+            active,
+            asynchronous,
+        }
+    }
+
+    fn reset_condition(&mut self, symbol: Option<Symbol>) -> String {
+        let symbol = symbol.or_else(|| self.default_reset.and_then(symbol_table::get));
+        let info = self.reset_info(symbol);
+        format!("{} = {}", info.name, info.active)
+    }
+
+    fn process_declarations(&mut self, decls: Vec<(VerylToken, String)>) {
+        self.indent += 1;
+        for (token, decl) in decls {
+            self.line_with(&token, &decl);
+        }
+        self.indent -= 1;
+    }
+
+    fn always_ff_declaration(&mut self, arg: &AlwaysFfDeclaration) {
+        let (clock, reset) = if let Some(ref x) = arg.always_ff_declaration_opt {
+            let x = &x.alwayf_ff_event_list;
+            let clock = symbol_table::resolve(x.always_ff_clock.hierarchical_identifier.as_ref())
+                .ok()
+                .map(|x| x.found);
+            let reset = x.alwayf_ff_event_list_opt.as_ref().and_then(|x| {
+                symbol_table::resolve(x.always_ff_reset.hierarchical_identifier.as_ref())
+                    .ok()
+                    .map(|x| x.found)
+            });
+            (clock, reset)
+        } else {
+            let clock = self.default_clock.and_then(symbol_table::get);
+            let reset = self.default_reset.and_then(symbol_table::get);
+            (clock, reset)
+        };
+
+        let if_reset = arg
+            .statement_block
+            .statement_block_list
+            .iter()
+            .find_map(|x| {
+                if let StatementBlockItem::Statement(x) = &*x.statement_block_item {
+                    if let Statement::IfResetStatement(x) = &*x.statement {
+                        return Some(x.if_reset_statement.as_ref());
+                    }
+                }
+                None
+            });
+        let reset = if if_reset.is_some() {
+            Some(self.reset_info(reset))
+        } else {
+            None
+        };
+
+        let edge = self.clock_edge(clock.clone());
+        let clock_name = clock.map(|x| signal_name(&x)).unwrap_or("clk".to_string());
+
+        let mut decls = Vec::new();
+        self.variables.clear();
+        self.collect_variables(&arg.statement_block, &mut decls);
+
+        let sensitivity = match &reset {
+            Some(x) if x.asynchronous => format!("{clock_name}, {}", x.name),
+            _ => clock_name,
+        };
+
+        self.line_with(
+            &arg.always_ff.always_ff_token,
+            &format!("process ({sensitivity})"),
+        );
+        self.process_declarations(decls);
+        self.line("begin");
+        self.indent += 1;
+
+        if let (Some(info), Some(if_reset)) = (&reset, if_reset) {
+            let cond = format!("{} = {}", info.name, info.active);
+            if info.asynchronous {
+                self.line_with(
+                    &if_reset.if_reset.if_reset_token,
+                    &format!("if {cond} then"),
+                );
+                self.indent += 1;
+                self.statement_block(&if_reset.statement_block);
+                self.indent -= 1;
+                self.line(&format!("elsif {edge} then"));
+                self.indent += 1;
+                self.clocked_statements(arg, if_reset);
+                self.indent -= 1;
+                self.line("end if;");
+            } else {
+                self.line(&format!("if {edge} then"));
+                self.indent += 1;
+                self.line_with(
+                    &if_reset.if_reset.if_reset_token,
+                    &format!("if {cond} then"),
+                );
+                self.indent += 1;
+                self.statement_block(&if_reset.statement_block);
+                self.indent -= 1;
+                let has_else = !if_reset.if_reset_statement_list.is_empty()
+                    || if_reset.if_reset_statement_opt.is_some();
+                if has_else || arg.statement_block.statement_block_list.len() > 1 {
+                    self.line("else");
+                    self.indent += 1;
+                    self.clocked_statements(arg, if_reset);
+                    self.indent -= 1;
+                }
+                self.line("end if;");
+                self.indent -= 1;
+                self.line("end if;");
+            }
+        } else {
+            self.line(&format!("if {edge} then"));
+            self.indent += 1;
+            self.statement_block(&arg.statement_block);
+            self.indent -= 1;
+            self.line("end if;");
+        }
+
+        self.indent -= 1;
+        self.line("end process;");
+        self.variables.clear();
+    }
+
+    /// Statements executed at clock edge except reset
+    fn clocked_statements(&mut self, arg: &AlwaysFfDeclaration, if_reset: &IfResetStatement) {
+        let mut empty = true;
+        if !if_reset.if_reset_statement_list.is_empty() {
+            let x = &if_reset.if_reset_statement_list[0];
+            let cond = self.expression(&x.expression);
+            self.line_with(&x.r#if.if_token, &format!("if {cond} then"));
+            self.indent += 1;
+            self.statement_block(&x.statement_block);
+            self.indent -= 1;
+            for x in &if_reset.if_reset_statement_list[1..] {
+                let cond = self.expression(&x.expression);
+                self.line_with(&x.r#if.if_token, &format!("elsif {cond} then"));
+                self.indent += 1;
+                self.statement_block(&x.statement_block);
+                self.indent -= 1;
+            }
+            if let Some(ref x) = if_reset.if_reset_statement_opt {
+                self.line_with(&x.r#else.else_token, "else");
+                self.indent += 1;
+                self.statement_block(&x.statement_block);
+                self.indent -= 1;
+            }
+            self.line("end if;");
+            empty = false;
+        } else if let Some(ref x) = if_reset.if_reset_statement_opt {
+            self.statement_block(&x.statement_block);
+            empty = false;
+        }
+
+        for x in &arg.statement_block.statement_block_list {
+            match &*x.statement_block_item {
+                StatementBlockItem::Statement(x)
+                    if matches!(*x.statement, Statement::IfResetStatement(_)) => {}
+                StatementBlockItem::Statement(x) => {
+                    self.statement(&x.statement);
+                    empty = false;
+                }
+                StatementBlockItem::LetStatement(x) => {
+                    let x = &x.let_statement;
+                    let name = self.declaration_name(&x.identifier);
+                    let target = self.scalar_type_target(&x.array_type.scalar_type);
+                    let value = self.assigned_expression(&x.expression, &target);
+                    self.line_with(
+                        &x.identifier.identifier_token,
+                        &format!("{name} := {value};"),
+                    );
+                    empty = false;
+                }
+                StatementBlockItem::VarDeclaration(_) => (),
+            }
+        }
+        if empty {
+            self.line("null;");
+        }
+    }
+
+    fn always_comb_declaration(&mut self, arg: &AlwaysCombDeclaration) {
+        let mut decls = Vec::new();
+        self.variables.clear();
+        self.collect_variables(&arg.statement_block, &mut decls);
+
+        self.line_with(&arg.always_comb.always_comb_token, "process (all)");
+        self.process_declarations(decls);
+        self.line("begin");
+        self.indent += 1;
+        self.statement_block(&arg.statement_block);
+        self.indent -= 1;
+        self.line("end process;");
+        self.variables.clear();
+    }
+
+    /// Process without sensitivity list which is suspended forever after execution
+    fn initial_declaration(&mut self, arg: &InitialDeclaration) {
+        let mut decls = Vec::new();
+        self.variables.clear();
+        self.collect_variables(&arg.statement_block, &mut decls);
+
+        self.line_with(&arg.initial.initial_token, "process");
+        self.process_declarations(decls);
+        self.line("begin");
+        self.indent += 1;
+        self.statement_block(&arg.statement_block);
+        self.line("wait;");
+        self.indent -= 1;
+        self.line("end process;");
+        self.variables.clear();
+    }
+
+    // ------------------------------------------------------------------
+    // Declarations
+    // ------------------------------------------------------------------
+
+    fn function_declaration(&mut self, arg: &FunctionDeclaration, body: bool) {
+        let name = vhdl_identifier(&arg.identifier.identifier_token.to_string());
+        let mut params = Vec::new();
+        if let Some(ref x) = arg.function_declaration_opt0 {
+            if let Some(ref x) = x.port_declaration.port_declaration_opt {
+                let items: Vec<PortDeclarationItem> = x.port_declaration_list.as_ref().into();
+                for item in items {
+                    let param_name = vhdl_identifier(&item.identifier.identifier_token.to_string());
+                    if let PortDeclarationItemGroup::PortTypeConcrete(x) =
+                        &*item.port_declaration_item_group
+                    {
+                        let x = &x.port_type_concrete;
+                        let mut types = Vec::new();
+                        let r#type = self.array_type(&x.array_type, &param_name, &mut types);
+                        let direction = match &*x.direction {
+                            Direction::Output(_) => "out ",
+                            Direction::Inout(_) | Direction::Ref(_) => "inout ",
+                            _ => "",
+                        };
+                        params.push(format!("{param_name} : {direction}{type}"));
+                    }
+                }
+            }
+        }
+        let params = if params.is_empty() {
+            String::new()
+        } else {
+            format!(" ({})", params.join("; "))
+        };
+
+        let ret = arg.function_declaration_opt1.as_ref().map(|x| {
+            let r#type = self.scalar_type(&x.scalar_type);
+            // return type of VHDL function should be type mark
+            r#type
+                .split_once('(')
+                .map(|x| x.0.to_string())
+                .unwrap_or(r#type)
+        });
+
+        let head = if let Some(ref ret) = ret {
+            format!("function {name}{params} return {ret}")
+        } else {
+            format!("procedure {name}{params}")
+        };
+
+        if !body {
+            self.line_with(&arg.identifier.identifier_token, &format!("{head};"));
+            return;
+        }
+
+        let mut decls = Vec::new();
+        self.variables.clear();
+        self.collect_variables(&arg.statement_block, &mut decls);
+
+        self.line_with(&arg.identifier.identifier_token, &format!("{head} is"));
+        self.process_declarations(decls);
+        self.line("begin");
+        self.indent += 1;
+        self.in_function = true;
+        self.statement_block(&arg.statement_block);
+        self.in_function = false;
+        self.indent -= 1;
+        if ret.is_some() {
+            self.line(&format!("end function {name};"));
+        } else {
+            self.line(&format!("end procedure {name};"));
+        }
+        self.variables.clear();
+    }
+
+    fn const_declaration(&mut self, arg: &ConstDeclaration) {
+        let name = vhdl_identifier(&arg.identifier.identifier_token.to_string());
+        let token = &arg.identifier.identifier_token;
+        match &*arg.const_declaration_group {
+            ConstDeclarationGroup::ArrayTypeEquExpression(x) => {
+                let mut types = Vec::new();
+                let r#type = self.array_type(&x.array_type, &name, &mut types);
+                for x in types {
+                    self.line_with(token, &x);
+                }
+                let target = self.scalar_type_target(&x.array_type.scalar_type);
+                let value = self.assigned_expression(&x.expression, &target);
+                self.line_with(token, &format!("constant {name} : {type} := {value};"));
+            }
+            ConstDeclarationGroup::TypeEquTypeExpression(x) => {
+                let r#type = self.type_expression(&x.type_expression);
+                self.line_with(token, &format!("subtype {name} is {type};"));
+            }
+        }
+    }
+
+    fn type_expression(&mut self, arg: &TypeExpression) -> String {
+        match arg {
+            TypeExpression::ScalarType(x) => self.scalar_type(&x.scalar_type),
+            TypeExpression::TypeLParenExpressionRParen(x) => {
+                format!("{}'subtype", self.expression(&x.expression))
+            }
+        }
+    }
+
+    fn type_def_declaration(&mut self, arg: &TypeDefDeclaration) {
+        let name = vhdl_identifier(&arg.identifier.identifier_token.to_string());
+        let token = &arg.identifier.identifier_token;
+        let mut types = Vec::new();
+        let r#type = self.array_type(&arg.array_type, &name, &mut types);
+        for x in types {
+            self.line_with(token, &x);
+        }
+        self.line_with(token, &format!("subtype {name} is {type};"));
+    }
+
+    fn enum_declaration(&mut self, arg: &EnumDeclaration) {
+        let name = vhdl_identifier(&arg.identifier.identifier_token.to_string());
+        let token = &arg.identifier.identifier_token;
+        let Ok(found) = symbol_table::resolve(arg.identifier.as_ref()) else {
+            return;
+        };
+        let SymbolKind::Enum(ref property) = found.found.kind else {
+            return;
+        };
+        let width = property.width.max(1);
+        self.line_with(
+            token,
+            &format!(
+                "subtype {name} is std_logic_vector({} downto 0);",
+                width - 1
+            ),
+        );
+        for id in &property.members {
+            let member = symbol_table::get(*id).unwrap();
+            if let SymbolKind::EnumMember(ref x) = member.kind {
+                let member_name = vhdl_identifier(&format!("{}_{}", x.prefix, member.token));
+                let value = x.value.value().unwrap_or(0);
+                let value = format!("{:0width$b}", value, width = width);
+                self.line_with(
+                    &VerylToken::new(member.token),
+                    &format!("constant {member_name} : {name} := \"{value}\";"),
+                );
+            }
+        }
+    }
+
+    fn struct_union_declaration(&mut self, arg: &StructUnionDeclaration) {
+        let name = vhdl_identifier(&arg.identifier.identifier_token.to_string());
+        let token = &arg.identifier.identifier_token;
+        // union is reported as unsupported by analyzer
+        if let StructUnion::Union(_) = &*arg.struct_union {
+            return;
+        }
+        self.line_with(token, &format!("type {name} is record"));
+        self.indent += 1;
+        let items: Vec<StructUnionItem> = arg.struct_union_list.as_ref().into();
+        for item in items {
+            let member = vhdl_identifier(&item.identifier.identifier_token.to_string());
+            let r#type = self.scalar_type(&item.scalar_type);
+            self.line_with(
+                &item.identifier.identifier_token,
+                &format!("{member} : {type};"),
+            );
+        }
+        self.indent -= 1;
+        self.line(&format!("end record {name};"));
+    }
+
+    /// Signals declared by instance of interface
+    fn interface_instance(&mut self, arg: &InstDeclaration) -> bool {
+        let Ok(found) = symbol_table::resolve(arg.scoped_identifier.as_ref()) else {
+            return false;
+        };
+        if !matches!(found.found.kind, SymbolKind::Interface(_)) {
+            return false;
+        }
+        let prefix = arg.identifier.identifier_token.to_string();
+        let namespace = found.found.inner_namespace();
+        for symbol in symbol_table::get_all() {
+            if symbol.namespace != namespace {
+                continue;
+            }
+            if let SymbolKind::Variable(ref x) = symbol.kind {
+                let name = vhdl_identifier(&format!("{prefix}_{}", symbol.token));
+                let mut r#type = self.symbol_type_string(&x.r#type, &symbol);
+                // array of interface instance is flattened to array of each member
+                if let Some(ref x) = arg.inst_declaration_opt {
+                    let mut types = Vec::new();
+                    r#type = self.array_of(&x.array, r#type, &name, &mut types);
+                    for x in types {
+                        self.line_with(&arg.identifier.identifier_token, &x);
+                    }
+                }
+                self.line_with(
+                    &arg.identifier.identifier_token,
+                    &format!("signal {name} : {type};"),
+                );
+            }
+        }
+        true
+    }
+
+    /// Members of modport, returns pairs of (name, direction, type)
+    fn modport_members(&mut self, port: &Symbol) -> Vec<(String, String, String)> {
+        let mut ret = Vec::new();
+        let Some(r#type) = symbol_type(port) else {
+            return ret;
+        };
+        let TypeKind::UserDefined(ref path) = r#type.kind else {
+            return ret;
+        };
+        let Ok(modport) = symbol_table::resolve((path, &port.namespace)) else {
+            return ret;
+        };
+        let SymbolKind::Modport(ref property) = modport.found.kind else {
+            return ret;
+        };
+        for id in &property.members {
+            let member = symbol_table::get(*id).unwrap();
+            if let SymbolKind::ModportVariableMember(ref x) = member.kind {
+                let direction = match x.direction {
+                    SymDirection::Input => "in",
+                    SymDirection::Output => "out",
+                    _ => "inout",
+                };
+                let path = SymbolPath::new(&[member.token.text]);
+                if let Ok(variable) = symbol_table::resolve((&path, &modport.found.namespace)) {
+                    if let SymbolKind::Variable(ref y) = variable.found.kind {
+                        let r#type = self.symbol_type_string(&y.r#type, &variable.found);
+                        ret.push((member.token.to_string(), direction.to_string(), r#type));
+                    }
+                }
+            }
+        }
+        ret
+    }
+
+    fn inst_declaration(&mut self, arg: &InstDeclaration) {
+        let name = vhdl_identifier(&arg.identifier.identifier_token.to_string());
+        let token = &arg.identifier.identifier_token;
+        let module = symbol_table::resolve(arg.scoped_identifier.as_ref()).ok();
+        if let Some(ref x) = module {
+            if matches!(x.found.kind, SymbolKind::Interface(_)) {
+                return;
+            }
+        }
+        let entity = self.scoped_identifier(&arg.scoped_identifier);
+        // array instance of module is reported as unsupported by analyzer
+        if arg.inst_declaration_opt.is_some() {
+            return;
+        }
+
+        self.line_with(token, &format!("{name}: entity work.{entity}"));
+        self.indent += 1;
+
+        if let Some(ref x) = arg.inst_declaration_opt0 {
+            if let Some(ref x) = x.inst_parameter.inst_parameter_opt {
+                let items: Vec<InstParameterItem> = x.inst_parameter_list.as_ref().into();
+                let mut list = Vec::new();
+                for item in items {
+                    let param = vhdl_identifier(&item.identifier.identifier_token.to_string());
+                    let value = if let Some(ref x) = item.inst_parameter_item_opt {
+                        self.expression(&x.expression)
+                    } else {
+                        self.declaration_name(&item.identifier)
+                    };
+                    list.push((
+                        Some(item.identifier.identifier_token.clone()),
+                        format!("{param} => {value}"),
+                    ));
+                }
+                self.line("generic map (");
+                self.indent += 1;
+                self.list(list, ",");
+                self.indent -= 1;
+                self.line(")");
+            }
+        }
+
+        let ports = if let Some(SymbolKind::Module(ref x)) = module.as_ref().map(|x| &x.found.kind)
+        {
+            x.ports.clone()
+        } else {
+            Vec::new()
+        };
+
+        let mut list = Vec::new();
+        if let Some(ref x) = arg.inst_declaration_opt1 {
+            if let Some(ref x) = x.inst_declaration_opt2 {
+                let items: Vec<InstPortItem> = x.inst_port_list.as_ref().into();
+                for item in items {
+                    let port_token = &item.identifier.identifier_token;
+                    let value = if let Some(ref x) = item.inst_port_item_opt {
+                        if is_anonymous(&x.expression) {
+                            "open".to_string()
+                        } else {
+                            self.expression(&x.expression)
+                        }
+                    } else {
+                        self.declaration_name(&item.identifier)
+                    };
+                    let port = ports
+                        .iter()
+                        .find(|x| x.name == port_token.token.text)
+                        .and_then(|x| symbol_table::get(x.symbol));
+                    if let Some(port) = port {
+                        // generic interface port is omitted from entity
+                        if matches!(port.kind, SymbolKind::Port(ref x) if x.direction == SymDirection::Interface)
+                        {
+                            continue;
+                        }
+                        if self.is_interface(&port) {
+                            let port_name = port_token.to_string();
+                            // element of interface instance array is connected with its selects
+                            let (value, selects) = match item
+                                .inst_port_item_opt
+                                .as_ref()
+                                .and_then(|x| single_factor(&x.expression))
+                            {
+                                Some(Factor::ExpressionIdentifierFactorOpt(x)) => {
+                                    let x = &x.expression_identifier;
+                                    let base = self.scoped_identifier(&x.scoped_identifier);
+                                    let mut selects = String::new();
+                                    for x in &x.expression_identifier_list {
+                                        selects.push_str(&self.select(&x.select));
+                                    }
+                                    (base, selects)
+                                }
+                                _ => (value, String::new()),
+                            };
+                            for (member, _, _) in self.modport_members(&port) {
+                                list.push((
+                                    Some(port_token.clone()),
+                                    format!(
+                                        "{} => {}{selects}",
+                                        vhdl_identifier(&format!("{port_name}_{member}")),
+                                        vhdl_identifier(&format!("{value}_{member}")),
+                                    ),
+                                ));
+                            }
+                            continue;
+                        }
+                        let port_name = signal_name(&port);
+                        let (port_name, value) =
+                            self.port_association(&port, &item, port_name, value);
+                        list.push((Some(port_token.clone()), format!("{port_name} => {value}")));
+                    } else {
+                        let port_name = vhdl_identifier(&port_token.to_string());
+                        list.push((Some(port_token.clone()), format!("{port_name} => {value}")));
+                    }
+                }
+            }
+        }
+        if !list.is_empty() {
+            self.line("port map (");
+            self.indent += 1;
+            self.list(list, ",");
+            self.indent -= 1;
+            self.line(");");
+        } else {
+            let last = self.string.len() - NEWLINE.len();
+            self.string.truncate(last);
+            self.str(";");
+            self.str(NEWLINE);
+        }
+        self.indent -= 1;
+    }
+
+    /// Convert the actual of `unsigned` port, which is declared as u32/u64
+    fn port_association(
+        &mut self,
+        port: &Symbol,
+        item: &InstPortItem,
+        port_name: String,
+        value: String,
+    ) -> (String, String) {
+        let SymbolKind::Port(ref property) = port.kind else {
+            return (port_name, value);
+        };
+        let width = match property.r#type.as_ref().map(|x| &x.kind) {
+            Some(TypeKind::U32) => "32",
+            Some(TypeKind::U64) => "64",
+            _ => return (port_name, value),
+        };
+        if value == "open" {
+            return (port_name, value);
+        }
+        let target = Target::Unsigned(width.to_string());
+        let from = if let Some(ref x) = item.inst_port_item_opt {
+            if property.direction == SymDirection::Input {
+                return (port_name, self.assigned_expression(&x.expression, &target));
+            }
+            match single_factor(&x.expression) {
+                Some(Factor::ExpressionIdentifierFactorOpt(x)) if x.factor_opt.is_none() => {
+                    self.expression_identifier_target(&x.expression_identifier, &value)
+                }
+                _ => Target::Other,
+            }
+        } else {
+            let symbol = symbol_table::resolve(item.identifier.as_ref())
+                .ok()
+                .map(|x| x.found);
+            self.target_of(&value, symbol, &[])
+        };
+        if property.direction == SymDirection::Input {
+            return (port_name, convert(&value, &from, &target));
+        }
+        // output is converted at the formal side, which can't be resized
+        match from {
+            Target::Vector(_) => (format!("std_logic_vector({port_name})"), value),
+            Target::Integer => (format!("to_integer({port_name})"), value),
+            _ => (port_name, value),
+        }
+    }
+
+    /// Emit declarative items of generate items
+    fn generate_declarations(&mut self, items: &[GenerateItem]) -> bool {
+        let len = self.string.len();
+        for item in items {
+            match item {
+                GenerateItem::LetDeclaration(x) => {
+                    let x = &x.let_declaration;
+                    self.signal_declaration(&x.identifier, &x.array_type);
+                }
+                GenerateItem::VarDeclaration(x) => {
+                    let x = &x.var_declaration;
+                    self.signal_declaration(&x.identifier, &x.array_type);
+                }
+                GenerateItem::InstDeclaration(x) => {
+                    self.interface_instance(&x.inst_declaration);
+                }
+                GenerateItem::ConstDeclaration(x) => {
+                    self.const_declaration(&x.const_declaration);
+                }
+                GenerateItem::FunctionDeclaration(x) => {
+                    self.function_declaration(&x.function_declaration, true);
+                }
+                GenerateItem::TypeDefDeclaration(x) => {
+                    self.type_def_declaration(&x.type_def_declaration);
+                }
+                GenerateItem::EnumDeclaration(x) => {
+                    self.enum_declaration(&x.enum_declaration);
+                }
+                GenerateItem::StructUnionDeclaration(x) => {
+                    self.struct_union_declaration(&x.struct_union_declaration);
+                }
+                GenerateItem::UnsafeBlock(x) => {
+                    let items = Self::unsafe_block_items(&x.unsafe_block);
+                    self.generate_declarations(&items);
+                }
+                _ => (),
+            }
+        }
+        self.string.len() != len
+    }
+
+    fn signal_declaration(&mut self, identifier: &Identifier, array_type: &ArrayType) {
+        let name = self.declaration_name(identifier);
+        let mut types = Vec::new();
+        let r#type = self.array_type(array_type, &name, &mut types);
+        for x in types {
+            self.line_with(&identifier.identifier_token, &x);
+        }
+        self.line_with(
+            &identifier.identifier_token,
+            &format!("signal {name} : {type};"),
+        );
+    }
+
+    fn unsafe_block_items(arg: &UnsafeBlock) -> Vec<GenerateItem> {
+        let mut ret = Vec::new();
+        for x in &arg.unsafe_block_list {
+            let mut items: Vec<GenerateItem> = x.generate_group.as_ref().into();
+            ret.append(&mut items);
+        }
+        ret
+    }
+
+    /// Emit concurrent statements of generate items
+    fn generate_statements(&mut self, items: &[GenerateItem]) {
+        for item in items {
+            match item {
+                GenerateItem::LetDeclaration(x) => {
+                    let x = &x.let_declaration;
+                    let name = self.declaration_name(&x.identifier);
+                    let target = self.scalar_type_target(&x.array_type.scalar_type);
+                    let value = self.assigned_expression(&x.expression, &target);
+                    self.line_with(
+                        &x.identifier.identifier_token,
+                        &format!("{name} <= {value};"),
+                    );
+                }
+                GenerateItem::AssignDeclaration(x) => {
+                    let x = &x.assign_declaration;
+                    let name = self.hierarchical_identifier(&x.hierarchical_identifier);
+                    let target =
+                        self.hierarchical_identifier_target(&x.hierarchical_identifier, &name);
+                    let value = self.assigned_expression(&x.expression, &target);
+                    self.line_with(
+                        &x.hierarchical_identifier.identifier.identifier_token,
+                        &format!("{name} <= {value};"),
+                    );
+                }
+                GenerateItem::InstDeclaration(x) => {
+                    let x = &x.inst_declaration;
+                    if let Ok(found) = symbol_table::resolve(x.scoped_identifier.as_ref()) {
+                        if matches!(found.found.kind, SymbolKind::Interface(_)) {
+                            continue;
+                        }
+                    }
+                    self.blank();
+                    self.inst_declaration(x);
+                }
+                GenerateItem::AlwaysFfDeclaration(x) => {
+                    self.blank();
+                    self.always_ff_declaration(&x.always_ff_declaration);
+                }
+                GenerateItem::AlwaysCombDeclaration(x) => {
+                    self.blank();
+                    self.always_comb_declaration(&x.always_comb_declaration);
+                }
+                GenerateItem::InitialDeclaration(x) => {
+                    self.blank();
+                    self.initial_declaration(&x.initial_declaration);
+                }
+                GenerateItem::GenerateIfDeclaration(x) => {
+                    self.blank();
+                    self.generate_if_declaration(&x.generate_if_declaration);
+                }
+                GenerateItem::GenerateForDeclaration(x) => {
+                    self.blank();
+                    self.generate_for_declaration(&x.generate_for_declaration);
+                }
+                GenerateItem::GenerateBlockDeclaration(x) => {
+                    self.blank();
+                    let x = &x.generate_block_declaration.generate_named_block;
+                    let label = vhdl_identifier(&x.identifier.identifier_token.to_string());
+                    self.line_with(&x.identifier.identifier_token, &format!("{label}: block"));
+                    let items = Self::named_block_items(x);
+                    self.generate_body(&items, true);
+                    self.line(&format!("end block {label};"));
+                }
+                GenerateItem::UnsafeBlock(x) => {
+                    let items = Self::unsafe_block_items(&x.unsafe_block);
+                    self.generate_statements(&items);
+                }
+                _ => (),
+            }
+        }
+    }
+
+    fn named_block_items(arg: &GenerateNamedBlock) -> Vec<GenerateItem> {
+        let mut ret = Vec::new();
+        for x in &arg.generate_named_block_list {
+            let mut items: Vec<GenerateItem> = x.generate_group.as_ref().into();
+            ret.append(&mut items);
+        }
+        ret
+    }
+
+    fn optional_named_block_items(arg: &GenerateOptionalNamedBlock) -> Vec<GenerateItem> {
+        let mut ret = Vec::new();
+        for x in &arg.generate_optional_named_block_list {
+            let mut items: Vec<GenerateItem> = x.generate_group.as_ref().into();
+            ret.append(&mut items);
+        }
+        ret
+    }
+
+    /// Body of generate statement or block; `begin` is required if declarations exist
+    fn generate_body(&mut self, items: &[GenerateItem], force_begin: bool) {
+        self.indent += 1;
+        let has_decl = self.generate_declarations(items);
+        self.indent -= 1;
+        if has_decl || force_begin {
+            self.line("begin");
+        }
+        self.indent += 1;
+        self.generate_statements(items);
+        self.indent -= 1;
+    }
+
+    fn generate_condition(&mut self, arg: &Expression) -> String {
+        self.boolean = true;
+        let ret = self.expression(arg);
+        self.boolean = false;
+        ret
+    }
+
+    fn generate_if_declaration(&mut self, arg: &GenerateIfDeclaration) {
+        let block = &arg.generate_named_block;
+        let label = vhdl_identifier(&block.identifier.identifier_token.to_string());
+        let cond = self.generate_condition(&arg.expression);
+        self.line_with(
+            &block.identifier.identifier_token,
+            &format!("{label}: if {cond} generate"),
+        );
+        let items = Self::named_block_items(block);
+        self.generate_body(&items, false);
+        for x in &arg.generate_if_declaration_list {
+            let cond = self.generate_condition(&x.expression);
+            self.line_with(&x.r#if.if_token, &format!("elsif {cond} generate"));
+            let items = Self::optional_named_block_items(&x.generate_optional_named_block);
+            self.generate_body(&items, false);
+        }
+        if let Some(ref x) = arg.generate_if_declaration_opt {
+            self.line_with(&x.r#else.else_token, "else generate");
+            let items = Self::optional_named_block_items(&x.generate_optional_named_block);
+            self.generate_body(&items, false);
+        }
+        self.line(&format!("end generate {label};"));
+    }
+
+    fn generate_for_declaration(&mut self, arg: &GenerateForDeclaration) {
+        let block = &arg.generate_named_block;
+        let label = vhdl_identifier(&block.identifier.identifier_token.to_string());
+        let name = vhdl_identifier(&arg.identifier.identifier_token.to_string());
+        let items = Self::named_block_items(block);
+
+        // VHDL for generate can't have step,
+        // so the index is scaled to the value of the loop variable.
+        // Only `+=` step is accepted by analyzer.
+        if let Some(ref x) = arg.generate_for_declaration_opt {
+            let index = vhdl_identifier(&format!("{}_index", arg.identifier.identifier_token));
+            let beg = self.expression(&arg.range.expression);
+            let step = paren(self.expression_inner(&x.expression));
+            let (beg, count) = if let Some(ref x) = arg.range.range_opt {
+                let end = self.expression(&x.expression);
+                let span = if beg == "0" {
+                    end
+                } else {
+                    format!("{end} - {beg}")
+                };
+                let count = match &*x.range_operator {
+                    RangeOperator::DotDot(_) => format!("({span} - 1) / {step}"),
+                    RangeOperator::DotDotEqu(_) => format!("({span}) / {step}"),
+                };
+                (beg, count)
+            } else {
+                ("0".to_string(), format!("({beg} - 1) / {step}"))
+            };
+            self.line_with(
+                &block.identifier.identifier_token,
+                &format!("{label}: for {index} in 0 to {count} generate"),
+            );
+            self.indent += 1;
+            self.line(&format!(
+                "constant {name} : integer := {beg} + {index} * {step};"
+            ));
+            self.generate_declarations(&items);
+            self.indent -= 1;
+            self.line("begin");
+            self.indent += 1;
+            self.generate_statements(&items);
+            self.indent -= 1;
+            self.line(&format!("end generate {label};"));
+            return;
+        }
+
+        let range = self.for_range(&arg.range);
+        self.line_with(
+            &block.identifier.identifier_token,
+            &format!("{label}: for {name} in {range} generate"),
+        );
+        self.generate_body(&items, false);
+        self.line(&format!("end generate {label};"));
+    }
+
+    // ------------------------------------------------------------------
+    // Design units
+    // ------------------------------------------------------------------
+
+    fn module_declaration(&mut self, arg: &ModuleDeclaration) {
+        let symbol = symbol_table::resolve(arg.identifier.as_ref()).unwrap();
+        if let SymbolKind::Module(ref x) = symbol.found.kind {
+            self.default_clock = x.default_clock;
+            self.default_reset = x.default_reset;
+        }
+
+        let name = self.design_unit_name(&arg.identifier);
+
+        let mut items = Vec::new();
+        for x in &arg.module_declaration_list {
+            let mut group: Vec<ModuleItem> = x.module_group.as_ref().into();
+            for x in group.drain(..) {
+                items.push(*x.generate_item);
+            }
+        }
+
+        let mut imports = Vec::new();
+        for item in &items {
+            if let GenerateItem::ImportDeclaration(x) = item {
+                imports.push(self.import_declaration(&x.import_declaration));
+            }
+        }
+        self.context_clause(&imports);
+
+        self.line_with(
+            &arg.identifier.identifier_token,
+            &format!("entity {name} is"),
+        );
+        self.indent += 1;
+
+        let mut constants = Vec::new();
+        if let Some(ref x) = arg.module_declaration_opt2 {
+            if let Some(ref x) = x.with_parameter.with_parameter_opt {
+                let items: Vec<WithParameterItem> = x.with_parameter_list.as_ref().into();
+                let mut list = Vec::new();
+                for item in items {
+                    if let WithParameterItemGroup::Const(_) = &*item.with_parameter_item_group {
+                        constants.push(item);
+                        continue;
+                    }
+                    let param = vhdl_identifier(&item.identifier.identifier_token.to_string());
+                    let text = match &*item.with_parameter_item_group0 {
+                        WithParameterItemGroup0::ArrayTypeEquExpression(x) => {
+                            let r#type = self.scalar_type(&x.array_type.scalar_type);
+                            let target = self.scalar_type_target(&x.array_type.scalar_type);
+                            let value = self.assigned_expression(&x.expression, &target);
+                            format!("{param} : {type} := {value}")
+                        }
+                        WithParameterItemGroup0::TypeEquTypeExpression(_) => {
+                            format!("type {param}")
+                        }
+                    };
+                    list.push((Some(item.identifier.identifier_token.clone()), text));
+                }
+                if !list.is_empty() {
+                    self.line("generic (");
+                    self.indent += 1;
+                    self.list(list, ";");
+                    self.indent -= 1;
+                    self.line(");");
+                }
+            }
+        }
+
+        if let Some(ref x) = arg.module_declaration_opt3 {
+            if let Some(ref x) = x.port_declaration.port_declaration_opt {
+                let items: Vec<PortDeclarationItem> = x.port_declaration_list.as_ref().into();
+                let mut list = Vec::new();
+                for item in items {
+                    self.port_declaration_item(&item, &mut list);
+                }
+                if !list.is_empty() {
+                    self.line("port (");
+                    self.indent += 1;
+                    self.list(list, ";");
+                    self.indent -= 1;
+                    self.line(");");
+                }
+            }
+        }
+
+        self.indent -= 1;
+        self.line(&format!("end entity {name};"));
+        self.str(NEWLINE);
+
+        self.line(&format!("architecture rtl of {name} is"));
+        self.indent += 1;
+        for item in constants {
+            let param = vhdl_identifier(&item.identifier.identifier_token.to_string());
+            let token = &item.identifier.identifier_token;
+            match &*item.with_parameter_item_group0 {
+                WithParameterItemGroup0::ArrayTypeEquExpression(x) => {
+                    let r#type = self.scalar_type(&x.array_type.scalar_type);
+                    let target = self.scalar_type_target(&x.array_type.scalar_type);
+                    let value = self.assigned_expression(&x.expression, &target);
+                    self.line_with(token, &format!("constant {param} : {type} := {value};"));
+                }
+                WithParameterItemGroup0::TypeEquTypeExpression(x) => {
+                    let r#type = self.type_expression(&x.type_expression);
+                    self.line_with(token, &format!("subtype {param} is {type};"));
+                }
+            }
+        }
+        self.generate_declarations(&items);
+        self.indent -= 1;
+        self.line("begin");
+        self.indent += 1;
+        self.generate_statements(&items);
+        self.indent -= 1;
+        self.line("end architecture rtl;");
+
+        self.default_clock = None;
+        self.default_reset = None;
+    }
+
+    fn port_declaration_item(
+        &mut self,
+        arg: &PortDeclarationItem,
+        list: &mut Vec<(Option<VerylToken>, String)>,
+    ) {
+        let token = &arg.identifier.identifier_token;
+        // generic interface port is omitted because it is unreferenced,
+        // referenced one is reported as unsupported by analyzer
+        let PortDeclarationItemGroup::PortTypeConcrete(x) = &*arg.port_declaration_item_group
+        else {
+            return;
+        };
+        let x = &x.port_type_concrete;
+        let name = self.declaration_name(&arg.identifier);
+        let direction = match &*x.direction {
+            Direction::Input(_) => "in",
+            Direction::Output(_) => "out",
+            Direction::Inout(_) => "inout",
+            Direction::Modport(_) => {
+                if let Ok(found) = symbol_table::resolve(arg.identifier.as_ref()) {
+                    let base = token.to_string();
+                    for (member, direction, r#type) in self.modport_members(&found.found) {
+                        let member = vhdl_identifier(&format!("{base}_{member}"));
+                        list.push((
+                            Some(token.clone()),
+                            format!("{member} : {direction} {type}"),
+                        ));
+                    }
+                }
+                return;
+            }
+            _ => "inout",
+        };
+        let scalar_type = &x.array_type.scalar_type;
+        let r#type = match &*scalar_type.scalar_type_group {
+            // 32/64bit port is emitted as `unsigned` to connect with vectors
+            ScalarTypeGroup::FixedType(x) => match &*x.fixed_type {
+                FixedType::U32(_) => "unsigned(31 downto 0)".to_string(),
+                FixedType::U64(_) => "unsigned(63 downto 0)".to_string(),
+                _ => self.scalar_type(scalar_type),
+            },
+            _ => self.scalar_type(scalar_type),
+        };
+        list.push((Some(token.clone()), format!("{name} : {direction} {type}")));
+    }
+
+    fn package_declaration(&mut self, arg: &PackageDeclaration) {
+        // generic package is reported as unsupported by analyzer
+        if arg.package_declaration_opt0.is_some() {
+            return;
+        }
+
+        let name = self.design_unit_name(&arg.identifier);
+
+        let mut items = Vec::new();
+        for x in &arg.package_declaration_list {
+            let mut group: Vec<PackageItem> = x.package_group.as_ref().into();
+            items.append(&mut group);
+        }
+
+        let mut imports = Vec::new();
+        for item in &items {
+            if let PackageItem::ImportDeclaration(x) = item {
+                imports.push(self.import_declaration(&x.import_declaration));
+            }
+        }
+        self.context_clause(&imports);
+
+        self.line_with(
+            &arg.identifier.identifier_token,
+            &format!("package {name} is"),
+        );
+        self.indent += 1;
+        let mut has_function = false;
+        for item in &items {
+            match item {
+                PackageItem::VarDeclaration(x) => {
+                    let x = &x.var_declaration;
+                    self.signal_declaration(&x.identifier, &x.array_type);
+                }
+                PackageItem::ConstDeclaration(x) => self.const_declaration(&x.const_declaration),
+                PackageItem::TypeDefDeclaration(x) => {
+                    self.type_def_declaration(&x.type_def_declaration)
+                }
+                PackageItem::EnumDeclaration(x) => self.enum_declaration(&x.enum_declaration),
+                PackageItem::StructUnionDeclaration(x) => {
+                    self.struct_union_declaration(&x.struct_union_declaration)
+                }
+                PackageItem::FunctionDeclaration(x) => {
+                    self.function_declaration(&x.function_declaration, false);
+                    has_function = true;
+                }
+                PackageItem::ImportDeclaration(_) | PackageItem::ExportDeclaration(_) => (),
+            }
+        }
+        self.indent -= 1;
+        self.line(&format!("end package {name};"));
+
+        if has_function {
+            self.str(NEWLINE);
+            self.line(&format!("package body {name} is"));
+            self.indent += 1;
+            for item in &items {
+                if let PackageItem::FunctionDeclaration(x) = item {
+                    self.function_declaration(&x.function_declaration, true);
+                }
+            }
+            self.indent -= 1;
+            self.line(&format!("end package body {name};"));
+        }
+    }
+}
@@ -1,11 +1,18 @@
 use crate::server::{semantic_legend, MsgFromServer, MsgToServer, Server, ServerConfigItem};
+use crate::test_runner;
 use async_channel::{unbounded, Receiver, Sender};
 use serde_json::Value;
-use tower_lsp::jsonrpc::Result;
+use tower_lsp::jsonrpc::{Error, Result};
+use tower_lsp::lsp_types::request::{
+    GotoImplementationParams, GotoImplementationResponse, GotoTypeDefinitionParams,
+    GotoTypeDefinitionResponse,
+};
 use tower_lsp::lsp_types::*;
-use tower_lsp::{Client, LanguageServer};
+use tower_lsp::{Client, ClientSocket, LanguageServer, LspService};
 
 const COMPLETION_TRIGGER: &[&str] = &["<", ">", "=", "!", "."];
+const SIGNATURE_HELP_TRIGGER: &[&str] = &["(", ",", "<"];
+const ON_TYPE_FORMATTING_TRIGGER: &[&str] = &["}", ";", "\n"];
 
 #[derive(Debug)]
 pub struct Backend {
@@ -28,6 +35,28 @@ impl Backend {
         }
     }
 
+    pub fn service() -> (LspService<Backend>, ClientSocket) {
+        LspService::build(Backend::new)
+            .custom_method("veryl/instanceTree", Backend::instance_tree)
+            .finish()
+    }
+
+    /// Custom request `veryl/instanceTree` returning the instance tree from the module `top`
+    pub async fn instance_tree(&self, params: Value) -> Result<Option<Value>> {
+        let Some(top) = params["top"].as_str() else {
+            return Err(Error::invalid_params("\"top\" module name is required"));
+        };
+        let top = top.to_string();
+
+        self.send(MsgToServer::InstanceTree { top }).await;
+
+        if let Some(MsgFromServer::InstanceTree(x)) = self.recv().await {
+            Ok(x)
+        } else {
+            Ok(None)
+        }
+    }
+
     async fn send(&self, msg: MsgToServer) {
         if let Err(x) = self.snd.send(msg).await {
             self.client.log_message(MessageType::ERROR, x).await;
@@ -61,10 +90,59 @@ impl LanguageServer for Backend {
                     file_operations: None,
                 }),
                 definition_provider: Some(OneOf::Left(true)),
+                implementation_provider: Some(ImplementationProviderCapability::Simple(true)),
+                type_definition_provider: Some(TypeDefinitionProviderCapability::Simple(true)),
                 document_formatting_provider: Some(OneOf::Left(true)),
+                document_range_formatting_provider: Some(OneOf::Left(true)),
+                document_on_type_formatting_provider: Some(DocumentOnTypeFormattingOptions {
+                    first_trigger_character: ON_TYPE_FORMATTING_TRIGGER[0].to_string(),
+                    more_trigger_character: Some(
+                        ON_TYPE_FORMATTING_TRIGGER[1..]
+                            .iter()
+                            .map(|x| x.to_string())
+                            .collect(),
+                    ),
+                }),
                 workspace_symbol_provider: Some(OneOf::Left(true)),
+                document_symbol_provider: Some(OneOf::Left(true)),
+                inlay_hint_provider: Some(OneOf::Left(true)),
+                folding_range_provider: Some(FoldingRangeProviderCapability::Simple(true)),
+                selection_range_provider: Some(SelectionRangeProviderCapability::Simple(true)),
+                document_highlight_provider: Some(OneOf::Left(true)),
+                call_hierarchy_provider: Some(CallHierarchyServerCapability::Simple(true)),
+                signature_help_provider: Some(SignatureHelpOptions {
+                    trigger_characters: Some(
+                        SIGNATURE_HELP_TRIGGER
+                            .iter()
+                            .map(|x| x.to_string())
+                            .collect(),
+                    ),
+                    retrigger_characters: None,
+                    work_done_progress_options: WorkDoneProgressOptions::default(),
+                }),
                 hover_provider: Some(HoverProviderCapability::Simple(true)),
                 references_provider: Some(OneOf::Left(true)),
+                rename_provider: Some(OneOf::Right(RenameOptions {
+                    prepare_provider: Some(true),
+                    work_done_progress_options: WorkDoneProgressOptions::default(),
+                })),
+                code_action_provider: Some(CodeActionProviderCapability::Options(
+                    CodeActionOptions {
+                        code_action_kinds: Some(vec![CodeActionKind::QUICKFIX]),
+                        work_done_progress_options: WorkDoneProgressOptions::default(),
+                        resolve_provider: Some(false),
+                    },
+                )),
+                code_lens_provider: Some(CodeLensOptions {
+                    resolve_provider: Some(false),
+                }),
+                execute_command_provider: Some(ExecuteCommandOptions {
+                    commands: vec![
+                        test_runner::RUN_TEST.to_string(),
+                        test_runner::RUN_TEST_WITH_WAVE.to_string(),
+                    ],
+                    work_done_progress_options: WorkDoneProgressOptions::default(),
+                }),
                 semantic_tokens_provider: Some(
                     SemanticTokensServerCapabilities::SemanticTokensOptions(
                         SemanticTokensOptions {
@@ -75,8 +153,8 @@ impl LanguageServer for Backend {
                                 token_types: semantic_legend::get_token_types(),
                                 token_modifiers: semantic_legend::get_token_modifiers(),
                             },
-                            range: Some(false),
-                            full: Some(SemanticTokensFullOptions::Delta { delta: Some(false) }),
+                            range: Some(true),
+                            full: Some(SemanticTokensFullOptions::Delta { delta: Some(true) }),
                         },
                     ),
                 ),
@@ -203,6 +281,129 @@ impl LanguageServer for Backend {
         }
     }
 
+    async fn document_symbol(
+        &self,
+        params: DocumentSymbolParams,
+    ) -> Result<Option<DocumentSymbolResponse>> {
+        let url = params.text_document.uri;
+
+        self.send(MsgToServer::DocumentSymbol { url }).await;
+
+        if let Some(MsgFromServer::DocumentSymbol(x)) = self.recv().await {
+            Ok(x)
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn prepare_call_hierarchy(
+        &self,
+        params: CallHierarchyPrepareParams,
+    ) -> Result<Option<Vec<CallHierarchyItem>>> {
+        let url = params.text_document_position_params.text_document.uri;
+        let line = params.text_document_position_params.position.line as usize + 1;
+        let column = params.text_document_position_params.position.character as usize + 1;
+
+        self.send(MsgToServer::PrepareCallHierarchy { url, line, column })
+            .await;
+
+        if let Some(MsgFromServer::PrepareCallHierarchy(x)) = self.recv().await {
+            Ok(x)
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn incoming_calls(
+        &self,
+        params: CallHierarchyIncomingCallsParams,
+    ) -> Result<Option<Vec<CallHierarchyIncomingCall>>> {
+        let item = params.item;
+
+        self.send(MsgToServer::IncomingCalls { item }).await;
+
+        if let Some(MsgFromServer::IncomingCalls(x)) = self.recv().await {
+            Ok(x)
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn outgoing_calls(
+        &self,
+        params: CallHierarchyOutgoingCallsParams,
+    ) -> Result<Option<Vec<CallHierarchyOutgoingCall>>> {
+        let item = params.item;
+
+        self.send(MsgToServer::OutgoingCalls { item }).await;
+
+        if let Some(MsgFromServer::OutgoingCalls(x)) = self.recv().await {
+            Ok(x)
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn folding_range(&self, params: FoldingRangeParams) -> Result<Option<Vec<FoldingRange>>> {
+        let url = params.text_document.uri;
+
+        self.send(MsgToServer::FoldingRange { url }).await;
+
+        if let Some(MsgFromServer::FoldingRange(x)) = self.recv().await {
+            Ok(x)
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn selection_range(
+        &self,
+        params: SelectionRangeParams,
+    ) -> Result<Option<Vec<SelectionRange>>> {
+        let url = params.text_document.uri;
+        let positions = params.positions;
+
+        self.send(MsgToServer::SelectionRange { url, positions })
+            .await;
+
+        if let Some(MsgFromServer::SelectionRange(x)) = self.recv().await {
+            Ok(x)
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn document_highlight(
+        &self,
+        params: DocumentHighlightParams,
+    ) -> Result<Option<Vec<DocumentHighlight>>> {
+        let url = params.text_document_position_params.text_document.uri;
+        let line = params.text_document_position_params.position.line as usize + 1;
+        let column = params.text_document_position_params.position.character as usize + 1;
+
+        self.send(MsgToServer::DocumentHighlight { url, line, column })
+            .await;
+
+        if let Some(MsgFromServer::DocumentHighlight(x)) = self.recv().await {
+            Ok(x)
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn inlay_hint(&self, params: InlayHintParams) -> Result<Option<Vec<InlayHint>>> {
+        let url = params.text_document.uri;
+        let range = params.range;
+
+        self.send(MsgToServer::InlayHint { url, range }).await;
+
+        if let Some(MsgFromServer::InlayHint(x)) = self.recv().await {
+            Ok(x)
+        } else {
+            Ok(None)
+        }
+    }
+
     async fn hover(&self, params: HoverParams) -> Result<Option<Hover>> {
         let url = params.text_document_position_params.text_document.uri;
         let line = params.text_document_position_params.position.line as usize + 1;
@@ -217,6 +418,42 @@ impl LanguageServer for Backend {
         }
     }
 
+    async fn goto_implementation(
+        &self,
+        params: GotoImplementationParams,
+    ) -> Result<Option<GotoImplementationResponse>> {
+        let url = params.text_document_position_params.text_document.uri;
+        let line = params.text_document_position_params.position.line as usize + 1;
+        let column = params.text_document_position_params.position.character as usize + 1;
+
+        self.send(MsgToServer::GotoImplementation { url, line, column })
+            .await;
+
+        if let Some(MsgFromServer::GotoImplementation(x)) = self.recv().await {
+            Ok(x)
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn goto_type_definition(
+        &self,
+        params: GotoTypeDefinitionParams,
+    ) -> Result<Option<GotoTypeDefinitionResponse>> {
+        let url = params.text_document_position_params.text_document.uri;
+        let line = params.text_document_position_params.position.line as usize + 1;
+        let column = params.text_document_position_params.position.character as usize + 1;
+
+        self.send(MsgToServer::GotoTypeDefinition { url, line, column })
+            .await;
+
+        if let Some(MsgFromServer::GotoTypeDefinition(x)) = self.recv().await {
+            Ok(x)
+        } else {
+            Ok(None)
+        }
+    }
+
     async fn references(&self, params: ReferenceParams) -> Result<Option<Vec<Location>>> {
         let url = params.text_document_position.text_document.uri;
         let line = params.text_document_position.position.line as usize + 1;
@@ -232,6 +469,60 @@ impl LanguageServer for Backend {
         }
     }
 
+    async fn signature_help(&self, params: SignatureHelpParams) -> Result<Option<SignatureHelp>> {
+        let url = params.text_document_position_params.text_document.uri;
+        let line = params.text_document_position_params.position.line as usize + 1;
+        let column = params.text_document_position_params.position.character as usize + 1;
+
+        self.send(MsgToServer::SignatureHelp { url, line, column })
+            .await;
+
+        if let Some(MsgFromServer::SignatureHelp(x)) = self.recv().await {
+            Ok(x)
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn prepare_rename(
+        &self,
+        params: TextDocumentPositionParams,
+    ) -> Result<Option<PrepareRenameResponse>> {
+        let url = params.text_document.uri;
+        let line = params.position.line as usize + 1;
+        let column = params.position.character as usize + 1;
+
+        self.send(MsgToServer::PrepareRename { url, line, column })
+            .await;
+
+        if let Some(MsgFromServer::PrepareRename(x)) = self.recv().await {
+            Ok(x)
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn rename(&self, params: RenameParams) -> Result<Option<WorkspaceEdit>> {
+        let url = params.text_document_position.text_document.uri;
+        let line = params.text_document_position.position.line as usize + 1;
+        let column = params.text_document_position.position.character as usize + 1;
+        let new_name = params.new_name;
+
+        self.send(MsgToServer::Rename {
+            url,
+            line,
+            column,
+            new_name,
+        })
+        .await;
+
+        match self.recv().await {
+            Some(MsgFromServer::Rename(Ok(x))) => Ok(Some(x)),
+            Some(MsgFromServer::Rename(Err(x))) => Err(Error::invalid_params(x)),
+            _ => Ok(None),
+        }
+    }
+
     async fn semantic_tokens_full(
         &self,
         params: SemanticTokensParams,
@@ -247,6 +538,43 @@ impl LanguageServer for Backend {
         }
     }
 
+    async fn semantic_tokens_full_delta(
+        &self,
+        params: SemanticTokensDeltaParams,
+    ) -> Result<Option<SemanticTokensFullDeltaResult>> {
+        let url = params.text_document.uri;
+        let previous_result_id = params.previous_result_id;
+
+        self.send(MsgToServer::SemanticTokensDelta {
+            url,
+            previous_result_id,
+        })
+        .await;
+
+        if let Some(MsgFromServer::SemanticTokensDelta(x)) = self.recv().await {
+            Ok(x)
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn semantic_tokens_range(
+        &self,
+        params: SemanticTokensRangeParams,
+    ) -> Result<Option<SemanticTokensRangeResult>> {
+        let url = params.text_document.uri;
+        let range = params.range;
+
+        self.send(MsgToServer::SemanticTokensRange { url, range })
+            .await;
+
+        if let Some(MsgFromServer::SemanticTokensRange(x)) = self.recv().await {
+            Ok(x)
+        } else {
+            Ok(None)
+        }
+    }
+
     async fn formatting(&self, params: DocumentFormattingParams) -> Result<Option<Vec<TextEdit>>> {
         let url = params.text_document.uri;
 
@@ -259,6 +587,79 @@ impl LanguageServer for Backend {
         }
     }
 
+    async fn range_formatting(
+        &self,
+        params: DocumentRangeFormattingParams,
+    ) -> Result<Option<Vec<TextEdit>>> {
+        let url = params.text_document.uri;
+        let range = params.range;
+
+        self.send(MsgToServer::RangeFormatting { url, range }).await;
+
+        if let Some(MsgFromServer::RangeFormatting(x)) = self.recv().await {
+            Ok(x)
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn on_type_formatting(
+        &self,
+        params: DocumentOnTypeFormattingParams,
+    ) -> Result<Option<Vec<TextEdit>>> {
+        let url = params.text_document_position.text_document.uri;
+        let position = params.text_document_position.position;
+        let ch = params.ch;
+
+        self.send(MsgToServer::OnTypeFormatting { url, position, ch })
+            .await;
+
+        if let Some(MsgFromServer::OnTypeFormatting(x)) = self.recv().await {
+            Ok(x)
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn code_action(&self, params: CodeActionParams) -> Result<Option<CodeActionResponse>> {
+        let url = params.text_document.uri;
+        let range = params.range;
+
+        self.send(MsgToServer::CodeAction { url, range }).await;
+
+        if let Some(MsgFromServer::CodeAction(x)) = self.recv().await {
+            Ok(x)
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn code_lens(&self, params: CodeLensParams) -> Result<Option<Vec<CodeLens>>> {
+        let url = params.text_document.uri;
+
+        self.send(MsgToServer::CodeLens { url }).await;
+
+        if let Some(MsgFromServer::CodeLens(x)) = self.recv().await {
+            Ok(x)
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn execute_command(&self, params: ExecuteCommandParams) -> Result<Option<Value>> {
+        let command = params.command;
+        let arguments = params.arguments;
+
+        self.send(MsgToServer::ExecuteCommand { command, arguments })
+            .await;
+
+        if let Some(MsgFromServer::ExecuteCommand(x)) = self.recv().await {
+            Ok(x)
+        } else {
+            Ok(None)
+        }
+    }
+
     async fn shutdown(&self) -> Result<()> {
         Ok(())
     }
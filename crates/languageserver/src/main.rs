@@ -1,19 +1,20 @@
 #![recursion_limit = "256"]
 
 mod backend;
-mod keyword;
 mod server;
+mod syntax_range;
+mod test_runner;
 #[cfg(test)]
 mod tests;
 
 use backend::Backend;
-use tower_lsp::{LspService, Server};
+use tower_lsp::Server;
 
 #[tokio::main]
 async fn main() {
     let stdin = tokio::io::stdin();
     let stdout = tokio::io::stdout();
 
-    let (service, socket) = LspService::new(Backend::new);
+    let (service, socket) = Backend::service();
     Server::new(stdin, stdout, socket).serve(service).await;
 }
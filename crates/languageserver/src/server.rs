@@ -1,22 +1,39 @@
-use crate::keyword::KEYWORDS;
+use crate::syntax_range::{self, SyntaxRangeCollector};
+use crate::test_runner;
 use async_channel::{Receiver, Sender};
 use dashmap::DashMap;
 use futures::executor::block_on;
 use ropey::Rope;
-use std::collections::VecDeque;
+use serde_json::{json, Value};
+use similar::{ChangeTag, DiffTag, TextDiff};
+use std::collections::{HashMap, VecDeque};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tower_lsp::lsp_types::request::{GotoImplementationResponse, GotoTypeDefinitionResponse};
 use tower_lsp::lsp_types::*;
 use tower_lsp::Client;
+use veryl_analyzer::assign::AssignPositionType;
+use veryl_analyzer::evaluator::{Evaluated, Evaluator};
+use veryl_analyzer::fix::{self, Fix};
+use veryl_analyzer::keyword::KEYWORDS;
 use veryl_analyzer::namespace::Namespace;
 use veryl_analyzer::symbol::SymbolKind as VerylSymbolKind;
-use veryl_analyzer::symbol::{Symbol, TypeKind};
+use veryl_analyzer::symbol::{
+    GenericBoundKind, InstanceProperty, Parameter, Port, Symbol, SymbolId, Type, TypeKind,
+};
 use veryl_analyzer::symbol_path::SymbolPath;
-use veryl_analyzer::{namespace_table, symbol_table, Analyzer, AnalyzerError};
+use veryl_analyzer::{
+    function_table, namespace_table, rename, symbol_table, Analyzer, AnalyzerError,
+};
 use veryl_formatter::Formatter;
 use veryl_metadata::Metadata;
-use veryl_parser::veryl_token::Token;
+use veryl_parser::first_token::FirstToken;
+use veryl_parser::last_token::LastToken;
+use veryl_parser::resource_table::{self, PathId};
+use veryl_parser::veryl_grammar_trait as syntax_tree;
+use veryl_parser::veryl_token::{Token, TokenSource};
 use veryl_parser::veryl_walker::VerylWalker;
-use veryl_parser::{resource_table, Finder, Parser, ParserError};
+use veryl_parser::{Finder, Parser, ParserError};
 use veryl_path::PathPair;
 
 pub enum MsgToServer {
@@ -55,12 +72,99 @@ pub enum MsgToServer {
         line: usize,
         column: usize,
     },
+    GotoImplementation {
+        url: Url,
+        line: usize,
+        column: usize,
+    },
+    GotoTypeDefinition {
+        url: Url,
+        line: usize,
+        column: usize,
+    },
+    SignatureHelp {
+        url: Url,
+        line: usize,
+        column: usize,
+    },
+    PrepareRename {
+        url: Url,
+        line: usize,
+        column: usize,
+    },
+    Rename {
+        url: Url,
+        line: usize,
+        column: usize,
+        new_name: String,
+    },
+    DocumentSymbol {
+        url: Url,
+    },
+    PrepareCallHierarchy {
+        url: Url,
+        line: usize,
+        column: usize,
+    },
+    IncomingCalls {
+        item: CallHierarchyItem,
+    },
+    OutgoingCalls {
+        item: CallHierarchyItem,
+    },
+    InstanceTree {
+        top: String,
+    },
+    InlayHint {
+        url: Url,
+        range: Range,
+    },
+    FoldingRange {
+        url: Url,
+    },
+    SelectionRange {
+        url: Url,
+        positions: Vec<Position>,
+    },
+    DocumentHighlight {
+        url: Url,
+        line: usize,
+        column: usize,
+    },
     SemanticTokens {
         url: Url,
     },
+    SemanticTokensDelta {
+        url: Url,
+        previous_result_id: String,
+    },
+    SemanticTokensRange {
+        url: Url,
+        range: Range,
+    },
     Formatting {
         url: Url,
     },
+    RangeFormatting {
+        url: Url,
+        range: Range,
+    },
+    OnTypeFormatting {
+        url: Url,
+        position: Position,
+        ch: String,
+    },
+    CodeAction {
+        url: Url,
+        range: Range,
+    },
+    CodeLens {
+        url: Url,
+    },
+    ExecuteCommand {
+        command: String,
+        arguments: Vec<Value>,
+    },
 }
 
 pub enum MsgFromServer {
@@ -69,8 +173,29 @@ pub enum MsgFromServer {
     Symbol(Vec<SymbolInformation>),
     Hover(Option<Hover>),
     References(Vec<Location>),
+    GotoImplementation(Option<GotoImplementationResponse>),
+    GotoTypeDefinition(Option<GotoTypeDefinitionResponse>),
+    SignatureHelp(Option<SignatureHelp>),
+    PrepareRename(Option<PrepareRenameResponse>),
+    Rename(Result<WorkspaceEdit, String>),
+    DocumentSymbol(Option<DocumentSymbolResponse>),
+    PrepareCallHierarchy(Option<Vec<CallHierarchyItem>>),
+    IncomingCalls(Option<Vec<CallHierarchyIncomingCall>>),
+    OutgoingCalls(Option<Vec<CallHierarchyOutgoingCall>>),
+    InstanceTree(Option<Value>),
+    InlayHint(Option<Vec<InlayHint>>),
+    FoldingRange(Option<Vec<FoldingRange>>),
+    SelectionRange(Option<Vec<SelectionRange>>),
+    DocumentHighlight(Option<Vec<DocumentHighlight>>),
     SemanticTokens(Option<SemanticTokensResult>),
+    SemanticTokensDelta(Option<SemanticTokensFullDeltaResult>),
+    SemanticTokensRange(Option<SemanticTokensRangeResult>),
     Formatting(Option<Vec<TextEdit>>),
+    RangeFormatting(Option<Vec<TextEdit>>),
+    OnTypeFormatting(Option<Vec<TextEdit>>),
+    CodeAction(Option<CodeActionResponse>),
+    CodeLens(Option<Vec<CodeLens>>),
+    ExecuteCommand(Option<Value>),
 }
 
 pub struct BackgroundTask {
@@ -103,6 +228,10 @@ pub struct Server {
     snd: Sender<MsgFromServer>,
     document_map: DashMap<PathBuf, Rope>,
     parser_map: DashMap<PathBuf, Parser>,
+    fix_map: DashMap<PathBuf, Vec<(Diagnostic, Fix)>>,
+    diag_map: Arc<DashMap<PathBuf, Vec<Diagnostic>>>,
+    semantic_tokens_map: DashMap<PathBuf, SemanticTokens>,
+    semantic_tokens_id: usize,
     metadata_map: DashMap<PathBuf, Metadata>,
     cache_dir: PathBuf,
     lsp_token: i32,
@@ -120,6 +249,10 @@ impl Server {
             snd,
             document_map: DashMap::new(),
             parser_map: DashMap::new(),
+            fix_map: DashMap::new(),
+            diag_map: Arc::new(DashMap::new()),
+            semantic_tokens_map: DashMap::new(),
+            semantic_tokens_id: 0,
             metadata_map: DashMap::new(),
             cache_dir: veryl_path::cache_path(),
             lsp_token: 0,
@@ -157,8 +290,59 @@ impl Server {
                     MsgToServer::References { url, line, column } => {
                         self.references(&url, line, column)
                     }
+                    MsgToServer::GotoImplementation { url, line, column } => {
+                        self.goto_implementation(&url, line, column)
+                    }
+                    MsgToServer::GotoTypeDefinition { url, line, column } => {
+                        self.goto_type_definition(&url, line, column)
+                    }
+                    MsgToServer::SignatureHelp { url, line, column } => {
+                        self.signature_help(&url, line, column)
+                    }
+                    MsgToServer::PrepareRename { url, line, column } => {
+                        self.prepare_rename(&url, line, column)
+                    }
+                    MsgToServer::Rename {
+                        url,
+                        line,
+                        column,
+                        new_name,
+                    } => self.rename(&url, line, column, &new_name),
+                    MsgToServer::DocumentSymbol { url } => self.document_symbol(&url),
+                    MsgToServer::PrepareCallHierarchy { url, line, column } => {
+                        self.prepare_call_hierarchy(&url, line, column)
+                    }
+                    MsgToServer::IncomingCalls { item } => self.incoming_calls(&item),
+                    MsgToServer::OutgoingCalls { item } => self.outgoing_calls(&item),
+                    MsgToServer::InstanceTree { top } => self.instance_tree(&top),
+                    MsgToServer::InlayHint { url, range } => self.inlay_hint(&url, &range),
+                    MsgToServer::FoldingRange { url } => self.folding_range(&url),
+                    MsgToServer::SelectionRange { url, positions } => {
+                        self.selection_range(&url, &positions)
+                    }
+                    MsgToServer::DocumentHighlight { url, line, column } => {
+                        self.document_highlight(&url, line, column)
+                    }
                     MsgToServer::SemanticTokens { url } => self.semantic_tokens(&url),
+                    MsgToServer::SemanticTokensDelta {
+                        url,
+                        previous_result_id,
+                    } => self.semantic_tokens_delta(&url, &previous_result_id),
+                    MsgToServer::SemanticTokensRange { url, range } => {
+                        self.semantic_tokens_range(&url, &range)
+                    }
                     MsgToServer::Formatting { url } => self.formatting(&url),
+                    MsgToServer::RangeFormatting { url, range } => {
+                        self.range_formatting(&url, &range)
+                    }
+                    MsgToServer::OnTypeFormatting { url, position, ch } => {
+                        self.on_type_formatting(&url, &position, &ch)
+                    }
+                    MsgToServer::CodeAction { url, range } => self.code_action(&url, &range),
+                    MsgToServer::CodeLens { url } => self.code_lens(&url),
+                    MsgToServer::ExecuteCommand { command, arguments } => {
+                        self.execute_command(&command, &arguments)
+                    }
                 }
             }
 
@@ -328,37 +512,7 @@ impl Server {
         for symbol in symbol_table::get_all() {
             let name = symbol.token.text.to_string();
             if name.contains(query) {
-                let kind = match symbol.kind {
-                    VerylSymbolKind::Port(_) => SymbolKind::VARIABLE,
-                    VerylSymbolKind::Variable(_) => SymbolKind::VARIABLE,
-                    VerylSymbolKind::Module(_) => SymbolKind::MODULE,
-                    VerylSymbolKind::ProtoModule(_) => SymbolKind::MODULE,
-                    VerylSymbolKind::Interface(_) => SymbolKind::INTERFACE,
-                    VerylSymbolKind::Function(_) => SymbolKind::FUNCTION,
-                    VerylSymbolKind::Parameter(_) => SymbolKind::CONSTANT,
-                    VerylSymbolKind::Instance(_) => SymbolKind::OBJECT,
-                    VerylSymbolKind::Block => SymbolKind::NAMESPACE,
-                    VerylSymbolKind::Package(_) => SymbolKind::PACKAGE,
-                    VerylSymbolKind::Struct(_) => SymbolKind::STRUCT,
-                    VerylSymbolKind::StructMember(_) => SymbolKind::VARIABLE,
-                    VerylSymbolKind::Union(_) => SymbolKind::STRUCT,
-                    VerylSymbolKind::UnionMember(_) => SymbolKind::VARIABLE,
-                    VerylSymbolKind::Enum(_) => SymbolKind::ENUM,
-                    VerylSymbolKind::EnumMember(_) => SymbolKind::ENUM_MEMBER,
-                    VerylSymbolKind::EnumMemberMangled => SymbolKind::ENUM_MEMBER,
-                    VerylSymbolKind::Modport(_) => SymbolKind::INTERFACE,
-                    VerylSymbolKind::Genvar => SymbolKind::VARIABLE,
-                    VerylSymbolKind::TypeDef(_) => SymbolKind::TYPE_PARAMETER,
-                    VerylSymbolKind::ModportVariableMember(_) => SymbolKind::VARIABLE,
-                    VerylSymbolKind::ModportFunctionMember(_) => SymbolKind::FUNCTION,
-                    VerylSymbolKind::SystemVerilog => SymbolKind::NAMESPACE,
-                    VerylSymbolKind::Namespace => SymbolKind::NAMESPACE,
-                    VerylSymbolKind::SystemFunction => SymbolKind::FUNCTION,
-                    VerylSymbolKind::GenericParameter(_) => SymbolKind::TYPE_PARAMETER,
-                    VerylSymbolKind::GenericInstance(_) => SymbolKind::MODULE,
-                    VerylSymbolKind::ClockDomain => SymbolKind::TYPE_PARAMETER,
-                    VerylSymbolKind::Test(_) => SymbolKind::MODULE,
-                };
+                let kind = to_symbol_kind(&symbol.kind);
                 let location = to_location(&symbol.token);
                 #[allow(deprecated)]
                 let symbol_info = SymbolInformation {
@@ -437,73 +591,462 @@ impl Server {
             .unwrap();
     }
 
-    fn semantic_tokens(&mut self, url: &Url) {
+    fn goto_implementation(&mut self, url: &Url, line: usize, column: usize) {
+        let mut ret = None;
+        if let Some(symbol) = self.symbol_at(url, line, column) {
+            let proto = match &symbol.kind {
+                VerylSymbolKind::Module(x) => {
+                    // from an implementation to its prototype
+                    let proto = x.proto.as_ref().and_then(|x| {
+                        symbol_table::resolve((x, &symbol.namespace))
+                            .ok()
+                            .map(|x| x.found)
+                    });
+                    if let Some(proto) = proto {
+                        let location = to_location(&proto.token);
+                        ret = Some(GotoImplementationResponse::Array(vec![location]));
+                    }
+                    None
+                }
+                VerylSymbolKind::Instance(x) => {
+                    let path = SymbolPath::new(&x.type_name);
+                    symbol_table::resolve((&path, &symbol.namespace))
+                        .ok()
+                        .and_then(|x| bound_proto(&x.found))
+                }
+                _ => bound_proto(&symbol),
+            };
+            if let Some(proto) = proto {
+                let locations = implementations(&proto)
+                    .iter()
+                    .map(|x| to_location(&x.token))
+                    .collect();
+                ret = Some(GotoImplementationResponse::Array(locations));
+            }
+        }
+        self.snd
+            .send_blocking(MsgFromServer::GotoImplementation(ret))
+            .unwrap();
+    }
+
+    fn goto_type_definition(&mut self, url: &Url, line: usize, column: usize) {
         let mut ret = None;
+        if let Some(symbol) = self.symbol_at(url, line, column) {
+            let found = if let VerylSymbolKind::Instance(ref x) = symbol.kind {
+                instance_type(&symbol, x)
+            } else if let Some(TypeKind::UserDefined(path)) = symbol_type(&symbol).map(|x| x.kind) {
+                let path = SymbolPath::new(&path);
+                symbol_table::resolve((&path, &symbol.namespace))
+                    .ok()
+                    .map(|x| x.found)
+            } else {
+                None
+            };
+            if let Some(found) = found {
+                let location = to_location(&found.token);
+                ret = Some(GotoTypeDefinitionResponse::Scalar(location));
+            }
+        }
+        self.snd
+            .send_blocking(MsgFromServer::GotoTypeDefinition(ret))
+            .unwrap();
+    }
 
+    fn signature_help(&mut self, url: &Url, line: usize, column: usize) {
+        let mut ret = None;
         if let Ok(path) = url.to_file_path() {
-            if let Some(path) = resource_table::get_path_id(path) {
-                let mut tokens = Vec::new();
-                for symbol in &symbol_table::get_all() {
-                    if symbol.token.source == path {
-                        if let VerylSymbolKind::Port(_) = symbol.kind {
-                            let token_type = semantic_legend::PROPERTY;
-                            tokens.push((symbol.token, token_type));
-                            for reference in &symbol.references {
-                                if reference.source == path {
-                                    tokens.push((*reference, token_type));
-                                }
-                            }
-                        }
-                    }
+            if let Some(rope) = self.document_map.get(&path) {
+                let line_char = rope.try_line_to_char(line - 1).ok();
+                let text = line_char.and_then(|x| rope.get_slice(..x + column - 1));
+                let context = text.and_then(|x| signature_context(&x.to_string()));
+                let namespace = current_namespace(url, line, column);
+                if let (Some(context), Some(namespace)) = (context, namespace) {
+                    ret = to_signature_help(&context, &namespace);
+                }
+            }
+        }
+        self.snd
+            .send_blocking(MsgFromServer::SignatureHelp(ret))
+            .unwrap();
+    }
+
+    fn prepare_rename(&mut self, url: &Url, line: usize, column: usize) {
+        let ret = self
+            .rename_target(url, line, column)
+            .map(|(token, _)| PrepareRenameResponse::Range(to_location(&token).range));
+        self.snd
+            .send_blocking(MsgFromServer::PrepareRename(ret))
+            .unwrap();
+    }
+
+    fn rename(&mut self, url: &Url, line: usize, column: usize, new_name: &str) {
+        let ret = if let Some((_, symbol)) = self.rename_target(url, line, column) {
+            self.rename_edit(&symbol, new_name)
+        } else {
+            Err("this element can't be renamed".to_string())
+        };
+        self.snd.send_blocking(MsgFromServer::Rename(ret)).unwrap();
+    }
+
+    /// Token under the cursor and the symbol which it refers
+    fn rename_target(&self, url: &Url, line: usize, column: usize) -> Option<(Token, Symbol)> {
+        let path = url.to_file_path().ok()?;
+        let parser = self.parser_map.get(&path)?;
+        let mut finder = Finder::new();
+        finder.line = line as u32;
+        finder.column = column as u32;
+        finder.veryl(&parser.veryl);
+
+        let token = finder.token?;
+        let namespace = namespace_table::get(token.id)?;
+        let path = if finder.token_group.is_empty() {
+            SymbolPath::new(&[token.text])
+        } else {
+            // path until the token under the cursor
+            let group = &finder.token_group;
+            let end = group
+                .iter()
+                .position(|x| x.id == token.id)
+                .unwrap_or(group.len() - 1);
+            SymbolPath::from(&group[..=end])
+        };
+        // port name of instance connection refers the port of the instantiated module
+        let port = symbol_table::get_all().into_iter().find(|x| {
+            matches!(x.kind, VerylSymbolKind::Port(_))
+                && x.references.iter().any(|x| x.id == token.id)
+        });
+        let mut symbol = match port {
+            Some(x) => x,
+            None => symbol_table::resolve((&path, &namespace)).ok()?.found,
+        };
+
+        // generic instance is renamed through its base
+        if let VerylSymbolKind::GenericInstance(ref x) = symbol.kind {
+            symbol = symbol_table::get(x.base)?;
+        }
+
+        match symbol.kind {
+            VerylSymbolKind::SystemVerilog
+            | VerylSymbolKind::Namespace
+            | VerylSymbolKind::SystemFunction
+            | VerylSymbolKind::EnumMemberMangled
+            | VerylSymbolKind::GenericInstance(_) => return None,
+            _ => (),
+        }
+
+        if token.text != symbol.token.text || !self.is_project_token(&symbol.token) {
+            return None;
+        }
+
+        Some((token, symbol))
+    }
+
+    /// Whether the token is placed in a source file of the project, not in dependencies
+    fn is_project_token(&self, token: &Token) -> bool {
+        if let TokenSource::File(x) = token.source {
+            if let Some(path) = resource_table::get_path_value(x) {
+                return !path.starts_with(&self.cache_dir);
+            }
+        }
+        false
+    }
+
+    fn rename_edit(&self, symbol: &Symbol, new_name: &str) -> Result<WorkspaceEdit, String> {
+        if rename::rename_group(symbol)
+            .iter()
+            .any(|x| !self.is_project_token(&x.token))
+        {
+            return Err("symbols in dependencies can't be renamed".to_string());
+        }
+
+        let mut changes: HashMap<Url, Vec<TextEdit>> = HashMap::new();
+        for edit in rename::rename(symbol, new_name)? {
+            let location = to_location(&edit.token);
+            changes.entry(location.uri).or_default().push(TextEdit {
+                range: location.range,
+                new_text: edit.text,
+            });
+        }
+
+        Ok(WorkspaceEdit {
+            changes: Some(changes),
+            ..Default::default()
+        })
+    }
+
+    fn document_symbol(&mut self, url: &Url) {
+        let mut ret = None;
+        if let Ok(path) = url.to_file_path() {
+            if let Some(id) = resource_table::get_path_id(path.clone()) {
+                let mut symbols: Vec<_> = symbol_table::get_all()
+                    .into_iter()
+                    .filter(|x| x.token.source == id && is_document_symbol(&x.kind))
+                    .collect();
+                symbols.sort_by_key(|x| (x.token.line, x.token.column));
+
+                // symbols which are not placed in the inner namespace of other symbols
+                let roots: Vec<_> = symbols
+                    .iter()
+                    .filter(|x| {
+                        !symbols
+                            .iter()
+                            .any(|y| y.inner_namespace().matched(&x.namespace))
+                    })
+                    .collect();
+                let mut collector = SyntaxRangeCollector::new();
+                if let Some(parser) = self.parser_map.get(&path) {
+                    collector.veryl(&parser.veryl);
+                }
+                let tree = roots
+                    .into_iter()
+                    .map(|x| to_document_symbol(x, &symbols, &collector))
+                    .collect();
+                ret = Some(DocumentSymbolResponse::Nested(tree));
+            }
+        }
+        self.snd
+            .send_blocking(MsgFromServer::DocumentSymbol(ret))
+            .unwrap();
+    }
+
+    fn prepare_call_hierarchy(&mut self, url: &Url, line: usize, column: usize) {
+        let mut ret = None;
+        if let Some(symbol) = self.symbol_at(url, line, column) {
+            let symbol = match symbol.kind {
+                VerylSymbolKind::Instance(ref x) => instance_type(&symbol, x),
+                VerylSymbolKind::GenericInstance(ref x) => symbol_table::get(x.base),
+                _ => Some(symbol),
+            };
+            if let Some(symbol) = symbol.filter(|x| is_callable(&x.kind)) {
+                ret = Some(vec![to_call_hierarchy_item(&symbol)]);
+            }
+        }
+        self.snd
+            .send_blocking(MsgFromServer::PrepareCallHierarchy(ret))
+            .unwrap();
+    }
+
+    fn incoming_calls(&mut self, item: &CallHierarchyItem) {
+        let mut ret = None;
+        if let Some(symbol) = call_hierarchy_symbol(item) {
+            let mut calls: Vec<CallHierarchyIncomingCall> = Vec::new();
+            for edge in call_edges()
+                .into_iter()
+                .filter(|x| x.callee.id == symbol.id)
+            {
+                let range = to_location(&edge.token).range;
+                let from = to_call_hierarchy_item(&edge.caller);
+                if let Some(call) = calls.iter_mut().find(|x| x.from == from) {
+                    call.from_ranges.push(range);
+                } else {
+                    calls.push(CallHierarchyIncomingCall {
+                        from,
+                        from_ranges: vec![range],
+                    });
+                }
+            }
+            ret = Some(calls);
+        }
+        self.snd
+            .send_blocking(MsgFromServer::IncomingCalls(ret))
+            .unwrap();
+    }
+
+    fn outgoing_calls(&mut self, item: &CallHierarchyItem) {
+        let mut ret = None;
+        if let Some(symbol) = call_hierarchy_symbol(item) {
+            let mut calls: Vec<CallHierarchyOutgoingCall> = Vec::new();
+            for edge in call_edges()
+                .into_iter()
+                .filter(|x| x.caller.id == symbol.id)
+            {
+                let range = to_location(&edge.token).range;
+                let to = to_call_hierarchy_item(&edge.callee);
+                if let Some(call) = calls.iter_mut().find(|x| x.to == to) {
+                    call.from_ranges.push(range);
+                } else {
+                    calls.push(CallHierarchyOutgoingCall {
+                        to,
+                        from_ranges: vec![range],
+                    });
                 }
+            }
+            ret = Some(calls);
+        }
+        self.snd
+            .send_blocking(MsgFromServer::OutgoingCalls(ret))
+            .unwrap();
+    }
+
+    fn instance_tree(&mut self, top: &str) {
+        let top = symbol_table::get_all()
+            .into_iter()
+            .find(|x| matches!(x.kind, VerylSymbolKind::Module(_)) && x.token.to_string() == top);
+        let ret = top.map(|x| {
+            let edges: Vec<_> = call_edges().into_iter().filter(|x| x.instance).collect();
+            to_instance_tree(&x, &x.token, &edges, &mut Vec::new())
+        });
+        self.snd
+            .send_blocking(MsgFromServer::InstanceTree(ret))
+            .unwrap();
+    }
+
+    fn inlay_hint(&mut self, url: &Url, range: &Range) {
+        let mut ret = None;
+        if let Ok(path) = url.to_file_path() {
+            if let Some(parser) = self.parser_map.get(&path) {
+                let mut collector = InlayHintCollector::default();
+                collector.veryl(&parser.veryl);
+                let hints = collector
+                    .hints
+                    .into_iter()
+                    .filter(|x| range.start <= x.position && x.position <= range.end)
+                    .collect();
+                ret = Some(hints);
+            }
+        }
+        self.snd
+            .send_blocking(MsgFromServer::InlayHint(ret))
+            .unwrap();
+    }
+
+    fn folding_range(&mut self, url: &Url) {
+        let mut ret = None;
+        if let Ok(path) = url.to_file_path() {
+            if let Some(parser) = self.parser_map.get(&path) {
+                let mut collector = SyntaxRangeCollector::new();
+                collector.veryl(&parser.veryl);
+                ret = Some(collector.folding_ranges);
+            }
+        }
+        self.snd
+            .send_blocking(MsgFromServer::FoldingRange(ret))
+            .unwrap();
+    }
+
+    fn selection_range(&mut self, url: &Url, positions: &[Position]) {
+        let mut ret = None;
+        if let Ok(path) = url.to_file_path() {
+            if let Some(parser) = self.parser_map.get(&path) {
+                let mut collector = SyntaxRangeCollector::new();
+                collector.veryl(&parser.veryl);
+                let ranges = positions
+                    .iter()
+                    .map(|x| collector.selection_range(*x))
+                    .collect();
+                ret = Some(ranges);
+            }
+        }
+        self.snd
+            .send_blocking(MsgFromServer::SelectionRange(ret))
+            .unwrap();
+    }
 
-                tokens.sort_by(|a, b| {
-                    a.0.line
-                        .partial_cmp(&b.0.line)
-                        .unwrap()
-                        .then(a.0.column.partial_cmp(&b.0.column).unwrap())
-                });
-
-                let mut line = 0;
-                let mut column = 0;
-                let mut data = Vec::new();
-                for (token, token_type) in tokens {
-                    let token_line = token.line - 1;
-                    let token_column = token.column - 1;
-
-                    let delta_line = token_line - line;
-                    let delta_start = if delta_line == 0 {
-                        token_column - column
+    fn document_highlight(&mut self, url: &Url, line: usize, column: usize) {
+        let mut ret = None;
+        let path = url.to_file_path().ok();
+        let path = path.and_then(resource_table::get_path_id);
+        if let (Some(symbol), Some(path)) = (self.symbol_at(url, line, column), path) {
+            let mut tokens = vec![symbol.token];
+            tokens.extend(symbol.references.iter());
+            tokens.retain(|x| x.source == path);
+            tokens.sort_by_key(|x| x.pos);
+            tokens.dedup_by_key(|x| x.pos);
+
+            let writes = written_tokens(&symbol, &tokens);
+            let highlights = tokens
+                .iter()
+                .map(|x| {
+                    let kind = if writes.contains(&x.pos) {
+                        DocumentHighlightKind::WRITE
+                    } else if x.pos == symbol.token.pos {
+                        DocumentHighlightKind::TEXT
                     } else {
-                        token_column
+                        DocumentHighlightKind::READ
                     };
+                    DocumentHighlight {
+                        range: to_location(x).range,
+                        kind: Some(kind),
+                    }
+                })
+                .collect();
+            ret = Some(highlights);
+        }
+        self.snd
+            .send_blocking(MsgFromServer::DocumentHighlight(ret))
+            .unwrap();
+    }
 
-                    let semantic_token = SemanticToken {
-                        delta_line,
-                        delta_start,
-                        length: token.length,
-                        token_type,
-                        token_modifiers_bitset: 0,
-                    };
-                    data.push(semantic_token);
+    fn semantic_tokens(&mut self, url: &Url) {
+        let ret = self
+            .update_semantic_tokens(url)
+            .map(SemanticTokensResult::Tokens);
+        self.snd
+            .send_blocking(MsgFromServer::SemanticTokens(ret))
+            .unwrap();
+    }
 
-                    line = token_line;
-                    column = token_column;
-                }
+    fn semantic_tokens_delta(&mut self, url: &Url, previous_result_id: &str) {
+        let previous = url
+            .to_file_path()
+            .ok()
+            .and_then(|x| self.semantic_tokens_map.get(&x).map(|x| x.clone()))
+            .filter(|x| x.result_id.as_deref() == Some(previous_result_id));
+
+        let ret = self.update_semantic_tokens(url).map(|tokens| {
+            if let Some(previous) = previous {
+                let delta = SemanticTokensDelta {
+                    result_id: tokens.result_id,
+                    edits: semantic_tokens_edits(&previous.data, &tokens.data),
+                };
+                SemanticTokensFullDeltaResult::TokensDelta(delta)
+            } else {
+                SemanticTokensFullDeltaResult::Tokens(tokens)
+            }
+        });
+        self.snd
+            .send_blocking(MsgFromServer::SemanticTokensDelta(ret))
+            .unwrap();
+    }
 
+    fn semantic_tokens_range(&mut self, url: &Url, range: &Range) {
+        let mut ret = None;
+        if let Ok(path) = url.to_file_path() {
+            if let Some(path) = resource_table::get_path_id(path) {
+                let tokens: Vec<_> = semantic_token_list(path)
+                    .into_iter()
+                    .filter(|(x, _)| {
+                        let position = to_location(x).range.start;
+                        range.start <= position && position < range.end
+                    })
+                    .collect();
                 let tokens = SemanticTokens {
                     result_id: None,
-                    data,
+                    data: encode_semantic_tokens(&tokens),
                 };
-                ret = Some(SemanticTokensResult::Tokens(tokens))
+                ret = Some(SemanticTokensRangeResult::Tokens(tokens));
             }
         }
-
         self.snd
-            .send_blocking(MsgFromServer::SemanticTokens(ret))
+            .send_blocking(MsgFromServer::SemanticTokensRange(ret))
             .unwrap();
     }
 
+    /// Compute semantic tokens of the whole file and keep it as base of the next delta request
+    fn update_semantic_tokens(&mut self, url: &Url) -> Option<SemanticTokens> {
+        let file = url.to_file_path().ok()?;
+        let path = resource_table::get_path_id(file.clone())?;
+        let tokens = semantic_token_list(path);
+
+        self.semantic_tokens_id += 1;
+        let tokens = SemanticTokens {
+            result_id: Some(self.semantic_tokens_id.to_string()),
+            data: encode_semantic_tokens(&tokens),
+        };
+        self.semantic_tokens_map.insert(file, tokens.clone());
+        Some(tokens)
+    }
+
     fn formatting(&mut self, url: &Url) {
         if let Ok(path) = url.to_file_path() {
             if let Some(metadata) = self.get_metadata(url) {
@@ -531,27 +1074,270 @@ impl Server {
             .send_blocking(MsgFromServer::Formatting(None))
             .unwrap();
     }
-}
-
-impl Server {
-    fn progress_start(&mut self, msg: &str) {
-        self.lsp_token += 1;
-        let token = NumberOrString::Number(self.lsp_token);
-        let begin = WorkDoneProgressBegin {
-            title: msg.to_string(),
-            cancellable: Some(false),
-            message: None,
-            percentage: Some(100),
-        };
 
-        block_on(self.client.send_request::<request::WorkDoneProgressCreate>(
-            WorkDoneProgressCreateParams {
-                token: token.clone(),
-            },
-        ))
-        .unwrap();
+    fn range_formatting(&mut self, url: &Url, range: &Range) {
+        let ret = self.format_range(url, range);
+        self.snd
+            .send_blocking(MsgFromServer::RangeFormatting(ret))
+            .unwrap();
+    }
 
-        block_on(
+    fn on_type_formatting(&mut self, url: &Url, position: &Position, ch: &str) {
+        let mut ret = None;
+        if let Some(rope) = url.to_file_path().ok().and_then(|x| self.get_rope(&x)) {
+            let line = position.line as usize;
+            let start = match ch {
+                // format the whole block closed by the brace
+                "}" => {
+                    // the position is placed after the typed brace
+                    let column = (position.character as usize).saturating_sub(1);
+                    let char_idx = rope.try_line_to_char(line).ok();
+                    let text = char_idx.and_then(|x| rope.get_slice(..x + column));
+                    let open = text.and_then(|x| matching_brace(&x.to_string()));
+                    open.and_then(|x| rope.try_byte_to_line(x).ok())
+                        .unwrap_or(line)
+                }
+                // format the line just finished
+                "\n" => line.saturating_sub(1),
+                _ => line,
+            };
+            let range = Range::new(
+                Position::new(start as u32, 0),
+                Position::new(line as u32, u32::MAX),
+            );
+            ret = self.format_range(url, &range);
+        }
+        self.snd
+            .send_blocking(MsgFromServer::OnTypeFormatting(ret))
+            .unwrap();
+    }
+
+    /// Format the whole document, and extract changes of lines overlapped with the range
+    fn format_range(&mut self, url: &Url, range: &Range) -> Option<Vec<TextEdit>> {
+        let path = url.to_file_path().ok()?;
+        let metadata = self.get_metadata(url)?;
+        let rope = self.get_rope(&path)?;
+        let parser = self.parser_map.get(&path)?;
+
+        let mut formatter = Formatter::new(&metadata);
+        formatter.format(&parser.veryl);
+
+        let old = rope.to_string();
+        let new = formatter.as_str();
+        let diff = TextDiff::from_lines(old.as_str(), new);
+        let new_lines: Vec<_> = diff
+            .iter_all_changes()
+            .filter(|x| x.tag() != ChangeTag::Delete)
+            .map(|x| x.value())
+            .collect();
+
+        let first = range.start.line as usize;
+        let last = range.end.line as usize;
+        let mut ret = Vec::new();
+        for op in diff.ops() {
+            let (tag, old_range, new_range) = op.as_tag_tuple();
+            // replacement of the same number of lines is split into each line
+            let hunks: Vec<_> = match tag {
+                DiffTag::Equal => continue,
+                DiffTag::Replace if old_range.len() == new_range.len() => old_range
+                    .zip(new_range)
+                    .map(|(x, y)| (x..x + 1, y..y + 1))
+                    .collect(),
+                _ => vec![(old_range, new_range)],
+            };
+            for (old_range, new_range) in hunks {
+                // insertion has empty old range, so it is checked by its position
+                let included = if old_range.is_empty() {
+                    first <= old_range.start && old_range.start <= last + 1
+                } else {
+                    old_range.start <= last && first < old_range.end
+                };
+                if included {
+                    let new_text = new_lines[new_range].concat();
+                    ret.push(TextEdit {
+                        range: Range::new(
+                            Position::new(old_range.start as u32, 0),
+                            Position::new(old_range.end as u32, 0),
+                        ),
+                        new_text,
+                    });
+                }
+            }
+        }
+        Some(ret)
+    }
+
+    fn code_action(&mut self, url: &Url, range: &Range) {
+        let mut ret = Vec::new();
+        if let Ok(path) = url.to_file_path() {
+            if let Some(fixes) = self.fix_map.get(&path) {
+                for (diag, fix) in fixes.iter() {
+                    if diag.range.start > range.end || range.start > diag.range.end {
+                        continue;
+                    }
+
+                    let Ok(edits) = fix.text_edits() else {
+                        continue;
+                    };
+                    let mut changes: HashMap<Url, Vec<TextEdit>> = HashMap::new();
+                    for edit in edits {
+                        let file = PathBuf::from(edit.path.to_string());
+                        let Some(rope) = self.get_rope(&file) else {
+                            continue;
+                        };
+                        let Ok(uri) = Url::from_file_path(&file) else {
+                            continue;
+                        };
+                        let range = Range::new(
+                            to_position(&rope, edit.beg as usize),
+                            to_position(&rope, edit.end as usize),
+                        );
+                        changes.entry(uri).or_default().push(TextEdit {
+                            range,
+                            new_text: edit.text,
+                        });
+                    }
+
+                    let action = CodeAction {
+                        title: fix.title.clone(),
+                        kind: Some(CodeActionKind::QUICKFIX),
+                        diagnostics: Some(vec![diag.clone()]),
+                        is_preferred: Some(fix.preferred),
+                        edit: Some(WorkspaceEdit {
+                            changes: Some(changes),
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    };
+                    ret.push(CodeActionOrCommand::CodeAction(action));
+                }
+            }
+        }
+
+        self.snd
+            .send_blocking(MsgFromServer::CodeAction(Some(ret)))
+            .unwrap();
+    }
+
+    fn code_lens(&mut self, url: &Url) {
+        let mut ret = Vec::new();
+        if let Ok(path) = url.to_file_path() {
+            if let Some(path) = resource_table::get_path_id(path) {
+                for symbol in symbol_table::get_all() {
+                    if !matches!(symbol.kind, VerylSymbolKind::Test(_))
+                        || symbol.token.source != TokenSource::File(path)
+                    {
+                        continue;
+                    }
+
+                    let range = syntax_range::token_range(&symbol.token);
+                    let arguments = vec![json!(url), json!(symbol.token.to_string())];
+                    for (title, command) in [
+                        ("Run test", test_runner::RUN_TEST),
+                        ("Run with waveform", test_runner::RUN_TEST_WITH_WAVE),
+                    ] {
+                        ret.push(CodeLens {
+                            range,
+                            command: Some(Command {
+                                title: title.to_string(),
+                                command: command.to_string(),
+                                arguments: Some(arguments.clone()),
+                            }),
+                            data: None,
+                        });
+                    }
+                }
+            }
+        }
+        ret.sort_by_key(|x| x.range.start);
+
+        self.snd
+            .send_blocking(MsgFromServer::CodeLens(Some(ret)))
+            .unwrap();
+    }
+
+    fn execute_command(&mut self, command: &str, arguments: &[Value]) {
+        // reply before running because the client should not wait simulation
+        self.snd
+            .send_blocking(MsgFromServer::ExecuteCommand(None))
+            .unwrap();
+
+        let wave = match command {
+            test_runner::RUN_TEST => false,
+            test_runner::RUN_TEST_WITH_WAVE => true,
+            _ => return,
+        };
+        let url = arguments
+            .first()
+            .and_then(|x| x.as_str())
+            .and_then(|x| Url::parse(x).ok());
+        let test = arguments.get(1).and_then(|x| x.as_str());
+        if let (Some(url), Some(test)) = (url, test) {
+            self.run_test(&url, test, wave);
+        }
+    }
+
+    fn run_test(&mut self, url: &Url, test: &str, wave: bool) {
+        let Some(mut metadata) = self.get_metadata(url) else {
+            return;
+        };
+
+        let mut sources = Vec::new();
+        match metadata.paths::<&str>(&[], true) {
+            Ok(paths) => {
+                for path in paths {
+                    // unsaved changes in the editor are also tested
+                    let text = if let Some(rope) = self.document_map.get(&path.src) {
+                        Some(rope.to_string())
+                    } else {
+                        std::fs::read_to_string(&path.src).ok()
+                    };
+                    if let Some(text) = text {
+                        sources.push((path, text));
+                    }
+                }
+            }
+            Err(x) => {
+                let msg = format!("Failed test ({test}): {x}");
+                block_on(self.client.show_message(MessageType::ERROR, msg));
+                return;
+            }
+        }
+
+        self.progress_start(&format!("test ({test})"));
+
+        let task = test_runner::TestTask {
+            client: self.client.clone(),
+            token: NumberOrString::Number(self.lsp_token),
+            metadata,
+            sources,
+            test: test.to_string(),
+            wave,
+            diag_map: self.diag_map.clone(),
+        };
+        task.spawn();
+    }
+}
+
+impl Server {
+    fn progress_start(&mut self, msg: &str) {
+        self.lsp_token += 1;
+        let token = NumberOrString::Number(self.lsp_token);
+        let begin = WorkDoneProgressBegin {
+            title: msg.to_string(),
+            cancellable: Some(false),
+            message: None,
+            percentage: Some(100),
+        };
+
+        block_on(self.client.send_request::<request::WorkDoneProgressCreate>(
+            WorkDoneProgressCreateParams {
+                token: token.clone(),
+            },
+        ))
+        .unwrap();
+
+        block_on(
             self.client
                 .send_notification::<notification::Progress>(ProgressParams {
                     token,
@@ -602,6 +1388,7 @@ impl Server {
                 if let Some(src) = resource_table::get_path_id(&src) {
                     symbol_table::drop(src);
                     namespace_table::drop(src);
+                    function_table::drop(src);
                 }
                 let analyzer = Analyzer::new(metadata);
                 let _ = analyzer.analyze_pass1(&path.prj, &text, &src, &x.veryl);
@@ -614,6 +1401,35 @@ impl Server {
         }
     }
 
+    fn symbol_at(&self, url: &Url, line: usize, column: usize) -> Option<Symbol> {
+        let path = url.to_file_path().ok()?;
+        let parser = self.parser_map.get(&path)?;
+        let mut finder = Finder::new();
+        finder.line = line as u32;
+        finder.column = column as u32;
+        finder.veryl(&parser.veryl);
+        let token = finder.token?;
+        let namespace = namespace_table::get(token.id)?;
+        let path = if finder.token_group.is_empty() {
+            SymbolPath::new(&[token.text])
+        } else {
+            SymbolPath::from(finder.token_group.as_slice())
+        };
+        symbol_table::resolve((&path, &namespace))
+            .ok()
+            .map(|x| x.found)
+    }
+
+    fn get_rope(&self, path: &Path) -> Option<Rope> {
+        if let Some(rope) = self.document_map.get(path) {
+            Some(rope.clone())
+        } else {
+            std::fs::read_to_string(path)
+                .ok()
+                .map(|x| Rope::from_str(&x))
+        }
+    }
+
     fn get_metadata(&mut self, url: &Url) -> Option<Metadata> {
         if let Ok(path) = url.to_file_path() {
             if let Some(metadata) = self.metadata_map.get(&path) {
@@ -644,11 +1460,13 @@ impl Server {
                         {
                             symbol_table::drop(path);
                             namespace_table::drop(path);
+                            function_table::drop(path);
                         }
                         let analyzer = Analyzer::new(&metadata);
                         let mut errors = analyzer.analyze_pass1(prj, text, &path, &x.veryl);
                         errors.append(&mut analyzer.analyze_pass2(prj, text, &path, &x.veryl));
                         errors.append(&mut analyzer.analyze_pass3(prj, text, &path, &x.veryl));
+                        let mut fixes = Vec::new();
                         let ret: Vec<_> = errors
                             .drain(0..)
                             .filter(|x| {
@@ -664,20 +1482,29 @@ impl Server {
                                     )
                                 }
                             })
-                            .map(|x| {
-                                let x: miette::ErrReport = x.into();
-                                to_diag(x, &rope)
+                            .map(|err| {
+                                let mut x_fixes = err.fixes().to_vec();
+                                x_fixes.extend(fix::allow_attribute(&err, text, &x.veryl));
+                                let x: miette::ErrReport = err.into();
+                                let diag = to_diag(x, &rope);
+                                for fix in x_fixes {
+                                    fixes.push((diag.clone(), fix));
+                                }
+                                diag
                             })
                             .collect();
                         self.parser_map.insert(path.clone(), x);
+                        self.fix_map.insert(path.clone(), fixes);
                         ret
                     }
                     Err(x) => {
                         self.parser_map.remove(&path);
+                        self.fix_map.remove(&path);
                         vec![to_diag(x.into(), &rope)]
                     }
                 };
 
+                self.diag_map.insert(path.clone(), diag.clone());
                 block_on(
                     self.client
                         .publish_diagnostics(url.clone(), diag, Some(version)),
@@ -754,6 +1581,408 @@ fn to_diag(err: miette::ErrReport, rope: &Rope) -> Diagnostic {
     )
 }
 
+fn to_position(rope: &Rope, byte: usize) -> Position {
+    let byte = byte.min(rope.len_bytes());
+    let line = rope.byte_to_line(byte);
+    let pos = byte - rope.line_to_byte(line);
+    Position::new(line as u32, pos as u32)
+}
+
+fn to_symbol_kind(kind: &VerylSymbolKind) -> SymbolKind {
+    match kind {
+        VerylSymbolKind::Port(_) => SymbolKind::VARIABLE,
+        VerylSymbolKind::Variable(_) => SymbolKind::VARIABLE,
+        VerylSymbolKind::Module(_) => SymbolKind::MODULE,
+        VerylSymbolKind::ProtoModule(_) => SymbolKind::MODULE,
+        VerylSymbolKind::Interface(_) => SymbolKind::INTERFACE,
+        VerylSymbolKind::Function(_) => SymbolKind::FUNCTION,
+        VerylSymbolKind::Parameter(_) => SymbolKind::CONSTANT,
+        VerylSymbolKind::Instance(_) => SymbolKind::OBJECT,
+        VerylSymbolKind::Block => SymbolKind::NAMESPACE,
+        VerylSymbolKind::Package(_) => SymbolKind::PACKAGE,
+        VerylSymbolKind::Struct(_) => SymbolKind::STRUCT,
+        VerylSymbolKind::StructMember(_) => SymbolKind::FIELD,
+        VerylSymbolKind::Union(_) => SymbolKind::STRUCT,
+        VerylSymbolKind::UnionMember(_) => SymbolKind::FIELD,
+        VerylSymbolKind::Enum(_) => SymbolKind::ENUM,
+        VerylSymbolKind::EnumMember(_) => SymbolKind::ENUM_MEMBER,
+        VerylSymbolKind::EnumMemberMangled => SymbolKind::ENUM_MEMBER,
+        VerylSymbolKind::Modport(_) => SymbolKind::INTERFACE,
+        VerylSymbolKind::Genvar => SymbolKind::VARIABLE,
+        VerylSymbolKind::TypeDef(_) => SymbolKind::TYPE_PARAMETER,
+        VerylSymbolKind::ModportVariableMember(_) => SymbolKind::VARIABLE,
+        VerylSymbolKind::ModportFunctionMember(_) => SymbolKind::FUNCTION,
+        VerylSymbolKind::SystemVerilog => SymbolKind::NAMESPACE,
+        VerylSymbolKind::Namespace => SymbolKind::NAMESPACE,
+        VerylSymbolKind::SystemFunction => SymbolKind::FUNCTION,
+        VerylSymbolKind::GenericParameter(_) => SymbolKind::TYPE_PARAMETER,
+        VerylSymbolKind::GenericInstance(_) => SymbolKind::MODULE,
+        VerylSymbolKind::ClockDomain => SymbolKind::TYPE_PARAMETER,
+        VerylSymbolKind::Test(_) => SymbolKind::MODULE,
+    }
+}
+
+fn is_document_symbol(kind: &VerylSymbolKind) -> bool {
+    !matches!(
+        kind,
+        VerylSymbolKind::EnumMemberMangled
+            | VerylSymbolKind::SystemVerilog
+            | VerylSymbolKind::Namespace
+            | VerylSymbolKind::SystemFunction
+            | VerylSymbolKind::GenericInstance(_)
+            | VerylSymbolKind::ClockDomain
+    )
+}
+
+fn to_document_symbol(
+    symbol: &Symbol,
+    symbols: &[Symbol],
+    collector: &SyntaxRangeCollector,
+) -> DocumentSymbol {
+    let namespace = symbol.inner_namespace();
+    let children: Vec<_> = symbols
+        .iter()
+        .filter(|x| x.namespace.matched(&namespace))
+        .map(|x| to_document_symbol(x, symbols, collector))
+        .collect();
+
+    // the range includes the whole declaration and all children
+    let selection_range = to_location(&symbol.token).range;
+    let mut range = collector
+        .declaration_range(selection_range)
+        .unwrap_or(selection_range);
+    for x in &children {
+        range.start = range.start.min(x.range.start);
+        range.end = range.end.max(x.range.end);
+    }
+
+    #[allow(deprecated)]
+    DocumentSymbol {
+        name: symbol.token.to_string(),
+        detail: Some(symbol.kind.to_kind_name()),
+        kind: to_symbol_kind(&symbol.kind),
+        tags: None,
+        deprecated: None,
+        range,
+        selection_range,
+        children: if children.is_empty() {
+            None
+        } else {
+            Some(children)
+        },
+    }
+}
+
+/// Positions of the tokens which are assigned in the assign list
+fn written_tokens(symbol: &Symbol, tokens: &[Token]) -> Vec<u32> {
+    let mut ret = Vec::new();
+    for assign in symbol_table::get_assign_list() {
+        if !assign.path.0.contains(&symbol.id) {
+            continue;
+        }
+        let Some(position) = assign.position.0.last() else {
+            continue;
+        };
+        let marker = position.token();
+        let sources: Vec<_> = tokens
+            .iter()
+            .filter(|x| x.source == marker.source)
+            .collect();
+
+        // the assignment operator follows the assigned identifier,
+        // but `let`, `assign`, `for` and port connection precede it
+        let before =
+            matches!(position, AssignPositionType::Statement { .. }) && marker.to_string() != "for";
+        let found = if before {
+            sources.iter().rev().find(|x| x.pos < marker.pos)
+        } else {
+            sources.iter().find(|x| x.pos >= marker.pos)
+        };
+        if let Some(x) = found {
+            ret.push(x.pos);
+        }
+    }
+    ret
+}
+
+/// The module prototype which bounds the generic parameter
+fn bound_proto(symbol: &Symbol) -> Option<Symbol> {
+    match &symbol.kind {
+        VerylSymbolKind::ProtoModule(_) => Some(symbol.clone()),
+        VerylSymbolKind::GenericParameter(x) => {
+            if let GenericBoundKind::Proto(ref x) = x.bound {
+                let found = symbol_table::resolve((x, &symbol.namespace)).ok()?.found;
+                matches!(found.kind, VerylSymbolKind::ProtoModule(_)).then_some(found)
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+fn implementations(proto: &Symbol) -> Vec<Symbol> {
+    let mut ret: Vec<_> = symbol_table::get_all()
+        .into_iter()
+        .filter(|symbol| {
+            if let VerylSymbolKind::Module(ref x) = symbol.kind {
+                x.proto.as_ref().is_some_and(|x| {
+                    symbol_table::resolve((x, &symbol.namespace))
+                        .is_ok_and(|x| x.found.id == proto.id)
+                })
+            } else {
+                false
+            }
+        })
+        .collect();
+    ret.sort_by_key(|x| (x.token.source.to_string(), x.token.line, x.token.column));
+    ret
+}
+
+fn symbol_type(symbol: &Symbol) -> Option<Type> {
+    match &symbol.kind {
+        VerylSymbolKind::Variable(x) => Some(x.r#type.clone()),
+        VerylSymbolKind::Port(x) => x.r#type.clone(),
+        VerylSymbolKind::Parameter(x) => Some(x.r#type.clone()),
+        VerylSymbolKind::StructMember(x) => Some(x.r#type.clone()),
+        VerylSymbolKind::UnionMember(x) => Some(x.r#type.clone()),
+        VerylSymbolKind::TypeDef(x) => Some(x.r#type.clone()),
+        VerylSymbolKind::Function(x) => x.ret.clone(),
+        _ => symbol.r#type.clone(),
+    }
+}
+
+fn is_callable(kind: &VerylSymbolKind) -> bool {
+    matches!(
+        kind,
+        VerylSymbolKind::Module(_) | VerylSymbolKind::Interface(_) | VerylSymbolKind::Function(_)
+    )
+}
+
+fn to_call_hierarchy_item(symbol: &Symbol) -> CallHierarchyItem {
+    let location = to_location(&symbol.token);
+    let range = match &symbol.kind {
+        VerylSymbolKind::Module(x) => Some(x.range),
+        VerylSymbolKind::Interface(x) => Some(x.range),
+        VerylSymbolKind::Function(x) => Some(x.range),
+        _ => None,
+    };
+    let range = if let Some(x) = range {
+        Range::new(
+            to_location(&x.beg).range.start,
+            to_location(&x.end).range.end,
+        )
+    } else {
+        location.range
+    };
+
+    CallHierarchyItem {
+        name: symbol.token.to_string(),
+        kind: to_symbol_kind(&symbol.kind),
+        tags: None,
+        detail: Some(symbol.kind.to_kind_name()),
+        uri: location.uri,
+        range,
+        selection_range: location.range,
+        data: None,
+    }
+}
+
+fn call_hierarchy_symbol(item: &CallHierarchyItem) -> Option<Symbol> {
+    let path = resource_table::get_path_id(item.uri.to_file_path().ok()?)?;
+    symbol_table::get_all().into_iter().find(|x| {
+        x.token.source == path
+            && is_callable(&x.kind)
+            && to_location(&x.token).range == item.selection_range
+    })
+}
+
+fn instance_type(symbol: &Symbol, property: &InstanceProperty) -> Option<Symbol> {
+    let path = SymbolPath::new(&property.type_name);
+    let found = symbol_table::resolve((&path, &symbol.namespace))
+        .ok()?
+        .found;
+    if let VerylSymbolKind::GenericInstance(ref x) = found.kind {
+        symbol_table::get(x.base)
+    } else {
+        Some(found)
+    }
+}
+
+/// A call from `caller` to `callee` placed at `token`
+struct CallEdge {
+    caller: Symbol,
+    callee: Symbol,
+    token: Token,
+    instance: bool,
+}
+
+fn call_edges() -> Vec<CallEdge> {
+    let symbols = symbol_table::get_all();
+    let callables: Vec<_> = symbols.iter().filter(|x| is_callable(&x.kind)).collect();
+
+    // the innermost callable which contains the namespace
+    let enclosing = |namespace: &Namespace| {
+        callables
+            .iter()
+            .filter(|x| namespace.included(&x.inner_namespace()))
+            .max_by_key(|x| x.inner_namespace().depth())
+            .map(|x| (*x).clone())
+    };
+
+    let mut ret = Vec::new();
+    for symbol in &symbols {
+        match &symbol.kind {
+            VerylSymbolKind::Instance(x) => {
+                let caller = enclosing(&symbol.namespace);
+                let callee = instance_type(symbol, x).filter(|x| is_callable(&x.kind));
+                if let (Some(caller), Some(callee)) = (caller, callee) {
+                    ret.push(CallEdge {
+                        caller,
+                        callee,
+                        token: symbol.token,
+                        instance: true,
+                    });
+                }
+            }
+            VerylSymbolKind::Function(_) => {
+                for reference in &symbol.references {
+                    let caller = namespace_table::get(reference.id).and_then(|x| enclosing(&x));
+                    if let Some(caller) = caller {
+                        ret.push(CallEdge {
+                            caller,
+                            callee: symbol.clone(),
+                            token: *reference,
+                            instance: false,
+                        });
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+    ret
+}
+
+fn to_instance_tree(
+    symbol: &Symbol,
+    name: &Token,
+    edges: &[CallEdge],
+    stack: &mut Vec<SymbolId>,
+) -> Value {
+    let mut children = Vec::new();
+
+    // stop expansion of recursive instantiation
+    if !stack.contains(&symbol.id) {
+        stack.push(symbol.id);
+        for edge in edges.iter().filter(|x| x.caller.id == symbol.id) {
+            children.push(to_instance_tree(&edge.callee, &edge.token, edges, stack));
+        }
+        stack.pop();
+    }
+
+    json!({
+        "name": name.to_string(),
+        "module": symbol.token.to_string(),
+        "kind": symbol.kind.to_kind_name(),
+        "location": to_location(name),
+        "children": children,
+    })
+}
+
+fn semantic_token_list(path: PathId) -> Vec<(Token, u32)> {
+    let mut tokens = Vec::new();
+    for symbol in &symbol_table::get_all() {
+        if symbol.token.source == path {
+            if let VerylSymbolKind::Port(_) = symbol.kind {
+                let token_type = semantic_legend::PROPERTY;
+                tokens.push((symbol.token, token_type));
+                for reference in &symbol.references {
+                    if reference.source == path {
+                        tokens.push((*reference, token_type));
+                    }
+                }
+            }
+        }
+    }
+
+    tokens.sort_by(|a, b| {
+        a.0.line
+            .partial_cmp(&b.0.line)
+            .unwrap()
+            .then(a.0.column.partial_cmp(&b.0.column).unwrap())
+    });
+    tokens
+}
+
+fn encode_semantic_tokens(tokens: &[(Token, u32)]) -> Vec<SemanticToken> {
+    let mut line = 0;
+    let mut column = 0;
+    let mut data = Vec::new();
+    for (token, token_type) in tokens {
+        let token_line = token.line - 1;
+        let token_column = token.column - 1;
+
+        let delta_line = token_line - line;
+        let delta_start = if delta_line == 0 {
+            token_column - column
+        } else {
+            token_column
+        };
+
+        let semantic_token = SemanticToken {
+            delta_line,
+            delta_start,
+            length: token.length,
+            token_type: *token_type,
+            token_modifiers_bitset: 0,
+        };
+        data.push(semantic_token);
+
+        line = token_line;
+        column = token_column;
+    }
+    data
+}
+
+/// A single edit replacing the range between common prefix and suffix
+fn semantic_tokens_edits(old: &[SemanticToken], new: &[SemanticToken]) -> Vec<SemanticTokensEdit> {
+    let prefix = old.iter().zip(new).take_while(|(x, y)| x == y).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+
+    let delete_count = old.len() - prefix - suffix;
+    let data = &new[prefix..new.len() - suffix];
+    if delete_count == 0 && data.is_empty() {
+        vec![]
+    } else {
+        // each token is encoded as 5 integers
+        vec![SemanticTokensEdit {
+            start: prefix as u32 * 5,
+            delete_count: delete_count as u32 * 5,
+            data: Some(data.to_vec()),
+        }]
+    }
+}
+
+/// Byte position of the unclosed open brace
+fn matching_brace(text: &str) -> Option<usize> {
+    let mut depth = 0;
+    for (i, x) in text.char_indices().rev() {
+        match x {
+            '}' => depth += 1,
+            '{' if depth == 0 => return Some(i),
+            '{' => depth -= 1,
+            _ => (),
+        }
+    }
+    None
+}
+
 fn to_location(token: &Token) -> Location {
     let line = token.line - 1;
     let column = token.column - 1;
@@ -1020,6 +2249,508 @@ fn completion_symbol(
     items
 }
 
+#[derive(Debug, PartialEq, Eq)]
+enum SignatureTarget {
+    Function,
+    Ports,
+    Parameters,
+    Generics,
+}
+
+#[derive(Debug)]
+struct SignatureContext {
+    target: SignatureTarget,
+    path: Vec<String>,
+    /// Position of the argument under the cursor
+    index: usize,
+    /// Name of the argument under the cursor like `name: expression`
+    name: Option<String>,
+}
+
+fn is_identifier_char(x: u8) -> bool {
+    x.is_ascii_alphanumeric() || x == b'_' || x == b'$'
+}
+
+/// Identifier path like `PackageA::FuncA` or `u_if.func` at the end of the text
+fn trailing_path(text: &str) -> Option<(Vec<String>, usize)> {
+    let bytes = text.trim_end().as_bytes();
+    let mut ret = Vec::new();
+    let mut end = bytes.len();
+    loop {
+        // skip generic arguments like `::<A, B>`
+        if end > 0 && bytes[end - 1] == b'>' {
+            let mut depth = 0;
+            while end > 0 {
+                end -= 1;
+                match bytes[end] {
+                    b'>' => depth += 1,
+                    b'<' => depth -= 1,
+                    _ => (),
+                }
+                if depth == 0 {
+                    break;
+                }
+            }
+            if end < 2 || &bytes[end - 2..end] != b"::" {
+                return None;
+            }
+            end -= 2;
+        }
+
+        let mut beg = end;
+        while beg > 0 && is_identifier_char(bytes[beg - 1]) {
+            beg -= 1;
+        }
+        if beg == end {
+            return None;
+        }
+        ret.push(text[beg..end].to_string());
+
+        if beg >= 2 && &bytes[beg - 2..beg] == b"::" {
+            end = beg - 2;
+        } else if beg >= 1 && bytes[beg - 1] == b'.' {
+            end = beg - 1;
+        } else {
+            ret.reverse();
+            return Some((ret, beg));
+        }
+    }
+}
+
+/// Whether the text ends with `inst name:`
+fn is_inst_type(text: &str) -> bool {
+    let Some(text) = text.trim_end().strip_suffix(':') else {
+        return false;
+    };
+    let text = text.trim_end();
+    let text = text.trim_end_matches(|x: char| x.is_ascii() && is_identifier_char(x as u8));
+    text.trim_end().ends_with("inst")
+}
+
+/// Find the call or instantiation which encloses the end of the text
+fn signature_context(text: &str) -> Option<SignatureContext> {
+    let bytes = text.as_bytes();
+    let mut depth = 0;
+    let mut angle = 0;
+    let mut index = 0;
+    let mut arg_beg = bytes.len();
+    let mut i = bytes.len();
+    while i > 0 {
+        i -= 1;
+        match bytes[i] {
+            b')' | b']' | b'}' => depth += 1,
+            b'(' | b'[' | b'{' if depth > 0 => depth -= 1,
+            b'[' | b'{' | b';' => return None,
+            b'>' if depth == 0 => {
+                // `->`, `>=` and `>>` are operators
+                let is_operator = (i > 0 && matches!(bytes[i - 1], b'-' | b'>'))
+                    || matches!(bytes.get(i + 1), Some(b'=') | Some(b'>'));
+                if !is_operator {
+                    angle += 1;
+                }
+            }
+            b'<' if depth == 0 && i >= 2 && &bytes[i - 2..i] == b"::" => {
+                if angle > 0 {
+                    angle -= 1;
+                } else {
+                    let (path, _) = trailing_path(&text[..i - 2])?;
+                    return Some(SignatureContext::new(
+                        SignatureTarget::Generics,
+                        path,
+                        index,
+                        &text[arg_beg..],
+                    ));
+                }
+            }
+            b',' if depth == 0 && angle == 0 => {
+                if index == 0 {
+                    arg_beg = i + 1;
+                }
+                index += 1;
+            }
+            b'(' if angle == 0 => {
+                if index == 0 {
+                    arg_beg = i + 1;
+                }
+                let head = text[..i].trim_end();
+                let (target, head) = if let Some(head) = head.strip_suffix('#') {
+                    (SignatureTarget::Parameters, head)
+                } else if head.ends_with(')') {
+                    // port connection following parameter override like `Foo #(...) (`
+                    let mut depth = 0;
+                    let mut beg = head.len();
+                    for (j, x) in head.bytes().enumerate().rev() {
+                        match x {
+                            b')' => depth += 1,
+                            b'(' => depth -= 1,
+                            _ => (),
+                        }
+                        if depth == 0 {
+                            beg = j;
+                            break;
+                        }
+                    }
+                    let head = head[..beg].trim_end().strip_suffix('#')?;
+                    (SignatureTarget::Ports, head)
+                } else {
+                    (SignatureTarget::Function, head)
+                };
+                let (path, beg) = trailing_path(head)?;
+                let target = if target == SignatureTarget::Function && is_inst_type(&head[..beg]) {
+                    SignatureTarget::Ports
+                } else {
+                    target
+                };
+                return Some(SignatureContext::new(target, path, index, &text[arg_beg..]));
+            }
+            _ => (),
+        }
+    }
+    None
+}
+
+impl SignatureContext {
+    fn new(target: SignatureTarget, path: Vec<String>, index: usize, arg: &str) -> Self {
+        let arg = arg.trim_start();
+        let len = arg.bytes().take_while(|x| is_identifier_char(*x)).count();
+        let rest = arg[len..].trim_start();
+        let name = if len > 0 && rest.starts_with(':') && !rest.starts_with("::") {
+            Some(arg[..len].to_string())
+        } else {
+            None
+        };
+        Self {
+            target,
+            path,
+            index,
+            name,
+        }
+    }
+}
+
+fn to_signature_help(context: &SignatureContext, namespace: &Namespace) -> Option<SignatureHelp> {
+    let path: Vec<_> = context
+        .path
+        .iter()
+        .map(|x| resource_table::insert_str(x))
+        .collect();
+    let mut symbol = symbol_table::resolve((&path, namespace)).ok()?.found;
+
+    match symbol.kind {
+        VerylSymbolKind::GenericInstance(ref x) => {
+            symbol = symbol_table::get(x.base)?;
+        }
+        VerylSymbolKind::ModportFunctionMember(_) => {
+            let mut namespace = symbol.namespace.clone();
+            namespace.pop();
+            let path = SymbolPath::new(&[symbol.token.text]);
+            symbol = symbol_table::resolve((&path, &namespace)).ok()?.found;
+        }
+        _ => (),
+    }
+
+    // (name, description, symbol of the argument)
+    let mut args: Vec<(String, String, Option<Symbol>)> = Vec::new();
+    let (open, close, suffix) = match (&context.target, &symbol.kind) {
+        (SignatureTarget::Function, VerylSymbolKind::Function(x)) => {
+            for port in &x.ports {
+                args.push(port_argument(port));
+            }
+            let suffix = x.ret.as_ref().map(|x| format!(" -> {x}"));
+            ("(", ")", suffix)
+        }
+        (SignatureTarget::Ports, VerylSymbolKind::Module(x)) => {
+            for port in &x.ports {
+                args.push(port_argument(port));
+            }
+            (" (", ")", None)
+        }
+        (SignatureTarget::Ports, VerylSymbolKind::ProtoModule(x)) => {
+            for port in &x.ports {
+                args.push(port_argument(port));
+            }
+            (" (", ")", None)
+        }
+        (SignatureTarget::Parameters, VerylSymbolKind::Module(x)) => {
+            for param in &x.parameters {
+                args.push(parameter_argument(param));
+            }
+            (" #(", ")", None)
+        }
+        (SignatureTarget::Parameters, VerylSymbolKind::ProtoModule(x)) => {
+            for param in &x.parameters {
+                args.push(parameter_argument(param));
+            }
+            (" #(", ")", None)
+        }
+        (SignatureTarget::Parameters, VerylSymbolKind::Interface(x)) => {
+            for param in &x.parameters {
+                args.push(parameter_argument(param));
+            }
+            (" #(", ")", None)
+        }
+        (SignatureTarget::Generics, _) => {
+            for (name, property) in symbol.generic_parameters() {
+                let mut text = format!("{}: {}", name, property.bound);
+                if let Some(ref x) = property.default_value {
+                    text.push_str(&format!(" = {x}"));
+                }
+                args.push((name.to_string(), text, None));
+            }
+            ("::<", ">", None)
+        }
+        _ => return None,
+    };
+
+    let mut label = format!("{}{}", symbol.token, open);
+    let mut parameters = Vec::new();
+    for (i, (_, text, arg)) in args.iter().enumerate() {
+        if i != 0 {
+            label.push_str(", ");
+        }
+        let beg = label.encode_utf16().count() as u32;
+        label.push_str(text);
+        let end = label.encode_utf16().count() as u32;
+        parameters.push(ParameterInformation {
+            label: ParameterLabel::LabelOffsets([beg, end]),
+            documentation: arg.as_ref().and_then(to_documentation),
+        });
+    }
+    label.push_str(close);
+    if let Some(x) = suffix {
+        label.push_str(&x);
+    }
+
+    let active_parameter = if let Some(ref name) = context.name {
+        args.iter().position(|x| &x.0 == name)
+    } else {
+        Some(context.index)
+    };
+
+    let signature = SignatureInformation {
+        label,
+        documentation: to_documentation(&symbol),
+        parameters: Some(parameters),
+        active_parameter: active_parameter.map(|x| x as u32),
+    };
+    Some(SignatureHelp {
+        signatures: vec![signature],
+        active_signature: Some(0),
+        active_parameter: active_parameter.map(|x| x as u32),
+    })
+}
+
+fn port_argument(port: &Port) -> (String, String, Option<Symbol>) {
+    let property = port.property();
+    let text = if let Some(ref x) = property.r#type {
+        format!("{}: {} {}", port.name, property.direction, x)
+    } else {
+        format!("{}: {}", port.name, property.direction)
+    };
+    (port.name.to_string(), text, symbol_table::get(port.symbol))
+}
+
+fn parameter_argument(param: &Parameter) -> (String, String, Option<Symbol>) {
+    let property = param.property();
+    let text = format!("{}: {}", param.name, property.r#type);
+    (
+        param.name.to_string(),
+        text,
+        symbol_table::get(param.symbol),
+    )
+}
+
+fn to_documentation(symbol: &Symbol) -> Option<Documentation> {
+    if symbol.doc_comment.is_empty() {
+        None
+    } else {
+        Some(Documentation::MarkupContent(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: symbol.doc_comment.format(false),
+        }))
+    }
+}
+
+#[derive(Default)]
+struct InlayHintCollector {
+    hints: Vec<InlayHint>,
+    ports: Vec<Port>,
+}
+
+impl InlayHintCollector {
+    fn push(&mut self, token: &Token, before: bool, label: String, kind: Option<InlayHintKind>) {
+        let line = token.line - 1;
+        let column = if before {
+            token.column - 1
+        } else {
+            token.column - 1 + token.length
+        };
+        self.hints.push(InlayHint {
+            position: Position::new(line, column),
+            label: InlayHintLabel::String(label),
+            kind,
+            text_edits: None,
+            tooltip: None,
+            padding_left: Some(!before),
+            padding_right: Some(before),
+            data: None,
+        });
+    }
+
+    fn value(
+        &mut self,
+        identifier: &syntax_tree::Identifier,
+        expression: &syntax_tree::Expression,
+    ) {
+        let Ok(symbol) = symbol_table::resolve(identifier) else {
+            return;
+        };
+        let Evaluated::Fixed { value, .. } = symbol.found.evaluate() else {
+            return;
+        };
+
+        let mut first = FirstToken::new();
+        first.expression(expression);
+        let mut last = LastToken::new();
+        last.expression(expression);
+        let (Some(first), Some(last)) = (first.token(), last.token()) else {
+            return;
+        };
+
+        // skip literal because its value is obvious
+        if first.id == last.id && first.to_string() == value.to_string() {
+            return;
+        }
+        self.push(last, false, format!("= {value}"), None);
+    }
+
+    fn width(&mut self, identifier: &syntax_tree::Identifier, array_type: &syntax_tree::ArrayType) {
+        let Ok(symbol) = symbol_table::resolve(identifier) else {
+            return;
+        };
+        let VerylSymbolKind::Variable(x) = symbol.found.kind else {
+            return;
+        };
+        if !x.r#type.array.is_empty() {
+            return;
+        }
+        let Some(width) = Evaluator::new().type_width(x.r#type) else {
+            return;
+        };
+
+        let mut last = LastToken::new();
+        last.array_type(array_type);
+        if let Some(last) = last.token() {
+            self.push(
+                last,
+                false,
+                to_width_label(width),
+                Some(InlayHintKind::TYPE),
+            );
+        }
+    }
+
+    fn expression_width(&mut self, expression: &syntax_tree::Expression) {
+        let Evaluated::Variable { width } = Evaluator::new().expression(expression) else {
+            return;
+        };
+
+        let mut last = LastToken::new();
+        last.expression(expression);
+        if let Some(last) = last.token() {
+            self.push(
+                last,
+                false,
+                to_width_label(width),
+                Some(InlayHintKind::TYPE),
+            );
+        }
+    }
+}
+
+impl VerylWalker for InlayHintCollector {
+    fn const_declaration(&mut self, arg: &syntax_tree::ConstDeclaration) {
+        if let syntax_tree::ConstDeclarationGroup::ArrayTypeEquExpression(x) =
+            arg.const_declaration_group.as_ref()
+        {
+            self.value(&arg.identifier, &x.expression);
+        }
+    }
+
+    fn with_parameter_item(&mut self, arg: &syntax_tree::WithParameterItem) {
+        if let syntax_tree::WithParameterItemGroup0::ArrayTypeEquExpression(x) =
+            arg.with_parameter_item_group0.as_ref()
+        {
+            self.value(&arg.identifier, &x.expression);
+        }
+    }
+
+    fn let_declaration(&mut self, arg: &syntax_tree::LetDeclaration) {
+        self.width(&arg.identifier, &arg.array_type);
+        self.expression_width(&arg.expression);
+    }
+
+    fn let_statement(&mut self, arg: &syntax_tree::LetStatement) {
+        self.width(&arg.identifier, &arg.array_type);
+        self.expression_width(&arg.expression);
+    }
+
+    fn var_declaration(&mut self, arg: &syntax_tree::VarDeclaration) {
+        self.width(&arg.identifier, &arg.array_type);
+    }
+
+    fn assign_declaration(&mut self, arg: &syntax_tree::AssignDeclaration) {
+        self.expression_width(&arg.expression);
+    }
+
+    fn inst_declaration(&mut self, arg: &syntax_tree::InstDeclaration) {
+        let Ok(symbol) = symbol_table::resolve(arg.scoped_identifier.as_ref()) else {
+            return;
+        };
+        let mut symbol = symbol.found;
+        if let VerylSymbolKind::GenericInstance(ref x) = symbol.kind {
+            let Some(x) = symbol_table::get(x.base) else {
+                return;
+            };
+            symbol = x;
+        }
+        self.ports = match symbol.kind {
+            VerylSymbolKind::Module(x) => x.ports,
+            VerylSymbolKind::ProtoModule(x) => x.ports,
+            _ => return,
+        };
+
+        if let Some(ref x) = arg.inst_declaration_opt1 {
+            if let Some(ref x) = x.inst_declaration_opt2 {
+                self.inst_port_list(&x.inst_port_list);
+            }
+        }
+        self.ports.clear();
+    }
+
+    fn inst_port_item(&mut self, arg: &syntax_tree::InstPortItem) {
+        let token = arg.identifier.identifier_token.token;
+        let Some(port) = self.ports.iter().find(|x| x.name == token.text) else {
+            return;
+        };
+        let property = port.property();
+        let label = if let Some(ref x) = property.r#type {
+            format!("{} {}", property.direction, x)
+        } else {
+            property.direction.to_string()
+        };
+        self.push(&token, true, label, Some(InlayHintKind::PARAMETER));
+    }
+}
+
+fn to_width_label(width: usize) -> String {
+    if width == 1 {
+        "1 bit".to_string()
+    } else {
+        format!("{width} bits")
+    }
+}
+
 fn current_namespace(url: &Url, line: usize, column: usize) -> Option<Namespace> {
     let url = resource_table::get_path_id(Path::new(url.path()).to_path_buf()).unwrap();
 
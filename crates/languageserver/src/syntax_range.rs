@@ -0,0 +1,379 @@
+use tower_lsp::lsp_types::{FoldingRange, FoldingRangeKind, Position, Range, SelectionRange};
+use veryl_parser::veryl_grammar_trait::*;
+use veryl_parser::veryl_token::{Token, VerylToken};
+use veryl_parser::veryl_walker::{Handler, HandlerPoint, VerylWalker};
+use veryl_parser::ParolError;
+
+/// Collect ranges of syntax tree nodes, braces and comments
+#[derive(Default)]
+pub struct SyntaxRangeCollector {
+    nodes: SyntaxNodes,
+    tokens: Vec<Range>,
+    braces: Vec<Token>,
+    comment_lines: Option<(u32, u32)>,
+    pub folding_ranges: Vec<FoldingRange>,
+}
+
+impl SyntaxRangeCollector {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Nested ranges from the token at the position to the outermost node
+    pub fn selection_range(&self, position: Position) -> SelectionRange {
+        let included = |x: &&Range| x.start <= position && position <= x.end;
+        let mut ranges: Vec<_> = self.nodes.ranges.iter().filter(included).collect();
+        if let Some(x) = self.tokens.iter().find(included) {
+            ranges.push(x);
+        }
+
+        // from outer to inner
+        ranges.sort_by(|a, b| a.start.cmp(&b.start).then(b.end.cmp(&a.end)));
+        ranges.dedup();
+
+        let mut ret: Option<SelectionRange> = None;
+        for range in ranges {
+            ret = Some(SelectionRange {
+                range: *range,
+                parent: ret.map(Box::new),
+            });
+        }
+        ret.unwrap_or(SelectionRange {
+            range: Range::new(position, position),
+            parent: None,
+        })
+    }
+
+    /// Range of the innermost declaration which includes the identifier
+    pub fn declaration_range(&self, identifier: Range) -> Option<Range> {
+        self.nodes
+            .declarations
+            .iter()
+            .filter(|x| x.start <= identifier.start && identifier.end <= x.end)
+            .min_by(|a, b| b.start.cmp(&a.start).then(a.end.cmp(&b.end)))
+            .copied()
+    }
+
+    fn push_folding_range(&mut self, start: u32, end: u32, kind: Option<FoldingRangeKind>) {
+        if start < end {
+            self.folding_ranges.push(FoldingRange {
+                start_line: start,
+                start_character: None,
+                end_line: end,
+                end_character: None,
+                kind,
+                collapsed_text: None,
+            });
+        }
+    }
+
+    fn comment(&mut self, token: &Token) {
+        let range = token_range(token);
+        let text = token.to_string();
+        if text.starts_with("//") {
+            // consecutive line comments are folded together
+            match self.comment_lines {
+                Some((beg, end)) if end + 1 == range.start.line => {
+                    self.comment_lines = Some((beg, range.start.line));
+                }
+                _ => {
+                    self.flush_comment_lines();
+                    self.comment_lines = Some((range.start.line, range.start.line));
+                }
+            }
+        } else {
+            self.flush_comment_lines();
+            self.push_folding_range(
+                range.start.line,
+                range.end.line,
+                Some(FoldingRangeKind::Comment),
+            );
+        }
+    }
+
+    fn flush_comment_lines(&mut self) {
+        if let Some((beg, end)) = self.comment_lines.take() {
+            self.push_folding_range(beg, end, Some(FoldingRangeKind::Comment));
+        }
+    }
+}
+
+impl VerylWalker for SyntaxRangeCollector {
+    fn veryl_token(&mut self, arg: &VerylToken) {
+        for x in &arg.comments {
+            self.comment(x);
+        }
+        self.flush_comment_lines();
+
+        let token = arg.token;
+        let range = token_range(&token);
+        match token.to_string().as_str() {
+            "{" => self.braces.push(token),
+            "}" => {
+                // keep the line of the closing brace visible
+                if let Some(x) = self.braces.pop() {
+                    let start = x.line - 1;
+                    self.push_folding_range(start, range.start.line.saturating_sub(1), None);
+                }
+            }
+            // multi-line token like embed content
+            _ => self.push_folding_range(range.start.line, range.end.line, None),
+        }
+        self.tokens.push(range);
+
+        for x in self.nodes.stack.iter_mut().rev() {
+            if x.is_some() {
+                break;
+            }
+            *x = Some(token);
+        }
+        self.nodes.last = Some(token);
+    }
+
+    fn get_handlers(&mut self) -> Option<Vec<&mut dyn Handler>> {
+        Some(vec![&mut self.nodes])
+    }
+}
+
+#[derive(Default)]
+struct SyntaxNodes {
+    point: HandlerPoint,
+    stack: Vec<Option<Token>>,
+    last: Option<Token>,
+    ranges: Vec<Range>,
+    declarations: Vec<Range>,
+}
+
+impl SyntaxNodes {
+    fn node(&mut self) -> Result<(), ParolError> {
+        match self.point {
+            HandlerPoint::Before => self.stack.push(None),
+            HandlerPoint::After => {
+                if let (Some(Some(beg)), Some(end)) = (self.stack.pop(), self.last) {
+                    let range = Range::new(token_range(&beg).start, token_range(&end).end);
+                    self.ranges.push(range);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl SyntaxNodes {
+    fn declaration(&mut self) -> Result<(), ParolError> {
+        let len = self.ranges.len();
+        self.node()?;
+        if self.ranges.len() > len {
+            self.declarations.push(self.ranges[len]);
+        }
+        Ok(())
+    }
+}
+
+impl Handler for SyntaxNodes {
+    fn set_point(&mut self, p: HandlerPoint) {
+        self.point = p;
+    }
+}
+
+impl VerylGrammarTrait for SyntaxNodes {
+    fn scoped_identifier(&mut self, _arg: &ScopedIdentifier) -> Result<(), ParolError> {
+        self.node()
+    }
+
+    fn expression_identifier(&mut self, _arg: &ExpressionIdentifier) -> Result<(), ParolError> {
+        self.node()
+    }
+
+    fn hierarchical_identifier(&mut self, _arg: &HierarchicalIdentifier) -> Result<(), ParolError> {
+        self.node()
+    }
+
+    fn expression(&mut self, _arg: &Expression) -> Result<(), ParolError> {
+        self.node()
+    }
+
+    fn factor(&mut self, _arg: &Factor) -> Result<(), ParolError> {
+        self.node()
+    }
+
+    fn function_call(&mut self, _arg: &FunctionCall) -> Result<(), ParolError> {
+        self.node()
+    }
+
+    fn argument_list(&mut self, _arg: &ArgumentList) -> Result<(), ParolError> {
+        self.node()
+    }
+
+    fn argument_item(&mut self, _arg: &ArgumentItem) -> Result<(), ParolError> {
+        self.node()
+    }
+
+    fn array_type(&mut self, _arg: &ArrayType) -> Result<(), ParolError> {
+        self.node()
+    }
+
+    fn statement_block(&mut self, _arg: &StatementBlock) -> Result<(), ParolError> {
+        self.node()
+    }
+
+    fn statement_block_item(&mut self, _arg: &StatementBlockItem) -> Result<(), ParolError> {
+        self.node()
+    }
+
+    fn if_statement(&mut self, _arg: &IfStatement) -> Result<(), ParolError> {
+        self.node()
+    }
+
+    fn case_statement(&mut self, _arg: &CaseStatement) -> Result<(), ParolError> {
+        self.node()
+    }
+
+    fn case_item(&mut self, _arg: &CaseItem) -> Result<(), ParolError> {
+        self.node()
+    }
+
+    fn switch_statement(&mut self, _arg: &SwitchStatement) -> Result<(), ParolError> {
+        self.node()
+    }
+
+    fn switch_item(&mut self, _arg: &SwitchItem) -> Result<(), ParolError> {
+        self.node()
+    }
+
+    fn inst_parameter_item(&mut self, _arg: &InstParameterItem) -> Result<(), ParolError> {
+        self.node()
+    }
+
+    fn inst_port_item(&mut self, _arg: &InstPortItem) -> Result<(), ParolError> {
+        self.node()
+    }
+
+    fn with_parameter(&mut self, _arg: &WithParameter) -> Result<(), ParolError> {
+        self.node()
+    }
+
+    fn with_parameter_item(&mut self, _arg: &WithParameterItem) -> Result<(), ParolError> {
+        self.declaration()
+    }
+
+    fn port_declaration(&mut self, _arg: &PortDeclaration) -> Result<(), ParolError> {
+        self.node()
+    }
+
+    fn port_declaration_item(&mut self, _arg: &PortDeclarationItem) -> Result<(), ParolError> {
+        self.declaration()
+    }
+
+    fn function_declaration(&mut self, _arg: &FunctionDeclaration) -> Result<(), ParolError> {
+        self.declaration()
+    }
+
+    fn struct_union_declaration(
+        &mut self,
+        _arg: &StructUnionDeclaration,
+    ) -> Result<(), ParolError> {
+        self.declaration()
+    }
+
+    fn enum_declaration(&mut self, _arg: &EnumDeclaration) -> Result<(), ParolError> {
+        self.declaration()
+    }
+
+    fn modport_declaration(&mut self, _arg: &ModportDeclaration) -> Result<(), ParolError> {
+        self.declaration()
+    }
+
+    fn generate_if_declaration(&mut self, _arg: &GenerateIfDeclaration) -> Result<(), ParolError> {
+        self.node()
+    }
+
+    fn generate_for_declaration(
+        &mut self,
+        _arg: &GenerateForDeclaration,
+    ) -> Result<(), ParolError> {
+        self.node()
+    }
+
+    fn generate_named_block(&mut self, _arg: &GenerateNamedBlock) -> Result<(), ParolError> {
+        self.declaration()
+    }
+
+    fn generate_optional_named_block(
+        &mut self,
+        _arg: &GenerateOptionalNamedBlock,
+    ) -> Result<(), ParolError> {
+        self.node()
+    }
+
+    fn generate_item(&mut self, _arg: &GenerateItem) -> Result<(), ParolError> {
+        self.node()
+    }
+
+    fn package_item(&mut self, _arg: &PackageItem) -> Result<(), ParolError> {
+        self.node()
+    }
+
+    fn module_declaration(&mut self, _arg: &ModuleDeclaration) -> Result<(), ParolError> {
+        self.declaration()
+    }
+
+    fn interface_declaration(&mut self, _arg: &InterfaceDeclaration) -> Result<(), ParolError> {
+        self.declaration()
+    }
+
+    fn package_declaration(&mut self, _arg: &PackageDeclaration) -> Result<(), ParolError> {
+        self.declaration()
+    }
+
+    fn var_declaration(&mut self, _arg: &VarDeclaration) -> Result<(), ParolError> {
+        self.declaration()
+    }
+
+    fn let_declaration(&mut self, _arg: &LetDeclaration) -> Result<(), ParolError> {
+        self.declaration()
+    }
+
+    fn const_declaration(&mut self, _arg: &ConstDeclaration) -> Result<(), ParolError> {
+        self.declaration()
+    }
+
+    fn type_def_declaration(&mut self, _arg: &TypeDefDeclaration) -> Result<(), ParolError> {
+        self.declaration()
+    }
+
+    fn inst_declaration(&mut self, _arg: &InstDeclaration) -> Result<(), ParolError> {
+        self.declaration()
+    }
+
+    fn struct_union_item(&mut self, _arg: &StructUnionItem) -> Result<(), ParolError> {
+        self.declaration()
+    }
+
+    fn enum_item(&mut self, _arg: &EnumItem) -> Result<(), ParolError> {
+        self.declaration()
+    }
+
+    fn modport_item(&mut self, _arg: &ModportItem) -> Result<(), ParolError> {
+        self.declaration()
+    }
+
+    fn description_group(&mut self, _arg: &DescriptionGroup) -> Result<(), ParolError> {
+        self.node()
+    }
+}
+
+/// Range of the token including multi-line text
+pub fn token_range(token: &Token) -> Range {
+    let text = token.to_string();
+    let text = text.trim_end_matches(['\r', '\n']);
+    let line = token.line.saturating_sub(1);
+    let column = token.column.saturating_sub(1);
+    let end = if let Some((head, tail)) = text.rsplit_once('\n') {
+        let lines = head.matches('\n').count() as u32 + 1;
+        Position::new(line + lines, tail.encode_utf16().count() as u32)
+    } else {
+        Position::new(line, column + token.length)
+    };
+    Range::new(Position::new(line, column), end)
+}
@@ -0,0 +1,233 @@
+use dashmap::DashMap;
+use futures::executor::block_on;
+use miette::{miette, Diagnostic as _, IntoDiagnostic, Result, Severity};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use tempfile::TempDir;
+use tower_lsp::lsp_types::*;
+use tower_lsp::Client;
+use veryl_analyzer::symbol::{SymbolKind, TestProperty, TestType};
+use veryl_analyzer::{symbol_table, Analyzer};
+use veryl_metadata::{FilelistType, Metadata, SimType, Target};
+use veryl_parser::Parser;
+use veryl_path::PathPair;
+use veryl_runner::{
+    remapped_location, Cocotb, CocotbSource, MessageLevel, Runner, Vcs, Verilator, Vivado,
+};
+
+pub const RUN_TEST: &str = "veryl.runTest";
+pub const RUN_TEST_WITH_WAVE: &str = "veryl.runTestWithWave";
+
+/// Test execution which is requested through `workspace/executeCommand`
+pub struct TestTask {
+    pub client: Client,
+    /// Token of the progress which was created by the server
+    pub token: NumberOrString,
+    pub metadata: Metadata,
+    pub sources: Vec<(PathPair, String)>,
+    pub test: String,
+    pub wave: bool,
+    /// Diagnostics of the analyzer which are published by the server
+    pub diag_map: Arc<DashMap<PathBuf, Vec<Diagnostic>>>,
+}
+
+impl TestTask {
+    /// Run the test on a worker thread.
+    ///
+    /// The symbol table and other analyzer tables are thread local,
+    /// so the worker analyzes the project in its own tables without touching
+    /// the tables of the server.
+    pub fn spawn(self) -> JoinHandle<()> {
+        thread::spawn(move || self.run())
+    }
+
+    fn run(mut self) {
+        self.report("building");
+
+        let messages = Arc::new(Mutex::new(Vec::new()));
+        let ret = self.build().and_then(|target_dir| {
+            let ret = self.run_runner(messages.clone());
+            drop(target_dir);
+            ret
+        });
+
+        // simulator messages are published as diagnostics of the original Veryl sources
+        let mut diags: HashMap<PathBuf, Vec<Diagnostic>> = HashMap::new();
+        for (level, msg) in messages.lock().unwrap().iter() {
+            if let Some((path, diag)) = to_test_diag(*level, msg) {
+                diags.entry(path).or_default().push(diag);
+            }
+        }
+        for (path, mut diag) in diags {
+            // publishing replaces all diagnostics of the file, so those of the analyzer are kept
+            let mut all = self
+                .diag_map
+                .get(&path)
+                .map(|x| x.clone())
+                .unwrap_or_default();
+            all.append(&mut diag);
+            if let Ok(url) = Url::from_file_path(path) {
+                block_on(self.client.publish_diagnostics(url, all, None));
+            }
+        }
+
+        let test = &self.test;
+        let (typ, msg) = match ret {
+            Ok(true) => (MessageType::INFO, format!("Succeeded test ({test})")),
+            Ok(false) => (MessageType::ERROR, format!("Failed test ({test})")),
+            Err(x) => (MessageType::ERROR, format!("Failed test ({test}): {x}")),
+        };
+
+        let end = WorkDoneProgressEnd {
+            message: Some(msg.clone()),
+        };
+        block_on(
+            self.client
+                .send_notification::<notification::Progress>(ProgressParams {
+                    token: self.token.clone(),
+                    value: ProgressParamsValue::WorkDone(WorkDoneProgress::End(end)),
+                }),
+        );
+        block_on(self.client.show_message(typ, msg));
+    }
+
+    fn report(&self, msg: &str) {
+        report(&self.client, &self.token, msg);
+    }
+
+    /// Analyze and emit the whole project so that simulators can refer it through the filelist
+    ///
+    /// The outputs are emitted to the returned temporary directory
+    /// so that the build results of the project are not overwritten by unsaved changes.
+    fn build(&mut self) -> Result<TempDir> {
+        // force filelist_type to absolute which can be refered from temporary directory
+        self.metadata.build.filelist_type = FilelistType::Absolute;
+
+        let target_dir = TempDir::new().into_diagnostic()?;
+        let project_path = self.metadata.project_path();
+        for (path, _) in &mut self.sources {
+            let dst = path.dst.strip_prefix(&project_path).into_diagnostic()?;
+            path.dst = target_dir.path().join(dst);
+        }
+        self.metadata.metadata_path = target_dir.path().join("Veryl.toml");
+
+        let analyzer = Analyzer::new(&self.metadata);
+        let mut contexts = Vec::new();
+        for (path, text) in &self.sources {
+            let parser = Parser::parse(text, &path.src)?;
+            let _ = analyzer.analyze_pass1(&path.prj, text, &path.src, &parser.veryl);
+            contexts.push((path, text, parser));
+        }
+
+        let mut errors = Vec::new();
+        for (path, text, parser) in &contexts {
+            errors.append(&mut analyzer.analyze_pass2(&path.prj, text, &path.src, &parser.veryl));
+        }
+        for (path, text, parser) in &contexts {
+            errors.append(&mut analyzer.analyze_pass3(&path.prj, text, &path.src, &parser.veryl));
+        }
+        if let Some(x) = errors
+            .into_iter()
+            .find(|x| matches!(x.severity(), Some(Severity::Error) | None))
+        {
+            return Err(x.into());
+        }
+
+        let temp_dir = if let Target::Bundle { .. } = &self.metadata.build.target {
+            Some(TempDir::new().into_diagnostic()?)
+        } else {
+            None
+        };
+        let temp_path = temp_dir.as_ref().map(|x| x.path());
+
+        for (path, text, parser) in &contexts {
+            veryl_build::emit(&self.metadata, path, text, parser, temp_path)?;
+        }
+
+        let paths: Vec<_> = self.sources.iter().map(|(x, _)| x.clone()).collect();
+        veryl_build::gen_filelist(&self.metadata, &paths, temp_path)?;
+
+        Ok(target_dir)
+    }
+
+    fn run_runner(&self, messages: Arc<Mutex<Vec<(MessageLevel, String)>>>) -> Result<bool> {
+        let metadata = &self.metadata;
+        let test = symbol_table::get_all()
+            .into_iter()
+            .find_map(|x| match x.kind {
+                SymbolKind::Test(property)
+                    if x.namespace.to_string() == metadata.project.name
+                        && x.token.to_string() == self.test =>
+                {
+                    Some((x.token.text, property))
+                }
+                _ => None,
+            });
+        let Some((test, property)) = test else {
+            return Err(miette!("test is not found"));
+        };
+
+        let mut runner = runner(&property, metadata.test.simulator);
+
+        let client = self.client.clone();
+        let token = self.token.clone();
+        runner.set_reporter(Box::new(move |level, line| {
+            report(&client, &token, line.lines().next().unwrap_or_default());
+            messages.lock().unwrap().push((level, line.to_string()));
+        }));
+
+        self.report("running");
+        runner.run(metadata, test, property.top, property.path, self.wave)
+    }
+}
+
+fn report(client: &Client, token: &NumberOrString, msg: &str) {
+    let report = WorkDoneProgressReport {
+        cancellable: Some(false),
+        message: Some(msg.to_string()),
+        percentage: None,
+    };
+    block_on(
+        client.send_notification::<notification::Progress>(ProgressParams {
+            token: token.clone(),
+            value: ProgressParamsValue::WorkDone(WorkDoneProgress::Report(report)),
+        }),
+    );
+}
+
+pub fn runner(property: &TestProperty, sim_type: SimType) -> Box<dyn Runner> {
+    match property.r#type {
+        TestType::Inline => match sim_type {
+            SimType::Verilator => Verilator::new().runner(),
+            SimType::Vcs => Vcs::new().runner(),
+            SimType::Vivado => Vivado::new().runner(),
+        },
+        TestType::CocotbEmbed(x) => Cocotb::new(CocotbSource::Embed(x)).runner(),
+        TestType::CocotbInclude(x) => Cocotb::new(CocotbSource::Include(x)).runner(),
+    }
+}
+
+/// Diagnostic at the Veryl source location resolved through the source map
+pub fn to_test_diag(level: MessageLevel, msg: &str) -> Option<(PathBuf, Diagnostic)> {
+    let severity = match level {
+        MessageLevel::Info => return None,
+        MessageLevel::Warning => DiagnosticSeverity::WARNING,
+        MessageLevel::Error | MessageLevel::Fatal => DiagnosticSeverity::ERROR,
+    };
+    let (path, line, column) = remapped_location(msg)?;
+    let position = Position::new(line.saturating_sub(1), column.saturating_sub(1));
+    let message = msg.lines().next().unwrap_or_default().trim().to_string();
+
+    Some((
+        path,
+        Diagnostic {
+            range: Range::new(position, position),
+            severity: Some(severity),
+            source: Some("veryl test".to_string()),
+            message,
+            ..Default::default()
+        },
+    ))
+}
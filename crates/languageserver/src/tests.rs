@@ -6,12 +6,13 @@ use std::path::PathBuf;
 use tokio::io::{AsyncReadExt, AsyncWriteExt, DuplexStream};
 use tower_lsp::jsonrpc::{Id, Request, Response};
 use tower_lsp::lsp_types::*;
-use tower_lsp::{Client, LanguageServer, LspService, Server};
+use tower_lsp::{Client, ClientSocket, LanguageServer, LspService, Server};
 
 struct TestServer {
     req_stream: DuplexStream,
     res_stream: DuplexStream,
     responses: VecDeque<String>,
+    pending: Vec<u8>,
 }
 
 impl TestServer {
@@ -20,17 +21,21 @@ impl TestServer {
         F: FnOnce(Client) -> S,
         S: LanguageServer,
     {
+        let (service, socket) = LspService::new(init);
+        Self::from_service(service, socket)
+    }
+
+    fn from_service<S: LanguageServer>(service: LspService<S>, socket: ClientSocket) -> Self {
         let (req_client, req_server) = tokio::io::duplex(1024);
         let (res_server, res_client) = tokio::io::duplex(1024);
 
-        let (service, socket) = LspService::new(init);
-
         tokio::spawn(Server::new(req_server, res_server, socket).serve(service));
 
         Self {
             req_stream: req_client,
             res_stream: res_client,
             responses: VecDeque::new(),
+            pending: Vec::new(),
         }
     }
 
@@ -38,25 +43,41 @@ impl TestServer {
         format!("Content-Length: {}\r\n\r\n{}", payload.len(), payload)
     }
 
-    fn decode(text: &str) -> Vec<String> {
+    /// Decode complete messages, and return them with the consumed length
+    fn decode(text: &[u8]) -> (Vec<String>, usize) {
         let mut ret = Vec::new();
-        let mut temp = text;
-
-        while !temp.is_empty() {
-            let p = temp.find("\r\n\r\n").unwrap();
-            let (header, body) = temp.split_at(p + 4);
-            let len = header
-                .strip_prefix("Content-Length: ")
-                .unwrap()
-                .strip_suffix("\r\n\r\n")
-                .unwrap();
+        let mut consumed = 0;
+
+        while let Some(p) = text[consumed..].windows(4).position(|x| x == b"\r\n\r\n") {
+            let header = String::from_utf8(text[consumed..consumed + p].to_vec()).unwrap();
+            let len = header.strip_prefix("Content-Length: ").unwrap();
             let len: usize = len.parse().unwrap();
-            let (body, rest) = body.split_at(len);
-            ret.push(body.to_string());
-            temp = rest;
+            let beg = consumed + p + 4;
+            if text.len() < beg + len {
+                break;
+            }
+            ret.push(String::from_utf8(text[beg..beg + len].to_vec()).unwrap());
+            consumed = beg + len;
         }
 
-        ret
+        (ret, consumed)
+    }
+
+    async fn receive(&mut self) -> String {
+        while self.responses.is_empty() {
+            let mut buf = vec![0; 1024];
+            let n = self.res_stream.read(&mut buf).await.unwrap();
+            if n == 0 {
+                panic!("the server closed the stream before the response");
+            }
+            self.pending.extend_from_slice(&buf[..n]);
+            let (messages, consumed) = Self::decode(&self.pending);
+            self.pending.drain(..consumed);
+            for x in messages {
+                self.responses.push_front(x);
+            }
+        }
+        self.responses.pop_back().unwrap()
     }
 
     async fn send_request(&mut self, req: Request) {
@@ -73,28 +94,31 @@ impl TestServer {
     }
 
     async fn recv_response(&mut self) -> Response {
-        if self.responses.is_empty() {
-            let mut buf = vec![0; 1024];
-            let n = self.res_stream.read(&mut buf).await.unwrap();
-            let ret = String::from_utf8(buf[..n].to_vec()).unwrap();
-            for x in Self::decode(&ret) {
-                self.responses.push_front(x);
-            }
-        }
-        let res = self.responses.pop_back().unwrap();
+        let res = self.receive().await;
         serde_json::from_str(&res).unwrap()
     }
 
-    async fn recv_notification(&mut self) -> Request {
-        if self.responses.is_empty() {
-            let mut buf = vec![0; 1024];
-            let n = self.res_stream.read(&mut buf).await.unwrap();
-            let ret = String::from_utf8(buf[..n].to_vec()).unwrap();
-            for x in Self::decode(&ret) {
-                self.responses.push_front(x);
+    async fn recv_value(&mut self) -> Value {
+        let res = self.receive().await;
+        serde_json::from_str(&res).unwrap()
+    }
+
+    /// Receive the response of the request, skipping messages of background analysis
+    async fn recv_response_of(&mut self, id: i64) -> Value {
+        loop {
+            let res = self.recv_value().await;
+            if res["id"] == id {
+                return res;
+            }
+            if res["method"] == "window/workDoneProgress/create" {
+                let id: Id = serde_json::from_value(res["id"].clone()).unwrap();
+                self.send_ack(&id).await;
             }
         }
-        let res = self.responses.pop_back().unwrap();
+    }
+
+    async fn recv_notification(&mut self) -> Request {
+        let res = self.receive().await;
         serde_json::from_str(&res).unwrap()
     }
 }
@@ -155,7 +179,6 @@ async fn did_open() {
     assert_eq!(res.params().unwrap()["message"], "did_open");
 
     let res = server.recv_notification().await;
-    dbg!(&res);
     assert_eq!(res.method(), "textDocument/publishDiagnostics");
     assert!(res.params().unwrap()["diagnostics"]
         .as_array()
@@ -163,8 +186,26 @@ async fn did_open() {
         .is_empty());
 }
 
+fn build_document_symbol(id: i64) -> Request {
+    let mut path = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    path.pop();
+    path.pop();
+    path.push("test.veryl");
+    let uri = Url::from_file_path(path).unwrap();
+    let params = DocumentSymbolParams {
+        text_document: TextDocumentIdentifier { uri },
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+
+    Request::build("textDocument/documentSymbol")
+        .params(json!(params))
+        .id(id)
+        .finish()
+}
+
 #[tokio::test]
-async fn diagnostics() {
+async fn document_symbol() {
     let mut server = TestServer::new(Backend::new);
 
     let req = build_initialize(1);
@@ -176,28 +217,218 @@ async fn diagnostics() {
     server.send_request(req).await;
     let res = server.recv_notification().await;
     assert_eq!(res.method(), "window/logMessage");
-    assert_eq!(res.params().unwrap()["message"], "server initialized!");
 
-    let req = build_did_open("module A { var a: logic; }");
+    let req = build_did_open("module A (a: input logic) { var b: logic; assign b = a; }");
+    server.send_request(req).await;
+
+    let res = server.recv_notification().await;
+    assert_eq!(res.params().unwrap()["message"], "did_open");
+
+    let res = server.recv_notification().await;
+    assert_eq!(res.method(), "textDocument/publishDiagnostics");
+
+    let req = build_document_symbol(2);
+    server.send_request(req).await;
+
+    let res = server.recv_response_of(2).await;
+    let symbols = res["result"].as_array().unwrap();
+    assert_eq!(symbols.len(), 1);
+    assert_eq!(symbols[0]["name"], Value::from("A"));
+    assert_eq!(symbols[0]["kind"], json!(SymbolKind::MODULE));
+    let children = symbols[0]["children"].as_array().unwrap();
+    assert_eq!(children.len(), 2);
+    assert_eq!(children[0]["name"], Value::from("a"));
+    assert_eq!(children[1]["name"], Value::from("b"));
+    assert_eq!(
+        children[1]["selectionRange"]["start"]["character"],
+        Value::from(32)
+    );
+    // the range covers the whole declaration, and the selection range is the identifier
+    assert_eq!(symbols[0]["range"]["start"]["character"], Value::from(0));
+    assert_eq!(symbols[0]["range"]["end"]["character"], Value::from(57));
+    assert_eq!(
+        symbols[0]["selectionRange"]["start"]["character"],
+        Value::from(7)
+    );
+    assert_eq!(children[0]["range"]["start"]["character"], Value::from(10));
+    assert_eq!(children[0]["range"]["end"]["character"], Value::from(24));
+    assert_eq!(children[1]["range"]["start"]["character"], Value::from(28));
+    assert_eq!(children[1]["range"]["end"]["character"], Value::from(41));
+}
+
+fn build_signature_help(id: i64, line: u32, character: u32) -> Request {
+    let mut path = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    path.pop();
+    path.pop();
+    path.push("test.veryl");
+    let uri = Url::from_file_path(path).unwrap();
+    let params = SignatureHelpParams {
+        context: None,
+        text_document_position_params: TextDocumentPositionParams {
+            text_document: TextDocumentIdentifier { uri },
+            position: Position { line, character },
+        },
+        work_done_progress_params: Default::default(),
+    };
+
+    Request::build("textDocument/signatureHelp")
+        .params(json!(params))
+        .id(id)
+        .finish()
+}
+
+#[tokio::test]
+async fn signature_help() {
+    let mut server = TestServer::new(Backend::new);
+
+    let req = build_initialize(1);
     server.send_request(req).await;
+    let res = server.recv_response().await;
+    assert!(res.is_ok());
 
+    let req = build_initialized();
+    server.send_request(req).await;
     let res = server.recv_notification().await;
     assert_eq!(res.method(), "window/logMessage");
+
+    let code = "module A { function f (a: input logic, b: input logic) -> logic { return a; } let _x: logic = f(1, 0); }";
+    let req = build_did_open(code);
+    server.send_request(req).await;
+
+    let res = server.recv_notification().await;
     assert_eq!(res.params().unwrap()["message"], "did_open");
 
     let res = server.recv_notification().await;
-    dbg!(&res);
     assert_eq!(res.method(), "textDocument/publishDiagnostics");
-    let diags = res.params().unwrap()["diagnostics"].as_array().unwrap();
-    assert_eq!(diags[0]["code"], Value::from("unused_variable"));
-    assert_eq!(diags[0]["range"]["start"]["character"], Value::from(15));
-    assert_eq!(diags[0]["range"]["start"]["line"], Value::from(0));
-    assert_eq!(diags[0]["range"]["end"]["character"], Value::from(16));
-    assert_eq!(diags[0]["range"]["end"]["line"], Value::from(0));
+
+    let column = code.find("0);").unwrap() as u32;
+    let req = build_signature_help(2, 0, column);
+    server.send_request(req).await;
+
+    let res = server.recv_response_of(2).await;
+    let signature = &res["result"]["signatures"][0];
+    assert_eq!(
+        signature["label"],
+        Value::from("f(a: input logic, b: input logic) -> logic")
+    );
+    assert_eq!(signature["parameters"][1]["label"], json!([18, 32]));
+    assert_eq!(res["result"]["activeParameter"], Value::from(1));
+}
+
+fn build_prepare_call_hierarchy(id: i64, line: u32, character: u32) -> Request {
+    let mut path = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    path.pop();
+    path.pop();
+    path.push("test.veryl");
+    let uri = Url::from_file_path(path).unwrap();
+    let params = CallHierarchyPrepareParams {
+        text_document_position_params: TextDocumentPositionParams {
+            text_document: TextDocumentIdentifier { uri },
+            position: Position { line, character },
+        },
+        work_done_progress_params: Default::default(),
+    };
+
+    Request::build("textDocument/prepareCallHierarchy")
+        .params(json!(params))
+        .id(id)
+        .finish()
+}
+
+fn build_calls(id: i64, method: &'static str, item: &Value) -> Request {
+    let item: CallHierarchyItem = serde_json::from_value(item.clone()).unwrap();
+    let params = CallHierarchyIncomingCallsParams {
+        item,
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+
+    Request::build(method).params(json!(params)).id(id).finish()
 }
 
 #[tokio::test]
-async fn progress() {
+async fn call_hierarchy() {
+    let (service, socket) = Backend::service();
+    let mut server = TestServer::from_service(service, socket);
+
+    let req = build_initialize(1);
+    server.send_request(req).await;
+    let res = server.recv_response().await;
+    assert!(res.is_ok());
+
+    let req = build_initialized();
+    server.send_request(req).await;
+    let res = server.recv_notification().await;
+    assert_eq!(res.method(), "window/logMessage");
+
+    let code = "module A { inst u: B; function f () -> logic { return 1; } let _x: logic = f(); }\nmodule B {}";
+    let req = build_did_open(code);
+    server.send_request(req).await;
+
+    let res = server.recv_notification().await;
+    assert_eq!(res.params().unwrap()["message"], "did_open");
+
+    let res = server.recv_notification().await;
+    assert_eq!(res.method(), "textDocument/publishDiagnostics");
+
+    let req = build_prepare_call_hierarchy(2, 1, 7);
+    server.send_request(req).await;
+
+    let res = server.recv_response_of(2).await;
+    let item = &res["result"][0];
+    assert_eq!(item["name"], Value::from("B"));
+
+    let req = build_calls(3, "callHierarchy/incomingCalls", item);
+    server.send_request(req).await;
+
+    let res = server.recv_response_of(3).await;
+    let calls = res["result"].as_array().unwrap();
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0]["from"]["name"], Value::from("A"));
+    assert_eq!(
+        calls[0]["fromRanges"][0]["start"]["character"],
+        Value::from(code.find("u:").unwrap())
+    );
+
+    let req = build_calls(4, "callHierarchy/outgoingCalls", &calls[0]["from"]);
+    server.send_request(req).await;
+
+    let res = server.recv_response_of(4).await;
+    let calls = res["result"].as_array().unwrap();
+    assert_eq!(calls.len(), 2);
+
+    let req = Request::build("veryl/instanceTree")
+        .params(json!({"top": "A"}))
+        .id(5)
+        .finish();
+    server.send_request(req).await;
+
+    let res = server.recv_response_of(5).await;
+    assert_eq!(res["result"]["module"], Value::from("A"));
+    assert_eq!(res["result"]["children"][0]["name"], Value::from("u"));
+    assert_eq!(res["result"]["children"][0]["module"], Value::from("B"));
+}
+
+fn build_goto(id: i64, method: &'static str, line: u32, character: u32) -> Request {
+    let mut path = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    path.pop();
+    path.pop();
+    path.push("test.veryl");
+    let uri = Url::from_file_path(path).unwrap();
+    let params = GotoDefinitionParams {
+        text_document_position_params: TextDocumentPositionParams {
+            text_document: TextDocumentIdentifier { uri },
+            position: Position { line, character },
+        },
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+
+    Request::build(method).params(json!(params)).id(id).finish()
+}
+
+#[tokio::test]
+async fn goto_implementation() {
     let mut server = TestServer::new(Backend::new);
 
     let req = build_initialize(1);
@@ -209,38 +440,1025 @@ async fn progress() {
     server.send_request(req).await;
     let res = server.recv_notification().await;
     assert_eq!(res.method(), "window/logMessage");
-    assert_eq!(res.params().unwrap()["message"], "server initialized!");
 
-    let req = build_did_open("module A {}");
+    let code = [
+        "proto module P (a: input logic);",
+        "module A for P (a: input logic) {}",
+        "module B::<T: P> { inst u: T (a: 0); }",
+        "module C { struct S { a: logic } var s: S; assign s = 0; }",
+    ];
+    let req = build_did_open(&code.join("\n"));
+    server.send_request(req).await;
+
+    let res = server.recv_notification().await;
+    assert_eq!(res.params().unwrap()["message"], "did_open");
+
+    let res = server.recv_notification().await;
+    assert_eq!(res.method(), "textDocument/publishDiagnostics");
+
+    // from proto module to implementations
+    let req = build_goto(2, "textDocument/implementation", 0, 13);
+    server.send_request(req).await;
+    let res = server.recv_response_of(2).await;
+    let locations = res["result"].as_array().unwrap();
+    assert_eq!(locations.len(), 1);
+    assert_eq!(locations[0]["range"]["start"]["line"], Value::from(1));
+
+    // from implementation to proto module
+    let req = build_goto(3, "textDocument/implementation", 1, 7);
+    server.send_request(req).await;
+    let res = server.recv_response_of(3).await;
+    assert_eq!(res["result"][0]["range"]["start"]["line"], Value::from(0));
+
+    // from instance of generic parameter to implementations
+    let column = code[2].find("u:").unwrap() as u32;
+    let req = build_goto(4, "textDocument/implementation", 2, column);
+    server.send_request(req).await;
+    let res = server.recv_response_of(4).await;
+    assert_eq!(res["result"][0]["range"]["start"]["line"], Value::from(1));
+
+    // from variable to struct
+    let column = code[3].find("s:").unwrap() as u32;
+    let req = build_goto(5, "textDocument/typeDefinition", 3, column);
+    server.send_request(req).await;
+    let res = server.recv_response_of(5).await;
+    assert_eq!(
+        res["result"]["range"]["start"]["character"],
+        Value::from(code[3].find("S {").unwrap())
+    );
+}
+
+fn build_text_document_request(id: i64, method: &'static str, params: Value) -> Request {
+    let mut path = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    path.pop();
+    path.pop();
+    path.push("test.veryl");
+    let uri = Url::from_file_path(path).unwrap();
+    let mut params = params;
+    params["textDocument"] = json!(TextDocumentIdentifier { uri });
+
+    Request::build(method).params(params).id(id).finish()
+}
+
+#[tokio::test]
+async fn folding_selection_highlight() {
+    let mut server = TestServer::new(Backend::new);
+
+    let req = build_initialize(1);
     server.send_request(req).await;
+    let res = server.recv_response().await;
+    assert!(res.is_ok());
 
+    let req = build_initialized();
+    server.send_request(req).await;
     let res = server.recv_notification().await;
     assert_eq!(res.method(), "window/logMessage");
+
+    let code = [
+        "// a",
+        "// b",
+        "module A {",
+        "    var a: logic;",
+        "    var b: logic;",
+        "    always_comb {",
+        "        a = b;",
+        "    }",
+        "}",
+    ];
+    let req = build_did_open(&code.join("\n"));
+    server.send_request(req).await;
+
+    let res = server.recv_notification().await;
     assert_eq!(res.params().unwrap()["message"], "did_open");
 
     let res = server.recv_notification().await;
-    dbg!(&res);
     assert_eq!(res.method(), "textDocument/publishDiagnostics");
 
+    let req = build_text_document_request(2, "textDocument/foldingRange", json!({}));
+    server.send_request(req).await;
+    let res = server.recv_response_of(2).await;
+    let ranges: Vec<_> = res["result"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|x| (x["startLine"].clone(), x["endLine"].clone()))
+        .collect();
+    assert_eq!(
+        ranges,
+        vec![
+            (json!(0), json!(1)),
+            (json!(5), json!(6)),
+            (json!(2), json!(7))
+        ]
+    );
+    assert_eq!(res["result"][0]["kind"], Value::from("comment"));
+
+    let params = json!({"positions": [Position::new(6, 8)]});
+    let req = build_text_document_request(3, "textDocument/selectionRange", params);
+    server.send_request(req).await;
+    let res = server.recv_response_of(3).await;
+    let range = &res["result"][0];
+    assert_eq!(
+        range["range"],
+        json!(Range::new(Position::new(6, 8), Position::new(6, 9)))
+    );
+    assert_eq!(
+        range["parent"]["range"],
+        json!(Range::new(Position::new(6, 8), Position::new(6, 14)))
+    );
+
+    let params = json!({"position": Position::new(6, 8)});
+    let req = build_text_document_request(4, "textDocument/documentHighlight", params);
+    server.send_request(req).await;
+    let res = server.recv_response_of(4).await;
+    let highlights = res["result"].as_array().unwrap();
+    assert_eq!(highlights.len(), 2);
+    assert_eq!(highlights[0]["kind"], json!(DocumentHighlightKind::TEXT));
+    assert_eq!(highlights[1]["kind"], json!(DocumentHighlightKind::WRITE));
+
+    let params = json!({"position": Position::new(6, 12)});
+    let req = build_text_document_request(5, "textDocument/documentHighlight", params);
+    server.send_request(req).await;
+    let res = server.recv_response_of(5).await;
+    assert_eq!(res["result"][1]["kind"], json!(DocumentHighlightKind::READ));
+}
+
+#[tokio::test]
+async fn range_formatting() {
+    let mut server = TestServer::new(Backend::new);
+
+    let req = build_initialize(1);
+    server.send_request(req).await;
+    let res = server.recv_response().await;
+    assert!(res.is_ok());
+
+    let req = build_initialized();
+    server.send_request(req).await;
     let res = server.recv_notification().await;
-    assert_eq!(res.method(), "window/workDoneProgress/create");
+    assert_eq!(res.method(), "window/logMessage");
 
-    server.send_ack(res.id().unwrap()).await;
+    let code =
+        "module A {\n  var a: logic;\n  var b: logic;\n  assign a = 1;\n  assign b = 1;\n}\n";
+    let req = build_did_open(code);
+    server.send_request(req).await;
 
-    let mut progress_finish = false;
-    let mut percentage = 0;
-    while !progress_finish {
-        let res = server.recv_notification().await;
-        dbg!(&res);
-        if res.method() == "$/progress" {
-            if res.params().unwrap()["value"]["kind"] == Value::from("end") {
-                progress_finish = true;
-            } else if res.params().unwrap()["value"]["kind"] == Value::from("report") {
-                percentage = res.params().unwrap()["value"]["percentage"]
-                    .as_i64()
-                    .unwrap();
-            }
-        }
-    }
-    assert_eq!(percentage, 100);
+    let res = server.recv_notification().await;
+    assert_eq!(res.params().unwrap()["message"], "did_open");
+
+    let res = server.recv_notification().await;
+    assert_eq!(res.method(), "textDocument/publishDiagnostics");
+
+    let params = json!({
+        "range": Range::new(Position::new(1, 0), Position::new(1, 5)),
+        "options": FormattingOptions::default(),
+    });
+    let req = build_text_document_request(2, "textDocument/rangeFormatting", params);
+    server.send_request(req).await;
+    let res = server.recv_response_of(2).await;
+    let edits = res["result"].as_array().unwrap();
+    assert_eq!(edits.len(), 1);
+    assert_eq!(edits[0]["newText"], Value::from("    var a: logic;\n"));
+    assert_eq!(
+        edits[0]["range"],
+        json!(Range::new(Position::new(1, 0), Position::new(2, 0)))
+    );
+
+    let params = json!({
+        "position": Position::new(4, 15),
+        "ch": ";",
+        "options": FormattingOptions::default(),
+    });
+    let req = build_text_document_request(3, "textDocument/onTypeFormatting", params);
+    server.send_request(req).await;
+    let res = server.recv_response_of(3).await;
+    let edits = res["result"].as_array().unwrap();
+    assert_eq!(edits.len(), 1);
+    assert_eq!(edits[0]["newText"], Value::from("    assign b = 1;\n"));
+
+    let params = json!({
+        "position": Position::new(5, 1),
+        "ch": "}",
+        "options": FormattingOptions::default(),
+    });
+    let req = build_text_document_request(4, "textDocument/onTypeFormatting", params);
+    server.send_request(req).await;
+    let res = server.recv_response_of(4).await;
+    assert_eq!(res["result"].as_array().unwrap().len(), 4);
+}
+
+#[tokio::test]
+async fn semantic_tokens_delta() {
+    let mut server = TestServer::new(Backend::new);
+
+    let req = build_initialize(1);
+    server.send_request(req).await;
+    let res = server.recv_response().await;
+    assert!(res.is_ok());
+
+    let req = build_initialized();
+    server.send_request(req).await;
+    let res = server.recv_notification().await;
+    assert_eq!(res.method(), "window/logMessage");
+
+    let code = "module A (\n    a: input logic,\n    b: output logic,\n) {\n    assign b = a;\n}\n";
+    let req = build_did_open(code);
+    server.send_request(req).await;
+
+    let res = server.recv_notification().await;
+    assert_eq!(res.params().unwrap()["message"], "did_open");
+
+    let res = server.recv_notification().await;
+    assert_eq!(res.method(), "textDocument/publishDiagnostics");
+
+    let req = build_text_document_request(2, "textDocument/semanticTokens/full", json!({}));
+    server.send_request(req).await;
+    let res = server.recv_response_of(2).await;
+    let result_id = res["result"]["resultId"].clone();
+    assert_eq!(res["result"]["data"].as_array().unwrap().len(), 4 * 5);
+
+    let params = json!({"previousResultId": result_id});
+    let req = build_text_document_request(3, "textDocument/semanticTokens/full/delta", params);
+    server.send_request(req).await;
+    let res = server.recv_response_of(3).await;
+    assert_ne!(res["result"]["resultId"], result_id);
+    assert_eq!(res["result"]["edits"], json!([]));
+
+    let params = json!({"previousResultId": "unknown"});
+    let req = build_text_document_request(4, "textDocument/semanticTokens/full/delta", params);
+    server.send_request(req).await;
+    let res = server.recv_response_of(4).await;
+    assert_eq!(res["result"]["data"].as_array().unwrap().len(), 4 * 5);
+
+    let params = json!({"range": Range::new(Position::new(4, 0), Position::new(5, 0))});
+    let req = build_text_document_request(5, "textDocument/semanticTokens/range", params);
+    server.send_request(req).await;
+    let res = server.recv_response_of(5).await;
+    assert_eq!(res["result"]["data"].as_array().unwrap().len(), 2 * 5);
+}
+
+#[tokio::test]
+async fn code_lens() {
+    let mut server = TestServer::new(Backend::new);
+
+    let req = build_initialize(1);
+    server.send_request(req).await;
+    let res = server.recv_response().await;
+    assert!(res.is_ok());
+
+    let req = build_initialized();
+    server.send_request(req).await;
+    let res = server.recv_notification().await;
+    assert_eq!(res.method(), "window/logMessage");
+
+    let code = [
+        "module A {}",
+        "",
+        "#[test(test_a)]",
+        "embed (inline) sv{{{",
+        "    module test_a;",
+        "    endmodule",
+        "}}}",
+    ];
+    let req = build_did_open(&code.join("\n"));
+    server.send_request(req).await;
+
+    let res = server.recv_notification().await;
+    assert_eq!(res.params().unwrap()["message"], "did_open");
+
+    let res = server.recv_notification().await;
+    assert_eq!(res.method(), "textDocument/publishDiagnostics");
+
+    let req = build_text_document_request(2, "textDocument/codeLens", json!({}));
+    server.send_request(req).await;
+    let res = server.recv_response_of(2).await;
+    let lenses = res["result"].as_array().unwrap();
+    assert_eq!(lenses.len(), 2);
+    assert_eq!(
+        lenses[0]["range"],
+        json!(Range::new(Position::new(2, 7), Position::new(2, 13)))
+    );
+    assert_eq!(lenses[0]["command"]["command"], json!("veryl.runTest"));
+    assert_eq!(
+        lenses[1]["command"]["command"],
+        json!("veryl.runTestWithWave")
+    );
+    assert_eq!(lenses[1]["command"]["arguments"][1], json!("test_a"));
+}
+
+fn build_inlay_hint(id: i64, line: u32) -> Request {
+    let mut path = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    path.pop();
+    path.pop();
+    path.push("test.veryl");
+    let uri = Url::from_file_path(path).unwrap();
+    let params = InlayHintParams {
+        text_document: TextDocumentIdentifier { uri },
+        range: Range::new(Position::new(0, 0), Position::new(line, 0)),
+        work_done_progress_params: Default::default(),
+    };
+
+    Request::build("textDocument/inlayHint")
+        .params(json!(params))
+        .id(id)
+        .finish()
+}
+
+#[tokio::test]
+async fn inlay_hint() {
+    let mut server = TestServer::new(Backend::new);
+
+    let req = build_initialize(1);
+    server.send_request(req).await;
+    let res = server.recv_response().await;
+    assert!(res.is_ok());
+
+    let req = build_initialized();
+    server.send_request(req).await;
+    let res = server.recv_notification().await;
+    assert_eq!(res.method(), "window/logMessage");
+
+    let code = "module A { const W: u32 = 2 * 4; var a: logic<W>; inst u: B (x: a); }\nmodule B (x: input logic<8>) {}";
+    let req = build_did_open(code);
+    server.send_request(req).await;
+
+    let res = server.recv_notification().await;
+    assert_eq!(res.params().unwrap()["message"], "did_open");
+
+    let res = server.recv_notification().await;
+    assert_eq!(res.method(), "textDocument/publishDiagnostics");
+
+    let req = build_inlay_hint(2, 2);
+    server.send_request(req).await;
+
+    let res = server.recv_response_of(2).await;
+    let hints = res["result"].as_array().unwrap();
+    assert_eq!(hints.len(), 3);
+    assert_eq!(hints[0]["label"], Value::from("= 8"));
+    assert_eq!(
+        hints[0]["position"]["character"],
+        Value::from(code.find(';').unwrap())
+    );
+    assert_eq!(hints[1]["label"], Value::from("8 bits"));
+    assert_eq!(hints[2]["label"], Value::from("input logic<8>"));
+    assert_eq!(
+        hints[2]["position"]["character"],
+        Value::from(code.find("x: a").unwrap())
+    );
+}
+
+#[tokio::test]
+async fn diagnostics() {
+    let mut server = TestServer::new(Backend::new);
+
+    let req = build_initialize(1);
+    server.send_request(req).await;
+    let res = server.recv_response().await;
+    assert!(res.is_ok());
+
+    let req = build_initialized();
+    server.send_request(req).await;
+    let res = server.recv_notification().await;
+    assert_eq!(res.method(), "window/logMessage");
+    assert_eq!(res.params().unwrap()["message"], "server initialized!");
+
+    let req = build_did_open("module A { var a: logic; }");
+    server.send_request(req).await;
+
+    let res = server.recv_notification().await;
+    assert_eq!(res.method(), "window/logMessage");
+    assert_eq!(res.params().unwrap()["message"], "did_open");
+
+    let res = server.recv_notification().await;
+    assert_eq!(res.method(), "textDocument/publishDiagnostics");
+    let diags = res.params().unwrap()["diagnostics"].as_array().unwrap();
+    assert_eq!(diags[0]["code"], Value::from("unused_variable"));
+    assert_eq!(diags[0]["range"]["start"]["character"], Value::from(15));
+    assert_eq!(diags[0]["range"]["start"]["line"], Value::from(0));
+    assert_eq!(diags[0]["range"]["end"]["character"], Value::from(16));
+    assert_eq!(diags[0]["range"]["end"]["line"], Value::from(0));
+}
+
+#[tokio::test]
+async fn progress() {
+    let mut server = TestServer::new(Backend::new);
+
+    let req = build_initialize(1);
+    server.send_request(req).await;
+    let res = server.recv_response().await;
+    assert!(res.is_ok());
+
+    let req = build_initialized();
+    server.send_request(req).await;
+    let res = server.recv_notification().await;
+    assert_eq!(res.method(), "window/logMessage");
+    assert_eq!(res.params().unwrap()["message"], "server initialized!");
+
+    let req = build_did_open("module A {}");
+    server.send_request(req).await;
+
+    let res = server.recv_notification().await;
+    assert_eq!(res.method(), "window/logMessage");
+    assert_eq!(res.params().unwrap()["message"], "did_open");
+
+    let res = server.recv_notification().await;
+    assert_eq!(res.method(), "textDocument/publishDiagnostics");
+
+    let res = server.recv_notification().await;
+    assert_eq!(res.method(), "window/workDoneProgress/create");
+
+    server.send_ack(res.id().unwrap()).await;
+
+    let mut progress_finish = false;
+    let mut percentage = 0;
+    while !progress_finish {
+        let res = server.recv_notification().await;
+        if res.method() == "$/progress" {
+            if res.params().unwrap()["value"]["kind"] == Value::from("end") {
+                progress_finish = true;
+            } else if res.params().unwrap()["value"]["kind"] == Value::from("report") {
+                percentage = res.params().unwrap()["value"]["percentage"]
+                    .as_i64()
+                    .unwrap();
+            }
+        }
+    }
+    assert_eq!(percentage, 100);
+}
+
+#[tokio::test]
+async fn execute_command() {
+    let mut server = TestServer::new(Backend::new);
+
+    let req = build_initialize(1);
+    server.send_request(req).await;
+    let res = server.recv_response().await;
+    assert!(res.is_ok());
+
+    let req = build_initialized();
+    server.send_request(req).await;
+    let res = server.recv_notification().await;
+    assert_eq!(res.method(), "window/logMessage");
+
+    let dir = tempfile::TempDir::new().unwrap();
+    let toml = [
+        "[project]",
+        "name = \"prj\"",
+        "version = \"0.1.0\"",
+        "[build]",
+        "clock_type = \"posedge\"",
+        "reset_type = \"async_low\"",
+        "target = {type = \"directory\", path = \"target\"}",
+        "[test]",
+        "simulator = \"verilator\"",
+    ];
+    std::fs::write(dir.path().join("Veryl.toml"), toml.join("\n")).unwrap();
+    let code = [
+        "module A {}",
+        "",
+        "#[test(test_a)]",
+        "embed (inline) sv{{{",
+        "    module test_a;",
+        "    endmodule",
+        "}}}",
+    ];
+    let path = dir.path().join("src").join("a.veryl");
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(&path, code.join("\n")).unwrap();
+    let uri = Url::from_file_path(&path).unwrap();
+
+    let params = DidOpenTextDocumentParams {
+        text_document: TextDocumentItem {
+            uri: uri.clone(),
+            language_id: "veryl".to_string(),
+            version: 0,
+            text: code.join("\n"),
+        },
+    };
+    let req = Request::build("textDocument/didOpen")
+        .params(json!(params))
+        .finish();
+    server.send_request(req).await;
+
+    let params = ExecuteCommandParams {
+        command: "veryl.runTest".to_string(),
+        arguments: vec![json!(uri), json!("test_a")],
+        work_done_progress_params: Default::default(),
+    };
+    let req = Request::build("workspace/executeCommand")
+        .params(json!(params))
+        .id(2)
+        .finish();
+    server.send_request(req).await;
+
+    // the server should respond other requests while the test is running
+    let params = DocumentSymbolParams {
+        text_document: TextDocumentIdentifier { uri },
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+    let req = Request::build("textDocument/documentSymbol")
+        .params(json!(params))
+        .id(3)
+        .finish();
+    server.send_request(req).await;
+
+    let mut command = None;
+    let mut symbols = None;
+    let mut message = None;
+    let mut progress = Vec::new();
+    while command.is_none() || symbols.is_none() || message.is_none() {
+        let res = server.recv_value().await;
+        if res["id"] == 2 {
+            command = Some(res["result"].clone());
+        } else if res["id"] == 3 {
+            symbols = Some(res["result"].clone());
+        } else if res["method"] == "window/workDoneProgress/create" {
+            let id: Id = serde_json::from_value(res["id"].clone()).unwrap();
+            server.send_ack(&id).await;
+        } else if res["method"] == "$/progress" {
+            progress.push(res["params"]["value"]["message"].clone());
+        } else if res["method"] == "window/showMessage" {
+            message = res["params"]["message"].as_str().map(|x| x.to_string());
+        }
+    }
+
+    assert_eq!(command, Some(Value::Null));
+    assert_eq!(symbols.unwrap()[0]["name"], Value::from("A"));
+    assert!(progress.contains(&Value::from("building")));
+    assert!(progress.contains(&Value::from("running")));
+    let message = message.unwrap();
+    assert!(message.contains("test (test_a)"), "{message}");
+    assert!(!message.contains("test is not found"), "{message}");
+
+    // the project is built into a temporary directory by the worker thread
+    assert!(!dir.path().join("target").join("a.sv").exists());
+    assert!(!dir.path().join("prj.f").exists());
+}
+
+#[tokio::test]
+async fn code_action() {
+    let mut server = TestServer::new(Backend::new);
+
+    let req = build_initialize(1);
+    server.send_request(req).await;
+    let res = server.recv_response().await;
+    assert!(res.is_ok());
+
+    let req = build_initialized();
+    server.send_request(req).await;
+    let res = server.recv_notification().await;
+    assert_eq!(res.method(), "window/logMessage");
+
+    let req = build_did_open("module A { var a: logic; }");
+    server.send_request(req).await;
+
+    let res = server.recv_notification().await;
+    assert_eq!(res.params().unwrap()["message"], "did_open");
+
+    let res = server.recv_notification().await;
+    assert_eq!(res.method(), "textDocument/publishDiagnostics");
+    let diags = res.params().unwrap()["diagnostics"].clone();
+    assert_eq!(diags[0]["code"], Value::from("unused_variable"));
+
+    let range = Range::new(Position::new(0, 15), Position::new(0, 15));
+    let params = json!({"range": range, "context": {"diagnostics": diags}});
+    let req = build_text_document_request(2, "textDocument/codeAction", params);
+    server.send_request(req).await;
+    let res = server.recv_response_of(2).await;
+    let actions = res["result"].as_array().unwrap();
+    let titles: Vec<_> = actions.iter().map(|x| x["title"].clone()).collect();
+    assert_eq!(
+        titles,
+        vec![
+            Value::from("Add prefix `_`"),
+            Value::from("Add `#[allow(unused_variable)]`")
+        ]
+    );
+    assert_eq!(actions[0]["kind"], json!(CodeActionKind::QUICKFIX));
+    assert_eq!(actions[0]["isPreferred"], Value::from(true));
+    assert_eq!(actions[0]["diagnostics"][0]["code"], diags[0]["code"]);
+
+    let uri = json!(build_did_open(""))["params"]["textDocument"]["uri"].clone();
+    let uri = uri.as_str().unwrap();
+    let edit = |new_text: &str, beg: u32, end: u32| {
+        json!({
+            "changes": {
+                uri: [TextEdit {
+                    range: Range::new(Position::new(0, beg), Position::new(0, end)),
+                    new_text: new_text.to_string(),
+                }]
+            }
+        })
+    };
+    assert_eq!(actions[0]["edit"], edit("_a", 15, 16));
+    assert_eq!(
+        actions[1]["edit"],
+        edit("#[allow(unused_variable)] ", 11, 11)
+    );
+
+    // out of the diagnostic range
+    let range = Range::new(Position::new(0, 0), Position::new(0, 6));
+    let params = json!({"range": range, "context": {"diagnostics": []}});
+    let req = build_text_document_request(3, "textDocument/codeAction", params);
+    server.send_request(req).await;
+    let res = server.recv_response_of(3).await;
+    assert_eq!(res["result"], json!([]));
+}
+
+#[tokio::test]
+async fn code_action_suggestion() {
+    let mut server = TestServer::new(Backend::new);
+
+    let req = build_initialize(1);
+    server.send_request(req).await;
+    let res = server.recv_response().await;
+    assert!(res.is_ok());
+
+    let req = build_initialized();
+    server.send_request(req).await;
+    let res = server.recv_notification().await;
+    assert_eq!(res.method(), "window/logMessage");
+
+    let code = [
+        "module ModuleB (",
+        "    a: input  logic,",
+        "    b: output logic,",
+        "    c: output logic,",
+        ") {",
+        "    assign b = a;",
+        "    assign c = a;",
+        "}",
+        "module ModuleC {",
+        "    inst u0: ModuleB (a: 1);",
+        "}",
+    ];
+    let req = build_did_open(&code.join("\n"));
+    server.send_request(req).await;
+
+    let res = server.recv_notification().await;
+    assert_eq!(res.params().unwrap()["message"], "did_open");
+
+    let res = server.recv_notification().await;
+    assert_eq!(res.method(), "textDocument/publishDiagnostics");
+    let diags = res.params().unwrap()["diagnostics"].clone();
+    let codes: Vec<_> = diags
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|x| x["code"].as_str().unwrap())
+        .collect();
+    assert_eq!(codes, vec!["missing_port", "missing_port"]);
+
+    let range = Range::new(Position::new(9, 0), Position::new(9, 28));
+    let params = json!({"range": range, "context": {"diagnostics": diags}});
+    let req = build_text_document_request(2, "textDocument/codeAction", params);
+    server.send_request(req).await;
+    let res = server.recv_response_of(2).await;
+    let actions = res["result"].as_array().unwrap();
+    let edits = |title: &str| -> Vec<Value> {
+        actions
+            .iter()
+            .filter(|x| x["title"] == title)
+            .map(|x| {
+                assert_eq!(x["isPreferred"], Value::from(false));
+                let changes = x["edit"]["changes"].as_object().unwrap();
+                changes.values().next().unwrap().clone()
+            })
+            .collect()
+    };
+
+    // the fix connecting all missing ports is offered once for the instance
+    let position = Position::new(9, 26);
+    assert_eq!(
+        edits("Add missing ports"),
+        vec![json!([TextEdit {
+            range: Range::new(position, position),
+            new_text: ", b: _, c: _".to_string(),
+        }])]
+    );
+}
+
+fn build_rename(id: i64, line: u32, character: u32, new_name: &str) -> Request {
+    let params = json!({"position": Position::new(line, character), "newName": new_name});
+    build_text_document_request(id, "textDocument/rename", params)
+}
+
+fn build_prepare_rename(id: i64, line: u32, character: u32) -> Request {
+    let params = json!({"position": Position::new(line, character)});
+    build_text_document_request(id, "textDocument/prepareRename", params)
+}
+
+/// Positions of edits in each file, sorted
+fn rename_edits(res: &Value, new_name: &str) -> Vec<(String, u32, u32)> {
+    let mut ret = Vec::new();
+    for (uri, edits) in res["result"]["changes"].as_object().unwrap() {
+        let file = uri.rsplit('/').next().unwrap().to_string();
+        for edit in edits.as_array().unwrap() {
+            assert_eq!(edit["newText"], new_name);
+            let start = &edit["range"]["start"];
+            let line = start["line"].as_u64().unwrap() as u32;
+            let character = start["character"].as_u64().unwrap() as u32;
+            ret.push((file.clone(), line, character));
+        }
+    }
+    ret.sort();
+    ret
+}
+
+#[tokio::test]
+async fn rename() {
+    let mut server = TestServer::new(Backend::new);
+
+    let req = build_initialize(1);
+    server.send_request(req).await;
+    let res = server.recv_response().await;
+    assert!(res.is_ok());
+
+    let req = build_initialized();
+    server.send_request(req).await;
+    let res = server.recv_notification().await;
+    assert_eq!(res.method(), "window/logMessage");
+
+    let code = [
+        "module A::<W: const> (",
+        "    a: input  logic<W>,",
+        "    b: output logic<W>,",
+        ") {",
+        "    assign b = a;",
+        "}",
+        "module B {",
+        "    var x: logic<2>;",
+        "    inst u: A::<2> (a: 1, b: x);",
+        "}",
+    ];
+    let req = build_did_open(&code.join("\n"));
+    server.send_request(req).await;
+
+    let res = server.recv_notification().await;
+    assert_eq!(res.params().unwrap()["message"], "did_open");
+
+    let res = server.recv_notification().await;
+    assert_eq!(res.method(), "textDocument/publishDiagnostics");
+
+    let file = |line, character| ("test.veryl".to_string(), line, character);
+
+    // port is renamed with its connection of instance
+    let req = build_rename(2, 1, 4, "c");
+    server.send_request(req).await;
+    let res = server.recv_response_of(2).await;
+    let port = vec![file(1, 4), file(4, 15), file(8, 20)];
+    assert_eq!(rename_edits(&res, "c"), port);
+
+    // rename from connection of instance
+    let req = build_rename(3, 8, 20, "c");
+    server.send_request(req).await;
+    let res = server.recv_response_of(3).await;
+    assert_eq!(rename_edits(&res, "c"), port);
+
+    // generic parameter
+    let req = build_rename(4, 0, 11, "N");
+    server.send_request(req).await;
+    let res = server.recv_response_of(4).await;
+    let param = vec![file(0, 11), file(1, 20), file(2, 20)];
+    assert_eq!(rename_edits(&res, "N"), param);
+
+    let req = build_rename(5, 1, 4, "module");
+    server.send_request(req).await;
+    let res = server.recv_response_of(5).await;
+    assert_eq!(res["error"]["message"], "\"module\" is a keyword");
+
+    let req = build_prepare_rename(6, 1, 4);
+    server.send_request(req).await;
+    let res = server.recv_response_of(6).await;
+    assert_eq!(
+        res["result"],
+        json!(Range::new(Position::new(1, 4), Position::new(1, 5)))
+    );
+
+    // keyword can't be renamed
+    let req = build_prepare_rename(7, 0, 0);
+    server.send_request(req).await;
+    let res = server.recv_response_of(7).await;
+    assert_eq!(res["result"], Value::Null);
+
+    let req = build_rename(8, 0, 0, "c");
+    server.send_request(req).await;
+    let res = server.recv_response_of(8).await;
+    assert_eq!(res["error"]["message"], "this element can't be renamed");
+}
+
+#[tokio::test]
+async fn rename_implicit_connection() {
+    let mut server = TestServer::new(Backend::new);
+
+    let req = build_initialize(1);
+    server.send_request(req).await;
+    let res = server.recv_response().await;
+    assert!(res.is_ok());
+
+    let req = build_initialized();
+    server.send_request(req).await;
+    let res = server.recv_notification().await;
+    assert_eq!(res.method(), "window/logMessage");
+
+    let code = [
+        "module A (",
+        "    a: input  logic,",
+        "    b: output logic,",
+        ") {",
+        "    assign b = a;",
+        "}",
+        "module B {",
+        "    var a: logic;",
+        "    var b: logic;",
+        "    assign a = 1;",
+        "    inst u: A (a, b);",
+        "}",
+    ];
+    let req = build_did_open(&code.join("\n"));
+    server.send_request(req).await;
+
+    let res = server.recv_notification().await;
+    assert_eq!(res.params().unwrap()["message"], "did_open");
+
+    let res = server.recv_notification().await;
+    assert_eq!(res.method(), "textDocument/publishDiagnostics");
+
+    let edits = |res: &Value| {
+        let mut ret: Vec<_> = res["result"]["changes"]
+            .as_object()
+            .unwrap()
+            .values()
+            .flat_map(|x| x.as_array().unwrap().clone())
+            .map(|x| {
+                let start = &x["range"]["start"];
+                (
+                    start["line"].as_u64().unwrap(),
+                    start["character"].as_u64().unwrap(),
+                    x["newText"].as_str().unwrap().to_string(),
+                )
+            })
+            .collect();
+        ret.sort();
+        ret
+    };
+
+    // port name is added to the connection
+    let req = build_rename(2, 1, 4, "c");
+    server.send_request(req).await;
+    let res = server.recv_response_of(2).await;
+    assert_eq!(
+        edits(&res),
+        vec![
+            (1, 4, "c".to_string()),
+            (4, 15, "c".to_string()),
+            (10, 15, "c: a".to_string()),
+        ]
+    );
+
+    // connected variable is renamed without changing the port
+    let req = build_rename(3, 7, 8, "x");
+    server.send_request(req).await;
+    let res = server.recv_response_of(3).await;
+    assert_eq!(
+        edits(&res),
+        vec![
+            (7, 8, "x".to_string()),
+            (9, 11, "x".to_string()),
+            (10, 15, "a: x".to_string()),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn rename_across_files() {
+    let mut server = TestServer::new(Backend::new);
+
+    let req = build_initialize(1);
+    server.send_request(req).await;
+    let res = server.recv_response().await;
+    assert!(res.is_ok());
+
+    let req = build_initialized();
+    server.send_request(req).await;
+    let res = server.recv_notification().await;
+    assert_eq!(res.method(), "window/logMessage");
+
+    let dir = tempfile::TempDir::new().unwrap();
+    let toml = [
+        "[project]",
+        "name = \"prj\"",
+        "version = \"0.1.0\"",
+        "[build]",
+        "clock_type = \"posedge\"",
+        "reset_type = \"async_low\"",
+        "target = {type = \"directory\", path = \"target\"}",
+    ];
+    std::fs::write(dir.path().join("Veryl.toml"), toml.join("\n")).unwrap();
+    std::fs::create_dir_all(dir.path().join("src")).unwrap();
+
+    let pkg = ["package PkgA {", "    const X: u32 = 1;", "}"];
+    let top = [
+        "module Top (",
+        "    i_clk: input  clock,",
+        "    i_rst: input  reset,",
+        "    i_d  : input  logic,",
+        "    o_d  : output logic,",
+        ") {",
+        "    let _a: u32 = PkgA::X;",
+        "    inst u: $std::delay (",
+        "        i_clk,",
+        "        i_rst,",
+        "        i_d,",
+        "        o_d,",
+        "    );",
+        "}",
+    ];
+    let mut uris = Vec::new();
+    for (name, code) in [("pkg.veryl", &pkg[..]), ("top.veryl", &top[..])] {
+        let path = dir.path().join("src").join(name);
+        std::fs::write(&path, code.join("\n")).unwrap();
+        let uri = Url::from_file_path(&path).unwrap();
+        let params = DidOpenTextDocumentParams {
+            text_document: TextDocumentItem {
+                uri: uri.clone(),
+                language_id: "veryl".to_string(),
+                version: 0,
+                text: code.join("\n"),
+            },
+        };
+        let req = Request::build("textDocument/didOpen")
+            .params(json!(params))
+            .finish();
+        server.send_request(req).await;
+        uris.push(uri);
+    }
+
+    // wait for background analysis which loads the standard library
+    loop {
+        let res = server.recv_value().await;
+        if res["method"] == "window/workDoneProgress/create" {
+            let id: Id = serde_json::from_value(res["id"].clone()).unwrap();
+            server.send_ack(&id).await;
+        } else if res["method"] == "$/progress" && res["params"]["value"]["kind"] == "end" {
+            break;
+        }
+    }
+
+    let params = |line, character| {
+        json!({
+            "textDocument": TextDocumentIdentifier { uri: uris[1].clone() },
+            "position": Position::new(line, character),
+        })
+    };
+
+    // package member is renamed in all files
+    let mut x = params(6, 24);
+    x["newName"] = json!("Y");
+    let req = Request::build("textDocument/rename")
+        .params(x)
+        .id(2)
+        .finish();
+    server.send_request(req).await;
+    let res = server.recv_response_of(2).await;
+    assert_eq!(
+        rename_edits(&res, "Y"),
+        vec![
+            ("pkg.veryl".to_string(), 1, 10),
+            ("top.veryl".to_string(), 6, 24)
+        ]
+    );
+
+    // package in the path
+    let x = params(6, 19);
+    let req = Request::build("textDocument/prepareRename")
+        .params(x)
+        .id(3)
+        .finish();
+    server.send_request(req).await;
+    let res = server.recv_response_of(3).await;
+    assert_eq!(
+        res["result"],
+        json!(Range::new(Position::new(6, 18), Position::new(6, 22)))
+    );
+
+    // symbols of the standard library can't be renamed
+    let x = params(7, 19);
+    let req = Request::build("textDocument/definition")
+        .params(x)
+        .id(4)
+        .finish();
+    server.send_request(req).await;
+    let res = server.recv_response_of(4).await;
+    let uri = res["result"]["uri"].as_str().unwrap();
+    assert!(uri.ends_with("delay.veryl"), "{uri}");
+
+    let x = params(7, 19);
+    let req = Request::build("textDocument/prepareRename")
+        .params(x)
+        .id(5)
+        .finish();
+    server.send_request(req).await;
+    let res = server.recv_response_of(5).await;
+    assert_eq!(res["result"], Value::Null);
 }
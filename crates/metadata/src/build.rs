@@ -32,6 +32,10 @@ pub struct Build {
     pub expand_inside_operation: bool,
     #[serde(default)]
     pub exclude_std: bool,
+    #[serde(default)]
+    pub target_language: TargetLanguage,
+    #[serde(default)]
+    pub flatten_interface_ports: bool,
 }
 
 #[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
@@ -79,6 +83,27 @@ pub enum Target {
     Bundle { path: PathBuf },
 }
 
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum TargetLanguage {
+    #[default]
+    #[serde(rename = "systemverilog")]
+    SystemVerilog,
+    #[serde(rename = "vhdl")]
+    Vhdl,
+    #[serde(rename = "verilog")]
+    Verilog,
+}
+
+impl TargetLanguage {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            TargetLanguage::SystemVerilog => "sv",
+            TargetLanguage::Vhdl => "vhd",
+            TargetLanguage::Verilog => "v",
+        }
+    }
+}
+
 #[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(tag = "type")]
 pub enum SourceMapTarget {
@@ -12,10 +12,12 @@ mod publish;
 mod test;
 #[cfg(test)]
 mod tests;
-pub use build::{Build, BuiltinType, ClockType, FilelistType, ResetType, SourceMapTarget, Target};
+pub use build::{
+    Build, BuiltinType, ClockType, FilelistType, ResetType, SourceMapTarget, Target, TargetLanguage,
+};
 pub use doc::Doc;
 pub use format::Format;
-pub use lint::{Case, Lint};
+pub use lint::{Case, Lint, LintReset, LintWidth};
 pub use lockfile::Lockfile;
 pub use metadata::{BumpKind, Metadata};
 pub use metadata_error::MetadataError;
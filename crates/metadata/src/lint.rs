@@ -7,6 +7,53 @@ use std::fmt;
 pub struct Lint {
     #[serde(default)]
     pub naming: LintNaming,
+    #[serde(default)]
+    pub width: LintWidth,
+    #[serde(default)]
+    pub reset: LintReset,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LintWidth {
+    #[serde(default = "default_true")]
+    pub implicit_truncation: bool,
+    #[serde(default)]
+    pub signed_zero_extension: bool,
+    #[serde(default = "default_true")]
+    pub mismatch_port_width: bool,
+}
+
+impl Default for LintWidth {
+    fn default() -> Self {
+        Self {
+            implicit_truncation: true,
+            signed_zero_extension: false,
+            mismatch_port_width: true,
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LintReset {
+    #[serde(default = "default_true")]
+    pub reset_domain_crossing: bool,
+    #[serde(default = "default_true")]
+    pub unsynchronized_reset: bool,
+}
+
+impl Default for LintReset {
+    fn default() -> Self {
+        Self {
+            reset_domain_crossing: true,
+            unsynchronized_reset: true,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
@@ -152,7 +152,7 @@ impl Lockfile {
         Ok(modified)
     }
 
-    pub fn paths(&self, base_dst: &Path) -> Result<Vec<PathPair>, MetadataError> {
+    pub fn paths(&self, base_dst: &Path, ext: &str) -> Result<Vec<PathPair>, MetadataError> {
         let mut ret = Vec::new();
 
         for locks in self.lock_table.values() {
@@ -164,7 +164,7 @@ impl Lockfile {
                     let rel = src.strip_prefix(&path)?;
                     let mut dst = base_dst.join(&lock.name);
                     dst.push(rel);
-                    dst.set_extension("sv");
+                    dst.set_extension(ext);
                     ret.push(PathPair {
                         prj: lock.name.clone(),
                         src: src.to_path_buf(),
@@ -232,15 +232,17 @@ impl Metadata {
             ret
         };
 
+        let ext = self.build.target_language.extension();
+
         let mut ret = Vec::new();
         for src in src_files {
             let dst = match self.build.target {
-                Target::Source => src.with_extension("sv"),
+                Target::Source => src.with_extension(ext),
                 Target::Directory { ref path } => {
-                    base.join(path.join(src.with_extension("sv").file_name().unwrap()))
+                    base.join(path.join(src.with_extension(ext).file_name().unwrap()))
                 }
                 Target::Bundle { .. } => base.join(
-                    PathBuf::from("target").join(src.with_extension("sv").file_name().unwrap()),
+                    PathBuf::from("target").join(src.with_extension(ext).file_name().unwrap()),
                 ),
             };
             ret.push(PathPair {
@@ -257,12 +259,12 @@ impl Metadata {
 
         if !self.build.exclude_std {
             veryl_std::expand()?;
-            ret.append(&mut veryl_std::paths(&base_dst)?);
+            ret.append(&mut veryl_std::paths(&base_dst, ext)?);
         }
 
         self.update_lockfile()?;
 
-        let mut deps = self.lockfile.paths(&base_dst)?;
+        let mut deps = self.lockfile.paths(&base_dst, ext)?;
         ret.append(&mut deps);
 
         Ok(ret)
@@ -140,9 +140,41 @@ fn check_toml() {
     assert!(metadata.build.reset_high_suffix.is_none());
     assert!(metadata.build.reset_low_prefix.is_none());
     assert_eq!(metadata.build.reset_low_suffix.unwrap(), "_n");
+    assert_eq!(
+        metadata.build.target_language,
+        TargetLanguage::SystemVerilog
+    );
     assert_eq!(metadata.format.indent_width, 4);
 }
 
+#[test]
+fn lint_width() {
+    let metadata: Metadata = toml::from_str(TEST_TOML).unwrap();
+    assert!(metadata.lint.width.implicit_truncation);
+    assert!(!metadata.lint.width.signed_zero_extension);
+    assert!(metadata.lint.width.mismatch_port_width);
+
+    let toml = format!(
+        "{TEST_TOML}\n[lint.width]\nimplicit_truncation = false\nsigned_zero_extension = true\n"
+    );
+    let metadata: Metadata = toml::from_str(&toml).unwrap();
+    assert!(!metadata.lint.width.implicit_truncation);
+    assert!(metadata.lint.width.signed_zero_extension);
+    assert!(metadata.lint.width.mismatch_port_width);
+}
+
+#[test]
+fn lint_reset() {
+    let lint = Lint::default();
+    assert!(lint.reset.reset_domain_crossing);
+    assert!(lint.reset.unsynchronized_reset);
+
+    // Omitted keys keep enabled
+    let lint: Lint = toml::from_str("[reset]\nreset_domain_crossing = false\n").unwrap();
+    assert!(!lint.reset.reset_domain_crossing);
+    assert!(lint.reset.unsynchronized_reset);
+}
+
 #[test]
 fn search_config() {
     let path = Metadata::search_from_current();
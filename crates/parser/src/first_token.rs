@@ -0,0 +1,26 @@
+use crate::veryl_token::{Token, VerylToken};
+use crate::veryl_walker::VerylWalker;
+
+#[derive(Default)]
+pub struct FirstToken {
+    token: Option<Token>,
+}
+
+impl FirstToken {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn token(&self) -> &Option<Token> {
+        &self.token
+    }
+}
+
+impl VerylWalker for FirstToken {
+    /// Semantic action for non-terminal 'VerylToken'
+    fn veryl_token(&mut self, arg: &VerylToken) {
+        if self.token.is_none() {
+            self.token = Some(arg.token);
+        }
+    }
+}
@@ -1,5 +1,6 @@
 pub mod doc_comment_table;
 pub mod finder;
+pub mod first_token;
 pub mod generated;
 pub mod last_token;
 pub mod parser;
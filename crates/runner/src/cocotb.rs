@@ -1,4 +1,4 @@
-use crate::runner::Runner;
+use crate::{Reporter, Runner};
 use futures::prelude::*;
 use log::{error, info};
 use miette::{IntoDiagnostic, Result, WrapErr};
@@ -29,6 +29,7 @@ pub struct Cocotb {
     source: CocotbSource,
     state: State,
     success: bool,
+    reporter: Option<Reporter>,
 }
 
 impl Cocotb {
@@ -37,6 +38,7 @@ impl Cocotb {
             source,
             state: State::Idle,
             success: true,
+            reporter: None,
         }
     }
 
@@ -209,4 +211,12 @@ runner.test(
     fn failure(&mut self) {
         self.success = false;
     }
+
+    fn set_reporter(&mut self, reporter: Reporter) {
+        self.reporter = Some(reporter);
+    }
+
+    fn reporter(&self) -> Option<&Reporter> {
+        self.reporter.as_ref()
+    }
 }
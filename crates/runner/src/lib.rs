@@ -18,6 +18,17 @@ pub use vcs::*;
 pub use verilator::*;
 pub use vivado::*;
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MessageLevel {
+    Info,
+    Warning,
+    Error,
+    Fatal,
+}
+
+/// Callback receiving simulator messages instead of the console
+pub type Reporter = Box<dyn Fn(MessageLevel, &str) + Send>;
+
 pub trait Runner {
     fn run(
         &mut self,
@@ -32,6 +43,18 @@ pub trait Runner {
 
     fn failure(&mut self);
 
+    fn set_reporter(&mut self, reporter: Reporter);
+
+    fn reporter(&self) -> Option<&Reporter>;
+
+    fn report(&self, level: MessageLevel, line: &str, style: &Style) {
+        if let Some(reporter) = self.reporter() {
+            reporter(level, line);
+        } else if !log_enabled!(Level::Debug) {
+            println!("{}{}{}", style.render(), line, style.render_reset());
+        }
+    }
+
     fn debug(&self, line: &str) {
         if log_enabled!(Level::Debug) {
             debug!("{} : {}", self.name(), line);
@@ -41,34 +64,26 @@ pub trait Runner {
     fn info(&self, line: &str) {
         static STYLE: Lazy<Style> =
             Lazy::new(|| Style::new().fg_color(Some(AnsiColor::Green.into())));
-        if !log_enabled!(Level::Debug) {
-            println!("{}{}{}", STYLE.render(), line, STYLE.render_reset());
-        }
+        self.report(MessageLevel::Info, line, &STYLE);
     }
 
     fn warning(&mut self, line: &str) {
         static STYLE: Lazy<Style> =
             Lazy::new(|| Style::new().fg_color(Some(AnsiColor::Yellow.into())));
-        if !log_enabled!(Level::Debug) {
-            println!("{}{}{}", STYLE.render(), line, STYLE.render_reset());
-        }
+        self.report(MessageLevel::Warning, line, &STYLE);
     }
 
     fn error(&mut self, line: &str) {
         static STYLE: Lazy<Style> =
             Lazy::new(|| Style::new().fg_color(Some(AnsiColor::Red.into())));
-        if !log_enabled!(Level::Debug) {
-            println!("{}{}{}", STYLE.render(), line, STYLE.render_reset());
-        }
+        self.report(MessageLevel::Error, line, &STYLE);
         self.failure();
     }
 
     fn fatal(&mut self, line: &str) {
         static STYLE: Lazy<Style> =
             Lazy::new(|| Style::new().fg_color(Some(AnsiColor::Red.into())).bold());
-        if !log_enabled!(Level::Debug) {
-            println!("{}{}{}", STYLE.render(), line, STYLE.render_reset());
-        }
+        self.report(MessageLevel::Fatal, line, &STYLE);
         self.failure();
     }
 }
@@ -100,6 +115,16 @@ pub fn remap_msg_by_regex(line: &str, re: &Regex) -> String {
     ret
 }
 
+/// Source location appended by `remap_msg_by_regex`
+pub fn remapped_location(msg: &str) -> Option<(PathBuf, u32, u32)> {
+    let (_, location) = msg.rsplit_once("^ from: ")?;
+    let mut location = location.lines().next()?.rsplitn(3, ':');
+    let column = location.next()?.parse().ok()?;
+    let line = location.next()?.parse().ok()?;
+    let path = PathBuf::from(location.next()?);
+    Some((path, line, column))
+}
+
 pub fn copy_wave(
     test_name: StrId,
     test_path: PathId,
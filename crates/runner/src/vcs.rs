@@ -1,4 +1,4 @@
-use crate::runner::{copy_wave, remap_msg_by_regex, Runner};
+use crate::{copy_wave, remap_msg_by_regex, Reporter, Runner};
 use futures::prelude::*;
 use log::{error, info};
 use miette::{IntoDiagnostic, Result, WrapErr};
@@ -25,6 +25,7 @@ enum State {
 pub struct Vcs {
     state: State,
     success: bool,
+    reporter: Option<Reporter>,
 }
 
 fn remap_msg(line: &str) -> String {
@@ -39,6 +40,7 @@ impl Vcs {
         Self {
             state: State::Idle,
             success: true,
+            reporter: None,
         }
     }
 
@@ -111,6 +113,12 @@ impl Vcs {
     }
 }
 
+impl Default for Vcs {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Runner for Vcs {
     fn run(
         &mut self,
@@ -197,4 +205,12 @@ impl Runner for Vcs {
     fn failure(&mut self) {
         self.success = false;
     }
+
+    fn set_reporter(&mut self, reporter: Reporter) {
+        self.reporter = Some(reporter);
+    }
+
+    fn reporter(&self) -> Option<&Reporter> {
+        self.reporter.as_ref()
+    }
 }
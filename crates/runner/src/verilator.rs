@@ -1,4 +1,4 @@
-use crate::runner::{copy_wave, remap_msg_by_regex, Runner};
+use crate::{copy_wave, remap_msg_by_regex, Reporter, Runner};
 use futures::prelude::*;
 use log::{error, info};
 use miette::{IntoDiagnostic, Result, WrapErr};
@@ -21,6 +21,7 @@ enum State {
 pub struct Verilator {
     state: State,
     success: bool,
+    reporter: Option<Reporter>,
 }
 
 fn parse_msg(line: &str) -> String {
@@ -46,6 +47,7 @@ impl Verilator {
         Self {
             state: State::Idle,
             success: true,
+            reporter: None,
         }
     }
 
@@ -121,6 +123,12 @@ impl Verilator {
     }
 }
 
+impl Default for Verilator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Runner for Verilator {
     fn run(
         &mut self,
@@ -215,4 +223,12 @@ impl Runner for Verilator {
     fn failure(&mut self) {
         self.success = false;
     }
+
+    fn set_reporter(&mut self, reporter: Reporter) {
+        self.reporter = Some(reporter);
+    }
+
+    fn reporter(&self) -> Option<&Reporter> {
+        self.reporter.as_ref()
+    }
 }
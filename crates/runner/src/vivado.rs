@@ -1,4 +1,4 @@
-use crate::runner::{copy_wave, remap_msg_by_regex, Runner};
+use crate::{copy_wave, remap_msg_by_regex, Reporter, Runner};
 use futures::prelude::*;
 use log::{error, info};
 use miette::{IntoDiagnostic, Result, WrapErr};
@@ -23,6 +23,7 @@ enum State {
 pub struct Vivado {
     state: State,
     success: bool,
+    reporter: Option<Reporter>,
 }
 
 fn remap_msg(line: &str) -> String {
@@ -38,6 +39,7 @@ impl Vivado {
         Self {
             state: State::Idle,
             success: true,
+            reporter: None,
         }
     }
 
@@ -94,6 +96,12 @@ impl Vivado {
     }
 }
 
+impl Default for Vivado {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Runner for Vivado {
     fn run(
         &mut self,
@@ -214,4 +222,12 @@ impl Runner for Vivado {
     fn failure(&mut self) {
         self.success = false;
     }
+
+    fn set_reporter(&mut self, reporter: Reporter) {
+        self.reporter = Some(reporter);
+    }
+
+    fn reporter(&self) -> Option<&Reporter> {
+        self.reporter.as_ref()
+    }
 }
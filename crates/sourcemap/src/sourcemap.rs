@@ -5,6 +5,7 @@ use std::fs;
 use std::path::{Path, PathBuf};
 
 const LINK_HEADER: &str = "//# sourceMappingURL=";
+const VHDL_LINK_HEADER: &str = "--# sourceMappingURL=";
 
 pub struct SourceMap {
     pub src_path: PathBuf,
@@ -47,8 +48,10 @@ impl SourceMap {
         let src = fs::read_to_string(src_path)?;
 
         if let Some(line) = src.lines().last() {
-            if line.starts_with(LINK_HEADER) {
-                let map_path = line.strip_prefix(LINK_HEADER).unwrap();
+            let map_path = line
+                .strip_prefix(LINK_HEADER)
+                .or_else(|| line.strip_prefix(VHDL_LINK_HEADER));
+            if let Some(map_path) = map_path {
                 let map_path = src_path.parent().unwrap().join(map_path);
                 let text = fs::read(&map_path)?;
 
@@ -116,6 +119,10 @@ impl SourceMap {
         format!("{}{}", LINK_HEADER, self.map_path_from_dst)
     }
 
+    pub fn get_vhdl_link(&self) -> String {
+        format!("{}{}", VHDL_LINK_HEADER, self.map_path_from_dst)
+    }
+
     pub fn to_bytes(&self) -> Result<Vec<u8>, SourceMapError> {
         if let Some(ref x) = self.source_map {
             let mut ret = Vec::new();
@@ -40,7 +40,7 @@ pub fn expand() -> Result<(), PathError> {
     Ok(())
 }
 
-pub fn paths(base_dst: &Path) -> Result<Vec<PathPair>, PathError> {
+pub fn paths(base_dst: &Path, ext: &str) -> Result<Vec<PathPair>, PathError> {
     let mut ret = Vec::new();
     let std_dir = std_dir().canonicalize().unwrap();
 
@@ -48,7 +48,7 @@ pub fn paths(base_dst: &Path) -> Result<Vec<PathPair>, PathError> {
         let rel = src.strip_prefix(&std_dir)?;
         let mut dst = base_dst.join("std");
         dst.push(rel);
-        dst.set_extension("sv");
+        dst.set_extension(ext);
         ret.push(PathPair {
             prj: "$std".to_string(),
             src: src.to_path_buf(),
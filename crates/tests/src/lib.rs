@@ -158,3 +158,231 @@ mod emitter {
 
     include!(concat!(env!("OUT_DIR"), "/test.rs"));
 }
+
+#[cfg(test)]
+mod verilog {
+    use std::fs;
+    use std::path::PathBuf;
+    use veryl_analyzer::{Analyzer, AnalyzerError};
+    use veryl_emitter::Emitter;
+    use veryl_metadata::{Metadata, SourceMapTarget, TargetLanguage};
+    use veryl_parser::Parser;
+
+    /// Testcases including features which can't be emitted to Verilog.
+    /// They should be rejected by analyzer instead of emitting broken Verilog.
+    const UNSUPPORTED_TESTS: [&str; 4] = [
+        "04_module",
+        "39_modport",
+        "66_modport_connect",
+        "70_interface_connect",
+    ];
+
+    fn test(name: &str) {
+        let metadata_path = Metadata::search_from_current().unwrap();
+        let mut metadata = Metadata::load(&metadata_path).unwrap();
+        metadata.build.target_language = TargetLanguage::Verilog;
+        metadata.build.sourcemap_target = SourceMapTarget::None;
+
+        if crate::DEPENDENCY_TESTS.contains(&name) {
+            let paths = metadata.paths::<&str>(&[], false).unwrap();
+            let cache_path = veryl_path::cache_path().canonicalize().unwrap();
+            for path in paths {
+                if path.src.starts_with(&cache_path) {
+                    let input = fs::read_to_string(&path.src).unwrap();
+                    let ret = Parser::parse(&input, &path.src).unwrap();
+                    let analyzer = Analyzer::new(&metadata);
+                    let _ = analyzer.analyze_pass1(&path.prj, &input, &path.src, &ret.veryl);
+                }
+            }
+        }
+
+        let src_path = PathBuf::from(format!("../../testcases/veryl/{}.veryl", name));
+        let dst_path = PathBuf::from(format!("../../testcases/verilog/{}.v", name));
+        let map_path = PathBuf::from(format!(
+            "../../testcases/map/testcases/verilog/{}.v.map",
+            name
+        ));
+
+        let input = fs::read_to_string(&src_path).unwrap();
+        let ret = Parser::parse(&input, &src_path).unwrap();
+        let prj = &metadata.project.name;
+        let analyzer = Analyzer::new(&metadata);
+        let _ = analyzer.analyze_pass1(&prj, &input, &src_path, &ret.veryl);
+        let errors = analyzer.analyze_pass2(&prj, &input, &src_path, &ret.veryl);
+
+        if UNSUPPORTED_TESTS.contains(&name) {
+            assert!(!errors.is_empty());
+            assert!(
+                errors
+                    .iter()
+                    .all(|x| matches!(x, AnalyzerError::UnsupportedByTarget { .. })),
+                "{errors:?}"
+            );
+            assert!(!dst_path.exists());
+            return;
+        }
+
+        assert!(errors.is_empty(), "{errors:?}");
+
+        let mut emitter = Emitter::new(&metadata, &src_path, &dst_path, &map_path);
+        emitter.emit(&prj, &ret.veryl);
+
+        let out_code = emitter.as_str();
+        let ref_code = fs::read_to_string(&dst_path).unwrap();
+
+        assert_eq!(ref_code, out_code);
+    }
+
+    include!(concat!(env!("OUT_DIR"), "/test.rs"));
+}
+
+#[cfg(test)]
+mod vhdl {
+    use std::fs;
+    use std::path::PathBuf;
+    use std::process::Command;
+    use veryl_analyzer::{Analyzer, AnalyzerError};
+    use veryl_emitter::VhdlEmitter;
+    use veryl_metadata::{Metadata, SourceMapTarget, TargetLanguage};
+    use veryl_parser::Parser;
+
+    /// Testcases including features which can't be emitted to VHDL.
+    /// They should be rejected by analyzer instead of emitting broken VHDL.
+    const UNSUPPORTED_TESTS: [&str; 11] = [
+        "31_initial_final",
+        "39_modport",
+        "41_union",
+        "47_embed",
+        "49_system_function",
+        "52_include",
+        "54_generic_function",
+        "55_generic_module",
+        "57_generic_package",
+        "66_modport_connect",
+        "70_interface_connect",
+    ];
+
+    /// Testcases without any design unit in VHDL.
+    /// Interface is flattened into port lists and signals of its users.
+    const EMPTY_TESTS: [&str; 2] = ["05_interface", "30_empty"];
+
+    fn test(name: &str) {
+        let metadata_path = Metadata::search_from_current().unwrap();
+        let mut metadata = Metadata::load(&metadata_path).unwrap();
+        metadata.build.target_language = TargetLanguage::Vhdl;
+        metadata.build.sourcemap_target = SourceMapTarget::None;
+
+        if crate::DEPENDENCY_TESTS.contains(&name) {
+            let paths = metadata.paths::<&str>(&[], false).unwrap();
+            let cache_path = veryl_path::cache_path().canonicalize().unwrap();
+            for path in paths {
+                if path.src.starts_with(&cache_path) {
+                    let input = fs::read_to_string(&path.src).unwrap();
+                    let ret = Parser::parse(&input, &path.src).unwrap();
+                    let analyzer = Analyzer::new(&metadata);
+                    let _ = analyzer.analyze_pass1(&path.prj, &input, &path.src, &ret.veryl);
+                }
+            }
+        }
+
+        let src_path = PathBuf::from(format!("../../testcases/veryl/{}.veryl", name));
+        let dst_path = PathBuf::from(format!("../../testcases/vhdl/{}.vhd", name));
+        let map_path = PathBuf::from(format!(
+            "../../testcases/map/testcases/vhdl/{}.vhd.map",
+            name
+        ));
+
+        let input = fs::read_to_string(&src_path).unwrap();
+        let ret = Parser::parse(&input, &src_path).unwrap();
+        let prj = &metadata.project.name;
+        let analyzer = Analyzer::new(&metadata);
+        let _ = analyzer.analyze_pass1(&prj, &input, &src_path, &ret.veryl);
+        let errors: Vec<_> = analyzer
+            .analyze_pass2(&prj, &input, &src_path, &ret.veryl)
+            .into_iter()
+            .filter(|x| !matches!(x, AnalyzerError::CaseInsensitiveIdentifier { .. }))
+            .collect();
+
+        if UNSUPPORTED_TESTS.contains(&name) {
+            assert!(!errors.is_empty());
+            assert!(
+                errors
+                    .iter()
+                    .all(|x| matches!(x, AnalyzerError::UnsupportedByTarget { .. })),
+                "{errors:?}"
+            );
+            assert!(!dst_path.exists());
+            return;
+        }
+
+        assert!(errors.is_empty(), "{errors:?}");
+
+        let mut emitter = VhdlEmitter::new(&metadata, &src_path, &dst_path, &map_path);
+        emitter.emit(&prj, &ret.veryl);
+
+        let out_code = emitter.as_str();
+        if EMPTY_TESTS.contains(&name) {
+            assert!(out_code.is_empty(), "{out_code}");
+            assert!(!dst_path.exists());
+            return;
+        }
+        let ref_code = fs::read_to_string(&dst_path).unwrap();
+
+        assert_eq!(ref_code, out_code);
+    }
+
+    /// Analyze all VHDL testcases by GHDL if it is available.
+    /// Files are analyzed repeatedly until all dependencies are resolved.
+    /// Set `VERYL_REQUIRE_GHDL` to fail instead of skipping when GHDL is not found.
+    #[test]
+    fn ghdl() {
+        if Command::new("ghdl").arg("--version").output().is_err() {
+            if std::env::var_os("VERYL_REQUIRE_GHDL").is_some() {
+                panic!("ghdl is not found, but VERYL_REQUIRE_GHDL is set");
+            }
+            eprintln!("skipped: ghdl is not found (set VERYL_REQUIRE_GHDL to require it)");
+            return;
+        }
+
+        let workdir = std::env::temp_dir().join(format!("veryl_ghdl_{}", std::process::id()));
+        fs::create_dir_all(&workdir).unwrap();
+
+        let mut pending: Vec<_> = fs::read_dir("../../testcases/vhdl")
+            .unwrap()
+            .map(|x| x.unwrap().path())
+            .filter(|x| {
+                let name = x.file_stem().unwrap().to_string_lossy();
+                !crate::DEPENDENCY_TESTS.contains(&name.as_ref())
+            })
+            .collect();
+        pending.sort();
+
+        let mut errors = Vec::new();
+        loop {
+            errors.clear();
+            let mut next = Vec::new();
+            for path in &pending {
+                let output = Command::new("ghdl")
+                    .arg("-a")
+                    .arg("--std=08")
+                    .arg(format!("--workdir={}", workdir.to_string_lossy()))
+                    .arg(path)
+                    .output()
+                    .unwrap();
+                if !output.status.success() {
+                    errors.push(String::from_utf8_lossy(&output.stderr).into_owned());
+                    next.push(path.clone());
+                }
+            }
+            if next.is_empty() || next.len() == pending.len() {
+                break;
+            }
+            pending = next;
+        }
+
+        let _ = fs::remove_dir_all(&workdir);
+        assert!(errors.is_empty(), "{}", errors.join("\n"));
+    }
+
+    include!(concat!(env!("OUT_DIR"), "/test.rs"));
+}
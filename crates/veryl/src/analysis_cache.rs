@@ -0,0 +1,72 @@
+use log::debug;
+use std::fs;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::PathBuf;
+use veryl_metadata::Metadata;
+use veryl_path::PathPair;
+
+/// Immutable source files which were analyzed without any error and warning.
+///
+/// Locked dependencies and the standard library are placed under the cache path,
+/// and they are not changed for each version.
+/// If the source hash, the compiler version and the analysis settings are the same,
+/// checks of them can be skipped.
+pub struct AnalysisCache {
+    dir: PathBuf,
+    immutable: Option<PathBuf>,
+    settings: u64,
+}
+
+impl AnalysisCache {
+    pub fn new(metadata: &Metadata) -> Self {
+        let cache_path = veryl_path::cache_path();
+        Self::with_dir(
+            metadata,
+            cache_path.join("analysis"),
+            cache_path.canonicalize().ok(),
+        )
+    }
+
+    pub fn with_dir(metadata: &Metadata, dir: PathBuf, immutable: Option<PathBuf>) -> Self {
+        let mut hasher = DefaultHasher::new();
+        env!("CARGO_PKG_VERSION").hash(&mut hasher);
+        serde_json::to_string(&metadata.build)
+            .unwrap_or_default()
+            .hash(&mut hasher);
+        serde_json::to_string(&metadata.lint)
+            .unwrap_or_default()
+            .hash(&mut hasher);
+        Self {
+            dir,
+            immutable,
+            settings: hasher.finish(),
+        }
+    }
+
+    /// Key of the source file if it is immutable
+    pub fn key(&self, path: &PathPair, input: &str) -> Option<String> {
+        let immutable = self.immutable.as_ref()?;
+        if !path.src.starts_with(immutable) {
+            return None;
+        }
+
+        let mut hasher = DefaultHasher::new();
+        self.settings.hash(&mut hasher);
+        path.prj.hash(&mut hasher);
+        path.src.hash(&mut hasher);
+        input.hash(&mut hasher);
+        Some(format!("{:016x}", hasher.finish()))
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.dir.join(key).exists()
+    }
+
+    pub fn insert(&self, key: &str) {
+        // Failure of caching only disables skip of analysis
+        let ret = fs::create_dir_all(&self.dir).and_then(|_| fs::write(self.dir.join(key), ""));
+        if let Err(x) = ret {
+            debug!("Failed to write analysis cache ({x})");
+        }
+    }
+}
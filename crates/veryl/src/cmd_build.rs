@@ -1,60 +1,70 @@
-use crate::cmd_check::CheckError;
+use crate::cmd_check::{self, CheckError};
+use crate::diagnostics::MessagePrinter;
 use crate::OptBuild;
-use log::{debug, info};
-use miette::{IntoDiagnostic, Result, WrapErr};
-use std::collections::HashMap;
+use log::info;
+use miette::{IntoDiagnostic, Report, Result};
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::fs::OpenOptions;
-use std::io::Write;
 use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
 use tempfile::TempDir;
-use veryl_analyzer::namespace::Namespace;
-use veryl_analyzer::symbol::SymbolKind;
-use veryl_analyzer::{type_dag, Analyzer};
-use veryl_emitter::Emitter;
-use veryl_metadata::{FilelistType, Metadata, SourceMapTarget, Target};
+use veryl_analyzer::{function_table, namespace_table, symbol_table, type_dag, Analyzer};
+use veryl_metadata::{Metadata, Target};
 use veryl_parser::{resource_table, veryl_token::TokenSource, Parser};
 use veryl_path::PathPair;
 
+const WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
 pub struct CmdBuild {
     opt: OptBuild,
 }
 
+pub(crate) struct WatchContext {
+    input: String,
+    parser: Option<Parser>,
+    modified: Option<SystemTime>,
+    pub(crate) has_error: bool,
+}
+
 impl CmdBuild {
     pub fn new(opt: OptBuild) -> Self {
         Self { opt }
     }
 
     pub fn exec(&self, metadata: &mut Metadata) -> Result<bool> {
-        let paths = metadata.paths(&self.opt.files, true)?;
+        if self.opt.watch {
+            return self.watch(metadata);
+        }
 
-        let mut check_error = CheckError::default();
-        let mut contexts = Vec::new();
+        let ret = self.build(metadata);
+        MessagePrinter::new(self.opt.message_format).finish(ret)
+    }
 
-        for path in &paths {
-            info!("Processing file ({})", path.src.to_string_lossy());
+    fn build(&self, metadata: &mut Metadata) -> Result<bool> {
+        let paths = metadata.paths(&self.opt.files, true)?;
 
-            let input = fs::read_to_string(&path.src)
-                .into_diagnostic()
-                .wrap_err("")?;
-            let parser = Parser::parse(&input, &path.src)?;
+        let (contexts, check_error) = cmd_check::analyze(metadata, &paths, true)?;
 
-            let analyzer = Analyzer::new(metadata);
-            let mut errors = analyzer.analyze_pass1(&path.prj, &input, &path.src, &parser.veryl);
-            check_error = check_error.append(&mut errors).check_err()?;
+        let temp_dir = if let Target::Bundle { .. } = &metadata.build.target {
+            Some(TempDir::new().into_diagnostic()?)
+        } else {
+            None
+        };
+        let temp_path = temp_dir.as_ref().map(|x| x.path());
 
-            contexts.push((path, input, parser, analyzer));
+        for x in &contexts {
+            veryl_build::emit(metadata, x.path, &x.input, &x.parser, temp_path)?;
         }
 
-        for (path, input, parser, analyzer) in &contexts {
-            let mut errors = analyzer.analyze_pass2(&path.prj, input, &path.src, &parser.veryl);
-            check_error = check_error.append(&mut errors).check_err()?;
-        }
+        veryl_build::gen_filelist(metadata, &paths, temp_path)?;
 
-        for (path, input, parser, analyzer) in &contexts {
-            let mut errors = analyzer.analyze_pass3(&path.prj, input, &path.src, &parser.veryl);
-            check_error = check_error.append(&mut errors).check_err()?;
-        }
+        let _ = check_error.check_all()?;
+        Ok(true)
+    }
+
+    fn watch(&self, metadata: &mut Metadata) -> Result<bool> {
+        let mut paths = metadata.paths(&self.opt.files, true)?;
 
         let temp_dir = if let Target::Bundle { .. } = &metadata.build.target {
             Some(TempDir::new().into_diagnostic()?)
@@ -62,195 +72,249 @@ impl CmdBuild {
             None
         };
 
-        for (path, input, parser, _) in &contexts {
-            let dst = if let Some(ref temp_dir) = temp_dir {
-                temp_dir.path().join(
-                    path.dst
-                        .strip_prefix(metadata.project_path())
-                        .into_diagnostic()?,
-                )
-            } else {
-                path.dst.clone()
-            };
-
-            let map = match &metadata.build.sourcemap_target {
-                SourceMapTarget::Directory { path: map_dir } => {
-                    let dst = path
-                        .dst
-                        .strip_prefix(metadata.project_path())
-                        .into_diagnostic()?;
-                    let mut map = dst.to_path_buf();
-                    map.set_extension("sv.map");
-                    metadata.project_path().join(map_dir).join(map)
-                }
-                _ => {
-                    let mut map = dst.clone();
-                    map.set_extension("sv.map");
-                    map
+        let analyzer = Analyzer::new(metadata);
+        let mut contexts = HashMap::new();
+
+        let targets = paths.iter().map(|x| x.src.clone()).collect();
+        self.rebuild(
+            metadata,
+            &paths,
+            &analyzer,
+            &mut contexts,
+            targets,
+            &temp_dir,
+        );
+
+        loop {
+            thread::sleep(WATCH_INTERVAL);
+
+            let mut changed = HashSet::new();
+
+            // Files may be created or removed
+            if self.is_file_set_changed(metadata, &paths) {
+                match metadata.paths(&self.opt.files, true) {
+                    Ok(x) => {
+                        for path in &paths {
+                            if !x.iter().any(|x| x.src == path.src) {
+                                changed.insert(path.src.clone());
+                            }
+                        }
+                        paths = x;
+                    }
+                    Err(x) => self.print_error(x.into()),
                 }
-            };
-
-            let mut emitter = Emitter::new(metadata, &path.src, &dst, &map);
-            emitter.emit(&path.prj, &parser.veryl);
-
-            let dst_dir = dst.parent().unwrap();
-            if !dst_dir.exists() {
-                std::fs::create_dir_all(dst.parent().unwrap()).into_diagnostic()?;
             }
 
-            let mut file = OpenOptions::new()
-                .create(true)
-                .write(true)
-                .truncate(true)
-                .open(&dst)
-                .into_diagnostic()?;
-            file.write_all(emitter.as_str().as_bytes())
-                .into_diagnostic()?;
-            file.flush().into_diagnostic()?;
-
-            debug!("Output file ({})", dst.to_string_lossy());
-
-            if metadata.build.sourcemap_target != SourceMapTarget::None {
-                let source_map = emitter.source_map();
-                source_map.set_source_content(input);
-                let source_map = source_map.to_bytes().into_diagnostic()?;
-
-                let map_dir = map.parent().unwrap();
-                if !map_dir.exists() {
-                    std::fs::create_dir_all(map.parent().unwrap()).into_diagnostic()?;
+            for path in &paths {
+                let modified = Self::modified(&path.src);
+                let prev = contexts.get(&path.src).map(|x: &WatchContext| x.modified);
+                if prev != Some(modified) {
+                    changed.insert(path.src.clone());
                 }
+            }
 
-                let mut file = OpenOptions::new()
-                    .create(true)
-                    .write(true)
-                    .truncate(true)
-                    .open(&map)
-                    .into_diagnostic()?;
-                file.write_all(&source_map).into_diagnostic()?;
-                file.flush().into_diagnostic()?;
-
-                debug!("Output map ({})", map.to_string_lossy());
+            if !changed.is_empty() {
+                self.rebuild(
+                    metadata,
+                    &paths,
+                    &analyzer,
+                    &mut contexts,
+                    changed,
+                    &temp_dir,
+                );
             }
         }
-
-        self.gen_filelist(metadata, &paths, temp_dir)?;
-
-        let _ = check_error.check_all()?;
-        Ok(true)
     }
 
-    fn gen_filelist_line(&self, metadata: &Metadata, path: &Path) -> Result<String> {
-        let base_path = metadata.project_path();
-        let path = path.canonicalize().into_diagnostic()?;
-        let relative = path.strip_prefix(&base_path).into_diagnostic()?;
-        Ok(match metadata.build.filelist_type {
-            FilelistType::Absolute => format!("{}\n", path.to_string_lossy()),
-            FilelistType::Relative => format!("{}\n", relative.to_string_lossy()),
-            FilelistType::Flgen => {
-                format!("source_file '{}'\n", relative.to_string_lossy())
-            }
-        })
+    fn is_file_set_changed(&self, metadata: &Metadata, paths: &[PathPair]) -> bool {
+        // Explicitly specified files are not gathered
+        if !self.opt.files.is_empty() {
+            return false;
+        }
+
+        let Ok(files) =
+            veryl_path::gather_files_with_extension(metadata.project_path(), "veryl", true)
+        else {
+            return false;
+        };
+        let current: HashSet<_> = paths
+            .iter()
+            .filter(|x| x.prj == metadata.project.name)
+            .map(|x| &x.src)
+            .collect();
+        files.len() != current.len() || files.iter().any(|x| !current.contains(x))
     }
 
-    fn gen_filelist(
+    pub(crate) fn rebuild(
         &self,
         metadata: &Metadata,
         paths: &[PathPair],
-        temp_dir: Option<TempDir>,
-    ) -> Result<()> {
-        let filelist_path = metadata.filelist_path();
-        let base_path = metadata.project_path();
+        analyzer: &Analyzer,
+        contexts: &mut HashMap<PathBuf, WatchContext>,
+        changed: HashSet<PathBuf>,
+        temp_dir: &Option<TempDir>,
+    ) {
+        let mut targets = changed;
+
+        // Errors of the previous build may be resolved by the changed files
+        for (path, context) in contexts.iter() {
+            if context.has_error {
+                targets.insert(path.clone());
+            }
+        }
+
+        let targets = Self::dependents(targets);
+
+        for target in &targets {
+            if let Some(path) = resource_table::get_path_id(target) {
+                symbol_table::drop(path);
+                namespace_table::drop(path);
+                function_table::drop(path);
+                type_dag::drop(path);
+            }
+        }
 
-        let paths = Self::sort_filelist(metadata, paths);
+        // Removed files
+        contexts.retain(|x, _| paths.iter().any(|y| y.src == *x));
 
-        let text = if let Target::Bundle { path } = &metadata.build.target {
-            let temp_dir = temp_dir.unwrap();
-            let mut text = String::new();
-            let target_path = base_path.join(path);
+        let targets: Vec<_> = paths.iter().filter(|x| targets.contains(&x.src)).collect();
+        let mut errors = HashMap::new();
 
-            for path in paths {
-                let dst = temp_dir
-                    .path()
-                    .join(path.dst.strip_prefix(&base_path).into_diagnostic()?);
+        for path in &targets {
+            info!("Processing file ({})", path.src.to_string_lossy());
 
-                text.push_str(&fs::read_to_string(&dst).into_diagnostic()?);
-            }
+            let modified = Self::modified(&path.src);
+            let input = fs::read_to_string(&path.src).unwrap_or_default();
+            let parser = match Parser::parse(&input, &path.src) {
+                Ok(x) => {
+                    let x_errors = analyzer.analyze_pass1(&path.prj, &input, &path.src, &x.veryl);
+                    errors.insert(path.src.clone(), x_errors);
+                    Some(x)
+                }
+                Err(x) => {
+                    self.print_error(Report::new(x));
+                    errors.insert(path.src.clone(), vec![]);
+                    None
+                }
+            };
 
-            debug!("Output file ({})", target_path.to_string_lossy());
-            let mut file = OpenOptions::new()
-                .create(true)
-                .write(true)
-                .truncate(true)
-                .open(&target_path)
-                .into_diagnostic()?;
-            file.write_all(text.as_bytes()).into_diagnostic()?;
-            file.flush().into_diagnostic()?;
-
-            self.gen_filelist_line(metadata, &target_path)?
-        } else {
-            let mut text = String::new();
-            for path in paths {
-                let line = self.gen_filelist_line(metadata, &path.dst)?;
-                text.push_str(&line);
+            let context = WatchContext {
+                input,
+                parser,
+                modified,
+                has_error: false,
+            };
+            contexts.insert(path.src.clone(), context);
+        }
+
+        for path in &targets {
+            let context = &contexts[&path.src];
+            if let Some(parser) = &context.parser {
+                let mut x =
+                    analyzer.analyze_pass2(&path.prj, &context.input, &path.src, &parser.veryl);
+                errors.get_mut(&path.src).unwrap().append(&mut x);
             }
-            text
-        };
+        }
 
-        info!("Output filelist ({})", filelist_path.to_string_lossy());
-        let mut file = OpenOptions::new()
-            .create(true)
-            .write(true)
-            .truncate(true)
-            .open(filelist_path)
-            .into_diagnostic()?;
-        file.write_all(text.as_bytes()).into_diagnostic()?;
-        file.flush().into_diagnostic()?;
-
-        Ok(())
-    }
+        for path in &targets {
+            let context = &contexts[&path.src];
+            if let Some(parser) = &context.parser {
+                let mut x =
+                    analyzer.analyze_pass3(&path.prj, &context.input, &path.src, &parser.veryl);
+                errors.get_mut(&path.src).unwrap().append(&mut x);
+            }
+        }
 
-    fn sort_filelist(metadata: &Metadata, paths: &[PathPair]) -> Vec<PathPair> {
-        let mut table = HashMap::new();
-        for path in paths {
-            table.insert(path.src.clone(), path);
+        let mut check_error = CheckError::default();
+        for path in &targets {
+            let context = contexts.get_mut(&path.src).unwrap();
+            let mut errors = errors.remove(&path.src).unwrap();
+            context.has_error = context.parser.is_none() || !errors.is_empty();
+            check_error = check_error.append(&mut errors);
         }
 
-        // Remove files which are not connected from project
-        let connected_components = type_dag::connected_components();
-        let mut prj_namespace = Namespace::new();
-        prj_namespace.push(resource_table::insert_str(&metadata.project.name));
-        for symbols in &connected_components {
-            let used = symbols.iter().any(|x| x.namespace.included(&prj_namespace));
-            if !used {
-                for symbol in symbols {
-                    if let TokenSource::File(x) = symbol.token.source {
-                        let path = PathBuf::from(format!("{}", x));
-                        table.remove(&path);
-                    }
+        let has_error = contexts.values().any(|x| x.parser.is_none());
+        let check_error = match check_error.check_err() {
+            Ok(x) if !has_error => x,
+            Ok(x) => {
+                if let Err(x) = x.check_all() {
+                    self.print_error(x);
                 }
+                return;
+            }
+            Err(x) => {
+                self.print_error(x);
+                return;
             }
+        };
+
+        let temp_path = temp_dir.as_ref().map(|x| x.path());
+        for path in &targets {
+            let context = contexts.get_mut(&path.src).unwrap();
+            if let Some(parser) = &context.parser {
+                if let Err(x) = veryl_build::emit(metadata, path, &context.input, parser, temp_path)
+                {
+                    self.print_error(x);
+                    // Retry at the next rebuild
+                    context.has_error = true;
+                }
+            }
+        }
+
+        if let Err(x) = veryl_build::gen_filelist(metadata, paths, temp_path) {
+            self.print_error(x);
+        }
+
+        if let Err(x) = check_error.check_all() {
+            self.print_error(x);
+        }
+
+        info!("Watching files");
+    }
+
+    fn print_error(&self, report: Report) {
+        let mut printer = MessagePrinter::new(self.opt.message_format);
+        if printer.is_human() {
+            eprintln!("{report:?}");
+        } else {
+            printer.report(&report);
+            printer.print();
         }
+    }
+
+    fn modified(path: &Path) -> Option<SystemTime> {
+        fs::metadata(path).and_then(|x| x.modified()).ok()
+    }
+
+    /// Collect files which refer symbols in the target files transitively
+    pub(crate) fn dependents(mut targets: HashSet<PathBuf>) -> HashSet<PathBuf> {
+        let symbols = symbol_table::get_all();
 
-        let mut ret = vec![];
-        let sorted_symbols = type_dag::toposort();
-        for symbol in sorted_symbols {
-            if matches!(
-                symbol.kind,
-                SymbolKind::Module(_) | SymbolKind::Interface(_) | SymbolKind::Package(_)
-            ) {
+        loop {
+            let ids: HashSet<_> = targets
+                .iter()
+                .filter_map(resource_table::get_path_id)
+                .collect();
+
+            let mut added = false;
+            for symbol in &symbols {
                 if let TokenSource::File(x) = symbol.token.source {
-                    let path = PathBuf::from(format!("{}", x));
-                    if let Some(x) = table.remove(&path) {
-                        ret.push(x.clone());
+                    if !ids.contains(&x) {
+                        continue;
+                    }
+                    for reference in &symbol.references {
+                        if let TokenSource::File(x) = reference.source {
+                            if let Some(path) = resource_table::get_path_value(x) {
+                                added |= targets.insert(path);
+                            }
+                        }
                     }
                 }
             }
-        }
 
-        for path in table.into_values() {
-            ret.push(path.clone());
+            if !added {
+                return targets;
+            }
         }
-
-        ret
     }
 }
@@ -0,0 +1,99 @@
+use crate::cmd_check;
+use crate::OptCdc;
+use console::Style;
+use log::{error, info};
+use miette::Result;
+use std::collections::HashSet;
+use veryl_analyzer::cdc::{self, Crossing, Protection};
+use veryl_metadata::Metadata;
+use veryl_parser::resource_table;
+use veryl_parser::veryl_token::TokenSource;
+
+pub struct CmdCdc {
+    opt: OptCdc,
+}
+
+impl CmdCdc {
+    pub fn new(opt: OptCdc) -> Self {
+        Self { opt }
+    }
+
+    pub fn exec(&self, metadata: &mut Metadata) -> Result<bool> {
+        let paths = metadata.paths(&self.opt.files, true)?;
+
+        let (contexts, _) = cmd_check::analyze(metadata, &paths, false)?;
+
+        // dependencies are elaborated together, but crossings in them are out of scope
+        let mut targets = HashSet::new();
+        for x in &contexts {
+            if x.path.prj == metadata.project.name {
+                if let Some(id) = resource_table::get_path_id(x.path.src.clone()) {
+                    targets.insert(TokenSource::File(id));
+                }
+            }
+        }
+        let inputs: Vec<_> = contexts.iter().map(|x| &x.parser.veryl).collect();
+        let mut crossings = cdc::analyze(&inputs);
+        crossings.retain(|x| targets.contains(&x.range.beg.source));
+
+        for x in &crossings {
+            self.print(x);
+        }
+
+        let total = crossings.len();
+        let synchronized = crossings
+            .iter()
+            .filter(|x| x.protection.is_synchronized() && x.issues.is_empty())
+            .count();
+        let waived = crossings
+            .iter()
+            .filter(|x| x.protection == Protection::Unsafe && x.issues.is_empty())
+            .count();
+        let failed = total - synchronized - waived;
+
+        if failed == 0 {
+            info!(
+                "Completed CDC analysis : {} crossings, {} synchronized, {} waived, {} failed",
+                total, synchronized, waived, failed
+            );
+            Ok(true)
+        } else {
+            error!(
+                "Completed CDC analysis : {} crossings, {} synchronized, {} waived, {} failed",
+                total, synchronized, waived, failed
+            );
+            Ok(false)
+        }
+    }
+
+    fn print(&self, x: &Crossing) {
+        let style = if !x.issues.is_empty() || x.protection == Protection::None {
+            Style::new().red().bright()
+        } else if x.protection == Protection::Unsafe {
+            Style::new().yellow().bright()
+        } else {
+            Style::new().green().bright()
+        };
+
+        let signal = if let Some((inst, port)) = x.port {
+            format!("{} -> {}.{}", x.signal, inst, port)
+        } else {
+            x.signal.to_string()
+        };
+
+        println!(
+            "{} {} : {} -> {} : {} ({}:{}:{})",
+            style.apply_to(format!("[{}]", x.protection)),
+            x.module,
+            x.src,
+            x.dst,
+            signal,
+            x.range.beg.source,
+            x.range.beg.line,
+            x.range.beg.column,
+        );
+        for issue in &x.issues {
+            println!("    {}", style.apply_to(issue));
+        }
+    }
+}
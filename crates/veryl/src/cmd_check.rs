@@ -1,3 +1,5 @@
+use crate::analysis_cache::AnalysisCache;
+use crate::diagnostics::MessagePrinter;
 use crate::OptCheck;
 use log::info;
 use miette::{self, Diagnostic, IntoDiagnostic, Result, Severity, WrapErr};
@@ -6,6 +8,7 @@ use thiserror::Error;
 use veryl_analyzer::{Analyzer, AnalyzerError};
 use veryl_metadata::Metadata;
 use veryl_parser::Parser;
+use veryl_path::PathPair;
 
 pub struct CmdCheck {
     opt: OptCheck,
@@ -51,37 +54,107 @@ impl CmdCheck {
     }
 
     pub fn exec(&self, metadata: &mut Metadata) -> Result<bool> {
+        let ret = self.check(metadata);
+        MessagePrinter::new(self.opt.message_format).finish(ret)
+    }
+
+    fn check(&self, metadata: &mut Metadata) -> Result<bool> {
         let paths = metadata.paths(&self.opt.files, true)?;
+        let (_, check_error) = analyze(metadata, &paths, true)?;
+
+        let _ = check_error.check_all()?;
+        Ok(true)
+    }
+}
+
+/// Source file which was parsed and analyzed
+pub struct Context<'a> {
+    pub path: &'a PathPair,
+    pub input: String,
+    pub parser: Parser,
+}
 
-        let mut check_error = CheckError::default();
-        let mut contexts = Vec::new();
+/// Parse and analyze files.
+///
+/// If `fail_fast` is true, it returns at the first pass which found errors.
+pub fn analyze<'a>(
+    metadata: &Metadata,
+    paths: &'a [PathPair],
+    fail_fast: bool,
+) -> Result<(Vec<Context<'a>>, CheckError)> {
+    analyze_with_cache(metadata, paths, fail_fast, &AnalysisCache::new(metadata))
+}
 
-        for path in &paths {
-            info!("Processing file ({})", path.src.to_string_lossy());
+/// Parse and analyze files, skipping checks of files which are clean in `cache`.
+pub fn analyze_with_cache<'a>(
+    metadata: &Metadata,
+    paths: &'a [PathPair],
+    fail_fast: bool,
+    cache: &AnalysisCache,
+) -> Result<(Vec<Context<'a>>, CheckError)> {
+    let mut check_error = CheckError::default();
+    let mut contexts = Vec::new();
+    let mut keys = Vec::new();
+    let analyzer = Analyzer::new(metadata);
 
-            let input = fs::read_to_string(&path.src)
-                .into_diagnostic()
-                .wrap_err("")?;
-            let parser = Parser::parse(&input, &path.src)?;
+    let check = |x: CheckError| if fail_fast { x.check_err() } else { Ok(x) };
 
-            let analyzer = Analyzer::new(metadata);
-            let mut errors = analyzer.analyze_pass1(&path.prj, &input, &path.src, &parser.veryl);
-            check_error = check_error.append(&mut errors).check_err()?;
+    for path in paths {
+        info!("Processing file ({})", path.src.to_string_lossy());
 
-            contexts.push((path, input, parser, analyzer));
-        }
+        let input = fs::read_to_string(&path.src)
+            .into_diagnostic()
+            .wrap_err("")?;
+        let parser = Parser::parse(&input, &path.src)?;
+
+        // pass1 is always required to create the symbol table
+        let mut errors = analyzer.analyze_pass1(&path.prj, &input, &path.src, &parser.veryl);
+        let key = cache.key(path, &input).filter(|_| errors.is_empty());
+        check_error = check(check_error.append(&mut errors))?;
 
-        for (path, input, parser, analyzer) in &contexts {
-            let mut errors = analyzer.analyze_pass2(&path.prj, input, &path.src, &parser.veryl);
-            check_error = check_error.append(&mut errors).check_err()?;
+        contexts.push(Context {
+            path,
+            input,
+            parser,
+        });
+        keys.push(key);
+    }
+
+    let cached: Vec<_> = keys
+        .iter()
+        .map(|x| x.as_ref().is_some_and(|x| cache.contains(x)))
+        .collect();
+
+    for (i, x) in contexts.iter().enumerate() {
+        if cached[i] {
+            analyzer.analyze_pass2_tables(&x.path.prj, &x.input, &x.path.src, &x.parser.veryl);
+            continue;
         }
+        let mut errors =
+            analyzer.analyze_pass2(&x.path.prj, &x.input, &x.path.src, &x.parser.veryl);
+        if !errors.is_empty() {
+            keys[i] = None;
+        }
+        check_error = check(check_error.append(&mut errors))?;
+    }
 
-        for (path, input, parser, analyzer) in &contexts {
-            let mut errors = analyzer.analyze_pass3(&path.prj, input, &path.src, &parser.veryl);
-            check_error = check_error.append(&mut errors).check_err()?;
+    for (i, x) in contexts.iter().enumerate() {
+        if cached[i] {
+            continue;
+        }
+        let mut errors =
+            analyzer.analyze_pass3(&x.path.prj, &x.input, &x.path.src, &x.parser.veryl);
+        if !errors.is_empty() {
+            keys[i] = None;
         }
+        check_error = check(check_error.append(&mut errors))?;
+    }
 
-        let _ = check_error.check_all()?;
-        Ok(true)
+    for (key, cached) in keys.iter().zip(cached) {
+        if let (Some(key), false) = (key, cached) {
+            cache.insert(key);
+        }
     }
+
+    Ok((contexts, check_error))
 }
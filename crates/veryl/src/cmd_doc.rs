@@ -2,10 +2,10 @@ use crate::doc::{DocBuilder, TopLevelItem};
 use crate::OptDoc;
 use log::info;
 use miette::{IntoDiagnostic, Result, WrapErr};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use veryl_analyzer::symbol::{SymbolId, SymbolKind};
-use veryl_analyzer::{symbol_table, Analyzer};
+use veryl_analyzer::{fsm, symbol_table, Analyzer};
 use veryl_metadata::Metadata;
 use veryl_parser::resource_table;
 use veryl_parser::Parser;
@@ -45,6 +45,18 @@ impl CmdDoc {
             analyzer.analyze_pass3(&path.prj, input, &path.src, &parser.veryl);
         }
 
+        let mut state_machines = HashMap::new();
+        for (path, _, parser, _) in &contexts {
+            if path.prj == metadata.project.name {
+                for x in fsm::analyze(&parser.veryl) {
+                    state_machines
+                        .entry(x.module)
+                        .or_insert_with(Vec::new)
+                        .push(x);
+                }
+            }
+        }
+
         let mut modules = BTreeMap::new();
         let mut proto_modules = BTreeMap::new();
         let mut interfaces = BTreeMap::new();
@@ -102,7 +114,14 @@ impl CmdDoc {
         let interfaces: Vec<_> = interfaces.into_values().collect();
         let packages: Vec<_> = packages.into_values().collect();
 
-        let builder = DocBuilder::new(metadata, modules, proto_modules, interfaces, packages)?;
+        let builder = DocBuilder::new(
+            metadata,
+            modules,
+            proto_modules,
+            interfaces,
+            packages,
+            state_machines,
+        )?;
         builder.build()?;
 
         Ok(true)
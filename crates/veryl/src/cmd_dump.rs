@@ -1,10 +1,9 @@
+use crate::cmd_check;
 use crate::OptDump;
-use log::info;
-use miette::{IntoDiagnostic, Result, WrapErr};
-use std::fs;
-use veryl_analyzer::Analyzer;
+use log::error;
+use miette::Result;
+use veryl_analyzer::elaborator;
 use veryl_metadata::Metadata;
-use veryl_parser::Parser;
 
 pub struct CmdDump {
     opt: OptDump,
@@ -18,28 +17,7 @@ impl CmdDump {
     pub fn exec(&self, metadata: &mut Metadata) -> Result<bool> {
         let paths = metadata.paths(&self.opt.files, true)?;
 
-        let mut contexts = Vec::new();
-
-        for path in &paths {
-            info!("Processing file ({})", path.src.to_string_lossy());
-
-            let input = fs::read_to_string(&path.src)
-                .into_diagnostic()
-                .wrap_err("")?;
-            let parser = Parser::parse(&input, &path.src)?;
-            let analyzer = Analyzer::new(metadata);
-            analyzer.analyze_pass1(&path.prj, &input, &path.src, &parser.veryl);
-
-            contexts.push((path, input, parser, analyzer));
-        }
-
-        for (path, input, parser, analyzer) in &contexts {
-            analyzer.analyze_pass2(&path.prj, input, &path.src, &parser.veryl);
-        }
-
-        for (path, input, parser, analyzer) in &contexts {
-            analyzer.analyze_pass3(&path.prj, input, &path.src, &parser.veryl);
-        }
+        let (contexts, _) = cmd_check::analyze(metadata, &paths, false)?;
 
         if self.opt.symbol_table {
             println!("{}", veryl_analyzer::symbol_table::dump());
@@ -65,6 +43,16 @@ impl CmdDump {
             println!("{}", veryl_analyzer::unsafe_table::dump());
         }
 
+        if let Some(ref top) = self.opt.elaborated {
+            let inputs: Vec<_> = contexts.iter().map(|x| &x.parser.veryl).collect();
+            if let Some(x) = elaborator::elaborate(top, &inputs) {
+                print!("{}", x);
+            } else {
+                error!("Top module ({}) is not found", top);
+                return Ok(false);
+            }
+        }
+
         Ok(true)
     }
 }
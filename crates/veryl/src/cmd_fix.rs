@@ -0,0 +1,97 @@
+use crate::cmd_check;
+use crate::OptFix;
+use log::{debug, info};
+use miette::{bail, IntoDiagnostic, Result};
+use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io::Write;
+use veryl_analyzer::fix;
+use veryl_formatter::Formatter;
+use veryl_metadata::Metadata;
+use veryl_parser::{resource_table, Parser};
+
+pub struct CmdFix {
+    opt: OptFix,
+}
+
+impl CmdFix {
+    pub fn new(opt: OptFix) -> Self {
+        Self { opt }
+    }
+
+    pub fn exec(&self, metadata: &mut Metadata) -> Result<bool> {
+        let paths = metadata.paths(&self.opt.files, true)?;
+
+        let (contexts, check_error) = cmd_check::analyze(metadata, &paths, false)?;
+        let errors = check_error.related;
+
+        // Only project files can be modified
+        let mut targets = HashMap::new();
+        for x in &contexts {
+            if x.path.prj == metadata.project.name {
+                if let Some(id) = resource_table::get_path_id(x.path.src.clone()) {
+                    targets.insert(id, (x.path, &x.input));
+                }
+            }
+        }
+
+        let mut fixes = Vec::new();
+        for fix in errors
+            .iter()
+            .flat_map(|x| x.fixes())
+            .filter(|x| x.preferred)
+        {
+            match fix.text_edits() {
+                Ok(edits) => {
+                    if edits.iter().all(|x| targets.contains_key(&x.path)) {
+                        fixes.push(fix);
+                    }
+                }
+                Err(x) => info!("Skipping fix \"{}\" ({x})", fix.title),
+            }
+        }
+
+        let mut edits = HashMap::new();
+        for edit in fix::select(&fixes) {
+            edits.entry(edit.path).or_insert_with(Vec::new).push(edit);
+        }
+
+        // Write files only if all of them are fixed successfully
+        let mut outputs = Vec::new();
+        for (id, edits) in &edits {
+            let (path, input) = targets[id];
+
+            let fixed = match fix::apply(input, edits) {
+                Ok(x) => x,
+                Err(invalid) => {
+                    let ranges: Vec<_> = invalid
+                        .iter()
+                        .map(|x| format!("{}..{}", x.beg, x.end))
+                        .collect();
+                    bail!(
+                        "overlapped or out-of-bounds edits in \"{}\" ({})",
+                        path.src.to_string_lossy(),
+                        ranges.join(", ")
+                    );
+                }
+            };
+            let parser = Parser::parse(&fixed, &path.src)?;
+            let mut formatter = Formatter::new(metadata);
+            formatter.format(&parser.veryl);
+            outputs.push((path, formatter.as_str().to_string()));
+        }
+
+        for (path, output) in outputs {
+            let mut file = OpenOptions::new()
+                .write(true)
+                .truncate(true)
+                .open(&path.src)
+                .into_diagnostic()?;
+            file.write_all(output.as_bytes()).into_diagnostic()?;
+            file.flush().into_diagnostic()?;
+            debug!("Overwritten file ({})", path.src.to_string_lossy());
+        }
+
+        Ok(true)
+    }
+}
@@ -1,3 +1,4 @@
+use crate::diagnostics::{Message, MessagePrinter, Position, Span};
 use crate::OptFmt;
 use console::{style, Style};
 use log::{debug, info};
@@ -22,6 +23,12 @@ impl CmdFmt {
     }
 
     pub fn exec(&self, metadata: &mut Metadata) -> Result<bool> {
+        let mut printer = MessagePrinter::new(self.opt.message_format);
+        let ret = self.format(metadata, &mut printer);
+        printer.finish(ret)
+    }
+
+    fn format(&self, metadata: &mut Metadata, printer: &mut MessagePrinter) -> Result<bool> {
         let paths = metadata.paths(&self.opt.files, true)?;
 
         let mut all_pass = true;
@@ -39,7 +46,13 @@ impl CmdFmt {
 
             if !pass {
                 if self.opt.check {
-                    print_diff(&path.src, input.as_str(), formatter.as_str());
+                    if printer.is_human() {
+                        print_diff(&path.src, input.as_str(), formatter.as_str());
+                    } else {
+                        for x in diff_messages(&path.src, input.as_str(), formatter.as_str()) {
+                            printer.push(x);
+                        }
+                    }
                     all_pass = false;
                 } else {
                     let mut file = OpenOptions::new()
@@ -71,6 +84,40 @@ impl fmt::Display for Line {
     }
 }
 
+fn diff_messages(file: &Path, org: &str, new: &str) -> Vec<Message> {
+    let diff = TextDiff::from_lines(org, new);
+
+    let mut ret = Vec::new();
+    for group in diff.grouped_ops(0) {
+        let (Some(beg), Some(end)) = (group.first(), group.last()) else {
+            continue;
+        };
+        let beg = beg.old_range().start;
+        let end = end.old_range().end.max(beg + 1);
+
+        ret.push(Message {
+            code: Some("formatter::unformatted_code".to_string()),
+            severity: "error".to_string(),
+            message: "Code is not formatted".to_string(),
+            file: Some(file.to_string_lossy().to_string()),
+            span: Some(Span {
+                start: Position {
+                    line: beg + 1,
+                    column: 1,
+                },
+                end: Position {
+                    line: end + 1,
+                    column: 1,
+                },
+            }),
+            related: Vec::new(),
+            help: Some("run `veryl fmt` to format the code".to_string()),
+            url: None,
+        });
+    }
+    ret
+}
+
 fn print_diff(file: &Path, org: &str, new: &str) {
     let diff = TextDiff::from_lines(org, new);
 
@@ -0,0 +1,87 @@
+use crate::cmd_check;
+use crate::OptFsm;
+use console::Style;
+use log::{error, info};
+use miette::Result;
+use veryl_analyzer::fsm::{self, StateMachine};
+use veryl_metadata::Metadata;
+
+pub struct CmdFsm {
+    opt: OptFsm,
+}
+
+impl CmdFsm {
+    pub fn new(opt: OptFsm) -> Self {
+        Self { opt }
+    }
+
+    pub fn exec(&self, metadata: &mut Metadata) -> Result<bool> {
+        let paths = metadata.paths(&self.opt.files, true)?;
+
+        let (contexts, _) = cmd_check::analyze(metadata, &paths, false)?;
+
+        // state machines in dependencies are out of scope
+        let mut state_machines = Vec::new();
+        for x in &contexts {
+            if x.path.prj == metadata.project.name {
+                state_machines.append(&mut fsm::analyze(&x.parser.veryl));
+            }
+        }
+
+        for x in &state_machines {
+            self.print(x);
+        }
+
+        let total = state_machines.len();
+        let failed = state_machines
+            .iter()
+            .filter(|x| !x.issues.is_empty())
+            .count();
+
+        if failed == 0 {
+            info!(
+                "Completed FSM analysis : {} state machines, {} failed",
+                total, failed
+            );
+            Ok(true)
+        } else {
+            error!(
+                "Completed FSM analysis : {} state machines, {} failed",
+                total, failed
+            );
+            Ok(false)
+        }
+    }
+
+    fn print(&self, x: &StateMachine) {
+        let style = if x.issues.is_empty() {
+            Style::new().green().bright()
+        } else {
+            Style::new().red().bright()
+        };
+
+        let reset = x.reset.map(|x| x.to_string()).unwrap_or("-".to_string());
+
+        println!(
+            "{} {} : {} ({}) : {} states, {} transitions, reset {} ({}:{}:{})",
+            style.apply_to("[fsm]"),
+            x.module,
+            x.register,
+            x.r#enum,
+            x.states.len(),
+            x.transitions.len(),
+            reset,
+            x.range.beg.source,
+            x.range.beg.line,
+            x.range.beg.column,
+        );
+        for issue in &x.issues {
+            println!("    {}", style.apply_to(issue));
+        }
+        if self.opt.mermaid {
+            for line in x.mermaid().lines() {
+                println!("    {}", line);
+            }
+        }
+    }
+}
@@ -1,11 +1,11 @@
 use crate::cmd_build::CmdBuild;
-use crate::runner::{Cocotb, CocotbSource, Vcs, Verilator, Vivado};
-use crate::{OptBuild, OptTest};
+use crate::{MessageFormat, OptBuild, OptTest};
 use log::{error, info};
 use miette::Result;
 use veryl_analyzer::symbol::{SymbolKind, TestType};
 use veryl_analyzer::symbol_table;
 use veryl_metadata::{FilelistType, Metadata, SimType};
+use veryl_runner::{Cocotb, CocotbSource, Vcs, Verilator, Vivado};
 
 pub struct CmdTest {
     opt: OptTest,
@@ -22,6 +22,8 @@ impl CmdTest {
 
         let build = CmdBuild::new(OptBuild {
             files: self.opt.files.clone(),
+            watch: false,
+            message_format: MessageFormat::Human,
         });
         build.exec(metadata)?;
 
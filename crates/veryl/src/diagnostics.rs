@@ -0,0 +1,310 @@
+use crate::cmd_check::CheckError;
+use crate::MessageFormat;
+use miette::{Diagnostic, Report, Result, Severity, SourceCode, SourceSpan};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::path::Path;
+use veryl_parser::ParserError;
+
+#[derive(Clone, Debug, Serialize)]
+pub struct Message {
+    pub code: Option<String>,
+    pub severity: String,
+    pub message: String,
+    pub file: Option<String>,
+    pub span: Option<Span>,
+    pub related: Vec<RelatedSpan>,
+    pub help: Option<String>,
+    pub url: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct Span {
+    pub start: Position,
+    pub end: Position,
+}
+
+/// 1-based line and column
+#[derive(Clone, Copy, Debug, Serialize)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct RelatedSpan {
+    pub label: Option<String>,
+    pub file: Option<String>,
+    pub span: Span,
+}
+
+impl Message {
+    pub fn from_diagnostic(prefix: Option<&str>, diag: &dyn Diagnostic) -> Self {
+        let code = diag.code().map(|x| {
+            let code = x.to_string();
+            let code = code.rsplit("::").next().unwrap_or(&code).to_string();
+            if let Some(prefix) = prefix {
+                format!("{prefix}::{}", to_snake_case(&code))
+            } else {
+                code
+            }
+        });
+
+        let severity = match diag.severity() {
+            Some(Severity::Warning) => "warning",
+            Some(Severity::Advice) => "advice",
+            Some(Severity::Error) | None => "error",
+        };
+
+        let help = diag.help().map(|x| x.to_string()).filter(|x| !x.is_empty());
+        let url = diag.url().map(|x| x.to_string());
+
+        let mut file = None;
+        let mut span = None;
+        let mut related = Vec::new();
+
+        if let (Some(source), Some(labels)) = (diag.source_code(), diag.labels()) {
+            for (i, label) in labels.enumerate() {
+                let Some((name, x)) = to_span(source, label.inner()) else {
+                    continue;
+                };
+                if i == 0 {
+                    file = name;
+                    span = Some(x);
+                } else {
+                    related.push(RelatedSpan {
+                        label: label.label().map(|x| x.to_string()),
+                        file: name,
+                        span: x,
+                    });
+                }
+            }
+        }
+
+        Self {
+            code,
+            severity: severity.to_string(),
+            message: diag.to_string(),
+            file,
+            span,
+            related,
+            help,
+            url,
+        }
+    }
+
+    pub(crate) fn to_json(&self) -> Value {
+        serde_json::to_value(self).unwrap()
+    }
+
+    pub(crate) fn to_sarif(&self) -> Value {
+        let level = match self.severity.as_str() {
+            "warning" => "warning",
+            "advice" => "note",
+            _ => "error",
+        };
+
+        let mut ret = json!({
+            "level": level,
+            "message": { "text": self.message },
+        });
+
+        if let Some(code) = &self.code {
+            ret["ruleId"] = json!(code);
+        }
+
+        if let (Some(file), Some(span)) = (&self.file, &self.span) {
+            ret["locations"] = json!([sarif_location(file, span)]);
+        }
+
+        let related: Vec<_> = self
+            .related
+            .iter()
+            .enumerate()
+            .filter_map(|(i, x)| {
+                let file = x.file.as_ref().or(self.file.as_ref())?;
+                let mut location = sarif_location(file, &x.span);
+                location["id"] = json!(i);
+                if let Some(label) = &x.label {
+                    location["message"] = json!({ "text": label });
+                }
+                Some(location)
+            })
+            .collect();
+        if !related.is_empty() {
+            ret["relatedLocations"] = json!(related);
+        }
+
+        ret
+    }
+}
+
+pub struct MessagePrinter {
+    format: MessageFormat,
+    messages: Vec<Message>,
+}
+
+impl MessagePrinter {
+    pub fn new(format: MessageFormat) -> Self {
+        Self {
+            format,
+            messages: Vec::new(),
+        }
+    }
+
+    pub fn is_human(&self) -> bool {
+        matches!(self.format, MessageFormat::Human)
+    }
+
+    pub fn push(&mut self, message: Message) {
+        self.messages.push(message);
+    }
+
+    pub fn report(&mut self, report: &Report) {
+        if let Some(x) = report.downcast_ref::<CheckError>() {
+            for x in &x.related {
+                self.push(Message::from_diagnostic(Some("analyzer"), x));
+            }
+        } else if let Some(x) = report.downcast_ref::<ParserError>() {
+            self.push(Message::from_diagnostic(Some("parser"), x));
+        } else {
+            self.push(Message::from_diagnostic(None, report.as_ref()));
+        }
+    }
+
+    pub fn print(&mut self) {
+        match self.format {
+            MessageFormat::Human => (),
+            MessageFormat::Json => {
+                for message in &self.messages {
+                    println!("{}", message.to_json());
+                }
+            }
+            MessageFormat::Sarif => {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&self.to_sarif()).unwrap()
+                );
+            }
+        }
+        self.messages.clear();
+    }
+
+    /// Print the result of command as messages instead of returning error
+    pub fn finish(mut self, ret: Result<bool>) -> Result<bool> {
+        if self.is_human() {
+            return ret;
+        }
+
+        let ret = match ret {
+            Ok(x) => x && self.messages.is_empty(),
+            Err(x) => {
+                self.report(&x);
+                false
+            }
+        };
+        self.print();
+        Ok(ret)
+    }
+
+    pub(crate) fn to_sarif(&self) -> Value {
+        let mut rules = BTreeMap::new();
+        for message in &self.messages {
+            if let Some(code) = &message.code {
+                rules.entry(code.clone()).or_insert_with(|| {
+                    let mut rule = json!({ "id": code });
+                    if let Some(help) = &message.help {
+                        rule["help"] = json!({ "text": help });
+                    }
+                    if let Some(url) = &message.url {
+                        rule["helpUri"] = json!(url);
+                    }
+                    rule
+                });
+            }
+        }
+
+        let rules: Vec<_> = rules.into_values().collect();
+        let results: Vec<_> = self.messages.iter().map(|x| x.to_sarif()).collect();
+
+        json!({
+            "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "veryl",
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "informationUri": "https://veryl-lang.org/",
+                        "rules": rules,
+                    }
+                },
+                "results": results,
+            }]
+        })
+    }
+}
+
+pub(crate) fn to_span(
+    source: &dyn SourceCode,
+    span: &SourceSpan,
+) -> Option<(Option<String>, Span)> {
+    let beg = source.read_span(span, 0, 0).ok()?;
+    let name = beg.name().map(|x| x.to_string());
+    let start = Position {
+        line: beg.line() + 1,
+        column: beg.column() + 1,
+    };
+
+    let end = SourceSpan::from(span.offset() + span.len());
+    let end = source
+        .read_span(&end, 0, 0)
+        .map(|x| Position {
+            line: x.line() + 1,
+            column: x.column() + 1,
+        })
+        .unwrap_or(start);
+
+    Some((name, Span { start, end }))
+}
+
+fn sarif_location(file: &str, span: &Span) -> Value {
+    let uri = if Path::new(file).is_absolute() {
+        let file = file.replace('\\', "/");
+        if file.starts_with('/') {
+            format!("file://{file}")
+        } else {
+            format!("file:///{file}")
+        }
+    } else {
+        file.to_string()
+    };
+
+    json!({
+        "physicalLocation": {
+            "artifactLocation": { "uri": uri },
+            "region": {
+                "startLine": span.start.line,
+                "startColumn": span.start.column,
+                "endLine": span.end.line,
+                "endColumn": span.end.column,
+            }
+        }
+    })
+}
+
+pub(crate) fn to_snake_case(x: &str) -> String {
+    let mut ret = String::new();
+    for (i, c) in x.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if i != 0 {
+                ret.push('_');
+            }
+            ret.push(c.to_ascii_lowercase());
+        } else {
+            ret.push(c);
+        }
+    }
+    ret
+}
@@ -3,14 +3,16 @@ use handlebars::Handlebars;
 use mdbook::{Config, MDBook};
 use miette::{IntoDiagnostic, Result};
 use serde::Serialize;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fs::{self, File};
 use std::io::Write;
 use std::path::PathBuf;
 use tempfile::TempDir;
+use veryl_analyzer::fsm::StateMachine;
 use veryl_analyzer::symbol::{ClockDomain, ParameterKind, Symbol, SymbolKind};
 use veryl_analyzer::symbol_table;
 use veryl_metadata::Metadata;
+use veryl_parser::resource_table::StrId;
 use veryl_parser::veryl_token::Token;
 
 const SUMMARY_TMPL: &str = r###"
@@ -198,6 +200,19 @@ const MODULE_TMPL: &str = r#"
 </tbody>
 </table>
 {{/if}}
+
+{{#if state_machines}}
+### State Machines
+---
+
+{{#each state_machines}}
+#### {{this.register}}
+
+```mermaid
+{{this.diagram}}
+```
+{{/each}}
+{{/if}}
 "#;
 
 #[derive(Serialize)]
@@ -208,6 +223,13 @@ struct ModuleData {
     parameters: Vec<ParameterData>,
     clock_domains: Vec<String>,
     ports: Vec<PortData>,
+    state_machines: Vec<StateMachineData>,
+}
+
+#[derive(Serialize)]
+struct StateMachineData {
+    register: String,
+    diagram: String,
 }
 
 #[derive(Serialize)]
@@ -354,6 +376,7 @@ pub struct DocBuilder {
     proto_modules: Vec<TopLevelItem>,
     interfaces: Vec<TopLevelItem>,
     packages: Vec<TopLevelItem>,
+    state_machines: HashMap<StrId, Vec<StateMachine>>,
 }
 
 #[derive(Clone)]
@@ -370,6 +393,7 @@ impl DocBuilder {
         proto_modules: Vec<TopLevelItem>,
         interfaces: Vec<TopLevelItem>,
         packages: Vec<TopLevelItem>,
+        state_machines: HashMap<StrId, Vec<StateMachine>>,
     ) -> Result<Self> {
         let temp_dir = tempfile::tempdir().into_diagnostic()?;
         let root_dir = temp_dir.path().to_path_buf();
@@ -388,6 +412,7 @@ impl DocBuilder {
             proto_modules,
             interfaces,
             packages,
+            state_machines,
         })
     }
 
@@ -704,6 +729,19 @@ impl DocBuilder {
                 })
                 .collect();
 
+            let state_machines: Vec<_> = self
+                .state_machines
+                .get(&symbol.token.text)
+                .map(|x| {
+                    x.iter()
+                        .map(|x| StateMachineData {
+                            register: x.register.to_string(),
+                            diagram: x.mermaid(),
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+
             let data = ModuleData {
                 name: name.to_string(),
                 description: symbol.doc_comment.format(false),
@@ -711,6 +749,7 @@ impl DocBuilder {
                 parameters,
                 clock_domains,
                 ports,
+                state_machines,
             };
 
             let mut handlebars = Handlebars::new();
@@ -10,20 +10,26 @@ use std::str::FromStr;
 use std::time::Instant;
 use veryl_metadata::Metadata;
 
+mod analysis_cache;
 mod cmd_build;
+mod cmd_cdc;
 mod cmd_check;
 mod cmd_clean;
 mod cmd_doc;
 mod cmd_dump;
+mod cmd_fix;
 mod cmd_fmt;
+mod cmd_fsm;
 mod cmd_init;
 mod cmd_metadata;
 mod cmd_new;
 mod cmd_publish;
 mod cmd_test;
 mod cmd_update;
+mod diagnostics;
 mod doc;
-mod runner;
+#[cfg(test)]
+mod tests;
 
 // ---------------------------------------------------------------------------------------------------------------------
 // Opt
@@ -51,6 +57,7 @@ enum Commands {
     Init(OptInit),
     Fmt(OptFmt),
     Check(OptCheck),
+    Fix(OptFix),
     Build(OptBuild),
     Clean(OptClean),
     Update(OptUpdate),
@@ -59,6 +66,8 @@ enum Commands {
     Metadata(OptMetadata),
     Dump(OptDump),
     Test(OptTest),
+    Cdc(OptCdc),
+    Fsm(OptFsm),
 }
 
 /// Create a new project
@@ -83,6 +92,10 @@ pub struct OptFmt {
     /// Run fmt in check mode
     #[arg(long)]
     pub check: bool,
+
+    /// Diagnostic message format
+    #[arg(long, value_enum, default_value_t)]
+    pub message_format: MessageFormat,
 }
 
 /// Analyze the current project
@@ -90,6 +103,17 @@ pub struct OptFmt {
 pub struct OptCheck {
     /// Target files
     pub files: Vec<PathBuf>,
+
+    /// Diagnostic message format
+    #[arg(long, value_enum, default_value_t)]
+    pub message_format: MessageFormat,
+}
+
+/// Apply automatic fixes of analyzer suggestions
+#[derive(Args)]
+pub struct OptFix {
+    /// Target files
+    pub files: Vec<PathBuf>,
 }
 
 /// Build the target codes corresponding to the current project
@@ -97,6 +121,25 @@ pub struct OptCheck {
 pub struct OptBuild {
     /// Target files
     pub files: Vec<PathBuf>,
+
+    /// Watch source files and rebuild changed files
+    #[arg(long)]
+    pub watch: bool,
+
+    /// Diagnostic message format
+    #[arg(long, value_enum, default_value_t)]
+    pub message_format: MessageFormat,
+}
+
+#[derive(Clone, Copy, Default, Debug, ValueEnum)]
+pub enum MessageFormat {
+    /// Human-readable text
+    #[default]
+    Human,
+    /// JSON object per line
+    Json,
+    /// SARIF v2.1.0
+    Sarif,
 }
 
 /// Clean-up the current project
@@ -137,6 +180,29 @@ pub struct OptTest {
     pub wave: bool,
 }
 
+/// Report clock domain crossings
+///
+/// Modules are elaborated from the top modules, and signals are followed into instances to find
+/// synchronizers in wrapper modules and combinational paths through them. Paths through
+/// interfaces are not traced. Crossings in generate blocks are reported regardless of their
+/// conditions.
+#[derive(Args)]
+pub struct OptCdc {
+    /// Target files
+    pub files: Vec<PathBuf>,
+}
+
+/// Report state machines
+#[derive(Args)]
+pub struct OptFsm {
+    /// Target files
+    pub files: Vec<PathBuf>,
+
+    /// Output state diagrams in Mermaid
+    #[arg(long)]
+    pub mermaid: bool,
+}
+
 #[derive(Clone, Copy, Debug, ValueEnum)]
 pub enum SimType {
     /// Verilator
@@ -226,6 +292,10 @@ pub struct OptDump {
     /// output unsafe table
     #[arg(long)]
     pub unsafe_table: bool,
+
+    /// output elaborated instance tree from the top module
+    #[arg(long, value_name = "TOP")]
+    pub elaborated: Option<String>,
 }
 
 // ---------------------------------------------------------------------------------------------------------------------
@@ -290,6 +360,7 @@ fn main() -> Result<ExitCode> {
         Commands::Init(x) => cmd_init::CmdInit::new(x).exec()?,
         Commands::Fmt(x) => cmd_fmt::CmdFmt::new(x).exec(&mut metadata)?,
         Commands::Check(x) => cmd_check::CmdCheck::new(x).exec(&mut metadata)?,
+        Commands::Fix(x) => cmd_fix::CmdFix::new(x).exec(&mut metadata)?,
         Commands::Build(x) => cmd_build::CmdBuild::new(x).exec(&mut metadata)?,
         Commands::Clean(x) => cmd_clean::CmdClean::new(x).exec(&mut metadata)?,
         Commands::Update(x) => cmd_update::CmdUpdate::new(x).exec(&mut metadata)?,
@@ -298,6 +369,8 @@ fn main() -> Result<ExitCode> {
         Commands::Metadata(x) => cmd_metadata::CmdMetadata::new(x).exec(&metadata)?,
         Commands::Dump(x) => cmd_dump::CmdDump::new(x).exec(&mut metadata)?,
         Commands::Test(x) => cmd_test::CmdTest::new(x).exec(&mut metadata)?,
+        Commands::Cdc(x) => cmd_cdc::CmdCdc::new(x).exec(&mut metadata)?,
+        Commands::Fsm(x) => cmd_fsm::CmdFsm::new(x).exec(&mut metadata)?,
     };
 
     let elapsed_time = now.elapsed();
@@ -0,0 +1,383 @@
+use crate::diagnostics::{to_snake_case, to_span, Message, MessagePrinter};
+use crate::MessageFormat;
+use miette::{Diagnostic, SourceSpan};
+use veryl_analyzer::Analyzer;
+use veryl_metadata::Metadata;
+use veryl_parser::Parser;
+
+fn metadata() -> Metadata {
+    toml::from_str(&Metadata::create_default_toml("prj").unwrap()).unwrap()
+}
+
+fn analyze(path: &str, input: &str) -> Vec<veryl_analyzer::AnalyzerError> {
+    let metadata = metadata();
+    let analyzer = Analyzer::new(&metadata);
+    analyzer.clear();
+
+    let parser = Parser::parse(input, &path).unwrap();
+    let mut errors = analyzer.analyze_pass1("prj", input, path, &parser.veryl);
+    errors.append(&mut analyzer.analyze_pass2("prj", input, path, &parser.veryl));
+    errors.append(&mut analyzer.analyze_pass3("prj", input, path, &parser.veryl));
+    errors
+}
+
+const MODULE_E: &str = r#"module ModuleE {
+    var a: logic;
+    assign b = 1;
+}
+"#;
+
+fn message_of(code: &str) -> Message {
+    let errors = analyze("/tmp/e.veryl", MODULE_E);
+    let error = errors
+        .iter()
+        .find(|x| x.code().map(|x| x.to_string()).as_deref() == Some(code))
+        .unwrap();
+    Message::from_diagnostic(Some("analyzer"), error)
+}
+
+#[test]
+fn diagnostics_span() {
+    let source = "module A {\n    var a: logic;\n}\n";
+    let offset = source.find("a:").unwrap();
+    let (name, span) = to_span(&source, &SourceSpan::new(offset.into(), 1)).unwrap();
+    assert_eq!(name, None);
+    assert_eq!((span.start.line, span.start.column), (2, 9));
+    assert_eq!((span.end.line, span.end.column), (2, 10));
+
+    // span out of the source is ignored
+    assert!(to_span(&source, &SourceSpan::new(1000.into(), 1)).is_none());
+}
+
+#[test]
+fn diagnostics_rule_id() {
+    assert_eq!(to_snake_case("SyntaxError"), "syntax_error");
+    assert_eq!(to_snake_case("unused_variable"), "unused_variable");
+
+    let message = message_of("unused_variable");
+    assert_eq!(message.code.as_deref(), Some("analyzer::unused_variable"));
+
+    let error = Parser::parse("module A {", &"a.veryl").unwrap_err();
+    let message = Message::from_diagnostic(Some("parser"), &error);
+    assert_eq!(message.code.as_deref(), Some("parser::syntax_error"));
+    assert_eq!(message.severity, "error");
+}
+
+#[test]
+fn diagnostics_json() {
+    let message = message_of("unused_variable");
+    let json = message.to_json();
+    assert_eq!(json["code"], "analyzer::unused_variable");
+    assert_eq!(json["severity"], "warning");
+    assert_eq!(json["file"], "/tmp/e.veryl");
+    assert_eq!(json["span"]["start"]["line"], 2);
+    assert_eq!(json["span"]["start"]["column"], 9);
+    assert_eq!(json["span"]["end"]["line"], 2);
+    assert_eq!(json["span"]["end"]["column"], 10);
+    assert_eq!(json["help"], "add prefix `_` to unused variable name");
+    assert!(json["url"].as_str().unwrap().ends_with("#unused_variable"));
+
+    let message = message_of("undefined_identifier");
+    let json = message.to_json();
+    assert_eq!(json["severity"], "error");
+    assert_eq!(json["span"]["start"]["line"], 3);
+    assert_eq!(json["help"], serde_json::Value::Null);
+}
+
+#[test]
+fn diagnostics_sarif() {
+    let mut printer = MessagePrinter::new(MessageFormat::Sarif);
+    printer.push(message_of("unused_variable"));
+    printer.push(message_of("undefined_identifier"));
+    printer.push(message_of("unused_variable"));
+
+    let sarif = printer.to_sarif();
+    assert_eq!(sarif["version"], "2.1.0");
+    assert_eq!(
+        sarif["$schema"],
+        "https://json.schemastore.org/sarif-2.1.0.json"
+    );
+
+    let runs = sarif["runs"].as_array().unwrap();
+    assert_eq!(runs.len(), 1);
+    let driver = &runs[0]["tool"]["driver"];
+    assert_eq!(driver["name"], "veryl");
+    assert!(driver["version"].is_string());
+
+    // rules are deduplicated and sorted by id
+    let rules = driver["rules"].as_array().unwrap();
+    let ids: Vec<_> = rules.iter().map(|x| x["id"].as_str().unwrap()).collect();
+    assert_eq!(
+        ids,
+        vec![
+            "analyzer::undefined_identifier",
+            "analyzer::unused_variable"
+        ]
+    );
+    assert_eq!(
+        rules[1]["help"]["text"],
+        "add prefix `_` to unused variable name"
+    );
+    assert!(rules[1]["helpUri"].is_string());
+
+    let results = runs[0]["results"].as_array().unwrap();
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0]["level"], "warning");
+    assert_eq!(results[0]["ruleId"], "analyzer::unused_variable");
+    assert_eq!(results[1]["level"], "error");
+    assert!(results[0]["message"]["text"].is_string());
+
+    let location = &results[0]["locations"][0]["physicalLocation"];
+    assert_eq!(location["artifactLocation"]["uri"], "file:///tmp/e.veryl");
+    assert_eq!(location["region"]["startLine"], 2);
+    assert_eq!(location["region"]["startColumn"], 9);
+    assert_eq!(location["region"]["endLine"], 2);
+    assert_eq!(location["region"]["endColumn"], 10);
+}
+
+#[test]
+fn diagnostics_severity() {
+    let mut message = message_of("unused_variable");
+    message.severity = "advice".to_string();
+    assert_eq!(message.to_sarif()["level"], "note");
+    message.severity = "warning".to_string();
+    assert_eq!(message.to_sarif()["level"], "warning");
+    message.severity = "error".to_string();
+    assert_eq!(message.to_sarif()["level"], "error");
+}
+
+mod watch {
+    use crate::cmd_build::{CmdBuild, WatchContext};
+    use crate::{MessageFormat, OptBuild};
+    use std::collections::{HashMap, HashSet};
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use tempfile::TempDir;
+    use veryl_analyzer::{symbol_table, Analyzer};
+    use veryl_metadata::Metadata;
+
+    const PKG_A: &str = "package PkgA {\n    const X: u32 = 1;\n}\n";
+    const MODULE_B: &str = "module ModuleB {\n    let _a: u32 = PkgA::X;\n}\n";
+    const MODULE_C: &str = "module ModuleC {}\n";
+
+    struct Project {
+        dir: TempDir,
+        cmd: CmdBuild,
+        metadata: Metadata,
+        contexts: HashMap<PathBuf, WatchContext>,
+    }
+
+    impl Project {
+        fn new() -> Self {
+            let dir = TempDir::new().unwrap();
+            let toml = Metadata::create_default_toml("prj").unwrap();
+            let toml = format!("{toml}\n\n[build]\nexclude_std = true\n");
+            fs::write(dir.path().join("Veryl.toml"), toml).unwrap();
+            fs::write(dir.path().join("a.veryl"), PKG_A).unwrap();
+            fs::write(dir.path().join("b.veryl"), MODULE_B).unwrap();
+            fs::write(dir.path().join("c.veryl"), MODULE_C).unwrap();
+
+            let metadata = Metadata::load(dir.path().join("Veryl.toml")).unwrap();
+            let cmd = CmdBuild::new(OptBuild {
+                files: vec![],
+                watch: true,
+                message_format: MessageFormat::Json,
+            });
+
+            let mut ret = Self {
+                dir,
+                cmd,
+                metadata,
+                contexts: HashMap::new(),
+            };
+            let all = ["a.veryl", "b.veryl", "c.veryl"];
+            ret.rebuild(&all);
+            ret
+        }
+
+        fn path(&self, name: &str) -> PathBuf {
+            self.dir.path().canonicalize().unwrap().join(name)
+        }
+
+        fn rebuild(&mut self, changed: &[&str]) {
+            let paths = self.metadata.paths::<&Path>(&[], true).unwrap();
+            let analyzer = Analyzer::new(&self.metadata);
+            let changed: HashSet<_> = changed.iter().map(|x| self.path(x)).collect();
+            self.cmd.rebuild(
+                &self.metadata,
+                &paths,
+                &analyzer,
+                &mut self.contexts,
+                changed,
+                &None,
+            );
+        }
+
+        fn has_error(&self, name: &str) -> bool {
+            self.contexts[&self.path(name)].has_error
+        }
+    }
+
+    fn has_symbol(name: &str) -> bool {
+        symbol_table::get_all()
+            .iter()
+            .any(|x| x.token.to_string() == name)
+    }
+
+    #[test]
+    fn dependents() {
+        let project = Project::new();
+        let a = project.path("a.veryl");
+        let b = project.path("b.veryl");
+        let c = project.path("c.veryl");
+
+        let targets = CmdBuild::dependents(HashSet::from([a.clone()]));
+        assert_eq!(targets, HashSet::from([a, b.clone()]));
+
+        let targets = CmdBuild::dependents(HashSet::from([b.clone()]));
+        assert_eq!(targets, HashSet::from([b]));
+
+        let targets = CmdBuild::dependents(HashSet::from([c.clone()]));
+        assert_eq!(targets, HashSet::from([c]));
+    }
+
+    #[test]
+    fn rebuild_dependents() {
+        let mut project = Project::new();
+        assert!(!project.has_error("a.veryl"));
+        assert!(!project.has_error("b.veryl"));
+        assert!(has_symbol("X"));
+
+        // Only a.veryl is changed, but b.veryl refers the removed symbol
+        fs::write(project.path("a.veryl"), PKG_A.replace('X', "Y")).unwrap();
+        project.rebuild(&["a.veryl"]);
+        assert!(!has_symbol("X"));
+        assert!(has_symbol("Y"));
+        assert!(!project.has_error("a.veryl"));
+        assert!(project.has_error("b.veryl"));
+        assert!(!project.has_error("c.veryl"));
+
+        // The error of b.veryl is resolved by the next change of a.veryl
+        fs::write(project.path("a.veryl"), PKG_A).unwrap();
+        project.rebuild(&["a.veryl"]);
+        assert!(has_symbol("X"));
+        assert!(!has_symbol("Y"));
+        assert!(!project.has_error("b.veryl"));
+    }
+
+    #[test]
+    fn rebuild_removed() {
+        let mut project = Project::new();
+        assert!(has_symbol("ModuleC"));
+
+        fs::remove_file(project.path("c.veryl")).unwrap();
+        project.rebuild(&["c.veryl"]);
+        assert!(!has_symbol("ModuleC"));
+        assert!(!project.contexts.contains_key(&project.path("c.veryl")));
+
+        // Removing the referred file makes an error in the dependent
+        fs::remove_file(project.path("a.veryl")).unwrap();
+        project.rebuild(&["a.veryl"]);
+        assert!(!has_symbol("PkgA"));
+        assert!(project.has_error("b.veryl"));
+    }
+
+    #[test]
+    fn rebuild_renamed() {
+        let mut project = Project::new();
+
+        fs::rename(project.path("a.veryl"), project.path("d.veryl")).unwrap();
+        project.rebuild(&["a.veryl", "d.veryl"]);
+        assert!(!project.contexts.contains_key(&project.path("a.veryl")));
+        assert!(!project.has_error("d.veryl"));
+        assert!(!project.has_error("b.veryl"));
+        assert_eq!(
+            symbol_table::get_all()
+                .iter()
+                .filter(|x| x.token.to_string() == "PkgA")
+                .count(),
+            1
+        );
+    }
+}
+
+mod analysis_cache {
+    use crate::analysis_cache::AnalysisCache;
+    use crate::cmd_check;
+    use std::fs;
+    use tempfile::TempDir;
+    use veryl_analyzer::Analyzer;
+    use veryl_metadata::Metadata;
+    use veryl_path::PathPair;
+
+    const MODULE_B: &str =
+        "module ModuleB (\n    o: output logic,\n) {\n    inst u: ModuleD (o);\n}\n";
+    const MODULE_D: &str = "module ModuleD (\n    o: output logic,\n) {\n    assign o = 1;\n}\n";
+    const MODULE_W: &str = "module ModuleW {\n    var a: logic;\n}\n";
+
+    fn check(metadata: &Metadata, paths: &[PathPair], cache: &AnalysisCache) -> Vec<String> {
+        Analyzer::new(metadata).clear();
+        let (_, check_error) =
+            cmd_check::analyze_with_cache(metadata, paths, false, cache).unwrap();
+        check_error.related.iter().map(|x| format!("{x}")).collect()
+    }
+
+    #[test]
+    fn skip_immutable() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        let dep = root.join("dep");
+        fs::create_dir(&dep).unwrap();
+
+        let toml = Metadata::create_default_toml("prj").unwrap();
+        let toml = format!("{toml}\n\n[build]\nexclude_std = true\n");
+        fs::write(root.join("Veryl.toml"), toml).unwrap();
+        let metadata = Metadata::load(root.join("Veryl.toml")).unwrap();
+
+        let mut paths = Vec::new();
+        for (path, text) in [
+            (root.join("b.veryl"), MODULE_B),
+            (dep.join("d.veryl"), MODULE_D),
+            (dep.join("w.veryl"), MODULE_W),
+        ] {
+            fs::write(&path, text).unwrap();
+            paths.push(PathPair {
+                prj: "prj".to_string(),
+                src: path.clone(),
+                dst: path.with_extension("sv"),
+            });
+        }
+
+        let cache = AnalysisCache::with_dir(&metadata, root.join("cache"), Some(dep.clone()));
+        let keys: Vec<_> = paths
+            .iter()
+            .map(|x| cache.key(x, &fs::read_to_string(&x.src).unwrap()))
+            .collect();
+        assert!(keys[0].is_none());
+        let key_d = keys[1].clone().unwrap();
+        let key_w = keys[2].clone().unwrap();
+
+        // Only clean files are cached
+        let errors = check(&metadata, &paths, &cache);
+        assert_eq!(errors, ["a is unused", "a is unassigned"]);
+        assert!(cache.contains(&key_d));
+        assert!(!cache.contains(&key_w));
+
+        // The cached file is still referred by other files
+        let errors = check(&metadata, &paths, &cache);
+        assert_eq!(errors, ["a is unused", "a is unassigned"]);
+
+        // Checks of cached files are skipped
+        cache.insert(&key_w);
+        let errors = check(&metadata, &paths, &cache);
+        assert!(errors.is_empty(), "{errors:?}");
+
+        // Analysis settings are a part of the key
+        let mut metadata = metadata;
+        metadata.lint.width.implicit_truncation = !metadata.lint.width.implicit_truncation;
+        let cache = AnalysisCache::with_dir(&metadata, root.join("cache"), Some(dep));
+        let key = cache.key(&paths[1], MODULE_D).unwrap();
+        assert_ne!(key, key_d);
+        assert!(!cache.contains(&key));
+    }
+}
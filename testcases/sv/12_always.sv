@@ -87,9 +87,9 @@ module veryl_testcase_Module12_2 (
 
     // always_comb declaration
     always_comb begin
-        a    = 10;
-        aa   = 10'b0;
-        aa.a = 10'b01z;
+        a  = 10;
+        aa = 10'b0;
+        aa = 10'b01z;
 
         a  = 10 + 10;
         aa = 10 + 16'hffff * (3 / 4);
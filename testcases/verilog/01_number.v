@@ -0,0 +1,40 @@
+module veryl_testcase_Module01;
+    // integer
+    localparam [32-1:0] a = 0123456789;
+    localparam [32-1:0] aa = 01234_56789;
+
+    // binary
+    localparam [32-1:0] b = 32'b01xzXZ;
+    localparam [32-1:0] bb = 32'b01_xz_XZ;
+    localparam [32-1:0] bbb = 32'sb01_xz_XZ;
+
+    // octal
+    localparam [32-1:0] c = 32'o01234567xzXZ;
+    localparam [32-1:0] cc = 32'o01234_567xzXZ;
+    localparam [32-1:0] ccc = 32'so01234_567xzXZ;
+
+    // decimal
+    localparam [32-1:0] d = 32'd0123456789;
+    localparam [32-1:0] dd = 32'd01234_56789;
+    localparam [32-1:0] ddd = 32'sd01234_56789;
+
+    // hex
+    localparam [32-1:0] e = 128'h0123456789abcdefxzABCDEFXZ;
+    localparam [32-1:0] ee = 128'h01234_5678_9abc_defxz_ABCD_EFXZ;
+    localparam [32-1:0] eee = 128'sh01234_5678_9abc_defxz_ABCD_EFXZ;
+
+    // all0, all1, allx, allz
+    localparam [32-1:0] f = 0;
+    localparam [32-1:0] ff = ~0;
+    localparam [32-1:0] fff = 'bx;
+    localparam [32-1:0] ffff = 'bx;
+    localparam [32-1:0] fffff = 'bz;
+    localparam [32-1:0] ffffff = 'bz;
+
+    // floating point
+    localparam [32-1:0] g = 0123456789.0123456789;
+    localparam [32-1:0] gg = 0123456789.0123456789e+0123456789;
+    localparam [32-1:0] ggg = 0123456789.0123456789e-0123456789;
+    localparam [32-1:0] gggg = 0123456789.0123456789E+0123456789;
+    localparam [32-1:0] ggggg = 0123456789.0123456789E-0123456789;
+endmodule
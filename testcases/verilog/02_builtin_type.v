@@ -0,0 +1,49 @@
+module veryl_testcase_Module02;
+    // unsigned integer
+    wire [32-1:0] _a;
+    assign _a = 1;
+    wire [64-1:0] _aa;
+    assign _aa = 1;
+
+    // signed integer
+    wire signed [32-1:0] _b;
+    assign _b = 1;
+    wire signed [64-1:0] _bb;
+    assign _bb = 1;
+
+    // floating point
+    real _c;
+    assign _c = 1;
+    real _cc;
+    assign _cc = 1;
+
+    // 4 state (01xz) type
+    wire _d;
+    assign _d = 1;
+    wire [10-1:0] _dd;
+    assign _dd = 1;
+    wire [100-1:0] _ddd;
+    assign _ddd = 1;
+
+    // 2 state (01) type
+    wire _e;
+    assign _e = 1;
+    wire [10-1:0] _ee;
+    assign _ee = 1;
+    wire [100-1:0] _eee;
+    assign _eee = 1;
+
+    // array
+    wire [32-1:0] _f [0:10-1];
+    assign _f = 1;
+    wire [64-1:0] _ff [0:10-1];
+    assign _ff = 1;
+    wire signed [32-1:0] _fff [0:10-1];
+    assign _fff = 1;
+    wire signed [64-1:0] _ffff [0:10-1];
+    assign _ffff = 1;
+    real _fffff [0:10-1];
+    assign _fffff = 1;
+    real _ffffff [0:10-1];
+    assign _ffffff = 1;
+endmodule
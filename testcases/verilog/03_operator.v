@@ -0,0 +1,93 @@
+module veryl_testcase_Module03;
+    // unary arithmetic
+    wire _a;
+    assign _a = +1;
+    wire _aa;
+    assign _aa = -1;
+
+    // unary logical
+    wire _b;
+    assign _b = !1;
+    wire _bb;
+    assign _bb = ~1;
+
+    // unary reduce
+    wire _c;
+    assign _c = &1;
+    wire _cc;
+    assign _cc = |1;
+    wire _ccc;
+    assign _ccc = ^1;
+    wire _cccc;
+    assign _cccc = ~&1;
+    wire _ccccc;
+    assign _ccccc = ~|1;
+    wire _cccccc;
+    assign _cccccc = ~^1;
+    wire _ccccccc;
+    assign _ccccccc = ^~1;
+
+    // binary arithmetic
+    wire _d;
+    assign _d = 1 ** 1;
+    wire _dd;
+    assign _dd = 1 * 1;
+    wire _ddd;
+    assign _ddd = 1 / 1;
+    wire _dddd;
+    assign _dddd = 1 % 1;
+    wire _ddddd;
+    assign _ddddd = 1 + 1;
+    wire _dddddd;
+    assign _dddddd = 1 - 1;
+
+    // binary shift
+    wire _e;
+    assign _e = 1 << 1;
+    wire _ee;
+    assign _ee = 1 >> 1;
+    wire _eee;
+    assign _eee = 1 <<< 1;
+    wire _eeee;
+    assign _eeee = 1 >>> 1;
+
+    // binary compare
+    wire _f;
+    assign _f = 1 < 1;
+    wire _ff;
+    assign _ff = 1 <= 1;
+    wire _fff;
+    assign _fff = 1 > 1;
+    wire _ffff;
+    assign _ffff = 1 >= 1;
+    wire _fffff;
+    assign _fffff = 1 == 1;
+    wire _ffffff;
+    assign _ffffff = 1 != 1;
+    wire _fffffff;
+    assign _fffffff = 1 === 1;
+    wire _ffffffff;
+    assign _ffffffff = 1 !== 1;
+    wire _fffffffff;
+    assign _fffffffff = 1 ==? 1;
+    wire _ffffffffff;
+    assign _ffffffffff = 1 !=? 1;
+
+    // binary bitwise
+    wire _g;
+    assign _g = 1 & 1;
+    wire _gg;
+    assign _gg = 1 ^ 1;
+    wire _ggg;
+    assign _ggg = 1 ~^ 1;
+    wire _gggg;
+    assign _gggg = 1 ^~ 1;
+    wire _ggggg;
+    assign _ggggg = 1 | 1;
+
+    // binary logical
+    wire _h;
+    assign _h = 1 && 1;
+    wire _hh;
+    assign _hh = 1 || 1;
+endmodule
@@ -0,0 +1,48 @@
+module veryl_testcase_Module06;
+    localparam [32-1:0] ParamX = 1;
+
+    // function without parameter
+    function automatic [ParamX-1:0] FuncA(
+        input reg [ParamX-1:0] a,
+        output reg [ParamX-1:0] b,
+        inout reg [ParamX-1:0] c
+    );
+        reg [32-1:0] d;
+        begin
+            d = 1;
+            b = a + 1 + d;
+            c = a / 1;
+            FuncA = a + 2;
+        end
+    endfunction
+
+    // void function
+    task automatic FuncC(
+        input reg [ParamX-1:0] a,
+        inout reg [ParamX-1:0] c
+    );
+        begin
+            c = a / 1;
+        end
+    endtask
+
+    wire [ParamX-1:0] a;
+    assign a = 1;
+    wire [ParamX-1:0] b;
+    assign b = 1;
+    wire [ParamX-1:0] c;
+    assign c = 1;
+    wire [ParamX-1:0] d;
+    wire [ParamX-1:0] e;
+
+    // function call
+    assign d = FuncA(a, b, c);
+
+    // void function call
+    initial begin
+        FuncC(a, c);
+    end
+
+    // system function call
+    assign e = $clog2(a);
+endmodule
@@ -0,0 +1,93 @@
+module veryl_testcase_Module07;
+    reg a;
+    reg aa;
+    wire clk;
+    assign clk = 1;
+
+    always @* begin : veryl_block_1
+        // assignment statement
+        reg [32-1:0] i;
+        a = 1;
+        a = a + (1);
+        a = a - (1);
+        a = a * (1);
+        a = a / (1);
+        a = a % (1);
+        a = a & (1);
+        a = a | (1);
+        a = a ^ (1);
+        a = a << (1);
+        a = a >> (1);
+        a = a <<< (1);
+        a = a >>> (1);
+
+        // if statement
+        if (a) begin
+            a = 1;
+            aa = 1;
+        end else if (a) begin
+            a = 1;
+            aa = 1;
+        end else begin
+            a = 1;
+            aa = 1;
+        end
+
+        // for statement
+        for (i = 0; i < 10; i = i + 1) begin
+            a = i;
+            aa = i + 1;
+        end
+
+        // for statement with closed range
+        for (i = 0; i <= 10; i = i + 1) begin
+            a = i;
+            aa = i + 1;
+        end
+
+        // for statement with custom step
+        for (i = 0; i < 10; i = i + (2)) begin
+            a = i;
+            aa = i + 1;
+        end
+        for (i = 0; i < 10; i = i * (2)) begin
+            a = i;
+            aa = i + 1;
+        end
+
+        // for statement with break statement
+        for (i = 0; i < 10; i = i + 1) begin
+            a = i;
+            aa = i + 1;
+            if (i == 0) begin
+                break;
+            end
+        end
+
+        for (i = 0; i < 10; i = i + 1) begin : veryl_block_2
+            reg [32-1:0] j;
+            for (j = 0; j < 10; j = j + 1) begin
+                a = i;
+                aa = i + j;
+                if (i == 0 && j == 0) begin
+                    break;
+                end
+            end
+        end
+    end
+
+    always @ (posedge clk) begin
+        a <= a + (1);
+        a <= a - (1);
+        a <= a * (1);
+        a <= a / (1);
+        a <= a % (1);
+        a <= a & (1);
+        a <= a | (1);
+        a <= a ^ (1);
+        a <= a << (1);
+        a <= a >> (1);
+        a <= a <<< (1);
+        a <= a >>> (1);
+    end
+endmodule
@@ -0,0 +1,52 @@
+module veryl_testcase_Module08;
+    localparam [32-1:0] a = 1;
+    localparam [32-1:0] b = 1;
+    wire i_clk;
+    assign i_clk = 1;
+
+    // if declaration
+    generate
+    if (a == 1) begin :label
+        reg a;
+        always @ (posedge i_clk) begin
+            a <= 1;
+        end
+    end else if (b == 1) begin :label // label can be omit in else clause
+        reg a;
+        always @ (posedge i_clk) begin
+            a <= 1;
+        end
+    end else if (b == 1) begin :label1 // label can be override in the specified clause only
+        reg a;
+        always @ (posedge i_clk) begin
+            a <= 1;
+        end
+    end else begin :label
+        reg a;
+        always @ (posedge i_clk) begin
+            a <= 1;
+        end
+    end
+
+    // for declaration
+    endgenerate
+    generate
+    genvar i;
+    for (i = 0; i < 10; i = i + 1) begin :label2
+        reg a;
+        always @ (posedge i_clk) begin
+            a <= i;
+        end
+    end
+
+    // for declaration with custom step
+    endgenerate
+    generate
+    for (i = 0; i < 10; i += 2) begin :label3
+        reg a;
+        always @ (posedge i_clk) begin
+            a <= i;
+        end
+    end
+    endgenerate
+endmodule
@@ -0,0 +1,58 @@
+module veryl_testcase_Module09;
+    // struct declaration
+    localparam A_aaa_WIDTH = 32;
+    localparam A_aaa_OFFSET = 0;
+    localparam A_aa_WIDTH = 10;
+    localparam A_aa_OFFSET = A_aaa_OFFSET + A_aaa_WIDTH;
+    localparam A_a_WIDTH = 10;
+    localparam A_a_OFFSET = A_aa_OFFSET + A_aa_WIDTH;
+    localparam A_WIDTH = A_a_OFFSET + A_a_WIDTH;
+
+    // enum declaration
+    localparam [2-1:0] B_X = 1;
+    localparam [2-1:0] B_Y = 2;
+    localparam [2-1:0] B_Z = 2'd3;
+
+    localparam [3-1:0] C_X = 2;
+    localparam [3-1:0] C_Y = 3;
+    localparam [3-1:0] C_Z = 3'd4;
+
+    localparam [1-1:0] D_X = 1'd0;
+
+    localparam [2-1:0] E_X = 2'd0;
+    localparam [2-1:0] E_Y = 2'd1;
+    localparam [2-1:0] E_Z = 2'd2;
+
+    localparam [3-1:0] F_X = 3'd1;
+    localparam [3-1:0] F_Y = 3'd2;
+    localparam [3-1:0] F_Z = 3'd4;
+
+    localparam [2-1:0] G_X = 2'd0;
+    localparam [2-1:0] G_Y = 2'd1;
+    localparam [2-1:0] G_Z = 2'd3;
+
+    localparam [1-1:0] FOO_H_0 = 1'd0;
+    localparam [1-1:0] FOO_H_1 = 1'd1;
+
+    wire [A_WIDTH-1:0] a;
+    wire [2-1:0] b;
+    wire [3-1:0] c;
+    wire d;
+    wire [2-1:0] e;
+    wire [3-1:0] f;
+    wire [2-1:0] g;
+    wire h;
+    wire i;
+
+    assign a[A_a_OFFSET +: A_a_WIDTH] = 1;
+    assign a[A_aa_OFFSET +: A_aa_WIDTH] = 1;
+    assign a[A_aaa_OFFSET +: A_aaa_WIDTH] = 1;
+    assign b = B_X;
+    assign c = C_X;
+    assign d = D_X;
+    assign e = E_X;
+    assign f = F_X;
+    assign g = G_X;
+    assign h = FOO_H_0;
+    assign i = a[A_a_OFFSET +: A_a_WIDTH];
+endmodule
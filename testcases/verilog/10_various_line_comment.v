@@ -0,0 +1,57 @@
+module //a
+ veryl_testcase_Module10 //a
+ (
+
+    input //a
+     wire i_clk // a
+    ,
+    input wire i_rst_n,
+    input wire i_up,
+
+    input wire i_down,
+    output wire [8-1:0] o_count
+);
+
+    reg [8-1:0] count //a
+    ;
+    reg [2-1:0] up_down;
+
+    assign o_count = count;
+
+    always @* // a
+     begin
+        up_down = // a
+         (i_up // a
+         << //a
+         1) // a
+         | i_down;
+    end
+
+    always // a
+     @ (posedge i_clk // a
+    , // a
+     negedge i_rst_n // a
+    ) // a
+     begin
+        if // a
+         (!i_rst_n) begin
+            count <= 0;
+        end // a
+         else //
+         if // a
+         (up_down // a
+         == // a
+         2'b10) begin
+            count <= count // a
+             + 1 //a
+            ;
+        end // a
+         else // a
+         if //a
+         (up_down == 2'b01) begin
+            count // a
+             <= count - // a
+             1;
+        end
+    end
+endmodule
@@ -0,0 +1,15 @@
+module veryl_testcase_Module11;
+    // variable declaration
+    wire b;
+    wire [10-1:0] bb;
+    wire [100-1:0] _bbb;
+    assign _bbb = 1;
+
+    // variable declaration with assignment
+    wire [10-1:0] _c;
+    assign _c = 1;
+
+    // assign declaration
+    assign b = 1;
+    assign bb = 1;
+endmodule
@@ -0,0 +1,97 @@
+module veryl_testcase_Module12_1 (
+    input wire i_clk,
+    input wire i_rst_n
+);
+    reg a;
+    reg b;
+
+    always @ (posedge i_clk, negedge i_rst_n) begin
+        if (!i_rst_n) begin
+            a <= 0;
+        end else begin
+            a <= ~a;
+        end
+    end
+
+    always @ (posedge i_clk) begin
+        b <= a;
+    end
+endmodule
+
+module veryl_testcase_Module12_2 (
+    input wire i_clk,
+    input wire i_clk_p,
+    input wire i_clk_n,
+    input wire i_rst_n,
+    input wire i_rst_ah,
+    input wire i_rst_al,
+    input wire i_rst_sh,
+    input wire i_rst_sl
+);
+    reg a;
+    reg aa;
+    wire b;
+    assign b = 1;
+    wire c;
+    assign c = 1;
+
+    // always_ff declaration with default polarity
+    always @ (posedge i_clk, negedge i_rst_n) begin
+        if (!i_rst_n) begin
+            a <= 1'b0;
+        end else if (a) begin
+            a <= b[0];
+        end else begin
+            a <= c[5:0];
+        end
+    end
+
+    // always_ff declaration without reset
+    always @ (posedge i_clk) begin
+        if (a) begin
+            a <= b;
+        end else begin
+            a <= c[5:0];
+        end
+    end
+
+    // always_ff declaration with specified polarity
+    always @ (posedge i_clk_p, posedge i_rst_ah) begin
+        if (i_rst_ah) begin
+            a <= 1'b0;
+        end else begin
+            a <= c[5:0];
+        end
+    end
+    always @ (negedge i_clk_n, negedge i_rst_al) begin
+        if (!i_rst_al) begin
+            a <= 1'b0;
+        end else begin
+            a <= c[5:0];
+        end
+    end
+    always @ (posedge i_clk_p) begin
+        if (i_rst_sh) begin
+            a <= 1'b0;
+        end else begin
+            a <= c[5:0];
+        end
+    end
+    always @ (negedge i_clk_n) begin
+        if (!i_rst_sl) begin
+            a <= 1'b0;
+        end else begin
+            a <= c[5:0];
+        end
+    end
+
+    // always_comb declaration
+    always @* begin
+        a = 10;
+        aa = 10'b0;
+        aa = 10'b01z;
+
+        a = 10 + 10;
+        aa = 10 + 16'hffff * (3 / 4);
+    end
+endmodule
@@ -0,0 +1,22 @@
+module veryl_testcase_Module13;
+    wire a;
+    wire b;
+    wire c;
+    wire d;
+    wire e;
+    wire X;
+    assign X = 1;
+
+    // bit select
+    assign a = X[0];
+
+    // range select
+    assign b = X[1:0];
+
+    // position and width
+    assign c = X[1+:2];
+    assign d = X[1-:2];
+
+    // index by step
+    assign e = X[1*(2)+:(2)];
+endmodule
@@ -0,0 +1,43 @@
+module veryl_testcase_Module14;
+    localparam [32-1:0] X = 1;
+
+    wire a;
+    assign a = 1;
+    wire aa;
+    assign aa = 1;
+    wire bbb;
+    assign bbb = 1;
+
+    // module instantiation
+    veryl_testcase_Module14B x ();
+
+    // module instantiation with parameter and port
+    veryl_testcase_Module14C #(
+        .X (X),
+        .Y (10)
+    ) xx (
+        .a (a),
+        .bb (aa),
+        .bbbb (bbb)
+    );
+
+    // interface instantiation
+
+
+
+
+
+endmodule
+
+module veryl_testcase_Module14B;
+endmodule
+
+module veryl_testcase_Module14C #(
+    parameter [32-1:0] X = 1,
+    parameter [32-1:0] Y = 1
+) (
+    input wire [32-1:0] a,
+    input wire [32-1:0] bb,
+    input wire [32-1:0] bbbb
+);
+endmodule
@@ -0,0 +1,28 @@
+module veryl_testcase_Module15;
+    wire _a;
+    assign _a = 1;
+
+    generate
+    if (1) begin :label
+        wire _a;
+        assign _a = 1;
+    end
+    endgenerate
+
+    generate
+    if (1) begin :label1
+        wire _a;
+        assign _a = 1;
+    end
+    endgenerate
+
+    generate
+    genvar i;
+    for (i = 0; i < 10; i = i + 1) begin :label2
+        if (1) begin :label
+            wire _a;
+            assign _a = 1;
+        end
+    end
+    endgenerate
+endmodule
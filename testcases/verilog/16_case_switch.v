@@ -0,0 +1,40 @@
+module veryl_testcase_Module16;
+    localparam [0:0] y = 1;
+
+    reg a;
+    reg b;
+    wire x;
+    assign x = 1;
+    wire [3-1:0] z;
+    assign z = 1;
+
+    always @* begin
+        case (1'b1)
+            (x) == (0): a = 1;
+            (x) == (1): a = 1;
+            (x) == (2): begin
+                            a = 1;
+                            a = 1;
+                            a = 1;
+                        end
+            (x) == (3), (x) == (4): a = 1;
+            ((x) >= (5)) && ((x) <= (7)): a = 1;
+            (x) == (y - 1): a = 1;
+            default: a = 1;
+        endcase
+    end
+
+    always @* begin
+        case (1'b1)
+            z == 0: b = 1;
+            z == 1: b = 1;
+            z == 2: begin
+                        b = 1;
+                        b = 1;
+                        b = 1;
+                    end
+            z == 3, z == 4: b = 1;
+            default: b = 1;
+        endcase
+    end
+endmodule
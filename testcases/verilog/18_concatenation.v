@@ -0,0 +1,9 @@
+module veryl_testcase_Module18;
+    wire a;
+    wire b;
+    wire c;
+    assign c = 1;
+
+    assign a = {a[10:0], c};
+    assign b = {{10{a[10:0]}}, {4{c}}};
+endmodule
@@ -0,0 +1,7 @@
+
+
+
+module veryl_testcase_Module19;
+
+
+endmodule
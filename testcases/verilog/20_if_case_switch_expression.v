@@ -0,0 +1,45 @@
+module veryl_testcase_Module20;
+    wire a;
+    wire b;
+    wire c;
+    wire x;
+    assign x = 1;
+    wire y;
+    assign y = 1;
+
+    assign a = ((x) ? (
+        1
+    ) : (
+        ((y) ? (
+            1
+        ) : (
+            2
+        ))
+    ));
+
+    assign b = (((a) == (1)) ? (
+        0
+    ) : ((a) == (2)) ? (
+        1
+    ) : ((a) == (3)) ? (
+        2
+    ) : ((a) == (4)) ? (
+        2
+    ) : (((a) >= (5)) && ((a) <= (7))) ? (
+        3
+    ) : (
+        4
+    ));
+
+    assign c = (((a == 1) == 1'b1) ? (
+        0
+    ) : ((a == 2) == 1'b1) ? (
+        1
+    ) : ((a == 3) == 1'b1) ? (
+        2
+    ) : ((a == 4) == 1'b1) ? (
+        2
+    ) : (
+        3
+    ));
+endmodule
@@ -0,0 +1,13 @@
+module veryl_testcase_Module21;
+    wire a;
+    wire b;
+    assign b = 1;
+
+    localparam [1-1:0] EnumA_A = 1'd0;
+    localparam [1-1:0] EnumA_B = 1'd1;
+
+    localparam [1-1:0] EnumB_C = 1'd0;
+    localparam [1-1:0] EnumB_D = 1'd1;
+
+    assign a = (((b)));
+endmodule
@@ -0,0 +1,10 @@
+module veryl_testcase_Module22;
+    wire signed [10-1:0] _a;
+    assign _a = 1;
+    tri signed [10-1:0] _b;
+    assign _b = 1;
+    tri signed [10-1:0] _c;
+    assign _c = 1;
+    wire [10-1:0] _d;
+    assign _d = 1;
+endmodule
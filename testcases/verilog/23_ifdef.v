@@ -0,0 +1,42 @@
+module veryl_testcase_Module23 #(
+
+    parameter [32-1:0] ParamA = 1,
+    parameter [32-1:0] ParamB = 1,
+
+    parameter [32-1:0] ParamC = 1
+
+) (
+
+    input wire port_a,
+    input wire port_b,
+
+    input wire port_c
+
+);
+    `ifdef DEFINE_A
+    `ifdef DEFINE_B
+    wire [10-1:0] _a;
+    assign _a = 1;
+    `endif
+    `endif
+
+    `ifdef DEFINE_A
+    wire [10-1:0] _b;
+    assign _b = 1;
+    wire [10-1:0] _c;
+    assign _c = 1;
+    `endif
+
+endmodule
+
+`ifdef DEFINE_A
+module veryl_testcase_Module23_A;
+endmodule
+`endif
+
+`ifndef DEFINE_A
+module veryl_testcase_Module23_B;
+endmodule
+module veryl_testcase_Module23_C;
+endmodule
+`endif
@@ -0,0 +1,8 @@
+module veryl_testcase_Module24;
+    (* ram_style="block" *)
+    wire _a;
+    assign _a = 1;
+    (* mark_debug="true" *)
+    wire _b;
+    assign _b = 1;
+endmodule
@@ -0,0 +1,10 @@
+module veryl_testcase_Module26;
+    wire [10-1:0] _a;
+    assign _a = 1;
+    wire [100-1:0] _b;
+    assign _b = 1;
+    wire [100-1:0] _c [0:10-1];
+    assign _c = 1;
+    wire [100-1:0] _d [0:10-1][0:10-1];
+    assign _d = 1;
+endmodule
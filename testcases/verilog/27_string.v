@@ -0,0 +1,6 @@
+module veryl_testcase_Module27;
+    localparam a = "aaa";
+
+    wire _b;
+    assign _b = "bbb";
+endmodule
@@ -0,0 +1,14 @@
+module veryl_testcase_Module28;
+    localparam [32-1:0] WIDTH0 = 10;
+    localparam [32-1:0] WIDTH1 = 20;
+
+    wire [200-1:0] a;
+    assign a = 1;
+    wire [(WIDTH0 + 10)*WIDTH1-1:0] b;
+    assign b = 1;
+
+    wire _x;
+    assign _x = a[((10) - 1)][((20) - 1):0 + 1];
+    wire _y;
+    assign _y = b[((WIDTH0 + 10) - 1) - 3][((WIDTH1) - 1) + 5:0];
+endmodule
@@ -0,0 +1,24 @@
+module veryl_testcase_Module29 (
+    input wire clk,
+    input wire rst_n
+);
+    reg a;
+    reg b;
+
+    wire c;
+    assign c = 1;
+
+    always @ (posedge clk, negedge rst_n) begin
+        if (!rst_n) begin
+            a <= 0;
+        end else begin
+            a <= 0;
+            b <= 0;
+        end
+    end
+
+    veryl_testcase_Module29 u0 (
+
+
+    );
+endmodule
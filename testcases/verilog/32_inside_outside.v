@@ -0,0 +1,7 @@
+module veryl_testcase_Module32;
+    wire a;
+    wire b;
+
+    assign a = ((1 + 2 / 3) == (0) || ((1 + 2 / 3) >= (0)) && ((1 + 2 / 3) < (10)) || ((1 + 2 / 3) >= (1)) && ((1 + 2 / 3) <= (10)));
+    assign b = !((1 * 2 - 1) == (0) || ((1 * 2 - 1) >= (0)) && ((1 * 2 - 1) < (10)) || ((1 * 2 - 1) >= (1)) && ((1 * 2 - 1) <= (10)));
+endmodule
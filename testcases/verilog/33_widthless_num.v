@@ -0,0 +1,11 @@
+module veryl_testcase_Module33;
+    localparam [32-1:0] a0 = 1'b1;
+    localparam [32-1:0] a1 = 4'b1010;
+    localparam [32-1:0] a2 = 1'o1;
+    localparam [32-1:0] a3 = 10'o1234;
+    localparam [32-1:0] a4 = 1'd1;
+    localparam [32-1:0] a5 = 24'd12345678;
+    localparam [32-1:0] a6 = 1'h1;
+    localparam [32-1:0] a7 = 32'hffffffff;
+    localparam [32-1:0] a8 = 208'hffff_ffff_ffff_ffff_ffff_ffff_ffff_ffff_ffff_ffff_ffff_ffff_ffff;
+endmodule
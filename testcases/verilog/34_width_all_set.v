@@ -0,0 +1,10 @@
+module veryl_testcase_Module34;
+    localparam [32-1:0] a0 = 0;
+    localparam [32-1:0] a1 = ~0;
+    localparam [32-1:0] a2 = 'bx;
+    localparam [32-1:0] a3 = 'bz;
+    localparam [32-1:0] a4 = 10'b0000000000;
+    localparam [32-1:0] a5 = 10'b1111111111;
+    localparam [32-1:0] a6 = 10'bxxxxxxxxxx;
+    localparam [32-1:0] a7 = 10'bzzzzzzzzzz;
+endmodule
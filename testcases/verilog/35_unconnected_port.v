@@ -0,0 +1,17 @@
+module veryl_testcase_Module35;
+    wire aa;
+    assign aa = 1;
+
+    veryl_testcase_Module35B xx (
+        .aa (aa),
+        .bb (),
+        .bbbb ()
+    );
+endmodule
+
+module veryl_testcase_Module35B (
+    input wire [32-1:0] aa,
+    input wire [32-1:0] bb,
+    input wire [32-1:0] bbbb
+);
+endmodule
@@ -0,0 +1,41 @@
+/// Test module for doc comment
+///
+/// * list item0
+/// * list item1
+///
+/// ```wavedrom
+/// {signal: [
+///   {name: 'clk', wave: 'p.....|...'},
+///   {name: 'dat', wave: 'x.345x|=.x', data: ['head', 'body', 'tail', 'data']},
+///   {name: 'req', wave: '0.1..0|1.0'},
+///   {},
+///   {name: 'ack', wave: '1.....|01.'}
+///
+/// ]}
+/// ```
+///
+/// ```mermaid
+/// graph TD;
+///     A-->B;
+///     A-->C;
+///     B-->D;
+///     C-->D;
+/// ```
+///
+module veryl_testcase_Module36 #(
+    /// Data width
+    parameter [32-1:0] ParamA = 1,
+    parameter [32-1:0] ParamB = 1
+) (
+    input wire i_clk,
+    input wire i_rst_n,
+    input wire [ParamA-1:0] i_data,
+    output wire [ParamA-1:0] o_data
+);
+    assign o_data = 0;
+endmodule
+
+/// Test interface for doc comment
+///
+/// * list item0
+/// * list item1
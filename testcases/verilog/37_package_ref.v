@@ -0,0 +1,17 @@
+module veryl_testcase_Module37;
+    localparam [1-1:0] veryl_testcase_Package37_B_C = 1'd0;
+
+    wire [32-1:0] _a;
+    assign _a = 1;
+    wire [32-1:0] _b;
+    assign _b = veryl_testcase_Package37_B_C;
+    wire [32-1:0] _c;
+    assign _c = veryl_testcase_Package37_X(1'b0);
+
+
+    function automatic [32-1:0] veryl_testcase_Package37_X(input reg veryl_unused);
+        begin
+            veryl_testcase_Package37_X = 0;
+        end
+    endfunction
+endmodule
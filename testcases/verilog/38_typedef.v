@@ -0,0 +1,10 @@
+module veryl_testcase_Module38;
+
+
+
+
+
+
+    wire [16-1:0] rf;
+    assign rf[0] = 0;
+endmodule
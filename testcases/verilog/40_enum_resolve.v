@@ -0,0 +1,18 @@
+module veryl_testcase_Module40;
+
+    localparam [2-1:0] EnumA_member_a = 2'd0;
+
+    localparam [2-1:0] EnumA_member_b = 2'd1;
+    localparam [2-1:0] EnumA_member_c = 3;
+
+    wire [2-1:0] a;
+
+    assign a = EnumA_member_a;
+    function automatic [0:0] is_a(
+        input reg [2-1:0] e
+    );
+        begin
+            is_a = e == EnumA_member_a;
+        end
+    endfunction
+endmodule
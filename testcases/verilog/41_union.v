@@ -0,0 +1,16 @@
+module veryl_testcase_Module41;
+    localparam [1-1:0] Boolean_True = 1;
+    localparam [1-1:0] Boolean_False = 0;
+
+    localparam A_c_WIDTH = 1;
+    localparam A_c_OFFSET = 0;
+    localparam A_b_WIDTH = 1;
+    localparam A_b_OFFSET = 0;
+    localparam A_WIDTH = A_b_WIDTH;
+
+    wire [A_WIDTH-1:0] foo;
+    assign foo[A_b_OFFSET +: A_b_WIDTH] = 1'b0;
+
+    wire [A_WIDTH-1:0] bar;
+    assign bar[A_c_OFFSET +: A_c_WIDTH] = Boolean_True;
+endmodule
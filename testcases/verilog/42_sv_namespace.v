@@ -0,0 +1,23 @@
+module veryl_testcase_Module42 (
+    input wire i_clk,
+    input wire i_rst_n,
+    input wire i_d,
+    output wire o_d
+);
+    localparam [32-1:0] a = pkg::paramA;
+    //const b: u32 = pkg::paramA;
+
+    delay u0 (
+        .i_clk (i_clk),
+        .i_rst_n (i_rst_n),
+        .i_d (i_d),
+        .o_d (o_d)
+    );
+
+    delay u1 (
+        .i_clk (i_clk),
+        .i_rst_n (i_rst_n),
+        .i_d (i_d),
+        .o_d (o_d)
+    );
+endmodule
@@ -0,0 +1,6 @@
+module veryl_testcase_Module43;
+    wire a;
+    wire [10-1:0] b;
+
+    assign b = a.memberA;
+endmodule
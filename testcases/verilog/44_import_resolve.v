@@ -0,0 +1,12 @@
+module veryl_testcase_Module44;
+    wire [10-1:0] a;
+    wire [10-1:0] b;
+    wire [10-1:0] c;
+
+
+
+
+    assign a = 0;
+    assign b = 0;
+    assign c = 0;
+endmodule
@@ -0,0 +1,18 @@
+module veryl_testcase_Module45;
+    wire a;
+    assign a = 1;
+    reg [10-1:0] b;
+    reg [10-1:0] c;
+
+    always @ (posedge a) begin : veryl_block_1
+        reg [10-1:0] x;
+        x = 1;
+        b <= x * 1;
+    end
+
+    always @* begin : veryl_block_2
+        reg [10-1:0] y;
+        y = 1;
+        c = y * 1;
+    end
+endmodule
@@ -0,0 +1,52 @@
+module veryl_testcase_Module46;
+    wire a;
+    assign a = 1;
+    reg [10-1:0] b;
+    reg [10-1:0] c;
+    reg [10-1:0] d;
+    reg [10-1:0] e;
+
+    always @ (posedge a) begin : veryl_block_1
+        reg [10-1:0] x;
+        d <= 1;
+
+        x = 1;
+        b <= x * 1;
+    end
+
+    always @* begin : veryl_block_2
+        reg [10-1:0] y;
+        e = 1;
+        y = 1;
+        c = y * 1;
+    end
+
+    function automatic [10-1:0] FuncA(
+        input reg [10-1:0] a,
+        output reg [10-1:0] b,
+        inout reg [10-1:0] c
+    );
+        reg [32-1:0] d;
+        begin
+            c = a / 1;
+
+            d = 1;
+            b = a + 1 + d;
+            FuncA = a + 2;
+        end
+    endfunction
+
+    function automatic [10-1:0] FuncB(
+        input reg [10-1:0] a,
+        output reg [10-1:0] b,
+        inout reg [10-1:0] c
+    );
+        reg [32-1:0] d;
+        begin
+            c = a / 1;
+            d = 1;
+            b = a + 1 + d;
+            FuncB = a + 2;
+        end
+    endfunction
+endmodule
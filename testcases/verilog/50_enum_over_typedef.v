@@ -0,0 +1,11 @@
+module veryl_testcase_Module50;
+    localparam [1-1:0] veryl_testcase_Package50_EnumA_memberA = 1'd0;
+    localparam [1-1:0] veryl_testcase_Package50_EnumA_memberB = 1'd1;
+
+
+
+    wire _a;
+    assign _a = veryl_testcase_Package50_EnumA_memberA;
+    wire _b;
+    assign _b = veryl_testcase_Package50_EnumA_memberB;
+endmodule
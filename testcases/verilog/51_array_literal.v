@@ -0,0 +1,11 @@
+module veryl_testcase_Module51;
+    wire _a [0:2-1];
+    assign _a[0] = 1;
+    assign _a[1] = 1;
+    wire _b [0:2-1];
+    assign _b[0] = 1;
+    assign _b[1] = 1;
+    wire _c [0:2-1];
+    assign _c[0] = 1;
+    assign _c[1] = 1;
+endmodule
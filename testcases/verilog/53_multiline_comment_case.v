@@ -0,0 +1,13 @@
+module veryl_testcase_Module53;
+    localparam [1-1:0] EnumA_A = 1'd0;
+
+    reg _a;
+    always @* begin
+        case (1'b1)
+            /*
+            */
+            (EnumA_A) == (EnumA_A): _a = 0;
+            default: _a = 1;
+        endcase
+    end
+endmodule
@@ -0,0 +1,41 @@
+module veryl_testcase_Module54;
+    function automatic [10-1:0] __FuncA__10(
+        input reg [10-1:0] a
+    );
+        begin
+            __FuncA__10 = a + 1;
+        end
+    endfunction
+    function automatic [20-1:0] __FuncA__20(
+        input reg [20-1:0] a
+    );
+        begin
+            __FuncA__20 = a + 1;
+        end
+    endfunction
+
+    wire [10-1:0] _a;
+    assign _a = __FuncA__10(1);
+    wire [20-1:0] _b;
+    assign _b = __FuncA__20(1);
+
+    function automatic [(10 + 2)-1:0] __FuncB__10__2(
+        input reg [(10 + 2)-1:0] a
+    );
+        begin
+            __FuncB__10__2 = a + 1;
+        end
+    endfunction
+    function automatic [(10 + 4)-1:0] __FuncB__10__4(
+        input reg [(10 + 4)-1:0] a
+    );
+        begin
+            __FuncB__10__4 = a + 1;
+        end
+    endfunction
+
+    wire [12-1:0] _c;
+    assign _c = __FuncB__10__2(1);
+    wire [14-1:0] _d;
+    assign _d = __FuncB__10__4(1);
+endmodule
@@ -0,0 +1,8 @@
+module veryl_testcase_Module56;
+    veryl_testcase___Interface56A__Package56A u0 ();
+    veryl_testcase___Interface56A__Package56B u1 ();
+    veryl_testcase___Interface56B__Package56A u2 ();
+    wire [-1:0] u3__b;
+endmodule
+
+/// Generic interface test for doc comment
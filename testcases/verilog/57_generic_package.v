@@ -0,0 +1,11 @@
+module veryl_testcase_Module57;
+    localparam [32-1:0] A = veryl_testcase___Package57A__1::X;
+    localparam [64-1:0] B = veryl_testcase___Package57A__2::X;
+    localparam [32-1:0] C = veryl_testcase___Package57B__3::X;
+    localparam [64-1:0] D = veryl_testcase___Package57B__4::X;
+
+    wire [-1:0] _e;
+    assign _e[0 +: ] = 1;
+endmodule
+
+/// Generic package test for doc comment
@@ -0,0 +1,17 @@
+module veryl_testcase_Module58;
+    localparam StructA_A_WIDTH = 1;
+    localparam StructA_A_OFFSET = 0;
+    localparam StructA_WIDTH = StructA_A_OFFSET + StructA_A_WIDTH;
+
+
+
+    localparam StructB_B_WIDTH = 1;
+    localparam StructB_B_OFFSET = 0;
+    localparam StructB_WIDTH = StructB_B_OFFSET + StructB_B_WIDTH;
+
+    wire [StructA_WIDTH-1:0] _a;
+    wire [StructA_WIDTH-1:0] _b;
+    wire [StructA_WIDTH-1:0] _c;
+    wire [StructB_WIDTH-1:0] _d;
+    wire [StructB_WIDTH-1:0] _e;
+endmodule
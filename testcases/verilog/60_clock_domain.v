@@ -0,0 +1,33 @@
+module veryl_testcase_Module60A (
+    input wire i_clk_a,
+    input wire i_rst_a_n,
+    input wire i_dat_a,
+    output wire o_dat_a,
+    input wire i_clk_b,
+    input wire i_rst_b_n,
+    input wire i_dat_b,
+    output wire o_dat_b
+);
+    assign o_dat_a = i_dat_a;
+    assign o_dat_b = i_dat_b;
+endmodule
+
+module veryl_testcase_Module60B (
+    input wire i_clk,
+    input wire i_clk_x2,
+    input wire i_dat,
+    output wire o_dat
+);
+    assign o_dat = i_dat;
+endmodule
+
+module veryl_testcase_Module60C (
+    input wire i_clk,
+    input wire i_dat,
+    output wire o_dat,
+    input wire i_thr,
+    output wire o_thr
+);
+    assign o_dat = i_dat;
+    assign o_thr = i_thr;
+endmodule
@@ -0,0 +1,22 @@
+module veryl_testcase_Module61A (
+    input wire i_dat,
+    output wire o_dat
+);
+
+    assign o_dat = i_dat;
+
+endmodule
+
+module veryl_testcase_Module61B (
+    input wire i_clk,
+    input wire i_dat,
+    output wire o_dat
+);
+
+    Synchronizer u_sync (
+        .c (i_clk),
+        .d (i_dat),
+        .q (o_dat)
+    );
+
+endmodule
@@ -0,0 +1,6 @@
+module veryl_testcase_Module62;
+    wire clock;
+    wire reset;
+    assign clock = 1;
+    assign reset = 1;
+endmodule
@@ -0,0 +1,31 @@
+module veryl_testcase_Module63 (
+    input wire i_clk,
+    input wire i_rst_n,
+    input wire i_data_a
+);
+    veryl_testcase_Module63A u (
+        .i_clk (i_clk),
+        .i_rst_n (i_rst_n),
+        .i_data_a (i_data_a)
+    );
+
+    reg a;
+
+    always @ (posedge i_clk, negedge i_rst_n) begin
+        if (!i_rst_n) begin
+            a <= 0;
+        end else begin
+            a <= 1;
+        end
+    end
+
+    wire _b;
+    assign _b = i_rst_n;
+endmodule
+
+module veryl_testcase_Module63A (
+    input wire i_clk,
+    input wire i_rst_n,
+    input wire i_data_a
+);
+endmodule
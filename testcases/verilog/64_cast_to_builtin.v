@@ -0,0 +1,17 @@
+module veryl_testcase_Module64;
+    wire [32-1:0] a;
+    assign a = 1;
+
+    wire [32-1:0] _x0;
+    assign _x0 = $unsigned(a);
+    wire [64-1:0] _x1;
+    assign _x1 = $unsigned(a);
+    wire signed [32-1:0] _x2;
+    assign _x2 = $signed(a);
+    wire signed [64-1:0] _x3;
+    assign _x3 = $signed(a);
+    real _x4;
+    assign _x4 = $itor(a);
+    real _x5;
+    assign _x5 = $itor(a);
+endmodule
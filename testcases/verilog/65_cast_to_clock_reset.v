@@ -0,0 +1,35 @@
+module veryl_testcase_Module65;
+    wire a;
+    assign a = 1;
+    wire b_n;
+    assign b = 1;
+    wire c;
+    assign c = 1;
+    wire d;
+    assign d = 1;
+    wire e;
+    assign e = 1;
+    wire f;
+    assign f = 1;
+
+    wire _x0;
+    assign _x0 = a;
+    wire _x1;
+    assign _x1 = a;
+    wire _x2;
+    assign _x2 = ~b_n;
+    wire _x3;
+    assign _x3 = b_n;
+    wire _x4;
+    assign _x4 = ~b_n;
+    wire _x5;
+    assign _x5 = b_n;
+    wire _x6_n;
+    assign _x6 = ~c;
+    wire _x7_n;
+    assign _x7 = d;
+    wire _x8_n;
+    assign _x8 = ~e;
+    wire _x9_n;
+    assign _x9 = f;
+endmodule
@@ -0,0 +1,34 @@
+module veryl_testcase_Module67 (
+    input wire i_clk,
+    input wire i_d,
+    output reg o_d
+);
+
+    always @ (posedge i_clk) begin
+        o_d <= i_d;
+    end
+endmodule
+
+`ifdef __veryl_test_veryl_testcase_test67A__
+    `ifdef __veryl_wavedump_veryl_testcase_test67A__
+        module __veryl_wavedump;
+            initial begin
+                $dumpfile("test67A.vcd");
+                $dumpvars();
+            end
+        endmodule
+    `endif
+
+`endif
+
+`ifdef __veryl_test_veryl_testcase_test67B__
+    `ifdef __veryl_wavedump_veryl_testcase_test67B__
+        module __veryl_wavedump;
+            initial begin
+                $dumpfile("test67B.vcd");
+                $dumpvars();
+            end
+        endmodule
+    `endif
+
+`endif
@@ -0,0 +1,14 @@
+
+
+
+module veryl_testcase_Module69A #(
+    parameter [32-1:0] A = 1,
+    parameter [32-1:0] B = 1,
+    parameter [32-1:0] C = 1
+) (
+    input wire a,
+    input wire b,
+    output wire c
+);
+    assign c = a;
+endmodule
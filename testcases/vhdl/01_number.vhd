@@ -0,0 +1,37 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module01 is
+end entity veryl_testcase_Module01;
+
+architecture rtl of veryl_testcase_Module01 is
+    constant a : natural := 0123456789;
+    constant aa : natural := 0123456789;
+    constant b : natural := 32b"01xzXZ";
+    constant bb : natural := 32b"01xzXZ";
+    constant bbb : natural := 32sb"01xzXZ";
+    constant c : natural := 32o"01234567xzXZ";
+    constant cc : natural := 32o"01234567xzXZ";
+    constant ccc : natural := 32so"01234567xzXZ";
+    constant d : natural := 32d"0123456789";
+    constant dd : natural := 32d"0123456789";
+    constant ddd : natural := 32sd"0123456789";
+    constant e : natural := 128h"0123456789abcdefxzABCDEFXZ";
+    constant ee : natural := 128h"0123456789abcdefxzABCDEFXZ";
+    constant eee : natural := 128sh"0123456789abcdefxzABCDEFXZ";
+    constant f : natural := (others => '0');
+    constant ff : natural := (others => '1');
+    constant fff : natural := (others => 'X');
+    constant ffff : natural := (others => 'X');
+    constant fffff : natural := (others => 'Z');
+    constant ffffff : natural := (others => 'Z');
+    constant g : natural := 0123456789.0123456789;
+    constant gg : natural := 0123456789.0123456789e+0123456789;
+    constant ggg : natural := 0123456789.0123456789e-0123456789;
+    constant gggg : natural := 0123456789.0123456789E+0123456789;
+    constant ggggg : natural := 0123456789.0123456789E-0123456789;
+begin
+end architecture rtl;
@@ -0,0 +1,54 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module02 is
+end entity veryl_testcase_Module02;
+
+architecture rtl of veryl_testcase_Module02 is
+    signal \_a\ : natural;
+    signal \_aa\ : natural;
+    signal \_b\ : integer;
+    signal \_bb\ : integer;
+    signal \_c\ : real;
+    signal \_cc\ : real;
+    signal \_d\ : std_logic;
+    signal \_dd\ : std_logic_vector(9 downto 0);
+    signal \_ddd\ : std_logic_vector((10) * (10) - 1 downto 0);
+    signal \_e\ : std_logic;
+    signal \_ee\ : std_logic_vector(9 downto 0);
+    signal \_eee\ : std_logic_vector((10) * (10) - 1 downto 0);
+    type \_f\_type is array (0 to 9) of natural;
+    signal \_f\ : \_f\_type;
+    type \_ff\_type is array (0 to 9) of natural;
+    signal \_ff\ : \_ff\_type;
+    type \_fff\_type is array (0 to 9) of integer;
+    signal \_fff\ : \_fff\_type;
+    type \_ffff\_type is array (0 to 9) of integer;
+    signal \_ffff\ : \_ffff\_type;
+    type \_fffff\_type is array (0 to 9) of real;
+    signal \_fffff\ : \_fffff\_type;
+    type \_ffffff\_type is array (0 to 9) of real;
+    signal \_ffffff\ : \_ffffff\_type;
+begin
+    \_a\ <= 1;
+    \_aa\ <= 1;
+    \_b\ <= 1;
+    \_bb\ <= 1;
+    \_c\ <= 1;
+    \_cc\ <= 1;
+    \_d\ <= '1';
+    \_dd\ <= std_logic_vector(to_unsigned(1, 10));
+    \_ddd\ <= std_logic_vector(to_unsigned(1, 10 * (10)));
+    \_e\ <= '1';
+    \_ee\ <= std_logic_vector(to_unsigned(1, 10));
+    \_eee\ <= std_logic_vector(to_unsigned(1, 10 * (10)));
+    \_f\ <= 1;
+    \_ff\ <= 1;
+    \_fff\ <= 1;
+    \_ffff\ <= 1;
+    \_fffff\ <= 1;
+    \_ffffff\ <= 1;
+end architecture rtl;
@@ -0,0 +1,88 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module03 is
+end entity veryl_testcase_Module03;
+
+architecture rtl of veryl_testcase_Module03 is
+    signal \_a\ : std_logic;
+    signal \_aa\ : std_logic;
+    signal \_b\ : std_logic;
+    signal \_bb\ : std_logic;
+    signal \_c\ : std_logic;
+    signal \_cc\ : std_logic;
+    signal \_ccc\ : std_logic;
+    signal \_cccc\ : std_logic;
+    signal \_ccccc\ : std_logic;
+    signal \_cccccc\ : std_logic;
+    signal \_ccccccc\ : std_logic;
+    signal \_d\ : std_logic;
+    signal \_dd\ : std_logic;
+    signal \_ddd\ : std_logic;
+    signal \_dddd\ : std_logic;
+    signal \_ddddd\ : std_logic;
+    signal \_dddddd\ : std_logic;
+    signal \_e\ : std_logic;
+    signal \_ee\ : std_logic;
+    signal \_eee\ : std_logic;
+    signal \_eeee\ : std_logic;
+    signal \_f\ : std_logic;
+    signal \_ff\ : std_logic;
+    signal \_fff\ : std_logic;
+    signal \_ffff\ : std_logic;
+    signal \_fffff\ : std_logic;
+    signal \_ffffff\ : std_logic;
+    signal \_fffffff\ : std_logic;
+    signal \_ffffffff\ : std_logic;
+    signal \_fffffffff\ : std_logic;
+    signal \_ffffffffff\ : std_logic;
+    signal \_g\ : std_logic;
+    signal \_gg\ : std_logic;
+    signal \_ggg\ : std_logic;
+    signal \_gggg\ : std_logic;
+    signal \_ggggg\ : std_logic;
+    signal \_h\ : std_logic;
+    signal \_hh\ : std_logic;
+begin
+    \_a\ <= '1';
+    \_aa\ <= to_signed(-1, 32)(0);
+    \_b\ <= '0';
+    \_bb\ <= to_signed(-2, 32)(0);
+    \_c\ <= and std_logic_vector(to_unsigned(1, 32));
+    \_cc\ <= or std_logic_vector(to_unsigned(1, 32));
+    \_ccc\ <= xor std_logic_vector(to_unsigned(1, 32));
+    \_cccc\ <= nand std_logic_vector(to_unsigned(1, 32));
+    \_ccccc\ <= nor std_logic_vector(to_unsigned(1, 32));
+    \_cccccc\ <= xnor std_logic_vector(to_unsigned(1, 32));
+    \_ccccccc\ <= xnor std_logic_vector(to_unsigned(1, 32));
+    \_d\ <= '1';
+    \_dd\ <= '1';
+    \_ddd\ <= '1';
+    \_dddd\ <= '0';
+    \_ddddd\ <= to_unsigned(2, 32)(0);
+    \_dddddd\ <= '0';
+    \_e\ <= to_unsigned(2, 32)(0);
+    \_ee\ <= '0';
+    \_eee\ <= to_unsigned(2, 32)(0);
+    \_eeee\ <= '0';
+    \_f\ <= '0';
+    \_ff\ <= '1';
+    \_fff\ <= '0';
+    \_ffff\ <= '1';
+    \_fffff\ <= '1';
+    \_ffffff\ <= '0';
+    \_fffffff\ <= '1';
+    \_ffffffff\ <= '0';
+    \_fffffffff\ <= '1';
+    \_ffffffffff\ <= '0';
+    \_g\ <= '1';
+    \_gg\ <= '0';
+    \_ggg\ <= to_signed(-1, 32)(0);
+    \_gggg\ <= to_signed(-1, 32)(0);
+    \_ggggg\ <= '1';
+    \_h\ <= '1';
+    \_hh\ <= '1';
+end architecture rtl;
@@ -0,0 +1,33 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module04 is
+    generic (
+        a : natural := 1
+    );
+    port (
+        b : in std_logic_vector(9 downto 0);
+        bb : out std_logic_vector(9 downto 0);
+        bbb : inout std_logic_vector(9 downto 0);
+        bbbbb_c : in std_logic
+    );
+end entity veryl_testcase_Module04;
+
+architecture rtl of veryl_testcase_Module04 is
+    constant aa : natural := 1;
+    subtype aaa is std_logic_vector(9 downto 0);
+    constant c : natural := 1;
+    constant cc : natural := 1;
+    signal \_d\ : std_logic;
+    signal \_dd\ : std_logic_vector(9 downto 0);
+    signal \_ddd\ : std_logic_vector((10) * (10) - 1 downto 0);
+begin
+    \_d\ <= '1';
+    \_dd\ <= std_logic_vector(to_unsigned(1, 10));
+    \_ddd\ <= std_logic_vector(to_unsigned(1, 10 * (10)));
+    bb <= (others => '0');
+    bbb <= (others => '0');
+end architecture rtl;
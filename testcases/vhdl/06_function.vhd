@@ -0,0 +1,41 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module06 is
+end entity veryl_testcase_Module06;
+
+architecture rtl of veryl_testcase_Module06 is
+    constant ParamX : natural := 1;
+    function FuncA (a : std_logic_vector(ParamX - 1 downto 0); b : out std_logic_vector(ParamX - 1 downto 0); c : inout std_logic_vector(ParamX - 1 downto 0)) return std_logic_vector is
+        variable d : natural;
+    begin
+        d := 1;
+        b := a + 1 + d;
+        c := a / 1;
+        return a + 2;
+    end function FuncA;
+    procedure FuncC (a : std_logic_vector(ParamX - 1 downto 0); c : inout std_logic_vector(ParamX - 1 downto 0)) is
+    begin
+        c := a / 1;
+    end procedure FuncC;
+    signal a : std_logic_vector(ParamX - 1 downto 0);
+    signal b : std_logic_vector(ParamX - 1 downto 0);
+    signal c : std_logic_vector(ParamX - 1 downto 0);
+    signal d : std_logic_vector(ParamX - 1 downto 0);
+    signal e : std_logic_vector(ParamX - 1 downto 0);
+begin
+    a <= std_logic_vector(to_unsigned(1, ParamX));
+    b <= std_logic_vector(to_unsigned(1, ParamX));
+    c <= std_logic_vector(to_unsigned(1, ParamX));
+    d <= FuncA(a, b, c);
+
+    process
+    begin
+        FuncC(a, c);
+        wait;
+    end process;
+    e <= integer(ceil(log2(real(a))));
+end architecture rtl;
@@ -0,0 +1,98 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module07 is
+end entity veryl_testcase_Module07;
+
+architecture rtl of veryl_testcase_Module07 is
+    signal a : std_logic;
+    signal aa : std_logic;
+    signal clk : std_logic;
+begin
+    clk <= '1';
+
+    process (all)
+        variable i : natural;
+    begin
+        a <= '1';
+        a <= a xor '1';
+        a <= a xor '1';
+        a <= a and '1';
+        a <= to_unsigned(to_integer(unsigned'(0 => a)) / 1, 1)(0);
+        a <= to_unsigned(to_integer(unsigned'(0 => a)) mod 1, 1)(0);
+        a <= a and '1';
+        a <= a or '1';
+        a <= a xor '1';
+        a <= '0';
+        a <= '0';
+        a <= '0';
+        a <= '0';
+        if a then
+            a <= '1';
+            aa <= '1';
+        elsif a then
+            a <= '1';
+            aa <= '1';
+        else
+            a <= '1';
+            aa <= '1';
+        end if;
+        for i in 0 to 9 loop
+            a <= to_unsigned(i, 32)(0);
+            aa <= to_unsigned(i + 1, 32)(0);
+        end loop;
+        for i in 0 to 10 loop
+            a <= to_unsigned(i, 32)(0);
+            aa <= to_unsigned(i + 1, 32)(0);
+        end loop;
+        i := 0;
+        while i < 10 loop
+            a <= to_unsigned(i, 32)(0);
+            aa <= to_unsigned(i + 1, 32)(0);
+            i := i + 2;
+        end loop;
+        i := 0;
+        while i < 10 loop
+            a <= to_unsigned(i, 32)(0);
+            aa <= to_unsigned(i + 1, 32)(0);
+            i := i * 2;
+        end loop;
+        for i in 0 to 9 loop
+            a <= to_unsigned(i, 32)(0);
+            aa <= to_unsigned(i + 1, 32)(0);
+            if i = 0 then
+                exit;
+            end if;
+        end loop;
+        for i in 0 to 9 loop
+            for j in 0 to 9 loop
+                a <= to_unsigned(i, 32)(0);
+                aa <= to_unsigned(i + j, 32)(0);
+                if (i = 0) and (j = 0) then
+                    exit;
+                end if;
+            end loop;
+        end loop;
+    end process;
+
+    process (clk)
+    begin
+        if rising_edge(clk) then
+            a <= a xor '1';
+            a <= a xor '1';
+            a <= a and '1';
+            a <= to_unsigned(to_integer(unsigned'(0 => a)) / 1, 1)(0);
+            a <= to_unsigned(to_integer(unsigned'(0 => a)) mod 1, 1)(0);
+            a <= a and '1';
+            a <= a or '1';
+            a <= a xor '1';
+            a <= '0';
+            a <= '0';
+            a <= '0';
+            a <= '0';
+        end if;
+    end process;
+end architecture rtl;
@@ -0,0 +1,83 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module08 is
+end entity veryl_testcase_Module08;
+
+architecture rtl of veryl_testcase_Module08 is
+    constant a : natural := 1;
+    constant b : natural := 1;
+    signal i_clk : std_logic;
+begin
+    i_clk <= '1';
+
+    \label\: if a = 1 generate
+        signal a : std_logic;
+    begin
+
+        process (i_clk)
+        begin
+            if rising_edge(i_clk) then
+                a <= '1';
+            end if;
+        end process;
+    elsif b = 1 generate
+        signal a : std_logic;
+    begin
+
+        process (i_clk)
+        begin
+            if rising_edge(i_clk) then
+                a <= '1';
+            end if;
+        end process;
+    elsif b = 1 generate
+        signal a : std_logic;
+    begin
+
+        process (i_clk)
+        begin
+            if rising_edge(i_clk) then
+                a <= '1';
+            end if;
+        end process;
+    else generate
+        signal a : std_logic;
+    begin
+
+        process (i_clk)
+        begin
+            if rising_edge(i_clk) then
+                a <= '1';
+            end if;
+        end process;
+    end generate \label\;
+
+    label2: for i in 0 to 9 generate
+        signal a : std_logic;
+    begin
+
+        process (i_clk)
+        begin
+            if rising_edge(i_clk) then
+                a <= i;
+            end if;
+        end process;
+    end generate label2;
+
+    label3: for i_index in 0 to (10 - 1) / 2 generate
+        constant i : integer := 0 + i_index * 2;
+        signal a : std_logic;
+    begin
+
+        process (i_clk)
+        begin
+            if rising_edge(i_clk) then
+                a <= i;
+            end if;
+        end process;
+    end generate label3;
+end architecture rtl;
@@ -0,0 +1,62 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module09 is
+end entity veryl_testcase_Module09;
+
+architecture rtl of veryl_testcase_Module09 is
+    type \A\ is record
+        a : std_logic_vector(9 downto 0);
+        aa : std_logic_vector(9 downto 0);
+        aaa : natural;
+    end record \A\;
+    subtype \B\ is std_logic_vector(1 downto 0);
+    constant B_X : \B\ := "01";
+    constant B_Y : \B\ := "10";
+    constant B_Z : \B\ := "11";
+    subtype \C\ is std_logic_vector(2 downto 0);
+    constant C_X : \C\ := "010";
+    constant C_Y : \C\ := "011";
+    constant C_Z : \C\ := "100";
+    subtype \D\ is std_logic_vector(0 downto 0);
+    constant D_X : \D\ := "0";
+    subtype \E\ is std_logic_vector(1 downto 0);
+    constant E_X : \E\ := "00";
+    constant E_Y : \E\ := "01";
+    constant E_Z : \E\ := "10";
+    subtype \F\ is std_logic_vector(2 downto 0);
+    constant F_X : \F\ := "001";
+    constant F_Y : \F\ := "010";
+    constant F_Z : \F\ := "100";
+    subtype \G\ is std_logic_vector(1 downto 0);
+    constant G_X : \G\ := "00";
+    constant G_Y : \G\ := "01";
+    constant G_Z : \G\ := "11";
+    subtype \H\ is std_logic_vector(0 downto 0);
+    constant FOO_H_0 : \H\ := "0";
+    constant FOO_H_1 : \H\ := "1";
+    signal a : \A\;
+    signal b : \B\;
+    signal c : \C\;
+    signal d : \D\;
+    signal e : \E\;
+    signal f : \F\;
+    signal g : \G\;
+    signal h : \H\;
+    signal i : std_logic;
+begin
+    a.a <= std_logic_vector(to_unsigned(1, a.a'length));
+    a.aa <= std_logic_vector(to_unsigned(1, a.aa'length));
+    a.aaa <= 1;
+    b <= B_X;
+    c <= C_X;
+    d <= D_X;
+    e <= E_X;
+    f <= F_X;
+    g <= G_X;
+    h <= FOO_H_0;
+    i <= a.a(0);
+end architecture rtl;
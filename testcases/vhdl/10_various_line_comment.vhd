@@ -0,0 +1,40 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module10 is
+    port (
+        i_clk : in std_logic;
+        i_rst_n : in std_logic;
+        i_up : in std_logic;
+        i_down : in std_logic;
+        o_count : out std_logic_vector(7 downto 0)
+    );
+end entity veryl_testcase_Module10;
+
+architecture rtl of veryl_testcase_Module10 is
+    signal count : std_logic_vector(7 downto 0);
+    signal up_down : std_logic_vector(1 downto 0);
+begin
+    o_count <= count;
+
+    process (all)
+    begin
+        up_down <= (i_up sll 1) or i_down;
+    end process;
+
+    process (i_clk, i_rst_n)
+    begin
+        if i_rst_n = '0' then
+            count <= (others => '0');
+        elsif rising_edge(i_clk) then
+            if up_down ?= 2b"10" then
+                count <= count + 1;
+            elsif up_down ?= 2b"01" then
+                count <= count - 1;
+            end if;
+        end if;
+    end process;
+end architecture rtl;
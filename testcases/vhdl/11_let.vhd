@@ -0,0 +1,20 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module11 is
+end entity veryl_testcase_Module11;
+
+architecture rtl of veryl_testcase_Module11 is
+    signal b : std_logic;
+    signal bb : std_logic_vector(9 downto 0);
+    signal \_bbb\ : std_logic_vector((10) * (10) - 1 downto 0);
+    signal \_c\ : std_logic_vector(9 downto 0);
+begin
+    \_bbb\ <= std_logic_vector(to_unsigned(1, 10 * (10)));
+    \_c\ <= std_logic_vector(to_unsigned(1, 10));
+    b <= '1';
+    bb <= std_logic_vector(to_unsigned(1, bb'length));
+end architecture rtl;
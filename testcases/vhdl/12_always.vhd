@@ -0,0 +1,136 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module12_1 is
+    port (
+        i_clk : in std_logic;
+        i_rst_n : in std_logic
+    );
+end entity veryl_testcase_Module12_1;
+
+architecture rtl of veryl_testcase_Module12_1 is
+    signal a : std_logic;
+    signal b : std_logic;
+begin
+
+    process (i_clk, i_rst_n)
+    begin
+        if i_rst_n = '0' then
+            a <= '0';
+        elsif rising_edge(i_clk) then
+            a <= not a;
+        end if;
+    end process;
+
+    process (i_clk)
+    begin
+        if rising_edge(i_clk) then
+            b <= a;
+        end if;
+    end process;
+end architecture rtl;
+
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module12_2 is
+    port (
+        i_clk : in std_logic;
+        i_clk_p : in std_logic;
+        i_clk_n : in std_logic;
+        i_rst_n : in std_logic;
+        i_rst_ah : in std_logic;
+        i_rst_al : in std_logic;
+        i_rst_sh : in std_logic;
+        i_rst_sl : in std_logic
+    );
+end entity veryl_testcase_Module12_2;
+
+architecture rtl of veryl_testcase_Module12_2 is
+    signal a : std_logic;
+    signal aa : std_logic;
+    signal b : std_logic;
+    signal c : std_logic;
+begin
+    b <= '1';
+    c <= '1';
+
+    process (i_clk, i_rst_n)
+    begin
+        if i_rst_n = '0' then
+            a <= '0';
+        elsif rising_edge(i_clk) then
+            if a then
+                a <= b;
+            else
+                a <= c;
+            end if;
+        end if;
+    end process;
+
+    process (i_clk)
+    begin
+        if rising_edge(i_clk) then
+            if a then
+                a <= b;
+            else
+                a <= c;
+            end if;
+        end if;
+    end process;
+
+    process (i_clk_p, i_rst_ah)
+    begin
+        if i_rst_ah = '1' then
+            a <= '0';
+        elsif rising_edge(i_clk_p) then
+            a <= c;
+        end if;
+    end process;
+
+    process (i_clk_n, i_rst_al)
+    begin
+        if i_rst_al = '0' then
+            a <= '0';
+        elsif falling_edge(i_clk_n) then
+            a <= c;
+        end if;
+    end process;
+
+    process (i_clk_p)
+    begin
+        if rising_edge(i_clk_p) then
+            if i_rst_sh = '1' then
+                a <= '0';
+            else
+                a <= c;
+            end if;
+        end if;
+    end process;
+
+    process (i_clk_n)
+    begin
+        if falling_edge(i_clk_n) then
+            if i_rst_sl = '0' then
+                a <= '0';
+            else
+                a <= c;
+            end if;
+        end if;
+    end process;
+
+    process (all)
+    begin
+        a <= to_unsigned(10, 32)(0);
+        aa <= '0';
+        aa <= 'Z';
+        a <= to_unsigned(20, 32)(0);
+        aa <= to_unsigned(10, 32)(0);
+    end process;
+end architecture rtl;
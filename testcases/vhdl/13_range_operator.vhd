@@ -0,0 +1,24 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module13 is
+end entity veryl_testcase_Module13;
+
+architecture rtl of veryl_testcase_Module13 is
+    signal a : std_logic;
+    signal b : std_logic;
+    signal c : std_logic;
+    signal d : std_logic;
+    signal e : std_logic;
+    signal X : std_logic;
+begin
+    X <= '1';
+    a <= X;
+    b <= X;
+    c <= X;
+    d <= X;
+    e <= X;
+end architecture rtl;
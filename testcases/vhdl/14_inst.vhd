@@ -0,0 +1,67 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module14 is
+end entity veryl_testcase_Module14;
+
+architecture rtl of veryl_testcase_Module14 is
+    constant \X\ : natural := 1;
+    signal a : std_logic;
+    signal aa : std_logic;
+    signal bbb : std_logic;
+begin
+    a <= '1';
+    aa <= '1';
+    bbb <= '1';
+
+    x: entity work.veryl_testcase_Module14B;
+
+    xx: entity work.veryl_testcase_Module14C
+        generic map (
+            \X\ => \X\,
+            Y => 10
+        )
+        port map (
+            a => (0 => a, others => '0'),
+            bb => (0 => aa, others => '0'),
+            bbbb => (0 => bbb, others => '0')
+        );
+end architecture rtl;
+
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module14B is
+end entity veryl_testcase_Module14B;
+
+architecture rtl of veryl_testcase_Module14B is
+begin
+end architecture rtl;
+
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module14C is
+    generic (
+        \X\ : natural := 1;
+        Y : natural := 1
+    );
+    port (
+        a : in unsigned(31 downto 0);
+        bb : in unsigned(31 downto 0);
+        bbbb : in unsigned(31 downto 0)
+    );
+end entity veryl_testcase_Module14C;
+
+architecture rtl of veryl_testcase_Module14C is
+begin
+end architecture rtl;
@@ -0,0 +1,35 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module15 is
+end entity veryl_testcase_Module15;
+
+architecture rtl of veryl_testcase_Module15 is
+    signal \_a\ : std_logic;
+begin
+    \_a\ <= '1';
+
+    \label\: block
+        signal \_a\ : std_logic;
+    begin
+        \_a\ <= '1';
+    end block \label\;
+
+    label1: block
+        signal \_a\ : std_logic;
+    begin
+        \_a\ <= '1';
+    end block label1;
+
+    label2: for i in 0 to 9 generate
+
+        \label\: block
+            signal \_a\ : std_logic;
+        begin
+            \_a\ <= '1';
+        end block \label\;
+    end generate label2;
+end architecture rtl;
@@ -0,0 +1,57 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module16 is
+end entity veryl_testcase_Module16;
+
+architecture rtl of veryl_testcase_Module16 is
+    constant y : std_logic := '1';
+    signal a : std_logic;
+    signal b : std_logic;
+    signal x : std_logic;
+    signal z : std_logic_vector(2 downto 0);
+begin
+    x <= '1';
+    z <= std_logic_vector(to_unsigned(1, 3));
+
+    process (all)
+    begin
+        if (x ?= 0) then
+            a <= '1';
+        elsif (x ?= 1) then
+            a <= '1';
+        elsif (x ?= 2) then
+            a <= '1';
+            a <= '1';
+            a <= '1';
+        elsif (x ?= 3) or (x ?= 4) then
+            a <= '1';
+        elsif (x ?>= 5 and x ?<= 7) then
+            a <= '1';
+        elsif (x ?= (y - 1)) then
+            a <= '1';
+        else
+            a <= '1';
+        end if;
+    end process;
+
+    process (all)
+    begin
+        if (z ?= 0) then
+            b <= '1';
+        elsif (z ?= 1) then
+            b <= '1';
+        elsif (z ?= 2) then
+            b <= '1';
+            b <= '1';
+            b <= '1';
+        elsif (z ?= 3) or (z ?= 4) then
+            b <= '1';
+        else
+            b <= '1';
+        end if;
+    end process;
+end architecture rtl;
@@ -0,0 +1,31 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+package veryl_testcase_Package17 is
+    constant ParamX : natural := 1;
+    signal \_b\ : std_logic;
+    type A is record
+        a : std_logic_vector(9 downto 0);
+        aa : std_logic_vector(9 downto 0);
+        aaa : natural;
+    end record A;
+    subtype B is std_logic_vector(1 downto 0);
+    constant B_X : B := "01";
+    constant B_Y : B := "10";
+    constant B_Z : B := "11";
+    function FuncA (a : std_logic_vector(ParamX - 1 downto 0); b : out std_logic_vector(ParamX - 1 downto 0); c : inout std_logic_vector(ParamX - 1 downto 0)) return std_logic_vector;
+end package veryl_testcase_Package17;
+
+package body veryl_testcase_Package17 is
+    function FuncA (a : std_logic_vector(ParamX - 1 downto 0); b : out std_logic_vector(ParamX - 1 downto 0); c : inout std_logic_vector(ParamX - 1 downto 0)) return std_logic_vector is
+        variable d : natural;
+    begin
+        d := 1;
+        b := a + 1 + d;
+        c := a / 1;
+        return a + 2;
+    end function FuncA;
+end package body veryl_testcase_Package17;
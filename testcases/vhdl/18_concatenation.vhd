@@ -0,0 +1,18 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module18 is
+end entity veryl_testcase_Module18;
+
+architecture rtl of veryl_testcase_Module18 is
+    signal a : std_logic;
+    signal b : std_logic;
+    signal c : std_logic;
+begin
+    c <= '1';
+    a <= (a & c);
+    b <= (a & a & a & a & a & a & a & a & a & a & c & c & c & c);
+end architecture rtl;
@@ -0,0 +1,41 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+use work.PackageA.A;
+use work.PackageA.all;
+use work.veryl_testcase_PackageA.A;
+use work.veryl_testcase_PackageA.all;
+
+entity veryl_testcase_Module19 is
+end entity veryl_testcase_Module19;
+
+architecture rtl of veryl_testcase_Module19 is
+begin
+end architecture rtl;
+
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+use work.PackageA.A;
+use work.PackageA.all;
+use work.veryl_testcase_PackageA.A;
+use work.veryl_testcase_PackageA.all;
+
+package veryl_testcase_Package19 is
+end package veryl_testcase_Package19;
+
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+use work.PackageA.A;
+use work.PackageA.all;
+
+package veryl_testcase_PackageA is
+    constant A : natural := 0;
+end package veryl_testcase_PackageA;
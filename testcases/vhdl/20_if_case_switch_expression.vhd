@@ -0,0 +1,22 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module20 is
+end entity veryl_testcase_Module20;
+
+architecture rtl of veryl_testcase_Module20 is
+    signal a : std_logic;
+    signal b : std_logic;
+    signal c : std_logic;
+    signal x : std_logic;
+    signal y : std_logic;
+begin
+    x <= '1';
+    y <= '1';
+    a <= 1 when x else (1 when y else 2);
+    b <= 0 when (a ?= 1) else 1 when (a ?= 2) else 2 when (a ?= 3) or (a ?= 4) else 3 when (a ?>= 5 and a ?<= 7) else 4;
+    c <= 0 when (a ?= 1) else 1 when (a ?= 2) else 2 when (a ?= 3) or (a ?= 4) else 3;
+end architecture rtl;
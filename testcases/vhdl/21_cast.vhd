@@ -0,0 +1,22 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module21 is
+end entity veryl_testcase_Module21;
+
+architecture rtl of veryl_testcase_Module21 is
+    signal a : std_logic;
+    signal b : std_logic;
+    subtype EnumA is std_logic_vector(0 downto 0);
+    constant EnumA_A : EnumA := "0";
+    constant EnumA_B : EnumA := "1";
+    subtype EnumB is std_logic_vector(0 downto 0);
+    constant EnumB_C : EnumB := "0";
+    constant EnumB_D : EnumB := "1";
+begin
+    b <= '1';
+    a <= EnumB((EnumA(b)));
+end architecture rtl;
@@ -0,0 +1,20 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module22 is
+end entity veryl_testcase_Module22;
+
+architecture rtl of veryl_testcase_Module22 is
+    signal \_a\ : signed(9 downto 0);
+    signal \_b\ : signed(9 downto 0);
+    signal \_c\ : signed(9 downto 0);
+    signal \_d\ : std_logic_vector(9 downto 0);
+begin
+    \_a\ <= std_logic_vector(to_unsigned(1, 10));
+    \_b\ <= std_logic_vector(to_unsigned(1, 10));
+    \_c\ <= std_logic_vector(to_unsigned(1, 10));
+    \_d\ <= std_logic_vector(to_unsigned(1, 10));
+end architecture rtl;
@@ -0,0 +1,67 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module23 is
+    generic (
+        ParamA : natural := 1;
+        ParamB : natural := 1;
+        ParamC : natural := 1
+    );
+    port (
+        port_a : in std_logic;
+        port_b : in std_logic;
+        port_c : in std_logic
+    );
+end entity veryl_testcase_Module23;
+
+architecture rtl of veryl_testcase_Module23 is
+    signal \_a\ : std_logic_vector(9 downto 0);
+    signal \_b\ : std_logic_vector(9 downto 0);
+    signal \_c\ : std_logic_vector(9 downto 0);
+begin
+    \_a\ <= std_logic_vector(to_unsigned(1, 10));
+    \_b\ <= std_logic_vector(to_unsigned(1, 10));
+    \_c\ <= std_logic_vector(to_unsigned(1, 10));
+end architecture rtl;
+
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module23_A is
+end entity veryl_testcase_Module23_A;
+
+architecture rtl of veryl_testcase_Module23_A is
+begin
+end architecture rtl;
+
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module23_B is
+end entity veryl_testcase_Module23_B;
+
+architecture rtl of veryl_testcase_Module23_B is
+begin
+end architecture rtl;
+
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module23_C is
+end entity veryl_testcase_Module23_C;
+
+architecture rtl of veryl_testcase_Module23_C is
+begin
+end architecture rtl;
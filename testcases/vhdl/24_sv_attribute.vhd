@@ -0,0 +1,16 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module24 is
+end entity veryl_testcase_Module24;
+
+architecture rtl of veryl_testcase_Module24 is
+    signal \_a\ : std_logic;
+    signal \_b\ : std_logic;
+begin
+    \_a\ <= '1';
+    \_b\ <= '1';
+end architecture rtl;
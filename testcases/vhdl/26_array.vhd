@@ -0,0 +1,23 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module26 is
+end entity veryl_testcase_Module26;
+
+architecture rtl of veryl_testcase_Module26 is
+    signal \_a\ : std_logic_vector(9 downto 0);
+    signal \_b\ : std_logic_vector((10) * (10) - 1 downto 0);
+    type \_c\_type is array (0 to 9) of std_logic_vector((10) * (10) - 1 downto 0);
+    signal \_c\ : \_c\_type;
+    type \_d\_type1 is array (0 to 9) of std_logic_vector((10) * (10) - 1 downto 0);
+    type \_d\_type is array (0 to 9) of \_d\_type1;
+    signal \_d\ : \_d\_type;
+begin
+    \_a\ <= std_logic_vector(to_unsigned(1, 10));
+    \_b\ <= std_logic_vector(to_unsigned(1, 10 * (10)));
+    \_c\ <= std_logic_vector(to_unsigned(1, 10 * (10)));
+    \_d\ <= std_logic_vector(to_unsigned(1, 10 * (10)));
+end architecture rtl;
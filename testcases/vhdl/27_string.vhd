@@ -0,0 +1,15 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module27 is
+end entity veryl_testcase_Module27;
+
+architecture rtl of veryl_testcase_Module27 is
+    constant a : string := "aaa";
+    signal \_b\ : string;
+begin
+    \_b\ <= "bbb";
+end architecture rtl;
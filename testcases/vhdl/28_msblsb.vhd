@@ -0,0 +1,22 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module28 is
+end entity veryl_testcase_Module28;
+
+architecture rtl of veryl_testcase_Module28 is
+    constant WIDTH0 : natural := 10;
+    constant WIDTH1 : natural := 20;
+    signal a : std_logic_vector((10) * (20) - 1 downto 0);
+    signal b : std_logic_vector((WIDTH0 + 10) * (WIDTH1) - 1 downto 0);
+    signal \_x\ : std_logic;
+    signal \_y\ : std_logic;
+begin
+    a <= std_logic_vector(to_unsigned(1, 10 * (20)));
+    b <= std_logic_vector(to_unsigned(1, WIDTH0 + 10 * (WIDTH1)));
+    \_x\ <= a(9)(0 + 1);
+    \_y\ <= b((WIDTH0 + 10 - 1) - 3)(0);
+end architecture rtl;
@@ -0,0 +1,32 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module29 is
+    port (
+        clk : in std_logic;
+        rst_n : in std_logic
+    );
+end entity veryl_testcase_Module29;
+
+architecture rtl of veryl_testcase_Module29 is
+    signal a : std_logic;
+    signal b : std_logic;
+    signal c : std_logic;
+begin
+    c <= '1';
+
+    process (clk, rst_n)
+    begin
+        if rst_n = '0' then
+            a <= '0';
+        elsif rising_edge(clk) then
+            a <= '0';
+            b <= '0';
+        end if;
+    end process;
+
+    u0: entity work.veryl_testcase_Module29;
+end architecture rtl;
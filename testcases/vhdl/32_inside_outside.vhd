@@ -0,0 +1,16 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module32 is
+end entity veryl_testcase_Module32;
+
+architecture rtl of veryl_testcase_Module32 is
+    signal a : std_logic;
+    signal b : std_logic;
+begin
+    a <= '1';
+    b <= '0';
+end architecture rtl;
@@ -0,0 +1,21 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module33 is
+end entity veryl_testcase_Module33;
+
+architecture rtl of veryl_testcase_Module33 is
+    constant a0 : natural := '1';
+    constant a1 : natural := 4b"1010";
+    constant a2 : natural := '1';
+    constant a3 : natural := 10o"1234";
+    constant a4 : natural := '1';
+    constant a5 : natural := 24d"12345678";
+    constant a6 : natural := '1';
+    constant a7 : natural := 32h"ffffffff";
+    constant a8 : natural := 208h"ffffffffffffffffffffffffffffffffffffffffffffffffffff";
+begin
+end architecture rtl;
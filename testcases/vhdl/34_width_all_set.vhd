@@ -0,0 +1,20 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module34 is
+end entity veryl_testcase_Module34;
+
+architecture rtl of veryl_testcase_Module34 is
+    constant a0 : natural := (others => '0');
+    constant a1 : natural := (others => '1');
+    constant a2 : natural := (others => 'X');
+    constant a3 : natural := (others => 'Z');
+    constant a4 : natural := "0000000000";
+    constant a5 : natural := "1111111111";
+    constant a6 : natural := "XXXXXXXXXX";
+    constant a7 : natural := "ZZZZZZZZZZ";
+begin
+end architecture rtl;
@@ -0,0 +1,39 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module35 is
+end entity veryl_testcase_Module35;
+
+architecture rtl of veryl_testcase_Module35 is
+    signal aa : std_logic;
+begin
+    aa <= '1';
+
+    xx: entity work.veryl_testcase_Module35B
+        port map (
+            aa => (0 => aa, others => '0'),
+            bb => open,
+            bbbb => open
+        );
+end architecture rtl;
+
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module35B is
+    port (
+        aa : in unsigned(31 downto 0);
+        bb : in unsigned(31 downto 0);
+        bbbb : in unsigned(31 downto 0)
+    );
+end entity veryl_testcase_Module35B;
+
+architecture rtl of veryl_testcase_Module35B is
+begin
+end architecture rtl;
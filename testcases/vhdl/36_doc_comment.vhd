@@ -0,0 +1,32 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module36 is
+    generic (
+        ParamA : natural := 1
+    );
+    port (
+        i_clk : in std_logic;
+        i_rst_n : in std_logic;
+        i_data : in std_logic_vector(ParamA - 1 downto 0);
+        o_data : out std_logic_vector(ParamA - 1 downto 0)
+    );
+end entity veryl_testcase_Module36;
+
+architecture rtl of veryl_testcase_Module36 is
+    constant ParamB : natural := 1;
+begin
+    o_data <= (others => '0');
+end architecture rtl;
+
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+package veryl_testcase_Package36 is
+end package veryl_testcase_Package36;
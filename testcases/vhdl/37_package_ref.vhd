@@ -0,0 +1,38 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module37 is
+end entity veryl_testcase_Module37;
+
+architecture rtl of veryl_testcase_Module37 is
+    signal \_a\ : natural;
+    signal \_b\ : natural;
+    signal \_c\ : natural;
+begin
+    \_a\ <= work.veryl_testcase_Package37.A;
+    \_b\ <= work.veryl_testcase_Package37.B_C;
+    \_c\ <= work.veryl_testcase_Package37.X;
+end architecture rtl;
+
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+package veryl_testcase_Package37 is
+    constant A : natural := 1;
+    subtype B is std_logic_vector(0 downto 0);
+    constant B_C : B := "0";
+    function X return natural;
+end package veryl_testcase_Package37;
+
+package body veryl_testcase_Package37 is
+    function X return natural is
+    begin
+        return 0;
+    end function X;
+end package body veryl_testcase_Package37;
@@ -0,0 +1,35 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module38 is
+end entity veryl_testcase_Module38;
+
+architecture rtl of veryl_testcase_Module38 is
+    subtype word_t is std_logic_vector(15 downto 0);
+    subtype words_t is std_logic_vector((16) * (16) - 1 downto 0);
+    type regfile_t_type is array (0 to 15) of word_t;
+    subtype regfile_t is regfile_t_type;
+    type octbyte_type is array (0 to 7) of std_logic_vector(7 downto 0);
+    subtype octbyte is octbyte_type;
+    signal rf : regfile_t;
+begin
+    rf(0) <= (others => '0');
+end architecture rtl;
+
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+package veryl_testcase_Package38 is
+    subtype word_t is std_logic_vector(15 downto 0);
+    subtype words_t is std_logic_vector((16) * (16) - 1 downto 0);
+    type regfile_t_type is array (0 to 15) of word_t;
+    subtype regfile_t is regfile_t_type;
+    type octbyte_type is array (0 to 7) of std_logic_vector(7 downto 0);
+    subtype octbyte is octbyte_type;
+end package veryl_testcase_Package38;
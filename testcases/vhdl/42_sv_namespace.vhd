@@ -0,0 +1,35 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module42 is
+    port (
+        i_clk : in std_logic;
+        i_rst_n : in std_logic;
+        i_d : in std_logic;
+        o_d : out std_logic
+    );
+end entity veryl_testcase_Module42;
+
+architecture rtl of veryl_testcase_Module42 is
+    constant a : natural := work.pkg.paramA;
+begin
+
+    u0: entity work.delay
+        port map (
+            i_clk => i_clk,
+            i_rst_n => i_rst_n,
+            i_d => i_d,
+            o_d => o_d
+        );
+
+    u1: entity work.delay
+        port map (
+            i_clk => i_clk,
+            i_rst_n => i_rst_n,
+            i_d => i_d,
+            o_d => o_d
+        );
+end architecture rtl;
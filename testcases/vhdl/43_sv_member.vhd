@@ -0,0 +1,15 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module43 is
+end entity veryl_testcase_Module43;
+
+architecture rtl of veryl_testcase_Module43 is
+    signal a : StructA;
+    signal b : std_logic_vector(9 downto 0);
+begin
+    b <= a.memberA;
+end architecture rtl;
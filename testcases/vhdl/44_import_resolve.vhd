@@ -0,0 +1,40 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+use work.veryl_testcase_Package44A.z;
+use work.veryl_testcase_Package44B.all;
+
+entity veryl_testcase_Module44 is
+end entity veryl_testcase_Module44;
+
+architecture rtl of veryl_testcase_Module44 is
+    signal a : std_logic_vector(9 downto 0);
+    signal b : std_logic_vector(9 downto 0);
+    signal c : std_logic_vector(9 downto 0);
+begin
+    a <= std_logic_vector(to_unsigned(z, a'length));
+    b <= std_logic_vector(to_unsigned(z, b'length));
+    c <= std_logic_vector(to_unsigned(y, c'length));
+end architecture rtl;
+
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+package veryl_testcase_Package44A is
+    constant z : natural := 0;
+end package veryl_testcase_Package44A;
+
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+package veryl_testcase_Package44B is
+    constant y : natural := 0;
+end package veryl_testcase_Package44B;
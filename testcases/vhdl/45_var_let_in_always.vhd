@@ -0,0 +1,32 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module45 is
+end entity veryl_testcase_Module45;
+
+architecture rtl of veryl_testcase_Module45 is
+    signal a : std_logic;
+    signal b : std_logic_vector(9 downto 0);
+    signal c : std_logic_vector(9 downto 0);
+begin
+    a <= '1';
+
+    process (a)
+        variable x : std_logic_vector(9 downto 0);
+    begin
+        if rising_edge(a) then
+            x := std_logic_vector(to_unsigned(1, x'length));
+            b <= x * 1;
+        end if;
+    end process;
+
+    process (all)
+        variable y : std_logic_vector(9 downto 0);
+    begin
+        y := std_logic_vector(to_unsigned(1, 10));
+        c <= y * 1;
+    end process;
+end architecture rtl;
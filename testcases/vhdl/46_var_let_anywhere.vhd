@@ -0,0 +1,52 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module46 is
+end entity veryl_testcase_Module46;
+
+architecture rtl of veryl_testcase_Module46 is
+    signal a : std_logic;
+    signal b : std_logic_vector(9 downto 0);
+    signal c : std_logic_vector(9 downto 0);
+    signal d : std_logic_vector(9 downto 0);
+    signal e : std_logic_vector(9 downto 0);
+    function FuncA (a : std_logic_vector(9 downto 0); b : out std_logic_vector(9 downto 0); c : inout std_logic_vector(9 downto 0)) return std_logic_vector is
+        variable d : natural;
+    begin
+        c := a / 1;
+        d := 1;
+        b := a + 1 + d;
+        return a + 2;
+    end function FuncA;
+    function FuncB (a : std_logic_vector(9 downto 0); b : out std_logic_vector(9 downto 0); c : inout std_logic_vector(9 downto 0)) return std_logic_vector is
+        variable d : natural;
+    begin
+        c := a / 1;
+        d := 1;
+        b := a + 1 + d;
+        return a + 2;
+    end function FuncB;
+begin
+    a <= '1';
+
+    process (a)
+        variable x : std_logic_vector(9 downto 0);
+    begin
+        if rising_edge(a) then
+            d <= std_logic_vector(to_unsigned(1, d'length));
+            x := std_logic_vector(to_unsigned(1, x'length));
+            b <= x * 1;
+        end if;
+    end process;
+
+    process (all)
+        variable y : std_logic_vector(9 downto 0);
+    begin
+        e <= std_logic_vector(to_unsigned(1, e'length));
+        y := std_logic_vector(to_unsigned(1, 10));
+        c <= y * 1;
+    end process;
+end architecture rtl;
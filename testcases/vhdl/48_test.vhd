@@ -0,0 +1,12 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module48 is
+end entity veryl_testcase_Module48;
+
+architecture rtl of veryl_testcase_Module48 is
+begin
+end architecture rtl;
@@ -0,0 +1,29 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+package veryl_testcase_Package50 is
+    subtype EnumA is std_logic_vector(0 downto 0);
+    constant EnumA_memberA : EnumA := "0";
+    constant EnumA_memberB : EnumA := "1";
+end package veryl_testcase_Package50;
+
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module50 is
+end entity veryl_testcase_Module50;
+
+architecture rtl of veryl_testcase_Module50 is
+    subtype EnumB is work.veryl_testcase_Package50.EnumA;
+    signal \_a\ : work.veryl_testcase_Package50.EnumA;
+    signal \_b\ : EnumB;
+begin
+    \_a\ <= work.veryl_testcase_Package50.EnumA_memberA;
+    \_b\ <= work.veryl_testcase_Package50.EnumA_memberB;
+end architecture rtl;
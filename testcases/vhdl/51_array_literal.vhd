@@ -0,0 +1,21 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module51 is
+end entity veryl_testcase_Module51;
+
+architecture rtl of veryl_testcase_Module51 is
+    type \_a\_type is array (0 to 1) of std_logic;
+    signal \_a\ : \_a\_type;
+    type \_b\_type is array (0 to 1) of std_logic;
+    signal \_b\ : \_b\_type;
+    type \_c\_type is array (0 to 1) of std_logic;
+    signal \_c\ : \_c\_type;
+begin
+    \_a\ <= (1, 1);
+    \_b\ <= (1, 1);
+    \_c\ <= (others => 1);
+end architecture rtl;
@@ -0,0 +1,24 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module53 is
+end entity veryl_testcase_Module53;
+
+architecture rtl of veryl_testcase_Module53 is
+    subtype EnumA is std_logic_vector(0 downto 0);
+    constant EnumA_A : EnumA := "0";
+    signal \_a\ : std_logic;
+begin
+
+    process (all)
+    begin
+        if (EnumA_A ?= EnumA_A) then
+            \_a\ <= '0';
+        else
+            \_a\ <= '1';
+        end if;
+    end process;
+end architecture rtl;
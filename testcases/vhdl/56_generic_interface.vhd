@@ -0,0 +1,39 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module56 is
+end entity veryl_testcase_Module56;
+
+architecture rtl of veryl_testcase_Module56 is
+    signal \u3__b\ : std_logic_vector(T - 1 downto 0);
+begin
+
+    u0: entity work.\veryl_testcase___Interface56A__Package56A\;
+
+    u1: entity work.\veryl_testcase___Interface56A__Package56B\;
+
+    u2: entity work.\veryl_testcase___Interface56B__Package56A\;
+end architecture rtl;
+
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+package veryl_testcase_Package56A is
+    constant X : natural := 1;
+end package veryl_testcase_Package56A;
+
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+package veryl_testcase_Package56B is
+    constant X : natural := 2;
+end package veryl_testcase_Package56B;
@@ -0,0 +1,35 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module58 is
+end entity veryl_testcase_Module58;
+
+architecture rtl of veryl_testcase_Module58 is
+    type StructA is record
+        A : T;
+    end record StructA;
+    subtype C is integer;
+    type StructB is record
+        B : T;
+    end record StructB;
+    signal \_a\ : \__StructA__Package58_B\;
+    signal \_b\ : \__StructA__Package58_C\;
+    signal \_c\ : \__StructA__C\;
+    signal \_d\ : \__StructB__Package58_C\;
+    signal \_e\ : StructB;
+begin
+end architecture rtl;
+
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+package veryl_testcase_Package58 is
+    subtype B is natural;
+    subtype C is natural;
+end package veryl_testcase_Package58;
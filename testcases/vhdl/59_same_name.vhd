@@ -0,0 +1,19 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+package veryl_testcase_Package59A is
+    constant XLEN : natural := 32;
+end package veryl_testcase_Package59A;
+
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+package veryl_testcase_Package59B is
+    constant XLEN : natural := work.veryl_testcase_Package59A.XLEN;
+end package veryl_testcase_Package59B;
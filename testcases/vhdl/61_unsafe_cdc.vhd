@@ -0,0 +1,42 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module61A is
+    port (
+        i_dat : in std_logic;
+        o_dat : out std_logic
+    );
+end entity veryl_testcase_Module61A;
+
+architecture rtl of veryl_testcase_Module61A is
+begin
+    o_dat <= i_dat;
+end architecture rtl;
+
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module61B is
+    port (
+        i_clk : in std_logic;
+        i_dat : in std_logic;
+        o_dat : out std_logic
+    );
+end entity veryl_testcase_Module61B;
+
+architecture rtl of veryl_testcase_Module61B is
+begin
+
+    u_sync: entity work.Synchronizer
+        port map (
+            c => i_clk,
+            d => i_dat,
+            q => o_dat
+        );
+end architecture rtl;
@@ -0,0 +1,16 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module62 is
+end entity veryl_testcase_Module62;
+
+architecture rtl of veryl_testcase_Module62 is
+    signal clock : std_logic;
+    signal reset : std_logic;
+begin
+    clock <= '1';
+    reset <= '1';
+end architecture rtl;
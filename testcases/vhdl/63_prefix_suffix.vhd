@@ -0,0 +1,54 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module63 is
+    port (
+        i_clk : in std_logic;
+        i_rst_n : in std_logic;
+        i_data_a : in std_logic
+    );
+end entity veryl_testcase_Module63;
+
+architecture rtl of veryl_testcase_Module63 is
+    signal a : std_logic;
+    signal \_b\ : std_logic;
+begin
+
+    u: entity work.veryl_testcase_Module63A
+        port map (
+            i_clk => i_clk,
+            i_rst_n => i_rst_n,
+            i_data_a => i_data_a
+        );
+
+    process (i_clk, i_rst_n)
+    begin
+        if i_rst_n = '0' then
+            a <= '0';
+        elsif rising_edge(i_clk) then
+            a <= '1';
+        end if;
+    end process;
+    \_b\ <= i_rst_n;
+end architecture rtl;
+
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module63A is
+    port (
+        i_clk : in std_logic;
+        i_rst_n : in std_logic;
+        i_data_a : in std_logic
+    );
+end entity veryl_testcase_Module63A;
+
+architecture rtl of veryl_testcase_Module63A is
+begin
+end architecture rtl;
@@ -0,0 +1,26 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module64 is
+end entity veryl_testcase_Module64;
+
+architecture rtl of veryl_testcase_Module64 is
+    signal a : natural;
+    signal \_x0\ : natural;
+    signal \_x1\ : natural;
+    signal \_x2\ : integer;
+    signal \_x3\ : integer;
+    signal \_x4\ : real;
+    signal \_x5\ : real;
+begin
+    a <= 1;
+    \_x0\ <= to_integer(a);
+    \_x1\ <= to_integer(a);
+    \_x2\ <= to_integer(a);
+    \_x3\ <= to_integer(a);
+    \_x4\ <= a;
+    \_x5\ <= a;
+end architecture rtl;
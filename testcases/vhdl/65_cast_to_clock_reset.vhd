@@ -0,0 +1,44 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module65 is
+end entity veryl_testcase_Module65;
+
+architecture rtl of veryl_testcase_Module65 is
+    signal a : std_logic;
+    signal b_n : std_logic;
+    signal c : std_logic;
+    signal d : std_logic;
+    signal e : std_logic;
+    signal f : std_logic;
+    signal \_x0\ : std_logic;
+    signal \_x1\ : std_logic;
+    signal \_x2\ : std_logic;
+    signal \_x3\ : std_logic;
+    signal \_x4\ : std_logic;
+    signal \_x5\ : std_logic;
+    signal \_x6_n\ : std_logic;
+    signal \_x7_n\ : std_logic;
+    signal \_x8_n\ : std_logic;
+    signal \_x9_n\ : std_logic;
+begin
+    a <= '1';
+    b_n <= '1';
+    c <= '1';
+    d <= '1';
+    e <= '1';
+    f <= '1';
+    \_x0\ <= a;
+    \_x1\ <= a;
+    \_x2\ <= b_n;
+    \_x3\ <= b_n;
+    \_x4\ <= b_n;
+    \_x5\ <= b_n;
+    \_x6_n\ <= c;
+    \_x7_n\ <= d;
+    \_x8_n\ <= e;
+    \_x9_n\ <= f;
+end architecture rtl;
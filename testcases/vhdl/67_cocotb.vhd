@@ -0,0 +1,24 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module67 is
+    port (
+        i_clk : in std_logic;
+        i_d : in std_logic;
+        o_d : out std_logic
+    );
+end entity veryl_testcase_Module67;
+
+architecture rtl of veryl_testcase_Module67 is
+begin
+
+    process (i_clk)
+    begin
+        if rising_edge(i_clk) then
+            o_d <= i_d;
+        end if;
+    end process;
+end architecture rtl;
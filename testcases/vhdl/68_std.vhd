@@ -0,0 +1,27 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module68 is
+end entity veryl_testcase_Module68;
+
+architecture rtl of veryl_testcase_Module68 is
+begin
+
+    u: entity work.std_fifo
+        port map (
+            i_clk => open,
+            i_rst_n => open,
+            i_clear => open,
+            o_empty => open,
+            o_almost_full => open,
+            o_full => open,
+            o_word_count => open,
+            i_push => open,
+            i_data => open,
+            i_pop => open,
+            o_data => open
+        );
+end architecture rtl;
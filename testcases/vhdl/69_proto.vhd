@@ -0,0 +1,23 @@
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+use ieee.numeric_std_unsigned.all;
+use ieee.math_real.all;
+
+entity veryl_testcase_Module69A is
+    generic (
+        \A\ : natural := 1;
+        \B\ : natural := 1;
+        \C\ : natural := 1
+    );
+    port (
+        a : in std_logic;
+        b : in std_logic;
+        c : out std_logic
+    );
+end entity veryl_testcase_Module69A;
+
+architecture rtl of veryl_testcase_Module69A is
+begin
+    c <= a;
+end architecture rtl;